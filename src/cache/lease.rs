@@ -0,0 +1,165 @@
+//! Cache-backed leases for background job coordination
+//!
+//! When several replicas share a Redis cache, background work that reaches
+//! upstream (cache warm-up, version checks of subscribed crates) should run
+//! on only one of them at a time. [`LeaseCoordinator`] grants a time-bounded lease per job
+//! through [`Cache::try_acquire_lease`]; the replica holding it runs the job
+//! and the others skip that round.
+//!
+//! With the memory backend every replica has its own cache, so leases only
+//! deduplicate work within a single process.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use crates_docs::cache::lease::LeaseCoordinator;
+//! use crates_docs::cache::memory::MemoryCache;
+//!
+//! # async fn example() {
+//! let coordinator = LeaseCoordinator::new(Arc::new(MemoryCache::new(1000)));
+//! let ran = coordinator
+//!     .run_exclusive("cache_warmup", Duration::from_secs(30), || async {
+//!         // ... background work ...
+//!     })
+//!     .await;
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::Cache;
+
+/// Namespace for lease keys, keeping them apart from documentation entries
+const LEASE_KEY_PREFIX: &str = "lease";
+
+/// Lower bound for the renewal interval of a running job
+///
+/// Guards against a zero-length interval (which `tokio` rejects) when a very
+/// short TTL is configured.
+const MIN_RENEW_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Coordinates background jobs across replicas sharing a cache
+///
+/// Each coordinator has a unique holder ID. Cloning shares the ID, so clones
+/// act as the same replica.
+#[derive(Clone)]
+pub struct LeaseCoordinator {
+    cache: Arc<dyn Cache>,
+    holder_id: String,
+}
+
+impl LeaseCoordinator {
+    /// Create a coordinator with a randomly generated holder ID
+    #[must_use]
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self::with_holder_id(cache, uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Create a coordinator with an explicit holder ID (e.g. pod name)
+    #[must_use]
+    pub fn with_holder_id(cache: Arc<dyn Cache>, holder_id: impl Into<String>) -> Self {
+        Self {
+            cache,
+            holder_id: holder_id.into(),
+        }
+    }
+
+    /// Identifier this replica uses when acquiring leases
+    #[must_use]
+    pub fn holder_id(&self) -> &str {
+        &self.holder_id
+    }
+
+    /// Cache key used for a job's lease
+    #[must_use]
+    pub fn lease_key(job: &str) -> String {
+        format!("{LEASE_KEY_PREFIX}:{job}")
+    }
+
+    /// Try to acquire or renew the lease for `job`
+    ///
+    /// Backend errors are logged and reported as "not acquired": skipping a
+    /// round of background work is safer than running it on every replica.
+    pub async fn try_acquire(&self, job: &str, ttl: Duration) -> bool {
+        match self
+            .cache
+            .try_acquire_lease(&Self::lease_key(job), &self.holder_id, ttl)
+            .await
+        {
+            Ok(acquired) => acquired,
+            Err(e) => {
+                tracing::warn!(job = %job, error = %e, "Failed to acquire job lease; skipping");
+                false
+            }
+        }
+    }
+
+    /// Release the lease for `job` if this replica still holds it
+    ///
+    /// Failures are logged only; the lease expires on its own after its TTL.
+    pub async fn release(&self, job: &str) {
+        if let Err(e) = self
+            .cache
+            .release_lease(&Self::lease_key(job), &self.holder_id)
+            .await
+        {
+            tracing::warn!(job = %job, error = %e, "Failed to release job lease");
+        }
+    }
+
+    /// Run `task` only if this replica obtains the lease for `job`
+    ///
+    /// While the task runs, the lease is renewed every `ttl / 2` so long jobs
+    /// do not lose it midway. The lease is released once the task finishes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(output)` if the task ran, or `None` if another replica
+    /// holds the lease
+    pub async fn run_exclusive<F, Fut, T>(&self, job: &str, ttl: Duration, task: F) -> Option<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        if !self.try_acquire(job, ttl).await {
+            tracing::debug!(job = %job, "Job lease held by another replica; skipping");
+            return None;
+        }
+
+        let work = task();
+        tokio::pin!(work);
+
+        let mut renew = tokio::time::interval((ttl / 2).max(MIN_RENEW_INTERVAL));
+        // The first tick completes immediately; the lease was just acquired.
+        renew.tick().await;
+
+        let output = loop {
+            tokio::select! {
+                output = &mut work => break output,
+                _ = renew.tick() => {
+                    if !self.try_acquire(job, ttl).await {
+                        tracing::warn!(
+                            job = %job,
+                            "Lost job lease while running; another replica may run it concurrently"
+                        );
+                    }
+                }
+            }
+        };
+
+        self.release(job).await;
+        Some(output)
+    }
+}
+
+impl std::fmt::Debug for LeaseCoordinator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeaseCoordinator")
+            .field("holder_id", &self.holder_id)
+            .finish_non_exhaustive()
+    }
+}
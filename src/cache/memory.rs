@@ -3,13 +3,40 @@
 //! Memory cache using `moka::sync::Cache` with `TinyLFU` eviction policy.
 //! This provides better performance and hit rate than simple LRU.
 
+use moka::ops::compute::{CompResult, Op};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Stored value: text from [`Cache::set`](super::Cache::set) or bytes from
+/// [`Cache::set_bytes`](super::Cache::set_bytes)
+#[derive(Clone, Debug)]
+enum CacheValue {
+    Text(Arc<str>),
+    Bytes(Arc<[u8]>),
+}
+
+impl CacheValue {
+    /// The value if it is text
+    fn text(&self) -> Option<&Arc<str>> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Bytes(_) => None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Text(text) => text.len(),
+            Self::Bytes(bytes) => bytes.len(),
+        }
+    }
+}
+
 /// Cache entry with optional TTL
 #[derive(Clone, Debug)]
 struct CacheEntry {
-    value: Arc<str>,
+    value: CacheValue,
     ttl: Option<Duration>,
 }
 
@@ -26,6 +53,18 @@ impl moka::Expiry<String, CacheEntry> for CacheExpiry {
     ) -> Option<Duration> {
         value.ttl
     }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        value: &CacheEntry,
+        _updated_at: std::time::Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        // Overwriting an entry (including renewing a lease) restarts its TTL
+        // instead of inheriting the remaining lifetime of the old value.
+        value.ttl
+    }
 }
 
 /// Memory cache implementation using `moka::sync::Cache`
@@ -35,8 +74,13 @@ impl moka::Expiry<String, CacheEntry> for CacheExpiry {
 /// - `TinyLFU` eviction policy (better hit rate than LRU)
 /// - Per-entry TTL support via Expiry trait
 /// - Automatic expiration cleanup
+/// - Capacity in entries ([`MemoryCache::new`]) or bytes ([`MemoryCache::with_max_bytes`])
 pub struct MemoryCache {
     cache: moka::sync::Cache<String, CacheEntry>,
+    /// Entries dropped for capacity or expiry, counted by the eviction listener
+    evictions: Arc<AtomicU64>,
+    /// Whether the capacity is in bytes, so the weighted size is a byte count
+    weighs_bytes: bool,
 }
 
 impl MemoryCache {
@@ -46,11 +90,52 @@ impl MemoryCache {
     /// * `max_size` - Maximum number of cache entries
     #[must_use]
     pub fn new(max_size: usize) -> Self {
+        Self::build(
+            moka::sync::Cache::builder().max_capacity(max_size as u64),
+            false,
+        )
+    }
+
+    /// Create a memory cache bounded by the size of its keys and values
+    ///
+    /// A single rendered crate page can be hundreds of KB, so an entry count
+    /// says little about memory use; this bounds the cache by RAM instead.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - Maximum total bytes of cached keys and values
+    #[must_use]
+    pub fn with_max_bytes(max_bytes: u64) -> Self {
+        Self::build(
+            moka::sync::Cache::builder()
+                .max_capacity(max_bytes)
+                .weigher(|key: &String, entry: &CacheEntry| {
+                    u32::try_from(key.len() + entry.value.len()).unwrap_or(u32::MAX)
+                }),
+            true,
+        )
+    }
+
+    fn build(
+        builder: moka::sync::CacheBuilder<
+            String,
+            CacheEntry,
+            moka::sync::Cache<String, CacheEntry>,
+        >,
+        weighs_bytes: bool,
+    ) -> Self {
+        let evictions = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&evictions);
         Self {
-            cache: moka::sync::Cache::builder()
-                .max_capacity(max_size as u64)
+            cache: builder
                 .expire_after(CacheExpiry)
+                .eviction_listener(move |_key, _value, cause| {
+                    if cause.was_evicted() {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                })
                 .build(),
+            evictions,
+            weighs_bytes,
         }
     }
 
@@ -80,7 +165,10 @@ impl MemoryCache {
 impl super::Cache for MemoryCache {
     #[tracing::instrument(skip(self), level = "trace")]
     async fn get(&self, key: &str) -> Option<Arc<str>> {
-        let result = self.cache.get(key).map(|entry| Arc::clone(&entry.value));
+        let result = self
+            .cache
+            .get(key)
+            .and_then(|entry| entry.value.text().cloned());
         if result.is_some() {
             tracing::trace!(cache_type = "memory", key = %key, "Cache hit");
         } else {
@@ -93,11 +181,11 @@ impl super::Cache for MemoryCache {
     async fn set(
         &self,
         key: String,
-        value: String,
+        value: Arc<str>,
         ttl: Option<Duration>,
     ) -> crate::error::Result<()> {
         let entry = CacheEntry {
-            value: Arc::from(value.into_boxed_str()),
+            value: CacheValue::Text(value),
             ttl,
         };
         tracing::trace!(cache_type = "memory", key = %key, "Setting cache entry");
@@ -126,6 +214,108 @@ impl super::Cache for MemoryCache {
         result
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        let result = self.cache.get(key).map(|entry| match entry.value {
+            CacheValue::Text(text) => Arc::from(text.as_bytes()),
+            CacheValue::Bytes(bytes) => bytes,
+        });
+        tracing::trace!(cache_type = "memory", key = %key, hit = result.is_some(), "Binary cache lookup");
+        result
+    }
+
+    #[tracing::instrument(skip(self, value), level = "trace")]
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        tracing::trace!(cache_type = "memory", key = %key, "Setting binary cache entry");
+        self.cache.insert(
+            key,
+            CacheEntry {
+                value: CacheValue::Bytes(value),
+                ttl,
+            },
+        );
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        // `and_compute_with` serializes concurrent calls on the same key, so
+        // the ownership check and the write happen atomically.
+        let result = self
+            .cache
+            .entry(key.to_string())
+            .and_compute_with(|current| match current {
+                Some(entry) if entry.value().value.text().map(AsRef::as_ref) != Some(holder) => {
+                    Op::Nop
+                }
+                _ => Op::Put(CacheEntry {
+                    value: CacheValue::Text(Arc::from(holder)),
+                    ttl: Some(ttl),
+                }),
+            });
+        let acquired = matches!(
+            result,
+            CompResult::Inserted(_) | CompResult::ReplacedWith(_)
+        );
+        tracing::trace!(cache_type = "memory", key = %key, acquired, "Lease acquisition");
+        Ok(acquired)
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        self.cache
+            .entry(key.to_string())
+            .and_compute_with(|current| match current {
+                Some(entry) if entry.value().value.text().map(AsRef::as_ref) == Some(holder) => {
+                    Op::Remove
+                }
+                _ => Op::Nop,
+            });
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        // Same per-key serialization as leases, so no increment is lost
+        self.cache
+            .entry(key.to_string())
+            .and_compute_with(|current| {
+                let current = current.and_then(|entry| entry.value().value.text().cloned());
+                Op::Put(CacheEntry {
+                    value: CacheValue::Text(
+                        super::increment_counter_entry(current.as_deref(), member).into(),
+                    ),
+                    ttl: None,
+                })
+            });
+        Ok(())
+    }
+
+    async fn flush(&self) -> crate::error::Result<()> {
+        self.cache.run_pending_tasks();
+        Ok(())
+    }
+
+    async fn stats(&self) -> super::CacheStats {
+        super::CacheStats {
+            evictions: Some(self.evictions.load(Ordering::Relaxed)),
+            // Approximate: moka applies pending writes and removals lazily
+            entries: Some(self.cache.entry_count()),
+            bytes: self.weighs_bytes.then(|| self.cache.weighted_size()),
+            ..super::CacheStats::default()
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -152,7 +342,7 @@ mod tests {
 
         // Test set and get
         cache
-            .set("key1".to_string(), "value1".to_string(), None)
+            .set("key1".to_string(), "value1".into(), None)
             .await
             .expect("set should succeed");
         let result = cache.get("key1").await;
@@ -165,7 +355,7 @@ mod tests {
 
         // Test clear
         cache
-            .set("key2".to_string(), "value2".to_string(), None)
+            .set("key2".to_string(), "value2".into(), None)
             .await
             .expect("set should succeed");
         cache.clear().await.expect("clear should succeed");
@@ -174,6 +364,51 @@ mod tests {
         assert_eq!(cache.get("key2").await, None);
     }
 
+    #[tokio::test]
+    async fn test_memory_cache_shares_values() {
+        let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
+        let value: Arc<str> = "x".repeat(1 << 20).into();
+
+        cache
+            .set("large".to_string(), Arc::clone(&value), None)
+            .await
+            .expect("set should succeed");
+        let cached = cache.get("large").await.expect("value should be cached");
+        // The stored value is the caller's allocation, not a copy
+        assert!(Arc::ptr_eq(&cached, &value));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_bytes() {
+        let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
+        let value: Arc<[u8]> = Arc::from(&[0x1f, 0x8b, 0xff, 0x00][..]);
+
+        cache
+            .set_bytes("gzip".to_string(), Arc::clone(&value), None)
+            .await
+            .expect("set_bytes should succeed");
+        let cached = cache
+            .get_bytes("gzip")
+            .await
+            .expect("value should be cached");
+        assert!(Arc::ptr_eq(&cached, &value));
+        assert!(cache.exists("gzip").await);
+        // Binary entries are not text, text entries read as their bytes
+        assert_eq!(cache.get("gzip").await, None);
+        cache
+            .set("text".to_string(), "value".into(), None)
+            .await
+            .expect("set should succeed");
+        assert_eq!(
+            cache.get_bytes("text").await.as_deref(),
+            Some(&b"value"[..])
+        );
+        assert!(!cache
+            .try_acquire_lease("gzip", "a", Duration::from_secs(5))
+            .await
+            .unwrap());
+    }
+
     #[tokio::test]
     async fn test_memory_cache_ttl() {
         let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
@@ -182,7 +417,7 @@ mod tests {
         cache
             .set(
                 "key1".to_string(),
-                "value1".to_string(),
+                "value1".into(),
                 Some(Duration::from_millis(TEST_TTL_MS)),
             )
             .await
@@ -208,7 +443,7 @@ mod tests {
         // Fill cache with more entries than capacity
         for i in 0..5 {
             cache
-                .set(format!("key{i}"), format!("value{i}"), None)
+                .set(format!("key{i}"), format!("value{i}").into(), None)
                 .await
                 .expect("set should succeed");
         }
@@ -229,10 +464,124 @@ mod tests {
         let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
 
         cache
-            .set("key1".to_string(), "value1".to_string(), None)
+            .set("key1".to_string(), "value1".into(), None)
             .await
             .expect("set should succeed");
         assert!(cache.exists("key1").await);
         assert!(!cache.exists("key2").await);
     }
+
+    #[tokio::test]
+    async fn test_memory_cache_lease_exclusive() {
+        let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
+        let ttl = Duration::from_secs(30);
+
+        assert!(cache.try_acquire_lease("job", "a", ttl).await.unwrap());
+        // The owner can renew, another holder cannot take over.
+        assert!(cache.try_acquire_lease("job", "a", ttl).await.unwrap());
+        assert!(!cache.try_acquire_lease("job", "b", ttl).await.unwrap());
+
+        // Releasing as a non-owner is a no-op.
+        cache.release_lease("job", "b").await.unwrap();
+        assert!(!cache.try_acquire_lease("job", "b", ttl).await.unwrap());
+
+        cache.release_lease("job", "a").await.unwrap();
+        assert!(cache.try_acquire_lease("job", "b", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_lease_expires() {
+        let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
+
+        assert!(cache
+            .try_acquire_lease("job", "a", Duration::from_millis(TEST_TTL_MS))
+            .await
+            .unwrap());
+        sleep(Duration::from_millis(TEST_TTL_WAIT_MS)).await;
+        cache.run_pending_tasks();
+        assert!(cache
+            .try_acquire_lease("job", "b", Duration::from_secs(30))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_counters() {
+        let cache = Arc::new(MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..20 {
+            let cache = Arc::clone(&cache);
+            tasks.spawn(async move { cache.increment_counter("hits", "serde").await });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+        cache.increment_counter("hits", "tokio").await.unwrap();
+        cache.increment_counter("hits", "anyhow").await.unwrap();
+
+        assert_eq!(
+            cache.top_counters("hits", 2).await.unwrap(),
+            [("serde".to_string(), 20), ("anyhow".to_string(), 1)]
+        );
+        assert!(cache.top_counters("other", 5).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_flush_and_integrity() {
+        let cache = MemoryCache::new(DEFAULT_TEST_CACHE_CAPACITY);
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+
+        cache.flush().await.unwrap();
+        assert_eq!(cache.entry_count(), 1);
+        assert_eq!(
+            cache.check_integrity().await.unwrap(),
+            crate::cache::CacheIntegrity::Volatile
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_stats_count_evictions() {
+        let cache = MemoryCache::new(2);
+        for key in ["a", "b", "c"] {
+            cache
+                .set(key.to_string(), "value".into(), None)
+                .await
+                .unwrap();
+        }
+
+        cache.run_pending_tasks();
+        let stats = cache.stats().await;
+        assert_eq!(stats.evictions, Some(1));
+        assert_eq!(stats.entries, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_bounded_by_bytes() {
+        // Room for two 10-byte entries ("kN" plus an 8-byte value), not three
+        let cache = MemoryCache::with_max_bytes(25);
+        for key in ["k1", "k2", "k3"] {
+            cache
+                .set(key.to_string(), "01234567".into(), None)
+                .await
+                .unwrap();
+            cache.run_pending_tasks();
+        }
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, Some(2));
+        assert_eq!(stats.bytes, Some(20));
+        assert_eq!(stats.evictions, Some(1));
+
+        // An entry larger than the whole cache is never kept
+        cache
+            .set("big".to_string(), "x".repeat(100).into(), None)
+            .await
+            .unwrap();
+        cache.run_pending_tasks();
+        assert!(cache.get("big").await.is_none());
+    }
 }
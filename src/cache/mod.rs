@@ -1,11 +1,16 @@
 //! Cache module
 //!
-//! Provides memory cache and Redis cache support.
+//! Provides memory, `SQLite` and Redis cache support, and a memory tier in front of Redis.
 //!
 //! # Features
 //!
 //! - **Memory cache**: High-performance memory cache based on `moka`, supporting `TinyLFU` eviction strategy
+//! - **`SQLite` cache**: Persistent single-file cache (requires `cache-sqlite` feature)
 //! - **Redis cache**: Supports distributed deployment (requires `cache-redis` feature)
+//! - **Tiered cache**: Memory L1 in front of Redis, saving a round-trip per hot lookup
+//! - **Statistics**: Hit, miss, eviction and entry counts for monitoring
+//! - **Leases**: Cache-backed leases so only one replica runs each background job
+//! - **Fetch coalescing**: Only one session or replica fetches an uncached page at a time
 //!
 //! # Examples
 //!
@@ -22,6 +27,16 @@ pub mod memory;
 #[cfg(feature = "cache-redis")]
 pub mod redis;
 
+#[cfg(feature = "cache-sqlite")]
+pub mod sqlite;
+
+#[cfg(feature = "cache-memory")]
+pub mod tiered;
+
+pub mod lease;
+pub mod singleflight;
+pub mod stats;
+
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -73,6 +88,48 @@ const DEFAULT_ITEM_DOCS_TTL_SECS: u64 = 1800;
 /// Configurable via `CacheConfig::search_results_ttl_secs`.
 const DEFAULT_SEARCH_RESULTS_TTL_SECS: u64 = 300;
 
+/// Default TTL in seconds of a "crate not found" result
+///
+/// # Value
+///
+/// 60 seconds (1 minute)
+///
+/// # Rationale
+///
+/// Reused from ttl.rs for consistency. Long enough to absorb agent retries of
+/// a misspelled name, short enough that a newly published crate shows up soon.
+/// Configurable via `CacheConfig::missing_crate_ttl_secs`.
+const DEFAULT_MISSING_CRATE_TTL_SECS: u64 = 60;
+
+/// Default `SQLite` cache size limit in megabytes
+///
+/// # Value
+///
+/// 256 MB
+///
+/// # Rationale
+///
+/// Holds tens of thousands of rendered pages while keeping the database file
+/// small enough for a container volume.
+/// Configurable via `CacheConfig::sqlite_max_size_mb`.
+const DEFAULT_SQLITE_MAX_SIZE_MB: u64 = 256;
+
+/// Default prefix of Redis keys
+///
+/// # Rationale
+///
+/// Keeps the keys of this server apart from other applications sharing the
+/// database and lets `clear()` delete only them; an empty prefix would make
+/// clearing match every key.
+/// Configurable via `CacheConfig::redis_key_prefix`.
+pub const DEFAULT_REDIS_KEY_PREFIX: &str = "crates-docs";
+
+/// Maximum number of members kept by a counter stored as a single entry
+///
+/// Bounds the entry size for backends without native counters; the members
+/// with the lowest counts are dropped first.
+const MAX_COUNTER_MEMBERS: usize = 1000;
+
 /// Cache trait
 ///
 /// Defines basic cache operation interface, supporting async read/write, TTL expiration, and bulk cleanup.
@@ -80,7 +137,9 @@ const DEFAULT_SEARCH_RESULTS_TTL_SECS: u64 = 300;
 /// # Implementations
 ///
 /// - `memory::MemoryCache`: Memory cache implementation
+/// - `sqlite::SqliteCache`: `SQLite` cache implementation (requires `cache-sqlite` feature)
 /// - `redis::RedisCache`: Redis cache implementation (requires `cache-redis` feature)
+/// - `tiered::TieredCache`: Memory cache in front of another backend
 #[async_trait::async_trait]
 pub trait Cache: Send + Sync {
     /// Get cache value
@@ -99,7 +158,8 @@ pub trait Cache: Send + Sync {
     /// # Arguments
     ///
     /// * `key` - Cache key
-    /// * `value` - Cache value
+    /// * `value` - Cache value; shared rather than copied, so callers that
+    ///   also return the value to a client can keep a cheap clone
     /// * `ttl` - Optional expiration time
     ///
     /// # Errors
@@ -108,7 +168,7 @@ pub trait Cache: Send + Sync {
     async fn set(
         &self,
         key: String,
-        value: String,
+        value: Arc<str>,
         ttl: Option<Duration>,
     ) -> crate::error::Result<()>;
 
@@ -143,6 +203,212 @@ pub trait Cache: Send + Sync {
     /// Returns `true` if key exists, otherwise `false`
     async fn exists(&self, key: &str) -> bool;
 
+    /// Get several cache values at once
+    ///
+    /// Returns one result per key, in the order of `keys`. The default
+    /// implementation calls [`Cache::get`] per key; backends paying a round
+    /// trip per operation fetch all keys in one.
+    async fn get_many(&self, keys: &[String]) -> Vec<Option<Arc<str>>> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.get(key).await);
+        }
+        values
+    }
+
+    /// Set several cache values sharing one TTL at once
+    ///
+    /// The default implementation calls [`Cache::set`] per entry and stops at
+    /// the first error; backends paying a round trip per operation write all
+    /// entries in one.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    async fn set_many(
+        &self,
+        entries: Vec<(String, Arc<str>)>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        for (key, value) in entries {
+            self.set(key, value, ttl).await?;
+        }
+        Ok(())
+    }
+
+    /// Get a binary cache value
+    ///
+    /// Binary and text values share one key space: an entry written with
+    /// [`Cache::set`] reads as its UTF-8 bytes, but an entry written with
+    /// [`Cache::set_bytes`] should only be read with this method. The default
+    /// implementation only sees text entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        let value = self.get(key).await?;
+        Some(Arc::from(value.as_bytes()))
+    }
+
+    /// Set a binary cache value
+    ///
+    /// Stores payloads that are not UTF-8 text, such as compressed pages or
+    /// serialized structs, without an encoding round trip. The default
+    /// implementation rejects them; every built-in backend stores them
+    /// natively.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Cache key
+    /// * `value` - Cache value
+    /// * `ttl` - Optional expiration time
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails or the backend has no binary
+    /// storage
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        let _ = (value, ttl);
+        Err(crate::error::Error::cache(
+            "set_bytes",
+            Some(key),
+            "binary values are not supported by this cache backend",
+        ))
+    }
+
+    /// Try to acquire (or renew) a named lease
+    ///
+    /// A lease is a cache entry holding the identifier of its current owner.
+    /// It is granted when the key is free or already held by `holder`, in
+    /// which case its expiry is pushed out to `ttl`. Backends shared by
+    /// several replicas (Redis) make this a cross-process mutual exclusion
+    /// primitive; see [`lease::LeaseCoordinator`].
+    ///
+    /// The default implementation is a non-atomic get-then-set and is only
+    /// suitable for single-process backends. Implementations should override
+    /// it with an atomic compare-and-set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Lease key
+    /// * `holder` - Identifier of the replica requesting the lease
+    /// * `ttl` - How long the lease stays valid without renewal
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if `holder` now owns the lease, `false` if another holder does
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        if let Some(current) = self.get(key).await {
+            if current.as_ref() != holder {
+                return Ok(false);
+            }
+        }
+        self.set(key.to_string(), holder.into(), Some(ttl)).await?;
+        Ok(true)
+    }
+
+    /// Release a lease previously acquired with [`Cache::try_acquire_lease`]
+    ///
+    /// Only removes the lease if it is still owned by `holder`, so a replica
+    /// whose lease already expired cannot release a lease another replica
+    /// has since taken over.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        if let Some(current) = self.get(key).await {
+            if current.as_ref() == holder {
+                self.delete(key).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add one to the count of `member` in the counter `key`
+    ///
+    /// Counters never expire. Backends shared by several replicas (Redis)
+    /// aggregate the counts of all of them.
+    ///
+    /// The default implementation keeps the counter as a JSON object under
+    /// `key` with a non-atomic get-then-set, so concurrent increments may be
+    /// lost. Implementations should override it with an atomic increment.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        let current = self.get(key).await;
+        let updated = increment_counter_entry(current.as_deref(), member);
+        self.set(key.to_string(), updated.into(), None).await
+    }
+
+    /// The `limit` members of the counter `key` with the highest counts,
+    /// highest first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    async fn top_counters(
+        &self,
+        key: &str,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, u64)>> {
+        Ok(top_counter_entries(self.get(key).await.as_deref(), limit))
+    }
+
+    /// Flush pending writes
+    ///
+    /// Called once during graceful shutdown so writes still queued inside the
+    /// backend reach the store before the process exits. The default is a
+    /// no-op for backends that write synchronously.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if pending writes could not be persisted
+    async fn flush(&self) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    /// Verify the backing store
+    ///
+    /// Persistent backends detect an unclean previous shutdown here and
+    /// discard entries that cannot be trusted, so a crash never leads to
+    /// corrupted documentation being served. The server runs it at startup
+    /// and the internal health check reports its result, so implementations
+    /// should make repeated calls cheap.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the store could not be inspected
+    async fn check_integrity(&self) -> crate::error::Result<CacheIntegrity> {
+        Ok(CacheIntegrity::Volatile)
+    }
+
+    /// Usage statistics
+    ///
+    /// Backends report the evictions and entry count they track; hits and
+    /// misses are counted by [`stats::StatsCache`], which the server wraps
+    /// around its cache. The default reports nothing.
+    async fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+
     /// Convert to Any for downcasting (used in tests)
     ///
     /// This method allows downcasting the cache to its concrete type
@@ -150,20 +416,145 @@ pub trait Cache: Send + Sync {
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
+/// Counter stored as a single entry: a JSON object of member counts
+type CounterEntry = std::collections::HashMap<String, u64>;
+
+/// Increment `member` in a counter entry, returning the updated entry
+///
+/// An entry that does not parse is treated as empty.
+pub(crate) fn increment_counter_entry(current: Option<&str>, member: &str) -> String {
+    let mut counts: CounterEntry = current
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    *counts.entry(member.to_string()).or_insert(0) += 1;
+    if counts.len() > MAX_COUNTER_MEMBERS {
+        let mut ranked = sorted_counts(counts);
+        ranked.truncate(MAX_COUNTER_MEMBERS);
+        counts = ranked.into_iter().collect();
+    }
+    // SAFETY: a map of strings to integers always serializes
+    serde_json::to_string(&counts).expect("counter entry serializes")
+}
+
+/// Highest `limit` counts of a counter entry
+pub(crate) fn top_counter_entries(current: Option<&str>, limit: usize) -> Vec<(String, u64)> {
+    let counts: CounterEntry = current
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    let mut ranked = sorted_counts(counts);
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Counts ordered highest first, ties by member name
+fn sorted_counts(counts: CounterEntry) -> Vec<(String, u64)> {
+    let mut ranked: Vec<(String, u64)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Result of [`Cache::check_integrity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheIntegrity {
+    /// Nothing survives a restart, so there is nothing to verify
+    Volatile,
+    /// The previous shutdown was clean and the store is consistent
+    Clean,
+    /// An unclean shutdown was detected and the store was repaired
+    Repaired {
+        /// Number of entries discarded because they could not be trusted
+        discarded: usize,
+    },
+}
+
+impl std::fmt::Display for CacheIntegrity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Volatile => f.write_str("volatile store, no integrity check needed"),
+            Self::Clean => f.write_str("clean shutdown, store consistent"),
+            Self::Repaired { discarded } => write!(
+                f,
+                "recovered from unclean shutdown, {discarded} entries discarded"
+            ),
+        }
+    }
+}
+
+/// Result of [`Cache::stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct CacheStats {
+    /// Lookups that found a live entry
+    pub hits: u64,
+    /// Lookups that found nothing
+    pub misses: u64,
+    /// Entries removed by the backend because of its size limit or their TTL,
+    /// if the backend tracks them
+    pub evictions: Option<u64>,
+    /// Entries currently stored, if the backend can count them
+    pub entries: Option<u64>,
+    /// Bytes of stored keys and values, if the backend tracks them
+    pub bytes: Option<u64>,
+    /// Operations that failed because the backend was unreachable, if the
+    /// backend tracks them
+    pub errors: Option<u64>,
+}
+
+impl CacheStats {
+    /// Share of lookups that were hits, `None` before the first lookup
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits.saturating_add(self.misses);
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+impl std::fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)?;
+        if let Some(rate) = self.hit_rate() {
+            write!(f, " ({:.1}% hit rate)", rate * 100.0)?;
+        }
+        if let Some(evictions) = self.evictions {
+            write!(f, ", {evictions} evictions")?;
+        }
+        if let Some(entries) = self.entries {
+            write!(f, ", {entries} entries")?;
+        }
+        if let Some(bytes) = self.bytes {
+            write!(f, ", {bytes} bytes")?;
+        }
+        if let Some(errors) = self.errors {
+            write!(f, ", {errors} backend errors")?;
+        }
+        Ok(())
+    }
+}
+
 /// Cache configuration
 ///
 /// Configure cache type, size, TTL, and other parameters.
 ///
 /// # Fields
 ///
-/// - `cache_type`: Cache type, `"memory"` or `"redis"`
+/// - `cache_type`: Cache type, `"memory"`, `"sqlite"` or `"redis"`
 /// - `memory_size`: Memory cache size(number of entries)
+/// - `memory_max_bytes`: Memory cache size(bytes of keys and values)
 /// - `redis_url`: Redis connection URL
+/// - `redis_l1_ttl_secs`: Lifetime of the memory tier in front of Redis (seconds)
+/// - `redis_key_prefix`: Prefix of every Redis key
+/// - `sqlite_path`: `SQLite` database file
+/// - `sqlite_max_size_mb`: `SQLite` cache size limit (megabytes)
 /// - `key_prefix`: Key prefix (used to isolate caches of different services)
 /// - `default_ttl`: Default TTL (seconds)
 /// - `crate_docs_ttl_secs`: Crate document cache TTL (seconds)
 /// - `item_docs_ttl_secs`: Item document cache TTL (seconds)
 /// - `search_results_ttl_secs`: Search result cache TTL (seconds)
+/// - `versioned_docs_ttl_secs`: Cache TTL of docs for an exact version (seconds)
+/// - `missing_crate_ttl_secs`: Cache TTL of "crate not found" results (seconds)
+/// - `stale_while_revalidate_secs`: How long expired crate pages are served while refreshed (seconds)
+/// - `ttl_jitter_ratio`: Random spread of document TTLs (0.0-1.0)
+/// - `warmup`: Crates and items to prefetch on startup
 ///
 /// # Hot reload support
 ///
@@ -174,19 +565,26 @@ pub trait Cache: Send + Sync {
 /// - `crate_docs_ttl_secs`: Crate document cache TTL (seconds)
 /// - `item_docs_ttl_secs`: Item document cache TTL (seconds)
 /// - `search_results_ttl_secs`: Search result cache TTL (seconds)
+/// - `versioned_docs_ttl_secs`: Cache TTL of docs for an exact version (seconds)
+/// - `missing_crate_ttl_secs`: Cache TTL of "crate not found" results (seconds)
+/// - `stale_while_revalidate_secs`: How long expired crate pages are served while refreshed (seconds)
+/// - `ttl_jitter_ratio`: Random spread of document TTLs (0.0-1.0)
 ///
 /// ## Hot reload NOT supported fields ❌
 ///
 /// The following fields require server restart to take effect:
 /// - `cache_type`: Cache type (involves cache instance creation)
-/// - `memory_size`: Memory cache size(initialization parameter)
+/// - `memory_size`, `memory_max_bytes`: Memory cache size(initialization parameters)
 /// - `redis_url`: Redis connection URL(connection pool initialization)
-/// - `key_prefix`: Cache key prefix(initialization parameter)
+/// - `redis_l1_ttl_secs`: Memory tier in front of Redis(initialization parameter)
+/// - `sqlite_path`, `sqlite_max_size_mb`: `SQLite` database file and size limit(initialization parameters)
+/// - `redis_key_prefix`, `key_prefix`: Cache key prefixes(initialization parameters)
+/// - `warmup`: Only read when the server starts
 ///
-/// Reason: These configurations involve initialization of cache backend (memory/Redis) and connection pool creation.
+/// Reason: These configurations involve initialization of cache backend (memory/`SQLite`/Redis) and connection pool creation.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct CacheConfig {
-    /// Cache type: `memory` or `redis`
+    /// Cache type: `memory`, `sqlite` or `redis`
     #[serde(default = "default_cache_cache_type")]
     pub cache_type: String,
 
@@ -194,10 +592,36 @@ pub struct CacheConfig {
     #[serde(default)]
     pub memory_size: Option<usize>,
 
+    /// Memory cache size in bytes of cached keys and values; takes
+    /// precedence over `memory_size` when set
+    #[serde(default)]
+    pub memory_max_bytes: Option<u64>,
+
     /// Redis connection URL
     #[serde(default)]
     pub redis_url: Option<String>,
 
+    /// Put a memory cache of `memory_size` entries in front of Redis, serving
+    /// each entry from memory for at most this many seconds. `None` sends
+    /// every lookup to Redis
+    #[serde(default)]
+    pub redis_l1_ttl_secs: Option<u64>,
+
+    /// Prefix of every Redis key, so several deployments or other
+    /// applications can share one Redis database. `None` uses `key_prefix`,
+    /// or `crates-docs` if that is empty
+    #[serde(default)]
+    pub redis_key_prefix: Option<String>,
+
+    /// `SQLite` database file (required when `cache_type` is `sqlite`)
+    #[serde(default)]
+    pub sqlite_path: Option<String>,
+
+    /// `SQLite` cache size limit in megabytes; the oldest entries are pruned
+    /// above it. `None` leaves the cache unbounded
+    #[serde(default = "default_sqlite_max_size_mb")]
+    pub sqlite_max_size_mb: Option<u64>,
+
     /// Redis cache key prefix (used to isolate caches of different services)
     #[serde(default = "default_key_prefix")]
     pub key_prefix: String,
@@ -217,6 +641,127 @@ pub struct CacheConfig {
     /// Search result cache TTL (seconds)
     #[serde(default = "default_search_results_ttl")]
     pub search_results_ttl_secs: Option<u64>,
+
+    /// TTL of crate and item docs for an exact version such as `1.0.0`
+    /// (seconds); `None` uses the crate/item document TTLs. Published
+    /// versions never change, so this can be very long
+    #[serde(default)]
+    pub versioned_docs_ttl_secs: Option<u64>,
+
+    /// TTL of "crate not found" results (seconds); `0` disables caching
+    /// them. Repeated lookups of a missing crate, a common retry pattern,
+    /// are then answered without asking docs.rs again
+    #[serde(default = "default_missing_crate_ttl")]
+    pub missing_crate_ttl_secs: Option<u64>,
+
+    /// How long an expired crate page is still served (seconds) while a
+    /// background task refreshes it; `None` or `0` disables
+    /// stale-while-revalidate. Keeps tail latency low for popular crates
+    #[serde(default)]
+    pub stale_while_revalidate_secs: Option<u64>,
+
+    /// Random spread of document TTLs as a fraction of the TTL (0.0-1.0);
+    /// `None` uses 0.1, `0` disables it. With `0.1` an entry with a one hour
+    /// TTL expires after 54 to 66 minutes, so entries written together, such
+    /// as by the warm-up, are not all refreshed at once
+    #[serde(default)]
+    pub ttl_jitter_ratio: Option<f64>,
+
+    /// Crates and items to prefetch when the server starts (`[cache.warmup]`)
+    #[serde(default)]
+    pub warmup: CacheWarmupConfig,
+}
+
+/// Crates and items to prefetch when the server starts
+///
+/// Each entry is looked up the way `lookup_crate` / `lookup_item` would look
+/// it up, so a fresh deployment answers them from the cache right away.
+///
+/// ```toml
+/// [cache.warmup]
+/// crates = ["serde", "tokio@1.40.0"]
+/// items = ["serde::Serialize", "tokio::sync::Mutex"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct CacheWarmupConfig {
+    /// Crates, as `name` or `name@version`
+    #[serde(default)]
+    pub crates: Vec<String>,
+
+    /// Items, as `crate::path` or `crate@version::path`
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+
+/// A crate or item listed in [`CacheWarmupConfig`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmupTarget {
+    /// Crate name
+    pub crate_name: String,
+    /// Version, latest if `None`
+    pub version: Option<String>,
+    /// Full item path including the crate, for items
+    pub item_path: Option<String>,
+}
+
+impl CacheWarmupConfig {
+    /// Whether nothing is to be prefetched
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty() && self.items.is_empty()
+    }
+
+    /// The configured crates, then the configured items
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error naming the first malformed entry
+    pub fn targets(&self) -> crate::error::Result<Vec<WarmupTarget>> {
+        let crates = self.crates.iter().map(|spec| {
+            let (crate_name, version) = split_version(spec.trim())
+                .ok_or_else(|| warmup_error("cache.warmup.crates", spec, "name@version"))?;
+            Ok(WarmupTarget {
+                crate_name,
+                version,
+                item_path: None,
+            })
+        });
+        let items = self.items.iter().map(|spec| {
+            let (head, path) = spec
+                .trim()
+                .split_once("::")
+                .filter(|(_, path)| !path.trim().is_empty())
+                .ok_or_else(|| warmup_error("cache.warmup.items", spec, "crate@version::path"))?;
+            let (crate_name, version) = split_version(head)
+                .ok_or_else(|| warmup_error("cache.warmup.items", spec, "crate@version::path"))?;
+            let item_path = format!("{crate_name}::{}", path.trim());
+            Ok(WarmupTarget {
+                crate_name,
+                version,
+                item_path: Some(item_path),
+            })
+        });
+        crates.chain(items).collect()
+    }
+}
+
+/// Split `name` or `name@version`, `None` if either part is empty
+fn split_version(spec: &str) -> Option<(String, Option<String>)> {
+    let (name, version) = match spec.split_once('@') {
+        Some((name, version)) => (name.trim(), Some(version.trim())),
+        None => (spec, None),
+    };
+    if name.is_empty() || name.contains(char::is_whitespace) || version == Some("") {
+        return None;
+    }
+    Some((name.to_string(), version.map(str::to_string)))
+}
+
+fn warmup_error(field: &str, spec: &str, expected: &str) -> crate::error::Error {
+    crate::error::Error::config(
+        field,
+        format!("invalid entry '{spec}', expected {expected} (the version is optional)"),
+    )
 }
 
 /// Default crate document TTL (1 hour)
@@ -237,6 +782,18 @@ pub fn default_search_results_ttl() -> Option<u64> {
     Some(DEFAULT_SEARCH_RESULTS_TTL_SECS)
 }
 
+/// Default "crate not found" TTL (1 minute)
+#[must_use]
+pub fn default_missing_crate_ttl() -> Option<u64> {
+    Some(DEFAULT_MISSING_CRATE_TTL_SECS)
+}
+
+/// Default `SQLite` cache size limit (256 MB)
+#[must_use]
+pub fn default_sqlite_max_size_mb() -> Option<u64> {
+    Some(DEFAULT_SQLITE_MAX_SIZE_MB)
+}
+
 /// Default key prefix
 #[must_use]
 pub fn default_key_prefix() -> String {
@@ -248,12 +805,22 @@ impl Default for CacheConfig {
         Self {
             cache_type: "memory".to_string(),
             memory_size: Some(DEFAULT_MEMORY_CACHE_SIZE),
+            memory_max_bytes: None,
             redis_url: None,
+            redis_l1_ttl_secs: None,
+            redis_key_prefix: None,
+            sqlite_path: None,
+            sqlite_max_size_mb: default_sqlite_max_size_mb(),
             key_prefix: String::new(),
             default_ttl: Some(DEFAULT_CRATE_DOCS_TTL_SECS),
             crate_docs_ttl_secs: default_crate_docs_ttl(),
             item_docs_ttl_secs: default_item_docs_ttl(),
             search_results_ttl_secs: default_search_results_ttl(),
+            versioned_docs_ttl_secs: None,
+            missing_crate_ttl_secs: default_missing_crate_ttl(),
+            stale_while_revalidate_secs: None,
+            ttl_jitter_ratio: None,
+            warmup: CacheWarmupConfig::default(),
         }
     }
 }
@@ -262,6 +829,91 @@ fn default_cache_cache_type() -> String {
     CacheConfig::default().cache_type
 }
 
+impl CacheConfig {
+    /// Validate the cache configuration
+    ///
+    /// The live in-memory cache is sized from `memory_size` (see
+    /// [`create_cache`]), NOT `performance.cache_max_size`. A `memory_size`
+    /// of 0 builds a zero-capacity cache that evicts every entry immediately,
+    /// silently disabling caching, so it is rejected wherever a memory cache
+    /// is built.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error for an unknown `cache_type` or a zero
+    /// size or TTL that would disable the cache
+    pub fn validate(&self) -> crate::error::Result<()> {
+        let valid_cache_types = ["memory", "sqlite", "redis"];
+        if !valid_cache_types.contains(&self.cache_type.as_str()) {
+            return Err(crate::error::Error::config(
+                "cache.cache_type",
+                format!(
+                    "Invalid cache type: {}, valid values: {:?}",
+                    self.cache_type, valid_cache_types
+                ),
+            ));
+        }
+        let tiered = self.cache_type == "redis" && self.redis_l1_ttl_secs.is_some();
+        if self.cache_type == "memory" || tiered {
+            if self.memory_size == Some(0) {
+                return Err(crate::error::Error::config(
+                    "cache.memory_size",
+                    "cannot be 0 (this would disable the cache); omit it to use the default",
+                ));
+            }
+            if self.memory_max_bytes == Some(0) {
+                return Err(crate::error::Error::config(
+                    "cache.memory_max_bytes",
+                    "cannot be 0 (this would disable the cache); omit it to bound by entries",
+                ));
+            }
+        }
+        if self.cache_type == "redis" && self.redis_l1_ttl_secs == Some(0) {
+            return Err(crate::error::Error::config(
+                "cache.redis_l1_ttl_secs",
+                "cannot be 0; omit it to disable the memory tier",
+            ));
+        }
+        if self.redis_key_prefix.as_deref() == Some("") {
+            return Err(crate::error::Error::config(
+                "cache.redis_key_prefix",
+                format!(
+                    "cannot be empty (clearing the cache would have to match every key); \
+                     omit it to use key_prefix or \"{DEFAULT_REDIS_KEY_PREFIX}\""
+                ),
+            ));
+        }
+        if self.cache_type == "sqlite" && self.sqlite_max_size_mb == Some(0) {
+            return Err(crate::error::Error::config(
+                "cache.sqlite_max_size_mb",
+                "cannot be 0 (every entry would be pruned); omit it to use the default",
+            ));
+        }
+        if self
+            .ttl_jitter_ratio
+            .is_some_and(|ratio| !(0.0..=1.0).contains(&ratio))
+        {
+            return Err(crate::error::Error::config(
+                "cache.ttl_jitter_ratio",
+                "must be between 0.0 and 1.0",
+            ));
+        }
+        self.warmup.targets()?;
+        Ok(())
+    }
+
+    /// Prefix of Redis keys: `redis_key_prefix`, else a non-empty
+    /// `key_prefix`, else [`DEFAULT_REDIS_KEY_PREFIX`]
+    #[must_use]
+    pub fn resolved_redis_key_prefix(&self) -> &str {
+        match &self.redis_key_prefix {
+            Some(prefix) => prefix,
+            None if !self.key_prefix.is_empty() => &self.key_prefix,
+            None => DEFAULT_REDIS_KEY_PREFIX,
+        }
+    }
+}
+
 /// Create cache instance
 ///
 /// # Arguments
@@ -285,8 +937,7 @@ pub fn create_cache(config: &CacheConfig) -> Result<Box<dyn Cache>, crate::error
         "memory" => {
             #[cfg(feature = "cache-memory")]
             {
-                let size = config.memory_size.unwrap_or(DEFAULT_MEMORY_CACHE_SIZE);
-                Ok(Box::new(memory::MemoryCache::new(size)))
+                Ok(Box::new(create_memory_cache(config)))
             }
             #[cfg(not(feature = "cache-memory"))]
             {
@@ -296,6 +947,7 @@ pub fn create_cache(config: &CacheConfig) -> Result<Box<dyn Cache>, crate::error
                 ))
             }
         }
+        "sqlite" => create_sqlite_cache(config),
         "redis" => {
             #[cfg(feature = "cache-redis")]
             {
@@ -350,22 +1002,66 @@ pub async fn create_cache_async(
     config: &CacheConfig,
 ) -> Result<Box<dyn Cache>, crate::error::Error> {
     match config.cache_type.as_str() {
-        "memory" => {
-            let size = config.memory_size.unwrap_or(DEFAULT_MEMORY_CACHE_SIZE);
-            Ok(Box::new(memory::MemoryCache::new(size)))
-        }
+        "memory" => Ok(Box::new(create_memory_cache(config))),
         "redis" => {
             let url = config
                 .redis_url
                 .as_ref()
                 .ok_or_else(|| crate::error::Error::config("redis_url", "redis_url is required"))?;
-            Ok(Box::new(
-                redis::RedisCache::new(url, config.key_prefix.clone()).await?,
-            ))
+            // Entries are served from memory while Redis is down
+            let redis = redis::RedisCache::new(url, config.resolved_redis_key_prefix().to_string())
+                .await?
+                .with_fallback(Box::new(create_memory_cache(config)));
+            match config.redis_l1_ttl_secs {
+                Some(l1_ttl) => Ok(Box::new(tiered::TieredCache::new(
+                    create_memory_cache(config),
+                    Box::new(redis),
+                    Duration::from_secs(l1_ttl),
+                ))),
+                None => Ok(Box::new(redis)),
+            }
         }
+        "sqlite" => create_sqlite_cache(config),
         _ => Err(crate::error::Error::config(
             "cache_type",
             format!("unsupported cache type: {}", config.cache_type),
         )),
     }
 }
+
+/// Build the memory cache described by `config`, bounded by bytes when
+/// `memory_max_bytes` is set and by entries otherwise
+#[cfg(feature = "cache-memory")]
+fn create_memory_cache(config: &CacheConfig) -> memory::MemoryCache {
+    match config.memory_max_bytes {
+        Some(max_bytes) => memory::MemoryCache::with_max_bytes(max_bytes),
+        None => memory::MemoryCache::new(config.memory_size.unwrap_or(DEFAULT_MEMORY_CACHE_SIZE)),
+    }
+}
+
+/// Open the `SQLite` cache described by `config`
+fn create_sqlite_cache(config: &CacheConfig) -> Result<Box<dyn Cache>, crate::error::Error> {
+    #[cfg(feature = "cache-sqlite")]
+    {
+        let path = config
+            .sqlite_path
+            .as_ref()
+            .ok_or_else(|| crate::error::Error::config("sqlite_path", "sqlite_path is required"))?;
+        let max_size_bytes = config
+            .sqlite_max_size_mb
+            .map(|mb| mb.saturating_mul(1024 * 1024));
+        Ok(Box::new(sqlite::SqliteCache::new(
+            path,
+            config.key_prefix.clone(),
+            max_size_bytes,
+        )?))
+    }
+    #[cfg(not(feature = "cache-sqlite"))]
+    {
+        let _ = config;
+        Err(crate::error::Error::config(
+            "cache_type",
+            "sqlite cache feature is not enabled",
+        ))
+    }
+}
@@ -1,15 +1,62 @@
 //! Redis cache implementation
 //!
 //! Provides Redis backend cache support with safe operations.
+//!
+//! Every command goes through [`RedisCache::query`], which reconnects after a
+//! dropped connection, retries with backoff and tracks whether Redis is
+//! reachable. While it is not, commands fail fast instead of waiting for
+//! connection timeouts, and cache entries are served from the fallback cache.
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use super::{Cache, CacheIntegrity, CacheStats};
 use crate::error::Error;
 
 /// Default scan count for SCAN command when clearing keys
 const DEFAULT_SCAN_COUNT: usize = 100;
 
+/// Attempts of a command before Redis is considered down
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before retrying a command, doubled after every failed attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// How long commands fail fast after Redis went down, doubled while it stays
+/// down
+const MIN_DOWN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the fail-fast period
+const MAX_DOWN_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Acquire or renew a lease: succeeds when the key is free or already owned
+/// by the caller, and (re)sets the expiry in both cases.
+const ACQUIRE_LEASE_SCRIPT: &str = r"
+local current = redis.call('GET', KEYS[1])
+if current == false or current == ARGV[1] then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return 1
+end
+return 0
+";
+
+/// Set every key to its value with the expiry in ARGV[1], in one round trip.
+const SET_MANY_SCRIPT: &str = r"
+for i, key in ipairs(KEYS) do
+    redis.call('SET', key, ARGV[i + 1], 'PX', ARGV[1])
+end
+return 0
+";
+
+/// Delete a lease only if it is still owned by the caller.
+const RELEASE_LEASE_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
 /// Redis cache implementation
 ///
 /// Uses multiplexed connection (`MultiplexedConnection`) to avoid creating new connections for each operation.
@@ -20,11 +67,28 @@ const DEFAULT_SCAN_COUNT: usize = 100;
 /// - Uses key prefix to isolate cache entries from different services
 /// - `clear()` only deletes keys with the configured prefix using SCAN (no FLUSHDB)
 /// - All write operations return Result to properly propagate errors
+///
+/// # Resilience
+///
+/// - A dropped connection is re-established on the next command
+/// - Commands failing with a connection error are retried with backoff
+/// - Once retries are exhausted Redis is marked down, and commands fail fast
+///   for an exponentially growing period (1s up to 30s) before Redis is tried
+///   again
+/// - While Redis is down, reads, writes and `exists` use the fallback cache
+///   set with [`RedisCache::with_fallback`]; it is cleared once Redis is back.
+///   Leases and counters coordinate replicas, so they return the error
+///   instead
 pub struct RedisCache {
-    /// Multiplexed connection (cloneable, shared across multiple operations)
-    conn: redis::aio::MultiplexedConnection,
+    client: redis::Client,
+    /// Multiplexed connection (cloneable, shared across multiple operations);
+    /// `None` after a connection error until the next command reconnects
+    conn: tokio::sync::Mutex<Option<redis::aio::MultiplexedConnection>>,
     /// Key prefix for all cache entries
     key_prefix: String,
+    health: Health,
+    /// Cache used while Redis is down
+    fallback: Option<Box<dyn Cache>>,
 }
 
 impl RedisCache {
@@ -59,7 +123,29 @@ impl RedisCache {
             .await
             .map_err(|e| Error::cache("ping", None, format!("failed: {e}")))?;
 
-        Ok(Self { conn, key_prefix })
+        Ok(Self {
+            client,
+            conn: tokio::sync::Mutex::new(Some(conn)),
+            key_prefix,
+            health: Health::default(),
+            fallback: None,
+        })
+    }
+
+    /// Serve cache entries from `fallback` while Redis is down
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Box<dyn Cache>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Whether Redis is considered reachable
+    ///
+    /// `false` from the moment a command exhausted its retries until a
+    /// command succeeds again.
+    #[must_use]
+    pub fn is_available(&self) -> bool {
+        self.health.consecutive_failures.load(Ordering::Relaxed) == 0
     }
 
     /// Build full key with prefix
@@ -70,6 +156,152 @@ impl RedisCache {
             format!("{}:{}", self.key_prefix, key)
         }
     }
+
+    /// Run `cmd`, reconnecting and retrying after connection errors
+    ///
+    /// Fails fast while Redis is marked down. `operation` and `key` describe
+    /// the command in the returned error.
+    async fn query<T: redis::FromRedisValue>(
+        &self,
+        operation: &str,
+        key: Option<&str>,
+        cmd: &redis::Cmd,
+    ) -> crate::error::Result<T> {
+        let error = |message: String| Error::cache(operation, key.map(str::to_string), message);
+        if let Some(remaining) = self.health.backoff_remaining() {
+            return Err(error(format!(
+                "Redis unavailable, retrying in {}ms",
+                remaining.as_millis()
+            )));
+        }
+
+        let mut attempt = 1;
+        let mut delay = RETRY_BASE_DELAY;
+        loop {
+            let result = match self.connection().await {
+                Ok(mut conn) => cmd.query_async(&mut conn).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(value) => {
+                    self.record_success().await;
+                    return Ok(value);
+                }
+                Err(e) if !is_connection_error(&e) => {
+                    tracing::warn!(operation, error = %e, "Redis command failed");
+                    return Err(error(format!("failed: {e}")));
+                }
+                Err(e) => {
+                    // The connection may be broken; the next attempt reconnects
+                    *self.conn.lock().await = None;
+                    if attempt == MAX_ATTEMPTS {
+                        let backoff = self.health.record_failure();
+                        tracing::warn!(
+                            operation,
+                            error = %e,
+                            "Redis unreachable after {MAX_ATTEMPTS} attempts; \
+                             failing fast for {}ms",
+                            backoff.as_millis()
+                        );
+                        return Err(error(format!("failed after {MAX_ATTEMPTS} attempts: {e}")));
+                    }
+                    tracing::debug!(operation, attempt, error = %e, "Retrying Redis command");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    delay *= 2;
+                }
+            }
+        }
+    }
+
+    /// The shared connection, connecting first if there is none
+    async fn connection(&self) -> redis::RedisResult<redis::aio::MultiplexedConnection> {
+        // Held across the connect so concurrent commands share one reconnect
+        let mut conn = self.conn.lock().await;
+        if let Some(conn) = conn.as_ref() {
+            return Ok(conn.clone());
+        }
+        let connected = self.client.get_multiplexed_async_connection().await?;
+        tracing::debug!("Reconnected to Redis");
+        *conn = Some(connected.clone());
+        Ok(connected)
+    }
+
+    /// Mark Redis reachable, dropping entries the fallback collected while
+    /// it was down
+    async fn record_success(&self) {
+        if !self.health.record_success() {
+            return;
+        }
+        tracing::info!("Redis is reachable again");
+        if let Some(fallback) = &self.fallback {
+            if let Err(e) = fallback.clear().await {
+                tracing::warn!("Failed to clear the Redis fallback cache: {e}");
+            }
+        }
+    }
+
+    /// Look `key` up in the fallback cache after Redis failed
+    async fn fallback_get(&self, key: &str) -> Option<Arc<str>> {
+        self.fallback.as_ref()?.get(key).await
+    }
+}
+
+/// Whether `error` means Redis could not be reached, as opposed to a
+/// command Redis rejected
+fn is_connection_error(error: &redis::RedisError) -> bool {
+    error.is_io_error() || error.is_timeout() || error.is_unrecoverable_error()
+}
+
+/// Reachability of Redis, shared by all commands
+#[derive(Default)]
+struct Health {
+    /// Commands that exhausted their retries since the last success
+    consecutive_failures: AtomicU32,
+    /// Commands that exhausted their retries since the cache was created
+    errors: AtomicU64,
+    /// End of the fail-fast period while Redis is down
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl Health {
+    /// Time left in the fail-fast period, `None` outside of it
+    fn backoff_remaining(&self) -> Option<Duration> {
+        let down_until = (*self.lock_down_until())?;
+        down_until
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Record a command that reached Redis, returning whether Redis was down
+    fn record_success(&self) -> bool {
+        self.lock_down_until().take();
+        self.consecutive_failures.swap(0, Ordering::Relaxed) > 0
+    }
+
+    /// Record a command that exhausted its retries, returning the fail-fast
+    /// period it starts
+    fn record_failure(&self) -> Duration {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let backoff = down_backoff(failures);
+        *self.lock_down_until() = Some(Instant::now() + backoff);
+        backoff
+    }
+
+    fn lock_down_until(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.down_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Fail-fast period after `failures` consecutive failed commands
+fn down_backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    MIN_DOWN_BACKOFF
+        .saturating_mul(1 << doublings)
+        .min(MAX_DOWN_BACKOFF)
 }
 
 /// Compute the millisecond expiry for a Redis `PX` argument from a TTL.
@@ -100,26 +332,19 @@ fn scan_pattern_for_prefix(prefix: &str) -> Option<String> {
 }
 
 #[async_trait::async_trait]
-impl super::Cache for RedisCache {
+impl Cache for RedisCache {
     async fn get(&self, key: &str) -> Option<Arc<str>> {
-        let mut conn = self.conn.clone();
         let full_key = self.build_key(key);
-        let result: redis::RedisResult<Option<String>> = redis::cmd("GET")
-            .arg(&full_key)
-            .query_async(&mut conn)
+        let result: crate::error::Result<Option<String>> = self
+            .query("get", Some(key), redis::cmd("GET").arg(&full_key))
             .await;
         match result {
             Ok(value) => value.map(|s| Arc::from(s.into_boxed_str())),
             Err(e) => {
-                // Distinguish a backend failure from a genuine cache miss: a
-                // silent miss would let a Redis outage degrade latency with no
-                // observability. Log and fall back to treating it as a miss.
-                tracing::warn!(
-                    key = %key,
-                    error = %e,
-                    "Redis GET failed; treating as cache miss"
-                );
-                None
+                // Distinguish a backend failure from a genuine cache miss: the
+                // entry may still be in the fallback cache
+                tracing::debug!(key = %key, error = %e, "Redis GET failed; using fallback cache");
+                self.fallback_get(key).await
             }
         }
     }
@@ -127,48 +352,46 @@ impl super::Cache for RedisCache {
     async fn set(
         &self,
         key: String,
-        value: String,
+        value: Arc<str>,
         ttl: Option<Duration>,
     ) -> crate::error::Result<()> {
-        let mut conn = self.conn.clone();
         let full_key = self.build_key(&key);
-
-        let result: redis::RedisResult<()> = if let Some(ttl) = ttl {
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(&full_key).arg(&*value);
+        if let Some(ttl) = ttl {
             // Use PX for millisecond precision instead of SETEX (seconds only).
             // Any positive TTL maps to at least 1ms so a sub-millisecond TTL
             // never collapses to `PX 0` (which would drop the expiry entirely).
-            let ms = px_millis_for_ttl(ttl);
-            redis::cmd("SET")
-                .arg(&full_key)
-                .arg(&value)
-                .arg("PX")
-                .arg(ms)
-                .query_async(&mut conn)
-                .await
-        } else {
-            redis::cmd("SET")
-                .arg(&full_key)
-                .arg(&value)
-                .query_async(&mut conn)
-                .await
-        };
+            cmd.arg("PX").arg(px_millis_for_ttl(ttl));
+        }
 
-        result.map_err(|e| Error::cache("set", Some(key.clone()), format!("failed: {e}")))
+        match (
+            self.query::<()>("set", Some(&key), &cmd).await,
+            &self.fallback,
+        ) {
+            (Ok(()), _) => Ok(()),
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(key = %key, error = %e, "Redis SET failed; using fallback cache");
+                fallback.set(key, value, ttl).await
+            }
+            (Err(e), None) => Err(e),
+        }
     }
 
     async fn delete(&self, key: &str) -> crate::error::Result<()> {
-        let mut conn = self.conn.clone();
+        if let Some(fallback) = &self.fallback {
+            fallback.delete(key).await?;
+        }
         let full_key = self.build_key(key);
-
-        let result: redis::RedisResult<()> = redis::cmd("DEL")
-            .arg(&full_key)
-            .query_async(&mut conn)
-            .await;
-
-        result.map_err(|e| Error::cache("delete", Some(key.to_string()), format!("failed: {e}")))
+        self.query("delete", Some(key), redis::cmd("DEL").arg(&full_key))
+            .await
     }
 
     async fn clear(&self) -> crate::error::Result<()> {
+        if let Some(fallback) = &self.fallback {
+            fallback.clear().await?;
+        }
+
         // Use SCAN to find and delete only keys with our prefix.
         // This is safer than FLUSHDB which would delete ALL keys in the database.
         //
@@ -185,50 +408,36 @@ impl super::Cache for RedisCache {
             ));
         };
 
-        let mut conn = self.conn.clone();
-
         let mut cursor: u64 = 0;
         let mut total_deleted: u64 = 0;
 
         loop {
             // SCAN returns (new_cursor, keys)
-            let scan_result: redis::RedisResult<(u64, Vec<String>)> = redis::cmd("SCAN")
-                .arg(cursor)
-                .arg("MATCH")
-                .arg(&pattern)
-                .arg("COUNT")
-                .arg(DEFAULT_SCAN_COUNT)
-                .query_async(&mut conn)
-                .await;
-
-            match scan_result {
-                Ok((new_cursor, keys)) => {
-                    if !keys.is_empty() {
-                        // Delete the found keys
-                        let del_result: redis::RedisResult<u64> =
-                            redis::cmd("DEL").arg(&keys).query_async(&mut conn).await;
-
-                        match del_result {
-                            Ok(deleted) => total_deleted += deleted,
-                            Err(e) => {
-                                return Err(Error::cache(
-                                    "clear",
-                                    None,
-                                    format!("DEL failed: {e}"),
-                                ));
-                            }
-                        }
-                    }
+            let (new_cursor, keys): (u64, Vec<String>) = self
+                .query(
+                    "clear",
+                    None,
+                    redis::cmd("SCAN")
+                        .arg(cursor)
+                        .arg("MATCH")
+                        .arg(&pattern)
+                        .arg("COUNT")
+                        .arg(DEFAULT_SCAN_COUNT),
+                )
+                .await?;
+
+            if !keys.is_empty() {
+                // Delete the found keys
+                let deleted: u64 = self
+                    .query("clear", None, redis::cmd("DEL").arg(&keys))
+                    .await?;
+                total_deleted += deleted;
+            }
 
-                    cursor = new_cursor;
-                    // SCAN returns 0 when iteration is complete
-                    if cursor == 0 {
-                        break;
-                    }
-                }
-                Err(e) => {
-                    return Err(Error::cache("clear", None, format!("SCAN failed: {e}")));
-                }
+            cursor = new_cursor;
+            // SCAN returns 0 when iteration is complete
+            if cursor == 0 {
+                break;
             }
         }
 
@@ -244,25 +453,232 @@ impl super::Cache for RedisCache {
     }
 
     async fn exists(&self, key: &str) -> bool {
-        let mut conn = self.conn.clone();
         let full_key = self.build_key(key);
-        let result: redis::RedisResult<i64> = redis::cmd("EXISTS")
-            .arg(&full_key)
-            .query_async(&mut conn)
+        let result: crate::error::Result<i64> = self
+            .query("exists", Some(key), redis::cmd("EXISTS").arg(&full_key))
             .await;
-        match result {
-            Ok(count) => count > 0,
-            Err(e) => {
-                tracing::warn!(
-                    key = %key,
-                    error = %e,
-                    "Redis EXISTS failed; treating as not present"
-                );
+        match (result, &self.fallback) {
+            (Ok(count), _) => count > 0,
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(key = %key, error = %e, "Redis EXISTS failed; using fallback cache");
+                fallback.exists(key).await
+            }
+            (Err(e), None) => {
+                tracing::debug!(key = %key, error = %e, "Redis EXISTS failed; treating as not present");
                 false
             }
         }
     }
 
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        let full_key = self.build_key(key);
+        let result: crate::error::Result<Option<Vec<u8>>> = self
+            .query("get_bytes", Some(key), redis::cmd("GET").arg(&full_key))
+            .await;
+        match (result, &self.fallback) {
+            (Ok(value), _) => value.map(Arc::from),
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(key = %key, error = %e, "Redis GET failed; using fallback cache");
+                fallback.get_bytes(key).await
+            }
+            (Err(e), None) => {
+                tracing::debug!(key = %key, error = %e, "Redis GET failed; treating as cache miss");
+                None
+            }
+        }
+    }
+
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        let full_key = self.build_key(&key);
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(&full_key).arg(&*value);
+        if let Some(ttl) = ttl {
+            cmd.arg("PX").arg(px_millis_for_ttl(ttl));
+        }
+
+        match (
+            self.query::<()>("set_bytes", Some(&key), &cmd).await,
+            &self.fallback,
+        ) {
+            (Ok(()), _) => Ok(()),
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(key = %key, error = %e, "Redis SET failed; using fallback cache");
+                fallback.set_bytes(key, value, ttl).await
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Vec<Option<Arc<str>>> {
+        if keys.is_empty() {
+            return Vec::new();
+        }
+        let full_keys: Vec<String> = keys.iter().map(|key| self.build_key(key)).collect();
+        let result: crate::error::Result<Vec<Option<String>>> = self
+            .query("get_many", None, redis::cmd("MGET").arg(&full_keys))
+            .await;
+        match (result, &self.fallback) {
+            (Ok(values), _) => values
+                .into_iter()
+                .map(|value| value.map(|s| Arc::from(s.into_boxed_str())))
+                .collect(),
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(error = %e, "Redis MGET failed; using fallback cache");
+                fallback.get_many(keys).await
+            }
+            (Err(e), None) => {
+                tracing::debug!(error = %e, "Redis MGET failed; treating as cache misses");
+                vec![None; keys.len()]
+            }
+        }
+    }
+
+    async fn set_many(
+        &self,
+        entries: Vec<(String, Arc<str>)>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let cmd = if let Some(ttl) = ttl {
+            // MSET cannot set an expiry, so a script sets every key with PX
+            let mut cmd = redis::cmd("EVAL");
+            cmd.arg(SET_MANY_SCRIPT).arg(entries.len());
+            for (key, _) in &entries {
+                cmd.arg(self.build_key(key));
+            }
+            cmd.arg(px_millis_for_ttl(ttl));
+            for (_, value) in &entries {
+                cmd.arg(&**value);
+            }
+            cmd
+        } else {
+            let mut cmd = redis::cmd("MSET");
+            for (key, value) in &entries {
+                cmd.arg(self.build_key(key)).arg(&**value);
+            }
+            cmd
+        };
+
+        match (
+            self.query::<()>("set_many", None, &cmd).await,
+            &self.fallback,
+        ) {
+            (Ok(()), _) => Ok(()),
+            (Err(e), Some(fallback)) => {
+                tracing::debug!(error = %e, "Redis batch SET failed; using fallback cache");
+                fallback.set_many(entries, ttl).await
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        let full_key = self.build_key(key);
+
+        // A Lua script keeps the ownership check and the write atomic across
+        // every replica sharing this Redis instance.
+        let acquired: i64 = self
+            .query(
+                "acquire_lease",
+                Some(key),
+                redis::cmd("EVAL")
+                    .arg(ACQUIRE_LEASE_SCRIPT)
+                    .arg(1)
+                    .arg(&full_key)
+                    .arg(holder)
+                    .arg(px_millis_for_ttl(ttl)),
+            )
+            .await?;
+        Ok(acquired == 1)
+    }
+
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        let full_key = self.build_key(key);
+
+        let _: i64 = self
+            .query(
+                "release_lease",
+                Some(key),
+                redis::cmd("EVAL")
+                    .arg(RELEASE_LEASE_SCRIPT)
+                    .arg(1)
+                    .arg(&full_key)
+                    .arg(holder),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        let full_key = self.build_key(key);
+
+        // A sorted set per counter: ZINCRBY is atomic and keeps the members
+        // ordered by count for `top_counters`.
+        let _: f64 = self
+            .query(
+                "increment_counter",
+                Some(key),
+                redis::cmd("ZINCRBY").arg(&full_key).arg(1).arg(member),
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    async fn top_counters(
+        &self,
+        key: &str,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, u64)>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let full_key = self.build_key(key);
+
+        let members: Vec<(String, f64)> = self
+            .query(
+                "top_counters",
+                Some(key),
+                redis::cmd("ZREVRANGE")
+                    .arg(&full_key)
+                    .arg(0)
+                    .arg(limit - 1)
+                    .arg("WITHSCORES"),
+            )
+            .await?;
+
+        Ok(members
+            .into_iter()
+            // Scores are integral increments, so the cast is exact
+            .map(|(member, score)| (member, score.max(0.0) as u64))
+            .collect())
+    }
+
+    /// Ping Redis, so the health check reports an outage
+    async fn check_integrity(&self) -> crate::error::Result<CacheIntegrity> {
+        let _: String = self.query("ping", None, &redis::cmd("PING")).await?;
+        Ok(CacheIntegrity::Volatile)
+    }
+
+    async fn stats(&self) -> CacheStats {
+        CacheStats {
+            errors: Some(self.health.errors.load(Ordering::Relaxed)),
+            ..CacheStats::default()
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -271,7 +687,6 @@ impl super::Cache for RedisCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cache::Cache;
 
     #[tokio::test]
     #[ignore = "Requires Redis server"]
@@ -286,7 +701,7 @@ mod tests {
 
         // Test set and get
         cache
-            .set("test_key".to_string(), "test_value".to_string(), None)
+            .set("test_key".to_string(), "test_value".into(), None)
             .await
             .expect("set should succeed");
         let value = cache.get("test_key").await;
@@ -303,7 +718,7 @@ mod tests {
 
         // Test exists
         cache
-            .set("exists_key".to_string(), "exists_value".to_string(), None)
+            .set("exists_key".to_string(), "exists_value".into(), None)
             .await
             .expect("set should succeed");
         assert!(cache.exists("exists_key").await);
@@ -311,12 +726,133 @@ mod tests {
 
         // Test clear (should only clear keys with our prefix)
         cache
-            .set("clear_test".to_string(), "value".to_string(), None)
+            .set("clear_test".to_string(), "value".into(), None)
             .await
             .expect("set should succeed");
         cache.clear().await.expect("clear should succeed");
         let cleared_value = cache.get("clear_test").await;
         assert!(cleared_value.is_none());
+
+        // Test batch operations, with and without expiry
+        let ttl = Duration::from_secs(5);
+        cache
+            .set_many(vec![("many_a".to_string(), "a".into())], Some(ttl))
+            .await
+            .expect("set_many should succeed");
+        cache
+            .set_many(vec![("many_b".to_string(), "b".into())], None)
+            .await
+            .expect("set_many should succeed");
+        let values = cache
+            .get_many(&[
+                "many_a".to_string(),
+                "missing".to_string(),
+                "many_b".to_string(),
+            ])
+            .await;
+        assert_eq!(values[0].as_deref(), Some("a"));
+        assert_eq!(values[1], None);
+        assert_eq!(values[2].as_deref(), Some("b"));
+
+        // Test binary values
+        let bytes: Arc<[u8]> = Arc::from(&[0x1f, 0x8b, 0xff, 0x00][..]);
+        cache
+            .set_bytes("bytes".to_string(), Arc::clone(&bytes), Some(ttl))
+            .await
+            .expect("set_bytes should succeed");
+        assert_eq!(cache.get_bytes("bytes").await, Some(bytes));
+        assert_eq!(cache.get_bytes("many_a").await.as_deref(), Some(&b"a"[..]));
+
+        // Test leases
+        assert!(cache.try_acquire_lease("lease", "a", ttl).await.unwrap());
+        assert!(!cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        cache.release_lease("lease", "a").await.unwrap();
+        assert!(cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        cache.release_lease("lease", "b").await.unwrap();
+    }
+
+    /// A cache whose Redis refuses connections: nothing listens on port 1
+    fn unreachable_cache() -> RedisCache {
+        RedisCache {
+            client: redis::Client::open("redis://127.0.0.1:1").unwrap(),
+            conn: tokio::sync::Mutex::new(None),
+            key_prefix: "test_prefix".to_string(),
+            health: Health::default(),
+            fallback: None,
+        }
+    }
+
+    #[cfg(feature = "cache-memory")]
+    #[tokio::test]
+    async fn test_redis_cache_falls_back_while_down() {
+        let cache = unreachable_cache()
+            .with_fallback(Box::new(crate::cache::memory::MemoryCache::new(100)));
+        assert!(cache.is_available());
+
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .expect("set should fall back to memory");
+        assert!(!cache.is_available());
+        assert_eq!(cache.get("key").await.as_deref(), Some("value"));
+        assert!(cache.exists("key").await);
+        cache
+            .set_many(vec![("other".to_string(), "2".into())], None)
+            .await
+            .expect("set_many should fall back to memory");
+        let values = cache
+            .get_many(&["key".to_string(), "other".to_string()])
+            .await;
+        assert_eq!(values[1].as_deref(), Some("2"));
+        cache
+            .set_bytes("bytes".to_string(), Arc::from(&[0xff][..]), None)
+            .await
+            .expect("set_bytes should fall back to memory");
+        assert_eq!(cache.get_bytes("bytes").await.as_deref(), Some(&[0xff][..]));
+
+        // Redis is marked down, so commands fail fast without retrying
+        assert_eq!(cache.stats().await.errors, Some(1));
+        let error = cache
+            .try_acquire_lease("lease", "a", Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Redis unavailable"));
+        assert!(cache.check_integrity().await.is_err());
+        assert_eq!(cache.stats().await.errors, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_without_fallback_reports_errors() {
+        let cache = unreachable_cache();
+        assert!(cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .is_err());
+        assert_eq!(cache.get("key").await, None);
+        assert!(!cache.exists("key").await);
+    }
+
+    #[test]
+    fn test_health_tracks_outages() {
+        let health = Health::default();
+        assert_eq!(health.backoff_remaining(), None);
+        assert!(!health.record_success());
+
+        assert_eq!(health.record_failure(), Duration::from_secs(1));
+        assert!(health.backoff_remaining().is_some());
+        assert_eq!(health.record_failure(), Duration::from_secs(2));
+
+        assert!(health.record_success());
+        assert_eq!(health.backoff_remaining(), None);
+        assert_eq!(health.errors.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_down_backoff_is_capped() {
+        assert_eq!(down_backoff(1), Duration::from_secs(1));
+        assert_eq!(down_backoff(3), Duration::from_secs(4));
+        assert_eq!(down_backoff(6), MAX_DOWN_BACKOFF);
+        assert_eq!(down_backoff(u32::MAX), MAX_DOWN_BACKOFF);
     }
 
     #[test]
@@ -0,0 +1,271 @@
+//! Coalescing of concurrent fetches for the same cache key
+//!
+//! When many sessions ask for the same uncached page at once, only one of
+//! them should hit the upstream. [`FetchCoordinator::begin`] serializes
+//! fetches per key within the process and, across replicas sharing a Redis
+//! cache, publishes a short-lived "being fetched" marker through
+//! [`Cache::try_acquire_lease`]. Callers that find the marker held by another
+//! replica wait for it to go away, then re-check the cache before fetching
+//! themselves.
+//!
+//! The marker is best effort: if the cache backend fails, or the fetching
+//! replica does not finish within the marker TTL, waiters fall back to
+//! fetching on their own.
+//!
+//! [`InFlight`] coalesces within the process without the cache: concurrent
+//! callers of the same key share the result of one running operation, which
+//! also covers responses that are never cached.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use crates_docs::cache::memory::MemoryCache;
+//! use crates_docs::cache::singleflight::FetchCoordinator;
+//!
+//! # async fn example() {
+//! let fetches = FetchCoordinator::new(Arc::new(MemoryCache::new(1000)));
+//! let permit = fetches.begin("crate:serde:latest").await;
+//! // ... re-check the cache, fetch and store the page ...
+//! drop(permit);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OnceCell, OwnedMutexGuard};
+
+use super::lease::LeaseCoordinator;
+use super::Cache;
+
+/// How long a "being fetched" marker lives if its holder never releases it
+///
+/// Matches the default upstream request timeout, so a marker outlives any
+/// fetch that can still succeed.
+const MARKER_TTL: Duration = Duration::from_secs(30);
+
+/// How often a waiting caller checks whether the marker is gone
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+type LocalLocks = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// Coordinates fetches of the same key across sessions and replicas
+pub struct FetchCoordinator {
+    cache: Arc<dyn Cache>,
+    holder_id: String,
+    local: LocalLocks,
+    marker_ttl: Duration,
+    poll_interval: Duration,
+}
+
+impl FetchCoordinator {
+    /// Create a coordinator with a randomly generated replica ID
+    #[must_use]
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self {
+            cache,
+            holder_id: uuid::Uuid::new_v4().to_string(),
+            local: Arc::default(),
+            marker_ttl: MARKER_TTL,
+            poll_interval: POLL_INTERVAL,
+        }
+    }
+
+    /// Override the marker TTL and the polling interval of waiting callers
+    #[must_use]
+    pub fn with_timing(mut self, marker_ttl: Duration, poll_interval: Duration) -> Self {
+        self.marker_ttl = marker_ttl;
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Cache key of the "being fetched" marker for `key`
+    #[must_use]
+    pub fn marker_key(key: &str) -> String {
+        LeaseCoordinator::lease_key(&format!("fetch:{key}"))
+    }
+
+    /// Wait for the right to fetch `key`
+    ///
+    /// Resolves once no other session of this process is fetching `key`
+    /// and no other replica is either (or its marker expired). The caller
+    /// should then re-check the cache, since the page may have been stored
+    /// while it waited, and fetch only on a miss. Other callers wait until
+    /// the returned permit is dropped.
+    pub async fn begin(&self, key: &str) -> FetchPermit {
+        let lock = {
+            let mut local = self
+                .local
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Arc::clone(local.entry(key.to_string()).or_default())
+        };
+        let guard = lock.lock_owned().await;
+
+        let marker = Self::marker_key(key);
+        let marker_held = loop {
+            match self
+                .cache
+                .try_acquire_lease(&marker, &self.holder_id, self.marker_ttl)
+                .await
+            {
+                Ok(true) => break true,
+                // Once the other replica is done, take the marker over so
+                // that the next replica waits for this one
+                Ok(false) => {
+                    if !self.wait_for_marker(&marker).await {
+                        break false;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!(key = %key, error = %e, "Fetch marker unavailable; fetching uncoordinated");
+                    break false;
+                }
+            }
+        };
+
+        FetchPermit {
+            key: key.to_string(),
+            guard: Some(guard),
+            local: Arc::clone(&self.local),
+            marker: marker_held.then(|| (Arc::clone(&self.cache), marker, self.holder_id.clone())),
+        }
+    }
+
+    /// Wait until another replica's marker is released or expires
+    ///
+    /// Returns `false` if the marker is still there after the marker TTL,
+    /// i.e. its holder neither released nor renewed it in time.
+    async fn wait_for_marker(&self, marker: &str) -> bool {
+        let deadline = tokio::time::Instant::now() + self.marker_ttl;
+        tracing::debug!(marker = %marker, "Another replica is fetching; waiting");
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(self.poll_interval).await;
+            if !self.cache.exists(marker).await {
+                return true;
+            }
+        }
+        tracing::debug!(marker = %marker, "Fetch marker not released in time; fetching anyway");
+        false
+    }
+}
+
+/// Right to fetch a key, released on drop
+pub struct FetchPermit {
+    key: String,
+    guard: Option<OwnedMutexGuard<()>>,
+    local: LocalLocks,
+    /// Cache, marker key and holder ID when this replica set the marker
+    marker: Option<(Arc<dyn Cache>, String, String)>,
+}
+
+impl FetchPermit {
+    /// Whether this replica published the "being fetched" marker
+    #[must_use]
+    pub fn holds_marker(&self) -> bool {
+        self.marker.is_some()
+    }
+}
+
+impl Drop for FetchPermit {
+    fn drop(&mut self) {
+        let guard = self.guard.take();
+        let local = Arc::clone(&self.local);
+        let key = std::mem::take(&mut self.key);
+        let unlock = move || {
+            // Release the local lock, then forget it if nobody waits on it
+            drop(guard);
+            let mut local = local
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if local
+                .get(&key)
+                .is_some_and(|lock| Arc::strong_count(lock) == 1)
+            {
+                local.remove(&key);
+            }
+        };
+
+        // The marker is released before the local lock, so the next local
+        // fetch cannot have its freshly renewed marker removed
+        match (self.marker.take(), tokio::runtime::Handle::try_current()) {
+            (Some((cache, marker, holder)), Ok(handle)) => {
+                handle.spawn(async move {
+                    if let Err(e) = cache.release_lease(&marker, &holder).await {
+                        tracing::debug!(marker = %marker, error = %e, "Failed to release fetch marker");
+                    }
+                    unlock();
+                });
+            }
+            // Without a runtime the marker simply expires after its TTL
+            _ => unlock(),
+        }
+    }
+}
+
+/// Shares the result of identical concurrent operations within the process
+///
+/// The first caller of a key runs the operation; callers arriving while it
+/// runs wait for it and receive a clone of its result. Nothing is kept once
+/// the operation completes, so a later call runs it afresh. If the running
+/// caller is cancelled, one of the waiters runs the operation instead.
+pub struct InFlight<V> {
+    calls: Mutex<HashMap<String, Arc<OnceCell<V>>>>,
+}
+
+impl<V> Default for InFlight<V> {
+    fn default() -> Self {
+        Self {
+            calls: Mutex::default(),
+        }
+    }
+}
+
+impl<V: Clone> InFlight<V> {
+    /// Create an empty coalescer
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `operation` for `key`, or join the run already in flight
+    pub async fn run<F, Fut>(&self, key: &str, operation: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let cell = Arc::clone(self.lock().entry(key.to_string()).or_default());
+        let value = cell.get_or_init(operation).await.clone();
+
+        // Forget the finished run, unless a newer one already replaced it
+        let mut calls = self.lock();
+        if calls
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            calls.remove(key);
+        }
+        value
+    }
+
+    /// Number of keys with an operation in flight
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no operation is in flight
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceCell<V>>>> {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
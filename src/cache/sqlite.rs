@@ -0,0 +1,1021 @@
+//! `SQLite` cache implementation
+//!
+//! Persists entries in a single `SQLite` file: a middle ground between the
+//! in-process memory cache and a Redis deployment. Entries survive restarts
+//! and can be shared by processes on the same host, without running a
+//! separate server.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::types::{FromSqlError, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+
+use super::{CacheIntegrity, CacheStats};
+use crate::error::Error;
+
+/// How often expired entries are swept in the background
+///
+/// Sweeping deletes through the expiry index and sums the stored sizes, so
+/// it runs off the request path; reads check the expiry of their own key.
+const PRUNE_PERIOD: Duration = Duration::from_mins(1);
+
+/// Share of `max_size_bytes` (1/N) this process may write between two prunes
+///
+/// Writes count the bytes they store and prune inline once the share is
+/// reached, so between background runs the size limit is exceeded by at
+/// most this share (plus writes of other processes sharing the file).
+const WRITE_BUDGET_DIVISOR: u64 = 10;
+
+/// How long a connection waits for a lock held by another process
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tables and indexes, created on open
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS cache_entries (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    expires_at INTEGER,
+    updated_at INTEGER NOT NULL,
+    size INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS cache_entries_expires_at ON cache_entries (expires_at);
+CREATE INDEX IF NOT EXISTS cache_entries_updated_at ON cache_entries (updated_at);
+CREATE TABLE IF NOT EXISTS cache_counters (
+    key TEXT NOT NULL,
+    member TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (key, member)
+);
+";
+
+/// Connection and the bookkeeping that shares its lock
+struct State {
+    conn: Connection,
+    /// Result of the first integrity check, reused by later calls
+    integrity: Option<CacheIntegrity>,
+    /// Bytes this process stored since the last prune
+    written_since_prune: u64,
+}
+
+impl State {
+    /// Prune, restarting the count of bytes written
+    fn prune(&mut self, max_size_bytes: Option<u64>) -> rusqlite::Result<usize> {
+        self.written_since_prune = 0;
+        prune(&self.conn, now_millis(), max_size_bytes)
+    }
+
+    /// Count `size` bytes written, pruning once the write budget is spent
+    ///
+    /// Only a sum of lengths: the stored size is not queried on writes.
+    fn count_write(
+        &mut self,
+        size: u64,
+        max_size_bytes: Option<u64>,
+        evictions: &AtomicU64,
+    ) -> rusqlite::Result<()> {
+        let Some(max) = max_size_bytes else {
+            return Ok(());
+        };
+        self.written_since_prune = self.written_since_prune.saturating_add(size);
+        if self.written_since_prune > max / WRITE_BUDGET_DIVISOR {
+            let deleted = self.prune(max_size_bytes)?;
+            evictions.fetch_add(deleted as u64, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+/// `SQLite` cache implementation
+///
+/// Every entry is a row with its expiry time; expired rows read as missing
+/// and are deleted by a background task every [`PRUNE_PERIOD`]. When
+/// `max_size_bytes` is set, pruning then drops the least recently written
+/// entries until the stored keys and values fit; writes also prune once they
+/// have stored a tenth of the limit since the last run (see
+/// [`WRITE_BUDGET_DIVISOR`]).
+///
+/// Queries run on the blocking thread pool. Leases take the write lock
+/// before reading and counters are single upserts, so both stay atomic
+/// across processes sharing the database file.
+pub struct SqliteCache {
+    state: Arc<Mutex<State>>,
+    /// Key prefix for all cache entries
+    key_prefix: String,
+    /// Upper bound on the stored bytes, `None` for unbounded
+    max_size_bytes: Option<u64>,
+    /// Entries deleted by pruning
+    evictions: Arc<AtomicU64>,
+}
+
+impl SqliteCache {
+    /// Open (or create) a `SQLite` cache
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Database file; missing parent directories are created
+    /// * `key_prefix` - Prefix for all cache keys (can be empty string)
+    /// * `max_size_bytes` - Size above which old entries are pruned, `None` for unbounded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or initialized
+    pub fn new(
+        path: impl AsRef<Path>,
+        key_prefix: String,
+        max_size_bytes: Option<u64>,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::cache(
+                    "open",
+                    None,
+                    format!("cannot create {}: {e}", parent.display()),
+                )
+            })?;
+        }
+        let conn = Connection::open(path)
+            .and_then(|conn| {
+                conn.busy_timeout(BUSY_TIMEOUT)?;
+                // WAL lets readers proceed while another process writes
+                conn.pragma_update(None, "journal_mode", "WAL")?;
+                conn.pragma_update(None, "synchronous", "NORMAL")?;
+                conn.execute_batch(SCHEMA)?;
+                Ok(conn)
+            })
+            .map_err(|e| Error::cache("open", None, format!("{}: {e}", path.display())))?;
+
+        let cache = Self {
+            state: Arc::new(Mutex::new(State {
+                conn,
+                integrity: None,
+                written_since_prune: 0,
+            })),
+            key_prefix,
+            max_size_bytes,
+            evictions: Arc::new(AtomicU64::new(0)),
+        };
+        cache.spawn_pruning();
+        Ok(cache)
+    }
+
+    /// Prune every [`PRUNE_PERIOD`] on a background task
+    ///
+    /// The task holds a weak reference and stops once the cache is dropped.
+    /// Outside a Tokio runtime nothing is spawned; entries are then pruned
+    /// only by [`Self::prune`].
+    fn spawn_pruning(&self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let state = Arc::downgrade(&self.state);
+        let max_size_bytes = self.max_size_bytes;
+        let evictions = Arc::clone(&self.evictions);
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(PRUNE_PERIOD);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let Some(state) = state.upgrade() else {
+                    break;
+                };
+                let result =
+                    tokio::task::spawn_blocking(move || lock(&state).prune(max_size_bytes)).await;
+                match result {
+                    Ok(Ok(deleted)) => {
+                        evictions.fetch_add(deleted as u64, Ordering::Relaxed);
+                    }
+                    Ok(Err(e)) => tracing::warn!(error = %e, "SQLite cache pruning failed"),
+                    Err(e) => tracing::warn!(error = %e, "SQLite cache pruning task failed"),
+                }
+            }
+        });
+    }
+
+    /// Build full key with prefix
+    fn build_key(&self, key: &str) -> String {
+        if self.key_prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}:{}", self.key_prefix, key)
+        }
+    }
+
+    /// Run `f` on the connection from the blocking thread pool
+    async fn with_state<T, F>(
+        &self,
+        operation: &str,
+        key: Option<&str>,
+        f: F,
+    ) -> crate::error::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut State) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let state = Arc::clone(&self.state);
+        let result = tokio::task::spawn_blocking(move || f(&mut lock(&state))).await;
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(Error::cache(
+                operation,
+                key.map(str::to_string),
+                format!("failed: {e}"),
+            )),
+            Err(e) => Err(Error::cache(
+                operation,
+                key.map(str::to_string),
+                format!("task failed: {e}"),
+            )),
+        }
+    }
+
+    /// Store one entry, pruning if the write budget is spent
+    async fn write(
+        &self,
+        operation: &str,
+        key: String,
+        value: StoredValue,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        let full_key = self.build_key(&key);
+        let max_size_bytes = self.max_size_bytes;
+        let evictions = Arc::clone(&self.evictions);
+        self.with_state(operation, Some(&key), move |state| {
+            let now = now_millis();
+            let size = upsert(
+                &state.conn,
+                &full_key,
+                value.as_value_ref(),
+                expires_at(now, ttl),
+                now,
+            )?;
+            state.count_write(size, max_size_bytes, &evictions)
+        })
+        .await
+    }
+
+    /// Number of entries stored, expired or not
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails
+    pub async fn entry_count(&self) -> crate::error::Result<u64> {
+        self.with_state("entry_count", None, |state| {
+            state
+                .conn
+                .query_row("SELECT COUNT(*) FROM cache_entries", [], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .map(|count| u64::try_from(count).unwrap_or(0))
+        })
+        .await
+    }
+
+    /// Delete expired entries, then the oldest ones while over the size limit
+    ///
+    /// Runs in the background every [`PRUNE_PERIOD`] and on writes once the
+    /// write budget is spent; exposed for maintenance and tests.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be updated
+    pub async fn prune(&self) -> crate::error::Result<usize> {
+        let max_size_bytes = self.max_size_bytes;
+        let deleted = self
+            .with_state("prune", None, move |state| state.prune(max_size_bytes))
+            .await?;
+        self.evictions.fetch_add(deleted as u64, Ordering::Relaxed);
+        Ok(deleted)
+    }
+}
+
+/// Value written by [`SqliteCache::write`]
+enum StoredValue {
+    Text(Arc<str>),
+    Bytes(Arc<[u8]>),
+}
+
+impl StoredValue {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        match self {
+            Self::Text(text) => ValueRef::Text(text.as_bytes()),
+            Self::Bytes(bytes) => ValueRef::Blob(bytes),
+        }
+    }
+}
+
+/// Lock the state, recovering it if a query panicked while holding the lock
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Current time in milliseconds since the Unix epoch
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Expiry timestamp for an entry written at `now` with `ttl`
+fn expires_at(now: i64, ttl: Option<Duration>) -> Option<i64> {
+    ttl.map(|ttl| now.saturating_add(i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX)))
+}
+
+/// Key range `[start, end)` covering every key under `prefix`
+///
+/// `;` follows `:` in byte order, so the range holds exactly the keys
+/// starting with `{prefix}:`. Returns `None` for an empty prefix.
+fn prefix_range(prefix: &str) -> Option<(String, String)> {
+    if prefix.is_empty() {
+        None
+    } else {
+        Some((format!("{prefix}:"), format!("{prefix};")))
+    }
+}
+
+/// Store `value` under `key`, replacing any previous entry
+///
+/// Text values are bound as `TEXT` and binary ones as `BLOB`; the column's
+/// text affinity leaves blobs untouched.
+fn upsert(
+    conn: &Connection,
+    key: &str,
+    value: ValueRef<'_>,
+    expires_at: Option<i64>,
+    now: i64,
+) -> rusqlite::Result<u64> {
+    let len = match value {
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => bytes.len(),
+        _ => 0,
+    };
+    let size = key.len() + len;
+    conn.execute(
+        "INSERT INTO cache_entries (key, value, expires_at, updated_at, size)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (key) DO UPDATE SET
+             value = excluded.value,
+             expires_at = excluded.expires_at,
+             updated_at = excluded.updated_at,
+             size = excluded.size",
+        params![
+            key,
+            ToSqlOutput::Borrowed(value),
+            expires_at,
+            now,
+            i64::try_from(size).unwrap_or(i64::MAX)
+        ],
+    )?;
+    Ok(size as u64)
+}
+
+/// Text value of `key` unless it is missing, expired or binary
+fn live_value(conn: &Connection, key: &str, now: i64) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT value FROM cache_entries
+         WHERE key = ?1 AND (expires_at IS NULL OR expires_at > ?2)",
+        params![key, now],
+        |row| match row.get_ref(0)? {
+            ValueRef::Blob(_) => Ok(None),
+            _ => row.get(0).map(Some),
+        },
+    )
+    .optional()
+    .map(Option::flatten)
+}
+
+/// Bytes of `key` unless it is missing or expired; text entries read as
+/// their UTF-8 bytes
+fn live_bytes(conn: &Connection, key: &str, now: i64) -> rusqlite::Result<Option<Vec<u8>>> {
+    conn.query_row(
+        "SELECT value FROM cache_entries
+         WHERE key = ?1 AND (expires_at IS NULL OR expires_at > ?2)",
+        params![key, now],
+        |row| match row.get_ref(0)? {
+            ValueRef::Text(bytes) | ValueRef::Blob(bytes) => Ok(bytes.to_vec()),
+            other => Err(rusqlite::Error::FromSqlConversionFailure(
+                0,
+                other.data_type(),
+                Box::new(FromSqlError::InvalidType),
+            )),
+        },
+    )
+    .optional()
+}
+
+/// Delete expired entries, then the least recently written ones until the
+/// stored size is at most `max_size_bytes`; returns the number deleted
+fn prune(conn: &Connection, now: i64, max_size_bytes: Option<u64>) -> rusqlite::Result<usize> {
+    let mut deleted = conn.execute(
+        "DELETE FROM cache_entries WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+        [now],
+    )?;
+    let Some(max) = max_size_bytes else {
+        return Ok(deleted);
+    };
+    let max = i64::try_from(max).unwrap_or(i64::MAX);
+    let total: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(size), 0) FROM cache_entries",
+        [],
+        |row| row.get(0),
+    )?;
+    if total > max {
+        // Walk the entries newest first and drop everything past the budget
+        deleted += conn.execute(
+            "DELETE FROM cache_entries WHERE key IN (
+                 SELECT key FROM (
+                     SELECT key, SUM(size) OVER (ORDER BY updated_at DESC, key) AS running
+                     FROM cache_entries
+                 ) WHERE running > ?1
+             )",
+            [max],
+        )?;
+    }
+    Ok(deleted)
+}
+
+#[async_trait::async_trait]
+impl super::Cache for SqliteCache {
+    async fn get(&self, key: &str) -> Option<Arc<str>> {
+        let full_key = self.build_key(key);
+        let result = self
+            .with_state("get", Some(key), move |state| {
+                live_value(&state.conn, &full_key, now_millis())
+            })
+            .await;
+        match result {
+            Ok(value) => value.map(Arc::from),
+            Err(e) => {
+                // Same policy as Redis: a broken store degrades to misses
+                tracing::warn!(key = %key, error = %e, "SQLite GET failed; treating as cache miss");
+                None
+            }
+        }
+    }
+
+    async fn set(
+        &self,
+        key: String,
+        value: Arc<str>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.write("set", key, StoredValue::Text(value), ttl).await
+    }
+
+    async fn delete(&self, key: &str) -> crate::error::Result<()> {
+        let full_key = self.build_key(key);
+        self.with_state("delete", Some(key), move |state| {
+            state
+                .conn
+                .execute("DELETE FROM cache_entries WHERE key = ?1", [full_key])
+                .map(|_| ())
+        })
+        .await
+    }
+
+    async fn clear(&self) -> crate::error::Result<()> {
+        // The database file belongs to this cache, so without a prefix every
+        // entry is ours to delete
+        let range = prefix_range(&self.key_prefix);
+        let deleted = self
+            .with_state("clear", None, move |state| {
+                let tx = state.conn.transaction()?;
+                let (start, end) = range.unzip();
+                // NULL bounds (no prefix) match every key
+                let deleted = tx.execute(
+                    "DELETE FROM cache_entries WHERE ?1 IS NULL OR (key >= ?1 AND key < ?2)",
+                    params![start, end],
+                )?;
+                tx.execute(
+                    "DELETE FROM cache_counters WHERE ?1 IS NULL OR (key >= ?1 AND key < ?2)",
+                    params![start, end],
+                )?;
+                tx.commit()?;
+                Ok(deleted)
+            })
+            .await?;
+
+        if deleted > 0 {
+            tracing::debug!(
+                "Cleared {} cache entries with prefix '{}'",
+                deleted,
+                self.key_prefix
+            );
+        }
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> bool {
+        self.get_bytes(key).await.is_some()
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Vec<Option<Arc<str>>> {
+        let full_keys: Vec<String> = keys.iter().map(|key| self.build_key(key)).collect();
+        let result = self
+            .with_state("get_many", None, move |state| {
+                let now = now_millis();
+                full_keys
+                    .iter()
+                    .map(|key| live_value(&state.conn, key, now))
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await;
+        match result {
+            Ok(values) => values
+                .into_iter()
+                .map(|value| value.map(Arc::from))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "SQLite GET failed; treating as cache misses");
+                vec![None; keys.len()]
+            }
+        }
+    }
+
+    async fn set_many(
+        &self,
+        entries: Vec<(String, Arc<str>)>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        let entries: Vec<(String, Arc<str>)> = entries
+            .into_iter()
+            .map(|(key, value)| (self.build_key(&key), value))
+            .collect();
+        let max_size_bytes = self.max_size_bytes;
+        let evictions = Arc::clone(&self.evictions);
+        // One transaction: a single commit instead of one per entry
+        self.with_state("set_many", None, move |state| {
+            let now = now_millis();
+            let tx = state.conn.transaction()?;
+            let mut size = 0;
+            for (key, value) in &entries {
+                size += upsert(
+                    &tx,
+                    key,
+                    ValueRef::Text(value.as_bytes()),
+                    expires_at(now, ttl),
+                    now,
+                )?;
+            }
+            tx.commit()?;
+            state.count_write(size, max_size_bytes, &evictions)
+        })
+        .await
+    }
+
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        let full_key = self.build_key(key);
+        let result = self
+            .with_state("get_bytes", Some(key), move |state| {
+                live_bytes(&state.conn, &full_key, now_millis())
+            })
+            .await;
+        match result {
+            Ok(value) => value.map(Arc::from),
+            Err(e) => {
+                tracing::warn!(key = %key, error = %e, "SQLite GET failed; treating as cache miss");
+                None
+            }
+        }
+    }
+
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.write("set_bytes", key, StoredValue::Bytes(value), ttl)
+            .await
+    }
+
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        let full_key = self.build_key(key);
+        let holder = holder.to_string();
+        // An immediate transaction takes the write lock before reading, so
+        // the ownership check and the write are atomic across processes
+        self.with_state("acquire_lease", Some(key), move |state| {
+            let tx = state
+                .conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let now = now_millis();
+            let acquired = match live_value(&tx, &full_key, now)? {
+                Some(current) if current != holder => false,
+                _ => {
+                    upsert(
+                        &tx,
+                        &full_key,
+                        ValueRef::Text(holder.as_bytes()),
+                        expires_at(now, Some(ttl)),
+                        now,
+                    )?;
+                    true
+                }
+            };
+            tx.commit()?;
+            Ok(acquired)
+        })
+        .await
+    }
+
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        let full_key = self.build_key(key);
+        let holder = holder.to_string();
+        self.with_state("release_lease", Some(key), move |state| {
+            state
+                .conn
+                .execute(
+                    "DELETE FROM cache_entries WHERE key = ?1 AND value = ?2",
+                    params![full_key, holder],
+                )
+                .map(|_| ())
+        })
+        .await
+    }
+
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        let full_key = self.build_key(key);
+        let member = member.to_string();
+        // One row per member; the upsert is a single atomic statement
+        self.with_state("increment_counter", Some(key), move |state| {
+            state
+                .conn
+                .execute(
+                    "INSERT INTO cache_counters (key, member, count) VALUES (?1, ?2, 1)
+                     ON CONFLICT (key, member) DO UPDATE SET count = count + 1",
+                    params![full_key, member],
+                )
+                .map(|_| ())
+        })
+        .await
+    }
+
+    async fn top_counters(
+        &self,
+        key: &str,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, u64)>> {
+        let full_key = self.build_key(key);
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        self.with_state("top_counters", Some(key), move |state| {
+            let mut stmt = state.conn.prepare(
+                "SELECT member, count FROM cache_counters WHERE key = ?1
+                 ORDER BY count DESC, member LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![full_key, limit], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?;
+            rows.map(|row| row.map(|(member, count)| (member, u64::try_from(count).unwrap_or(0))))
+                .collect()
+        })
+        .await
+    }
+
+    async fn flush(&self) -> crate::error::Result<()> {
+        // Fold the write-ahead log back into the database file
+        self.with_state("flush", None, |state| {
+            state
+                .conn
+                .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        })
+        .await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        let entries = self.entry_count().await;
+        CacheStats {
+            evictions: Some(self.evictions.load(Ordering::Relaxed)),
+            entries: entries.ok(),
+            ..CacheStats::default()
+        }
+    }
+
+    async fn check_integrity(&self) -> crate::error::Result<CacheIntegrity> {
+        self.with_state("check_integrity", None, |state| {
+            if let Some(integrity) = state.integrity {
+                return Ok(integrity);
+            }
+            // SQLite recovers committed transactions on its own after a
+            // crash; only a damaged file needs repairing
+            let result: String = state
+                .conn
+                .query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+            let integrity = if result == "ok" {
+                CacheIntegrity::Clean
+            } else {
+                let discarded: i64 =
+                    state
+                        .conn
+                        .query_row("SELECT COUNT(*) FROM cache_entries", [], |row| row.get(0))?;
+                let discarded = usize::try_from(discarded).unwrap_or(0);
+                state.conn.execute_batch(
+                    "DELETE FROM cache_entries; DELETE FROM cache_counters; VACUUM;",
+                )?;
+                CacheIntegrity::Repaired { discarded }
+            };
+            state.integrity = Some(integrity);
+            Ok(integrity)
+        })
+        .await
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Cache;
+
+    fn open(dir: &tempfile::TempDir, max_size_bytes: Option<u64>) -> SqliteCache {
+        SqliteCache::new(
+            dir.path().join("cache.db"),
+            "test".to_string(),
+            max_size_bytes,
+        )
+        .expect("open sqlite cache")
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_basic_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("key").await.as_deref(), Some("value"));
+        assert!(cache.exists("key").await);
+
+        cache
+            .set("key".to_string(), "updated".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("key").await.as_deref(), Some("updated"));
+
+        cache.delete("key").await.unwrap();
+        assert!(cache.get("key").await.is_none());
+        assert!(!cache.exists("key").await);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_batch_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+
+        cache
+            .set_many(
+                vec![("a".to_string(), "1".into()), ("b".to_string(), "2".into())],
+                Some(Duration::from_mins(1)),
+            )
+            .await
+            .unwrap();
+        let keys = ["b".to_string(), "missing".to_string(), "a".to_string()];
+        let values = cache.get_many(&keys).await;
+        assert_eq!(values[0].as_deref(), Some("2"));
+        assert_eq!(values[1], None);
+        assert_eq!(values[2].as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_bytes_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let value: Arc<[u8]> = Arc::from(&[0x1f, 0x8b, 0xff, 0x00][..]);
+        {
+            let cache = open(&dir, None);
+            cache
+                .set_bytes("gzip".to_string(), Arc::clone(&value), None)
+                .await
+                .unwrap();
+            cache
+                .set("text".to_string(), "value".into(), None)
+                .await
+                .unwrap();
+        }
+
+        let cache = open(&dir, None);
+        assert_eq!(cache.get_bytes("gzip").await, Some(value));
+        assert!(cache.exists("gzip").await);
+        assert_eq!(cache.get("gzip").await, None);
+        assert_eq!(
+            cache.get_bytes("text").await.as_deref(),
+            Some(&b"value"[..])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_ttl_expiration() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+
+        cache
+            .set(
+                "short".to_string(),
+                "value".into(),
+                Some(Duration::from_millis(50)),
+            )
+            .await
+            .unwrap();
+        assert!(cache.get("short").await.is_some());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(cache.get("short").await.is_none());
+        assert_eq!(cache.prune().await.unwrap(), 1);
+        assert_eq!(cache.entry_count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_prunes_oldest_entries_over_size() {
+        let dir = tempfile::tempdir().unwrap();
+        // Each entry is "test:kN" (7 bytes) plus a 10-byte value
+        let cache = open(&dir, Some(40));
+
+        for i in 0..4 {
+            cache
+                .set(format!("k{i}"), "0123456789".into(), None)
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        // Every write spends the 4-byte write budget, so the writes pruned
+        assert_eq!(cache.prune().await.unwrap(), 0);
+        let stats = cache.stats().await;
+        assert_eq!((stats.evictions, stats.entries), (Some(2), Some(2)));
+        assert!(cache.get("k0").await.is_none());
+        assert!(cache.get("k1").await.is_none());
+        assert!(cache.get("k2").await.is_some());
+        assert!(cache.get("k3").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_prunes_expired_entries_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+
+        cache
+            .set(
+                "short".to_string(),
+                "value".into(),
+                Some(Duration::from_millis(50)),
+            )
+            .await
+            .unwrap();
+        cache
+            .set("long".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // Writes and reads leave the expired row for the sweep
+        assert!(cache.get("short").await.is_none());
+        assert_eq!(cache.entry_count().await.unwrap(), 2);
+
+        tokio::time::pause();
+        tokio::time::advance(PRUNE_PERIOD).await;
+        tokio::time::resume();
+        for _ in 0..100 {
+            if cache.entry_count().await.unwrap() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(cache.entry_count().await.unwrap(), 1);
+        assert_eq!(cache.stats().await.evictions, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_writes_prune_once_budget_is_spent() {
+        let dir = tempfile::tempdir().unwrap();
+        // Budget of 100 bytes; each entry is "test:kNN" (8 bytes) plus 42
+        let cache = open(&dir, Some(1000));
+        let value: Arc<str> = "x".repeat(42).into();
+
+        for i in 10..40 {
+            cache
+                .set(format!("k{i}"), Arc::clone(&value), None)
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        // 1500 bytes were written, but never more than the limit plus the
+        // budget were stored, without the background sweep
+        let stored = cache.entry_count().await.unwrap() * 50;
+        assert!(stored <= 1000 + 100, "{stored} bytes stored");
+        assert!(cache.get("k39").await.is_some());
+        assert!(cache.get("k10").await.is_none());
+
+        // set_many counts its entries too
+        let entries = (40..70)
+            .map(|i| (format!("k{i}"), Arc::clone(&value)))
+            .collect();
+        cache.set_many(entries, None).await.unwrap();
+        let stored = cache.entry_count().await.unwrap() * 50;
+        assert!(stored <= 1000, "{stored} bytes stored");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let cache = open(&dir, None);
+            cache
+                .set("key".to_string(), "value".into(), None)
+                .await
+                .unwrap();
+            cache.increment_counter("hits", "serde").await.unwrap();
+            cache.flush().await.unwrap();
+        }
+
+        let cache = open(&dir, None);
+        assert_eq!(
+            cache.check_integrity().await.unwrap(),
+            CacheIntegrity::Clean
+        );
+        assert_eq!(cache.get("key").await.as_deref(), Some("value"));
+        assert_eq!(
+            cache.top_counters("hits", 10).await.unwrap(),
+            vec![("serde".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_clear_only_touches_own_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+        let ours = SqliteCache::new(&path, "ours".to_string(), None).unwrap();
+        let theirs = SqliteCache::new(&path, "theirs".to_string(), None).unwrap();
+
+        ours.set("key".to_string(), "a".into(), None).await.unwrap();
+        ours.increment_counter("hits", "serde").await.unwrap();
+        theirs
+            .set("key".to_string(), "b".into(), None)
+            .await
+            .unwrap();
+
+        ours.clear().await.unwrap();
+        assert!(ours.get("key").await.is_none());
+        assert!(ours.top_counters("hits", 10).await.unwrap().is_empty());
+        assert_eq!(theirs.get("key").await.as_deref(), Some("b"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_leases() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+        let ttl = Duration::from_secs(5);
+
+        assert!(cache.try_acquire_lease("lease", "a", ttl).await.unwrap());
+        assert!(cache.try_acquire_lease("lease", "a", ttl).await.unwrap());
+        assert!(!cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        cache.release_lease("lease", "b").await.unwrap();
+        assert!(!cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        cache.release_lease("lease", "a").await.unwrap();
+        assert!(cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+
+        // An expired lease is free for the next holder
+        assert!(cache
+            .try_acquire_lease("short", "a", Duration::from_millis(20))
+            .await
+            .unwrap());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(cache.try_acquire_lease("short", "b", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cache_counters() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(&dir, None);
+
+        for member in ["serde", "tokio", "serde"] {
+            cache.increment_counter("hits", member).await.unwrap();
+        }
+        assert_eq!(
+            cache.top_counters("hits", 10).await.unwrap(),
+            vec![("serde".to_string(), 2), ("tokio".to_string(), 1)]
+        );
+        assert_eq!(cache.top_counters("hits", 1).await.unwrap().len(), 1);
+        assert!(cache.top_counters("hits", 0).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prefix_range() {
+        assert_eq!(prefix_range(""), None);
+        assert_eq!(
+            prefix_range("app"),
+            Some(("app:".to_string(), "app;".to_string()))
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Cache statistics
+//!
+//! Counts hits and misses in front of any backend, which reports its own
+//! evictions and entry count through [`Cache::stats`]. Lookups made inside
+//! [`track_lookups`] are also counted for that request alone.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{Cache, CacheIntegrity, CacheStats};
+
+tokio::task_local! {
+    static REQUEST_LOOKUPS: Arc<RequestLookups>;
+}
+
+/// Cache hits and misses of a single request, see [`track_lookups`]
+#[derive(Debug, Default)]
+pub struct RequestLookups {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl RequestLookups {
+    /// Number of lookups that found a value
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that found nothing
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// `"hit"` if every lookup hit, `"miss"` if none did, `"partial"` in
+    /// between and `"none"` without lookups
+    #[must_use]
+    pub fn outcome(&self) -> &'static str {
+        match (self.hits(), self.misses()) {
+            (0, 0) => "none",
+            (_, 0) => "hit",
+            (0, _) => "miss",
+            _ => "partial",
+        }
+    }
+}
+
+/// Run `future`, counting the lookups it makes through a [`StatsCache`]
+///
+/// Lookups made by tasks spawned from `future` are not counted.
+pub async fn track_lookups<F: Future>(future: F) -> (F::Output, Arc<RequestLookups>) {
+    let lookups = Arc::new(RequestLookups::default());
+    let output = REQUEST_LOOKUPS.scope(Arc::clone(&lookups), future).await;
+    (output, lookups)
+}
+
+/// Cache wrapper counting hits and misses of [`Cache::get`] and [`Cache::get_bytes`]
+///
+/// Every other operation is passed through unchanged, so backend-specific
+/// atomic leases and counters keep working.
+pub struct StatsCache {
+    inner: Arc<dyn Cache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl StatsCache {
+    /// Wrap `inner`, starting from zero hits and misses
+    #[must_use]
+    pub fn new(inner: Arc<dyn Cache>) -> Self {
+        Self {
+            inner,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Count `hits` and `misses`, for the current request too
+    fn record(&self, hits: u64, misses: u64) {
+        self.hits.fetch_add(hits, Ordering::Relaxed);
+        self.misses.fetch_add(misses, Ordering::Relaxed);
+        let _ = REQUEST_LOOKUPS.try_with(|lookups| {
+            lookups.hits.fetch_add(hits, Ordering::Relaxed);
+            lookups.misses.fetch_add(misses, Ordering::Relaxed);
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for StatsCache {
+    async fn get(&self, key: &str) -> Option<Arc<str>> {
+        let value = self.inner.get(key).await;
+        let hit = u64::from(value.is_some());
+        self.record(hit, 1 - hit);
+        value
+    }
+
+    async fn set(
+        &self,
+        key: String,
+        value: Arc<str>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.inner.set(key, value, ttl).await
+    }
+
+    async fn delete(&self, key: &str) -> crate::error::Result<()> {
+        self.inner.delete(key).await
+    }
+
+    async fn clear(&self) -> crate::error::Result<()> {
+        self.inner.clear().await
+    }
+
+    async fn exists(&self, key: &str) -> bool {
+        self.inner.exists(key).await
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Vec<Option<Arc<str>>> {
+        let values = self.inner.get_many(keys).await;
+        let hits = values.iter().filter(|value| value.is_some()).count() as u64;
+        self.record(hits, values.len() as u64 - hits);
+        values
+    }
+
+    async fn set_many(
+        &self,
+        entries: Vec<(String, Arc<str>)>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.inner.set_many(entries, ttl).await
+    }
+
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        let value = self.inner.get_bytes(key).await;
+        let hit = u64::from(value.is_some());
+        self.record(hit, 1 - hit);
+        value
+    }
+
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.inner.set_bytes(key, value, ttl).await
+    }
+
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        self.inner.try_acquire_lease(key, holder, ttl).await
+    }
+
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        self.inner.release_lease(key, holder).await
+    }
+
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        self.inner.increment_counter(key, member).await
+    }
+
+    async fn top_counters(
+        &self,
+        key: &str,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, u64)>> {
+        self.inner.top_counters(key, limit).await
+    }
+
+    async fn flush(&self) -> crate::error::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn check_integrity(&self) -> crate::error::Result<CacheIntegrity> {
+        self.inner.check_integrity().await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            ..self.inner.stats().await
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(all(test, feature = "cache-memory"))]
+mod tests {
+    use super::*;
+    use crate::cache::memory::MemoryCache;
+
+    #[tokio::test]
+    async fn test_stats_cache_counts_hits_and_misses() {
+        let cache = StatsCache::new(Arc::new(MemoryCache::new(10)));
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+
+        assert!(cache.get("key").await.is_some());
+        assert!(cache.get("key").await.is_some());
+        assert!(cache.get("missing").await.is_none());
+        // Existence checks are not lookups
+        assert!(cache.exists("key").await);
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert_eq!(stats.hit_rate(), Some(2.0 / 3.0));
+
+        // Every key of a batch lookup counts
+        let keys = ["key".to_string(), "missing".to_string()];
+        assert_eq!(cache.get_many(&keys).await.len(), 2);
+        let stats = cache.stats().await;
+        assert_eq!((stats.hits, stats.misses), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn test_track_lookups_counts_one_request() {
+        let cache = StatsCache::new(Arc::new(MemoryCache::new(10)));
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+        // Outside any request: only the global counters move
+        assert!(cache.get("missing").await.is_none());
+
+        let ((), lookups) = track_lookups(async {
+            assert!(cache.get("key").await.is_some());
+        })
+        .await;
+        assert_eq!((lookups.hits(), lookups.misses()), (1, 0));
+        assert_eq!(lookups.outcome(), "hit");
+
+        let ((), lookups) = track_lookups(async {
+            assert!(cache.get("key").await.is_some());
+            assert!(cache.get("missing").await.is_none());
+        })
+        .await;
+        assert_eq!(lookups.outcome(), "partial");
+        assert_eq!(track_lookups(async {}).await.1.outcome(), "none");
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_stats_cache_passes_through_leases() {
+        let cache = StatsCache::new(Arc::new(MemoryCache::new(10)));
+        let ttl = Duration::from_secs(5);
+        assert!(cache.try_acquire_lease("lease", "a", ttl).await.unwrap());
+        assert!(!cache.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        assert_eq!(cache.stats().await.hits, 0);
+    }
+
+    #[test]
+    fn test_cache_stats_display() {
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            evictions: Some(2),
+            entries: Some(10),
+            bytes: None,
+            errors: None,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 hits, 1 misses (75.0% hit rate), 2 evictions, 10 entries"
+        );
+        assert_eq!(CacheStats::default().to_string(), "0 hits, 0 misses");
+    }
+}
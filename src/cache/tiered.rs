@@ -0,0 +1,360 @@
+//! Tiered cache implementation
+//!
+//! A process-local memory cache (L1) in front of a shared cache (L2, usually
+//! Redis), so repeated lookups of hot entries skip the network round-trip.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::memory::MemoryCache;
+use super::{Cache, CacheIntegrity, CacheStats};
+
+/// Two-level cache: `MemoryCache` L1 over a shared L2
+///
+/// Reads go through L1 to L2 and copy L2 hits into L1; writes and deletes go
+/// to L2 first, then L1. Entries written by other replicas only reach L1
+/// through a read, and L1 never holds an entry longer than `l1_ttl`, which
+/// bounds how stale a replica's view of a shared entry can get.
+///
+/// Leases and counters coordinate replicas, so they bypass L1 entirely.
+pub struct TieredCache {
+    l1: MemoryCache,
+    l2: Box<dyn Cache>,
+    /// Upper bound on the lifetime of an L1 entry
+    l1_ttl: Duration,
+}
+
+impl TieredCache {
+    /// Create a tiered cache
+    ///
+    /// # Arguments
+    ///
+    /// * `l1` - Process-local cache consulted first
+    /// * `l2` - Shared cache holding the authoritative entries
+    /// * `l1_ttl` - Longest time an entry is served from L1 without
+    ///   consulting L2
+    #[must_use]
+    pub fn new(l1: MemoryCache, l2: Box<dyn Cache>, l1_ttl: Duration) -> Self {
+        Self { l1, l2, l1_ttl }
+    }
+
+    /// TTL of the L1 copy of an entry stored with `ttl`
+    fn l1_ttl_for(&self, ttl: Option<Duration>) -> Duration {
+        ttl.map_or(self.l1_ttl, |ttl| ttl.min(self.l1_ttl))
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for TieredCache {
+    async fn get(&self, key: &str) -> Option<Arc<str>> {
+        if let Some(value) = self.l1.get(key).await {
+            tracing::trace!(cache_type = "tiered", key = %key, "L1 hit");
+            return Some(value);
+        }
+        let value = self.l2.get(key).await?;
+        tracing::trace!(cache_type = "tiered", key = %key, "L2 hit");
+        // L1 is best-effort: a failed copy only costs the next lookup a round-trip
+        let _ = self
+            .l1
+            .set(key.to_string(), Arc::clone(&value), Some(self.l1_ttl))
+            .await;
+        Some(value)
+    }
+
+    async fn set(
+        &self,
+        key: String,
+        value: Arc<str>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.l2.set(key.clone(), Arc::clone(&value), ttl).await?;
+        self.l1.set(key, value, Some(self.l1_ttl_for(ttl))).await
+    }
+
+    async fn delete(&self, key: &str) -> crate::error::Result<()> {
+        self.l2.delete(key).await?;
+        self.l1.delete(key).await
+    }
+
+    async fn clear(&self) -> crate::error::Result<()> {
+        self.l2.clear().await?;
+        self.l1.clear().await
+    }
+
+    async fn exists(&self, key: &str) -> bool {
+        self.l1.exists(key).await || self.l2.exists(key).await
+    }
+
+    async fn get_many(&self, keys: &[String]) -> Vec<Option<Arc<str>>> {
+        let mut values = self.l1.get_many(keys).await;
+        let missing: Vec<usize> = (0..keys.len()).filter(|&i| values[i].is_none()).collect();
+        if missing.is_empty() {
+            return values;
+        }
+        let missing_keys: Vec<String> = missing.iter().map(|&i| keys[i].clone()).collect();
+        let found = self.l2.get_many(&missing_keys).await;
+        let mut copies = Vec::new();
+        for (i, value) in missing.into_iter().zip(found) {
+            if let Some(value) = value {
+                copies.push((keys[i].clone(), Arc::clone(&value)));
+                values[i] = Some(value);
+            }
+        }
+        // L1 is best-effort: a failed copy only costs the next lookup a round-trip
+        let _ = self.l1.set_many(copies, Some(self.l1_ttl)).await;
+        values
+    }
+
+    async fn set_many(
+        &self,
+        entries: Vec<(String, Arc<str>)>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.l2.set_many(entries.clone(), ttl).await?;
+        self.l1.set_many(entries, Some(self.l1_ttl_for(ttl))).await
+    }
+
+    async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+        if let Some(value) = self.l1.get_bytes(key).await {
+            return Some(value);
+        }
+        let value = self.l2.get_bytes(key).await?;
+        let _ = self
+            .l1
+            .set_bytes(key.to_string(), Arc::clone(&value), Some(self.l1_ttl))
+            .await;
+        Some(value)
+    }
+
+    async fn set_bytes(
+        &self,
+        key: String,
+        value: Arc<[u8]>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.l2
+            .set_bytes(key.clone(), Arc::clone(&value), ttl)
+            .await?;
+        self.l1
+            .set_bytes(key, value, Some(self.l1_ttl_for(ttl)))
+            .await
+    }
+
+    async fn try_acquire_lease(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::error::Result<bool> {
+        self.l2.try_acquire_lease(key, holder, ttl).await
+    }
+
+    async fn release_lease(&self, key: &str, holder: &str) -> crate::error::Result<()> {
+        self.l2.release_lease(key, holder).await
+    }
+
+    async fn increment_counter(&self, key: &str, member: &str) -> crate::error::Result<()> {
+        self.l2.increment_counter(key, member).await
+    }
+
+    async fn top_counters(
+        &self,
+        key: &str,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, u64)>> {
+        self.l2.top_counters(key, limit).await
+    }
+
+    async fn flush(&self) -> crate::error::Result<()> {
+        self.l1.flush().await?;
+        self.l2.flush().await
+    }
+
+    async fn check_integrity(&self) -> crate::error::Result<CacheIntegrity> {
+        self.l2.check_integrity().await
+    }
+
+    /// The shared tier's statistics; L1 copies are not separate entries
+    async fn stats(&self) -> CacheStats {
+        self.l2.stats().await
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tiered cache over a memory L2 that the test can also reach directly,
+    /// standing in for another replica writing to Redis
+    fn tiered(l1_ttl: Duration) -> (TieredCache, Arc<MemoryCache>) {
+        let l2 = Arc::new(MemoryCache::new(100));
+        let cache = TieredCache::new(
+            MemoryCache::new(100),
+            Box::new(SharedL2(Arc::clone(&l2))),
+            l1_ttl,
+        );
+        (cache, l2)
+    }
+
+    struct SharedL2(Arc<MemoryCache>);
+
+    #[async_trait::async_trait]
+    impl Cache for SharedL2 {
+        async fn get(&self, key: &str) -> Option<Arc<str>> {
+            self.0.get(key).await
+        }
+
+        async fn set(
+            &self,
+            key: String,
+            value: Arc<str>,
+            ttl: Option<Duration>,
+        ) -> crate::error::Result<()> {
+            self.0.set(key, value, ttl).await
+        }
+
+        async fn delete(&self, key: &str) -> crate::error::Result<()> {
+            self.0.delete(key).await
+        }
+
+        async fn clear(&self) -> crate::error::Result<()> {
+            self.0.clear().await
+        }
+
+        async fn exists(&self, key: &str) -> bool {
+            self.0.exists(key).await
+        }
+
+        async fn get_bytes(&self, key: &str) -> Option<Arc<[u8]>> {
+            self.0.get_bytes(key).await
+        }
+
+        async fn set_bytes(
+            &self,
+            key: String,
+            value: Arc<[u8]>,
+            ttl: Option<Duration>,
+        ) -> crate::error::Result<()> {
+            self.0.set_bytes(key, value, ttl).await
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_writes_both_levels() {
+        let (cache, l2) = tiered(Duration::from_mins(1));
+
+        cache
+            .set("key".to_string(), "value".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(cache.l1.get("key").await.as_deref(), Some("value"));
+        assert_eq!(l2.get("key").await.as_deref(), Some("value"));
+
+        cache.delete("key").await.unwrap();
+        assert!(cache.l1.get("key").await.is_none());
+        assert!(l2.get("key").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_populates_l1_on_l2_hit() {
+        let (cache, l2) = tiered(Duration::from_mins(1));
+
+        l2.set("key".to_string(), "from l2".into(), None)
+            .await
+            .unwrap();
+        assert!(cache.l1.get("key").await.is_none());
+        assert_eq!(cache.get("key").await.as_deref(), Some("from l2"));
+        assert_eq!(cache.l1.get("key").await.as_deref(), Some("from l2"));
+
+        // Later reads are served from L1 even if L2 loses the entry
+        l2.delete("key").await.unwrap();
+        assert_eq!(cache.get("key").await.as_deref(), Some("from l2"));
+        assert!(cache.exists("key").await);
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_get_many_reads_l2_for_l1_misses() {
+        let (cache, l2) = tiered(Duration::from_mins(1));
+
+        cache
+            .set_many(vec![("a".to_string(), "from l1".into())], None)
+            .await
+            .unwrap();
+        l2.set("b".to_string(), "from l2".into(), None)
+            .await
+            .unwrap();
+        let keys = ["a".to_string(), "b".to_string(), "c".to_string()];
+        let values = cache.get_many(&keys).await;
+        assert_eq!(values[0].as_deref(), Some("from l1"));
+        assert_eq!(values[1].as_deref(), Some("from l2"));
+        assert_eq!(values[2], None);
+        assert_eq!(cache.l1.get("b").await.as_deref(), Some("from l2"));
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_bytes() {
+        let (cache, l2) = tiered(Duration::from_mins(1));
+        let value: Arc<[u8]> = Arc::from(&[0x1f, 0x8b, 0xff][..]);
+
+        cache
+            .set_bytes("key".to_string(), Arc::clone(&value), None)
+            .await
+            .unwrap();
+        assert_eq!(l2.get_bytes("key").await, Some(Arc::clone(&value)));
+
+        cache.l1.delete("key").await.unwrap();
+        assert_eq!(cache.get_bytes("key").await, Some(Arc::clone(&value)));
+        assert_eq!(cache.l1.get_bytes("key").await, Some(value));
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_l1_copies_expire() {
+        let (cache, l2) = tiered(Duration::from_millis(50));
+
+        l2.set("key".to_string(), "old".into(), None).await.unwrap();
+        assert_eq!(cache.get("key").await.as_deref(), Some("old"));
+
+        // Another replica updates L2; the stale L1 copy expires on its own
+        l2.set("key".to_string(), "new".into(), None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(cache.get("key").await.as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn test_l1_ttl_is_capped() {
+        let (cache, _) = tiered(Duration::from_mins(1));
+        assert_eq!(cache.l1_ttl_for(None), Duration::from_mins(1));
+        assert_eq!(
+            cache.l1_ttl_for(Some(Duration::from_hours(1))),
+            Duration::from_mins(1)
+        );
+        assert_eq!(
+            cache.l1_ttl_for(Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tiered_cache_counters_and_leases_use_l2() {
+        let (cache, l2) = tiered(Duration::from_mins(1));
+
+        cache.increment_counter("hits", "serde").await.unwrap();
+        assert_eq!(
+            l2.top_counters("hits", 10).await.unwrap(),
+            vec![("serde".to_string(), 1)]
+        );
+
+        let ttl = Duration::from_secs(5);
+        assert!(cache.try_acquire_lease("lease", "a", ttl).await.unwrap());
+        assert!(!l2.try_acquire_lease("lease", "b", ttl).await.unwrap());
+        assert!(cache.l1.get("lease").await.is_none());
+    }
+}
@@ -8,11 +8,11 @@ use std::path::PathBuf;
 pub enum Commands {
     /// Start the server
     Serve {
-        /// Transport mode [stdio, http, sse, hybrid]
+        /// Transport mode [stdio, http, sse, hybrid, all]
         #[arg(short, long)]
         mode: Option<String>,
 
-        /// Listen host
+        /// Listen host, or a comma-separated list of hosts
         #[arg(long)]
         host: Option<String>,
 
@@ -87,6 +87,11 @@ pub enum Commands {
         /// Overwrite existing file
         #[arg(short, long)]
         force: bool,
+
+        /// Upgrade an existing config file instead of generating a new one:
+        /// new fields get their defaults, user values and comments are kept
+        #[arg(long, value_name = "EXISTING")]
+        upgrade: Option<PathBuf>,
     },
 
     /// Test tool
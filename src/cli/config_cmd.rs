@@ -1,6 +1,8 @@
 //! Config command implementation
 
-use std::path::PathBuf;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Generate configuration file command
 pub fn run_config_command(output: &PathBuf, force: bool) -> Result<(), Box<dyn std::error::Error>> {
@@ -22,3 +24,265 @@ pub fn run_config_command(output: &PathBuf, force: bool) -> Result<(), Box<dyn s
 
     Ok(())
 }
+
+/// Upgrade an existing configuration file to the current release.
+///
+/// Fields added since the file was written are filled in with their defaults,
+/// while user values, comments and formatting are preserved. The upgraded
+/// config is written to `output` and a migration report to
+/// `{output}.migration.md`. Upgrading in place (`output` equal to `existing`)
+/// keeps a copy of the original as `{existing}.bak`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The existing file cannot be read or is not valid TOML
+/// - `output` exists, is not the file being upgraded, and `force` is not set
+/// - The upgraded configuration does not load or validate
+/// - Any file cannot be written
+pub fn run_config_upgrade_command(
+    existing: &Path,
+    output: &Path,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !existing.exists() {
+        return Err(format!("Configuration file not found: {}", existing.display()).into());
+    }
+    let in_place = same_file(existing, output);
+    if output.exists() && !in_place && !force {
+        return Err(format!(
+            "Config file already exists: {}, use --force to overwrite",
+            output.display()
+        )
+        .into());
+    }
+
+    let content = fs::read_to_string(existing)
+        .map_err(|e| format!("Failed to read configuration file: {e}"))?;
+    let (upgraded, report) = upgrade_config(&content)?;
+
+    if in_place {
+        let backup = append_extension(existing, "bak");
+        fs::copy(existing, &backup).map_err(|e| format!("Failed to back up config file: {e}"))?;
+        println!("Original config backed up to: {}", backup.display());
+    }
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+    fs::write(output, upgraded).map_err(|e| format!("Failed to write config file: {e}"))?;
+    let report_path = append_extension(output, "migration.md");
+    fs::write(&report_path, report.to_string())
+        .map_err(|e| format!("Failed to write migration report: {e}"))?;
+
+    println!("Config file upgraded: {}", output.display());
+    println!(
+        "{} field(s) added, {} unrecognized, {} conflicting",
+        report.added.len(),
+        report.unknown.len(),
+        report.conflicts.len()
+    );
+    println!("Migration report: {}", report_path.display());
+
+    Ok(())
+}
+
+/// Changes made (or not made) while upgrading a configuration file
+#[derive(Debug, Default)]
+pub(crate) struct UpgradeReport {
+    /// Fields added with their default value, as `path = value`
+    pub(crate) added: Vec<String>,
+    /// Fields the current release does not know; kept untouched
+    pub(crate) unknown: Vec<String>,
+    /// Fields whose shape differs from the current release; kept untouched
+    pub(crate) conflicts: Vec<String>,
+}
+
+impl fmt::Display for UpgradeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Config migration report (crates-docs {})\n",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        let sections = [
+            ("Added with default values", &self.added),
+            (
+                "Not recognized by this release (kept, ignored at runtime)",
+                &self.unknown,
+            ),
+            (
+                "Kept but differ in shape from the current format (please review)",
+                &self.conflicts,
+            ),
+        ];
+        for (title, entries) in sections {
+            writeln!(f, "## {title}\n")?;
+            if entries.is_empty() {
+                writeln!(f, "None.\n")?;
+                continue;
+            }
+            for entry in entries {
+                writeln!(f, "- `{entry}`")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge the current defaults into an existing configuration document.
+///
+/// Returns the upgraded document text and the report of what changed. The
+/// result is checked to load and validate like a config read at startup.
+pub(crate) fn upgrade_config(content: &str) -> Result<(String, UpgradeReport), String> {
+    let mut doc = content
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| format!("Failed to parse configuration file: {e}"))?;
+    let defaults = toml::to_string_pretty(&crate::config::AppConfig::default())
+        .map_err(|e| format!("Failed to serialize default configuration: {e}"))?
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| format!("Failed to parse default configuration: {e}"))?;
+
+    let mut report = UpgradeReport::default();
+    merge_defaults(doc.as_table_mut(), defaults.as_table(), "", &mut report);
+
+    let upgraded = doc.to_string();
+    let config: crate::config::AppConfig = toml::from_str(&upgraded)
+        .map_err(|e| format!("Upgraded configuration does not load: {e}"))?;
+    config
+        .validate()
+        .map_err(|e| format!("Upgraded configuration is invalid: {e}"))?;
+    Ok((upgraded, report))
+}
+
+/// Recursively add keys of `defaults` missing from `table`, recording every
+/// difference in `report`
+fn merge_defaults(
+    table: &mut dyn toml_edit::TableLike,
+    defaults: &dyn toml_edit::TableLike,
+    prefix: &str,
+    report: &mut UpgradeReport,
+) {
+    let path_of = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+
+    for (key, default) in defaults.iter() {
+        let path = path_of(key);
+        match table.get_mut(key) {
+            None => {
+                report_added(&path, default, report);
+                table.insert(key, default.clone());
+            }
+            Some(existing) => match (existing.as_table_like_mut(), default.as_table_like()) {
+                (Some(existing), Some(default)) => {
+                    merge_defaults(existing, default, &path, report);
+                }
+                (None, None) => {}
+                _ => report.conflicts.push(path),
+            },
+        }
+    }
+
+    for (key, _) in table.iter() {
+        if !defaults.contains_key(key) {
+            report.unknown.push(path_of(key));
+        }
+    }
+}
+
+/// Record an added item, listing each leaf value of an added table
+fn report_added(path: &str, item: &toml_edit::Item, report: &mut UpgradeReport) {
+    if let Some(table) = item.as_table_like() {
+        for (key, child) in table.iter() {
+            report_added(&format!("{path}.{key}"), child, report);
+        }
+    } else {
+        let value = item.to_string();
+        report.added.push(format!("{path} = {}", value.trim()));
+    }
+}
+
+/// Whether two paths name the same file, even if spelled differently
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// `config.toml` + `bak` -> `config.toml.bak`
+fn append_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_CONFIG: &str = r#"# My server
+[server]
+name = "my-docs"   # custom name
+host = "0.0.0.0"
+legacy_flag = true
+
+[cache]
+cache_type = "memory"
+"#;
+
+    #[test]
+    fn test_upgrade_preserves_values_and_comments() {
+        let (upgraded, report) = upgrade_config(OLD_CONFIG).unwrap();
+        assert!(upgraded.starts_with("# My server\n[server]\nname = \"my-docs\"   # custom name"));
+        assert!(upgraded.contains("host = \"0.0.0.0\""));
+        assert!(upgraded.contains("[journal]"));
+
+        let config: crate::config::AppConfig = toml::from_str(&upgraded).unwrap();
+        assert_eq!(config.server.name, "my-docs");
+        assert_eq!(config.server.host, "0.0.0.0");
+
+        assert!(report.added.iter().any(|a| a.starts_with("server.port = ")));
+        assert!(report
+            .added
+            .iter()
+            .any(|a| a.starts_with("journal.enabled = false")));
+        assert!(!report.added.iter().any(|a| a.starts_with("server.name")));
+        assert_eq!(report.unknown, ["server.legacy_flag"]);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_is_idempotent() {
+        let (once, _) = upgrade_config(OLD_CONFIG).unwrap();
+        let (twice, report) = upgrade_config(&once).unwrap();
+        assert_eq!(once, twice);
+        assert!(report.added.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_reports_shape_conflicts() {
+        let content = "cache = \"memory\"\n";
+        // A scalar where a table is expected cannot load afterwards
+        assert!(upgrade_config(content).is_err());
+
+        let mut report = UpgradeReport::default();
+        let mut doc = content.parse::<toml_edit::DocumentMut>().unwrap();
+        let defaults = "[cache]\ncache_type = \"memory\"\n"
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+        merge_defaults(doc.as_table_mut(), defaults.as_table(), "", &mut report);
+        assert_eq!(report.conflicts, ["cache"]);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_invalid_toml() {
+        assert!(upgrade_config("[server\nname = ").is_err());
+    }
+}
@@ -64,14 +64,15 @@ pub fn run_list_api_keys_command(config_path: &Path) -> Result<(), Box<dyn std::
     println!("Status: ENABLED");
     println!();
 
-    if config.auth.api_key.keys.is_empty() {
+    let keys = config.auth.api_key.all_keys()?;
+    if keys.is_empty() {
         println!("No API keys configured.");
         println!("Use 'crates-docs generate-api-key' to create a new key.");
     } else {
-        println!("Configured API keys ({}):", config.auth.api_key.keys.len());
+        println!("Configured API keys ({}):", keys.len());
         println!();
 
-        for (index, key_hash) in config.auth.api_key.keys.iter().enumerate() {
+        for (index, key_hash) in keys.iter().enumerate() {
             let key_type = if key_hash.starts_with("legacy:") {
                 "Legacy Hash"
             } else if key_hash.starts_with("$argon2") {
@@ -80,7 +81,12 @@ pub fn run_list_api_keys_command(config_path: &Path) -> Result<(), Box<dyn std::
                 "Plaintext (Insecure)"
             };
 
-            println!("  [{}] {}", index + 1, key_type);
+            println!(
+                "  [{}] {} (ID: {})",
+                index + 1,
+                key_type,
+                crate::server::auth::ApiKeyConfig::key_id(key_hash, index)
+            );
 
             // Show a truncated version of the hash for identification
             println!("      {}", truncate_hash_for_display(key_hash));
@@ -88,6 +94,9 @@ pub fn run_list_api_keys_command(config_path: &Path) -> Result<(), Box<dyn std::
         }
 
         println!("Configuration:");
+        if let Some(keys_file) = &config.auth.api_key.keys_file {
+            println!("  Keys file: {keys_file}");
+        }
         println!("  Header name: {}", config.auth.api_key.header_name);
         println!("  Query param: {}", config.auth.api_key.query_param_name);
         println!(
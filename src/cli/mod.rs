@@ -17,7 +17,7 @@ use std::path::PathBuf;
 
 pub use api_key_cmd::run_generate_api_key_command;
 pub use commands::Commands;
-pub use config_cmd::run_config_command;
+pub use config_cmd::{run_config_command, run_config_upgrade_command};
 pub use health_cmd::run_health_command;
 pub use list_api_keys_cmd::run_list_api_keys_command;
 pub use revoke_api_key_cmd::run_revoke_api_key_command;
@@ -91,9 +91,14 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Commands::RevokeApiKey { config, key } => {
             run_revoke_api_key_command(&config, &key)?;
         }
-        Commands::Config { output, force } => {
-            run_config_command(&output, force)?;
-        }
+        Commands::Config {
+            output,
+            force,
+            upgrade,
+        } => match upgrade {
+            Some(existing) => run_config_upgrade_command(&existing, &output, force)?,
+            None => run_config_command(&output, force)?,
+        },
         Commands::Test {
             tool,
             crate_name,
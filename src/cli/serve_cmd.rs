@@ -44,22 +44,20 @@ fn init_logging(
     config: &crate::config::AppConfig,
     debug: bool,
     verbose: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<crate::logging::LogLevelHandle, Box<dyn std::error::Error>> {
     // Both `--debug` and the global `--verbose` raise logging to debug level
     // (the only level above the default `info` used by this server), so the
     // documented `--verbose` flag has a real effect instead of being ignored.
-    if debug || verbose {
+    let handle = if debug || verbose {
         let debug_config = crate::config::LoggingConfig {
             level: "debug".to_string(),
             ..config.logging.clone()
         };
-        crate::init_logging_with_config(&debug_config)
-            .map_err(|e| format!("Failed to initialize logging system: {e}"))?;
+        crate::logging::init_reloadable_logging(&debug_config)
     } else {
-        crate::init_logging_with_config(&config.logging)
-            .map_err(|e| format!("Failed to initialize logging system: {e}"))?;
-    }
-    Ok(())
+        crate::logging::init_reloadable_logging(&config.logging)
+    };
+    handle.map_err(|e| format!("Failed to initialize logging system: {e}").into())
 }
 
 fn start_config_reloader(config_path: &std::path::Path, server: &CratesDocsServer) {
@@ -73,6 +71,8 @@ fn start_config_reloader(config_path: &std::path::Path, server: &CratesDocsServe
                 config_path.display()
             );
 
+            // Clones share the tool registry of the running server
+            let server = server.clone();
             tokio::spawn(async move {
                 let mut check_interval = interval(Duration::from_secs(1));
 
@@ -95,9 +95,12 @@ fn start_config_reloader(config_path: &std::path::Path, server: &CratesDocsServe
                             for change_desc in changes {
                                 tracing::info!(" - {}", change_desc);
                             }
+                            if let Some(new_config) = change.new_config() {
+                                server.apply_disabled_tools(&new_config.server.disabled_tools);
+                            }
                             tracing::warn!(
-                                "Detected configuration changes are NOT applied to the already-running server. \
-                                 Restart the server for these changes to take effect."
+                                "Detected configuration changes other than server.disabled_tools are NOT applied \
+                                 to the already-running server. Restart the server for these changes to take effect."
                             );
                             tracing::warn!(
                                 "Security note: API key and OAuth changes (including key removals) do NOT take \
@@ -128,9 +131,8 @@ async fn run_server_by_mode(
         }
         "http" => {
             tracing::info!(
-                "Using HTTP transport mode, listening on {}:{}",
-                server.config().server.host,
-                server.config().server.port
+                "Using HTTP transport mode, listening on {}",
+                server.config().server.listen_addresses().join(", ")
             );
             transport::run_hyper_server(server, HyperServerConfig::http())
                 .await
@@ -138,9 +140,8 @@ async fn run_server_by_mode(
         }
         "sse" => {
             tracing::info!(
-                "Using SSE transport mode, listening on {}:{}",
-                server.config().server.host,
-                server.config().server.port
+                "Using SSE transport mode, listening on {}",
+                server.config().server.listen_addresses().join(", ")
             );
             transport::run_hyper_server(server, HyperServerConfig::sse())
                 .await
@@ -148,14 +149,22 @@ async fn run_server_by_mode(
         }
         "hybrid" => {
             tracing::info!(
-                "Using hybrid transport mode (HTTP + SSE), listening on {}:{}",
-                server.config().server.host,
-                server.config().server.port
+                "Using hybrid transport mode (HTTP + SSE), listening on {}",
+                server.config().server.listen_addresses().join(", ")
             );
             transport::run_hyper_server(server, HyperServerConfig::hybrid())
                 .await
                 .map_err(|e| format!("Failed to start hybrid server: {e}"))?;
         }
+        "all" => {
+            tracing::info!(
+                "Using all transport modes (stdio + HTTP + SSE), listening on {}",
+                server.config().server.listen_addresses().join(", ")
+            );
+            transport::run_all_server(server)
+                .await
+                .map_err(|e| format!("Failed to start stdio + hybrid server: {e}"))?;
+        }
         _ => {
             return Err(format!("Unknown transport mode: {transport_mode}").into());
         }
@@ -198,7 +207,7 @@ pub async fn run_serve_command(
 
     let transport_mode = &config.server.transport_mode;
 
-    init_logging(&config, debug, verbose)?;
+    let log_level = init_logging(&config, debug, verbose)?;
 
     tracing::info!(
         "Starting Crates Docs MCP Server v{}",
@@ -207,16 +216,50 @@ pub async fn run_serve_command(
 
     let server: CratesDocsServer = CratesDocsServer::new_async(config.clone())
         .await
-        .map_err(|e| format!("Failed to create server: {e}"))?;
+        .map_err(|e| format!("Failed to create server: {e}"))?
+        .with_log_level_handle(log_level);
 
     let mode_str = transport_mode.to_lowercase();
-    let should_enable_reload = matches!(mode_str.as_str(), "http" | "sse" | "hybrid");
+    let should_enable_reload = matches!(mode_str.as_str(), "http" | "sse" | "hybrid" | "all");
 
     if should_enable_reload && config_path.exists() {
         start_config_reloader(config_path, &server);
     }
 
-    run_server_by_mode(&server, transport_mode).await
+    server
+        .check_cache_integrity()
+        .await
+        .map_err(|e| format!("Cache integrity check failed: {e}"))?;
+
+    // A journal that cannot be read must not keep the server down
+    if let Err(e) = server.recover_interrupted_requests().await {
+        tracing::warn!("Failed to read request journal: {e}");
+    }
+
+    // Warm in the background: the server is usable (if slower) meanwhile
+    if !config.cache.warmup.is_empty() {
+        let server = server.clone();
+        tokio::spawn(async move {
+            match server.warm_cache().await {
+                Ok(Some(report)) => tracing::info!("Cache warm-up finished: {report}"),
+                Ok(None) => tracing::info!("Cache warm-up skipped: another replica is warming"),
+                Err(e) => tracing::warn!("Cache warm-up failed: {e}"),
+            }
+        });
+    }
+
+    let result = tokio::select! {
+        result = run_server_by_mode(&server, transport_mode) => result,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("Received shutdown signal");
+            Ok(())
+        }
+    };
+
+    if let Err(e) = server.shutdown().await {
+        tracing::warn!("Failed to flush cache on shutdown: {}", e);
+    }
+    result
 }
 
 /// Load configuration
@@ -251,10 +294,10 @@ fn load_config(
 
     // Only override config file when command line arguments are explicitly provided
     if let Some(h) = host {
-        config.server.host = h;
+        config.server.set_bind_hosts(&h);
         tracing::info!(
             "Command line argument overrides host: {}",
-            config.server.host
+            config.server.bind_hosts().join(", ")
         );
     }
     if let Some(p) = port {
@@ -30,6 +30,12 @@
 
 use crate::cache::CacheConfig;
 use crate::server::auth::{AuthConfig, OAuthConfig};
+use crate::server::event_store::EventStoreConfig;
+use crate::server::journal::JournalConfig;
+use crate::tools::docs::provider::DocsConfig;
+use crate::tools::docs::registry::RegistryConfig;
+use crate::tools::docs::upstream::UpstreamConfig;
+use crate::utils::chaos::ChaosConfig;
 use rust_mcp_sdk::schema::{Icon, IconTheme};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -64,6 +70,14 @@ const DEFAULT_SERVER_MAX_CONNECTIONS: usize = 100;
 const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
 /// Default response timeout in seconds (60 seconds)
 const DEFAULT_RESPONSE_TIMEOUT_SECS: u64 = 60;
+/// Default interval between checks for new versions of subscribed crates (15 minutes)
+const DEFAULT_SUBSCRIPTION_POLL_INTERVAL_SECS: u64 = 900;
+/// Shortest allowed interval between checks for new versions (1 minute)
+const MIN_SUBSCRIPTION_POLL_INTERVAL_SECS: u64 = 60;
+/// Default longest time tasks and their results are kept (1 hour)
+const DEFAULT_TASK_TTL_SECS: u64 = 3600;
+/// Default number of items per page of MCP list results
+const DEFAULT_PAGE_SIZE: usize = 100;
 
 // Cache/Rate limit defaults
 
@@ -75,6 +89,8 @@ const DEFAULT_CACHE_DEFAULT_TTL_SECS: u64 = 3600;
 const DEFAULT_RATE_LIMIT_PER_SECOND: u32 = 100;
 /// Default concurrent request limit (50 requests)
 const DEFAULT_CONCURRENT_REQUEST_LIMIT: usize = 50;
+/// Default per-client burst of tool calls (20 calls)
+const DEFAULT_CLIENT_RATE_LIMIT_BURST: u32 = 20;
 
 // File upload defaults
 
@@ -94,18 +110,30 @@ const DEFAULT_MAX_FILES: usize = 10;
 /// - `auth`: Authentication configuration (OAuth and API Key)
 /// - `logging`: Logging configuration
 /// - `performance`: Performance configuration
+/// - `docs`: Documentation providers (upstream sources and fallback order)
+/// - `registry`: Package registry the metadata tools query (crates.io or an alternative)
+/// - `upstream`: Upstream service credentials (crates.io API token)
+/// - `journal`: In-flight request journal for crash diagnosis
+/// - `event_store`: Where SSE events are kept for stream resumption
+/// - `chaos`: Upstream failure injection for resilience testing (development only)
 ///
 /// # Hot Reload Support
 ///
 /// The following configuration items support hot reload (runtime update without restart):
 /// - `logging` section: All fields
 /// - `auth` section: All fields (including API Key and OAuth)
-/// - `cache` section: TTL-related fields (`default_ttl`, `crate_docs_ttl_secs`, `item_docs_ttl_secs`, `search_results_ttl_secs`)
+/// - `cache` section: TTL-related fields (`default_ttl`, `crate_docs_ttl_secs`, `item_docs_ttl_secs`, `search_results_ttl_secs`, `versioned_docs_ttl_secs`, `missing_crate_ttl_secs`, `stale_while_revalidate_secs`, `ttl_jitter_ratio`)
 /// - `performance` section: `rate_limit_per_second`, `concurrent_request_limit`, `enable_metrics`, `enable_response_compression`
 ///
 /// The following configuration items **do not** support hot reload (require server restart):
-/// - `server` section: All fields (host, port, `transport_mode`, `max_connections`, etc.)
-/// - `cache` section: `cache_type`, `memory_size`, `redis_url` (cache initialization parameters)
+/// - `server` section: All fields (host, hosts, port, `transport_mode`, `max_connections`, etc.)
+/// - `docs` section: All fields (the provider chain is built at startup)
+/// - `registry` section: All fields (installed at startup)
+/// - `upstream` section: All fields (handed to the document service at startup)
+/// - `journal` section: All fields (the journal is opened at startup)
+/// - `event_store` section: All fields (the store is opened at startup)
+/// - `chaos` section: All fields (installed in the HTTP client at startup)
+/// - `cache` section: `cache_type`, `memory_size`, `memory_max_bytes`, `redis_url`, `redis_l1_ttl_secs`, `redis_key_prefix`, `sqlite_path`, `sqlite_max_size_mb` (cache initialization parameters)
 /// - `performance` section: `http_client_*`, `cache_max_size`, `cache_default_ttl_secs`, `metrics_port`
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct AppConfig {
@@ -132,6 +160,30 @@ pub struct AppConfig {
     /// Performance configuration
     #[serde(default)]
     pub performance: PerformanceConfig,
+
+    /// Documentation source configuration
+    #[serde(default)]
+    pub docs: DocsConfig,
+
+    /// Package registry configuration
+    #[serde(default)]
+    pub registry: RegistryConfig,
+
+    /// Upstream service credentials
+    #[serde(default)]
+    pub upstream: UpstreamConfig,
+
+    /// In-flight request journal for crash diagnosis
+    #[serde(default)]
+    pub journal: JournalConfig,
+
+    /// Where SSE events are kept for stream resumption
+    #[serde(default)]
+    pub event_store: EventStoreConfig,
+
+    /// Upstream failure injection for resilience testing (development only)
+    #[serde(default)]
+    pub chaos: ChaosConfig,
 }
 
 /// Server configuration
@@ -143,6 +195,7 @@ pub struct AppConfig {
 /// Reason: These configurations involve server listening socket, transport layer initialization and other core parameters,
 /// runtime changes may cause connection interruption or state inconsistency.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // independent on/off switches from the config file
 pub struct ServerConfig {
     /// Server name
     #[serde(default = "default_server_name")]
@@ -168,6 +221,14 @@ pub struct ServerConfig {
     #[serde(default = "default_server_host")]
     pub host: String,
 
+    /// Addresses to listen on, all with `port`
+    ///
+    /// When non-empty, replaces `host`, e.g. `["127.0.0.1", "192.168.1.5"]`
+    /// or `["::"]`. `::` also accepts IPv4 connections on dual-stack systems,
+    /// so listing both `::` and `0.0.0.0` binds only `::` there.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+
     /// Port
     #[serde(default = "default_server_port")]
     pub port: u16,
@@ -180,7 +241,8 @@ pub struct ServerConfig {
     #[serde(default = "default_server_enable_sse")]
     pub enable_sse: bool,
 
-    /// Enable OAuth authentication
+    /// Enable OAuth authentication with the legacy top-level `oauth` section
+    /// (`auth.oauth.enabled` needs no such switch)
     #[serde(default = "default_server_enable_oauth")]
     pub enable_oauth: bool,
 
@@ -188,14 +250,57 @@ pub struct ServerConfig {
     #[serde(default = "default_server_max_connections")]
     pub max_connections: usize,
 
-    /// Request timeout (seconds)
+    /// Upstream request timeout (seconds)
+    ///
+    /// Bounds each fetch from docs.rs, crates.io and the other upstreams,
+    /// retries included.
     #[serde(default = "default_server_request_timeout_secs")]
     pub request_timeout_secs: u64,
 
     /// Response timeout (seconds)
+    ///
+    /// Bounds the execution of a tool call; a call still running after this
+    /// long fails with a timeout error.
     #[serde(default = "default_server_response_timeout_secs")]
     pub response_timeout_secs: u64,
 
+    /// Interval between checks for new versions of subscribed crates (seconds)
+    ///
+    /// Clients subscribed to `docs://{crate}/latest` are notified when a
+    /// check finds a newer version. At least 60.
+    #[serde(default = "default_server_subscription_poll_interval_secs")]
+    pub subscription_poll_interval_secs: u64,
+
+    /// Items per page of `tools/list`, `resources/list` and the other list
+    /// requests
+    ///
+    /// Longer lists return a `nextCursor` for the next page.
+    #[serde(default = "default_server_page_size")]
+    pub page_size: usize,
+
+    /// Longest time a task-augmented tool call and its result are kept
+    /// (seconds)
+    ///
+    /// Clients may ask for a shorter TTL when creating the task.
+    #[serde(default = "default_server_task_ttl_secs")]
+    pub task_ttl_secs: u64,
+
+    /// Tools left out of `tools/list` and refused when called
+    ///
+    /// Applied again when the configuration file is reloaded; the admin tool
+    /// `set_tool_enabled` changes the same state at runtime.
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+
+    /// MCP clients allowed to call admin tools such as `set_log_level`
+    ///
+    /// Entries are `client:<id>` (API key ID, or JWT/OAuth client ID) or
+    /// `user:<id>` (JWT subject or OAuth user). Stdio clients, which started
+    /// the server themselves, are always admins; HTTP clients only when
+    /// listed here, so admin tools are refused over HTTP by default.
+    #[serde(default)]
+    pub admin_clients: Vec<String>,
+
     /// Allowed `Host` header values for DNS-rebinding protection.
     ///
     /// Only enforced when `dns_rebinding_protection` is `true`. Matching is
@@ -229,6 +334,21 @@ pub struct ServerConfig {
     /// defaults with a `*` wildcard will not match.
     #[serde(default = "default_server_dns_rebinding_protection")]
     pub dns_rebinding_protection: bool,
+
+    /// Path prefix of every HTTP route, e.g. `/mcp/crates-docs`
+    ///
+    /// For reverse proxies that forward a sub-path without stripping it. The
+    /// MCP endpoint is then served at `{base_path}/mcp`, likewise SSE, the
+    /// health probes and the OAuth routes. Empty by default.
+    #[serde(default)]
+    pub base_path: String,
+
+    /// Trust the `X-Forwarded-Proto`/`-Host`/`-For` headers
+    ///
+    /// Only enable behind a reverse proxy that sets them: they are used for
+    /// the public OAuth callback URL and the client address in the OAuth logs.
+    #[serde(default)]
+    pub trust_forwarded_headers: bool,
 }
 
 /// Default server version from Cargo.toml
@@ -299,6 +419,18 @@ fn default_server_response_timeout_secs() -> u64 {
     ServerConfig::default().response_timeout_secs
 }
 
+fn default_server_subscription_poll_interval_secs() -> u64 {
+    ServerConfig::default().subscription_poll_interval_secs
+}
+
+fn default_server_page_size() -> usize {
+    ServerConfig::default().page_size
+}
+
+fn default_server_task_ttl_secs() -> u64 {
+    ServerConfig::default().task_ttl_secs
+}
+
 fn default_server_allowed_hosts() -> Vec<String> {
     ServerConfig::default().allowed_hosts
 }
@@ -381,6 +513,14 @@ fn default_perf_concurrent_request_limit() -> usize {
     PerformanceConfig::default().concurrent_request_limit
 }
 
+fn default_perf_client_rate_limit_per_second() -> u32 {
+    PerformanceConfig::default().client_rate_limit_per_second
+}
+
+fn default_perf_client_rate_limit_burst() -> u32 {
+    PerformanceConfig::default().client_rate_limit_burst
+}
+
 fn default_perf_enable_response_compression() -> bool {
     PerformanceConfig::default().enable_response_compression
 }
@@ -454,6 +594,7 @@ pub struct LoggingConfig {
 /// - `cache_max_size`: Cache maximum size
 /// - `cache_default_ttl_secs`: Cache default TTL
 /// - `metrics_port`: Metrics server port
+/// - `client_rate_limit_*`: Per-client tool call rate limit
 ///
 /// Reason: These configurations involve underlying connection pool, cache instance initialization parameters.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -506,6 +647,18 @@ pub struct PerformanceConfig {
     #[serde(default = "default_perf_concurrent_request_limit")]
     pub concurrent_request_limit: usize,
 
+    /// Tool calls per second allowed for each client (0 = unlimited)
+    ///
+    /// Clients are told apart by the API key or OAuth client they
+    /// authenticated with, or else by their MCP session. Limiting by IP
+    /// address has to be done by a reverse proxy in front of the server.
+    #[serde(default = "default_perf_client_rate_limit_per_second")]
+    pub client_rate_limit_per_second: u32,
+
+    /// Tool calls a client may make in a burst before being throttled
+    #[serde(default = "default_perf_client_rate_limit_burst")]
+    pub client_rate_limit_burst: u32,
+
     /// Enable response compression
     #[serde(default = "default_perf_enable_response_compression")]
     pub enable_response_compression: bool,
@@ -523,6 +676,175 @@ pub struct PerformanceConfig {
     pub metrics_port: u16,
 }
 
+impl ServerConfig {
+    /// Hosts to listen on: `hosts` if set, otherwise `host`
+    ///
+    /// IPv6 brackets are stripped and duplicates removed.
+    #[must_use]
+    pub fn bind_hosts(&self) -> Vec<String> {
+        let configured = if self.hosts.is_empty() {
+            std::slice::from_ref(&self.host)
+        } else {
+            self.hosts.as_slice()
+        };
+        let mut hosts: Vec<String> = Vec::with_capacity(configured.len());
+        for host in configured {
+            let host = host.trim();
+            let host = host
+                .strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host);
+            if !hosts.iter().any(|known| known.eq_ignore_ascii_case(host)) {
+                hosts.push(host.to_string());
+            }
+        }
+        hosts
+    }
+
+    /// `host:port` of every listen address, IPv6 addresses in brackets
+    #[must_use]
+    pub fn listen_addresses(&self) -> Vec<String> {
+        self.bind_hosts()
+            .iter()
+            .map(|host| {
+                if host.contains(':') {
+                    format!("[{host}]:{}", self.port)
+                } else {
+                    format!("{host}:{}", self.port)
+                }
+            })
+            .collect()
+    }
+
+    /// Set the listen hosts from a comma-separated list
+    ///
+    /// A single host goes to `host`; a list goes to `hosts`, with its first
+    /// entry also stored in `host`.
+    pub fn set_bind_hosts(&mut self, list: &str) {
+        let mut hosts: Vec<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        if let Some(first) = hosts.first() {
+            self.host.clone_from(first);
+        }
+        if hosts.len() < 2 {
+            hosts.clear();
+        }
+        self.hosts = hosts;
+    }
+
+    /// Validate the `[server]` section
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field is invalid (e.g., empty hostname, invalid port, etc.)
+    fn validate(&self) -> Result<(), crate::error::Error> {
+        if self.host.is_empty() {
+            return Err(crate::error::Error::config("host", "cannot be empty"));
+        }
+
+        if self.hosts.iter().any(|host| host.trim().is_empty()) {
+            return Err(crate::error::Error::config(
+                "hosts",
+                "entries cannot be empty",
+            ));
+        }
+
+        if self.port == 0 {
+            return Err(crate::error::Error::config("port", "cannot be 0"));
+        }
+
+        if self
+            .base_path
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '?' | '#' | '{' | '}' | '*'))
+        {
+            return Err(crate::error::Error::config(
+                "base_path",
+                format!("'{}' is not a plain URL path", self.base_path),
+            ));
+        }
+
+        if self.max_connections == 0 {
+            return Err(crate::error::Error::config(
+                "max_connections",
+                "cannot be 0",
+            ));
+        }
+
+        if self.page_size == 0 {
+            return Err(crate::error::Error::config("page_size", "cannot be 0"));
+        }
+
+        for (field, secs) in [
+            ("request_timeout_secs", self.request_timeout_secs),
+            ("response_timeout_secs", self.response_timeout_secs),
+            ("task_ttl_secs", self.task_ttl_secs),
+        ] {
+            if secs == 0 {
+                return Err(crate::error::Error::config(field, "cannot be 0"));
+            }
+        }
+
+        if let Some(entry) = self.admin_clients.iter().find(|entry| {
+            !matches!(
+                entry.split_once(':'),
+                Some(("client" | "user", id)) if !id.trim().is_empty()
+            )
+        }) {
+            return Err(crate::error::Error::config(
+                "admin_clients",
+                format!("'{entry}' is not of the form client:<id> or user:<id>"),
+            ));
+        }
+
+        if self.subscription_poll_interval_secs < MIN_SUBSCRIPTION_POLL_INTERVAL_SECS {
+            return Err(crate::error::Error::config(
+                "subscription_poll_interval_secs",
+                format!("must be at least {MIN_SUBSCRIPTION_POLL_INTERVAL_SECS}"),
+            ));
+        }
+
+        // The SDK matches allowlist entries exactly, so a wildcard entry
+        // would reject every request once the allowlists are enforced.
+        if self.dns_rebinding_protection {
+            for (field, entries) in [
+                ("allowed_hosts", &self.allowed_hosts),
+                ("allowed_origins", &self.allowed_origins),
+            ] {
+                if let Some(entry) = entries.iter().find(|entry| entry.contains('*')) {
+                    return Err(crate::error::Error::config(
+                        field,
+                        format!(
+                            "wildcard entry '{entry}' never matches when dns_rebinding_protection is enabled; list exact values"
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // Validate transport mode. Match case-insensitively to stay consistent
+        // with the dispatcher (`run_server_by_mode`) and `TransportMode::from_str`,
+        // which both lowercase the value; otherwise `--mode HTTP` would be
+        // rejected here even though it would dispatch fine.
+        let valid_modes = ["stdio", "http", "sse", "hybrid", "all"];
+        if !valid_modes.contains(&self.transport_mode.to_lowercase().as_str()) {
+            return Err(crate::error::Error::config(
+                "transport_mode",
+                format!(
+                    "Invalid transport mode: {}, valid values: {:?}",
+                    self.transport_mode, valid_modes
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
@@ -534,6 +856,7 @@ impl Default for ServerConfig {
             icons: default_icons(),
             website_url: Some("https://github.com/KingingWang/crates-docs".to_string()),
             host: "127.0.0.1".to_string(),
+            hosts: Vec::new(),
             port: DEFAULT_SERVER_PORT,
             transport_mode: "hybrid".to_string(),
             enable_sse: true,
@@ -541,12 +864,19 @@ impl Default for ServerConfig {
             max_connections: DEFAULT_SERVER_MAX_CONNECTIONS,
             request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
             response_timeout_secs: DEFAULT_RESPONSE_TIMEOUT_SECS,
+            subscription_poll_interval_secs: DEFAULT_SUBSCRIPTION_POLL_INTERVAL_SECS,
+            page_size: DEFAULT_PAGE_SIZE,
+            task_ttl_secs: DEFAULT_TASK_TTL_SECS,
+            disabled_tools: Vec::new(),
+            admin_clients: Vec::new(),
             // Secure defaults: only allow localhost by default
             allowed_hosts: vec!["localhost".to_string(), "127.0.0.1".to_string()],
             allowed_origins: vec!["http://localhost:*".to_string()],
             // Off by default: the exact-match allowlists above (with a `*`
             // wildcard and no ports) would otherwise 403 normal requests.
             dns_rebinding_protection: false,
+            base_path: String::new(),
+            trust_forwarded_headers: false,
         }
     }
 }
@@ -579,6 +909,8 @@ impl Default for PerformanceConfig {
             cache_default_ttl_secs: DEFAULT_CACHE_DEFAULT_TTL_SECS,
             rate_limit_per_second: DEFAULT_RATE_LIMIT_PER_SECOND,
             concurrent_request_limit: DEFAULT_CONCURRENT_REQUEST_LIMIT,
+            client_rate_limit_per_second: 0,
+            client_rate_limit_burst: DEFAULT_CLIENT_RATE_LIMIT_BURST,
             enable_response_compression: true,
             enable_metrics: false,
             metrics_port: 0,
@@ -609,12 +941,22 @@ impl Default for PerformanceConfig {
 pub struct EnvServerConfig {
     /// Server name
     pub name: Option<String>,
-    /// Host address
+    /// Host address, or a comma-separated list of them
     pub host: Option<String>,
     /// Port
     pub port: Option<u16>,
     /// Transport mode
     pub transport_mode: Option<String>,
+    /// Allowed `Host` header values
+    pub allowed_hosts: Option<Vec<String>>,
+    /// Allowed `Origin` header values
+    pub allowed_origins: Option<Vec<String>>,
+    /// DNS rebinding protection
+    pub dns_rebinding_protection: Option<bool>,
+    /// Path prefix of every HTTP route
+    pub base_path: Option<String>,
+    /// Trust `X-Forwarded-*` headers
+    pub trust_forwarded_headers: Option<bool>,
 }
 
 /// Environment variable configuration for logging
@@ -652,6 +994,8 @@ pub struct EnvApiKeyConfig {
     pub enabled: Option<bool>,
     /// List of valid API keys
     pub keys: Option<Vec<String>>,
+    /// File with more API keys
+    pub keys_file: Option<String>,
     /// Header name for API key
     pub header_name: Option<String>,
     /// Query parameter name for API key
@@ -729,36 +1073,7 @@ impl AppConfig {
     ///
     /// Returns an error if configuration is invalid (e.g., empty hostname, invalid port, etc.)
     pub fn validate(&self) -> Result<(), crate::error::Error> {
-        // Validate server configuration
-        if self.server.host.is_empty() {
-            return Err(crate::error::Error::config("host", "cannot be empty"));
-        }
-
-        if self.server.port == 0 {
-            return Err(crate::error::Error::config("port", "cannot be 0"));
-        }
-
-        if self.server.max_connections == 0 {
-            return Err(crate::error::Error::config(
-                "max_connections",
-                "cannot be 0",
-            ));
-        }
-
-        // Validate transport mode. Match case-insensitively to stay consistent
-        // with the dispatcher (`run_server_by_mode`) and `TransportMode::from_str`,
-        // which both lowercase the value; otherwise `--mode HTTP` would be
-        // rejected here even though it would dispatch fine.
-        let valid_modes = ["stdio", "http", "sse", "hybrid"];
-        if !valid_modes.contains(&self.server.transport_mode.to_lowercase().as_str()) {
-            return Err(crate::error::Error::config(
-                "transport_mode",
-                format!(
-                    "Invalid transport mode: {}, valid values: {:?}",
-                    self.server.transport_mode, valid_modes
-                ),
-            ));
-        }
+        self.server.validate()?;
 
         // Validate log level
         let valid_levels = ["trace", "debug", "info", "warn", "error"];
@@ -818,29 +1133,17 @@ impl AppConfig {
             return Err(crate::error::Error::config("cache_max_size", "cannot be 0"));
         }
 
-        // Validate cache configuration.
-        //
-        // Note: the live in-memory cache is sized from `cache.memory_size`
-        // (see `create_cache`), NOT `performance.cache_max_size`. A
-        // `memory_size` of 0 builds a zero-capacity cache that evicts every
-        // entry immediately, silently disabling caching, so reject it here.
-        let valid_cache_types = ["memory", "redis"];
-        if !valid_cache_types.contains(&self.cache.cache_type.as_str()) {
-            return Err(crate::error::Error::config(
-                "cache.cache_type",
-                format!(
-                    "Invalid cache type: {}, valid values: {:?}",
-                    self.cache.cache_type, valid_cache_types
-                ),
-            ));
-        }
-        if self.cache.cache_type == "memory" && self.cache.memory_size == Some(0) {
+        if self.performance.client_rate_limit_per_second > 0
+            && self.performance.client_rate_limit_burst == 0
+        {
             return Err(crate::error::Error::config(
-                "cache.memory_size",
-                "cannot be 0 (this would disable the cache); omit it to use the default",
+                "client_rate_limit_burst",
+                "cannot be 0 when client_rate_limit_per_second is set",
             ));
         }
 
+        self.cache.validate()?;
+
         // Validate OAuth configuration
         if self.server.enable_oauth {
             self.oauth.validate()?;
@@ -853,6 +1156,12 @@ impl AppConfig {
         // never validated.
         self.auth.validate()?;
 
+        self.docs.validate()?;
+        self.registry.validate()?;
+        self.journal.validate(&self.cache.cache_type)?;
+        self.event_store.validate(&self.cache.cache_type)?;
+        self.chaos.validate()?;
+
         Ok(())
     }
 
@@ -887,6 +1196,26 @@ impl AppConfig {
             config.server.transport_mode = Some(mode);
         }
 
+        if let Ok(hosts) = std::env::var("CRATES_DOCS_ALLOWED_HOSTS") {
+            config.server.allowed_hosts = Some(split_env_list(&hosts));
+        }
+
+        if let Ok(origins) = std::env::var("CRATES_DOCS_ALLOWED_ORIGINS") {
+            config.server.allowed_origins = Some(split_env_list(&origins));
+        }
+
+        if let Ok(protection) = std::env::var("CRATES_DOCS_DNS_REBINDING_PROTECTION") {
+            config.server.dns_rebinding_protection = protection.parse().ok();
+        }
+
+        if let Ok(base_path) = std::env::var("CRATES_DOCS_BASE_PATH") {
+            config.server.base_path = Some(base_path);
+        }
+
+        if let Ok(trust) = std::env::var("CRATES_DOCS_TRUST_FORWARDED_HEADERS") {
+            config.server.trust_forwarded_headers = trust.parse().ok();
+        }
+
         // Load logging configuration from environment variables
         if let Ok(level) = std::env::var("CRATES_DOCS_LOG_LEVEL") {
             config.logging.level = Some(level);
@@ -907,13 +1236,11 @@ impl AppConfig {
             }
 
             if let Ok(keys) = std::env::var("CRATES_DOCS_API_KEYS") {
-                config.auth_api_key.keys = Some(
-                    keys.split(',')
-                        .map(str::trim)
-                        .filter(|s| !s.is_empty())
-                        .map(ToOwned::to_owned)
-                        .collect(),
-                );
+                config.auth_api_key.keys = Some(split_env_list(&keys));
+            }
+
+            if let Ok(keys_file) = std::env::var("CRATES_DOCS_API_KEYS_FILE") {
+                config.auth_api_key.keys_file = Some(keys_file);
             }
 
             if let Ok(header_name) = std::env::var("CRATES_DOCS_API_KEY_HEADER") {
@@ -958,7 +1285,7 @@ impl AppConfig {
                 config.server.name = name;
             }
             if let Some(host) = env.server.host {
-                config.server.host = host;
+                config.server.set_bind_hosts(&host);
             }
             if let Some(port) = env.server.port {
                 config.server.port = port;
@@ -966,6 +1293,21 @@ impl AppConfig {
             if let Some(transport_mode) = env.server.transport_mode {
                 config.server.transport_mode = transport_mode;
             }
+            if let Some(allowed_hosts) = env.server.allowed_hosts {
+                config.server.allowed_hosts = allowed_hosts;
+            }
+            if let Some(allowed_origins) = env.server.allowed_origins {
+                config.server.allowed_origins = allowed_origins;
+            }
+            if let Some(protection) = env.server.dns_rebinding_protection {
+                config.server.dns_rebinding_protection = protection;
+            }
+            if let Some(base_path) = env.server.base_path {
+                config.server.base_path = base_path;
+            }
+            if let Some(trust) = env.server.trust_forwarded_headers {
+                config.server.trust_forwarded_headers = trust;
+            }
 
             // Merge logging configuration - only override if explicitly set
             if let Some(level) = env.logging.level {
@@ -986,6 +1328,9 @@ impl AppConfig {
                 if let Some(keys) = env.auth_api_key.keys {
                     config.auth.api_key.keys = keys;
                 }
+                if let Some(keys_file) = env.auth_api_key.keys_file {
+                    config.auth.api_key.keys_file = Some(keys_file);
+                }
                 if let Some(header_name) = env.auth_api_key.header_name {
                     config.auth.api_key.header_name = header_name;
                 }
@@ -1004,3 +1349,13 @@ impl AppConfig {
         config
     }
 }
+
+/// Split a comma-separated environment variable into its non-empty entries
+fn split_env_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
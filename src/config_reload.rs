@@ -180,6 +180,7 @@ impl ConfigReloader {
     /// - Cache TTL configuration
     /// - Rate limiting and concurrent request limits
     /// - Metrics and compression toggles
+    /// - Disabled tools (applied to the running server)
     ///
     /// Note: Server basic configuration (host, port, etc.) changes will be detected and logged,
     /// but these configurations require server restart to take effect.
@@ -280,6 +281,17 @@ impl ConfigReloader {
             ));
         }
 
+        let allow_lists = |oauth: &crate::server::auth::OAuthConfig| {
+            (
+                oauth.allowed_users.clone(),
+                oauth.allowed_orgs.clone(),
+                oauth.allowed_domains.clone(),
+            )
+        };
+        if allow_lists(&self.current_config.oauth) != allow_lists(&new_config.oauth) {
+            changes.push("OAuth allowed users, orgs or domains changed".to_string());
+        }
+
         // Check logging configuration changes (all fields support hot-reload)
         if self.current_config.logging.level != new_config.logging.level {
             changes.push(format!(
@@ -363,6 +375,43 @@ impl ConfigReloader {
             ));
         }
 
+        if self.current_config.cache.versioned_docs_ttl_secs
+            != new_config.cache.versioned_docs_ttl_secs
+        {
+            changes.push(format!(
+                "Versioned docs cache TTL changed: {:?} -> {:?}",
+                self.current_config.cache.versioned_docs_ttl_secs,
+                new_config.cache.versioned_docs_ttl_secs
+            ));
+        }
+
+        if self.current_config.cache.missing_crate_ttl_secs
+            != new_config.cache.missing_crate_ttl_secs
+        {
+            changes.push(format!(
+                "Missing crate cache TTL changed: {:?} -> {:?}",
+                self.current_config.cache.missing_crate_ttl_secs,
+                new_config.cache.missing_crate_ttl_secs
+            ));
+        }
+
+        if self.current_config.cache.stale_while_revalidate_secs
+            != new_config.cache.stale_while_revalidate_secs
+        {
+            changes.push(format!(
+                "Stale-while-revalidate window changed: {:?} -> {:?}",
+                self.current_config.cache.stale_while_revalidate_secs,
+                new_config.cache.stale_while_revalidate_secs
+            ));
+        }
+
+        if self.current_config.cache.ttl_jitter_ratio != new_config.cache.ttl_jitter_ratio {
+            changes.push(format!(
+                "Cache TTL jitter ratio changed: {:?} -> {:?}",
+                self.current_config.cache.ttl_jitter_ratio, new_config.cache.ttl_jitter_ratio
+            ));
+        }
+
         // Check performance configuration changes (hot-reloadable fields only)
         if self.current_config.performance.rate_limit_per_second
             != new_config.performance.rate_limit_per_second
@@ -408,6 +457,14 @@ impl ConfigReloader {
             ));
         }
 
+        // Applied to the running server by the reload task
+        if self.current_config.server.disabled_tools != new_config.server.disabled_tools {
+            changes.push(format!(
+                "Disabled tools changed: {:?} -> {:?}",
+                self.current_config.server.disabled_tools, new_config.server.disabled_tools
+            ));
+        }
+
         // Check server configuration changes (require restart)
         // These are detected for logging purposes but require server restart
         let mut restart_required = false;
@@ -436,6 +493,14 @@ impl ConfigReloader {
             restart_required = true;
         }
 
+        if self.current_config.server.admin_clients != new_config.server.admin_clients {
+            changes.push(format!(
+                "[RESTART REQUIRED] Admin clients changed: {:?} -> {:?}",
+                self.current_config.server.admin_clients, new_config.server.admin_clients
+            ));
+            restart_required = true;
+        }
+
         if self.current_config.server.max_connections != new_config.server.max_connections {
             changes.push(format!(
                 "[RESTART REQUIRED] Max connections changed: {} -> {}",
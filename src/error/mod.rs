@@ -104,6 +104,52 @@ pub enum Error {
         message: String,
     },
 
+    /// Requested resource (crate, version, item) does not exist
+    ///
+    /// Carries structured "did you mean" candidates so callers can render
+    /// them directly instead of parsing the message.
+    #[error("{resource} '{name}' not found{}", format_suggestions(.suggestions))]
+    NotFound {
+        /// Kind of resource ("crate", "version", "item")
+        resource: String,
+        /// Name that was looked up
+        name: String,
+        /// Similar names that do exist, best match first
+        suggestions: Vec<String>,
+    },
+
+    /// Name matches several resources (e.g. an item defined in several
+    /// modules)
+    ///
+    /// Carries the candidates so the caller can re-query with one of them.
+    #[error("{resource} '{name}' is ambiguous; repeat the request with one of: {}", .candidates.join(", "))]
+    Ambiguous {
+        /// Kind of resource ("item")
+        resource: String,
+        /// Name that was looked up
+        name: String,
+        /// Exact names of the matching resources
+        candidates: Vec<String>,
+    },
+
+    /// Client exceeded its request rate limit
+    #[error("Rate limit exceeded for client '{client}'; retry after {retry_after_secs}s")]
+    RateLimited {
+        /// Client that was throttled
+        client: String,
+        /// Seconds until the client may retry
+        retry_after_secs: u64,
+    },
+
+    /// Operation did not finish within its configured time limit
+    #[error("{operation} timed out after {timeout_secs}s")]
+    Timeout {
+        /// What timed out (a tool call, an upstream request)
+        operation: String,
+        /// Time limit that was exceeded, in seconds
+        timeout_secs: u64,
+    },
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -236,6 +282,123 @@ impl Error {
         }
     }
 
+    /// Create not-found error
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - Kind of resource ("crate", "version", "item")
+    /// * `name` - Name that was looked up
+    /// * `suggestions` - Similar existing names, best match first (may be empty)
+    #[must_use]
+    pub fn not_found(
+        resource: impl Into<String>,
+        name: impl Into<String>,
+        suggestions: Vec<String>,
+    ) -> Self {
+        Self::NotFound {
+            resource: resource.into(),
+            name: name.into(),
+            suggestions,
+        }
+    }
+
+    /// Create ambiguity error
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - Kind of resource ("item")
+    /// * `name` - Name that was looked up
+    /// * `candidates` - Exact names of the matching resources
+    #[must_use]
+    pub fn ambiguous(
+        resource: impl Into<String>,
+        name: impl Into<String>,
+        candidates: Vec<String>,
+    ) -> Self {
+        Self::Ambiguous {
+            resource: resource.into(),
+            name: name.into(),
+            candidates,
+        }
+    }
+
+    /// Create rate-limit error
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client that was throttled
+    /// * `retry_after` - Time until the client may retry, rounded up to whole seconds
+    #[must_use]
+    pub fn rate_limited(client: impl Into<String>, retry_after: std::time::Duration) -> Self {
+        Self::RateLimited {
+            client: client.into(),
+            retry_after_secs: ceil_secs(retry_after),
+        }
+    }
+
+    /// Create timeout error
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - What timed out (a tool call, an upstream request)
+    /// * `timeout` - Time limit that was exceeded
+    #[must_use]
+    pub fn timeout(operation: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self::Timeout {
+            operation: operation.into(),
+            timeout_secs: ceil_secs(timeout),
+        }
+    }
+
+    /// Structured payload for the client, if this error carries one
+    ///
+    /// Returns a JSON object for [`Error::NotFound`] (`kind`, `resource`,
+    /// `name`, `suggestions`) and [`Error::Ambiguous`] (`kind`, `resource`,
+    /// `name`, `candidates`), [`Error::RateLimited`] (`kind`,
+    /// `retry_after_secs`) and [`Error::Timeout`] (`kind`, `operation`,
+    /// `timeout_secs`); `None` for errors that are fully described by their
+    /// message.
+    #[must_use]
+    pub fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::NotFound {
+                resource,
+                name,
+                suggestions,
+            } => Some(serde_json::json!({
+                "kind": "not_found",
+                "resource": resource,
+                "name": name,
+                "suggestions": suggestions,
+            })),
+            Self::Ambiguous {
+                resource,
+                name,
+                candidates,
+            } => Some(serde_json::json!({
+                "kind": "ambiguous",
+                "resource": resource,
+                "name": name,
+                "candidates": candidates,
+            })),
+            Self::RateLimited {
+                retry_after_secs, ..
+            } => Some(serde_json::json!({
+                "kind": "rate_limited",
+                "retry_after_secs": retry_after_secs,
+            })),
+            Self::Timeout {
+                operation,
+                timeout_secs,
+            } => Some(serde_json::json!({
+                "kind": "timeout",
+                "operation": operation,
+                "timeout_secs": timeout_secs,
+            })),
+            _ => None,
+        }
+    }
+
     /// Create authentication error
     ///
     /// # Arguments
@@ -251,6 +414,33 @@ impl Error {
     }
 }
 
+/// Whole seconds in `duration`, rounded up
+fn ceil_secs(duration: std::time::Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/// Render the "did you mean" tail of a [`Error::NotFound`] message
+fn format_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(". Did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+/// Extract the structured payload of a tool error, if it wraps an [`Error`]
+/// that carries one (see [`Error::data`]).
+#[must_use]
+pub fn tool_error_data(error: &rust_mcp_sdk::schema::CallToolError) -> Option<serde_json::Value> {
+    error.0.downcast_ref::<Error>().and_then(Error::data)
+}
+
+impl From<Error> for rust_mcp_sdk::schema::CallToolError {
+    fn from(err: Error) -> Self {
+        Self::new(err)
+    }
+}
+
 impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
     fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
         Error::Other(err.to_string())
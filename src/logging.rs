@@ -0,0 +1,378 @@
+//! Logging initialization and runtime log level changes
+//!
+//! [`init_reloadable_logging`] installs the global `tracing` subscriber with
+//! a reloadable level filter and returns a [`LogLevelHandle`]. The handle is
+//! kept on the server and changes the active level without a restart, via
+//! the `set_log_level` tool.
+//!
+//! The subscriber also captures the events emitted while serving a client's
+//! request, which are forwarded to that client as MCP log messages (see
+//! [`subscribe_client_logs`]).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use crates_docs::config::LoggingConfig;
+//!
+//! let handle = crates_docs::logging::init_reloadable_logging(&LoggingConfig::default())
+//!     .expect("Failed to initialize logging");
+//! handle.set_level("info,crates_docs=debug").expect("valid directive");
+//! assert_eq!(handle.level(), "info,crates_docs=debug");
+//! ```
+
+use crate::error::{Error, Result};
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::broadcast;
+use tracing_subscriber::{layer::Context, reload, EnvFilter, Layer, Registry};
+
+/// Handle of the subscriber installed by [`init_reloadable_logging`]
+static GLOBAL_LOG_LEVEL: OnceLock<LogLevelHandle> = OnceLock::new();
+
+/// Events of client requests captured by the installed subscriber
+static CLIENT_LOGS: OnceLock<broadcast::Sender<ClientLogRecord>> = OnceLock::new();
+
+/// Captured events buffered per receiver; slow receivers skip older ones
+const CLIENT_LOG_CAPACITY: usize = 256;
+
+/// Handle changing the level filter of the installed subscriber
+#[derive(Clone)]
+pub struct LogLevelHandle {
+    reload: reload::Handle<EnvFilter, Registry>,
+    directive: Arc<RwLock<String>>,
+}
+
+impl std::fmt::Debug for LogLevelHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogLevelHandle")
+            .field("level", &self.level())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LogLevelHandle {
+    /// Create a reloadable filter layer and the handle controlling it
+    ///
+    /// `directive` is a level (`debug`) or an `EnvFilter` directive
+    /// (`info,crates_docs=debug`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `directive` is not a valid filter directive
+    pub fn layer(directive: &str) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let directive = directive.trim();
+        let (layer, reload) = reload::Layer::new(parse_filter(directive)?);
+        let handle = Self {
+            reload,
+            directive: Arc::new(RwLock::new(directive.to_string())),
+        };
+        Ok((layer, handle))
+    }
+
+    /// Active level filter directive
+    #[must_use]
+    pub fn level(&self) -> String {
+        self.directive
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the active level filter
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `directive` is not a valid filter directive or the
+    /// subscriber the handle belongs to no longer exists
+    pub fn set_level(&self, directive: &str) -> Result<()> {
+        let directive = directive.trim();
+        let filter = parse_filter(directive)?;
+        let mut current = self
+            .directive
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.reload
+            .reload(filter)
+            .map_err(|e| Error::Other(format!("Failed to change log level: {e}")))?;
+        tracing::info!(from = %current, to = %directive, "Log level changed");
+        *current = directive.to_string();
+        Ok(())
+    }
+}
+
+fn parse_filter(directive: &str) -> Result<EnvFilter> {
+    if directive.is_empty() {
+        return Err(Error::config("logging.level", "must not be empty"));
+    }
+    EnvFilter::try_new(directive).map_err(|e| {
+        Error::config(
+            "logging.level",
+            format!("invalid level or filter directive '{directive}': {e}"),
+        )
+    })
+}
+
+/// Level directive for a configured `logging.level` (unknown levels mean `info`)
+fn configured_level(level: &str) -> String {
+    let level = level.to_lowercase();
+    match level.as_str() {
+        "trace" | "debug" | "warn" | "error" => level,
+        _ => "info".to_string(),
+    }
+}
+
+/// An event of this crate emitted while serving a client's request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientLogRecord {
+    /// Session key of the client, see [`crate::tools::docs::profile::session_key`]
+    pub session: String,
+    /// Event level
+    pub level: tracing::Level,
+    /// Module that emitted the event
+    pub target: String,
+    /// Event message, followed by its other fields as `key=value`
+    pub message: String,
+}
+
+/// Layer capturing the events emitted while serving a client's request
+///
+/// Only events of this crate inside a session scope (see
+/// [`crate::tools::docs::profile::with_session`]) are captured, so clients
+/// never see the events of other clients or of background jobs.
+pub struct ClientLogLayer {
+    sender: broadcast::Sender<ClientLogRecord>,
+}
+
+impl ClientLogLayer {
+    /// Create a layer sending the captured events to `sender`
+    #[must_use]
+    pub fn new(sender: broadcast::Sender<ClientLogRecord>) -> Self {
+        Self { sender }
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for ClientLogLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+            || self.sender.receiver_count() == 0
+        {
+            return;
+        }
+        let Some(session) = crate::tools::docs::profile::scoped_session() else {
+            return;
+        };
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        // No receivers left is not an error
+        let _ = self.sender.send(ClientLogRecord {
+            session,
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        });
+    }
+}
+
+/// Formats an event as its message followed by ` key=value` per field
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+fn client_logs() -> &'static broadcast::Sender<ClientLogRecord> {
+    CLIENT_LOGS.get_or_init(|| broadcast::channel(CLIENT_LOG_CAPACITY).0)
+}
+
+/// Receive the events of client requests captured by the subscriber of
+/// [`init_reloadable_logging`]
+///
+/// Events are only captured while there is a receiver.
+#[must_use]
+pub fn subscribe_client_logs() -> broadcast::Receiver<ClientLogRecord> {
+    client_logs().subscribe()
+}
+
+/// Handle of the globally installed subscriber, if logging was initialized
+/// through [`init_reloadable_logging`]
+#[must_use]
+pub fn global_log_level() -> Option<LogLevelHandle> {
+    GLOBAL_LOG_LEVEL.get().cloned()
+}
+
+/// Initialize the logging system with a reloadable level filter
+///
+/// The returned handle is also available through [`global_log_level`].
+///
+/// # Errors
+///
+/// Returns an error if the log directory cannot be created or a global
+/// subscriber is already installed
+pub fn init_reloadable_logging(config: &crate::config::LoggingConfig) -> Result<LogLevelHandle> {
+    use tracing_subscriber::{fmt, prelude::*};
+
+    /// Helper macro to create fmt layer with standard configuration
+    macro_rules! fmt_layer {
+        () => {
+            fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_target(true)
+                .with_thread_ids(true)
+                .with_thread_names(true)
+                .compact()
+        };
+        ($writer:expr) => {
+            fmt::layer()
+                .with_writer($writer)
+                .with_target(true)
+                .with_thread_ids(true)
+                .with_thread_names(true)
+                .compact()
+        };
+    }
+
+    /// Helper macro to initialize subscriber with error handling
+    macro_rules! try_init {
+        ($subscriber:expr) => {
+            $subscriber
+                .try_init()
+                .map_err(|e| Error::initialization("logging", e.to_string()))?
+        };
+    }
+
+    let (filter, handle) = LogLevelHandle::layer(&configured_level(&config.level))?;
+    let client_log_layer = ClientLogLayer::new(client_logs().clone());
+
+    // Build log layers based on configuration
+    match (config.enable_console, config.enable_file, &config.file_path) {
+        (true, true, Some(file_path)) => {
+            // Enable both console and file logging
+            let (log_dir, log_file_name) = parse_log_path(file_path);
+            ensure_log_directory(&log_dir)?;
+            let file_appender = tracing_appender::rolling::daily(&log_dir, log_file_name);
+
+            try_init!(tracing_subscriber::registry()
+                .with(filter)
+                .with(client_log_layer)
+                .with(fmt_layer!())
+                .with(fmt_layer!(file_appender)));
+        }
+
+        (false, true, Some(file_path)) => {
+            // Enable file logging only
+            let (log_dir, log_file_name) = parse_log_path(file_path);
+            ensure_log_directory(&log_dir)?;
+            let file_appender = tracing_appender::rolling::daily(&log_dir, log_file_name);
+
+            try_init!(tracing_subscriber::registry()
+                .with(filter)
+                .with(client_log_layer)
+                .with(fmt_layer!(file_appender)));
+        }
+
+        // Default: console logging (covers all other cases)
+        _ => {
+            try_init!(tracing_subscriber::registry()
+                .with(filter)
+                .with(client_log_layer)
+                .with(fmt_layer!()));
+        }
+    }
+
+    // Only one subscriber can be installed, so this is the first handle
+    let _ = GLOBAL_LOG_LEVEL.set(handle.clone());
+    Ok(handle)
+}
+
+/// Parse log file path into directory and file name components
+fn parse_log_path(file_path: &str) -> (std::path::PathBuf, std::ffi::OsString) {
+    let path = std::path::Path::new(file_path);
+    let log_dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map_or_else(|| std::path::PathBuf::from("."), std::path::PathBuf::from);
+    let log_file_name = path.file_name().map_or_else(
+        || std::ffi::OsString::from("crates-docs.log"),
+        std::ffi::OsString::from,
+    );
+    (log_dir, log_file_name)
+}
+
+/// Ensure log directory exists
+fn ensure_log_directory(log_dir: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(log_dir)
+        .map_err(|e| Error::initialization("log_directory", format!("Failed to create: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_level_defaults_to_info() {
+        assert_eq!(configured_level("DEBUG"), "debug");
+        assert_eq!(configured_level("verbose"), "info");
+    }
+
+    #[test]
+    fn test_set_level_validates_and_reloads() {
+        let (layer, handle) = LogLevelHandle::layer("info").unwrap();
+        assert_eq!(handle.level(), "info");
+
+        handle.set_level(" debug,hyper=warn ").unwrap();
+        assert_eq!(handle.level(), "debug,hyper=warn");
+
+        assert!(handle.set_level("").is_err());
+        assert!(handle.set_level("crates_docs=loud").is_err());
+        assert_eq!(handle.level(), "debug,hyper=warn");
+
+        // Without its layer the filter can no longer be changed
+        drop(layer);
+        assert!(handle.set_level("info").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_log_layer_captures_session_events() {
+        use tracing_subscriber::prelude::*;
+
+        let (sender, mut receiver) = broadcast::channel(8);
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(ClientLogLayer::new(sender)),
+        );
+
+        // Outside of a session scope
+        tracing::warn!("background job failed");
+        crate::tools::docs::profile::with_session(Some("s1".to_string()), async {
+            tracing::warn!(attempt = 2, "Upstream failed: {}", "timeout");
+            // Other crates' events are not the client's concern
+            tracing::warn!(target: "hyper", "connection reset");
+        })
+        .await;
+
+        let record = receiver.try_recv().unwrap();
+        assert_eq!(record.session, "s1");
+        assert_eq!(record.level, tracing::Level::WARN);
+        assert_eq!(record.target, module_path!());
+        assert_eq!(record.message, "Upstream failed: timeout attempt=2");
+        assert!(receiver.try_recv().is_err());
+    }
+}
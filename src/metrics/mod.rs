@@ -65,6 +65,8 @@ pub struct ServerMetrics {
     active_connections: Gauge<u64, AtomicU64>,
     /// Error counter
     error_counter: Family<RequestLabels, Counter>,
+    /// Requests interrupted by a crash, found in the request journal
+    interrupted_counter: Family<RequestLabels, Counter>,
     /// Registry
     registry: Arc<Registry>,
 }
@@ -167,6 +169,14 @@ impl ServerMetrics {
             error_counter.clone(),
         );
 
+        // Interrupted request counter
+        let interrupted_counter = Family::<RequestLabels, Counter>::default();
+        registry.register(
+            "mcp_interrupted_requests",
+            "Requests left unfinished by the previous run, found in the request journal",
+            interrupted_counter.clone(),
+        );
+
         Self {
             request_counter,
             request_duration,
@@ -179,6 +189,7 @@ impl ServerMetrics {
             http_duration,
             active_connections,
             error_counter,
+            interrupted_counter,
             registry: Arc::new(registry),
         }
     }
@@ -204,6 +215,15 @@ impl ServerMetrics {
         }
     }
 
+    /// Record a request the previous run did not finish
+    pub fn record_interrupted_request(&self, tool: &str) {
+        let labels = RequestLabels {
+            tool: tool.to_string(),
+            status: "interrupted".to_string(),
+        };
+        self.interrupted_counter.get_or_create(&labels).inc();
+    }
+
     /// Record a cache operation
     pub fn record_cache_operation(&self, operation: &str, cache_type: &str) {
         let labels = CacheLabels {
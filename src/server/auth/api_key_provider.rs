@@ -46,21 +46,30 @@ const API_KEY_TTL_SECS: u64 = 87_600 * 60 * 60;
 /// Opaque, non-secret identifier reported for every accepted API key.
 ///
 /// `AuthInfo` is attached to the request/session and may be logged, so we must
-/// never place the raw key here. API-key auth carries no per-user identity, so a
-/// constant tag is sufficient and leaks nothing.
+/// never place the raw key here. Which key was used is reported separately as
+/// the `client_id` (see [`ApiKeyConfig::key_id`]).
 const API_KEY_TOKEN_ID: &str = "api-key";
 
 /// Adapts the project's [`ApiKeyConfig`] to the rust-mcp-sdk `AuthProvider`
 /// trait, enabling in-process Bearer-token enforcement of API keys.
 pub struct ApiKeyAuthProvider {
     config: ApiKeyConfig,
+    /// Inline keys plus those of `keys_file`, read once
+    keys: Vec<String>,
 }
 
 impl ApiKeyAuthProvider {
     /// Create a provider backed by the given API-key configuration.
+    ///
+    /// An unreadable `keys_file` is logged and its keys are rejected;
+    /// configuration validation reports it before the server starts.
     #[must_use]
     pub fn new(config: ApiKeyConfig) -> Self {
-        Self { config }
+        let keys = config.all_keys().unwrap_or_else(|e| {
+            tracing::error!("{e}; only the inline API keys are accepted");
+            config.keys.clone()
+        });
+        Self { config, keys }
     }
 }
 
@@ -90,12 +99,13 @@ impl SdkAuthProvider for ApiKeyAuthProvider {
             });
         }
 
-        // `is_valid_key` performs the actual (constant-time) verification against
+        // `find_key` performs the actual (constant-time) verification against
         // the configured Argon2 / legacy / plaintext key material.
-        if self.config.is_valid_key(&access_token) {
+        if let Some(key_id) = self.config.find_key(&access_token, &self.keys) {
+            tracing::debug!(api_key_id = %key_id, "API key accepted");
             Ok(AuthInfo {
                 token_unique_id: API_KEY_TOKEN_ID.to_string(),
-                client_id: None,
+                client_id: Some(key_id),
                 user_id: None,
                 scopes: None,
                 expires_at: Some(SystemTime::now() + Duration::from_secs(API_KEY_TTL_SECS)),
@@ -103,6 +113,7 @@ impl SdkAuthProvider for ApiKeyAuthProvider {
                 extra: None,
             })
         } else {
+            tracing::warn!("Rejected request with an invalid API key");
             Err(AuthenticationError::InvalidToken {
                 description: "Invalid API key",
             })
@@ -158,7 +169,7 @@ mod tests {
         let provider = ApiKeyAuthProvider::new(config);
 
         let info = provider
-            .verify_token(key)
+            .verify_token(key.clone())
             .await
             .expect("valid key should be accepted");
 
@@ -167,6 +178,48 @@ mod tests {
         assert!(expires_at > SystemTime::now());
         // The raw key must never be echoed back as the token identifier.
         assert_eq!(info.token_unique_id, API_KEY_TOKEN_ID);
+        assert!(info.client_id.is_some_and(|id| !key.contains(&id)));
+    }
+
+    #[tokio::test]
+    async fn verify_token_identifies_keys_from_keys_file() {
+        let generated = ApiKeyConfig::default()
+            .generate_key()
+            .expect("failed to generate API key");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys");
+        std::fs::write(
+            &path,
+            format!("# team keys\n\n{}\nplain_key\n", generated.hash),
+        )
+        .unwrap();
+        let config = ApiKeyConfig {
+            enabled: true,
+            keys: vec!["inline_key".to_string()],
+            keys_file: Some(path.display().to_string()),
+            ..Default::default()
+        };
+        let provider = ApiKeyAuthProvider::new(config);
+
+        let info = provider.verify_token(generated.key).await.unwrap();
+        let salt = generated.hash.split('$').nth(4).unwrap();
+        assert_eq!(info.client_id.as_deref(), Some(salt));
+
+        let info = provider
+            .verify_token("plain_key".to_string())
+            .await
+            .unwrap();
+        assert_eq!(info.client_id.as_deref(), Some("key-3"));
+        let info = provider
+            .verify_token("inline_key".to_string())
+            .await
+            .unwrap();
+        assert_eq!(info.client_id.as_deref(), Some("key-1"));
+
+        assert!(provider
+            .verify_token("# team keys".to_string())
+            .await
+            .is_err());
     }
 
     #[tokio::test]
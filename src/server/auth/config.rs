@@ -32,6 +32,18 @@ pub struct OAuthConfig {
     /// Token endpoint
     #[serde(default)]
     pub token_endpoint: Option<String>,
+    /// Userinfo endpoint, asked who signed in before a token is issued
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+    /// Users allowed to sign in, by login, username, subject or email
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Organizations (GitHub) or groups (`groups` claim) allowed to sign in
+    #[serde(default)]
+    pub allowed_orgs: Vec<String>,
+    /// Verified email domains allowed to sign in, e.g. `example.com`
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
     /// Scopes
     #[serde(default = "default_oauth_scopes")]
     pub scopes: Vec<String>,
@@ -57,6 +69,10 @@ impl Default for OAuthConfig {
             redirect_uri: None,
             authorization_endpoint: None,
             token_endpoint: None,
+            userinfo_endpoint: None,
+            allowed_users: Vec::new(),
+            allowed_orgs: Vec::new(),
+            allowed_domains: Vec::new(),
             scopes: vec![
                 "openid".to_string(),
                 "profile".to_string(),
@@ -78,8 +94,10 @@ impl OAuthConfig {
             redirect_uri: Some(redirect_uri),
             authorization_endpoint: Some("https://github.com/login/oauth/authorize".to_string()),
             token_endpoint: Some("https://github.com/login/oauth/access_token".to_string()),
+            userinfo_endpoint: Some("https://api.github.com/user".to_string()),
             scopes: vec!["read:user".to_string(), "user:email".to_string()],
             provider: OAuthProvider::GitHub,
+            ..Default::default()
         }
     }
 
@@ -95,12 +113,14 @@ impl OAuthConfig {
                 "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
             ),
             token_endpoint: Some("https://oauth2.googleapis.com/token".to_string()),
+            userinfo_endpoint: Some("https://openidconnect.googleapis.com/v1/userinfo".to_string()),
             scopes: vec![
                 "openid".to_string(),
                 "https://www.googleapis.com/auth/userinfo.profile".to_string(),
                 "https://www.googleapis.com/auth/userinfo.email".to_string(),
             ],
             provider: OAuthProvider::Google,
+            ..Default::default()
         }
     }
 
@@ -125,12 +145,16 @@ impl OAuthConfig {
             token_endpoint: Some(format!(
                 "{base}/realms/{realm}/protocol/openid-connect/token"
             )),
+            userinfo_endpoint: Some(format!(
+                "{base}/realms/{realm}/protocol/openid-connect/userinfo"
+            )),
             scopes: vec![
                 "openid".to_string(),
                 "profile".to_string(),
                 "email".to_string(),
             ],
             provider: OAuthProvider::Keycloak,
+            ..Default::default()
         }
     }
 
@@ -177,18 +201,40 @@ impl OAuthConfig {
                 .map_err(|e| Error::config("token_endpoint", format!("Invalid URL: {e}")))?;
         }
 
+        if let Some(endpoint) = &self.userinfo_endpoint {
+            Url::parse(endpoint)
+                .map_err(|e| Error::config("userinfo_endpoint", format!("Invalid URL: {e}")))?;
+        } else if self.restricts_users() {
+            return Err(Error::config(
+                "userinfo_endpoint",
+                "is required with allowed_users, allowed_orgs or allowed_domains",
+            ));
+        }
+
         Ok(())
     }
 
-    /// Convert to rust-mcp-sdk `OAuthConfig`
+    /// Whether only some users of the identity provider may sign in
+    #[must_use]
+    pub fn restricts_users(&self) -> bool {
+        !self.allowed_users.is_empty()
+            || !self.allowed_orgs.is_empty()
+            || !self.allowed_domains.is_empty()
+    }
+
+    /// Build the rust-mcp-sdk auth provider serving the authorization-code flow
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if OAuth is disabled or the configuration is invalid
     #[cfg(feature = "auth")]
-    pub fn to_mcp_config(&self) -> Result<()> {
+    pub fn to_mcp_config(&self) -> Result<super::OAuthAuthProvider> {
         if !self.enabled {
             return Err(Error::config("oauth", "is not enabled"));
         }
+        self.validate()?;
 
-        // Temporarily return empty result, to be implemented when OAuth feature is complete
-        Ok(())
+        Ok(super::OAuthAuthProvider::new(self.clone()))
     }
 
     /// Convert to rust-mcp-sdk `OAuthConfig`
@@ -198,6 +244,76 @@ impl OAuthConfig {
     }
 }
 
+/// JWT access-token validation (resource-server mode)
+///
+/// Accepts access tokens issued by an external authorization server such as
+/// Keycloak or Auth0, verified against the keys it publishes as a JWKS.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct JwtConfig {
+    /// Whether JWT validation is enabled
+    #[serde(default)]
+    pub enabled: bool,
+    /// Expected `iss` claim; also used to discover `jwks_uri` through
+    /// `{issuer}/.well-known/openid-configuration`
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// JWKS URL, skipping discovery
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+    /// Accepted `aud` values; empty disables the audience check
+    #[serde(default)]
+    pub audience: Vec<String>,
+    /// How long fetched keys are reused before being fetched again (seconds)
+    #[serde(default = "default_jwks_cache_ttl_secs")]
+    pub jwks_cache_ttl_secs: u64,
+}
+
+fn default_jwks_cache_ttl_secs() -> u64 {
+    3600
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer: None,
+            jwks_uri: None,
+            audience: Vec::new(),
+            jwks_cache_ttl_secs: default_jwks_cache_ttl_secs(),
+        }
+    }
+}
+
+impl JwtConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.issuer.is_none() && self.jwks_uri.is_none() {
+            return Err(Error::config("jwt", "requires issuer or jwks_uri"));
+        }
+
+        if let Some(issuer) = &self.issuer {
+            Url::parse(issuer).map_err(|e| Error::config("issuer", format!("Invalid URL: {e}")))?;
+        }
+
+        if let Some(uri) = &self.jwks_uri {
+            Url::parse(uri).map_err(|e| Error::config("jwks_uri", format!("Invalid URL: {e}")))?;
+        }
+
+        if self.jwks_cache_ttl_secs == 0 {
+            return Err(Error::config(
+                "jwks_cache_ttl_secs",
+                "must be greater than 0",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// API Key configuration
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 #[cfg(feature = "api-key")]
@@ -213,6 +329,12 @@ pub struct ApiKeyConfig {
     /// `ApiKeyConfig::generate_key()`.
     #[serde(default)]
     pub keys: Vec<String>,
+    /// File with more keys, one per line, in the same formats as `keys`
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Read once at
+    /// startup, so keys can be kept out of the main configuration file.
+    #[serde(default)]
+    pub keys_file: Option<String>,
     /// Header name for API key (default: "X-API-Key")
     #[serde(default = "default_header_name")]
     pub header_name: String,
@@ -225,6 +347,28 @@ pub struct ApiKeyConfig {
     /// API key prefix used by generated keys (e.g., "sk")
     #[serde(default = "default_key_prefix")]
     pub key_prefix: String,
+    /// Keys checked by [`ApiKeyConfig::is_valid_key`], read on first use
+    #[serde(skip)]
+    pub loaded_keys: LoadedApiKeys,
+}
+
+/// [`ApiKeyConfig::all_keys`], read once and kept for later checks
+///
+/// Leave at its default; changing `keys` or `keys_file` after the first
+/// check has no effect on it.
+#[cfg(feature = "api-key")]
+#[derive(Clone, Default)]
+pub struct LoadedApiKeys(std::sync::OnceLock<Vec<String>>);
+
+#[cfg(feature = "api-key")]
+impl std::fmt::Debug for LoadedApiKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Key material stays out of logs
+        match self.0.get() {
+            Some(keys) => write!(f, "LoadedApiKeys({} keys)", keys.len()),
+            None => f.write_str("LoadedApiKeys(not loaded)"),
+        }
+    }
 }
 
 #[cfg(feature = "api-key")]
@@ -248,10 +392,12 @@ impl Default for ApiKeyConfig {
         Self {
             enabled: false,
             keys: Vec::new(),
+            keys_file: None,
             header_name: default_header_name(),
             query_param_name: default_query_param_name(),
             allow_query_param: false,
             key_prefix: default_key_prefix(),
+            loaded_keys: LoadedApiKeys::default(),
         }
     }
 }
@@ -307,7 +453,7 @@ impl ApiKeyConfig {
             return Ok(());
         }
 
-        if self.keys.is_empty() {
+        if self.all_keys()?.is_empty() {
             tracing::warn!("API key authentication is enabled but no keys are configured");
         }
 
@@ -331,13 +477,67 @@ impl ApiKeyConfig {
         Ok(())
     }
 
-    /// Check if a key is valid
+    /// The inline `keys` followed by those of `keys_file`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keys_file` is set but cannot be read
+    pub fn all_keys(&self) -> Result<Vec<String>> {
+        let mut keys = self.keys.clone();
+        if let Some(path) = &self.keys_file {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| Error::config("keys_file", format!("cannot read {path}: {e}")))?;
+            keys.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        Ok(keys)
+    }
+
+    /// Non-secret identifier of a stored key, for logs
+    ///
+    /// Argon2 hashes are identified by their salt, the same ID
+    /// `revoke-api-key --key-id` accepts; plain-text keys by their 1-based
+    /// position, since any part of them would leak key material.
+    #[must_use]
+    pub fn key_id(stored: &str, index: usize) -> String {
+        let hash = stored.trim_start_matches("legacy:");
+        if Self::looks_like_hash(hash) {
+            if let Some(salt) = hash.split('$').nth(4).filter(|salt| !salt.is_empty()) {
+                return salt.to_string();
+            }
+        }
+        format!("key-{}", index + 1)
+    }
+
+    /// Check if a key is one of [`Self::all_keys`], as the HTTP middleware
+    /// does
+    ///
+    /// `keys_file` is read on the first check only. If it cannot be read,
+    /// only the inline keys are accepted.
     #[must_use]
     pub fn is_valid_key(&self, key: &str) -> bool {
         if !self.enabled {
             return true;
         }
+        let keys = self.loaded_keys.0.get_or_init(|| {
+            self.all_keys().unwrap_or_else(|e| {
+                tracing::error!("{e}; only the inline API keys are accepted");
+                self.keys.clone()
+            })
+        });
+        self.find_key(key, keys).is_some()
+    }
 
+    /// Find which of `keys` matches `key`, returning its [`Self::key_id`]
+    ///
+    /// Unlike [`Self::is_valid_key`] this does not consult `enabled`.
+    #[must_use]
+    pub fn find_key(&self, key: &str, keys: &[String]) -> Option<String> {
         let manager = self.manager().ok();
         let legacy_manager = self.legacy_manager().ok();
         let provided_key = SecureString::from(key.to_string());
@@ -345,29 +545,33 @@ impl ApiKeyConfig {
         // Evaluate every stored key without short-circuiting so that lookup
         // time does not depend on which key (if any) matches. `iter().any()`
         // would return as soon as the first match is found, leaking a timing
-        // side-channel about key position; fold-with-OR always visits all keys.
-        self.keys.iter().fold(false, |found, stored| {
-            let matched = if Self::looks_like_legacy_hash(stored) {
-                if let Some(legacy_manager) = &legacy_manager {
-                    let stored_hash = stored.trim_start_matches("legacy:");
-                    matches!(
-                        legacy_manager.verify(&provided_key, stored_hash),
-                        Ok(KeyStatus::Valid)
-                    )
-                } else {
-                    false
-                }
-            } else if Self::looks_like_hash(stored) {
-                if let Some(manager) = &manager {
-                    matches!(manager.verify(&provided_key, stored), Ok(KeyStatus::Valid))
+        // side-channel about key position; the fold always visits all keys.
+        let found = keys
+            .iter()
+            .enumerate()
+            .fold(None, |found, (index, stored)| {
+                let matched = if Self::looks_like_legacy_hash(stored) {
+                    if let Some(legacy_manager) = &legacy_manager {
+                        let stored_hash = stored.trim_start_matches("legacy:");
+                        matches!(
+                            legacy_manager.verify(&provided_key, stored_hash),
+                            Ok(KeyStatus::Valid)
+                        )
+                    } else {
+                        false
+                    }
+                } else if Self::looks_like_hash(stored) {
+                    if let Some(manager) = &manager {
+                        matches!(manager.verify(&provided_key, stored), Ok(KeyStatus::Valid))
+                    } else {
+                        false
+                    }
                 } else {
-                    false
-                }
-            } else {
-                Self::verify_plaintext_fallback(key, stored)
-            };
-            found | matched
-        })
+                    Self::verify_plaintext_fallback(key, stored)
+                };
+                found.or(matched.then_some(index))
+            });
+        found.map(|index| Self::key_id(&keys[index], index))
     }
 
     /// Generate a new API key and corresponding hash using api-keys-simplified.
@@ -416,6 +620,9 @@ pub struct AuthConfig {
     /// OAuth configuration
     #[serde(default)]
     pub oauth: OAuthConfig,
+    /// JWT validation configuration
+    #[serde(default)]
+    pub jwt: JwtConfig,
     /// API key configuration
     #[cfg(feature = "api-key")]
     #[serde(default)]
@@ -426,6 +633,7 @@ impl AuthConfig {
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {
         self.oauth.validate()?;
+        self.jwt.validate()?;
         #[cfg(feature = "api-key")]
         self.api_key.validate()?;
         Ok(())
@@ -435,13 +643,13 @@ impl AuthConfig {
     #[must_use]
     #[cfg(feature = "api-key")]
     pub fn is_enabled(&self) -> bool {
-        self.oauth.enabled || self.api_key.enabled
+        self.oauth.enabled || self.jwt.enabled || self.api_key.enabled
     }
 
     /// Check if any authentication is enabled
     #[must_use]
     #[cfg(not(feature = "api-key"))]
     pub fn is_enabled(&self) -> bool {
-        self.oauth.enabled
+        self.oauth.enabled || self.jwt.enabled
     }
 }
@@ -0,0 +1,435 @@
+//! JWT access-token validation against a JWKS (resource-server mode).
+//!
+//! Tokens issued by an external authorization server (Keycloak, Auth0, ...)
+//! are verified locally: the signing key is looked up by `kid` in the
+//! server's JSON Web Key Set, then signature, expiry, audience and issuer are
+//! checked. The JWKS is located through `OpenID` discovery on `issuer` unless
+//! `jwks_uri` is configured, and cached for `jwks_cache_ttl_secs`. A token
+//! signed with an unknown `kid` triggers an early refetch so key rotation is
+//! picked up without waiting for the cache to expire.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rust_mcp_sdk::auth::{
+    Audience, AuthInfo, AuthProvider as SdkAuthProvider, AuthenticationError, JsonWebKeySet,
+    OauthEndpoint,
+};
+use rust_mcp_sdk::mcp_http::{GenericBody, McpAppState};
+use rust_mcp_sdk::mcp_server::error::TransportServerError;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use super::JwtConfig;
+
+/// Timeout for discovery and JWKS requests
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Minimum age of the cached JWKS before an unknown `kid` may refetch it,
+/// so forged `kid`s cannot make every request hit the authorization server
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Identifier reported for tokens that carry no `jti`, so the raw token is
+/// never attached to the request
+const JWT_TOKEN_ID: &str = "jwt";
+
+struct CachedKeys {
+    keys: Arc<JsonWebKeySet>,
+    fetched_at: Instant,
+}
+
+/// Validates JWT access tokens with keys fetched from the issuer's JWKS.
+pub struct JwtValidator {
+    config: JwtConfig,
+    audience: Option<Audience>,
+    client: reqwest::Client,
+    /// JWKS URL, configured or discovered
+    jwks_uri: RwLock<Option<String>>,
+    cache: RwLock<Option<CachedKeys>>,
+    /// Provider consulted for tokens that are not JWTs (API keys)
+    fallback: Option<Arc<dyn SdkAuthProvider>>,
+}
+
+impl std::fmt::Debug for JwtValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtValidator")
+            .field("config", &self.config)
+            .field("fallback", &self.fallback.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtValidator {
+    /// Create a validator for the given configuration; keys are fetched on
+    /// first use.
+    #[must_use]
+    pub fn new(config: JwtConfig) -> Self {
+        let audience = match config.audience.as_slice() {
+            [] => None,
+            [single] => Some(Audience::Single(single.clone())),
+            many => Some(Audience::Multiple(many.to_vec())),
+        };
+        Self {
+            jwks_uri: RwLock::new(config.jwks_uri.clone()),
+            audience,
+            client: reqwest::Client::builder()
+                .timeout(FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            cache: RwLock::new(None),
+            fallback: None,
+            config,
+        }
+    }
+
+    /// Also accept tokens verified by `fallback`, e.g. API keys
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Arc<dyn SdkAuthProvider>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Validate a JWT access token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is malformed, signed by an unknown key,
+    /// expired, issued for another audience or issuer, or if the JWKS cannot
+    /// be fetched.
+    pub async fn validate(&self, token: &str) -> Result<AuthInfo, AuthenticationError> {
+        let keys = self.keys(false).await?;
+        match self.verify(&keys, token) {
+            Err(AuthenticationError::InvalidToken {
+                description: "No matching key found in JWKS",
+            }) if self.cache_age().await >= Some(MIN_REFRESH_INTERVAL) => {
+                tracing::debug!("Unknown JWT key id; refetching the JWKS");
+                let keys = self.keys(true).await?;
+                self.verify(&keys, token)
+            }
+            other => other,
+        }
+    }
+
+    fn verify(&self, keys: &JsonWebKeySet, token: &str) -> Result<AuthInfo, AuthenticationError> {
+        let data = keys.verify(
+            token.to_string(),
+            self.audience.as_ref(),
+            self.config.issuer.as_ref(),
+        )?;
+        let mut info = AuthInfo::from_token_data(token.to_string(), data, None)?;
+        if info.token_unique_id == token {
+            info.token_unique_id = JWT_TOKEN_ID.to_string();
+        }
+        Ok(info)
+    }
+
+    async fn cache_age(&self) -> Option<Duration> {
+        self.cache
+            .read()
+            .await
+            .as_ref()
+            .map(|cached| cached.fetched_at.elapsed())
+    }
+
+    /// Cached JWKS, fetched again when stale or when `refresh` is set
+    async fn keys(&self, refresh: bool) -> Result<Arc<JsonWebKeySet>, AuthenticationError> {
+        let ttl = Duration::from_secs(self.config.jwks_cache_ttl_secs);
+        if !refresh {
+            if let Some(cached) = self.cache.read().await.as_ref() {
+                if cached.fetched_at.elapsed() < ttl {
+                    return Ok(Arc::clone(&cached.keys));
+                }
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // Another request may have refreshed while this one waited.
+        if let Some(cached) = cache.as_ref() {
+            let fresh = if refresh {
+                cached.fetched_at.elapsed() < MIN_REFRESH_INTERVAL
+            } else {
+                cached.fetched_at.elapsed() < ttl
+            };
+            if fresh {
+                return Ok(Arc::clone(&cached.keys));
+            }
+        }
+
+        let uri = self.jwks_uri().await?;
+        let keys: JsonWebKeySet = self.fetch_json(&uri).await?;
+        tracing::debug!(jwks_uri = %uri, keys = keys.keys.len(), "Fetched JWKS");
+        let keys = Arc::new(keys);
+        *cache = Some(CachedKeys {
+            keys: Arc::clone(&keys),
+            fetched_at: Instant::now(),
+        });
+        Ok(keys)
+    }
+
+    /// Configured JWKS URL, or the one advertised by the issuer's discovery
+    /// document
+    async fn jwks_uri(&self) -> Result<String, AuthenticationError> {
+        if let Some(uri) = self.jwks_uri.read().await.as_ref() {
+            return Ok(uri.clone());
+        }
+
+        let issuer = self
+            .config
+            .issuer
+            .as_deref()
+            .ok_or_else(|| AuthenticationError::Jwks("no issuer or jwks_uri".to_string()))?;
+        let discovery = format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        );
+        let document: serde_json::Value = self.fetch_json(&discovery).await?;
+        let uri = document
+            .get("jwks_uri")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                AuthenticationError::Jwks(format!("{discovery} does not advertise a jwks_uri"))
+            })?
+            .to_string();
+
+        *self.jwks_uri.write().await = Some(uri.clone());
+        Ok(uri)
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<T, AuthenticationError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| AuthenticationError::Jwks(format!("{url}: {e}")))?;
+        response
+            .json()
+            .await
+            .map_err(|e| AuthenticationError::Jwks(format!("{url}: {e}")))
+    }
+}
+
+/// Whether `token` has the three dot-separated segments of a compact JWS
+fn looks_like_jwt(token: &str) -> bool {
+    token.split('.').count() == 3
+}
+
+#[async_trait]
+impl SdkAuthProvider for JwtValidator {
+    async fn verify_token(&self, access_token: String) -> Result<AuthInfo, AuthenticationError> {
+        if !self.config.enabled {
+            return Err(AuthenticationError::InvalidToken {
+                description: "JWT authentication is disabled",
+            });
+        }
+
+        match &self.fallback {
+            Some(fallback) if !looks_like_jwt(&access_token) => {
+                fallback.verify_token(access_token).await
+            }
+            _ => self.validate(&access_token).await.inspect_err(|e| {
+                tracing::warn!("Rejected request with an invalid JWT: {e}");
+            }),
+        }
+    }
+
+    fn auth_endpoints(&self) -> Option<&HashMap<String, OauthEndpoint>> {
+        None
+    }
+
+    async fn handle_request(
+        &self,
+        _request: http::Request<&str>,
+        _state: Arc<McpAppState>,
+    ) -> Result<http::Response<GenericBody>, TransportServerError> {
+        Err(TransportServerError::HttpError(
+            "JWT validation exposes no OAuth endpoints".to_string(),
+        ))
+    }
+
+    fn protected_resource_metadata_url(&self) -> Option<&str> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use std::time::SystemTime;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const SECRET: &[u8] = b"jwt-test-secret-with-32-bytes!!!";
+    /// `SECRET`, base64url-encoded as a JWK `k` parameter
+    const SECRET_B64: &str = "and0LXRlc3Qtc2VjcmV0LXdpdGgtMzItYnl0ZXMhISE";
+
+    fn jwks(kid: &str) -> serde_json::Value {
+        serde_json::json!({
+            "keys": [{ "kty": "oct", "kid": kid, "alg": "HS256", "k": SECRET_B64 }]
+        })
+    }
+
+    fn now() -> i64 {
+        i64::try_from(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        )
+        .unwrap()
+    }
+
+    fn token(kid: &str, claims: &serde_json::Value) -> String {
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = Some(kid.to_string());
+        encode(&header, claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    async fn server_with_discovery(kid: &str) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issuer": server.uri(),
+                "jwks_uri": format!("{}/certs", server.uri()),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/certs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(jwks(kid)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn config(server: &MockServer) -> JwtConfig {
+        JwtConfig {
+            enabled: true,
+            issuer: Some(server.uri()),
+            audience: vec!["crates-docs".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_valid_token_via_discovered_jwks() {
+        let server = server_with_discovery("k1").await;
+        let validator = JwtValidator::new(config(&server));
+
+        let claims = serde_json::json!({
+            "iss": server.uri(),
+            "aud": "crates-docs",
+            "sub": "alice",
+            "azp": "mcp-client",
+            "scope": "docs:read",
+            "exp": now() + 300,
+        });
+        let info = validator.verify_token(token("k1", &claims)).await.unwrap();
+        assert_eq!(info.user_id.as_deref(), Some("alice"));
+        assert_eq!(info.client_id.as_deref(), Some("mcp-client"));
+        assert_eq!(info.scopes, Some(vec!["docs:read".to_string()]));
+        assert_eq!(info.token_unique_id, JWT_TOKEN_ID);
+
+        // The JWKS is cached: a second token causes no further fetches
+        // (enforced by the mocks' `expect(1)`).
+        validator.verify_token(token("k1", &claims)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_audience_issuer_and_expired_tokens() {
+        let server = server_with_discovery("k1").await;
+        let validator = JwtValidator::new(config(&server));
+
+        for claims in [
+            serde_json::json!({ "iss": server.uri(), "aud": "other", "exp": now() + 300 }),
+            serde_json::json!({ "iss": "https://evil.example", "aud": "crates-docs", "exp": now() + 300 }),
+            serde_json::json!({ "iss": server.uri(), "aud": "crates-docs", "exp": now() - 600 }),
+        ] {
+            assert!(validator.verify_token(token("k1", &claims)).await.is_err());
+        }
+        assert!(validator
+            .verify_token("not-a-jwt".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_token_signed_with_another_secret() {
+        let server = server_with_discovery("k1").await;
+        let validator = JwtValidator::new(config(&server));
+
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = Some("k1".to_string());
+        let forged = encode(
+            &header,
+            &serde_json::json!({ "iss": server.uri(), "aud": "crates-docs", "exp": now() + 300 }),
+            &EncodingKey::from_secret(b"some-other-secret-of-32-bytes!!!"),
+        )
+        .unwrap();
+        assert!(validator.verify_token(forged).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn refetches_jwks_for_unknown_key_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/certs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(jwks("old")))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/certs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(jwks("new")))
+            .mount(&server)
+            .await;
+
+        let validator = JwtValidator::new(JwtConfig {
+            enabled: true,
+            jwks_uri: Some(format!("{}/certs", server.uri())),
+            ..Default::default()
+        });
+        let claims = serde_json::json!({ "exp": now() + 300 });
+        validator.verify_token(token("old", &claims)).await.unwrap();
+
+        // Too soon after the last fetch: the unknown key is rejected outright.
+        assert!(validator.verify_token(token("new", &claims)).await.is_err());
+
+        tokio::time::pause();
+        tokio::time::advance(MIN_REFRESH_INTERVAL).await;
+        tokio::time::resume();
+        validator.verify_token(token("new", &claims)).await.unwrap();
+    }
+
+    #[cfg(feature = "api-key")]
+    #[tokio::test]
+    async fn non_jwt_tokens_fall_back_to_api_keys() {
+        let api_keys = super::super::ApiKeyAuthProvider::new(super::super::ApiKeyConfig {
+            enabled: true,
+            keys: vec!["plain_key".to_string()],
+            ..Default::default()
+        });
+        let validator = JwtValidator::new(JwtConfig {
+            enabled: true,
+            jwks_uri: Some("http://127.0.0.1:9/certs".to_string()),
+            ..Default::default()
+        })
+        .with_fallback(Arc::new(api_keys));
+
+        let info = validator
+            .verify_token("plain_key".to_string())
+            .await
+            .unwrap();
+        assert_eq!(info.client_id.as_deref(), Some("key-1"));
+    }
+}
@@ -5,6 +5,8 @@
 //! # Authentication Methods
 //!
 //! - **OAuth 2.0**: Full OAuth flow with GitHub, Google, Keycloak support
+//! - **JWT**: Access tokens from an external authorization server, checked
+//!   against its JWKS
 //! - **API Key**: Simple API key authentication with secure hashing
 //!
 //! # Example
@@ -28,6 +30,10 @@ mod types;
 
 #[cfg(all(feature = "api-key", feature = "auth"))]
 mod api_key_provider;
+#[cfg(feature = "auth")]
+mod jwt;
+#[cfg(feature = "auth")]
+mod oauth_provider;
 
 #[cfg(test)]
 mod tests;
@@ -35,9 +41,13 @@ mod tests;
 #[cfg(all(feature = "api-key", feature = "auth"))]
 pub use api_key_provider::ApiKeyAuthProvider;
 #[cfg(feature = "api-key")]
-pub use config::ApiKeyConfig;
-pub use config::{AuthConfig, OAuthConfig};
+pub use config::{ApiKeyConfig, LoadedApiKeys};
+pub use config::{AuthConfig, JwtConfig, OAuthConfig};
+#[cfg(feature = "auth")]
+pub use jwt::JwtValidator;
 pub use manager::AuthManager;
+#[cfg(feature = "auth")]
+pub use oauth_provider::{OAuthAuthProvider, AUTHORIZE_PATH, CALLBACK_PATH};
 pub use token::{TokenInfo, TokenStore, TokenStoreError, TokenStoreResult};
 #[cfg(feature = "api-key")]
 pub use types::GeneratedApiKey;
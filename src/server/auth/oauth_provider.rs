@@ -0,0 +1,1211 @@
+//! OAuth 2.0 authorization-code flow for the HTTP/SSE transport.
+//!
+//! The server acts as an OAuth *client* of the configured identity provider
+//! (GitHub, Google, Keycloak, ...) and mounts two routes next to the MCP
+//! endpoint:
+//!
+//! - `GET /oauth/authorize` redirects the browser to the provider's
+//!   `authorization_endpoint` with a one-time `state`.
+//! - `GET /oauth/callback` checks that `state`, exchanges the `code` at the
+//!   provider's `token_endpoint` and answers with a server-issued Bearer token.
+//!
+//! The provider's tokens are kept in a [`TokenStore`] keyed by the issued
+//! token, so they never leave the server. Every MCP request must then send
+//! `Authorization: Bearer <token>`; the SDK's `AuthMiddleware` rejects missing,
+//! unknown or expired tokens with 401. `redirect_uri` must therefore point at
+//! this server's `/oauth/callback`.
+//!
+//! Behind a reverse proxy the routes move under `server.base_path`, and with
+//! `server.trust_forwarded_headers` an unset `redirect_uri` is derived from
+//! the public URL the browser used to reach `/oauth/authorize`.
+//!
+//! With a `userinfo_endpoint` the callback asks the provider who signed in.
+//! When `allowed_users`, `allowed_orgs` or `allowed_domains` is set, only
+//! matching users are issued a token; the others get 403 `access_denied`.
+//!
+//! When the provider handed out a refresh token, a token presented within a
+//! minute of its expiry (or after it) is refreshed at the token endpoint and
+//! the result written back under the same key, so clients keep using the token
+//! they were issued.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use rust_mcp_sdk::auth::{
+    AuthInfo, AuthProvider as SdkAuthProvider, AuthenticationError, OauthEndpoint,
+};
+use rust_mcp_sdk::mcp_http::{GenericBody, GenericBodyExt, McpAppState};
+use rust_mcp_sdk::mcp_server::error::TransportServerError;
+use url::Url;
+
+use super::{OAuthConfig, OAuthProvider, TokenInfo, TokenStore};
+use crate::server::forwarded::Forwarded;
+
+/// Path that starts the flow by redirecting to the identity provider
+pub const AUTHORIZE_PATH: &str = "/oauth/authorize";
+
+/// Path the identity provider redirects back to with the authorization code
+pub const CALLBACK_PATH: &str = "/oauth/callback";
+
+/// How long an authorization request may take before its `state` is dropped
+const STATE_TTL: Duration = Duration::from_mins(10);
+
+/// Lifetime of an issued token when the provider reports no `expires_in`
+/// (GitHub OAuth apps, for instance, hand out non-expiring tokens)
+const DEFAULT_TOKEN_LIFETIME_SECS: i64 = 60 * 60;
+
+/// Tokens are refreshed once they are this close to expiry (or past it)
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Timeout for the code exchange with the provider's token endpoint
+const TOKEN_EXCHANGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Non-secret identifier reported for every accepted OAuth token
+const OAUTH_TOKEN_ID: &str = "oauth";
+
+/// Token endpoint response (RFC 6749 §5.1)
+#[derive(Debug, serde::Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// The user who signed in, from the provider's userinfo endpoint
+#[derive(Debug, Default, PartialEq)]
+struct Identity {
+    /// Login, username or subject, whichever the provider reports first
+    user_id: Option<String>,
+    email: Option<String>,
+    /// Providers without an `email_verified` claim only report verified
+    /// addresses (GitHub)
+    email_verified: bool,
+    /// GitHub organizations or the `groups` claim
+    orgs: Vec<String>,
+}
+
+impl Identity {
+    fn from_userinfo(user: &serde_json::Value) -> Self {
+        let claim = |name: &str| match &user[name] {
+            serde_json::Value::String(value) if !value.is_empty() => Some(value.clone()),
+            serde_json::Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        };
+        Self {
+            user_id: ["login", "preferred_username", "sub", "id"]
+                .into_iter()
+                .find_map(claim),
+            email: claim("email"),
+            email_verified: user["email_verified"].as_bool().unwrap_or(true),
+            orgs: names(&user["groups"], None),
+        }
+    }
+
+    /// Whether the identity matches one of the allow-lists of `config`
+    ///
+    /// Without allow-lists every identity is allowed.
+    fn is_allowed(&self, config: &OAuthConfig) -> bool {
+        if !config.restricts_users() {
+            return true;
+        }
+        let matches = |allowed: &[String], value: &str| {
+            allowed.iter().any(|allowed| {
+                allowed
+                    .trim_start_matches(['@', '/'])
+                    .eq_ignore_ascii_case(value)
+            })
+        };
+        let verified_email = self.email.as_deref().filter(|_| self.email_verified);
+
+        self.user_id
+            .iter()
+            .chain(verified_email.map(str::to_string).iter())
+            .any(|user| matches(&config.allowed_users, user))
+            || verified_email
+                .and_then(|email| email.rsplit_once('@'))
+                .is_some_and(|(_, domain)| matches(&config.allowed_domains, domain))
+            || self
+                .orgs
+                .iter()
+                .any(|org| matches(&config.allowed_orgs, org))
+    }
+}
+
+/// Strings of a JSON array, or the `field` of its objects, without a leading `/`
+/// (Keycloak group paths)
+fn names(value: &serde_json::Value, field: Option<&str>) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| match field {
+            Some(field) => entry[field].as_str(),
+            None => entry.as_str(),
+        })
+        .map(|name| name.trim_start_matches('/').to_string())
+        .collect()
+}
+
+/// An authorization request waiting for its callback
+#[derive(Debug)]
+struct PendingAuthorization {
+    issued: Instant,
+    /// `redirect_uri` sent to the provider, repeated in the code exchange
+    redirect_uri: Option<String>,
+}
+
+/// Adapts [`OAuthConfig`] to the rust-mcp-sdk `AuthProvider` trait: serves the
+/// authorization-code routes and validates the tokens issued by them.
+pub struct OAuthAuthProvider {
+    config: OAuthConfig,
+    tokens: Arc<TokenStore>,
+    client: reqwest::Client,
+    endpoints: HashMap<String, OauthEndpoint>,
+    /// Path prefix of the routes (`server.base_path`)
+    base_path: String,
+    /// Reads the public URL and client address of requests
+    forwarded: Forwarded,
+    /// Outstanding `state` values and their authorization requests
+    pending: Mutex<HashMap<String, PendingAuthorization>>,
+    /// One lock per refresh token being redeemed, so each is redeemed only
+    /// once while refreshes of other tokens run in parallel
+    refresh_locks: Mutex<HashMap<String, Weak<tokio::sync::Mutex<()>>>>,
+    /// Provider consulted for tokens this one did not issue (API keys)
+    fallback: Option<Arc<dyn SdkAuthProvider>>,
+}
+
+impl std::fmt::Debug for OAuthAuthProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The configuration holds the client secret; keep it out of logs.
+        f.debug_struct("OAuthAuthProvider")
+            .field("provider", &self.config.provider)
+            .field("client_id", &self.config.client_id)
+            .field("fallback", &self.fallback.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl OAuthAuthProvider {
+    /// Create a provider for the given configuration with an empty token store.
+    ///
+    /// The configuration is expected to be validated; missing endpoints make
+    /// the routes answer with 500 rather than panic.
+    #[must_use]
+    pub fn new(config: OAuthConfig) -> Self {
+        if let Some(path) = config
+            .redirect_uri
+            .as_deref()
+            .and_then(|uri| Url::parse(uri).ok())
+            .map(|uri| uri.path().to_string())
+            .filter(|path| !path.ends_with(CALLBACK_PATH))
+        {
+            tracing::warn!(
+                redirect_path = %path,
+                "OAuth redirect_uri does not point at {CALLBACK_PATH}; the provider will redirect \
+                 to a route this server does not serve"
+            );
+        }
+
+        if config.enabled && !config.restricts_users() {
+            tracing::warn!(
+                "OAuth issues tokens to every user of the identity provider; set allowed_users, \
+                 allowed_orgs or allowed_domains to restrict who may sign in"
+            );
+        }
+
+        Self {
+            config,
+            tokens: Arc::new(TokenStore::new()),
+            client: reqwest::Client::builder()
+                .timeout(TOKEN_EXCHANGE_TIMEOUT)
+                // The GitHub API rejects requests without a User-Agent.
+                .user_agent(crate::user_agent())
+                .build()
+                .unwrap_or_default(),
+            endpoints: endpoints(""),
+            base_path: String::new(),
+            forwarded: Forwarded::default(),
+            pending: Mutex::new(HashMap::new()),
+            refresh_locks: Mutex::new(HashMap::new()),
+            fallback: None,
+        }
+    }
+
+    /// Also accept tokens verified by `fallback`, e.g. API keys
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Arc<dyn SdkAuthProvider>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Serve the routes below `base_path` (e.g. `/mcp/crates-docs`)
+    #[must_use]
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.base_path = crate::server::forwarded::normalize_base_path(base_path);
+        self.endpoints = endpoints(&self.base_path);
+        self
+    }
+
+    /// Read the public URL and client address through `forwarded`
+    #[must_use]
+    pub fn with_forwarded(mut self, forwarded: Forwarded) -> Self {
+        self.forwarded = forwarded;
+        self
+    }
+
+    /// Store holding the tokens issued through the callback
+    #[must_use]
+    pub fn token_store(&self) -> &Arc<TokenStore> {
+        &self.tokens
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, PendingAuthorization>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// `redirect_uri` for an authorization request with `headers`
+    ///
+    /// The configured value, or with trusted forwarded headers the public URL
+    /// of the callback route.
+    fn redirect_uri(&self, headers: &HeaderMap) -> Option<String> {
+        self.config.redirect_uri.clone().or_else(|| {
+            self.forwarded
+                .is_trusted()
+                .then(|| {
+                    self.forwarded
+                        .public_url(headers, &self.base_path, CALLBACK_PATH)
+                })
+                .flatten()
+        })
+    }
+
+    /// Build the provider URL for `GET /oauth/authorize` and remember its state
+    fn authorization_url(&self, headers: &HeaderMap) -> Result<Url, String> {
+        let endpoint = self
+            .config
+            .authorization_endpoint
+            .as_deref()
+            .ok_or("authorization_endpoint is not configured")?;
+        let mut url = Url::parse(endpoint).map_err(|e| e.to_string())?;
+
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let redirect_uri = self.redirect_uri(headers);
+        {
+            let mut pending = self.pending();
+            pending.retain(|_, request| request.issued.elapsed() < STATE_TTL);
+            pending.insert(
+                state.clone(),
+                PendingAuthorization {
+                    issued: Instant::now(),
+                    redirect_uri: redirect_uri.clone(),
+                },
+            );
+        }
+
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("response_type", "code");
+            if let Some(client_id) = &self.config.client_id {
+                query.append_pair("client_id", client_id);
+            }
+            if let Some(redirect_uri) = &redirect_uri {
+                query.append_pair("redirect_uri", redirect_uri);
+            }
+            if !self.config.scopes.is_empty() {
+                query.append_pair("scope", &self.config.scopes.join(" "));
+            }
+            query.append_pair("state", &state);
+        }
+        Ok(url)
+    }
+
+    /// Consume a `state` value, returning its request if it was issued and
+    /// is fresh
+    fn take_state(&self, state: &str) -> Option<PendingAuthorization> {
+        self.pending()
+            .remove(state)
+            .filter(|request| request.issued.elapsed() < STATE_TTL)
+    }
+
+    /// Exchange an authorization code at the provider's token endpoint
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: Option<&str>,
+    ) -> Result<TokenResponse, String> {
+        let mut params = vec![("grant_type", "authorization_code"), ("code", code)];
+        if let Some(redirect_uri) = redirect_uri {
+            params.push(("redirect_uri", redirect_uri));
+        }
+        self.request_token(&params).await
+    }
+
+    /// Obtain a new access token with a refresh token (RFC 6749 §6)
+    async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse, String> {
+        self.request_token(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ])
+        .await
+    }
+
+    /// POST a grant to the provider's token endpoint with the client credentials
+    async fn request_token(&self, params: &[(&str, &str)]) -> Result<TokenResponse, String> {
+        let endpoint = self
+            .config
+            .token_endpoint
+            .as_deref()
+            .ok_or("token_endpoint is not configured")?;
+
+        let form = {
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            form.extend_pairs(params);
+            if let Some(client_id) = &self.config.client_id {
+                form.append_pair("client_id", client_id);
+            }
+            if let Some(client_secret) = &self.config.client_secret {
+                form.append_pair("client_secret", client_secret);
+            }
+            form.finish()
+        };
+
+        let response = self
+            .client
+            .post(endpoint)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            // GitHub answers with a form body unless JSON is asked for.
+            .header(header::ACCEPT, "application/json")
+            .body(form)
+            .send()
+            .await
+            .map_err(|e| format!("token request failed: {e}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("token endpoint returned HTTP {status}"));
+        }
+        response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| format!("invalid token response: {e}"))
+    }
+
+    /// Ask the provider's userinfo endpoint who holds `access_token`
+    ///
+    /// GitHub reports organizations at a separate endpoint, which is only
+    /// asked when `allowed_orgs` is set (the token needs `read:org`).
+    async fn identity(&self, access_token: &str) -> Result<Identity, String> {
+        let endpoint = self
+            .config
+            .userinfo_endpoint
+            .as_deref()
+            .ok_or("userinfo_endpoint is not configured")?;
+        let mut identity = Identity::from_userinfo(&self.get_json(endpoint, access_token).await?);
+        if self.config.provider == OAuthProvider::GitHub && !self.config.allowed_orgs.is_empty() {
+            let orgs = format!("{}/orgs", endpoint.trim_end_matches('/'));
+            identity.orgs = names(&self.get_json(&orgs, access_token).await?, Some("login"));
+        }
+        Ok(identity)
+    }
+
+    /// GET a JSON document from the provider with `access_token`
+    async fn get_json(&self, url: &str, access_token: &str) -> Result<serde_json::Value, String> {
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(access_token)
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| format!("userinfo request failed: {e}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("userinfo endpoint returned HTTP {status}"));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("invalid userinfo response: {e}"))
+    }
+
+    /// Lock serializing the redemption of `refresh_token`
+    fn refresh_lock(&self, refresh_token: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self
+            .refresh_locks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        locks.retain(|_, lock| lock.strong_count() > 0);
+        if let Some(lock) = locks.get(refresh_token).and_then(Weak::upgrade) {
+            return lock;
+        }
+        let lock = Arc::new(tokio::sync::Mutex::new(()));
+        locks.insert(refresh_token.to_string(), Arc::downgrade(&lock));
+        lock
+    }
+
+    /// Turn a token response into stored token information and its lifetime
+    ///
+    /// Fields a refresh response may omit (`refresh_token`, `scope`) are
+    /// carried over from `previous`.
+    fn token_info(
+        &self,
+        response: TokenResponse,
+        previous: Option<&TokenInfo>,
+    ) -> (TokenInfo, i64) {
+        let lifetime = response
+            .expires_in
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_TOKEN_LIFETIME_SECS);
+        let scopes = match (response.scope, previous) {
+            (Some(scope), _) => scope
+                .split([' ', ','])
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            (None, Some(previous)) => previous.scopes.clone(),
+            (None, None) => self.config.scopes.clone(),
+        };
+        let info = TokenInfo {
+            access_token: response.access_token,
+            refresh_token: response
+                .refresh_token
+                .or_else(|| previous.and_then(|p| p.refresh_token.clone())),
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(lifetime),
+            scopes,
+            user_id: previous.and_then(|p| p.user_id.clone()),
+            user_email: previous.and_then(|p| p.user_email.clone()),
+        };
+        (info, lifetime)
+    }
+
+    /// Refresh the token stored under `key` and write the result back
+    ///
+    /// Returns the refreshed information, or `info` unchanged when the
+    /// provider refuses the refresh token.
+    async fn refresh(&self, key: &str, info: TokenInfo) -> TokenInfo {
+        let Some(refresh_token) = info.refresh_token.as_deref() else {
+            return info;
+        };
+        // Refresh tokens may be single-use: let only one request redeem it.
+        let lock = self.refresh_lock(refresh_token);
+        let _guard = lock.lock().await;
+        if let Ok(Some(current)) = self.tokens.get_token(key).await {
+            if !current.expires_within(refresh_margin()) {
+                return current;
+            }
+        }
+
+        match self.refresh_token(refresh_token).await {
+            Ok(response) => {
+                let (refreshed, _) = self.token_info(response, Some(&info));
+                if let Err(e) = self
+                    .tokens
+                    .store_token(key.to_string(), refreshed.clone())
+                    .await
+                {
+                    tracing::warn!("Failed to store refreshed OAuth token: {e}");
+                }
+                tracing::debug!(expires_at = %refreshed.expires_at, "Refreshed OAuth token");
+                refreshed
+            }
+            Err(e) => {
+                tracing::warn!("OAuth token refresh failed: {e}");
+                info
+            }
+        }
+    }
+
+    /// Handle `GET /oauth/callback?code=...&state=...`
+    ///
+    /// `client` is the address of the browser, for the logs.
+    async fn handle_callback(
+        &self,
+        query: &str,
+        client: Option<&str>,
+    ) -> http::Response<GenericBody> {
+        let params: HashMap<String, String> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        let client = client.unwrap_or("-");
+
+        if let Some(error) = params.get("error") {
+            tracing::warn!(error = %error, client, "OAuth authorization was denied by the provider");
+            return error_response(StatusCode::BAD_REQUEST, error);
+        }
+        let Some(request) = params.get("state").and_then(|state| self.take_state(state)) else {
+            tracing::warn!(
+                client,
+                "Rejected OAuth callback with an unknown or expired state"
+            );
+            return error_response(StatusCode::BAD_REQUEST, "invalid_state");
+        };
+        let Some(code) = params.get("code") else {
+            return error_response(StatusCode::BAD_REQUEST, "invalid_request");
+        };
+
+        let response = match self
+            .exchange_code(code, request.redirect_uri.as_deref())
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::warn!("OAuth code exchange failed: {e}");
+                return error_response(StatusCode::BAD_GATEWAY, "token_exchange_failed");
+            }
+        };
+
+        let identity = if self.config.userinfo_endpoint.is_some() {
+            match self.identity(&response.access_token).await {
+                Ok(identity) => Some(identity),
+                Err(e) if self.config.restricts_users() => {
+                    tracing::warn!(client, "Cannot tell who signed in with OAuth: {e}");
+                    return error_response(StatusCode::BAD_GATEWAY, "userinfo_failed");
+                }
+                Err(e) => {
+                    tracing::warn!(client, "OAuth userinfo request failed: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        if self.config.restricts_users()
+            && !identity
+                .as_ref()
+                .is_some_and(|identity| identity.is_allowed(&self.config))
+        {
+            tracing::warn!(
+                client,
+                user = identity
+                    .as_ref()
+                    .and_then(|i| i.user_id.as_deref())
+                    .unwrap_or("-"),
+                "Rejected OAuth sign-in of a user outside allowed_users, allowed_orgs and \
+                 allowed_domains"
+            );
+            return error_response(StatusCode::FORBIDDEN, "access_denied");
+        }
+
+        let (mut info, lifetime) = self.token_info(response, None);
+        if let Some(identity) = identity {
+            info.user_id = identity.user_id;
+            info.user_email = identity.email;
+        }
+        let scopes = info.scopes.join(" ");
+
+        let issued = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        if let Err(e) = self.tokens.cleanup_unrefreshable().await {
+            tracing::debug!("Skipped OAuth token cleanup: {e}");
+        }
+        if let Err(e) = self.tokens.store_token(issued.clone(), info).await {
+            tracing::error!("Failed to store OAuth token: {e}");
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "temporarily_unavailable");
+        }
+        tracing::info!(
+            client,
+            "OAuth authorization completed; issued a new access token"
+        );
+
+        GenericBody::from_value(&serde_json::json!({
+            "access_token": issued,
+            "token_type": "Bearer",
+            "expires_in": lifetime,
+            "scope": scopes,
+        }))
+        .into_json_response(StatusCode::OK, Some(no_store_headers()))
+    }
+}
+
+/// Authorize and callback routes below `base_path`
+fn endpoints(base_path: &str) -> HashMap<String, OauthEndpoint> {
+    // Both routes are plain browser GETs, which the SDK only allows for
+    // the authorization endpoint kind.
+    HashMap::from([
+        (
+            format!("{base_path}{AUTHORIZE_PATH}"),
+            OauthEndpoint::AuthorizationEndpoint,
+        ),
+        (
+            format!("{base_path}{CALLBACK_PATH}"),
+            OauthEndpoint::AuthorizationEndpoint,
+        ),
+    ])
+}
+
+fn refresh_margin() -> chrono::Duration {
+    chrono::Duration::seconds(REFRESH_MARGIN_SECS)
+}
+
+/// OAuth error body (RFC 6749 §5.2)
+fn error_response(status: StatusCode, error: &str) -> http::Response<GenericBody> {
+    GenericBody::from_value(&serde_json::json!({ "error": error }))
+        .into_json_response(status, Some(no_store_headers()))
+}
+
+/// Responses carrying tokens or one-time state must not be cached
+fn no_store_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers
+}
+
+#[async_trait]
+impl SdkAuthProvider for OAuthAuthProvider {
+    async fn verify_token(&self, access_token: String) -> Result<AuthInfo, AuthenticationError> {
+        if !self.config.enabled {
+            return Err(AuthenticationError::InvalidToken {
+                description: "OAuth authentication is disabled",
+            });
+        }
+
+        let stored = self.tokens.get_token(&access_token).await.map_err(|e| {
+            AuthenticationError::ServerError {
+                description: e.to_string(),
+            }
+        })?;
+
+        let stored = match stored {
+            Some(info) if info.refresh_token.is_some() && info.expires_within(refresh_margin()) => {
+                Some(self.refresh(&access_token, info).await)
+            }
+            other => other,
+        };
+
+        match stored {
+            Some(info) if info.expires_at > chrono::Utc::now() => Ok(AuthInfo {
+                token_unique_id: OAUTH_TOKEN_ID.to_string(),
+                client_id: self.config.client_id.clone(),
+                user_id: info.user_id,
+                scopes: Some(info.scopes),
+                expires_at: Some(SystemTime::from(info.expires_at)),
+                audience: None,
+                extra: None,
+            }),
+            Some(_) => {
+                // Expired and could not be refreshed: forget it so the store
+                // does not grow unbounded.
+                let _ = self.tokens.remove_token(&access_token).await;
+                Err(AuthenticationError::InvalidOrExpiredToken(
+                    "OAuth token has expired".to_string(),
+                ))
+            }
+            None => match &self.fallback {
+                Some(fallback) => fallback.verify_token(access_token).await,
+                None => Err(AuthenticationError::InvalidToken {
+                    description: "Unknown OAuth token",
+                }),
+            },
+        }
+    }
+
+    fn auth_endpoints(&self) -> Option<&HashMap<String, OauthEndpoint>> {
+        Some(&self.endpoints)
+    }
+
+    async fn handle_request(
+        &self,
+        request: http::Request<&str>,
+        _state: Arc<McpAppState>,
+    ) -> Result<http::Response<GenericBody>, TransportServerError> {
+        let Some(endpoint) = self.endpoint_type(&request) else {
+            return Ok(GenericBody::create_404_response());
+        };
+        if let Some(response) = self.validate_allowed_methods(endpoint, request.method()) {
+            return Ok(response);
+        }
+        if request.method() != Method::GET {
+            return Ok(GenericBody::empty().into_response(StatusCode::NO_CONTENT, None));
+        }
+
+        let path = request.uri().path();
+        match path.strip_prefix(self.base_path.as_str()).unwrap_or(path) {
+            AUTHORIZE_PATH => match self.authorization_url(request.headers()) {
+                Ok(url) => {
+                    let mut headers = no_store_headers();
+                    let location = HeaderValue::from_str(url.as_str())
+                        .map_err(|e| TransportServerError::HttpError(e.to_string()))?;
+                    headers.insert(header::LOCATION, location);
+                    Ok(GenericBody::empty().into_response(StatusCode::FOUND, Some(headers)))
+                }
+                Err(e) => {
+                    tracing::error!("Cannot start OAuth authorization: {e}");
+                    Ok(error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "server_error",
+                    ))
+                }
+            },
+            _ => Ok(self
+                .handle_callback(
+                    request.uri().query().unwrap_or_default(),
+                    self.forwarded.client_addr(request.headers()),
+                )
+                .await),
+        }
+    }
+
+    fn protected_resource_metadata_url(&self) -> Option<&str> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(token_endpoint: &str) -> OAuthConfig {
+        OAuthConfig {
+            enabled: true,
+            client_id: Some("client".to_string()),
+            client_secret: Some("secret".to_string()),
+            redirect_uri: Some(format!("http://localhost:8080{CALLBACK_PATH}")),
+            authorization_endpoint: Some("https://idp.example.com/authorize".to_string()),
+            token_endpoint: Some(token_endpoint.to_string()),
+            scopes: vec!["read:user".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn state_of(url: &Url) -> String {
+        url.query_pairs()
+            .find(|(k, _)| k == "state")
+            .map(|(_, v)| v.into_owned())
+            .expect("authorization URL must carry a state")
+    }
+
+    #[test]
+    fn mounts_authorize_and_callback_routes() {
+        let provider = OAuthAuthProvider::new(config("https://idp.example.com/token"));
+        let endpoints = provider.auth_endpoints().unwrap();
+        assert!(endpoints.contains_key(AUTHORIZE_PATH));
+        assert!(endpoints.contains_key(CALLBACK_PATH));
+    }
+
+    #[test]
+    fn behind_a_proxy_routes_move_and_redirect_uri_is_derived() {
+        let mut config = config("https://idp.example.com/token");
+        config.redirect_uri = None;
+        let provider = OAuthAuthProvider::new(config)
+            .with_base_path("/mcp/crates-docs/")
+            .with_forwarded(Forwarded::new(true));
+        let endpoints = provider.auth_endpoints().unwrap();
+        assert!(endpoints.contains_key("/mcp/crates-docs/oauth/authorize"));
+        assert!(endpoints.contains_key("/mcp/crates-docs/oauth/callback"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_static("10.0.0.2:8080"));
+        headers.insert("x-forwarded-proto", HeaderValue::from_static("https"));
+        headers.insert(
+            "x-forwarded-host",
+            HeaderValue::from_static("docs.example.com"),
+        );
+        let url = provider.authorization_url(&headers).unwrap();
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            query["redirect_uri"],
+            "https://docs.example.com/mcp/crates-docs/oauth/callback"
+        );
+        // The code exchange repeats the same redirect_uri.
+        let request = provider.take_state(&state_of(&url)).unwrap();
+        assert_eq!(
+            request.redirect_uri.as_deref(),
+            Some("https://docs.example.com/mcp/crates-docs/oauth/callback")
+        );
+    }
+
+    #[test]
+    fn authorization_url_carries_client_and_state() {
+        let provider = OAuthAuthProvider::new(config("https://idp.example.com/token"));
+        let url = provider.authorization_url(&HeaderMap::new()).unwrap();
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["client_id"], "client");
+        assert_eq!(
+            query["redirect_uri"],
+            "http://localhost:8080/oauth/callback"
+        );
+        assert_eq!(query["scope"], "read:user");
+
+        // A state can be redeemed exactly once.
+        let state = state_of(&url);
+        assert!(provider.take_state(&state).is_some());
+        assert!(provider.take_state(&state).is_none());
+    }
+
+    #[tokio::test]
+    async fn callback_exchanges_code_and_issues_a_verifiable_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .and(body_string_contains("code=the-code"))
+            .and(body_string_contains("client_secret=secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "upstream-token",
+                "refresh_token": "upstream-refresh",
+                "expires_in": 120,
+                "scope": "read:user",
+                "token_type": "bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(config(&format!("{}/token", server.uri())));
+        let state = state_of(&provider.authorization_url(&HeaderMap::new()).unwrap());
+        let response = provider
+            .handle_callback(&format!("code=the-code&state={state}"), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let issued = body["access_token"].as_str().unwrap().to_string();
+        assert_ne!(issued, "upstream-token");
+        assert_eq!(body["expires_in"], 120);
+
+        let stored = provider.tokens.get_token(&issued).await.unwrap().unwrap();
+        assert_eq!(stored.access_token, "upstream-token");
+        assert_eq!(stored.refresh_token.as_deref(), Some("upstream-refresh"));
+
+        let info = provider.verify_token(issued).await.unwrap();
+        assert_eq!(info.token_unique_id, OAUTH_TOKEN_ID);
+        assert_eq!(info.scopes, Some(vec!["read:user".to_string()]));
+        assert!(info.expires_at.unwrap() > SystemTime::now());
+    }
+
+    #[test]
+    fn identity_is_matched_against_the_allow_lists() {
+        let github = Identity::from_userinfo(&serde_json::json!({
+            "login": "octocat",
+            "id": 583_231,
+            "email": "octocat@github.com",
+        }));
+        assert_eq!(github.user_id.as_deref(), Some("octocat"));
+        assert!(github.email_verified);
+
+        let keycloak = Identity::from_userinfo(&serde_json::json!({
+            "sub": "f1b2",
+            "preferred_username": "alice",
+            "email": "alice@example.com",
+            "email_verified": false,
+            "groups": ["/platform", "/ops"],
+        }));
+        assert_eq!(keycloak.user_id.as_deref(), Some("alice"));
+        assert_eq!(keycloak.orgs, ["platform", "ops"]);
+
+        let mut config = config("https://idp.example.com/token");
+        assert!(keycloak.is_allowed(&config));
+
+        config.allowed_users = vec!["OctoCat".to_string()];
+        assert!(github.is_allowed(&config));
+        assert!(!keycloak.is_allowed(&config));
+
+        config.allowed_users.clear();
+        config.allowed_domains = vec!["example.com".to_string()];
+        // Unverified addresses do not count
+        assert!(!keycloak.is_allowed(&config));
+
+        config.allowed_orgs = vec!["/ops".to_string()];
+        assert!(keycloak.is_allowed(&config));
+        assert!(!github.is_allowed(&config));
+    }
+
+    async fn mount_token_endpoint(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "upstream-token",
+                "expires_in": 120,
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn callback_only_issues_tokens_to_allowed_users() {
+        let server = MockServer::start().await;
+        mount_token_endpoint(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "octocat",
+                "email": "octocat@github.com",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/user/orgs"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{ "login": "rust-lang" }])),
+            )
+            .mount(&server)
+            .await;
+
+        let provider_with = |allowed_orgs: &[&str]| {
+            OAuthAuthProvider::new(OAuthConfig {
+                userinfo_endpoint: Some(format!("{}/user", server.uri())),
+                allowed_orgs: allowed_orgs.iter().map(ToString::to_string).collect(),
+                provider: OAuthProvider::GitHub,
+                ..config(&format!("{}/token", server.uri()))
+            })
+        };
+        let callback = |provider: &OAuthAuthProvider| {
+            let state = state_of(&provider.authorization_url(&HeaderMap::new()).unwrap());
+            format!("code=the-code&state={state}")
+        };
+
+        let provider = provider_with(&["tokio-rs"]);
+        let response = provider.handle_callback(&callback(&provider), None).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let provider = provider_with(&["rust-lang"]);
+        let response = provider.handle_callback(&callback(&provider), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let info = provider
+            .verify_token(body["access_token"].as_str().unwrap().to_string())
+            .await
+            .unwrap();
+        assert_eq!(info.user_id.as_deref(), Some("octocat"));
+    }
+
+    #[tokio::test]
+    async fn callback_fails_when_allowed_users_cannot_be_checked() {
+        let server = MockServer::start().await;
+        mount_token_endpoint(&server).await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(OAuthConfig {
+            userinfo_endpoint: Some(format!("{}/userinfo", server.uri())),
+            allowed_users: vec!["alice".to_string()],
+            ..config(&format!("{}/token", server.uri()))
+        });
+        let state = state_of(&provider.authorization_url(&HeaderMap::new()).unwrap());
+        let response = provider
+            .handle_callback(&format!("code=the-code&state={state}"), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn refresh_locks_are_per_refresh_token() {
+        let provider = OAuthAuthProvider::new(config("https://idp.example.com/token"));
+        let first = provider.refresh_lock("refresh-1");
+        assert!(Arc::ptr_eq(&first, &provider.refresh_lock("refresh-1")));
+
+        let held = first.lock().await;
+        let other = provider.refresh_lock("refresh-2");
+        assert!(other.try_lock().is_ok());
+
+        drop(held);
+        drop(first);
+        drop(other);
+        provider.refresh_lock("refresh-3");
+        // Locks nobody holds are dropped
+        assert_eq!(provider.refresh_locks.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn callback_rejects_unknown_state_without_exchanging() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(config(&format!("{}/token", server.uri())));
+        let response = provider
+            .handle_callback("code=the-code&state=forged", None)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn callback_reports_failed_exchange() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": "bad_verification_code",
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(config(&format!("{}/token", server.uri())));
+        let state = state_of(&provider.authorization_url(&HeaderMap::new()).unwrap());
+        let response = provider
+            .handle_callback(&format!("code=stale&state={state}"), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn verify_token_rejects_unknown_and_expired_tokens() {
+        let provider = OAuthAuthProvider::new(config("https://idp.example.com/token"));
+        assert!(provider.verify_token("nope".to_string()).await.is_err());
+
+        provider
+            .tokens
+            .store_token(
+                "expired".to_string(),
+                TokenInfo {
+                    access_token: "upstream".to_string(),
+                    refresh_token: None,
+                    expires_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+                    scopes: Vec::new(),
+                    user_id: None,
+                    user_email: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            provider.verify_token("expired".to_string()).await,
+            Err(AuthenticationError::InvalidOrExpiredToken(_))
+        ));
+        assert!(provider
+            .tokens
+            .get_token("expired")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    fn stored(expires_in_secs: i64, refresh_token: Option<&str>) -> TokenInfo {
+        TokenInfo {
+            access_token: "upstream".to_string(),
+            refresh_token: refresh_token.map(str::to_string),
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(expires_in_secs),
+            scopes: vec!["read:user".to_string()],
+            user_id: None,
+            user_email: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_token_refreshes_expiring_tokens_and_stores_the_result() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=old-refresh"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "upstream-2",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(config(&format!("{}/token", server.uri())));
+        // Expiring soon, and already expired: both are renewed.
+        for (key, expires_in) in [("soon", 10), ("late", -10)] {
+            provider
+                .tokens
+                .store_token(key.to_string(), stored(expires_in, Some("old-refresh")))
+                .await
+                .unwrap();
+        }
+
+        let info = provider.verify_token("soon".to_string()).await.unwrap();
+        assert!(info.expires_at.unwrap() > SystemTime::now() + Duration::from_mins(50));
+        let refreshed = provider.tokens.get_token("soon").await.unwrap().unwrap();
+        assert_eq!(refreshed.access_token, "upstream-2");
+        // The response omitted these, so they are carried over.
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("old-refresh"));
+        assert_eq!(refreshed.scopes, vec!["read:user".to_string()]);
+
+        // A fresh token is served from the store without another refresh.
+        provider.verify_token("soon".to_string()).await.unwrap();
+
+        server.reset().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "upstream-3",
+                "refresh_token": "rotated",
+                "expires_in": 3600,
+            })))
+            .mount(&server)
+            .await;
+        provider.verify_token("late".to_string()).await.unwrap();
+        let refreshed = provider.tokens.get_token("late").await.unwrap().unwrap();
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("rotated"));
+    }
+
+    #[tokio::test]
+    async fn verify_token_keeps_or_drops_tokens_when_refresh_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "invalid_grant",
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = OAuthAuthProvider::new(config(&format!("{}/token", server.uri())));
+        provider
+            .tokens
+            .store_token("soon".to_string(), stored(10, Some("revoked")))
+            .await
+            .unwrap();
+        provider
+            .tokens
+            .store_token("late".to_string(), stored(-10, Some("revoked")))
+            .await
+            .unwrap();
+
+        // Still valid for a few seconds: keep serving it.
+        provider.verify_token("soon".to_string()).await.unwrap();
+        // Expired and not renewable: rejected and forgotten.
+        assert!(provider.verify_token("late".to_string()).await.is_err());
+        assert!(provider.tokens.get_token("late").await.unwrap().is_none());
+    }
+
+    #[cfg(feature = "api-key")]
+    #[tokio::test]
+    async fn verify_token_falls_back_to_api_keys() {
+        let api_keys = super::super::ApiKeyAuthProvider::new(super::super::ApiKeyConfig {
+            enabled: true,
+            keys: vec!["plain_key".to_string()],
+            ..Default::default()
+        });
+        let provider = OAuthAuthProvider::new(config("https://idp.example.com/token"))
+            .with_fallback(Arc::new(api_keys));
+
+        let info = provider
+            .verify_token("plain_key".to_string())
+            .await
+            .unwrap();
+        assert_eq!(info.client_id.as_deref(), Some("key-1"));
+        assert!(provider.verify_token("other".to_string()).await.is_err());
+    }
+}
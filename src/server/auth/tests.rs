@@ -415,8 +415,30 @@ fn test_oauth_config_validate_all_fields() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_oauth_config_validate_allow_lists_need_userinfo_endpoint() {
+    let mut config = OAuthConfig {
+        allowed_domains: vec!["example.com".to_string()],
+        ..OAuthConfig::github(
+            "client".to_string(),
+            "secret".to_string(),
+            "http://localhost/oauth/callback".to_string(),
+        )
     };
+    assert!(config.restricts_users());
     assert!(config.validate().is_ok());
+
+    config.userinfo_endpoint = None;
+    let result = config.validate();
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("userinfo_endpoint"));
 }
 
 #[test]
@@ -430,6 +452,7 @@ fn test_oauth_config_validate_missing_client_secret() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -447,6 +470,7 @@ fn test_oauth_config_validate_missing_authorization_endpoint() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -467,6 +491,7 @@ fn test_oauth_config_validate_missing_token_endpoint() {
         token_endpoint: None,
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -484,6 +509,7 @@ fn test_oauth_config_validate_invalid_redirect_uri() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -503,6 +529,7 @@ fn test_oauth_config_validate_invalid_authorization_endpoint() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -522,6 +549,7 @@ fn test_oauth_config_validate_invalid_token_endpoint() {
         token_endpoint: Some("not\\a\\valid\\url".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -572,6 +600,7 @@ fn test_oauth_config_disabled_bypasses_validation() {
         token_endpoint: None,
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     assert!(config.validate().is_ok());
 }
@@ -613,6 +642,69 @@ fn test_auth_config_validate_oauth_invalid() {
     assert!(config.validate().is_err());
 }
 
+#[tokio::test]
+async fn test_token_store_cleanup_unrefreshable_keeps_refreshable_tokens() {
+    let store = TokenStore::new();
+    let expired = |refresh_token: Option<&str>| TokenInfo {
+        access_token: "access".to_string(),
+        refresh_token: refresh_token.map(str::to_string),
+        expires_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+        scopes: Vec::new(),
+        user_id: None,
+        user_email: None,
+    };
+    store
+        .store_token("dead".to_string(), expired(None))
+        .await
+        .unwrap();
+    store
+        .store_token("renewable".to_string(), expired(Some("refresh")))
+        .await
+        .unwrap();
+
+    store.cleanup_unrefreshable().await.unwrap();
+    assert!(store.get_token("dead").await.unwrap().is_none());
+    let kept = store.get_token("renewable").await.unwrap().unwrap();
+    assert!(kept.expires_within(chrono::Duration::zero()));
+}
+
+#[test]
+fn test_jwt_config_validate() {
+    let mut config = JwtConfig::default();
+    assert!(config.validate().is_ok());
+
+    config.enabled = true;
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("issuer or jwks_uri"), "{err}");
+
+    config.issuer = Some("not a url".to_string());
+    assert!(config
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .contains("issuer"));
+
+    config.issuer = Some("https://auth.example.com/realms/demo".to_string());
+    assert!(config.validate().is_ok());
+
+    config.jwks_cache_ttl_secs = 0;
+    assert!(config
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .contains("jwks_cache_ttl_secs"));
+
+    let config = AuthConfig {
+        jwt: JwtConfig {
+            enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+    assert!(config.is_enabled());
+}
+
 // ============================================================================
 // API Key comprehensive tests (feature-gated)
 // ============================================================================
@@ -683,6 +775,72 @@ mod api_key_comprehensive_tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_api_key_config_validate_unreadable_keys_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = ApiKeyConfig {
+            enabled: true,
+            keys_file: Some(dir.path().join("missing").display().to_string()),
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(result.unwrap_err().to_string().contains("keys_file"));
+
+        let path = dir.path().join("keys");
+        std::fs::write(&path, "# comment\nfile_key\n").unwrap();
+        config.keys_file = Some(path.display().to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(config.all_keys().unwrap(), ["file_key"]);
+    }
+
+    #[test]
+    fn test_api_key_manager_accepts_keys_file_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys");
+        std::fs::write(&path, "file_key\n").unwrap();
+        let config = AuthConfig {
+            api_key: ApiKeyConfig {
+                enabled: true,
+                keys: vec!["inline_key".to_string()],
+                keys_file: Some(path.display().to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let manager = AuthManager::with_config(config).unwrap();
+        assert!(manager.validate_api_key("inline_key"));
+        assert!(manager.validate_api_key("file_key"));
+        assert!(!manager.validate_api_key("other_key"));
+
+        // Read once: later changes of the file are not seen
+        std::fs::write(&path, "").unwrap();
+        assert!(manager.validate_api_key("file_key"));
+    }
+
+    #[test]
+    fn test_api_key_find_key_reports_key_id() {
+        let generated = ApiKeyConfig::default().generate_key().unwrap();
+        let config = ApiKeyConfig {
+            enabled: true,
+            keys: vec!["plain_key".to_string(), generated.hash.clone()],
+            ..Default::default()
+        };
+        let salt = generated.hash.split('$').nth(4).unwrap();
+        assert_eq!(
+            config.find_key(&generated.key, &config.keys).as_deref(),
+            Some(salt)
+        );
+        assert_eq!(
+            config.find_key("plain_key", &config.keys).as_deref(),
+            Some("key-1")
+        );
+        assert_eq!(config.find_key("wrong_key", &config.keys), None);
+        assert_eq!(
+            ApiKeyConfig::key_id(&format!("legacy:{}", generated.hash), 0),
+            salt
+        );
+    }
+
     #[test]
     fn test_api_key_is_valid_with_multiple_keys() {
         let generator = ApiKeyConfig::default();
@@ -53,6 +53,14 @@ pub struct TokenInfo {
     pub user_email: Option<String>,
 }
 
+impl TokenInfo {
+    /// Whether the token is expired or expires within `window` from now
+    #[must_use]
+    pub fn expires_within(&self, window: chrono::Duration) -> bool {
+        self.expires_at <= chrono::Utc::now() + window
+    }
+}
+
 impl TokenStore {
     /// Create a new token store
     #[must_use]
@@ -104,6 +112,21 @@ impl TokenStore {
         Ok(())
     }
 
+    /// Remove expired tokens that cannot be refreshed
+    ///
+    /// Expired tokens with a refresh token are kept so they can still be
+    /// renewed the next time they are presented.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TokenStoreError::LockTimeout` if write lock cannot be acquired within timeout.
+    pub async fn cleanup_unrefreshable(&self) -> TokenStoreResult<()> {
+        let now = chrono::Utc::now();
+        let mut guard = self.acquire_write_lock().await?;
+        guard.retain(|_, token| token.expires_at > now || token.refresh_token.is_some());
+        Ok(())
+    }
+
     /// Acquire a read lock with timeout protection
     ///
     /// Uses `tokio::time::timeout` to prevent indefinite blocking.
@@ -19,14 +19,26 @@ use crate::server::auth::ApiKeyConfig;
 #[cfg(feature = "api-key")]
 pub struct ApiKeyMiddleware {
     config: ApiKeyConfig,
+    /// Inline keys plus those of `keys_file`, read once
+    keys: Vec<String>,
 }
 
 #[cfg(feature = "api-key")]
 impl ApiKeyMiddleware {
     /// Create a new API Key middleware
+    ///
+    /// An unreadable `keys_file` is logged and its keys are rejected.
     #[must_use]
     pub fn new(config: ApiKeyConfig) -> Self {
-        Self { config }
+        let keys = config.all_keys().unwrap_or_else(|e| {
+            tracing::error!("{e}; only the inline API keys are accepted");
+            config.keys.clone()
+        });
+        Self { config, keys }
+    }
+
+    fn is_valid_key(&self, key: &str) -> bool {
+        self.config.find_key(key, &self.keys).is_some()
     }
 
     /// Validate API key from headers or query parameters
@@ -51,14 +63,14 @@ impl ApiKeyMiddleware {
 
         // Try to get API key from header first
         if let Some(key) = headers.get(&self.config.header_name) {
-            return self.config.is_valid_key(key);
+            return self.is_valid_key(key);
         }
 
         // Fallback to query parameter if allowed
         if self.config.allow_query_param {
             if let Some(params) = query_params {
                 if let Some(key) = params.get(&self.config.query_param_name) {
-                    return self.config.is_valid_key(key);
+                    return self.is_valid_key(key);
                 }
             }
         }
@@ -0,0 +1,276 @@
+//! Caller addresses of HTTP sessions
+//!
+//! The SDK's HTTP server hands neither the peer address nor the request
+//! headers to the MCP handler, so per-IP rate limiting cannot tell callers
+//! apart on its own. When per-client rate limiting is on, each listener is
+//! fronted by a small proxy that records the caller address of every request
+//! carrying a session id and forwards the request unchanged to the SDK server
+//! on a loopback port.
+//!
+//! The caller address is the first `X-Forwarded-For` entry with
+//! `server.trust_forwarded_headers`, else the peer IP.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode, Uri, Version};
+use axum::response::{IntoResponse, Response};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+
+use super::forwarded::Forwarded;
+
+/// Header carrying the session of a Streamable HTTP request
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// Query parameter carrying the session of an SSE message
+const SESSION_ID_QUERY: &str = "sessionId";
+
+/// Sessions without a request for this long are forgotten
+const IDLE_SESSION_TTL: Duration = Duration::from_mins(30);
+
+/// Prune idle sessions once the map grows past this many sessions
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Address a session was last seen from
+#[derive(Debug)]
+struct Seen {
+    addr: String,
+    at: Instant,
+}
+
+/// Last caller address of each HTTP session
+#[derive(Debug, Default)]
+pub struct ClientAddrs {
+    sessions: Mutex<HashMap<String, Seen>>,
+}
+
+impl ClientAddrs {
+    /// Create an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `session_id` sent a request from `addr`
+    pub fn record(&self, session_id: &str, addr: &str) {
+        self.record_at(session_id, addr, Instant::now());
+    }
+
+    fn record_at(&self, session_id: &str, addr: &str, now: Instant) {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if sessions.len() >= PRUNE_THRESHOLD {
+            sessions.retain(|_, seen| now.duration_since(seen.at) < IDLE_SESSION_TTL);
+        }
+        sessions.insert(
+            session_id.to_string(),
+            Seen {
+                addr: addr.to_string(),
+                at: now,
+            },
+        );
+    }
+
+    /// Address `session_id` last sent a request from, if known
+    #[must_use]
+    pub fn get(&self, session_id: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(session_id)
+            .map(|seen| seen.addr.clone())
+    }
+}
+
+/// Shared state of a listener's proxy
+#[derive(Clone)]
+struct Proxy {
+    backend: SocketAddr,
+    client: Client<HttpConnector, Body>,
+    addrs: Arc<ClientAddrs>,
+    forwarded: Forwarded,
+}
+
+/// Serve `listener`, forwarding every request to the SDK server at `backend`
+///
+/// # Errors
+///
+/// Returns an error if accepting connections fails
+pub(crate) async fn serve(
+    listener: tokio::net::TcpListener,
+    backend: SocketAddr,
+    addrs: Arc<ClientAddrs>,
+    forwarded: Forwarded,
+) -> std::io::Result<()> {
+    let proxy = Proxy {
+        backend,
+        client: Client::builder(TokioExecutor::new()).build_http(),
+        addrs,
+        forwarded,
+    };
+    let app = axum::Router::new().fallback(forward).with_state(proxy);
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+}
+
+/// Record the caller of `request` and pass it on to the SDK server
+async fn forward(
+    State(proxy): State<Proxy>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    mut request: Request,
+) -> Response {
+    if let Some(session_id) = session_id(request.headers(), request.uri()) {
+        let addr = caller_addr(proxy.forwarded, request.headers(), peer);
+        proxy.addrs.record(&session_id, &addr);
+    }
+
+    // HTTP/2 requests carry the host in the URI only; keep it for the
+    // SDK's DNS rebinding check
+    if let Some(authority) = request.uri().authority() {
+        if let Ok(host) = HeaderValue::from_str(authority.as_str()) {
+            request.headers_mut().entry(header::HOST).or_insert(host);
+        }
+    }
+    let path = request
+        .uri()
+        .path_and_query()
+        .map_or("/", |path| path.as_str());
+    let Ok(uri) = Uri::try_from(format!("http://{}{path}", proxy.backend)) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    *request.uri_mut() = uri;
+    *request.version_mut() = Version::HTTP_11;
+
+    match proxy.client.request(request).await {
+        Ok(response) => response.map(Body::new).into_response(),
+        Err(e) => {
+            tracing::warn!("Failed to forward request to the MCP server: {}", e);
+            StatusCode::BAD_GATEWAY.into_response()
+        }
+    }
+}
+
+/// Session a request belongs to: the `Mcp-Session-Id` header, or the
+/// `sessionId` query parameter of SSE messages
+fn session_id(headers: &HeaderMap, uri: &Uri) -> Option<String> {
+    if let Some(id) = headers
+        .get(SESSION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        return Some(id.to_string());
+    }
+    url::form_urlencoded::parse(uri.query()?.as_bytes())
+        .find(|(name, _)| name == SESSION_ID_QUERY)
+        .map(|(_, id)| id.into_owned())
+}
+
+/// Address of the caller: the forwarded client address if trusted, else the
+/// peer IP
+fn caller_addr(forwarded: Forwarded, headers: &HeaderMap, peer: SocketAddr) -> String {
+    forwarded
+        .client_addr(headers)
+        .map_or_else(|| peer.ip().to_string(), str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_addrs_keep_last_address() {
+        let addrs = ClientAddrs::new();
+        assert_eq!(addrs.get("s1"), None);
+
+        addrs.record("s1", "203.0.113.7");
+        addrs.record("s1", "203.0.113.8");
+        addrs.record("s2", "203.0.113.7");
+        assert_eq!(addrs.get("s1").as_deref(), Some("203.0.113.8"));
+        assert_eq!(addrs.get("s2").as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn test_client_addrs_prune_idle_sessions() {
+        let addrs = ClientAddrs::new();
+        let start = Instant::now();
+        for i in 0..PRUNE_THRESHOLD {
+            addrs.record_at(&format!("s{i}"), "203.0.113.7", start);
+        }
+        addrs.record_at("late", "203.0.113.8", start + IDLE_SESSION_TTL);
+
+        assert_eq!(addrs.get("s0"), None);
+        assert_eq!(addrs.get("late").as_deref(), Some("203.0.113.8"));
+    }
+
+    #[test]
+    fn test_session_id_from_header_or_query() {
+        let mut headers = HeaderMap::new();
+        let uri: Uri = "/messages?sessionId=abc".parse().unwrap();
+        assert_eq!(session_id(&headers, &uri).as_deref(), Some("abc"));
+
+        headers.insert(SESSION_ID_HEADER, HeaderValue::from_static("xyz"));
+        assert_eq!(session_id(&headers, &uri).as_deref(), Some("xyz"));
+        assert_eq!(
+            session_id(&HeaderMap::new(), &"/mcp".parse().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_caller_addr_uses_forwarded_only_when_trusted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+        let peer: SocketAddr = "10.0.0.1:51234".parse().unwrap();
+
+        assert_eq!(
+            caller_addr(Forwarded::new(true), &headers, peer),
+            "203.0.113.7"
+        );
+        assert_eq!(
+            caller_addr(Forwarded::new(false), &headers, peer),
+            "10.0.0.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_proxy_records_caller_and_forwards() {
+        let backend = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let backend_addr = backend.local_addr().unwrap();
+        tokio::spawn(async move {
+            let app = axum::Router::new().fallback(|uri: Uri| async move { uri.to_string() });
+            axum::serve(backend, app).await
+        });
+
+        let front = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let front_addr = front.local_addr().unwrap();
+        let addrs = Arc::new(ClientAddrs::new());
+        tokio::spawn(serve(
+            front,
+            backend_addr,
+            Arc::clone(&addrs),
+            Forwarded::new(false),
+        ));
+
+        let body = reqwest::Client::new()
+            .get(format!("http://{front_addr}/mcp?x=1"))
+            .header(SESSION_ID_HEADER, "s1")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "/mcp?x=1");
+        assert_eq!(addrs.get("s1").as_deref(), Some("127.0.0.1"));
+    }
+}
@@ -0,0 +1,218 @@
+//! MCP log messages for connected clients
+//!
+//! Events of this crate emitted while serving a client's request, such as
+//! upstream failures, cache fallbacks and rate limiting, are sent to that
+//! client as `notifications/message` (see [`crate::logging::ClientLogLayer`]).
+//! Each client chooses the least severe level it receives with
+//! `logging/setLevel`; until then it receives warnings and errors.
+//!
+//! Events below the server's own log level are never emitted, so they cannot
+//! be forwarded either. Sessions are held weakly, so ending a session needs
+//! no cleanup here.
+
+use rust_mcp_sdk::schema::{LoggingLevel, LoggingMessageNotificationParams};
+use rust_mcp_sdk::McpServer;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::logging::ClientLogRecord;
+use crate::tools::docs::profile::session_key;
+
+/// Level of clients that have not sent `logging/setLevel`
+pub const DEFAULT_CLIENT_LEVEL: LoggingLevel = LoggingLevel::Warning;
+
+struct Client {
+    runtime: Weak<dyn McpServer>,
+    level: LoggingLevel,
+}
+
+/// Initialized sessions with their log levels, and the task forwarding log
+/// events to them
+pub struct ClientLogForwarder {
+    /// Keyed by session key
+    sessions: Mutex<HashMap<String, Client>>,
+    forwarder_started: AtomicBool,
+}
+
+impl Default for ClientLogForwarder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientLogForwarder {
+    /// Create a forwarder without sessions
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            forwarder_started: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Client>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Send log events of the session of `runtime` to its client, at the
+    /// level it set before or [`DEFAULT_CLIENT_LEVEL`]
+    ///
+    /// The first session starts the task forwarding events.
+    pub fn register(self: &Arc<Self>, runtime: &Arc<dyn McpServer>) {
+        self.insert(runtime, None);
+    }
+
+    /// Send log events of the session of `runtime` at `level` and above
+    pub fn set_level(self: &Arc<Self>, runtime: &Arc<dyn McpServer>, level: LoggingLevel) {
+        self.insert(runtime, Some(level));
+        tracing::debug!("Client log level set to {level}");
+    }
+
+    fn insert(self: &Arc<Self>, runtime: &Arc<dyn McpServer>, level: Option<LoggingLevel>) {
+        let mut sessions = self.lock();
+        sessions.retain(|_, client| client.runtime.strong_count() > 0);
+        let client = sessions
+            .entry(session_key(runtime.session_id()))
+            .or_insert_with(|| Client {
+                runtime: Arc::downgrade(runtime),
+                level: DEFAULT_CLIENT_LEVEL,
+            });
+        client.runtime = Arc::downgrade(runtime);
+        if let Some(level) = level {
+            client.level = level;
+        }
+        drop(sessions);
+
+        if !self.forwarder_started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(forward_all(
+                Arc::downgrade(self),
+                crate::logging::subscribe_client_logs(),
+            ));
+        }
+    }
+
+    /// Level of the session with key `session`, if it is connected
+    #[must_use]
+    pub fn level(&self, session: &str) -> Option<LoggingLevel> {
+        self.lock()
+            .get(session)
+            .filter(|client| client.runtime.strong_count() > 0)
+            .map(|client| client.level)
+    }
+
+    /// Send `record` to its session as `notifications/message`, if the
+    /// session is connected and wants events of its level
+    ///
+    /// Returns whether the message was sent; sessions failing to receive it
+    /// are dropped.
+    pub async fn forward(&self, record: &ClientLogRecord) -> bool {
+        let level = mcp_level(record.level);
+        let runtime = {
+            let sessions = self.lock();
+            let Some(client) = sessions.get(&record.session) else {
+                return false;
+            };
+            if severity(level) < severity(client.level) {
+                return false;
+            }
+            client.runtime.upgrade()
+        };
+        let Some(runtime) = runtime else {
+            self.lock().remove(&record.session);
+            return false;
+        };
+
+        let params = LoggingMessageNotificationParams {
+            data: serde_json::Value::String(record.message.clone()),
+            level,
+            logger: Some(record.target.clone()),
+            meta: None,
+        };
+        match runtime.notify_log_message(params).await {
+            Ok(()) => true,
+            Err(e) => {
+                // Not in a session scope, so this is not forwarded in turn
+                tracing::debug!("Dropping session {} from log messages: {e}", record.session);
+                self.lock().remove(&record.session);
+                false
+            }
+        }
+    }
+}
+
+/// Forward captured events until the forwarder is dropped
+async fn forward_all(
+    forwarder: Weak<ClientLogForwarder>,
+    mut records: broadcast::Receiver<ClientLogRecord>,
+) {
+    loop {
+        let record = match records.recv().await {
+            Ok(record) => record,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!("Skipped {skipped} client log message(s)");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        let Some(forwarder) = forwarder.upgrade() else {
+            break;
+        };
+        forwarder.forward(&record).await;
+    }
+}
+
+/// MCP level of a `tracing` level
+fn mcp_level(level: tracing::Level) -> LoggingLevel {
+    match level {
+        tracing::Level::ERROR => LoggingLevel::Error,
+        tracing::Level::WARN => LoggingLevel::Warning,
+        tracing::Level::INFO => LoggingLevel::Info,
+        _ => LoggingLevel::Debug,
+    }
+}
+
+/// Syslog severity rank of an MCP level, `Debug` lowest
+///
+/// `LoggingLevel` orders alphabetically, not by severity.
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_by_severity() {
+        assert_eq!(mcp_level(tracing::Level::WARN), LoggingLevel::Warning);
+        assert_eq!(mcp_level(tracing::Level::TRACE), LoggingLevel::Debug);
+        assert!(severity(LoggingLevel::Warning) > severity(LoggingLevel::Notice));
+        assert!(severity(LoggingLevel::Emergency) > severity(LoggingLevel::Error));
+    }
+
+    #[tokio::test]
+    async fn test_records_of_unknown_sessions_are_not_forwarded() {
+        let forwarder = ClientLogForwarder::new();
+        assert_eq!(forwarder.level("s1"), None);
+        let record = ClientLogRecord {
+            session: "s1".to_string(),
+            level: tracing::Level::ERROR,
+            target: "crates_docs".to_string(),
+            message: "Upstream failed".to_string(),
+        };
+        assert!(!forwarder.forward(&record).await);
+    }
+}
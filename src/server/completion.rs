@@ -0,0 +1,242 @@
+//! Argument completion
+//!
+//! `completion/complete` suggests values for the arguments of the prompts
+//! and of the `docs://` resource templates: crate names from a crates.io
+//! search, item paths from the `all.html` item index of the crate (as used
+//! by `find_symbol`), and `latest` for template versions.
+//!
+//! Item paths are completed for the crate given as the `crate` argument
+//! before, or else for the crate the typed path starts with.
+
+use rust_mcp_sdk::schema::{
+    CompleteRequestParams, CompleteRequestRef, CompleteResult, CompleteResultCompletion, RpcError,
+};
+
+use crate::server::CratesDocsServer;
+
+/// Most values of one completion, as allowed by MCP
+const MAX_VALUES: usize = 100;
+
+/// Version segment of template URIs for the latest release
+const LATEST: &str = "latest";
+
+/// What an argument holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentKind {
+    CrateName,
+    Version,
+    ItemPath,
+    /// Free text, not completed
+    Other,
+}
+
+/// Completions for an argument of a prompt or resource template
+///
+/// # Errors
+///
+/// Returns an invalid-params error for an unknown prompt or resource template
+pub async fn complete(
+    server: &CratesDocsServer,
+    params: &CompleteRequestParams,
+) -> std::result::Result<CompleteResult, RpcError> {
+    let name = params.argument.name.as_str();
+    let kind = match &params.ref_ {
+        CompleteRequestRef::PromptReference(prompt) => {
+            if !crate::server::prompts::list_prompts()
+                .iter()
+                .any(|known| known.name == prompt.name)
+            {
+                return Err(RpcError::invalid_params()
+                    .with_message(format!("Unknown prompt '{}'", prompt.name)));
+            }
+            // Prompt versions are semver requirements, not `latest`
+            match argument_kind(name) {
+                ArgumentKind::Version => ArgumentKind::Other,
+                kind => kind,
+            }
+        }
+        CompleteRequestRef::ResourceTemplateReference(template) => {
+            if !crate::server::resources::docs_resource_templates()
+                .iter()
+                .any(|known| known.uri_template == template.uri)
+            {
+                return Err(RpcError::invalid_params()
+                    .with_message(format!("Unknown resource template '{}'", template.uri)));
+            }
+            argument_kind(name)
+        }
+    };
+
+    let value = params.argument.value.as_str();
+    let service = server.doc_service();
+    let values = match kind {
+        ArgumentKind::CrateName => {
+            crate::tools::docs::search::complete_crate_names(service, value).await
+        }
+        ArgumentKind::Version => [LATEST]
+            .into_iter()
+            .filter(|latest| latest.starts_with(value.trim()))
+            .map(str::to_string)
+            .collect(),
+        ArgumentKind::ItemPath => match item_crate(params) {
+            Some(crate_name) => {
+                crate::tools::docs::symbol::complete_item_paths(service, &crate_name, value).await
+            }
+            None => Vec::new(),
+        },
+        ArgumentKind::Other => Vec::new(),
+    };
+    Ok(completion(values))
+}
+
+fn argument_kind(name: &str) -> ArgumentKind {
+    match name {
+        "crate" | "crate_name" => ArgumentKind::CrateName,
+        "version" => ArgumentKind::Version,
+        "item_path" => ArgumentKind::ItemPath,
+        _ => ArgumentKind::Other,
+    }
+}
+
+/// Crate whose item paths complete the argument: the `crate` argument given
+/// before, or the first segment of a typed path
+fn item_crate(params: &CompleteRequestParams) -> Option<String> {
+    let given = params
+        .context
+        .as_ref()
+        .and_then(|context| context.arguments.as_ref())
+        .and_then(|arguments| {
+            ["crate", "crate_name"]
+                .into_iter()
+                .find_map(|name| arguments.get(name))
+        })
+        .map(|crate_name| crate_name.trim())
+        .filter(|crate_name| !crate_name.is_empty());
+    let typed = params
+        .argument
+        .value
+        .trim()
+        .split_once("::")
+        .map(|(root, _)| root);
+    given.or(typed).map(str::to_string)
+}
+
+/// The first [`MAX_VALUES`] of `values`, with their total
+fn completion(mut values: Vec<String>) -> CompleteResult {
+    let total = values.len();
+    values.truncate(MAX_VALUES);
+    CompleteResult {
+        completion: CompleteResultCompletion {
+            has_more: Some(total > values.len()),
+            total: i64::try_from(total).ok(),
+            values,
+        },
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppConfig;
+    use rust_mcp_sdk::schema::{
+        CompleteRequestArgument, CompleteRequestContext, PromptReference, ResourceTemplateReference,
+    };
+
+    fn request(ref_: CompleteRequestRef, name: &str, value: &str) -> CompleteRequestParams {
+        CompleteRequestParams {
+            argument: CompleteRequestArgument {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+            context: None,
+            meta: None,
+            ref_,
+        }
+    }
+
+    fn item_template() -> CompleteRequestRef {
+        ResourceTemplateReference::new("docs://{crate}/{version}/{item_path}".to_string()).into()
+    }
+
+    #[tokio::test]
+    async fn test_complete_template_arguments() {
+        let server = CratesDocsServer::new(AppConfig::default()).unwrap();
+        let index = serde_json::json!([
+            { "module": "", "kind": "trait", "name": "Serialize" },
+            { "module": "ser", "kind": "trait", "name": "Serializer" },
+            { "module": "de", "kind": "trait", "name": "Deserialize" },
+        ]);
+        server
+            .doc_service()
+            .doc_cache()
+            .set_symbol_index("serde", index.to_string())
+            .await
+            .unwrap();
+
+        let result = complete(&server, &request(item_template(), "item_path", "serde::S"))
+            .await
+            .unwrap();
+        assert_eq!(result.completion.values, ["serde::Serialize"]);
+
+        // By item name, for the crate given before
+        let mut params = request(item_template(), "item_path", "ser");
+        params.context = Some(CompleteRequestContext {
+            arguments: Some([("crate".to_string(), "serde".to_string())].into()),
+        });
+        let result = complete(&server, &params).await.unwrap();
+        assert_eq!(
+            result.completion.values,
+            ["serde::Serialize", "serde::ser::Serializer"]
+        );
+        assert_eq!(result.completion.total, Some(2));
+        assert_eq!(result.completion.has_more, Some(false));
+
+        // Without a crate there is nothing to complete
+        let result = complete(&server, &request(item_template(), "item_path", "Ser"))
+            .await
+            .unwrap();
+        assert!(result.completion.values.is_empty());
+
+        let result = complete(&server, &request(item_template(), "version", "la"))
+            .await
+            .unwrap();
+        assert_eq!(result.completion.values, [LATEST]);
+    }
+
+    #[tokio::test]
+    async fn test_complete_rejects_unknown_references() {
+        let server = CratesDocsServer::new(AppConfig::default()).unwrap();
+        let prompt = |name: &str| -> CompleteRequestRef {
+            PromptReference::new(name.to_string(), None).into()
+        };
+
+        assert!(
+            complete(&server, &request(prompt("no_such_prompt"), "crate", "se"))
+                .await
+                .is_err()
+        );
+        let template = ResourceTemplateReference::new("file:///{path}".to_string()).into();
+        assert!(complete(&server, &request(template, "path", "/"))
+            .await
+            .is_err());
+
+        // Free-text arguments are not completed
+        let result = complete(
+            &server,
+            &request(prompt("explain_crate_usage"), "task", "se"),
+        )
+        .await
+        .unwrap();
+        assert!(result.completion.values.is_empty());
+    }
+
+    #[test]
+    fn test_completion_is_capped() {
+        let values: Vec<String> = (0..150).map(|i| format!("crate-{i}")).collect();
+        let result = completion(values);
+        assert_eq!(result.completion.values.len(), MAX_VALUES);
+        assert_eq!(result.completion.total, Some(150));
+        assert_eq!(result.completion.has_more, Some(true));
+    }
+}
@@ -0,0 +1,619 @@
+//! SSE event store
+//!
+//! The HTTP/SSE transports record the messages they send on each stream so a
+//! client that reconnects with `Last-Event-ID` gets the messages it missed.
+//! The SDK keeps them in memory; the persistent backends here keep them
+//! outside the process so they survive a server restart.
+//!
+//! Three backends are available:
+//!
+//! - `memory`: the SDK's in-memory store (default)
+//! - `file`: one JSON file per session in a directory
+//! - `redis`: one key per server instance in the configured Redis cache,
+//!   holding that instance's sessions
+//!
+//! Event IDs use the SDK's format, `session-.-stream-.-timestamp`.
+
+use crate::cache::Cache;
+use async_trait::async_trait;
+use rust_mcp_sdk::event_store::{EventStore, EventStoreEntry, EventStoreError, InMemoryEventStore};
+use rust_mcp_sdk::{EventId, SessionId, StreamId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Separator of the parts of an event ID, as used by the SDK
+const ID_SEPARATOR: &str = "-.-";
+
+/// SSE event store backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStoreBackend {
+    /// In process memory; events are lost on restart
+    #[default]
+    Memory,
+    /// One file per session in `path`
+    File,
+    /// The Redis cache (`cache.cache_type = "redis"`)
+    Redis,
+}
+
+/// SSE event store configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the store is handed to the HTTP
+/// transport when the server starts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventStoreConfig {
+    /// Storage backend
+    #[serde(default)]
+    pub backend: EventStoreBackend,
+
+    /// Event directory (`file` backend)
+    #[serde(default = "default_event_store_path")]
+    pub path: String,
+
+    /// Name of this server instance (`redis` backend); replicas sharing a
+    /// Redis server need distinct names
+    #[serde(default = "default_event_store_instance")]
+    pub instance: String,
+
+    /// Events kept per session; older ones are dropped
+    #[serde(default = "default_event_store_max_events_per_session")]
+    pub max_events_per_session: usize,
+
+    /// Sessions without new events for this long are dropped (persistent
+    /// backends)
+    #[serde(default = "default_event_store_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+fn default_event_store_path() -> String {
+    EventStoreConfig::default().path
+}
+
+fn default_event_store_instance() -> String {
+    EventStoreConfig::default().instance
+}
+
+fn default_event_store_max_events_per_session() -> usize {
+    EventStoreConfig::default().max_events_per_session
+}
+
+fn default_event_store_ttl_secs() -> u64 {
+    EventStoreConfig::default().ttl_secs
+}
+
+impl Default for EventStoreConfig {
+    fn default() -> Self {
+        Self {
+            backend: EventStoreBackend::Memory,
+            path: "./data/events".to_string(),
+            instance: "default".to_string(),
+            max_events_per_session: 64,
+            ttl_secs: 3600,
+        }
+    }
+}
+
+impl EventStoreConfig {
+    /// Validate the event store configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error for a zero event limit or TTL, an empty
+    /// path or instance name, or the `redis` backend on a non-Redis cache
+    pub fn validate(&self, cache_type: &str) -> crate::error::Result<()> {
+        if self.max_events_per_session == 0 {
+            return Err(crate::error::Error::config(
+                "event_store.max_events_per_session",
+                "cannot be 0",
+            ));
+        }
+        if self.ttl_secs == 0 {
+            return Err(crate::error::Error::config(
+                "event_store.ttl_secs",
+                "cannot be 0",
+            ));
+        }
+        match self.backend {
+            EventStoreBackend::File if self.path.trim().is_empty() => Err(
+                crate::error::Error::config("event_store.path", "must not be empty"),
+            ),
+            EventStoreBackend::Redis if cache_type != "redis" => Err(crate::error::Error::config(
+                "event_store.backend",
+                "\"redis\" requires cache.cache_type = \"redis\"",
+            )),
+            EventStoreBackend::Redis if self.instance.trim().is_empty() => Err(
+                crate::error::Error::config("event_store.instance", "must not be empty"),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Open the event store described by `config`
+///
+/// The `file` backend creates its directory; the `redis` backend uses
+/// `cache`.
+///
+/// # Errors
+///
+/// Returns error if the event directory cannot be created
+pub fn open(
+    config: &EventStoreConfig,
+    cache: Arc<dyn Cache>,
+) -> crate::error::Result<Arc<dyn EventStore>> {
+    let store = match config.backend {
+        EventStoreBackend::Memory => {
+            return Ok(Arc::new(InMemoryEventStore::new(Some(
+                config.max_events_per_session,
+            ))));
+        }
+        EventStoreBackend::File => {
+            let dir = PathBuf::from(&config.path);
+            std::fs::create_dir_all(&dir)?;
+            Store::File(dir)
+        }
+        EventStoreBackend::Redis => Store::Redis {
+            cache,
+            key: format!("sse_events:{}", config.instance.trim()),
+        },
+    };
+    Ok(Arc::new(PersistentEventStore {
+        store,
+        max_events_per_session: config.max_events_per_session,
+        ttl: Duration::from_secs(config.ttl_secs),
+        lock: tokio::sync::Mutex::new(()),
+    }))
+}
+
+/// A stored event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StoredEvent {
+    stream_id: StreamId,
+    /// Microseconds since the Unix epoch, as handed in by the transport
+    timestamp: u128,
+    message: String,
+}
+
+type Sessions = BTreeMap<SessionId, Vec<StoredEvent>>;
+
+enum Store {
+    File(PathBuf),
+    Redis { cache: Arc<dyn Cache>, key: String },
+}
+
+/// Event store keeping events in files or in the Redis cache
+pub struct PersistentEventStore {
+    store: Store,
+    max_events_per_session: usize,
+    ttl: Duration,
+    // Serializes the read-modify-write of the stored sessions
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl PersistentEventStore {
+    /// Events of `session_id`, oldest first
+    async fn load(&self, session_id: &str) -> Result<Vec<StoredEvent>, EventStoreError> {
+        match &self.store {
+            Store::File(dir) => match tokio::fs::read(session_path(dir, session_id)).await {
+                Ok(bytes) => serde_json::from_slice(&bytes).map_err(store_error),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+                Err(e) => Err(store_error(e)),
+            },
+            Store::Redis { .. } => Ok(self.load_all().await.remove(session_id).unwrap_or_default()),
+        }
+    }
+
+    /// Replace the events of `session_id`; no events removes the session
+    async fn save(
+        &self,
+        session_id: &str,
+        events: Vec<StoredEvent>,
+    ) -> Result<(), EventStoreError> {
+        match &self.store {
+            Store::File(dir) => {
+                let path = session_path(dir, session_id);
+                if events.is_empty() {
+                    return match tokio::fs::remove_file(path).await {
+                        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(store_error(e)),
+                        _ => Ok(()),
+                    };
+                }
+                let json = serde_json::to_vec(&events).map_err(store_error)?;
+                tokio::fs::write(path, json).await.map_err(store_error)
+            }
+            Store::Redis { .. } => {
+                let mut sessions = self.load_all().await;
+                if events.is_empty() {
+                    sessions.remove(session_id);
+                } else {
+                    sessions.insert(session_id.to_string(), events);
+                }
+                self.save_all(sessions).await
+            }
+        }
+    }
+
+    /// All sessions (`redis` backend)
+    async fn load_all(&self) -> Sessions {
+        let Store::Redis { cache, key } = &self.store else {
+            return Sessions::new();
+        };
+        cache
+            .get(key)
+            .await
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Replace all sessions (`redis` backend), dropping expired ones
+    async fn save_all(&self, mut sessions: Sessions) -> Result<(), EventStoreError> {
+        let Store::Redis { cache, key } = &self.store else {
+            return Ok(());
+        };
+        let cutoff = self.cutoff();
+        sessions.retain(|_, events| newest(events) >= cutoff);
+        if sessions.is_empty() {
+            return cache.delete(key).await.map_err(store_error);
+        }
+        let json = serde_json::to_string(&sessions).map_err(store_error)?;
+        cache
+            .set(key.clone(), json.into(), Some(self.ttl))
+            .await
+            .map_err(store_error)
+    }
+
+    /// Session files (`file` backend)
+    async fn session_files(&self) -> Result<Vec<PathBuf>, EventStoreError> {
+        let Store::File(dir) = &self.store else {
+            return Ok(Vec::new());
+        };
+        let mut paths = Vec::new();
+        let mut files = tokio::fs::read_dir(dir).await.map_err(store_error)?;
+        while let Some(file) = files.next_entry().await.map_err(store_error)? {
+            let path = file.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Remove session files without events newer than the TTL
+    async fn prune_files(&self) -> Result<(), EventStoreError> {
+        let cutoff = self.cutoff();
+        for path in self.session_files().await? {
+            let expired = match tokio::fs::read(&path).await {
+                Ok(bytes) => serde_json::from_slice::<Vec<StoredEvent>>(&bytes)
+                    .map_or(true, |events| newest(&events) < cutoff),
+                Err(_) => false,
+            };
+            if expired {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Timestamp before which events are expired
+    fn cutoff(&self) -> u128 {
+        SystemTime::now()
+            .checked_sub(self.ttl)
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_micros())
+    }
+}
+
+#[async_trait]
+impl EventStore for PersistentEventStore {
+    async fn store_event(
+        &self,
+        session_id: SessionId,
+        stream_id: StreamId,
+        timestamp: u128,
+        message: String,
+    ) -> Result<EventId, EventStoreError> {
+        let event_id = format!("{session_id}{ID_SEPARATOR}{stream_id}{ID_SEPARATOR}{timestamp}");
+        let _guard = self.lock.lock().await;
+        let mut events = self.load(&session_id).await?;
+        if events.is_empty() {
+            // A new session is a good time to drop the abandoned ones: the
+            // transport never removes sessions from the store itself
+            self.prune_files().await?;
+        }
+        events.push(StoredEvent {
+            stream_id,
+            timestamp,
+            message,
+        });
+        let excess = events.len().saturating_sub(self.max_events_per_session);
+        events.drain(..excess);
+        self.save(&session_id, events).await?;
+        Ok(event_id)
+    }
+
+    async fn remove_by_session_id(&self, session_id: SessionId) -> Result<(), EventStoreError> {
+        let _guard = self.lock.lock().await;
+        self.save(&session_id, Vec::new()).await
+    }
+
+    async fn remove_stream_in_session(
+        &self,
+        session_id: SessionId,
+        stream_id: StreamId,
+    ) -> Result<(), EventStoreError> {
+        let _guard = self.lock.lock().await;
+        let mut events = self.load(&session_id).await?;
+        events.retain(|event| event.stream_id != stream_id);
+        self.save(&session_id, events).await
+    }
+
+    async fn clear(&self) -> Result<(), EventStoreError> {
+        let _guard = self.lock.lock().await;
+        match &self.store {
+            Store::File(_) => {
+                for path in self.session_files().await? {
+                    tokio::fs::remove_file(path).await.map_err(store_error)?;
+                }
+                Ok(())
+            }
+            Store::Redis { .. } => self.save_all(Sessions::new()).await,
+        }
+    }
+
+    async fn events_after(
+        &self,
+        last_event_id: EventId,
+    ) -> Result<Option<EventStoreEntry>, EventStoreError> {
+        let (session_id, stream_id, timestamp) = parse_event_id(&last_event_id)?;
+        let events = {
+            let _guard = self.lock.lock().await;
+            self.load(session_id).await?
+        };
+        if events.is_empty() {
+            tracing::warn!("No stored events for session '{session_id}'");
+            return Ok(None);
+        }
+        let messages = events
+            .iter()
+            .position(|e| e.stream_id == stream_id && e.timestamp == timestamp)
+            .map(|index| {
+                events[index + 1..]
+                    .iter()
+                    .filter(|e| e.stream_id == stream_id)
+                    .map(|e| e.message.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Some(EventStoreEntry {
+            session_id: session_id.to_string(),
+            stream_id: stream_id.to_string(),
+            messages,
+        }))
+    }
+
+    async fn prune_excess_events(
+        &self,
+        _session_id: Option<SessionId>,
+    ) -> Result<(), EventStoreError> {
+        let _guard = self.lock.lock().await;
+        match &self.store {
+            Store::File(_) => self.prune_files().await,
+            Store::Redis { .. } => self.save_all(self.load_all().await).await,
+        }
+    }
+
+    async fn count(&self) -> Result<usize, EventStoreError> {
+        let _guard = self.lock.lock().await;
+        match &self.store {
+            Store::File(_) => Ok(self.session_files().await?.len()),
+            Store::Redis { .. } => Ok(self.load_all().await.len()),
+        }
+    }
+}
+
+/// File holding the events of `session_id`
+///
+/// Session IDs come from the transport, but are hex-encoded unless they are
+/// plain identifiers so they cannot name a path outside `dir`.
+fn session_path(dir: &std::path::Path, session_id: &str) -> PathBuf {
+    let plain = !session_id.is_empty()
+        && session_id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    let name = if plain {
+        session_id.to_string()
+    } else {
+        session_id.bytes().fold(String::new(), |mut name, b| {
+            let _ = write!(name, "{b:02x}");
+            name
+        })
+    };
+    dir.join(format!("{name}.json"))
+}
+
+/// Split an event ID into session, stream and timestamp
+fn parse_event_id(event_id: &str) -> Result<(&str, &str, u128), EventStoreError> {
+    let mut parts = event_id.split(ID_SEPARATOR);
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(session), Some(stream), Some(timestamp), None)
+            if !session.is_empty() && !stream.is_empty() =>
+        {
+            let timestamp = timestamp
+                .parse()
+                .map_err(|e| format!("Invalid event ID timestamp: {e}"))?;
+            Ok((session, stream, timestamp))
+        }
+        _ => Err(format!("Invalid event ID: '{event_id}'").into()),
+    }
+}
+
+/// Timestamp of the newest of `events`
+fn newest(events: &[StoredEvent]) -> u128 {
+    events.iter().map(|e| e.timestamp).max().unwrap_or(0)
+}
+
+fn store_error(e: impl std::fmt::Display) -> EventStoreError {
+    e.to_string().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::memory::MemoryCache;
+
+    fn now_micros() -> u128 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_micros()
+    }
+
+    fn file_store(dir: &std::path::Path) -> Arc<dyn EventStore> {
+        let config = EventStoreConfig {
+            backend: EventStoreBackend::File,
+            path: dir.to_string_lossy().into_owned(),
+            max_events_per_session: 3,
+            ..EventStoreConfig::default()
+        };
+        open(&config, Arc::new(MemoryCache::new(10))).unwrap()
+    }
+
+    async fn replay(store: &dyn EventStore, event_id: &str) -> Vec<String> {
+        store
+            .events_after(event_id.to_string())
+            .await
+            .unwrap()
+            .map(|entry| entry.messages)
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_file_store_replays_after_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = file_store(dir.path());
+        let t = now_micros();
+        let first = store
+            .store_event("s1".into(), "main".into(), t, "a".into())
+            .await
+            .unwrap();
+        store
+            .store_event("s1".into(), "other".into(), t + 1, "x".into())
+            .await
+            .unwrap();
+        store
+            .store_event("s1".into(), "main".into(), t + 2, "b".into())
+            .await
+            .unwrap();
+        assert_eq!(first, format!("s1-.-main-.-{t}"));
+
+        // A new store over the same directory, as after a restart
+        let reopened = file_store(dir.path());
+        assert_eq!(replay(reopened.as_ref(), &first).await, ["b"]);
+        assert_eq!(reopened.count().await.unwrap(), 1);
+
+        reopened
+            .remove_stream_in_session("s1".into(), "main".into())
+            .await
+            .unwrap();
+        assert!(replay(reopened.as_ref(), &first).await.is_empty());
+        reopened.clear().await.unwrap();
+        assert_eq!(reopened.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_store_keeps_newest_events_per_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = file_store(dir.path());
+        let t = now_micros();
+        let mut ids = Vec::new();
+        for (i, message) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            ids.push(
+                store
+                    .store_event("s1".into(), "main".into(), t + i as u128, message.into())
+                    .await
+                    .unwrap(),
+            );
+        }
+        // "a" was dropped, so its ID no longer resumes anything
+        assert!(replay(store.as_ref(), &ids[0]).await.is_empty());
+        assert_eq!(replay(store.as_ref(), &ids[1]).await, ["c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_redis_backend_shares_one_cache_key() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(10));
+        let config = EventStoreConfig {
+            backend: EventStoreBackend::Redis,
+            ..EventStoreConfig::default()
+        };
+        let store = open(&config, Arc::clone(&cache)).unwrap();
+        let t = now_micros();
+        let id = store
+            .store_event("s1".into(), "main".into(), t, "a".into())
+            .await
+            .unwrap();
+        store
+            .store_event("s2".into(), "main".into(), t + 1, "b".into())
+            .await
+            .unwrap();
+        store
+            .store_event("s1".into(), "main".into(), t + 2, "c".into())
+            .await
+            .unwrap();
+        assert!(cache.get("sse_events:default").await.is_some());
+
+        let reopened = open(&config, cache).unwrap();
+        assert_eq!(replay(reopened.as_ref(), &id).await, ["c"]);
+        assert_eq!(reopened.count().await.unwrap(), 2);
+        reopened.remove_by_session_id("s1".into()).await.unwrap();
+        assert_eq!(reopened.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_sessions_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = file_store(dir.path());
+        let stale = now_micros() - 2 * 3600 * 1_000_000;
+        store
+            .store_event("old".into(), "main".into(), stale, "a".into())
+            .await
+            .unwrap();
+        store
+            .store_event("new".into(), "main".into(), now_micros(), "b".into())
+            .await
+            .unwrap();
+        assert_eq!(store.count().await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_session_path_stays_in_directory() {
+        let dir = std::path::Path::new("/events");
+        assert_eq!(session_path(dir, "abc-1"), dir.join("abc-1.json"));
+        assert_eq!(session_path(dir, "../x"), dir.join("2e2e2f78.json"));
+    }
+
+    #[test]
+    fn test_parse_event_id() {
+        assert_eq!(parse_event_id("s-.-m-.-12").unwrap(), ("s", "m", 12));
+        assert!(parse_event_id("s-.-m").is_err());
+        assert!(parse_event_id("s-.-m-.-x").is_err());
+    }
+
+    #[test]
+    fn test_config_validate() {
+        let mut config = EventStoreConfig::default();
+        assert!(config.validate("memory").is_ok());
+        config.backend = EventStoreBackend::Redis;
+        assert!(config.validate("memory").is_err());
+        assert!(config.validate("redis").is_ok());
+        config.max_events_per_session = 0;
+        assert!(config.validate("redis").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Reverse-proxy awareness
+//!
+//! Behind nginx or Traefik the server sees the proxy's connection: plain HTTP
+//! from the proxy's address, often under a path prefix. With
+//! `server.trust_forwarded_headers` the `X-Forwarded-Proto`,
+//! `X-Forwarded-Host` and `X-Forwarded-For` headers set by the proxy are used
+//! instead to work out the public URL of the server and the client address.
+//!
+//! Only enable it when every request goes through a proxy that overwrites
+//! these headers; otherwise clients can spoof them.
+
+use http::HeaderMap;
+
+const X_FORWARDED_PROTO: &str = "x-forwarded-proto";
+const X_FORWARDED_HOST: &str = "x-forwarded-host";
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Normalize a configured path prefix: `""` or `/a/b` without trailing slash
+#[must_use]
+pub fn normalize_base_path(base_path: &str) -> String {
+    let trimmed = base_path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{trimmed}")
+    }
+}
+
+/// Reads the public view of a request, trusting forwarded headers or not
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Forwarded {
+    trusted: bool,
+}
+
+impl Forwarded {
+    /// Create a reader that uses the `X-Forwarded-*` headers if `trusted`
+    #[must_use]
+    pub fn new(trusted: bool) -> Self {
+        Self { trusted }
+    }
+
+    /// Whether the `X-Forwarded-*` headers are used
+    #[must_use]
+    pub fn is_trusted(self) -> bool {
+        self.trusted
+    }
+
+    /// First value of a forwarded header, if trusted and present
+    fn header<'a>(self, headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+        if !self.trusted {
+            return None;
+        }
+        headers
+            .get(name)?
+            .to_str()
+            .ok()?
+            .split(',')
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+    }
+
+    /// Scheme the client used: `X-Forwarded-Proto`, or `http`
+    #[must_use]
+    pub fn scheme(self, headers: &HeaderMap) -> &str {
+        self.header(headers, X_FORWARDED_PROTO).unwrap_or("http")
+    }
+
+    /// Host the client connected to: `X-Forwarded-Host`, or `Host`
+    #[must_use]
+    pub fn host(self, headers: &HeaderMap) -> Option<&str> {
+        self.header(headers, X_FORWARDED_HOST).or_else(|| {
+            headers
+                .get(http::header::HOST)
+                .and_then(|host| host.to_str().ok())
+        })
+    }
+
+    /// Original client address: the first `X-Forwarded-For` entry
+    #[must_use]
+    pub fn client_addr(self, headers: &HeaderMap) -> Option<&str> {
+        self.header(headers, X_FORWARDED_FOR)
+    }
+
+    /// Public URL of `path` (below `base_path`) as the client sees it
+    ///
+    /// Returns `None` without a `Host` header.
+    #[must_use]
+    pub fn public_url(self, headers: &HeaderMap, base_path: &str, path: &str) -> Option<String> {
+        let host = self.host(headers)?;
+        Some(format!(
+            "{}://{host}{base_path}{path}",
+            self.scheme(headers)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_normalize_base_path() {
+        assert_eq!(normalize_base_path(""), "");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path("mcp/crates-docs/"), "/mcp/crates-docs");
+        assert_eq!(normalize_base_path("/mcp"), "/mcp");
+    }
+
+    #[test]
+    fn test_forwarded_headers_only_used_when_trusted() {
+        let headers = headers(&[
+            ("host", "10.0.0.2:8080"),
+            ("x-forwarded-proto", "https"),
+            ("x-forwarded-host", "docs.example.com"),
+            ("x-forwarded-for", "203.0.113.7, 10.0.0.1"),
+        ]);
+
+        let trusted = Forwarded::new(true);
+        assert_eq!(trusted.client_addr(&headers), Some("203.0.113.7"));
+        assert_eq!(
+            trusted.public_url(&headers, "/mcp/crates-docs", "/oauth/callback"),
+            Some("https://docs.example.com/mcp/crates-docs/oauth/callback".to_string())
+        );
+
+        let untrusted = Forwarded::new(false);
+        assert_eq!(untrusted.client_addr(&headers), None);
+        assert_eq!(
+            untrusted.public_url(&headers, "", "/oauth/callback"),
+            Some("http://10.0.0.2:8080/oauth/callback".to_string())
+        );
+    }
+}
@@ -17,4 +17,4 @@ mod types;
 
 pub use config::HandlerConfig;
 pub use standard::CratesDocsHandler;
-pub use types::ToolExecutionResult;
+pub use types::{ToolExecutionResult, ACCESS_LOG_TARGET};
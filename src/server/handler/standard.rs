@@ -4,10 +4,15 @@ use async_trait::async_trait;
 use rust_mcp_sdk::{
     mcp_server::ServerHandler,
     schema::{
-        CallToolError, CallToolRequestParams, CallToolResult, GetPromptRequestParams,
-        GetPromptResult, ListPromptsResult, ListResourcesResult, ListToolsResult,
-        PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, RpcError,
+        CallToolError, CallToolRequestParams, CallToolResult, CancelTaskParams, CancelTaskResult,
+        CompleteRequestParams, CompleteResult, CreateTaskResult, GetPromptRequestParams,
+        GetPromptResult, GetTaskParams, GetTaskPayloadParams, GetTaskPayloadResult, GetTaskResult,
+        ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListTasksResult,
+        ListToolsResult, PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult,
+        RpcError, SetLevelRequestParams, SubscribeRequestParams, ToolExecutionTaskSupport,
+        UnsubscribeRequestParams,
     },
+    task_store::{CreateTaskOptions, ServerTaskCreator},
     McpServer,
 };
 use std::sync::Arc;
@@ -19,6 +24,7 @@ use super::types::ToolExecutionResult;
 use crate::metrics::ServerMetrics;
 use crate::server::CratesDocsServer;
 use crate::tools::ToolRegistry;
+use crate::utils::pagination::paginate;
 
 /// MCP server handler
 ///
@@ -29,6 +35,7 @@ use crate::tools::ToolRegistry;
 /// - `server`: Server instance
 /// - `config`: Handler configuration
 /// - `metrics`: Optional metrics collector
+#[derive(Clone)]
 pub struct CratesDocsHandler {
     server: Arc<CratesDocsServer>,
     config: HandlerConfig,
@@ -119,34 +126,89 @@ impl CratesDocsHandler {
         self.metrics.as_ref()
     }
 
-    /// Get all tools list
+    /// Items per page of list results
+    fn page_size(&self) -> usize {
+        self.server.config().server.page_size
+    }
+
+    /// Get the first page of the tools list
     #[must_use]
     pub fn list_tools(&self) -> ListToolsResult {
-        ListToolsResult {
-            tools: self.tool_registry().get_tools(),
+        self.list_tools_page(None)
+            .expect("the first page needs no cursor")
+    }
+
+    /// Get the page of the tools list after `cursor`
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for an unknown cursor
+    pub fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> std::result::Result<ListToolsResult, RpcError> {
+        let page = self
+            .tool_registry()
+            .get_tools_page(cursor, self.page_size())
+            .ok_or_else(|| invalid_cursor(cursor))?;
+        Ok(ListToolsResult {
+            tools: page.items,
             meta: None,
-            next_cursor: None,
-        }
+            next_cursor: page.next_cursor,
+        })
     }
 
-    /// Get empty resources list
-    #[must_use]
-    pub fn list_resources(&self) -> ListResourcesResult {
-        ListResourcesResult {
-            resources: vec![],
+    /// Get the page of the cached crate docs resources after `cursor`
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for an unknown cursor, or an internal
+    /// error if the cache could not be read
+    pub async fn list_resources(
+        &self,
+        cursor: Option<&str>,
+    ) -> std::result::Result<ListResourcesResult, RpcError> {
+        let resources = crate::server::resources::list_docs_resources(&self.server)
+            .await
+            .map_err(|e| RpcError::internal_error().with_message(e.to_string()))?;
+        let page = paginate(
+            resources,
+            |resource| &resource.uri,
+            cursor,
+            self.page_size(),
+        )
+        .ok_or_else(|| invalid_cursor(cursor))?;
+        Ok(ListResourcesResult {
+            resources: page.items,
             meta: None,
-            next_cursor: None,
-        }
+            next_cursor: page.next_cursor,
+        })
     }
 
-    /// Get empty prompts list
+    /// Get the first page of the documentation workflow prompts
     #[must_use]
     pub fn list_prompts(&self) -> ListPromptsResult {
-        ListPromptsResult {
-            prompts: vec![],
+        self.list_prompts_page(None)
+            .expect("the first page needs no cursor")
+    }
+
+    /// Get the page of the documentation workflow prompts after `cursor`
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for an unknown cursor
+    pub fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> std::result::Result<ListPromptsResult, RpcError> {
+        let prompts = crate::server::prompts::list_prompts();
+        let page = paginate(prompts, |prompt| &prompt.name, cursor, self.page_size())
+            .ok_or_else(|| invalid_cursor(cursor))?;
+        Ok(ListPromptsResult {
+            prompts: page.items,
             meta: None,
-            next_cursor: None,
-        }
+            next_cursor: page.next_cursor,
+        })
     }
 
     /// Execute tool call (core logic)
@@ -155,16 +217,18 @@ impl CratesDocsHandler {
     /// - tracing tracking
     /// - timing statistics
     /// - metrics recording (if enabled)
+    /// - request journaling (if enabled)
+    /// - a request ID and an access-log line (see [`ToolExecutionResult::log_access`])
     ///
     /// # Returns
     ///
     /// Returns `ToolExecutionResult`, can be converted to different types to adapt to different traits
     pub async fn execute_tool(&self, params: CallToolRequestParams) -> ToolExecutionResult {
-        let trace_id = Uuid::new_v4().to_string();
+        let request_id = Uuid::new_v4().to_string();
         let tool_name = params.name.clone();
         let span = info_span!(
             "execute_tool",
-            trace_id = %trace_id,
+            request_id = %request_id,
             tool = %tool_name,
             verbose = self.config.verbose_logging,
         );
@@ -180,11 +244,25 @@ impl CratesDocsHandler {
             // and tools with required fields produce a clear
             // "missing field ..." error instead of "invalid type: null".
             let arguments = serde_json::Value::Object(params.arguments.unwrap_or_default());
-
-            let result = self
-                .tool_registry()
-                .execute_tool(&tool_name, arguments)
-                .await;
+            let crate_name = arguments["crate_name"].as_str().map(str::to_string);
+
+            // Dropping the guard removes the journal entry
+            let journaled = match self.server.journal() {
+                Some(journal) => Some(journal.begin(&tool_name, &arguments).await),
+                None => None,
+            };
+
+            let timeout =
+                std::time::Duration::from_secs(self.server.config().server.response_timeout_secs);
+            let (result, lookups) = crate::cache::stats::track_lookups(tokio::time::timeout(
+                timeout,
+                self.tool_registry().execute_tool(&tool_name, arguments),
+            ))
+            .await;
+            let result = result.unwrap_or_else(|_| {
+                Err(crate::error::Error::timeout(format!("Tool '{tool_name}'"), timeout).into())
+            });
+            drop(journaled);
 
             let duration = start.elapsed();
             let success = result.is_ok();
@@ -212,32 +290,196 @@ impl CratesDocsHandler {
                 metrics.record_request(&tool_name, success, duration);
             }
 
-            ToolExecutionResult {
+            let execution = ToolExecutionResult {
+                request_id,
                 tool_name,
                 duration,
                 success,
                 result,
-            }
+            };
+            execution.log_access(crate_name.as_deref(), lookups.outcome());
+            execution
         }
         .instrument(span)
         .await
     }
+
+    /// Call a tool for the client of `runtime`
+    ///
+    /// Applies the client's rate limit, and scopes the call to its session
+    /// and negotiated output format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tool failed without an error result
+    pub async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        runtime: &Arc<dyn McpServer>,
+    ) -> std::result::Result<CallToolResult, CallToolError> {
+        // Scope the call to its session so per-session state (project
+        // profiles, the negotiated output format) is visible to the tool,
+        // and its log events, throttling included, reach the client.
+        crate::tools::docs::profile::with_session(
+            runtime.session_id(),
+            self.call_tool_in_session(params, runtime),
+        )
+        .await
+    }
+
+    async fn call_tool_in_session(
+        &self,
+        params: CallToolRequestParams,
+        runtime: &Arc<dyn McpServer>,
+    ) -> std::result::Result<CallToolResult, CallToolError> {
+        // The authenticated client (e.g. which API key was used), if any
+        let auth_info = runtime.auth_info_cloned().await;
+        if let Some(limiter) = self.server.rate_limiter() {
+            let session_id = runtime.session_id();
+            let client_addr = session_id
+                .as_deref()
+                .and_then(|id| self.server.client_addrs().get(id));
+            let key = rate_limit_key(
+                auth_info.as_ref(),
+                client_addr.as_deref(),
+                session_id.as_deref(),
+            );
+            if let Err(retry_after) = limiter.check(&key) {
+                tracing::warn!(
+                    "Throttled tool call {} from client {}; retry after {:?}",
+                    params.name,
+                    key,
+                    retry_after
+                );
+                let crate_name = params
+                    .arguments
+                    .as_ref()
+                    .and_then(|arguments| arguments.get("crate_name"))
+                    .and_then(serde_json::Value::as_str);
+                let execution = ToolExecutionResult {
+                    request_id: Uuid::new_v4().to_string(),
+                    tool_name: params.name.clone(),
+                    duration: std::time::Duration::ZERO,
+                    success: false,
+                    result: Err(crate::error::Error::rate_limited(key, retry_after).into()),
+                };
+                execution.log_access(crate_name, "none");
+                return execution.into_call_tool_result();
+            }
+        }
+        if self.tool_registry().requires_admin(&params.name)
+            && !is_admin_client(
+                &self.server.config().server.admin_clients,
+                auth_info.as_ref(),
+                runtime.session_id().as_deref(),
+            )
+        {
+            tracing::warn!("Refused admin tool {} to a non-admin client", params.name);
+            let execution = ToolExecutionResult {
+                request_id: Uuid::new_v4().to_string(),
+                tool_name: params.name.clone(),
+                duration: std::time::Duration::ZERO,
+                success: false,
+                result: Err(crate::error::Error::auth(
+                    "admin",
+                    format!(
+                        "tool '{}' is only available to clients listed in server.admin_clients",
+                        params.name
+                    ),
+                )
+                .into()),
+            };
+            execution.log_access(None, "none");
+            return execution.into_call_tool_result();
+        }
+        let client = auth_info.and_then(|info| info.client_id);
+
+        let format =
+            crate::tools::docs::negotiate::preferred_format(runtime.client_info().as_ref());
+        crate::tools::docs::negotiate::with_default_format(format, self.execute_tool(params))
+            .instrument(info_span!("client", id = client.as_deref()))
+            .await
+            .into_call_tool_result()
+    }
+}
+
+/// Cursor of a list request
+fn request_cursor(request: Option<&PaginatedRequestParams>) -> Option<&str> {
+    request.and_then(|request| request.cursor.as_deref())
+}
+
+/// Error for a cursor that was not handed out for the list
+fn invalid_cursor(cursor: Option<&str>) -> RpcError {
+    RpcError::invalid_params()
+        .with_message(format!("Invalid cursor '{}'", cursor.unwrap_or_default()))
+}
+
+/// Bucket a tool call is rate-limited against
+///
+/// The authenticated client (API key or OAuth client) is preferred, then
+/// the authenticated user, then the caller's address, so a client cannot
+/// escape its limit by opening new sessions. The MCP session is the last
+/// resort for HTTP; stdio calls share one bucket.
+fn rate_limit_key(
+    auth_info: Option<&rust_mcp_sdk::auth::AuthInfo>,
+    client_addr: Option<&str>,
+    session_id: Option<&str>,
+) -> String {
+    if let Some(id) = auth_info.and_then(|info| info.client_id.as_deref()) {
+        return format!("client:{id}");
+    }
+    if let Some(id) = auth_info.and_then(|info| info.user_id.as_deref()) {
+        return format!("user:{id}");
+    }
+    if let Some(addr) = client_addr {
+        return format!("ip:{addr}");
+    }
+    session_id.map_or_else(|| "local".to_string(), |id| format!("session:{id}"))
+}
+
+/// Whether the caller may use admin tools
+///
+/// Stdio clients (no session) started the server and are always admins;
+/// others must be authenticated as a client or user in `admin_clients`.
+fn is_admin_client(
+    admin_clients: &[String],
+    auth_info: Option<&rust_mcp_sdk::auth::AuthInfo>,
+    session_id: Option<&str>,
+) -> bool {
+    let Some(info) = auth_info else {
+        return session_id.is_none();
+    };
+    let identities = [
+        info.client_id.as_deref().map(|id| format!("client:{id}")),
+        info.user_id.as_deref().map(|id| format!("user:{id}")),
+    ];
+    identities
+        .into_iter()
+        .flatten()
+        .any(|identity| admin_clients.contains(&identity))
 }
 
 #[async_trait]
 impl ServerHandler for CratesDocsHandler {
+    /// Register the new session for tool list change notifications and log
+    /// messages
+    async fn on_initialized(&self, runtime: Arc<dyn McpServer>) {
+        self.server.tool_list().register(&runtime);
+        self.server.client_log().register(&runtime);
+    }
+
     /// Handle list tools request
     async fn handle_list_tools_request(
         &self,
-        _request: Option<PaginatedRequestParams>,
+        request: Option<PaginatedRequestParams>,
         _runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListToolsResult, RpcError> {
-        let trace_id = Uuid::new_v4().to_string();
-        let span = info_span!("list_tools", trace_id = %trace_id);
+        let request_id = Uuid::new_v4().to_string();
+        let span = info_span!("list_tools", request_id = %request_id);
 
         async {
             tracing::debug!("Listing available tools");
-            let result = self.list_tools();
+            let result = self.list_tools_page(request_cursor(request.as_ref()))?;
             tracing::debug!("Found {} tools", result.tools.len());
             Ok(result)
         }
@@ -249,45 +491,241 @@ impl ServerHandler for CratesDocsHandler {
     async fn handle_call_tool_request(
         &self,
         params: CallToolRequestParams,
-        _runtime: Arc<dyn McpServer>,
+        runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<CallToolResult, CallToolError> {
-        self.execute_tool(params).await.into_call_tool_result()
+        self.call_tool(params, &runtime).await
+    }
+
+    /// Handle a tool call made as a task
+    ///
+    /// Returns the task right away; the tool runs in the background, see
+    /// [`crate::server::tasks`].
+    async fn handle_task_augmented_tool_call(
+        &self,
+        params: CallToolRequestParams,
+        task_creator: ServerTaskCreator,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<CreateTaskResult, CallToolError> {
+        let supports_tasks = self
+            .tool_registry()
+            .get_tools()
+            .into_iter()
+            .find(|tool| tool.name == params.name)
+            .ok_or_else(|| CallToolError::unknown_tool(params.name.clone()))?
+            .execution
+            .and_then(|execution| execution.task_support)
+            .is_some_and(|support| support != ToolExecutionTaskSupport::Forbidden);
+        if !supports_tasks {
+            return Err(CallToolError::from_message(format!(
+                "Tool '{}' cannot be called as a task",
+                params.name
+            )));
+        }
+
+        let tasks = self.server.tasks();
+        let ttl = tasks.ttl_ms(params.task.as_ref().and_then(|task| task.ttl));
+        let task = task_creator
+            .create_task(CreateTaskOptions {
+                ttl: Some(ttl),
+                poll_interval: Some(tasks.poll_interval_ms()),
+                meta: None,
+            })
+            .await;
+        tracing::info!("Tool {} started as task {}", params.name, task.task_id);
+
+        let handler = self.clone();
+        tasks.spawn(&task, runtime.session_id(), async move {
+            handler
+                .call_tool(params, &runtime)
+                .await
+                .unwrap_or_else(Into::into)
+        });
+        Ok(CreateTaskResult { meta: None, task })
+    }
+
+    /// Handle get task request
+    async fn handle_get_task_request(
+        &self,
+        params: GetTaskParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<GetTaskResult, RpcError> {
+        let task = self
+            .server
+            .tasks()
+            .get(&params.task_id, runtime.session_id())
+            .await?;
+        Ok(GetTaskResult {
+            created_at: task.created_at,
+            last_updated_at: task.last_updated_at,
+            meta: None,
+            poll_interval: task.poll_interval,
+            status: task.status,
+            status_message: task.status_message,
+            task_id: task.task_id,
+            ttl: task.ttl.unwrap_or_default(),
+            extra: None,
+        })
+    }
+
+    /// Handle get task result request, waiting for the task to finish
+    async fn handle_get_task_payload_request(
+        &self,
+        params: GetTaskPayloadParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<GetTaskPayloadResult, RpcError> {
+        let payload = self
+            .server
+            .tasks()
+            .result(&params.task_id, runtime.session_id())
+            .await?;
+        Ok(GetTaskPayloadResult {
+            meta: None,
+            extra: Some(payload),
+        })
+    }
+
+    /// Handle cancel task request
+    async fn handle_cancel_task_request(
+        &self,
+        params: CancelTaskParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<CancelTaskResult, RpcError> {
+        let task = self
+            .server
+            .tasks()
+            .cancel(&params.task_id, runtime.session_id())
+            .await?;
+        Ok(CancelTaskResult {
+            created_at: task.created_at,
+            last_updated_at: task.last_updated_at,
+            meta: None,
+            poll_interval: task.poll_interval,
+            status: task.status,
+            status_message: task.status_message,
+            task_id: task.task_id,
+            ttl: task.ttl.unwrap_or_default(),
+            extra: None,
+        })
+    }
+
+    /// Handle list tasks request
+    async fn handle_list_task_request(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<ListTasksResult, RpcError> {
+        let cursor = request_cursor(request.as_ref()).map(str::to_string);
+        Ok(self.server.tasks().list(cursor, runtime.session_id()).await)
+    }
+
+    /// Handle set logging level request
+    ///
+    /// Sets the least severe level of the log messages sent to the client;
+    /// the server's own log level is changed with the `set_log_level` tool.
+    async fn handle_set_level_request(
+        &self,
+        params: SetLevelRequestParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<rust_mcp_sdk::schema::Result, RpcError> {
+        self.server.client_log().set_level(&runtime, params.level);
+        Ok(rust_mcp_sdk::schema::Result::default())
     }
 
     /// Handle list resources request
     async fn handle_list_resources_request(
         &self,
-        _request: Option<PaginatedRequestParams>,
+        request: Option<PaginatedRequestParams>,
         _runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListResourcesResult, RpcError> {
-        Ok(self.list_resources())
+        self.list_resources(request_cursor(request.as_ref())).await
+    }
+
+    /// Handle list resource templates request
+    async fn handle_list_resource_templates_request(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<ListResourceTemplatesResult, RpcError> {
+        let cursor = request_cursor(request.as_ref());
+        let page = paginate(
+            crate::server::resources::docs_resource_templates(),
+            |template| &template.name,
+            cursor,
+            self.page_size(),
+        )
+        .ok_or_else(|| invalid_cursor(cursor))?;
+        Ok(ListResourceTemplatesResult {
+            resource_templates: page.items,
+            meta: None,
+            next_cursor: page.next_cursor,
+        })
     }
 
     /// Handle read resource request
     async fn handle_read_resource_request(
         &self,
-        _params: ReadResourceRequestParams,
+        params: ReadResourceRequestParams,
         _runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ReadResourceResult, RpcError> {
-        Err(RpcError::invalid_request().with_message("Resource not found".to_string()))
+        let span = info_span!("read_resource", uri = %params.uri);
+        crate::server::resources::read_docs_resource(&self.server, &params.uri)
+            .instrument(span)
+            .await
+    }
+
+    /// Handle completion request
+    async fn handle_complete_request(
+        &self,
+        params: CompleteRequestParams,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<CompleteResult, RpcError> {
+        let span = info_span!("complete", argument = %params.argument.name);
+        crate::server::completion::complete(&self.server, &params)
+            .instrument(span)
+            .await
+    }
+
+    /// Handle subscribe request
+    async fn handle_subscribe_request(
+        &self,
+        params: SubscribeRequestParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<rust_mcp_sdk::schema::Result, RpcError> {
+        self.server
+            .subscriptions()
+            .subscribe(&params.uri, runtime)
+            .await?;
+        Ok(rust_mcp_sdk::schema::Result::default())
+    }
+
+    /// Handle unsubscribe request
+    async fn handle_unsubscribe_request(
+        &self,
+        params: UnsubscribeRequestParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<rust_mcp_sdk::schema::Result, RpcError> {
+        self.server
+            .subscriptions()
+            .unsubscribe(&params.uri, runtime.session_id().as_deref());
+        Ok(rust_mcp_sdk::schema::Result::default())
     }
 
     /// Handle list prompts request
     async fn handle_list_prompts_request(
         &self,
-        _request: Option<PaginatedRequestParams>,
+        request: Option<PaginatedRequestParams>,
         _runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListPromptsResult, RpcError> {
-        Ok(self.list_prompts())
+        self.list_prompts_page(request_cursor(request.as_ref()))
     }
 
     /// Handle get prompt request
     async fn handle_get_prompt_request(
         &self,
-        _params: GetPromptRequestParams,
+        params: GetPromptRequestParams,
         _runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<GetPromptResult, RpcError> {
-        Err(RpcError::invalid_request().with_message("Prompt not found".to_string()))
+        crate::server::prompts::get_prompt(&params.name, params.arguments)
     }
 }
 
@@ -311,6 +749,58 @@ mod tests {
             .await;
 
         assert!(result.success);
+        assert_eq!(result.status(), "ok");
+        assert!(Uuid::parse_str(&result.request_id).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_key_prefers_authenticated_client() {
+        let mut info = rust_mcp_sdk::auth::AuthInfo {
+            token_unique_id: "api-key".to_string(),
+            client_id: Some("ci".to_string()),
+            user_id: Some("alice".to_string()),
+            scopes: None,
+            expires_at: None,
+            audience: None,
+            extra: None,
+        };
+        let addr = Some("203.0.113.7");
+        assert_eq!(rate_limit_key(Some(&info), addr, Some("s1")), "client:ci");
+
+        info.client_id = None;
+        assert_eq!(rate_limit_key(Some(&info), addr, Some("s1")), "user:alice");
+
+        // Sessions from one address share a bucket
+        assert_eq!(rate_limit_key(None, addr, Some("s1")), "ip:203.0.113.7");
+        assert_eq!(
+            rate_limit_key(None, addr, Some("s1")),
+            rate_limit_key(None, addr, Some("s2"))
+        );
+        assert_eq!(rate_limit_key(None, None, Some("s1")), "session:s1");
+        assert_eq!(rate_limit_key(None, None, None), "local");
+    }
+
+    #[test]
+    fn test_admin_clients() {
+        let admins = vec!["client:ops".to_string(), "user:alice".to_string()];
+        let mut info = rust_mcp_sdk::auth::AuthInfo {
+            token_unique_id: "api-key".to_string(),
+            client_id: Some("ci".to_string()),
+            user_id: Some("alice".to_string()),
+            scopes: None,
+            expires_at: None,
+            audience: None,
+            extra: None,
+        };
+        assert!(is_admin_client(&admins, Some(&info), Some("s1")));
+        info.user_id = None;
+        assert!(!is_admin_client(&admins, Some(&info), Some("s1")));
+        info.client_id = Some("ops".to_string());
+        assert!(is_admin_client(&admins, Some(&info), Some("s1")));
+
+        // Unauthenticated HTTP sessions never are; stdio always is
+        assert!(!is_admin_client(&admins, None, Some("s1")));
+        assert!(is_admin_client(&[], None, None));
     }
 
     #[tokio::test]
@@ -361,12 +851,51 @@ mod tests {
 
         let tools = handler.list_tools();
         assert!(!tools.tools.is_empty());
-        assert_eq!(tools.tools.len(), 4); // 4 default tools
+        assert_eq!(tools.tools.len(), 30); // 29 default tools and set_tool_enabled
 
-        let resources = handler.list_resources();
+        let resources = handler.list_resources(None).await.unwrap();
         assert!(resources.resources.is_empty());
 
         let prompts = handler.list_prompts();
-        assert!(prompts.prompts.is_empty());
+        assert_eq!(prompts.prompts.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_pages() {
+        let mut config = AppConfig::default();
+        config.server.page_size = 10;
+        let handler = CratesDocsHandler::new(Arc::new(CratesDocsServer::new(config).unwrap()));
+
+        let mut names = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = handler.list_tools_page(cursor.as_deref()).unwrap();
+            assert!(page.tools.len() <= 10);
+            names.extend(page.tools.into_iter().map(|tool| tool.name));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(names.len(), 30);
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let error = handler.list_tools_page(Some("no_such_tool")).unwrap_err();
+        assert_eq!(error.code, RpcError::invalid_params().code);
+    }
+
+    #[tokio::test]
+    async fn test_configured_disabled_tools() {
+        let mut config = AppConfig::default();
+        config.server.disabled_tools = vec!["health_check".to_string()];
+        let server = Arc::new(CratesDocsServer::new(config).unwrap());
+        let handler = CratesDocsHandler::new(server.clone());
+
+        let tools = handler.list_tools();
+        assert_eq!(tools.tools.len(), 29);
+        assert!(tools.tools.iter().all(|tool| tool.name != "health_check"));
+
+        server.apply_disabled_tools(&[]);
+        assert_eq!(handler.list_tools().tools.len(), 30);
     }
 }
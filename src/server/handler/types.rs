@@ -2,9 +2,14 @@
 
 use rust_mcp_sdk::schema::{CallToolError, CallToolResult, ResultFromServer};
 
+/// `tracing` target of the access log, one line per tool call
+pub const ACCESS_LOG_TARGET: &str = "crates_docs::access";
+
 /// Tool execution result (supports different return type conversions)
 #[derive(Debug)]
 pub struct ToolExecutionResult {
+    /// ID of the call, also recorded on its tracing span and in error messages
+    pub request_id: String,
     /// Tool name
     pub tool_name: String,
     /// Execution duration
@@ -17,12 +22,147 @@ pub struct ToolExecutionResult {
 
 impl ToolExecutionResult {
     /// Convert to `CallToolResult` (for `ServerHandler`)
+    ///
+    /// Errors carrying a structured payload (e.g. not-found suggestions) are
+    /// turned into an `isError` result here so the payload survives; plain
+    /// errors are left for the SDK to render as text.
+    ///
+    /// Error messages end with the request ID, so a client report can be
+    /// matched with the server logs.
     pub fn into_call_tool_result(self) -> std::result::Result<CallToolResult, CallToolError> {
-        self.result
+        match self.result {
+            Err(err) => match error_result_with_data(&err, &self.request_id) {
+                Some(result) => Ok(result),
+                None => Err(CallToolError::from_message(with_request_id(
+                    &err,
+                    &self.request_id,
+                ))),
+            },
+            ok => ok,
+        }
+    }
+
+    /// Outcome of the call for the access log: `ok`, `error`, `timeout` or
+    /// `throttled`
+    ///
+    /// `isError` results count as errors.
+    #[must_use]
+    pub fn status(&self) -> &'static str {
+        match &self.result {
+            Ok(result) if result.is_error != Some(true) => "ok",
+            Ok(_) => "error",
+            Err(err) => match crate::error::tool_error_data(err)
+                .as_ref()
+                .and_then(|data| data["kind"].as_str())
+            {
+                Some("timeout") => "timeout",
+                Some("rate_limited") => "throttled",
+                _ => "error",
+            },
+        }
+    }
+
+    /// Write the access-log line of the call
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Crate the call was about, if any
+    /// * `cache` - Cache outcome (see [`crate::cache::stats::RequestLookups::outcome`])
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn log_access(&self, crate_name: Option<&str>, cache: &str) {
+        tracing::info!(
+            target: ACCESS_LOG_TARGET,
+            request_id = %self.request_id,
+            tool = %self.tool_name,
+            crate_name,
+            duration_ms = self.duration.as_millis() as u64,
+            cache,
+            status = self.status(),
+            "tool call"
+        );
     }
 
     /// Convert to `ResultFromServer` (for `ServerHandlerCore`)
     pub fn into_result_from_server(self) -> ResultFromServer {
-        self.result.unwrap_or_else(CallToolResult::from).into()
+        self.into_call_tool_result()
+            .unwrap_or_else(CallToolResult::from)
+            .into()
+    }
+}
+
+/// Build an `isError` tool result whose `structuredContent.error` holds the
+/// error's structured payload, the tool-call counterpart of a JSON-RPC error
+/// `data` field.
+fn error_result_with_data(err: &CallToolError, request_id: &str) -> Option<CallToolResult> {
+    let mut data = crate::error::tool_error_data(err)?;
+    data["request_id"] = request_id.into();
+    let mut structured = serde_json::Map::new();
+    structured.insert("error".to_string(), data);
+
+    let mut result = CallToolResult::from(CallToolError::from_message(with_request_id(
+        err, request_id,
+    )));
+    result.structured_content = Some(structured);
+    Some(result)
+}
+
+/// Error message ending with the request ID
+fn with_request_id(err: &CallToolError, request_id: &str) -> String {
+    format!("{err} (request_id: {request_id})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(
+        result: std::result::Result<CallToolResult, CallToolError>,
+    ) -> ToolExecutionResult {
+        ToolExecutionResult {
+            request_id: "req-1".to_string(),
+            tool_name: "lookup_crate".to_string(),
+            duration: std::time::Duration::ZERO,
+            success: result.is_ok(),
+            result,
+        }
+    }
+
+    #[test]
+    fn test_not_found_error_carries_suggestions() {
+        let err = crate::error::Error::not_found("crate", "serd", vec!["serde".to_string()]);
+        let result = execution(Err(err.into()))
+            .into_call_tool_result()
+            .expect("structured errors become isError results");
+
+        assert_eq!(result.is_error, Some(true));
+        let data = &result.structured_content.expect("structured content")["error"];
+        assert_eq!(data["kind"], "not_found");
+        assert_eq!(data["suggestions"][0], "serde");
+        assert_eq!(data["request_id"], "req-1");
+    }
+
+    #[test]
+    fn test_plain_error_is_passed_through() {
+        let err = execution(Err(CallToolError::from_message("boom")))
+            .into_call_tool_result()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "boom (request_id: req-1)");
+    }
+
+    #[test]
+    fn test_status_classifies_errors() {
+        assert_eq!(
+            execution(Ok(CallToolResult::text_content(vec![]))).status(),
+            "ok"
+        );
+        assert_eq!(
+            execution(Err(CallToolError::from_message("boom"))).status(),
+            "error"
+        );
+        let timeout = crate::error::Error::timeout("Tool 'x'", std::time::Duration::from_secs(1));
+        assert_eq!(execution(Err(timeout.into())).status(), "timeout");
+        let throttled =
+            crate::error::Error::rate_limited("local", std::time::Duration::from_secs(1));
+        assert_eq!(execution(Err(throttled.into())).status(), "throttled");
     }
 }
@@ -0,0 +1,473 @@
+//! Request journal
+//!
+//! Optionally records every in-flight tool request (tool, arguments, start
+//! time) outside the process. An entry is written before the tool runs and
+//! removed once it returns, so entries still present at the next start belong
+//! to requests that were interrupted by a crash or kill. They are logged and
+//! counted at startup (see [`RequestJournal::recover`]), which lets operators
+//! correlate a crash with the document that triggered it.
+//!
+//! Two backends are available:
+//!
+//! - `file`: one JSON file per request in a directory
+//! - `redis`: one key per server instance in the configured Redis cache,
+//!   holding that instance's in-flight requests
+
+use crate::cache::Cache;
+use crate::metrics::ServerMetrics;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Maximum length of the arguments recorded for a request
+///
+/// Tools such as `set_project_profile` accept megabytes of input; the start
+/// of the arguments is enough to identify the request.
+const MAX_ARGUMENTS_LEN: usize = 2048;
+
+/// Expiry of the Redis journal key, so a decommissioned instance does not
+/// leave its entries behind forever
+const REDIS_JOURNAL_TTL: Duration = Duration::from_hours(7 * 24);
+
+/// Journal storage backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalBackend {
+    /// One file per in-flight request in `path`
+    #[default]
+    File,
+    /// The Redis cache (`cache.cache_type = "redis"`)
+    Redis,
+}
+
+/// Request journal configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the journal is opened, and interrupted
+/// requests are recovered, when the server starts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JournalConfig {
+    /// Whether in-flight requests are journaled
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Storage backend
+    #[serde(default)]
+    pub backend: JournalBackend,
+
+    /// Journal directory (`file` backend)
+    #[serde(default = "default_journal_path")]
+    pub path: String,
+
+    /// Name of this server instance (`redis` backend); replicas sharing a
+    /// Redis server need distinct names
+    #[serde(default = "default_journal_instance")]
+    pub instance: String,
+}
+
+fn default_journal_path() -> String {
+    JournalConfig::default().path
+}
+
+fn default_journal_instance() -> String {
+    JournalConfig::default().instance
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: JournalBackend::File,
+            path: "./data/journal".to_string(),
+            instance: "default".to_string(),
+        }
+    }
+}
+
+impl JournalConfig {
+    /// Validate the journal configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the journal is enabled with an empty
+    /// path or instance name, or with the `redis` backend on a non-Redis cache
+    pub fn validate(&self, cache_type: &str) -> crate::error::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        match self.backend {
+            JournalBackend::File if self.path.trim().is_empty() => Err(
+                crate::error::Error::config("journal.path", "must not be empty"),
+            ),
+            JournalBackend::Redis if cache_type != "redis" => Err(crate::error::Error::config(
+                "journal.backend",
+                "\"redis\" requires cache.cache_type = \"redis\"",
+            )),
+            JournalBackend::Redis if self.instance.trim().is_empty() => Err(
+                crate::error::Error::config("journal.instance", "must not be empty"),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A journaled request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Request identifier
+    pub id: String,
+    /// Tool name
+    pub tool: String,
+    /// JSON arguments, truncated to a bounded length
+    pub arguments: String,
+    /// Start time, seconds since the Unix epoch
+    pub started_at: u64,
+}
+
+impl JournalEntry {
+    fn new(tool: &str, arguments: &serde_json::Value) -> Self {
+        let mut arguments = arguments.to_string();
+        if arguments.len() > MAX_ARGUMENTS_LEN {
+            let mut end = MAX_ARGUMENTS_LEN;
+            while !arguments.is_char_boundary(end) {
+                end -= 1;
+            }
+            arguments.truncate(end);
+            arguments.push('…');
+        }
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: tool.to_string(),
+            arguments,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+enum Store {
+    File(PathBuf),
+    Redis {
+        cache: Arc<dyn Cache>,
+        key: String,
+        // Serializes the read-modify-write of this instance's key
+        lock: tokio::sync::Mutex<()>,
+    },
+}
+
+/// Journal of in-flight tool requests
+pub struct RequestJournal {
+    store: Store,
+}
+
+impl RequestJournal {
+    /// Open the journal described by `config`
+    ///
+    /// Returns `None` when journaling is disabled. The `file` backend creates
+    /// its directory; the `redis` backend uses `cache`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the journal directory cannot be created
+    pub fn open(
+        config: &JournalConfig,
+        cache: Arc<dyn Cache>,
+    ) -> crate::error::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let store = match config.backend {
+            JournalBackend::File => {
+                let dir = PathBuf::from(&config.path);
+                std::fs::create_dir_all(&dir)?;
+                Store::File(dir)
+            }
+            JournalBackend::Redis => Store::Redis {
+                cache,
+                key: format!("journal:{}", config.instance.trim()),
+                lock: tokio::sync::Mutex::new(()),
+            },
+        };
+        Ok(Some(Self { store }))
+    }
+
+    /// Journal the start of a request
+    ///
+    /// Failing to write the journal never fails the request; it is logged.
+    pub async fn begin(
+        self: &Arc<Self>,
+        tool: &str,
+        arguments: &serde_json::Value,
+    ) -> JournalGuard {
+        let entry = JournalEntry::new(tool, arguments);
+        let id = entry.id.clone();
+        if let Err(e) = self.insert(entry).await {
+            tracing::warn!("Failed to journal request {id} ({tool}): {e}");
+        }
+        JournalGuard {
+            journal: Arc::clone(self),
+            id: Some(id),
+        }
+    }
+
+    /// Report requests left in the journal by the previous run and clear them
+    ///
+    /// Each interrupted request is logged as a warning and counted in the
+    /// `mcp_interrupted_requests` metric.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the journal cannot be read or cleared
+    pub async fn recover(
+        &self,
+        metrics: Option<&ServerMetrics>,
+    ) -> crate::error::Result<Vec<JournalEntry>> {
+        let mut entries = self.drain().await?;
+        entries.sort_by_key(|entry| entry.started_at);
+        for entry in &entries {
+            tracing::warn!(
+                tool = %entry.tool,
+                started_at = entry.started_at,
+                arguments = %entry.arguments,
+                "Request {} was interrupted before completing (crash or forced shutdown)",
+                entry.id
+            );
+            if let Some(metrics) = metrics {
+                metrics.record_interrupted_request(&entry.tool);
+            }
+        }
+        if entries.is_empty() {
+            tracing::debug!("Request journal: no interrupted requests");
+        }
+        Ok(entries)
+    }
+
+    async fn insert(&self, entry: JournalEntry) -> crate::error::Result<()> {
+        match &self.store {
+            Store::File(dir) => {
+                let json = serde_json::to_vec(&entry)?;
+                tokio::fs::write(dir.join(format!("{}.json", entry.id)), json).await?;
+            }
+            Store::Redis { cache, key, lock } => {
+                let _guard = lock.lock().await;
+                let mut entries = read_map(cache.as_ref(), key).await;
+                entries.insert(entry.id.clone(), entry);
+                write_map(cache.as_ref(), key, &entries).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> crate::error::Result<()> {
+        match &self.store {
+            Store::File(dir) => {
+                tokio::fs::remove_file(dir.join(format!("{id}.json"))).await?;
+            }
+            Store::Redis { cache, key, lock } => {
+                let _guard = lock.lock().await;
+                let mut entries = read_map(cache.as_ref(), key).await;
+                if entries.remove(id).is_some() {
+                    write_map(cache.as_ref(), key, &entries).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn drain(&self) -> crate::error::Result<Vec<JournalEntry>> {
+        match &self.store {
+            Store::File(dir) => {
+                let mut entries = Vec::new();
+                let mut files = tokio::fs::read_dir(dir).await?;
+                while let Some(file) = files.next_entry().await? {
+                    let path = file.path();
+                    if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                        continue;
+                    }
+                    match tokio::fs::read(&path).await {
+                        Ok(bytes) => match serde_json::from_slice(&bytes) {
+                            Ok(entry) => entries.push(entry),
+                            Err(e) => tracing::warn!(
+                                "Skipping unreadable journal entry {}: {e}",
+                                path.display()
+                            ),
+                        },
+                        Err(e) => tracing::warn!(
+                            "Skipping unreadable journal entry {}: {e}",
+                            path.display()
+                        ),
+                    }
+                    tokio::fs::remove_file(&path).await?;
+                }
+                Ok(entries)
+            }
+            Store::Redis { cache, key, lock } => {
+                let _guard = lock.lock().await;
+                let entries = read_map(cache.as_ref(), key).await;
+                cache.delete(key).await?;
+                Ok(entries.into_values().collect())
+            }
+        }
+    }
+}
+
+async fn read_map(cache: &dyn Cache, key: &str) -> BTreeMap<String, JournalEntry> {
+    cache
+        .get(key)
+        .await
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+async fn write_map(
+    cache: &dyn Cache,
+    key: &str,
+    entries: &BTreeMap<String, JournalEntry>,
+) -> crate::error::Result<()> {
+    if entries.is_empty() {
+        return cache.delete(key).await;
+    }
+    let json = serde_json::to_string(entries)?;
+    cache
+        .set(key.to_string(), json.into(), Some(REDIS_JOURNAL_TTL))
+        .await
+}
+
+/// An in-flight journaled request
+///
+/// The entry is removed by [`JournalGuard::finish`] or, in the background,
+/// when the guard is dropped, so a request whose future is cancelled (e.g. the
+/// client went away) is not later mistaken for one interrupted by a crash.
+pub struct JournalGuard {
+    journal: Arc<RequestJournal>,
+    id: Option<String>,
+}
+
+impl JournalGuard {
+    /// Remove the request from the journal and wait for the removal
+    pub async fn finish(mut self) {
+        if let Some(id) = self.id.take() {
+            remove_logged(&self.journal, &id).await;
+        }
+    }
+}
+
+impl Drop for JournalGuard {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let journal = Arc::clone(&self.journal);
+            runtime.spawn(async move { remove_logged(&journal, &id).await });
+        }
+    }
+}
+
+async fn remove_logged(journal: &RequestJournal, id: &str) {
+    if let Err(e) = journal.remove(id).await {
+        tracing::warn!("Failed to remove request {id} from journal: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::memory::MemoryCache;
+
+    fn file_journal(dir: &std::path::Path) -> Arc<RequestJournal> {
+        let config = JournalConfig {
+            enabled: true,
+            path: dir.to_string_lossy().into_owned(),
+            ..JournalConfig::default()
+        };
+        Arc::new(
+            RequestJournal::open(&config, Arc::new(MemoryCache::new(10)))
+                .unwrap()
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_file_journal_recovers_unfinished_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = file_journal(dir.path());
+
+        journal
+            .begin("lookup_crate", &serde_json::json!({"crate_name": "serde"}))
+            .await
+            .finish()
+            .await;
+        let _interrupted = journal
+            .begin("lookup_item", &serde_json::json!({"crate_name": "tokio"}))
+            .await;
+
+        let metrics = ServerMetrics::new();
+        let entries = file_journal(dir.path())
+            .recover(Some(&metrics))
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tool, "lookup_item");
+        assert!(entries[0].arguments.contains("tokio"));
+        assert!(metrics.export().unwrap().contains("lookup_item"));
+
+        // Recovery clears the journal
+        assert!(journal.recover(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redis_journal_store() {
+        let journal = Arc::new(RequestJournal {
+            store: Store::Redis {
+                cache: Arc::new(MemoryCache::new(10)),
+                key: "journal:test".to_string(),
+                lock: tokio::sync::Mutex::new(()),
+            },
+        });
+
+        let first = journal.begin("search_crates", &serde_json::json!({})).await;
+        let _second = journal.begin("lookup_crate", &serde_json::json!({})).await;
+        first.finish().await;
+
+        let entries = journal.recover(None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tool, "lookup_crate");
+        assert!(journal.recover(None).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_journal_entry_truncates_arguments() {
+        let lockfile = "é".repeat(MAX_ARGUMENTS_LEN);
+        let entry = JournalEntry::new(
+            "set_project_profile",
+            &serde_json::json!({ "lockfile": lockfile }),
+        );
+        assert!(entry.arguments.len() <= MAX_ARGUMENTS_LEN + '…'.len_utf8());
+        assert!(entry.arguments.ends_with('…'));
+    }
+
+    #[test]
+    fn test_journal_config_validation() {
+        assert!(JournalConfig::default().validate("memory").is_ok());
+
+        let redis = JournalConfig {
+            enabled: true,
+            backend: JournalBackend::Redis,
+            ..JournalConfig::default()
+        };
+        assert!(redis.validate("memory").is_err());
+        assert!(redis.validate("redis").is_ok());
+
+        let no_path = JournalConfig {
+            enabled: true,
+            path: " ".to_string(),
+            ..JournalConfig::default()
+        };
+        assert!(no_path.validate("memory").is_err());
+    }
+}
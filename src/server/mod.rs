@@ -8,6 +8,16 @@
 //! - `handler`: MCP request handling
 //! - `transport`: Transport layer implementation
 //! - `auth`: OAuth authentication support
+//! - `client_addr`: Caller addresses of HTTP sessions, for per-IP rate limiting
+//! - `client_log`: Log messages to connected clients
+//! - `completion`: Completion of prompt and resource template arguments
+//! - `journal`: In-flight request journal for crash diagnosis
+//! - `prompts`: Prompts for common documentation workflows
+//! - `resources`: Cached documentation as MCP resources
+//! - `subscriptions`: Notifications of new crate versions to subscribed clients
+//! - `tasks`: Task-augmented tool calls
+//! - `tool_list`: Notifications of enabled and disabled tools to connected clients
+//! - `warmup`: Cache warm-up on startup
 //!
 //! # Handler Design
 //!
@@ -46,15 +56,32 @@
 
 pub mod auth;
 pub mod auth_middleware;
+pub mod client_addr;
+pub mod client_log;
+pub mod completion;
+pub mod event_store;
+pub mod forwarded;
 pub mod handler;
+pub mod journal;
+pub mod probes;
+pub mod prompts;
+pub mod rate_limit;
+pub mod resources;
+pub mod subscriptions;
+pub mod tasks;
+pub mod tool_list;
 pub mod transport;
+pub mod warmup;
 
-use crate::cache::Cache;
+use crate::cache::{Cache, CacheIntegrity};
 use crate::config::AppConfig;
 use crate::error::Result;
+use crate::logging::LogLevelHandle;
 use crate::tools::ToolRegistry;
 use rust_mcp_sdk::schema::{
-    Implementation, InitializeResult, ProtocolVersion, ServerCapabilities, ServerCapabilitiesTools,
+    Implementation, InitializeResult, ProtocolVersion, ServerCapabilities,
+    ServerCapabilitiesPrompts, ServerCapabilitiesResources, ServerCapabilitiesTools,
+    ServerTaskRequest, ServerTaskTools, ServerTasks,
 };
 use std::sync::Arc;
 
@@ -76,12 +103,27 @@ pub use transport::HyperServerConfig;
 ///
 /// - `config`: Application configuration
 /// - `tool_registry`: Tool registry
+/// - `doc_service`: Document service shared by the tools
 /// - `cache`: Cache instance
+/// - `journal`: In-flight request journal, if enabled
+/// - `log_level`: Handle changing the log level at runtime, if logging is reloadable
+/// - `tool_list`: Sessions to notify when tools are enabled or disabled
+/// - `client_log`: Sessions to send log messages to, and their levels
+/// - `tasks`: Task-augmented tool calls and their results
 #[derive(Clone)]
 pub struct CratesDocsServer {
     config: AppConfig,
     tool_registry: Arc<ToolRegistry>,
+    doc_service: Arc<crate::tools::docs::DocService>,
     cache: Arc<dyn Cache>,
+    journal: Option<Arc<journal::RequestJournal>>,
+    log_level: Option<LogLevelHandle>,
+    rate_limiter: Option<Arc<rate_limit::ClientRateLimiter>>,
+    client_addrs: Arc<client_addr::ClientAddrs>,
+    subscriptions: Arc<subscriptions::ResourceSubscriptions>,
+    tool_list: Arc<tool_list::ToolListNotifier>,
+    client_log: Arc<client_log::ClientLogForwarder>,
+    tasks: Arc<tasks::ToolTasks>,
 }
 
 impl CratesDocsServer {
@@ -94,27 +136,69 @@ impl CratesDocsServer {
     ///
     /// # Errors
     ///
-    /// Returns error if document service creation fails
+    /// Returns error if document service creation fails or the request journal
+    /// cannot be opened
     fn from_parts(config: AppConfig, cache: Arc<dyn Cache>) -> crate::error::Result<Self> {
+        // Count hits and misses of every lookup, for `cache_stats`
+        let cache: Arc<dyn Cache> = Arc::new(crate::cache::stats::StatsCache::new(cache));
+
         // Initialize global HTTP client with performance config for connection pool reuse
         // This ensures all HTTP requests share the same connection pool
         // Note: init_global_http_client will fail if already initialized, which is fine
-        let _ = crate::utils::init_global_http_client(&config.performance);
+        let _ =
+            crate::utils::init_global_http_client_with_chaos(&config.performance, &config.chaos);
+
+        // Point the metadata tools at the configured registry (first call wins)
+        crate::tools::docs::registry::install_registry(&config.registry);
 
         // Create document service with cache configuration
-        let doc_service = Arc::new(crate::tools::docs::DocService::with_config(
-            cache.clone(),
-            &config.cache,
-        )?);
+        let doc_service = Arc::new(
+            crate::tools::docs::DocService::with_config(cache.clone(), &config.cache)?
+                .with_providers(config.docs.build_providers())
+                .with_content_filters(config.docs.filters)
+                .with_sanitize_level(config.docs.sanitize)
+                .with_search_backends(config.docs.search_backends.clone())
+                .with_crates_io_token(config.upstream.crates_io_token())
+                .with_request_timeout(std::time::Duration::from_secs(
+                    config.server.request_timeout_secs,
+                )),
+        );
+
+        // Create tool registry, with the admin tool changing it
+        let tool_registry = Arc::new_cyclic(|registry| {
+            crate::tools::create_default_registry(&doc_service).register(
+                crate::tools::tool_admin::SetToolEnabledToolImpl::new(registry.clone()),
+            )
+        });
+        let tool_list = Arc::new(tool_list::ToolListNotifier::new(tool_registry.clone()));
+        let tasks = Arc::new(tasks::ToolTasks::new(std::time::Duration::from_secs(
+            config.server.task_ttl_secs,
+        )));
 
-        // Create tool registry
-        let tool_registry = Arc::new(crate::tools::create_default_registry(&doc_service));
+        let journal = journal::RequestJournal::open(&config.journal, cache.clone())?.map(Arc::new);
+        let rate_limiter =
+            rate_limit::ClientRateLimiter::from_config(&config.performance).map(Arc::new);
+        let subscriptions = Arc::new(subscriptions::ResourceSubscriptions::new(
+            doc_service.clone(),
+            std::time::Duration::from_secs(config.server.subscription_poll_interval_secs),
+        ));
 
-        Ok(Self {
+        let server = Self {
             config,
             tool_registry,
+            doc_service,
             cache,
-        })
+            journal,
+            log_level: crate::logging::global_log_level(),
+            rate_limiter,
+            client_addrs: Arc::new(client_addr::ClientAddrs::new()),
+            subscriptions,
+            tool_list,
+            client_log: Arc::new(client_log::ClientLogForwarder::new()),
+            tasks,
+        };
+        server.apply_disabled_tools(&server.config.server.disabled_tools);
+        Ok(server)
     }
 
     /// Create new server instance (synchronous)
@@ -203,12 +287,98 @@ impl CratesDocsServer {
         &self.tool_registry
     }
 
+    /// Get the document service shared by the tools
+    #[must_use]
+    pub fn doc_service(&self) -> &Arc<crate::tools::docs::DocService> {
+        &self.doc_service
+    }
+
     /// Get cache instance
     #[must_use]
     pub fn cache(&self) -> &Arc<dyn Cache> {
         &self.cache
     }
 
+    /// Hits, misses, evictions and entry count of the cache
+    ///
+    /// Hits and misses are counted since the server was created.
+    pub async fn cache_stats(&self) -> crate::cache::CacheStats {
+        self.cache.stats().await
+    }
+
+    /// Get the request journal, if enabled
+    #[must_use]
+    pub fn journal(&self) -> Option<&Arc<journal::RequestJournal>> {
+        self.journal.as_ref()
+    }
+
+    /// Get the per-client rate limiter, if `client_rate_limit_per_second`
+    /// is set
+    #[must_use]
+    pub fn rate_limiter(&self) -> Option<&Arc<rate_limit::ClientRateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Get the caller addresses of HTTP sessions
+    ///
+    /// Only filled in while per-client rate limiting is on.
+    #[must_use]
+    pub fn client_addrs(&self) -> &Arc<client_addr::ClientAddrs> {
+        &self.client_addrs
+    }
+
+    /// Get the subscriptions to `docs://{crate}/latest` resources
+    #[must_use]
+    pub fn subscriptions(&self) -> &Arc<subscriptions::ResourceSubscriptions> {
+        &self.subscriptions
+    }
+
+    /// Get the notifier of tool list changes
+    #[must_use]
+    pub fn tool_list(&self) -> &Arc<tool_list::ToolListNotifier> {
+        &self.tool_list
+    }
+
+    /// Get the forwarder of log messages to clients
+    #[must_use]
+    pub fn client_log(&self) -> &Arc<client_log::ClientLogForwarder> {
+        &self.client_log
+    }
+
+    /// Get the task store of task-augmented tool calls
+    #[must_use]
+    pub fn tasks(&self) -> &Arc<tasks::ToolTasks> {
+        &self.tasks
+    }
+
+    /// Disable exactly `disabled_tools`, as in `server.disabled_tools`
+    ///
+    /// Called at startup and when the configuration is reloaded. Connected
+    /// clients are notified if the enabled tools changed; unknown tool names
+    /// are logged and ignored.
+    pub fn apply_disabled_tools(&self, disabled_tools: &[String]) {
+        let unknown = self.tool_registry.set_disabled(disabled_tools);
+        if !unknown.is_empty() {
+            tracing::warn!("Ignoring unknown tools in server.disabled_tools: {unknown:?}");
+        }
+    }
+
+    /// Use `handle` to change the log level at runtime
+    ///
+    /// Defaults to the handle of [`crate::logging::init_reloadable_logging`]
+    /// when logging was initialized before the server was created.
+    #[must_use]
+    pub fn with_log_level_handle(mut self, handle: LogLevelHandle) -> Self {
+        self.log_level = Some(handle);
+        self
+    }
+
+    /// Get the runtime log level handle, if logging is reloadable
+    #[must_use]
+    pub fn log_level(&self) -> Option<&LogLevelHandle> {
+        self.log_level.as_ref()
+    }
+
     /// Get server info
     ///
     /// Returns MCP initialization result with server metadata and capabilities
@@ -224,13 +394,31 @@ impl CratesDocsServer {
                 website_url: self.config.server.website_url.clone(),
             },
             capabilities: ServerCapabilities {
-                tools: Some(ServerCapabilitiesTools { list_changed: None }),
-                resources: None,
-                prompts: None,
+                tools: Some(ServerCapabilitiesTools {
+                    list_changed: Some(true),
+                }),
+                // Cached crate docs, see `resources`; subscribers of
+                // `docs://{crate}/latest` hear of new versions
+                resources: Some(ServerCapabilitiesResources {
+                    list_changed: None,
+                    subscribe: Some(true),
+                }),
+                prompts: Some(ServerCapabilitiesPrompts { list_changed: None }),
                 experimental: None,
-                completions: None,
-                logging: None,
-                tasks: None,
+                // Crate names and item paths, see `completion`
+                completions: Some(serde_json::Map::new()),
+                // Events of a client's requests are sent to it, see `client_log`
+                logging: Some(serde_json::Map::new()),
+                // Tools declaring `taskSupport` can run as tasks, see `tasks`
+                tasks: Some(ServerTasks {
+                    cancel: Some(serde_json::Map::new()),
+                    list: Some(serde_json::Map::new()),
+                    requests: Some(ServerTaskRequest {
+                        tools: Some(ServerTaskTools {
+                            call: Some(serde_json::Map::new()),
+                        }),
+                    }),
+                }),
             },
             protocol_version: ProtocolVersion::V2025_11_25.into(),
             instructions: Some(
@@ -241,6 +429,82 @@ impl CratesDocsServer {
         }
     }
 
+    /// Verify the cache store after startup
+    ///
+    /// Logs the outcome of [`Cache::check_integrity`]; a repaired store is
+    /// logged as a warning. The same result is reported by the `internal`
+    /// health check.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the cache store could not be inspected
+    pub async fn check_cache_integrity(&self) -> Result<CacheIntegrity> {
+        let integrity = self.cache.check_integrity().await?;
+        if let CacheIntegrity::Repaired { .. } = integrity {
+            tracing::warn!("Cache integrity: {integrity}");
+        } else {
+            tracing::info!("Cache integrity: {integrity}");
+        }
+        Ok(integrity)
+    }
+
+    /// Report requests the previous run left unfinished
+    ///
+    /// Does nothing unless the request journal is enabled. See
+    /// [`journal::RequestJournal::recover`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the journal could not be read or cleared
+    pub async fn recover_interrupted_requests(&self) -> Result<usize> {
+        let Some(journal) = &self.journal else {
+            return Ok(0);
+        };
+        let metrics = crate::metrics::global_metrics();
+        let entries = journal.recover(metrics.as_deref()).await?;
+        if !entries.is_empty() {
+            tracing::warn!(
+                "{} request(s) were interrupted during the previous run",
+                entries.len()
+            );
+        }
+        Ok(entries.len())
+    }
+
+    /// Prefetch the crates and items listed in `[cache.warmup]`
+    ///
+    /// See [`warmup::warm_cache`].
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if nothing is configured or another replica is
+    /// warming the shared cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a warm-up entry is malformed
+    pub async fn warm_cache(&self) -> Result<Option<warmup::WarmupReport>> {
+        let targets = self.config.cache.warmup.targets()?;
+        if targets.is_empty() {
+            return Ok(None);
+        }
+        tracing::info!("Warming the cache with {} entries", targets.len());
+        Ok(warmup::warm_cache(&self.tool_registry, Arc::clone(&self.cache), &targets).await)
+    }
+
+    /// Shut the server down gracefully
+    ///
+    /// Flushes pending cache writes so the store is left consistent for the
+    /// next start. Call it after the transport has stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if pending cache writes could not be flushed
+    pub async fn shutdown(&self) -> Result<()> {
+        tracing::info!("Flushing cache before shutdown");
+        self.cache.flush().await
+    }
+
     /// Run Stdio server
     ///
     /// # Errors
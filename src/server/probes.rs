@@ -0,0 +1,95 @@
+//! Plain HTTP health probes
+//!
+//! `/healthz` (liveness) and `/readyz` (readiness) are served next to the MCP
+//! endpoints but outside the MCP protocol, for Kubernetes probes and load
+//! balancers. Both return the JSON report of the `health_check` tool, with
+//! `200 OK` when every check is healthy and `503 Service Unavailable`
+//! otherwise. They are not authenticated.
+//!
+//! - `/healthz` runs the internal checks only (memory, cache store), so an
+//!   upstream outage does not get the process restarted.
+//! - `/readyz` also probes docs.rs and crates.io.
+//!
+//! Both move below `server.base_path` when one is configured.
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use rust_mcp_sdk::mcp_server::HyperServer;
+
+use crate::tools::health::HealthCheckToolImpl;
+
+/// Liveness probe path
+pub const HEALTHZ_PATH: &str = "/healthz";
+
+/// Readiness probe path
+pub const READYZ_PATH: &str = "/readyz";
+
+/// Mount `/healthz` and `/readyz` below `base_path` on `server`
+pub(crate) fn with_probe_routes(
+    server: HyperServer,
+    health: Arc<HealthCheckToolImpl>,
+    base_path: &str,
+) -> HyperServer {
+    let readiness = Arc::clone(&health);
+    server
+        .with_route(
+            route_path(base_path, HEALTHZ_PATH),
+            axum::routing::get(move || probe(health, "internal")),
+        )
+        .with_route(
+            route_path(base_path, READYZ_PATH),
+            axum::routing::get(move || probe(readiness, "all")),
+        )
+}
+
+/// `path` below `base_path`, with the `'static` lifetime the SDK asks for
+///
+/// Leaks the prefixed path, which is only built once per listener at startup.
+fn route_path(base_path: &str, path: &'static str) -> &'static str {
+    if base_path.is_empty() {
+        path
+    } else {
+        format!("{base_path}{path}").leak()
+    }
+}
+
+/// Run the `check_type` health checks and render them as a probe response
+async fn probe(health: Arc<HealthCheckToolImpl>, check_type: &'static str) -> Response {
+    let (report, healthy) = health.run_check_json(check_type).await;
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    #[tokio::test]
+    async fn test_liveness_probe_reports_internal_checks() {
+        let cache: Arc<dyn crate::cache::Cache> =
+            Arc::new(crate::cache::memory::MemoryCache::new(10));
+        let health = Arc::new(HealthCheckToolImpl::new().with_cache(cache));
+
+        let response = probe(health, "internal").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["status"], "healthy");
+        let names: Vec<_> = report["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|check| check["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["memory", "cache"]);
+    }
+}
@@ -0,0 +1,264 @@
+//! MCP prompts for common documentation workflows
+//!
+//! Each prompt expands to a user message that tells the model which tools
+//! of this server to call, in what order, and how to format the answer, so
+//! a client can offer e.g. "explain how to use a crate" as a slash command.
+
+use rust_mcp_sdk::schema::{
+    GetPromptResult, Prompt, PromptArgument, PromptMessage, Role, RpcError, TextContent,
+};
+use std::collections::BTreeMap;
+
+/// A prompt argument: name, description, required
+type ArgumentSpec = (&'static str, &'static str, bool);
+
+/// A prompt this server offers
+struct PromptSpec {
+    name: &'static str,
+    title: &'static str,
+    description: &'static str,
+    arguments: &'static [ArgumentSpec],
+    render: fn(&Arguments) -> String,
+}
+
+const PROMPTS: &[PromptSpec] = &[
+    PromptSpec {
+        name: "explain_crate_usage",
+        title: "Explain how to use a crate",
+        description: "Explain how to use a crate for a task, with a working example based on its documentation",
+        arguments: &[
+            ("crate", "Crate name, e.g. reqwest", true),
+            ("task", "What to do with it, e.g. \"send a JSON POST request\"", true),
+            ("version", "Version or semver requirement; latest if omitted", false),
+        ],
+        render: explain_crate_usage,
+    },
+    PromptSpec {
+        name: "review_cargo_toml",
+        title: "Review my Cargo.toml",
+        description: "Audit the dependencies of a Cargo.toml: outdated versions, advisories, unmaintained crates and alternatives",
+        arguments: &[("cargo_toml", "Contents of the Cargo.toml", true)],
+        render: review_cargo_toml,
+    },
+    PromptSpec {
+        name: "compare_crates",
+        title: "Compare crates",
+        description: "Compare crates that solve the same problem and recommend one",
+        arguments: &[
+            ("crates", "Comma-separated crate names, e.g. \"reqwest, ureq\"", true),
+            ("use_case", "What they would be used for", false),
+        ],
+        render: compare_crates,
+    },
+];
+
+/// Arguments of a `prompts/get` request, checked against the prompt
+struct Arguments(BTreeMap<String, String>);
+
+impl Arguments {
+    /// Value of a required argument (validated before rendering)
+    fn required(&self, name: &str) -> &str {
+        self.0.get(name).map_or("", String::as_str)
+    }
+
+    /// Value of an optional argument, `None` if blank
+    fn optional(&self, name: &str) -> Option<&str> {
+        self.0
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// The prompts of `prompts/list`
+#[must_use]
+pub fn list_prompts() -> Vec<Prompt> {
+    PROMPTS
+        .iter()
+        .map(|spec| Prompt {
+            name: spec.name.to_string(),
+            title: Some(spec.title.to_string()),
+            description: Some(spec.description.to_string()),
+            arguments: spec
+                .arguments
+                .iter()
+                .map(|(name, description, required)| PromptArgument {
+                    name: (*name).to_string(),
+                    title: None,
+                    description: Some((*description).to_string()),
+                    required: Some(*required),
+                })
+                .collect(),
+            icons: vec![],
+            meta: None,
+        })
+        .collect()
+}
+
+/// Expand the prompt `name` with `arguments`
+///
+/// # Errors
+///
+/// Returns an invalid-params error for an unknown prompt or a missing
+/// required argument
+pub fn get_prompt(
+    name: &str,
+    arguments: Option<BTreeMap<String, String>>,
+) -> std::result::Result<GetPromptResult, RpcError> {
+    let Some(spec) = PROMPTS.iter().find(|spec| spec.name == name) else {
+        let names: Vec<&str> = PROMPTS.iter().map(|spec| spec.name).collect();
+        return Err(RpcError::invalid_params().with_message(format!(
+            "Unknown prompt '{name}', available prompts: {}",
+            names.join(", ")
+        )));
+    };
+    let arguments = Arguments(arguments.unwrap_or_default());
+    for (argument, _, required) in spec.arguments {
+        if *required && arguments.optional(argument).is_none() {
+            return Err(RpcError::invalid_params().with_message(format!(
+                "Prompt '{name}' requires the argument '{argument}'"
+            )));
+        }
+    }
+
+    Ok(GetPromptResult {
+        description: Some(spec.description.to_string()),
+        messages: vec![PromptMessage {
+            role: Role::User,
+            content: TextContent::new((spec.render)(&arguments), None, None).into(),
+        }],
+        meta: None,
+    })
+}
+
+fn explain_crate_usage(arguments: &Arguments) -> String {
+    let crate_name = arguments.required("crate").trim();
+    let task = arguments.required("task").trim();
+    let version = arguments.optional("version");
+    let version_argument = version.map_or_else(String::new, |v| format!(", \"version\": \"{v}\""));
+    let version_label = version.map_or_else(String::new, |v| format!(" {v}"));
+    format!(
+        "Explain how to use the Rust crate `{crate_name}`{version_label} to {task}.\n\
+         \n\
+         Use the crates-docs tools rather than memory, since APIs change between versions:\n\
+         1. Call `summarize_crate` with {{\"crate_name\": \"{crate_name}\"{version_argument}}} for an overview of the crate and its main items.\n\
+         2. Call `lookup_item` for each type, trait or function the task needs, with `format` set to `llm`, to confirm signatures and required features.\n\
+         3. Call `extract_doc_examples` for those items to base the example on the crate's own examples.\n\
+         4. Call `suggest_dependency_line` with the features the example needs.\n\
+         \n\
+         Answer with:\n\
+         - a one-paragraph summary of the approach\n\
+         - the `Cargo.toml` dependency line from step 4\n\
+         - a complete, compilable example for the task\n\
+         - pitfalls or feature flags to be aware of\n\
+         - links to the docs.rs pages you used"
+    )
+}
+
+fn review_cargo_toml(arguments: &Arguments) -> String {
+    let cargo_toml = arguments.required("cargo_toml");
+    format!(
+        "Review the dependencies of this Cargo.toml.\n\
+         \n\
+         1. Call `analyze_cargo_toml` with the manifest below as `cargo_toml` to find outdated requirements, security advisories and yanked or unmaintained crates.\n\
+         2. For each unmaintained or deprecated crate, call `suggest_alternatives` to find a replacement.\n\
+         3. For each major version upgrade, call `lookup_crate` for the new version and point out breaking changes mentioned in its documentation.\n\
+         \n\
+         Answer with a table of the dependencies that need attention (crate, current requirement, suggested change, reason), most severe first, followed by an updated `[dependencies]` section. Say so if nothing needs to change.\n\
+         \n\
+         ```toml\n\
+         {cargo_toml}\n\
+         ```"
+    )
+}
+
+fn compare_crates(arguments: &Arguments) -> String {
+    let crates: Vec<&str> = arguments
+        .required("crates")
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    let crates_json = serde_json::to_string(&crates).unwrap_or_default();
+    let use_case = arguments
+        .optional("use_case")
+        .map_or_else(String::new, |use_case| format!(" for {use_case}"));
+    format!(
+        "Compare the Rust crates {}{use_case} and recommend one.\n\
+         \n\
+         1. Call `batch_lookup_crates` with {{\"crates\": {crates_json}}} for versions, downloads and descriptions.\n\
+         2. Call `summarize_crate` for each crate to compare their APIs.\n\
+         3. Call `crate_build_info` for each crate to compare dependency and build weight.\n\
+         \n\
+         Answer with a comparison table (maturity, popularity, API style, async support, dependencies), a short example of the same task in each crate, and a recommendation with the reasoning behind it.",
+        crates
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    fn text(result: &GetPromptResult) -> &str {
+        &result.messages[0].content.as_text_content().unwrap().text
+    }
+
+    #[test]
+    fn test_list_prompts() {
+        let prompts = list_prompts();
+        let names: Vec<&str> = prompts.iter().map(|prompt| prompt.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["explain_crate_usage", "review_cargo_toml", "compare_crates"]
+        );
+        assert_eq!(prompts[0].arguments[2].required, Some(false));
+    }
+
+    #[test]
+    fn test_get_prompt_renders_tool_calls() {
+        let result = get_prompt(
+            "explain_crate_usage",
+            Some(arguments(&[
+                ("crate", "reqwest"),
+                ("task", "send a JSON POST request"),
+            ])),
+        )
+        .unwrap();
+        assert!(text(&result).contains("`reqwest` to send a JSON POST request"));
+        assert!(text(&result).contains("{\"crate_name\": \"reqwest\"}"));
+
+        let result = get_prompt(
+            "compare_crates",
+            Some(arguments(&[("crates", "reqwest, ureq")])),
+        )
+        .unwrap();
+        assert!(text(&result).contains("{\"crates\": [\"reqwest\",\"ureq\"]}"));
+    }
+
+    #[test]
+    fn test_get_prompt_rejects_unknown_prompts_and_missing_arguments() {
+        assert!(get_prompt("nope", None).is_err());
+        let error = get_prompt(
+            "explain_crate_usage",
+            Some(arguments(&[("crate", "serde")])),
+        )
+        .unwrap_err();
+        assert!(error.message.contains("'task'"));
+        assert!(get_prompt(
+            "review_cargo_toml",
+            Some(arguments(&[("cargo_toml", "  ")]))
+        )
+        .is_err());
+    }
+}
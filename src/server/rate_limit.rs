@@ -0,0 +1,140 @@
+//! Per-client request rate limiting
+//!
+//! Each client gets its own token bucket, so one agent issuing calls in a
+//! tight loop is throttled without slowing down the others. Clients are
+//! identified by the authenticated client id (e.g. the API key that was
+//! used), falling back to the caller's address and then the MCP session id.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::PerformanceConfig;
+
+/// Buckets idle for this long are full again and can be dropped
+const IDLE_BUCKET_TTL: Duration = Duration::from_mins(10);
+
+/// Prune idle buckets once the map grows past this many clients
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Token bucket of a single client
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token-bucket rate limiter keyed by client
+///
+/// Every client may burst up to `burst` calls and then `rate` calls per
+/// second on average.
+#[derive(Debug)]
+pub struct ClientRateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl ClientRateLimiter {
+    /// Create a limiter allowing `rate` calls per second with bursts of
+    /// `burst` calls per client
+    ///
+    /// A `burst` of 0 is treated as 1.
+    #[must_use]
+    pub fn new(rate: u32, burst: u32) -> Self {
+        Self {
+            rate: f64::from(rate),
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create the limiter configured in `[performance]`
+    ///
+    /// Returns `None` if `client_rate_limit_per_second` is 0.
+    #[must_use]
+    pub fn from_config(config: &PerformanceConfig) -> Option<Self> {
+        (config.client_rate_limit_per_second > 0).then(|| {
+            Self::new(
+                config.client_rate_limit_per_second,
+                config.client_rate_limit_burst,
+            )
+        })
+    }
+
+    /// Take one token from `client`'s bucket
+    ///
+    /// # Errors
+    ///
+    /// Returns how long the client has to wait for the next token if its
+    /// bucket is empty
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated) < IDLE_BUCKET_TTL);
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_throttle() {
+        let limiter = ClientRateLimiter::new(1, 3);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.check_at("a", now).is_ok());
+        }
+        let retry_after = limiter.check_at("a", now).unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_tokens_refill_over_time() {
+        let limiter = ClientRateLimiter::new(2, 1);
+        let now = Instant::now();
+        assert!(limiter.check_at("a", now).is_ok());
+        assert!(limiter.check_at("a", now).is_err());
+        assert!(limiter
+            .check_at("a", now + Duration::from_millis(500))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_clients_have_separate_buckets() {
+        let limiter = ClientRateLimiter::new(1, 1);
+        let now = Instant::now();
+        assert!(limiter.check_at("a", now).is_ok());
+        assert!(limiter.check_at("a", now).is_err());
+        assert!(limiter.check_at("b", now).is_ok());
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(ClientRateLimiter::from_config(&PerformanceConfig::default()).is_none());
+    }
+}
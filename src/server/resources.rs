@@ -0,0 +1,305 @@
+//! Cached documentation as MCP resources
+//!
+//! Crate docs fetched by `lookup_crate` are listed as resources with
+//! `docs://{crate}/{version}` URIs, `latest` standing for the newest
+//! release. Clients can attach them as context without a tool call.
+//!
+//! The URI templates `docs://{crate}/{version}` and
+//! `docs://{crate}/{version}/{item_path}` let clients build URIs for any
+//! crate or item. Reading a resource that is not cached fetches it through
+//! `lookup_crate` or `lookup_item`, which cache it like a tool call would.
+
+use rust_mcp_sdk::schema::{
+    schema_utils::SdkErrorCodes, ReadResourceResult, Resource, ResourceTemplate, RpcError,
+    TextResourceContents,
+};
+
+use crate::server::CratesDocsServer;
+
+/// URI scheme of documentation resources
+pub const DOCS_URI_SCHEME: &str = "docs://";
+
+/// Version segment of the URI for the latest release
+const LATEST: &str = "latest";
+
+/// MIME type of the resource contents
+const MARKDOWN: &str = "text/markdown";
+
+/// Most cached crate docs listed by `resources/list`, over all pages
+const MAX_LISTED: usize = 1000;
+
+/// A parsed `docs://{crate}/{version}[/{item_path}]` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocsUri {
+    /// Crate name
+    pub crate_name: String,
+    /// Version, `None` for the latest release
+    pub version: Option<String>,
+    /// Item path such as `serde::Serialize`, `None` for the crate page
+    pub item_path: Option<String>,
+}
+
+impl DocsUri {
+    /// Parse a `docs://` URI; `None` if it is not one
+    #[must_use]
+    pub fn parse(uri: &str) -> Option<Self> {
+        let mut segments = uri.strip_prefix(DOCS_URI_SCHEME)?.splitn(3, '/');
+        let crate_name = segments.next().filter(|name| !name.is_empty())?;
+        let version = segments.next().filter(|version| !version.is_empty())?;
+        let item_path = match segments.next() {
+            Some(path) if path.is_empty() || path.contains('/') => return None,
+            path => path.map(str::to_string),
+        };
+        Some(Self {
+            crate_name: crate_name.to_string(),
+            version: (!version.eq_ignore_ascii_case(LATEST)).then(|| version.to_string()),
+            item_path,
+        })
+    }
+
+    /// The URI of these docs
+    #[must_use]
+    pub fn to_uri(&self) -> String {
+        let uri = docs_uri(&self.crate_name, self.version.as_deref());
+        match &self.item_path {
+            Some(item_path) => format!("{uri}/{item_path}"),
+            None => uri,
+        }
+    }
+}
+
+/// URI of the documentation of `crate_name` at `version` (latest if `None`)
+#[must_use]
+pub fn docs_uri(crate_name: &str, version: Option<&str>) -> String {
+    format!(
+        "{DOCS_URI_SCHEME}{crate_name}/{}",
+        version.unwrap_or(LATEST)
+    )
+}
+
+/// Resources for the cached crate docs, most often fetched first
+///
+/// # Errors
+///
+/// Returns error if the cache could not be read
+pub async fn list_docs_resources(server: &CratesDocsServer) -> crate::error::Result<Vec<Resource>> {
+    let cached = server
+        .doc_service()
+        .doc_cache()
+        .cached_crate_docs(MAX_LISTED)
+        .await?;
+    Ok(cached
+        .into_iter()
+        .map(|(crate_name, version)| {
+            let label = version.as_deref().unwrap_or(LATEST);
+            Resource {
+                uri: docs_uri(&crate_name, version.as_deref()),
+                name: format!("{crate_name}@{label}"),
+                title: Some(format!("{crate_name} {label} documentation")),
+                description: Some(format!("docs.rs documentation of {crate_name} ({label})")),
+                mime_type: Some(MARKDOWN.to_string()),
+                annotations: None,
+                icons: vec![],
+                meta: None,
+                size: None,
+            }
+        })
+        .collect())
+}
+
+/// URI templates for crate and item docs
+#[must_use]
+pub fn docs_resource_templates() -> Vec<ResourceTemplate> {
+    let template =
+        |uri_template: &str, name: &str, title: &str, description: &str| ResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: Some(title.to_string()),
+            description: Some(description.to_string()),
+            mime_type: Some(MARKDOWN.to_string()),
+            annotations: None,
+            icons: vec![],
+            meta: None,
+        };
+    vec![
+        template(
+            "docs://{crate}/{version}",
+            "crate-docs",
+            "Crate documentation",
+            "docs.rs front page of a crate; use `latest` as version for the newest release",
+        ),
+        template(
+            "docs://{crate}/{version}/{item_path}",
+            "item-docs",
+            "Item documentation",
+            "docs.rs page of an item such as `serde::Serialize` or `tokio::spawn`; use `latest` as version for the newest release",
+        ),
+    ]
+}
+
+/// Contents of a `docs://` resource as markdown
+///
+/// # Errors
+///
+/// Returns a resource-not-found error for URIs that are not `docs://`
+/// URIs or docs that could not be looked up
+pub async fn read_docs_resource(
+    server: &CratesDocsServer,
+    uri: &str,
+) -> std::result::Result<ReadResourceResult, RpcError> {
+    let Some(docs) = DocsUri::parse(uri) else {
+        return Err(resource_not_found(
+            uri,
+            format!(
+                "Unknown resource '{uri}', expected docs://{{crate}}/{{version}}[/{{item_path}}]"
+            ),
+        ));
+    };
+    let doc_cache = server.doc_service().doc_cache();
+    let version = docs.version.as_deref();
+    let cached = match &docs.item_path {
+        Some(item_path) => {
+            doc_cache
+                .get_item_docs(&docs.crate_name, item_path, version)
+                .await
+        }
+        None => doc_cache.get_crate_docs(&docs.crate_name, version).await,
+    };
+    let text = match cached {
+        Some(text) => text.to_string(),
+        None => lookup_docs(server, &docs)
+            .await
+            .map_err(|e| resource_not_found(uri, format!("{uri}: {e}")))?,
+    };
+    Ok(ReadResourceResult {
+        contents: vec![TextResourceContents::new(text, uri.to_string())
+            .with_mime_type(MARKDOWN.to_string())
+            .into()],
+        meta: None,
+    })
+}
+
+/// The MCP "resource not found" error (-32002) for `uri`
+pub(crate) fn resource_not_found(uri: &str, message: String) -> RpcError {
+    RpcError {
+        code: SdkErrorCodes::RESOURCE_NOT_FOUND.into(),
+        data: Some(serde_json::json!({ "uri": uri })),
+        message,
+    }
+}
+
+/// Fetch docs that are not cached through `lookup_crate` or `lookup_item`,
+/// which also cache them
+async fn lookup_docs(
+    server: &CratesDocsServer,
+    docs: &DocsUri,
+) -> std::result::Result<String, String> {
+    let mut arguments = serde_json::json!({
+        "crate_name": docs.crate_name,
+        "version": docs.version,
+        "format": "markdown",
+    });
+    let tool = match &docs.item_path {
+        Some(item_path) => {
+            arguments["item_path"] = item_path.as_str().into();
+            "lookup_item"
+        }
+        None => "lookup_crate",
+    };
+    let result = server
+        .tool_registry()
+        .execute_tool(tool, arguments)
+        .await
+        .map_err(|e| e.to_string())?;
+    let text = result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text_content().ok())
+        .map(|content| content.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if result.is_error == Some(true) {
+        Err(text)
+    } else {
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppConfig;
+
+    #[test]
+    fn test_docs_uri_round_trip() {
+        assert_eq!(docs_uri("serde", None), "docs://serde/latest");
+        assert_eq!(docs_uri("tokio", Some("1.40.0")), "docs://tokio/1.40.0");
+        assert_eq!(
+            DocsUri::parse("docs://tokio/1.40.0"),
+            Some(DocsUri {
+                crate_name: "tokio".to_string(),
+                version: Some("1.40.0".to_string()),
+                item_path: None,
+            })
+        );
+        let item = DocsUri::parse("docs://serde/latest/serde::Serialize").unwrap();
+        assert_eq!(item.version, None);
+        assert_eq!(item.item_path.as_deref(), Some("serde::Serialize"));
+        assert_eq!(item.to_uri(), "docs://serde/latest/serde::Serialize");
+
+        assert_eq!(DocsUri::parse("docs://serde"), None);
+        assert_eq!(DocsUri::parse("docs:///1.0"), None);
+        assert_eq!(DocsUri::parse("docs://serde/1.0/"), None);
+        assert_eq!(DocsUri::parse("docs://serde/1.0/a/b"), None);
+        assert_eq!(DocsUri::parse("https://docs.rs/serde"), None);
+    }
+
+    #[test]
+    fn test_templates_parse_as_docs_uris() {
+        for template in docs_resource_templates() {
+            let uri = template
+                .uri_template
+                .replace("{crate}", "serde")
+                .replace("{version}", "latest")
+                .replace("{item_path}", "serde::Serialize");
+            assert!(DocsUri::parse(&uri).is_some(), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_docs_are_listed_and_read() {
+        let server = CratesDocsServer::new(AppConfig::default()).unwrap();
+        let doc_cache = server.doc_service().doc_cache();
+        doc_cache
+            .set_crate_docs("serde", Some("1.0.0"), "# serde")
+            .await
+            .unwrap();
+        doc_cache
+            .set_item_docs("serde", "serde::Serialize", None, "# Serialize")
+            .await
+            .unwrap();
+
+        let resources = list_docs_resources(&server).await.unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].uri, "docs://serde/1.0.0");
+        assert_eq!(resources[0].mime_type.as_deref(), Some(MARKDOWN));
+
+        for (uri, expected) in [
+            ("docs://serde/1.0.0", "# serde"),
+            ("docs://serde/latest/serde::Serialize", "# Serialize"),
+        ] {
+            let result = read_docs_resource(&server, uri).await.unwrap();
+            let rust_mcp_sdk::schema::ReadResourceContent::TextResourceContents(contents) =
+                &result.contents[0]
+            else {
+                panic!("expected text contents");
+            };
+            assert_eq!(contents.text, expected);
+        }
+
+        let error = read_docs_resource(&server, "file:///etc/passwd")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, -32002);
+    }
+}
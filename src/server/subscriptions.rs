@@ -0,0 +1,383 @@
+//! Resource subscriptions for new crate versions
+//!
+//! A client subscribes to `docs://{crate}/latest` and receives a
+//! `notifications/resources/updated` for that URI when a newer version of
+//! the crate is published. The subscribed crates are checked against the
+//! crates.io index every `server.subscription_poll_interval_secs` by a task
+//! started with the first subscription.
+//!
+//! Replicas sharing a Redis cache check each crate once per round: the
+//! replica holding the crate's [`LeaseCoordinator`] lease asks the index and
+//! stores the latest version in the cache, where the others read it. Each
+//! replica still notifies its own subscribers.
+//!
+//! Subscriptions live in memory: they end with the session, and with the
+//! process.
+
+use rust_mcp_sdk::schema::{CallToolError, ResourceUpdatedNotificationParams, RpcError};
+use rust_mcp_sdk::McpServer;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use super::resources::{docs_uri, DocsUri};
+use crate::cache::lease::LeaseCoordinator;
+use crate::tools::docs::resolve_version::{resolve, VersionSpec};
+use crate::tools::docs::DocService;
+
+/// Name the version lookups are logged under
+const LOG_NAME: &str = "resources/subscribe";
+
+/// Lease job name of the version check of `crate_name`
+fn poll_job(crate_name: &str) -> String {
+    format!("subscription_poll:{crate_name}")
+}
+
+/// Cache key of the latest version found by the lease holder
+fn latest_key(crate_name: &str) -> String {
+    format!("subscription_latest:{crate_name}")
+}
+
+/// Subscribers of one crate and its last seen latest version
+struct Subscribed {
+    latest: Option<String>,
+    /// Keyed by session ID
+    subscribers: HashMap<String, Arc<dyn McpServer>>,
+}
+
+/// Crates clients subscribed to, and the poller notifying them
+pub struct ResourceSubscriptions {
+    service: Arc<DocService>,
+    interval: Duration,
+    leases: LeaseCoordinator,
+    crates: Mutex<HashMap<String, Subscribed>>,
+    poller_started: AtomicBool,
+}
+
+impl ResourceSubscriptions {
+    /// Create an empty registry polling every `interval` once in use
+    #[must_use]
+    pub fn new(service: Arc<DocService>, interval: Duration) -> Self {
+        Self {
+            leases: LeaseCoordinator::new(Arc::clone(service.cache())),
+            service,
+            interval,
+            crates: Mutex::new(HashMap::new()),
+            poller_started: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Subscribed>> {
+        self.crates
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Subscribe the session of `runtime` to `uri`
+    ///
+    /// The first subscription starts the poller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error unless `uri` is `docs://{crate}/latest`,
+    /// or a resource-not-found error if the crate does not exist
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        uri: &str,
+        runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<(), RpcError> {
+        let crate_name = subscribed_crate(uri)?;
+        let known = self.lock().contains_key(&crate_name);
+        let latest = if known {
+            None
+        } else {
+            match self.latest_version(&crate_name).await {
+                Ok(latest) => Some(latest),
+                Err(e) if is_not_found(&e) => {
+                    return Err(super::resources::resource_not_found(uri, e.to_string()));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to look up the latest version of {crate_name}: {e}");
+                    None
+                }
+            }
+        };
+
+        let session = session_key(runtime.as_ref());
+        tracing::info!("Session {session} subscribed to {uri}");
+        self.lock()
+            .entry(crate_name)
+            .or_insert_with(|| Subscribed {
+                latest,
+                subscribers: HashMap::new(),
+            })
+            .subscribers
+            .insert(session, runtime);
+
+        if !self.poller_started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(poll_loop(Arc::downgrade(self), self.interval));
+        }
+        Ok(())
+    }
+
+    /// Remove the subscription of `session` to `uri`, if any
+    pub fn unsubscribe(&self, uri: &str, session: Option<&str>) {
+        let Ok(crate_name) = subscribed_crate(uri) else {
+            return;
+        };
+        let session = session.unwrap_or(LOCAL_SESSION);
+        let mut crates = self.lock();
+        if let Some(subscribed) = crates.get_mut(&crate_name) {
+            subscribed.subscribers.remove(session);
+            if subscribed.subscribers.is_empty() {
+                crates.remove(&crate_name);
+            }
+        }
+    }
+
+    /// Crates with at least one subscriber
+    #[must_use]
+    pub fn subscribed_crates(&self) -> Vec<String> {
+        let mut crates: Vec<String> = self.lock().keys().cloned().collect();
+        crates.sort();
+        crates
+    }
+
+    /// Check every subscribed crate for a new version and notify its
+    /// subscribers
+    ///
+    /// Subscribers whose session is gone are dropped. Returns the number of
+    /// notifications sent.
+    pub async fn poll(&self) -> usize {
+        let mut sent = 0;
+        for crate_name in self.subscribed_crates() {
+            let latest = match self.polled_version(&crate_name).await {
+                Ok(latest) => latest,
+                Err(e) => {
+                    tracing::warn!("Failed to check {crate_name} for new versions: {e}");
+                    continue;
+                }
+            };
+            let subscribers = {
+                let mut crates = self.lock();
+                let Some(subscribed) = crates.get_mut(&crate_name) else {
+                    continue;
+                };
+                let published = is_newer(subscribed.latest.as_deref(), &latest);
+                subscribed.latest = Some(latest.clone());
+                if !published {
+                    continue;
+                }
+                subscribed
+                    .subscribers
+                    .iter()
+                    .map(|(session, runtime)| (session.clone(), Arc::clone(runtime)))
+                    .collect::<Vec<_>>()
+            };
+
+            tracing::info!("{crate_name} {latest} was published");
+            // Readers of the latest docs should get the new release
+            if let Err(e) = self
+                .service
+                .doc_cache()
+                .forget_crate_docs(&crate_name, None)
+                .await
+            {
+                tracing::warn!("Failed to drop the cached docs of {crate_name}: {e}");
+            }
+
+            let uri = docs_uri(&crate_name, None);
+            for (session, runtime) in subscribers {
+                let params = ResourceUpdatedNotificationParams {
+                    uri: uri.clone(),
+                    meta: None,
+                };
+                match runtime.notify_resource_updated(params).await {
+                    Ok(()) => sent += 1,
+                    Err(e) => {
+                        tracing::debug!("Dropping subscriber {session} of {uri}: {e}");
+                        self.unsubscribe(&uri, Some(&session));
+                    }
+                }
+            }
+        }
+        sent
+    }
+
+    /// Latest version of `crate_name` for this round of [`Self::poll`]
+    ///
+    /// The lease is kept for the whole interval rather than released, so the
+    /// other replicas read the stored version until the next round. Without
+    /// a stored version (the holder has not checked yet, or other replicas do
+    /// not subscribe to the crate) the index is asked directly.
+    async fn polled_version(&self, crate_name: &str) -> std::result::Result<String, CallToolError> {
+        let cache = self.service.cache();
+        if !self
+            .leases
+            .try_acquire(&poll_job(crate_name), self.interval)
+            .await
+        {
+            if let Some(latest) = cache.get(&latest_key(crate_name)).await {
+                return Ok(latest.to_string());
+            }
+        }
+
+        let latest = self.latest_version(crate_name).await?;
+        if let Err(e) = cache
+            .set(
+                latest_key(crate_name),
+                Arc::from(latest.as_str()),
+                Some(self.interval * 2),
+            )
+            .await
+        {
+            tracing::debug!("Failed to share the latest version of {crate_name}: {e}");
+        }
+        Ok(latest)
+    }
+
+    /// Latest version of `crate_name`, read from the index rather than the
+    /// cache
+    async fn latest_version(&self, crate_name: &str) -> std::result::Result<String, CallToolError> {
+        let resolution = crate::tools::docs::cache::with_no_cache(
+            true,
+            resolve(
+                &self.service,
+                crate_name,
+                "latest",
+                &VersionSpec::Latest,
+                LOG_NAME,
+            ),
+        )
+        .await?;
+        Ok(resolution.version)
+    }
+}
+
+/// Whether the crate looked up does not exist
+fn is_not_found(error: &CallToolError) -> bool {
+    matches!(
+        error.0.downcast_ref::<crate::error::Error>(),
+        Some(crate::error::Error::NotFound { .. })
+    )
+}
+
+/// Session key of clients without a session (stdio)
+const LOCAL_SESSION: &str = "local";
+
+fn session_key(runtime: &dyn McpServer) -> String {
+    runtime
+        .session_id()
+        .unwrap_or_else(|| LOCAL_SESSION.to_string())
+}
+
+/// Crate of a subscribable URI
+///
+/// Only `docs://{crate}/latest` changes when a version is published; pinned
+/// versions and items are rejected.
+fn subscribed_crate(uri: &str) -> std::result::Result<String, RpcError> {
+    match DocsUri::parse(uri) {
+        Some(DocsUri {
+            crate_name,
+            version: None,
+            item_path: None,
+        }) => Ok(crate_name.trim().to_lowercase()),
+        _ => Err(RpcError::invalid_params().with_message(format!(
+            "Cannot subscribe to '{uri}': only docs://{{crate}}/latest changes when a new version is published"
+        ))),
+    }
+}
+
+/// Whether `latest` is a release after `previous`
+///
+/// Nothing is newer than an unknown previous version, which only sets the
+/// baseline; a yanked release makes the latest version go down, which is
+/// not a publication either.
+fn is_newer(previous: Option<&str>, latest: &str) -> bool {
+    let Some(previous) = previous else {
+        return false;
+    };
+    match (
+        semver::Version::parse(previous),
+        semver::Version::parse(latest),
+    ) {
+        (Ok(previous), Ok(latest)) => latest > previous,
+        _ => previous != latest,
+    }
+}
+
+/// Poll until the registry is dropped
+async fn poll_loop(subscriptions: Weak<ResourceSubscriptions>, interval: Duration) {
+    let mut ticks = tokio::time::interval(interval);
+    // The first tick completes immediately; subscribe already set the baseline
+    ticks.tick().await;
+    loop {
+        ticks.tick().await;
+        let Some(subscriptions) = subscriptions.upgrade() else {
+            break;
+        };
+        let sent = subscriptions.poll().await;
+        if sent > 0 {
+            tracing::debug!("Sent {sent} resource update notification(s)");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_latest_crate_docs_are_subscribable() {
+        assert_eq!(subscribed_crate("docs://Tokio/latest").unwrap(), "tokio");
+        assert!(subscribed_crate("docs://tokio/1.40.0").is_err());
+        assert!(subscribed_crate("docs://tokio/latest/tokio::spawn").is_err());
+        assert!(subscribed_crate("https://crates.io/crates/tokio").is_err());
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(!is_newer(None, "1.0.0"));
+        assert!(!is_newer(Some("1.0.0"), "1.0.0"));
+        assert!(is_newer(Some("1.40.0"), "2.0.0"));
+        // A yanked release lowers the latest version
+        assert!(!is_newer(Some("1.40.1"), "1.40.0"));
+    }
+
+    #[tokio::test]
+    async fn test_version_checks_are_shared_between_replicas() {
+        let cache: Arc<dyn crate::cache::Cache> =
+            Arc::new(crate::cache::memory::MemoryCache::new(10));
+        let replica = || {
+            let service = Arc::new(DocService::new(Arc::clone(&cache)).unwrap());
+            ResourceSubscriptions::new(service, Duration::from_mins(1))
+        };
+        let (first, second) = (replica(), replica());
+
+        // The first replica holds the lease of tokio for this round
+        assert!(
+            first
+                .leases
+                .try_acquire(&poll_job("tokio"), Duration::from_mins(1))
+                .await
+        );
+        cache
+            .set(latest_key("tokio"), Arc::from("1.40.0"), None)
+            .await
+            .unwrap();
+        // The second reads the version it found instead of asking the index
+        assert_eq!(second.polled_version("tokio").await.unwrap(), "1.40.0");
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_unknown_is_a_no_op() {
+        let cache: Arc<dyn crate::cache::Cache> =
+            Arc::new(crate::cache::memory::MemoryCache::new(10));
+        let service = Arc::new(DocService::new(cache).unwrap());
+        let subscriptions = ResourceSubscriptions::new(service, Duration::from_mins(1));
+        subscriptions.unsubscribe("docs://tokio/latest", Some("s1"));
+        subscriptions.unsubscribe("not a uri", None);
+        assert!(subscriptions.subscribed_crates().is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Task-augmented tool calls
+//!
+//! Tools declaring `taskSupport: optional` (dependency trees, batch and
+//! manifest analysis) can be called as MCP tasks: the call returns a task
+//! right away, the tool runs in the background, and the client polls
+//! `tasks/get` and fetches the result with `tasks/result`, or gives up with
+//! `tasks/cancel`.
+//!
+//! Tasks are kept in memory, per session, until their TTL expires; the TTL
+//! a client asks for is capped at `server.task_ttl_secs`.
+
+use rust_mcp_sdk::schema::{
+    schema_utils::{ClientJsonrpcRequest, ResultFromServer},
+    CallToolResult, ListTasksResult, RpcError, Task, TaskStatus, TextContent,
+};
+use rust_mcp_sdk::task_store::{InMemoryTaskStore, ServerTaskStore, TaskStore};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::AbortHandle;
+
+/// Suggested interval between `tasks/get` polls (milliseconds)
+const POLL_INTERVAL_MS: i64 = 1000;
+
+/// `_meta` key tying a task result to its task
+const RELATED_TASK_META: &str = "io.modelcontextprotocol/related-task";
+
+/// The task store of the server and the tool calls running in it
+pub struct ToolTasks {
+    store: Arc<InMemoryTaskStore<ClientJsonrpcRequest, ResultFromServer>>,
+    max_ttl: Duration,
+    /// Keyed by task ID; removed by whichever of completion and
+    /// cancellation comes first
+    running: Mutex<HashMap<String, AbortHandle>>,
+    finished: Notify,
+}
+
+impl ToolTasks {
+    /// Create an empty task store keeping tasks for at most `max_ttl`
+    #[must_use]
+    pub fn new(max_ttl: Duration) -> Self {
+        Self {
+            store: Arc::new(InMemoryTaskStore::new(None)),
+            max_ttl,
+            running: Mutex::new(HashMap::new()),
+            finished: Notify::new(),
+        }
+    }
+
+    /// The store passed to the SDK runtime, which creates the tasks
+    #[must_use]
+    pub fn store(&self) -> Arc<ServerTaskStore> {
+        self.store.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, AbortHandle>> {
+        self.running
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// TTL of a new task in milliseconds: the requested one, capped
+    #[must_use]
+    pub fn ttl_ms(&self, requested: Option<i64>) -> i64 {
+        let max = i64::try_from(self.max_ttl.as_millis()).unwrap_or(i64::MAX);
+        requested.map_or(max, |ttl| ttl.clamp(0, max))
+    }
+
+    /// Suggested interval between `tasks/get` polls in milliseconds
+    #[must_use]
+    pub fn poll_interval_ms(&self) -> i64 {
+        POLL_INTERVAL_MS
+    }
+
+    /// Run `call` in the background as the task `task`
+    ///
+    /// The task completes with the tool result, or fails if the result is
+    /// an error.
+    pub fn spawn<F>(self: &Arc<Self>, task: &Task, session: Option<String>, call: F)
+    where
+        F: Future<Output = CallToolResult> + Send + 'static,
+    {
+        let tasks = Arc::clone(self);
+        let task_id = task.task_id.clone();
+        // Held while spawning, so the task cannot finish before it is listed
+        let mut running = self.lock();
+        let handle = tokio::spawn(async move {
+            let result = call.await;
+            if tasks.lock().remove(&task_id).is_none() {
+                // Cancelled meanwhile
+                return;
+            }
+            let status = if result.is_error == Some(true) {
+                TaskStatus::Failed
+            } else {
+                TaskStatus::Completed
+            };
+            tracing::debug!("Task {task_id} {status}");
+            tasks.finish(&task_id, session, status, result).await;
+        });
+        running.insert(task.task_id.clone(), handle.abort_handle());
+    }
+
+    async fn finish(
+        &self,
+        task_id: &str,
+        session: Option<String>,
+        status: TaskStatus,
+        result: CallToolResult,
+    ) {
+        self.store
+            .store_task_result(task_id, status, result.into(), session.as_ref())
+            .await;
+        self.finished.notify_waiters();
+    }
+
+    /// State of a task of `session`
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error if there is no such task
+    pub async fn get(&self, task_id: &str, session: Option<String>) -> Result<Task, RpcError> {
+        self.store
+            .get_task(task_id, session)
+            .await
+            .ok_or_else(|| unknown_task(task_id))
+    }
+
+    /// Tasks of `session`, newest first
+    pub async fn list(&self, cursor: Option<String>, session: Option<String>) -> ListTasksResult {
+        self.store.list_tasks(cursor, session).await
+    }
+
+    /// Cancel a running task of `session`
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error if there is no such task or it has
+    /// already finished
+    pub async fn cancel(&self, task_id: &str, session: Option<String>) -> Result<Task, RpcError> {
+        let task = self.get(task_id, session.clone()).await?;
+        let Some(handle) = self.lock().remove(task_id) else {
+            return Err(RpcError::invalid_params()
+                .with_message(format!("Task '{task_id}' is already {}", task.status)));
+        };
+        handle.abort();
+        tracing::debug!("Task {task_id} cancelled");
+        let result = CallToolResult {
+            content: vec![TextContent::new("Task cancelled".to_string(), None, None).into()],
+            is_error: Some(true),
+            meta: None,
+            structured_content: None,
+        };
+        self.finish(task_id, session.clone(), TaskStatus::Cancelled, result)
+            .await;
+        self.get(task_id, session).await
+    }
+
+    /// Result of a task of `session`, waiting for the task to finish
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error if there is no such task, e.g. one
+    /// whose TTL expired while waiting
+    pub async fn result(
+        &self,
+        task_id: &str,
+        session: Option<String>,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, RpcError> {
+        loop {
+            let finished = self.finished.notified();
+            tokio::pin!(finished);
+            // Registered before checking, so a task finishing in between
+            // still wakes this loop
+            finished.as_mut().enable();
+            if self
+                .get(task_id, session.clone())
+                .await?
+                .status
+                .is_terminal()
+            {
+                break;
+            }
+            finished.await;
+        }
+
+        let result = self
+            .store
+            .get_task_result(task_id, session)
+            .await
+            .ok_or_else(|| unknown_task(task_id))?;
+        let mut payload = match serde_json::to_value(result) {
+            Ok(serde_json::Value::Object(payload)) => payload,
+            _ => serde_json::Map::new(),
+        };
+        let meta = payload
+            .entry("_meta")
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let Some(meta) = meta.as_object_mut() {
+            meta.insert(
+                RELATED_TASK_META.to_string(),
+                serde_json::json!({ "taskId": task_id }),
+            );
+        }
+        Ok(payload)
+    }
+}
+
+fn unknown_task(task_id: &str) -> RpcError {
+    RpcError::invalid_params().with_message(format!("Unknown task '{task_id}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_sdk::schema::{CallToolRequest, CallToolRequestParams, RequestId};
+    use rust_mcp_sdk::task_store::CreateTaskOptions;
+
+    async fn create_task(tasks: &ToolTasks) -> Task {
+        let request = CallToolRequest::new(
+            RequestId::Integer(1),
+            CallToolRequestParams {
+                arguments: None,
+                meta: None,
+                name: "dependency_tree".to_string(),
+                task: None,
+            },
+        );
+        tasks
+            .store
+            .create_task(
+                CreateTaskOptions {
+                    ttl: Some(tasks.ttl_ms(None)),
+                    poll_interval: Some(tasks.poll_interval_ms()),
+                    meta: None,
+                },
+                RequestId::Integer(1),
+                ClientJsonrpcRequest::CallToolRequest(request),
+                None,
+            )
+            .await
+    }
+
+    #[test]
+    fn test_ttl_is_capped() {
+        let tasks = ToolTasks::new(Duration::from_mins(1));
+        assert_eq!(tasks.ttl_ms(None), 60_000);
+        assert_eq!(tasks.ttl_ms(Some(5_000)), 5_000);
+        assert_eq!(tasks.ttl_ms(Some(3_600_000)), 60_000);
+    }
+
+    #[tokio::test]
+    async fn test_task_completes_with_result() {
+        let tasks = Arc::new(ToolTasks::new(Duration::from_mins(1)));
+        let task = create_task(&tasks).await;
+        tasks.spawn(&task, None, async {
+            CallToolResult::text_content(vec!["done".to_string().into()])
+        });
+
+        let payload = tasks.result(&task.task_id, None).await.unwrap();
+        assert_eq!(payload["content"][0]["text"], "done");
+        assert_eq!(
+            payload["_meta"][RELATED_TASK_META]["taskId"],
+            task.task_id.as_str()
+        );
+        let task = tasks.get(&task.task_id, None).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(tasks.list(None, None).await.tasks.len(), 1);
+
+        // Finished tasks cannot be cancelled
+        assert!(tasks.cancel(&task.task_id, None).await.is_err());
+        assert!(tasks.get("no-such-task", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_task() {
+        let tasks = Arc::new(ToolTasks::new(Duration::from_mins(1)));
+        let task = create_task(&tasks).await;
+        tasks.spawn(&task, None, std::future::pending());
+
+        let cancelled = tasks.cancel(&task.task_id, None).await.unwrap();
+        assert_eq!(cancelled.status, TaskStatus::Cancelled);
+        let payload = tasks.result(&task.task_id, None).await.unwrap();
+        assert_eq!(payload["isError"], true);
+    }
+}
@@ -0,0 +1,120 @@
+//! `notifications/tools/list_changed` for connected clients
+//!
+//! Every session is registered once initialized. When a tool is enabled or
+//! disabled, through `set_tool_enabled` or a reload of
+//! `server.disabled_tools`, each registered session is told to fetch
+//! `tools/list` again.
+//!
+//! Sessions are held weakly, so ending a session needs no cleanup here.
+
+use rust_mcp_sdk::McpServer;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::tools::ToolRegistry;
+
+/// Session key of clients without a session (stdio)
+const LOCAL_SESSION: &str = "local";
+
+/// Initialized sessions, and the task notifying them of tool list changes
+pub struct ToolListNotifier {
+    registry: Arc<ToolRegistry>,
+    /// Keyed by session ID
+    sessions: Mutex<HashMap<String, Weak<dyn McpServer>>>,
+    listener_started: AtomicBool,
+}
+
+impl ToolListNotifier {
+    /// Create a notifier for changes of `registry`
+    #[must_use]
+    pub fn new(registry: Arc<ToolRegistry>) -> Self {
+        Self {
+            registry,
+            sessions: Mutex::new(HashMap::new()),
+            listener_started: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Weak<dyn McpServer>>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Notify the session of `runtime` of later tool list changes
+    ///
+    /// The first session starts the task waiting for changes.
+    pub fn register(self: &Arc<Self>, runtime: &Arc<dyn McpServer>) {
+        let session = runtime
+            .session_id()
+            .unwrap_or_else(|| LOCAL_SESSION.to_string());
+        let mut sessions = self.lock();
+        sessions.retain(|_, runtime| runtime.strong_count() > 0);
+        sessions.insert(session, Arc::downgrade(runtime));
+        drop(sessions);
+
+        if !self.listener_started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(listen(Arc::downgrade(self), self.registry.watch_changes()));
+        }
+    }
+
+    /// Number of sessions still connected
+    #[must_use]
+    pub fn session_count(&self) -> usize {
+        self.lock()
+            .values()
+            .filter(|runtime| runtime.strong_count() > 0)
+            .count()
+    }
+
+    /// Send `notifications/tools/list_changed` to every connected session
+    ///
+    /// Sessions that are gone or fail to receive it are dropped. Returns the
+    /// number of notifications sent.
+    pub async fn notify(&self) -> usize {
+        let sessions: Vec<(String, Arc<dyn McpServer>)> = {
+            let mut sessions = self.lock();
+            sessions.retain(|_, runtime| runtime.strong_count() > 0);
+            sessions
+                .iter()
+                .filter_map(|(session, runtime)| Some((session.clone(), runtime.upgrade()?)))
+                .collect()
+        };
+
+        let mut sent = 0;
+        for (session, runtime) in sessions {
+            match runtime.notify_tool_list_changed(None).await {
+                Ok(()) => sent += 1,
+                Err(e) => {
+                    tracing::debug!("Dropping session {session} from tool list notifications: {e}");
+                    self.lock().remove(&session);
+                }
+            }
+        }
+        sent
+    }
+}
+
+/// Notify sessions of each change until the notifier is dropped
+async fn listen(notifier: Weak<ToolListNotifier>, mut changes: tokio::sync::watch::Receiver<u64>) {
+    while changes.changed().await.is_ok() {
+        let Some(notifier) = notifier.upgrade() else {
+            break;
+        };
+        let sent = notifier.notify().await;
+        tracing::debug!("Sent {sent} tool list changed notification(s)");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_notify_without_sessions() {
+        let notifier = ToolListNotifier::new(Arc::new(ToolRegistry::new()));
+        assert_eq!(notifier.session_count(), 0);
+        assert_eq!(notifier.notify().await, 0);
+    }
+}
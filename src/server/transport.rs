@@ -8,6 +8,7 @@
 //! - **HTTP**: Streamable HTTP, supports stateless requests
 //! - **SSE**: Server-Sent Events, supports server push
 //! - **Hybrid**: Hybrid mode, supports both HTTP and SSE
+//! - **All**: Stdio and Hybrid at once, sharing one server
 //!
 //! # Example
 //!
@@ -32,7 +33,6 @@ use crate::server::handler::CratesDocsHandler;
 use crate::server::CratesDocsServer;
 use rust_mcp_sdk::{
     error::McpSdkError,
-    event_store,
     mcp_server::{hyper_server, server_runtime, HyperServerOptions, McpServerOptions},
     McpServer, StdioTransport, ToMcpServerHandler, TransportOptions,
 };
@@ -80,7 +80,7 @@ pub async fn run_stdio_server(server: &CratesDocsServer) -> Result<()> {
             server_details: server_info,
             transport,
             handler: handler.to_mcp_server_handler(),
-            task_store: None,
+            task_store: Some(server.tasks().store()),
             client_task_store: None,
             message_observer: None,
         });
@@ -94,6 +94,26 @@ pub async fn run_stdio_server(server: &CratesDocsServer) -> Result<()> {
     Ok(())
 }
 
+/// Run the Stdio server and the Hybrid (HTTP + SSE) server side by side.
+///
+/// Both serve the same `CratesDocsServer`, so the local editor on stdio and
+/// remote clients over HTTP share the cache, metrics and rate limits. The
+/// process stops when either transport does, typically when the editor closes
+/// stdin.
+///
+/// # Errors
+///
+/// Returns error if either server fails to start
+pub async fn run_all_server(server: &CratesDocsServer) -> Result<()> {
+    tokio::select! {
+        result = run_stdio_server(server) => {
+            tracing::info!("Stdio transport closed, stopping the HTTP/SSE transport");
+            result
+        }
+        result = run_hyper_server(server, HyperServerConfig::hybrid()) => result,
+    }
+}
+
 /// Hyper server configuration
 ///
 /// Configuration for HTTP/SSE/Hybrid MCP servers using the Builder pattern.
@@ -203,8 +223,8 @@ fn build_api_key_auth(
 /// In-process API-key enforcement is active only when the binary is built with
 /// both the `api-key` and `auth` features and `api_key.enabled` is set; the
 /// SDK's `AuthMiddleware` then rejects requests without a valid
-/// `Authorization: Bearer <key>`. OAuth, by contrast, is still **not** wired
-/// into the request pipeline, so an enabled OAuth config protects nothing.
+/// `Authorization: Bearer <key>`. OAuth is enforced the same way whenever the
+/// `auth` feature is compiled in, with tokens issued by `/oauth/callback`.
 /// Reporting both accurately avoids giving operators a false sense of security
 /// (and avoids hiding that protection is, in fact, on).
 fn warn_if_auth_configured_but_unenforced(server_config: &crate::config::AppConfig) {
@@ -229,16 +249,98 @@ fn warn_if_auth_configured_but_unenforced(server_config: &crate::config::AppConf
         }
     }
 
-    // OAuth is accepted in configuration but never attached to the pipeline.
-    if server_config.auth.oauth.enabled || server_config.oauth.enabled {
+    if server_config.auth.jwt.enabled {
+        if cfg!(feature = "auth") {
+            tracing::info!(
+                issuer = server_config.auth.jwt.issuer.as_deref().unwrap_or_default(),
+                "JWT authentication is ENFORCED on the HTTP/SSE transport: clients must send an \
+                 access token signed by a key of the issuer's JWKS as \
+                 `Authorization: Bearer <jwt>` or receive 401."
+            );
+        } else {
+            tracing::warn!(
+                "JWT authentication is enabled in configuration but is NOT enforced: this binary \
+                 was built without the `auth` feature, so HTTP/SSE requests are unauthenticated."
+            );
+        }
+    }
+
+    if active_oauth_config(server_config).is_some() {
+        if cfg!(feature = "auth") {
+            tracing::info!(
+                "OAuth authentication is ENFORCED on the HTTP/SSE transport: clients obtain a \
+                 Bearer token by visiting /oauth/authorize and must send it as \
+                 `Authorization: Bearer <token>` or receive 401."
+            );
+        } else {
+            tracing::warn!(
+                "OAuth authentication is enabled in configuration but is NOT enforced: this \
+                 binary was built without the `auth` feature, so HTTP/SSE requests are \
+                 unauthenticated. Do not expose this server on an untrusted network."
+            );
+        }
+    } else if server_config.oauth.enabled {
         tracing::warn!(
-            "OAuth authentication is enabled in configuration but is NOT enforced on the \
-             HTTP/SSE transport: OAuth requests are not validated. Do not rely on it for access \
-             control; use API-key authentication or an authenticating reverse proxy instead."
+            "The top-level [oauth] section is enabled but server.enable_oauth is false, so it is \
+             ignored and OAuth is NOT enforced. Set server.enable_oauth = true or configure \
+             [auth.oauth] instead."
         );
     }
 }
 
+/// The OAuth configuration in effect, if any.
+///
+/// `auth.oauth` wins; the legacy top-level `oauth` section only applies together
+/// with `server.enable_oauth`, the same flag that gates its validation.
+fn active_oauth_config(
+    server_config: &crate::config::AppConfig,
+) -> Option<&crate::server::auth::OAuthConfig> {
+    if server_config.auth.oauth.enabled {
+        Some(&server_config.auth.oauth)
+    } else if server_config.server.enable_oauth && server_config.oauth.enabled {
+        Some(&server_config.oauth)
+    } else {
+        None
+    }
+}
+
+/// Build the SDK auth provider for the HTTP/SSE transport, if any is enabled.
+///
+/// Enabled methods are chained so a token accepted by any of them passes:
+/// OAuth (which also serves the authorization-code routes) first, then JWT
+/// validation, then API keys (where compiled in).
+#[cfg(feature = "auth")]
+fn build_auth(
+    server_config: &crate::config::AppConfig,
+) -> Option<Arc<dyn rust_mcp_sdk::auth::AuthProvider>> {
+    #[cfg(feature = "api-key")]
+    let mut provider = build_api_key_auth(server_config);
+    #[cfg(not(feature = "api-key"))]
+    let mut provider: Option<Arc<dyn rust_mcp_sdk::auth::AuthProvider>> = None;
+
+    if server_config.auth.jwt.enabled {
+        let jwt = crate::server::auth::JwtValidator::new(server_config.auth.jwt.clone());
+        provider = Some(Arc::new(match provider {
+            Some(fallback) => jwt.with_fallback(fallback),
+            None => jwt,
+        }));
+    }
+
+    if let Some(oauth) = active_oauth_config(server_config) {
+        let oauth = crate::server::auth::OAuthAuthProvider::new(oauth.clone())
+            .with_base_path(&server_config.server.base_path)
+            .with_forwarded(crate::server::forwarded::Forwarded::new(
+                server_config.server.trust_forwarded_headers,
+            ));
+        provider = Some(Arc::new(match provider {
+            Some(fallback) => oauth.with_fallback(fallback),
+            None => oauth,
+        }));
+    }
+
+    provider
+}
+
 /// Warn when API-key header / query settings are set but ignored in-process.
 ///
 /// The SDK middleware reads **only** `Authorization: Bearer <token>` — it cannot
@@ -290,20 +392,14 @@ fn warn_if_metrics_configured_but_unavailable(server_config: &crate::config::App
 
 /// Warn when server resource limits are configured but not enforced.
 ///
-/// `request_timeout_secs`, `response_timeout_secs`, and `max_connections` are
-/// accepted in configuration, but the underlying SDK `HyperServerOptions` does
-/// not expose request/response timeouts or a connection cap, so these values
-/// are never applied. Warning when an operator sets a non-default value avoids
-/// a false sense that the server enforces limits it does not.
+/// `max_connections` is accepted in configuration, but the underlying SDK
+/// `HyperServerOptions` does not expose a connection cap, so the value is
+/// never applied. Warning when an operator sets a non-default value avoids a
+/// false sense that the server enforces a limit it does not. (The request and
+/// response timeouts are enforced by the document service and the handler.)
 fn unenforced_server_limits(server_config: &crate::config::AppConfig) -> Vec<&'static str> {
     let defaults = crate::config::ServerConfig::default();
     let mut unenforced = Vec::new();
-    if server_config.server.request_timeout_secs != defaults.request_timeout_secs {
-        unenforced.push("request_timeout_secs");
-    }
-    if server_config.server.response_timeout_secs != defaults.response_timeout_secs {
-        unenforced.push("response_timeout_secs");
-    }
     if server_config.server.max_connections != defaults.max_connections {
         unenforced.push("max_connections");
     }
@@ -316,8 +412,8 @@ fn warn_if_unenforced_server_limits_configured(server_config: &crate::config::Ap
         tracing::warn!(
             fields = unenforced.join(", "),
             "These server limit settings are configured with non-default values but are NOT \
-             enforced: the HTTP transport applies neither request/response timeouts nor a maximum \
-             connection cap. These settings currently have no effect."
+             enforced: the HTTP transport applies no maximum connection cap. These settings \
+             currently have no effect."
         );
     }
 }
@@ -363,12 +459,19 @@ fn host_is_loopback(host: &str) -> bool {
 /// (requests and keys travel unencrypted over HTTP); otherwise the risk is the
 /// absence of any authentication. Both warrant a reverse proxy.
 fn warn_if_network_exposed(server_config: &crate::config::AppConfig) {
-    if host_is_loopback(&server_config.server.host) {
+    let exposed: Vec<String> = server_config
+        .server
+        .bind_hosts()
+        .into_iter()
+        .filter(|host| !host_is_loopback(host))
+        .collect();
+    if exposed.is_empty() {
         return;
     }
+    let host = exposed.join(", ");
     if api_key_auth_enforced(server_config) {
         tracing::warn!(
-            host = %server_config.server.host,
+            %host,
             "Server is binding to a non-loopback address and is reachable from other hosts on \
              the network. API-key authentication IS enforced (requests need `Authorization: \
              Bearer <key>`), but traffic is sent UNENCRYPTED over plain HTTP: anyone who can \
@@ -377,7 +480,7 @@ fn warn_if_network_exposed(server_config: &crate::config::AppConfig) {
         );
     } else {
         tracing::warn!(
-            host = %server_config.server.host,
+            %host,
             "Server is binding to a non-loopback address and is reachable from other hosts on \
              the network. The HTTP/SSE transport performs no authentication; put a reverse proxy \
              with authentication in front of it, restrict the network, or run in stdio mode."
@@ -385,6 +488,43 @@ fn warn_if_network_exposed(server_config: &crate::config::AppConfig) {
     }
 }
 
+/// Whether the IPv6 wildcard address also accepts IPv4 connections
+///
+/// Probed on an ephemeral port: if `[::]:p` is bound, binding `0.0.0.0:p`
+/// fails exactly when the IPv6 socket is dual-stack (the Linux default,
+/// unless `net.ipv6.bindv6only` is set).
+fn ipv6_wildcard_is_dual_stack() -> bool {
+    let Ok(v6) = std::net::TcpListener::bind((std::net::Ipv6Addr::UNSPECIFIED, 0)) else {
+        return false;
+    };
+    let Ok(port) = v6.local_addr().map(|addr| addr.port()) else {
+        return false;
+    };
+    matches!(
+        std::net::TcpListener::bind((std::net::Ipv4Addr::UNSPECIFIED, port)),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse
+    )
+}
+
+/// Hosts to bind, given the configured ones
+///
+/// Binding `0.0.0.0` next to a dual-stack `::` on the same port fails with
+/// "address in use", so the IPv4 wildcard is dropped when `::` already
+/// covers it.
+fn listen_hosts(hosts: Vec<String>, dual_stack: impl FnOnce() -> bool) -> Vec<String> {
+    let is = |host: &str, ip: std::net::IpAddr| host.parse::<std::net::IpAddr>() == Ok(ip);
+    let v4_any = std::net::IpAddr::from(std::net::Ipv4Addr::UNSPECIFIED);
+    let v6_any = std::net::IpAddr::from(std::net::Ipv6Addr::UNSPECIFIED);
+    if hosts.iter().any(|host| is(host, v6_any))
+        && hosts.iter().any(|host| is(host, v4_any))
+        && dual_stack()
+    {
+        tracing::info!("Binding only `::`: it accepts IPv4 connections on this system as well");
+        return hosts.into_iter().filter(|host| !is(host, v4_any)).collect();
+    }
+    hosts
+}
+
 /// Warn when DNS rebinding protection is disabled, so the configured
 /// `allowed_hosts`/`allowed_origins` allowlists are not enforced.
 ///
@@ -433,14 +573,12 @@ fn warn_if_dns_rebinding_protection_disabled(server_config: &crate::config::AppC
 /// ```
 pub async fn run_hyper_server(server: &CratesDocsServer, config: HyperServerConfig) -> Result<()> {
     let server_config = server.config();
-    let server_info = server.server_info();
-    let handler = CratesDocsHandler::new(Arc::new(server.clone()));
+    let addresses = server_config.server.listen_addresses().join(", ");
 
     tracing::info!(
-        "Starting {} MCP server on {}:{}...",
+        "Starting {} MCP server on {}...",
         config.protocol_name(),
-        server_config.server.host,
-        server_config.server.port
+        addresses
     );
 
     warn_if_auth_configured_but_unenforced(server_config);
@@ -452,72 +590,140 @@ pub async fn run_hyper_server(server: &CratesDocsServer, config: HyperServerConf
     warn_if_network_exposed(server_config);
     warn_if_dns_rebinding_protection_disabled(server_config);
 
-    // Create Hyper server options with security settings from config
-    let options = HyperServerOptions {
-        host: server_config.server.host.clone(),
-        port: server_config.server.port,
-        transport_options: Arc::new(TransportOptions::default()),
-        sse_support: config.sse_support(),
-        event_store: Some(Arc::new(event_store::InMemoryEventStore::default())),
-        task_store: None,
-        client_task_store: None,
-        allowed_hosts: Some(server_config.server.allowed_hosts.clone()),
-        allowed_origins: Some(server_config.server.allowed_origins.clone()),
-        // Without this flag the SDK never installs the DnsRebindProtector, so
-        // the allowlists above would be silently ignored. Honor the operator's
-        // explicit opt-in instead.
-        dns_rebinding_protection: server_config.server.dns_rebinding_protection,
-        health_endpoint: Some("/health".to_string()),
-        // Runtime on/off switch for in-process auth: `Some` only when
-        // `api_key.enabled` is set, which makes the SDK attach its
-        // `AuthMiddleware`. Toggling the config flag + restart flips
-        // enforcement without a rebuild. Cfg-gated as a field init (rather than
-        // a `mut` mutation) so `options` stays immutable under `-D warnings`.
-        #[cfg(all(feature = "api-key", feature = "auth"))]
-        auth: build_api_key_auth(server_config),
-        ..Default::default()
-    };
-
     if server_config.server.dns_rebinding_protection
         && server_config.server.allowed_hosts.is_empty()
         && server_config.server.allowed_origins.is_empty()
     {
         tracing::warn!(
-            "dns_rebinding_protection is enabled but both allowed_hosts and              allowed_origins are empty; no Host/Origin validation will occur"
+            "dns_rebinding_protection is enabled but both allowed_hosts and allowed_origins \
+             are empty; no Host/Origin validation will occur"
         );
     }
 
-    // Create HTTP/SSE/Hybrid server
-    let mcp_server =
-        hyper_server::create_server(server_info, handler.to_mcp_server_handler(), options);
+    // Shared by every listener, so a stream can be resumed on any of them
+    let event_store =
+        crate::server::event_store::open(&server_config.event_store, server.cache().clone())?;
+    #[cfg(feature = "auth")]
+    let auth = build_auth(server_config);
+    let health = Arc::new(
+        crate::tools::health::HealthCheckToolImpl::new().with_cache(server.cache().clone()),
+    );
+    let base_path = crate::server::forwarded::normalize_base_path(&server_config.server.base_path);
+
+    // The SDK binds a single address per server, so each host gets its own
+    // listener. Sessions live in the listener that created them.
+    let mut listeners = tokio::task::JoinSet::new();
+    for host in listen_hosts(
+        server_config.server.bind_hosts(),
+        ipv6_wildcard_is_dual_stack,
+    ) {
+        // The SDK does not expose caller addresses, so per-client rate
+        // limiting puts a recording proxy on the public address and moves
+        // the SDK server to a loopback port behind it
+        let (host, port) = if server.rate_limiter().is_some() {
+            let backend = front_with_client_addr_proxy(server, &host).await?;
+            (backend.ip().to_string(), backend.port())
+        } else {
+            (host, server_config.server.port)
+        };
+
+        // Create Hyper server options with security settings from config
+        let options = HyperServerOptions {
+            host,
+            port,
+            transport_options: Arc::new(TransportOptions::default()),
+            sse_support: config.sse_support(),
+            custom_streamable_http_endpoint: Some(format!("{base_path}/mcp")),
+            custom_sse_endpoint: Some(format!("{base_path}/sse")),
+            custom_messages_endpoint: Some(format!("{base_path}/messages")),
+            event_store: Some(Arc::clone(&event_store)),
+            task_store: Some(server.tasks().store()),
+            client_task_store: None,
+            allowed_hosts: Some(server_config.server.allowed_hosts.clone()),
+            allowed_origins: Some(server_config.server.allowed_origins.clone()),
+            // Without this flag the SDK never installs the DnsRebindProtector, so
+            // the allowlists above would be silently ignored. Honor the operator's
+            // explicit opt-in instead.
+            dns_rebinding_protection: server_config.server.dns_rebinding_protection,
+            health_endpoint: Some(format!("{base_path}/health")),
+            // Runtime on/off switch for in-process auth: `Some` only when
+            // `api_key.enabled` or OAuth is set, which makes the SDK attach its
+            // `AuthMiddleware` (and mount the OAuth routes). Toggling the config
+            // flags + restart flips enforcement without a rebuild. Cfg-gated as a
+            // field init (rather than a `mut` mutation) so `options` stays
+            // immutable under `-D warnings`.
+            #[cfg(feature = "auth")]
+            auth: auth.clone(),
+            ..Default::default()
+        };
+
+        // Create HTTP/SSE/Hybrid server
+        let handler = CratesDocsHandler::new(Arc::new(server.clone()));
+        let mcp_server = hyper_server::create_server(
+            server.server_info(),
+            handler.to_mcp_server_handler(),
+            options,
+        );
+        let mcp_server =
+            super::probes::with_probe_routes(mcp_server, Arc::clone(&health), &base_path);
+        listeners.spawn(mcp_server.start());
+    }
 
     // Build the started message based on the protocol
     let started_msg = if config.sse_support() && config.protocol_name() != "SSE" {
         // Hybrid mode
         format!(
-            "{} MCP server started, listening on {}:{} (HTTP + SSE)",
+            "{} MCP server started, listening on {} (HTTP + SSE)",
             config.protocol_name(),
-            server_config.server.host,
-            server_config.server.port
+            addresses
         )
     } else {
         format!(
-            "{} MCP server started, listening on {}:{}",
+            "{} MCP server started, listening on {}",
             config.protocol_name(),
-            server_config.server.host,
-            server_config.server.port
+            addresses
         )
     };
     tracing::info!("{}", started_msg);
 
-    mcp_server
-        .start()
-        .await
-        .map_err(|e: McpSdkError| crate::error::Error::mcp("server_start", e.to_string()))?;
+    // A listener that fails to bind takes the others down with it
+    while let Some(result) = listeners.join_next().await {
+        result
+            .map_err(|e| crate::error::Error::mcp("server_start", e.to_string()))?
+            .map_err(|e: McpSdkError| crate::error::Error::mcp("server_start", e.to_string()))?;
+    }
 
     Ok(())
 }
 
+/// Serve the recording proxy of [`super::client_addr`] on `host`
+///
+/// Returns the loopback address the SDK server has to listen on.
+async fn front_with_client_addr_proxy(
+    server: &CratesDocsServer,
+    host: &str,
+) -> Result<std::net::SocketAddr> {
+    let port = server.config().server.port;
+    let bind_error = |e: std::io::Error| crate::error::Error::mcp("server_start", e.to_string());
+    let listener = tokio::net::TcpListener::bind((host, port))
+        .await
+        .map_err(bind_error)?;
+    // Reserve a free loopback port; the SDK binds it again right away
+    let backend = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0))
+        .and_then(|reserved| reserved.local_addr())
+        .map_err(bind_error)?;
+
+    let addrs = Arc::clone(server.client_addrs());
+    let forwarded =
+        super::forwarded::Forwarded::new(server.config().server.trust_forwarded_headers);
+    tokio::spawn(async move {
+        if let Err(e) = super::client_addr::serve(listener, backend, addrs, forwarded).await {
+            tracing::error!("Client address proxy stopped: {}", e);
+        }
+    });
+    Ok(backend)
+}
+
 /// Transport mode
 ///
 /// Defines the transport protocol types supported by MCP server.
@@ -528,6 +734,7 @@ pub async fn run_hyper_server(server: &CratesDocsServer, config: HyperServerConf
 /// - `Http`: Streamable HTTP, supports stateless requests
 /// - `Sse`: Server-Sent Events, supports server push
 /// - `Hybrid`: Hybrid mode, supports both HTTP and SSE
+/// - `All`: Stdio and Hybrid at once
 ///
 /// # Example
 ///
@@ -549,6 +756,8 @@ pub enum TransportMode {
     Sse,
     /// Hybrid mode (supports both HTTP and SSE)
     Hybrid,
+    /// Stdio and Hybrid at once (local editor plus remote clients)
+    All,
 }
 
 impl std::str::FromStr for TransportMode {
@@ -560,6 +769,7 @@ impl std::str::FromStr for TransportMode {
             "http" => Ok(TransportMode::Http),
             "sse" => Ok(TransportMode::Sse),
             "hybrid" => Ok(TransportMode::Hybrid),
+            "all" => Ok(TransportMode::All),
             _ => Err(format!("Unknown transport mode: {s}")),
         }
     }
@@ -572,19 +782,22 @@ impl std::fmt::Display for TransportMode {
             TransportMode::Http => write!(f, "http"),
             TransportMode::Sse => write!(f, "sse"),
             TransportMode::Hybrid => write!(f, "hybrid"),
+            TransportMode::All => write!(f, "all"),
         }
     }
 }
 
 impl TransportMode {
     /// Convert to `HyperServerConfig`
+    ///
+    /// For `All` this is the configuration of its HTTP/SSE side.
     #[must_use]
     pub fn to_hyper_config(&self) -> Option<HyperServerConfig> {
         match self {
             TransportMode::Stdio => None,
             TransportMode::Http => Some(HyperServerConfig::http()),
             TransportMode::Sse => Some(HyperServerConfig::sse()),
-            TransportMode::Hybrid => Some(HyperServerConfig::hybrid()),
+            TransportMode::Hybrid | TransportMode::All => Some(HyperServerConfig::hybrid()),
         }
     }
 }
@@ -599,6 +812,7 @@ pub async fn run_server_with_mode(server: &CratesDocsServer, mode: TransportMode
                 .expect("Hyper config should exist for HTTP/SSE/Hybrid");
             run_hyper_server(server, config).await
         }
+        TransportMode::All => run_all_server(server).await,
     }
 }
 
@@ -619,9 +833,9 @@ mod tests {
         config.server.request_timeout_secs += 1;
         config.server.max_connections += 1;
         let flagged = unenforced_server_limits(&config);
-        assert!(flagged.contains(&"request_timeout_secs"));
         assert!(flagged.contains(&"max_connections"));
-        assert!(!flagged.contains(&"response_timeout_secs"));
+        // Enforced since the timeouts are applied to tool calls and fetches
+        assert!(!flagged.contains(&"request_timeout_secs"));
     }
 
     #[test]
@@ -637,6 +851,16 @@ mod tests {
         assert!(!super::host_is_loopback("example.com"));
     }
 
+    #[test]
+    fn test_listen_hosts_drops_ipv4_wildcard_on_dual_stack() {
+        let hosts = vec!["::".to_string(), "0.0.0.0".to_string()];
+        assert_eq!(super::listen_hosts(hosts.clone(), || true), ["::"]);
+        assert_eq!(super::listen_hosts(hosts.clone(), || false), hosts);
+
+        let hosts = vec!["127.0.0.1".to_string(), "::1".to_string()];
+        assert_eq!(super::listen_hosts(hosts.clone(), || true), hosts);
+    }
+
     #[test]
     fn test_enable_sse_setting_ignored() {
         // No contradiction: enable_sse matches the active SSE state.
@@ -658,6 +882,52 @@ mod tests {
         assert!(super::api_key_auth_enforced(&config));
     }
 
+    #[test]
+    fn test_active_oauth_config_prefers_auth_section() {
+        let mut config = AppConfig::default();
+        assert!(super::active_oauth_config(&config).is_none());
+
+        // The legacy section needs the server switch as well.
+        config.oauth.enabled = true;
+        config.oauth.client_id = Some("legacy".to_string());
+        assert!(super::active_oauth_config(&config).is_none());
+        config.server.enable_oauth = true;
+        assert_eq!(
+            super::active_oauth_config(&config).unwrap().client_id,
+            Some("legacy".to_string())
+        );
+
+        config.auth.oauth.enabled = true;
+        config.auth.oauth.client_id = Some("current".to_string());
+        assert_eq!(
+            super::active_oauth_config(&config).unwrap().client_id,
+            Some("current".to_string())
+        );
+    }
+
+    #[cfg(feature = "auth")]
+    #[test]
+    fn test_build_auth_mounts_oauth_routes_when_enabled() {
+        let mut config = AppConfig::default();
+        assert!(super::build_auth(&config).is_none());
+
+        config.auth.oauth.enabled = true;
+        let provider = super::build_auth(&config).expect("OAuth provider");
+        let endpoints = provider.auth_endpoints().expect("OAuth routes");
+        assert!(endpoints.contains_key(crate::server::auth::AUTHORIZE_PATH));
+        assert!(endpoints.contains_key(crate::server::auth::CALLBACK_PATH));
+    }
+
+    #[cfg(feature = "auth")]
+    #[test]
+    fn test_build_auth_enables_jwt_validation() {
+        let mut config = AppConfig::default();
+        config.auth.jwt.enabled = true;
+        config.auth.jwt.jwks_uri = Some("https://idp.example.com/certs".to_string());
+        let provider = super::build_auth(&config).expect("JWT validator");
+        assert!(provider.auth_endpoints().is_none());
+    }
+
     #[cfg(all(feature = "api-key", feature = "auth"))]
     #[test]
     fn test_build_api_key_auth_follows_enabled_flag() {
@@ -0,0 +1,118 @@
+//! Cache warm-up on startup
+//!
+//! Prefetches the crates and items listed in `[cache.warmup]` through the
+//! `lookup_crate` and `lookup_item` tools, so a fresh deployment answers
+//! lookups of common crates such as serde or tokio from the cache right away.
+//! Replicas sharing a Redis cache warm it once: the warm-up runs under a
+//! [`LeaseCoordinator`] lease.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cache::lease::LeaseCoordinator;
+use crate::cache::{Cache, WarmupTarget};
+use crate::tools::ToolRegistry;
+
+/// Lease job name of the warm-up
+const WARMUP_JOB: &str = "cache_warmup";
+
+/// TTL of the warm-up lease, renewed while the warm-up runs
+const WARMUP_LEASE_TTL: Duration = Duration::from_mins(1);
+
+/// Outcome of a cache warm-up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarmupReport {
+    /// Entries now cached
+    pub warmed: usize,
+    /// Entries whose lookup failed
+    pub failed: usize,
+}
+
+impl fmt::Display for WarmupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} warmed, {} failed", self.warmed, self.failed)
+    }
+}
+
+/// Look up every target once, in order
+///
+/// A failed lookup is logged and does not stop the warm-up.
+///
+/// # Returns
+///
+/// Returns `None` if another replica holds the warm-up lease
+pub async fn warm_cache(
+    registry: &ToolRegistry,
+    cache: Arc<dyn Cache>,
+    targets: &[WarmupTarget],
+) -> Option<WarmupReport> {
+    LeaseCoordinator::new(cache)
+        .run_exclusive(WARMUP_JOB, WARMUP_LEASE_TTL, || async {
+            let mut report = WarmupReport::default();
+            for target in targets {
+                let (tool, arguments) = tool_call(target);
+                let failure = match registry.execute_tool(tool, arguments).await {
+                    Ok(result) if result.is_error != Some(true) => None,
+                    Ok(_) => Some("the lookup returned an error".to_string()),
+                    Err(e) => Some(e.to_string()),
+                };
+                match failure {
+                    None => report.warmed += 1,
+                    Some(e) => {
+                        tracing::warn!(target = ?target, "Cache warm-up lookup failed: {e}");
+                        report.failed += 1;
+                    }
+                }
+            }
+            report
+        })
+        .await
+}
+
+/// The tool name and arguments looking up `target`
+fn tool_call(target: &WarmupTarget) -> (&'static str, serde_json::Value) {
+    match &target.item_path {
+        Some(item_path) => (
+            "lookup_item",
+            serde_json::json!({
+                "crate_name": target.crate_name,
+                "item_path": item_path,
+                "version": target.version,
+            }),
+        ),
+        None => (
+            "lookup_crate",
+            serde_json::json!({
+                "crate_name": target.crate_name,
+                "version": target.version,
+            }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_call() {
+        let (tool, arguments) = tool_call(&WarmupTarget {
+            crate_name: "tokio".to_string(),
+            version: Some("1.40.0".to_string()),
+            item_path: Some("tokio::sync::Mutex".to_string()),
+        });
+        assert_eq!(tool, "lookup_item");
+        assert_eq!(arguments["item_path"], "tokio::sync::Mutex");
+        assert_eq!(arguments["version"], "1.40.0");
+
+        let (tool, arguments) = tool_call(&WarmupTarget {
+            crate_name: "serde".to_string(),
+            version: None,
+            item_path: None,
+        });
+        assert_eq!(tool, "lookup_crate");
+        assert_eq!(arguments["crate_name"], "serde");
+        assert!(arguments["version"].is_null());
+    }
+}
@@ -0,0 +1,586 @@
+//! Crate alternatives tool
+//!
+//! Answers "is there something better than X": looks up the keywords and
+//! categories of a crate on crates.io, lists the most downloaded crates
+//! sharing them, and ranks the candidates by how much they overlap with the
+//! crate. Shared keywords count more than shared categories, since
+//! categories are broad (`encoding`, `asynchronous`) while keywords name the
+//! problem a crate solves.
+//!
+//! Crates named after the crate (`serde_json` for `serde`, `tokio-util` for
+//! `tokio`) are usually companions rather than alternatives, so they are
+//! reported separately as related crates.
+
+#![allow(missing_docs)]
+
+use super::search::{self, CrateInfo, SearchCratesResponse};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+const TOOL_NAME: &str = "suggest_alternatives";
+
+const DEFAULT_ALTERNATIVES_LIMIT: u32 = 5;
+
+/// Keywords and categories of the crate that are searched
+const MAX_KEYWORDS: usize = 5;
+const MAX_CATEGORIES: usize = 3;
+
+/// Most downloaded crates fetched per keyword or category
+const CANDIDATES_PER_QUERY: usize = 25;
+
+/// Weight of a shared keyword relative to a shared category
+const KEYWORD_WEIGHT: u32 = 2;
+const CATEGORY_WEIGHT: u32 = 1;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "suggest_alternatives",
+    title = "Suggest Alternatives",
+    description = "Suggest alternative crates to a given crate: crates sharing its crates.io keywords and categories, ranked by overlap and popularity, with a short comparison of downloads and versions. Companion crates of the same ecosystem (e.g. serde_json for serde) are listed separately. Use this to answer 'is there something better than X'.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `suggest_alternatives` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct SuggestAlternativesTool {
+    /// Crate name (e.g., "reqwest", "clap")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate to find alternatives for, e.g.: reqwest, clap, log"
+    )]
+    pub crate_name: String,
+
+    /// Maximum number of alternatives to return (range 1-25, defaults to 5)
+    #[json_schema(
+        title = "Result Limit",
+        description = "Maximum number of alternatives to return, range 1-25",
+        minimum = 1,
+        maximum = 25,
+        default = 5
+    )]
+    pub limit: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured alternatives with shared keywords and categories)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// The crate alternatives are suggested for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectCrate {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    pub recent_downloads: Option<u64>,
+    pub keywords: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+/// A suggested alternative
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alternative {
+    #[serde(flatten)]
+    info: CrateInfo,
+    /// Keywords shared with the subject crate
+    pub shared_keywords: Vec<String>,
+    /// Categories shared with the subject crate
+    pub shared_categories: Vec<String>,
+    /// Overlap score used for ranking
+    pub score: u32,
+}
+
+/// Alternatives to a crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateAlternatives {
+    #[serde(rename = "crate")]
+    pub subject: SubjectCrate,
+    pub alternatives: Vec<Alternative>,
+    /// Companion crates of the same ecosystem, by name
+    pub related: Vec<String>,
+}
+
+/// Whether `candidate` is named after `subject` (or the other way round),
+/// e.g. `serde_json` and `serde`
+fn is_companion(subject: &str, candidate: &str) -> bool {
+    let normalize = |name: &str| name.to_ascii_lowercase().replace('-', "_");
+    let (subject, candidate) = (normalize(subject), normalize(candidate));
+    let named_after = |name: &str, base: &str| {
+        name.strip_prefix(base)
+            .is_some_and(|rest| rest.starts_with('_'))
+            || name
+                .strip_suffix(base)
+                .is_some_and(|rest| rest.ends_with('_'))
+    };
+    named_after(&candidate, &subject) || named_after(&subject, &candidate)
+}
+
+/// Overlap of a candidate with the subject crate
+#[derive(Default)]
+struct Overlap {
+    info: Option<CrateInfo>,
+    keywords: Vec<String>,
+    categories: Vec<String>,
+}
+
+impl Overlap {
+    fn score(&self) -> u32 {
+        let count = |list: &Vec<String>| u32::try_from(list.len()).unwrap_or(u32::MAX);
+        count(&self.keywords) * KEYWORD_WEIGHT + count(&self.categories) * CATEGORY_WEIGHT
+    }
+}
+
+/// A crates.io listing query: a keyword or a category
+#[derive(Debug, Clone)]
+enum Query {
+    Keyword(String),
+    Category(String),
+}
+
+/// Rank the candidates found by `listings` and split off companion crates
+fn rank(
+    subject: &SubjectCrate,
+    listings: Vec<(Query, Vec<CrateInfo>)>,
+    limit: usize,
+) -> (Vec<Alternative>, Vec<String>) {
+    let mut overlaps: HashMap<String, Overlap> = HashMap::new();
+    for (query, crates) in listings {
+        for info in crates {
+            if info.name.eq_ignore_ascii_case(&subject.name) {
+                continue;
+            }
+            let overlap = overlaps.entry(info.name.clone()).or_default();
+            match &query {
+                Query::Keyword(keyword) => overlap.keywords.push(keyword.clone()),
+                Query::Category(category) => overlap.categories.push(category.clone()),
+            }
+            overlap.info.get_or_insert(info);
+        }
+    }
+
+    let mut related = Vec::new();
+    let mut alternatives: Vec<Alternative> = overlaps
+        .into_values()
+        .filter_map(|overlap| {
+            let score = overlap.score();
+            let info = overlap.info?;
+            if is_companion(&subject.name, &info.name) {
+                related.push(info.name);
+                return None;
+            }
+            Some(Alternative {
+                info,
+                shared_keywords: overlap.keywords,
+                shared_categories: overlap.categories,
+                score,
+            })
+        })
+        .collect();
+
+    alternatives.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| {
+                b.info
+                    .recent_downloads
+                    .unwrap_or(0)
+                    .cmp(&a.info.recent_downloads.unwrap_or(0))
+            })
+            .then_with(|| b.info.downloads.cmp(&a.info.downloads))
+            .then_with(|| a.info.name.cmp(&b.info.name))
+    });
+    alternatives.truncate(limit);
+    related.sort();
+    (alternatives, related)
+}
+
+/// How an alternative's downloads compare to the subject's, e.g. `3.2x the downloads`
+#[allow(clippy::cast_precision_loss)]
+fn compare_downloads(subject: u64, alternative: u64) -> Option<String> {
+    (subject > 0).then(|| {
+        let ratio = alternative as f64 / subject as f64;
+        format!("{ratio:.1}x the downloads")
+    })
+}
+
+fn format_alternatives(result: &CrateAlternatives, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let subject = &result.subject;
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Alternatives to {}\n", subject.name).unwrap();
+    } else {
+        writeln!(output, "Alternatives to {}\n", subject.name).unwrap();
+    }
+    writeln!(
+        output,
+        "{} {}: {} downloads{}",
+        subject.name,
+        subject.version,
+        subject.downloads,
+        subject
+            .recent_downloads
+            .map(|recent| format!(" ({recent} recent)"))
+            .unwrap_or_default()
+    )
+    .unwrap();
+    if !subject.keywords.is_empty() {
+        writeln!(output, "Keywords: {}", subject.keywords.join(", ")).unwrap();
+    }
+    if !subject.categories.is_empty() {
+        writeln!(output, "Categories: {}", subject.categories.join(", ")).unwrap();
+    }
+    output.push('\n');
+
+    if result.alternatives.is_empty() {
+        if subject.keywords.is_empty() && subject.categories.is_empty() {
+            output.push_str(
+                "No alternatives found: the crate declares no keywords or categories on crates.io. Try search_crates instead.\n",
+            );
+        } else {
+            output.push_str("No alternatives found sharing its keywords or categories.\n");
+        }
+    }
+
+    for (i, alternative) in result.alternatives.iter().enumerate() {
+        let info = &alternative.info;
+        let mut details = vec![
+            info.version.clone(),
+            format!("{} downloads", info.downloads),
+        ];
+        if let Some(comparison) = compare_downloads(subject.downloads, info.downloads) {
+            details.push(comparison);
+        }
+        if markdown {
+            writeln!(
+                output,
+                "{}. **{}** ({}) - <{}>",
+                i + 1,
+                info.name,
+                details.join(", "),
+                info.docs_rs
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                output,
+                "{}. {} ({}) - {}",
+                i + 1,
+                info.name,
+                details.join(", "),
+                info.docs_rs
+            )
+            .unwrap();
+        }
+        let shared: Vec<&str> = alternative
+            .shared_keywords
+            .iter()
+            .chain(&alternative.shared_categories)
+            .map(String::as_str)
+            .collect();
+        writeln!(output, "   Shares: {}", shared.join(", ")).unwrap();
+        if let Some(desc) = &info.description {
+            let desc = search::normalize_description(desc);
+            if markdown {
+                writeln!(output, "   {}", search::escape_markdown_text(&desc)).unwrap();
+            } else {
+                writeln!(output, "   {desc}").unwrap();
+            }
+        }
+    }
+
+    if !result.related.is_empty() {
+        writeln!(
+            output,
+            "\nRelated {} crates: {}",
+            subject.name,
+            result.related.join(", ")
+        )
+        .unwrap();
+    }
+    output
+}
+
+/// Fetch the most downloaded crates for a listing query
+///
+/// Errors are plain strings so the future can run on a [`JoinSet`].
+async fn fetch_listing(
+    service: Arc<super::DocService>,
+    query: Query,
+) -> Result<Vec<CrateInfo>, String> {
+    let (category, keyword) = match &query {
+        Query::Keyword(keyword) => (None, Some(keyword.as_str())),
+        Query::Category(category) => (Some(category.as_str()), None),
+    };
+    let url =
+        super::build_crates_io_listing_url("downloads", category, keyword, CANDIDATES_PER_QUERY);
+    let body = service
+        .fetch_html(&url, Some(TOOL_NAME))
+        .await
+        .map_err(|e| e.to_string())?;
+    let response: SearchCratesResponse =
+        serde_json::from_str(&body).map_err(|e| format!("JSON parsing failed: {e}"))?;
+    Ok(search::parse_crates_response(
+        response,
+        CANDIDATES_PER_QUERY,
+    ))
+}
+
+/// Implementation of the crate alternatives tool
+pub struct SuggestAlternativesToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl SuggestAlternativesToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn fetch_subject(&self, crate_name: &str) -> Result<SubjectCrate, CallToolError> {
+        let Some(summary) =
+            super::batch::fetch_crate_summary(&self.service, crate_name, TOOL_NAME).await?
+        else {
+            return Err(super::crate_not_found(crate_name, None).into());
+        };
+        Ok(SubjectCrate {
+            name: summary.name,
+            version: summary.version,
+            description: summary.description,
+            downloads: summary.downloads,
+            recent_downloads: summary.recent_downloads,
+            keywords: summary.keywords,
+            categories: summary.categories,
+        })
+    }
+
+    async fn fetch_alternatives(
+        &self,
+        crate_name: &str,
+        limit: u32,
+    ) -> Result<CrateAlternatives, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache.get_alternatives(crate_name, limit).await {
+            return serde_json::from_str(&cached).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Cache parsing failed: {e}"))
+            });
+        }
+
+        let subject = self.fetch_subject(crate_name).await?;
+        let queries: Vec<Query> = subject
+            .keywords
+            .iter()
+            .take(MAX_KEYWORDS)
+            .cloned()
+            .map(Query::Keyword)
+            .chain(
+                subject
+                    .categories
+                    .iter()
+                    .take(MAX_CATEGORIES)
+                    .cloned()
+                    .map(Query::Category),
+            )
+            .collect();
+
+        let mut tasks = JoinSet::new();
+        for (index, query) in queries.iter().enumerate() {
+            let service = Arc::clone(&self.service);
+            let query = query.clone();
+            tasks.spawn(async move {
+                let listing = fetch_listing(service, query.clone()).await;
+                (index, query, listing)
+            });
+        }
+        let mut listings = Vec::new();
+        let mut last_error = None;
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((index, query, listing)) = joined else {
+                continue;
+            };
+            match listing {
+                Ok(crates) => listings.push((index, query, crates)),
+                Err(e) => {
+                    tracing::warn!("[{TOOL_NAME}] failed to list {query:?} (skipping): {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        // Partial results are still useful; only fail when nothing was listed
+        if listings.is_empty() {
+            if let Some(e) = last_error {
+                return Err(CallToolError::from_message(format!(
+                    "[{TOOL_NAME}] crates.io listing failed: {e}"
+                )));
+            }
+        }
+
+        // Keep the crate's own keyword and category order in the report
+        listings.sort_by_key(|(index, _, _)| *index);
+        let listings = listings
+            .into_iter()
+            .map(|(_, query, crates)| (query, crates))
+            .collect();
+        let (alternatives, related) = rank(&subject, listings, limit as usize);
+        let result = CrateAlternatives {
+            subject,
+            alternatives,
+            related,
+        };
+
+        match serde_json::to_string(&result) {
+            Ok(value) => {
+                if let Err(e) = doc_cache.set_alternatives(crate_name, limit, value).await {
+                    tracing::warn!(
+                        "[{TOOL_NAME}] failed to cache alternatives (continuing uncached): {e}"
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize alternatives: {e}"),
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Tool for SuggestAlternativesToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SuggestAlternativesTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SuggestAlternativesTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_ALTERNATIVES_LIMIT)
+            .clamp(1, 25);
+
+        let crate_name = params.crate_name.trim();
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' is part of the Rust distribution and is not published on crates.io"
+                )),
+            ));
+        }
+
+        let result = self.fetch_alternatives(crate_name, limit).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&result).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_alternatives(&result, false),
+            _ => format_alternatives(&result, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for SuggestAlternativesToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(name: &str, downloads: u64) -> CrateInfo {
+        CrateInfo {
+            name: name.to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            downloads,
+            recent_downloads: None,
+            repository: None,
+            documentation: None,
+            docs_rs: format!("https://docs.rs/{name}/"),
+        }
+    }
+
+    #[test]
+    fn test_is_companion() {
+        assert!(is_companion("serde", "serde_json"));
+        assert!(is_companion("tokio", "tokio-util"));
+        assert!(is_companion("serde_json", "serde"));
+        assert!(is_companion("tracing", "opentelemetry-tracing"));
+        assert!(!is_companion("serde", "serdex"));
+        assert!(!is_companion("log", "tracing"));
+    }
+
+    #[test]
+    fn test_rank_prefers_overlap_and_splits_companions() {
+        let subject = SubjectCrate {
+            name: "log".to_string(),
+            version: "0.4.0".to_string(),
+            description: None,
+            downloads: 100,
+            recent_downloads: None,
+            keywords: vec!["logging".to_string()],
+            categories: vec!["development-tools::debugging".to_string()],
+        };
+        let listings = vec![
+            (
+                Query::Keyword("logging".to_string()),
+                vec![
+                    info("log", 100),
+                    info("tracing", 80),
+                    info("log4rs", 10),
+                    info("log-derive", 5),
+                ],
+            ),
+            (
+                Query::Category("development-tools::debugging".to_string()),
+                vec![info("tracing", 80), info("backtrace", 500)],
+            ),
+        ];
+
+        let (alternatives, related) = rank(&subject, listings, 5);
+        let names: Vec<&str> = alternatives.iter().map(|a| a.info.name.as_str()).collect();
+        assert_eq!(names, ["tracing", "log4rs", "backtrace"]);
+        assert_eq!(alternatives[0].score, KEYWORD_WEIGHT + CATEGORY_WEIGHT);
+        assert_eq!(related, ["log-derive"]);
+    }
+
+    #[test]
+    fn test_compare_downloads() {
+        assert_eq!(compare_downloads(100, 250).unwrap(), "2.5x the downloads");
+        assert!(compare_downloads(0, 250).is_none());
+    }
+}
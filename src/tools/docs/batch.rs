@@ -0,0 +1,422 @@
+//! Batch crate summary tool
+//!
+//! Returns crates.io summaries (latest version, description, downloads and
+//! links) for a list of crates in one call, so an agent analysing a
+//! `Cargo.toml` does not need one tool call per dependency. Summaries are
+//! fetched a few at a time and cached per crate; a crate that does not
+//! exist or cannot be fetched is reported in its entry without failing the
+//! others.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+const TOOL_NAME: &str = "batch_lookup_crates";
+
+/// Crates accepted in one call
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Summaries fetched at the same time
+const MAX_CONCURRENT_FETCHES: usize = 5;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "batch_lookup_crates",
+    title = "Batch Lookup Crates",
+    description = "Get summaries of many crates in one call: latest version, description, downloads, repository and docs.rs link for each, fetched concurrently from crates.io. Use this instead of repeated lookups when analysing a Cargo.toml or comparing several crates. Up to 50 crates per call.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    execution(task_support = "optional"),
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `batch_lookup_crates` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct BatchLookupCratesTool {
+    /// Crate names (e.g., `["serde", "tokio"]`)
+    #[json_schema(
+        title = "Crate Names",
+        description = "Crate names to summarize, at most 50, e.g.: [\"serde\", \"tokio\", \"anyhow\"]"
+    )]
+    pub crates: Vec<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (one entry per crate with a status and its summary)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Response of the crates.io crate endpoint
+#[derive(Debug, Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: CrateRecord,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateRecord {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    max_version: Option<String>,
+    #[serde(default)]
+    max_stable_version: Option<String>,
+    #[serde(default)]
+    downloads: u64,
+    #[serde(default)]
+    recent_downloads: Option<u64>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(default)]
+    keywords: Option<Vec<String>>,
+    #[serde(default)]
+    categories: Option<Vec<String>>,
+}
+
+/// crates.io summary of a crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateSummary {
+    /// Crate name as published
+    pub name: String,
+    /// Latest stable version (latest version if there is no stable one)
+    pub version: String,
+    pub description: Option<String>,
+    /// Total downloads
+    pub downloads: u64,
+    /// Downloads in the last 90 days
+    pub recent_downloads: Option<u64>,
+    pub repository: Option<String>,
+    pub homepage: Option<String>,
+    /// Last update time (RFC 3339)
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// docs.rs URL of the crate
+    pub docs_rs: String,
+}
+
+impl From<CrateRecord> for CrateSummary {
+    fn from(record: CrateRecord) -> Self {
+        Self {
+            docs_rs: format!("https://docs.rs/{}/", record.name),
+            version: record
+                .max_stable_version
+                .or(record.max_version)
+                .unwrap_or_else(|| "0.0.0".to_string()),
+            name: record.name,
+            description: record.description,
+            downloads: record.downloads,
+            recent_downloads: record.recent_downloads,
+            repository: record.repository,
+            homepage: record.homepage,
+            updated_at: record.updated_at,
+            keywords: record.keywords.unwrap_or_default(),
+            categories: record.categories.unwrap_or_default(),
+        }
+    }
+}
+
+/// Fetch the crates.io summary of a crate; `Ok(None)` if it does not exist
+///
+/// Summaries are cached with the search results TTL, since download counts
+/// change constantly.
+pub(super) async fn fetch_crate_summary(
+    service: &super::DocService,
+    crate_name: &str,
+    tool_name: &str,
+) -> Result<Option<CrateSummary>, CallToolError> {
+    let doc_cache = service.doc_cache();
+    if let Some(cached) = doc_cache.get_crate_summary(crate_name).await {
+        return serde_json::from_str(&cached).map(Some).map_err(|e| {
+            CallToolError::from_message(format!("[{tool_name}] Cache parsing failed: {e}"))
+        });
+    }
+
+    let Some(summary) = download_crate_summary(service, crate_name, tool_name).await? else {
+        return Ok(None);
+    };
+    match serde_json::to_string(&summary) {
+        Ok(value) => {
+            if let Err(e) = doc_cache.set_crate_summary(crate_name, value).await {
+                tracing::warn!(
+                    "[{tool_name}] failed to cache crate summary (continuing uncached): {e}"
+                );
+            }
+        }
+        Err(e) => tracing::warn!("[{tool_name}] failed to serialize crate summary: {e}"),
+    }
+    Ok(Some(summary))
+}
+
+/// Fetch the crates.io summary of a crate without consulting the cache
+async fn download_crate_summary(
+    service: &super::DocService,
+    crate_name: &str,
+    tool_name: &str,
+) -> Result<Option<CrateSummary>, CallToolError> {
+    let url = super::build_crates_io_crate_url(crate_name);
+    let Some(body) = service.fetch_html_optional(&url, Some(tool_name)).await? else {
+        return Ok(None);
+    };
+    let summary = serde_json::from_str::<CrateResponse>(&body)
+        .map_err(|e| {
+            CallToolError::from_message(format!("[{tool_name}] JSON parsing failed: {e}"))
+        })?
+        .krate
+        .into();
+    Ok(Some(summary))
+}
+
+/// Outcome of one crate of the batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchEntry {
+    Found(CrateSummary),
+    NotFound { crate_name: String },
+    Failed { crate_name: String, error: String },
+}
+
+/// Remove duplicates (case-insensitively), keeping the first spelling
+fn dedup_names(names: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    names
+        .iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .collect()
+}
+
+fn format_batch(entries: &[BatchEntry], markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Crate summaries ({})\n", entries.len()).unwrap();
+    } else {
+        writeln!(output, "Crate summaries ({})\n", entries.len()).unwrap();
+    }
+    for entry in entries {
+        match entry {
+            BatchEntry::Found(summary) => {
+                let mut details = vec![
+                    summary.version.clone(),
+                    format!("{} downloads", summary.downloads),
+                ];
+                if let Some(recent) = summary.recent_downloads {
+                    details.push(format!("{recent} recent"));
+                }
+                if markdown {
+                    writeln!(
+                        output,
+                        "- **{}** ({}) - <{}>",
+                        summary.name,
+                        details.join(", "),
+                        summary.docs_rs
+                    )
+                    .unwrap();
+                } else {
+                    writeln!(
+                        output,
+                        "- {} ({}) - {}",
+                        summary.name,
+                        details.join(", "),
+                        summary.docs_rs
+                    )
+                    .unwrap();
+                }
+                if let Some(desc) = &summary.description {
+                    let desc = super::search::normalize_description(desc);
+                    if markdown {
+                        writeln!(output, "  {}", super::search::escape_markdown_text(&desc))
+                            .unwrap();
+                    } else {
+                        writeln!(output, "  {desc}").unwrap();
+                    }
+                }
+                if let Some(repository) = &summary.repository {
+                    writeln!(output, "  Repository: {repository}").unwrap();
+                }
+            }
+            BatchEntry::NotFound { crate_name } => {
+                writeln!(output, "- {crate_name}: not found on crates.io").unwrap();
+            }
+            BatchEntry::Failed { crate_name, error } => {
+                writeln!(output, "- {crate_name}: lookup failed ({error})").unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the batch crate summary tool
+pub struct BatchLookupCratesToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl BatchLookupCratesToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Summaries of `names` in input order: cached ones are read with one
+    /// cache lookup, the others fetched a few at a time and cached together
+    async fn fetch_all(&self, names: &[String]) -> Vec<BatchEntry> {
+        let doc_cache = self.service.doc_cache();
+        let mut entries: Vec<Option<BatchEntry>> = doc_cache
+            .get_crate_summaries(names)
+            .await
+            .into_iter()
+            .map(|cached| {
+                cached
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .map(BatchEntry::Found)
+            })
+            .collect();
+        let missing: Vec<usize> = (0..names.len())
+            .filter(|&index| entries[index].is_none())
+            .collect();
+
+        let mut fetched = Vec::new();
+        for chunk in missing.chunks(MAX_CONCURRENT_FETCHES) {
+            let mut tasks = JoinSet::new();
+            for &index in chunk {
+                let service = Arc::clone(&self.service);
+                let name = names[index].clone();
+                tasks.spawn(async move {
+                    // CallToolError is not Send; keep only its message
+                    let summary = download_crate_summary(&service, &name, TOOL_NAME)
+                        .await
+                        .map_err(|e| e.to_string());
+                    (index, name, summary)
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                let Ok((index, crate_name, summary)) = joined else {
+                    continue;
+                };
+                entries[index] = Some(match summary {
+                    Ok(Some(summary)) => {
+                        match serde_json::to_string(&summary) {
+                            Ok(json) => fetched.push((crate_name, Arc::from(json))),
+                            Err(e) => tracing::warn!(
+                                "[{TOOL_NAME}] failed to serialize crate summary: {e}"
+                            ),
+                        }
+                        BatchEntry::Found(summary)
+                    }
+                    Ok(None) => BatchEntry::NotFound { crate_name },
+                    Err(error) => BatchEntry::Failed { crate_name, error },
+                });
+            }
+        }
+        if !fetched.is_empty() {
+            if let Err(e) = doc_cache.set_crate_summaries(fetched).await {
+                tracing::warn!(
+                    "[{TOOL_NAME}] failed to cache crate summaries (continuing uncached): {e}"
+                );
+            }
+        }
+        entries.into_iter().flatten().collect()
+    }
+}
+
+#[async_trait]
+impl Tool for BatchLookupCratesToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        BatchLookupCratesTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: BatchLookupCratesTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let names = dedup_names(&params.crates);
+        if names.is_empty() {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some("At least one crate name is required".to_string()),
+            ));
+        }
+        if names.len() > MAX_BATCH_SIZE {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "At most {MAX_BATCH_SIZE} crates can be looked up at once, got {}",
+                    names.len()
+                )),
+            ));
+        }
+        for name in &names {
+            super::validate_crate_name(TOOL_NAME, name)?;
+            if super::is_rust_std_crate(name) {
+                return Err(CallToolError::invalid_arguments(
+                    TOOL_NAME,
+                    Some(format!(
+                        "'{name}' is part of the Rust distribution and is not published on crates.io"
+                    )),
+                ));
+            }
+        }
+
+        let entries = self.fetch_all(&names).await;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&entries).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_batch(&entries, false),
+            _ => format_batch(&entries, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for BatchLookupCratesToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_names_keeps_first_spelling() {
+        let names = ["Serde", " tokio ", "serde", "anyhow"].map(String::from);
+        assert_eq!(dedup_names(&names), ["Serde", "tokio", "anyhow"]);
+    }
+}
@@ -0,0 +1,634 @@
+//! Output length budgets
+//!
+//! The front page of a large crate such as `tokio` renders to hundreds of
+//! kilobytes. The documentation tools accept `max_tokens` or `max_length`
+//! and cut their output at the last heading that fits, so the agent gets
+//! whole sections plus a note on how to fetch the rest (`get_toc` and the
+//! `section` parameter).
+//!
+//! `lookup_crate` and `lookup_item` also page through long documents: the
+//! note then carries a `cursor` that returns the next part, which is also
+//! set as `next_cursor` in the result's `_meta`.
+
+use super::Format;
+use rust_mcp_sdk::schema::{CallToolError, CallToolResult};
+
+/// Rough size of a token in characters, for English prose and Rust code
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Smallest accepted `max_tokens`
+pub const MIN_MAX_TOKENS: u32 = 100;
+
+/// Smallest accepted `max_length`
+pub const MIN_MAX_LENGTH: u32 = 400;
+
+/// Page size in tokens when a cursor is given without a budget
+pub const DEFAULT_PAGE_TOKENS: usize = 5_000;
+
+/// Sections named in the truncation note
+const MAX_LISTED_SECTIONS: usize = 10;
+
+/// Character budget for a `max_tokens` / `max_length` pair
+///
+/// `max_length` takes precedence; both are raised to their minimum.
+/// `None` when neither is set.
+#[must_use]
+pub fn char_budget(max_tokens: Option<u32>, max_length: Option<u32>) -> Option<usize> {
+    match (max_length, max_tokens) {
+        (Some(length), _) => Some(length.max(MIN_MAX_LENGTH) as usize),
+        (None, Some(tokens)) => Some(tokens.max(MIN_MAX_TOKENS) as usize * CHARS_PER_TOKEN),
+        (None, None) => None,
+    }
+}
+
+/// Title of the markdown heading starting at `lines[i]`, if any
+///
+/// Both ATX (`## Title`) and setext headings (a title underlined with `===`
+/// or `---`, as html2md renders `h1` and `h2`) are recognised.
+fn heading_title<'a>(lines: &[&'a str], i: usize) -> Option<&'a str> {
+    let line = lines[i].trim_end();
+    let rest = line.trim_start_matches('#');
+    let level = line.len() - rest.len();
+    if (1..=6).contains(&level) && rest.starts_with(' ') {
+        return Some(rest.trim());
+    }
+    let underline = lines.get(i + 1)?.trim();
+    let setext = underline.len() >= 2
+        && (underline.chars().all(|c| c == '=') || underline.chars().all(|c| c == '-'));
+    (setext && !line.trim().is_empty()).then(|| line.trim())
+}
+
+/// Whether a line opens or closes a fenced code block
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Byte offsets where the content can be cut, best kind first: section
+/// starts, then paragraph starts, then line starts
+///
+/// Code fences are only split at lines, for a block that does not fit on
+/// its own. The offsets of each kind are ascending.
+fn cut_points(content: &str, format: Format) -> [Vec<usize>; 3] {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut points: [Vec<usize>; 3] = Default::default();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if i > 0 && in_fence {
+            // Inside an overlong code block only lines are cut points
+            points[2].push(offset);
+        } else if i > 0 {
+            let heading = match format {
+                Format::Markdown | Format::Llm => heading_title(&lines, i).is_some(),
+                Format::Html => {
+                    let lower = trimmed.to_ascii_lowercase();
+                    (1..=6).any(|level| lower.starts_with(&format!("<h{level}")))
+                }
+                _ => false,
+            };
+            if heading {
+                points[0].push(offset);
+            } else if lines[i - 1].trim().is_empty() && !trimmed.is_empty() {
+                points[1].push(offset);
+            } else {
+                points[2].push(offset);
+            }
+        }
+        if matches!(format, Format::Markdown | Format::Llm) && is_fence(line) {
+            in_fence = !in_fence;
+        }
+        offset += line.len();
+    }
+    points
+}
+
+/// Titles of the markdown headings in `content`, outside code fences
+fn section_titles(content: &str) -> Vec<&str> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut in_fence = false;
+    let mut titles = Vec::new();
+    for i in 0..lines.len() {
+        if is_fence(lines[i]) {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            titles.extend(heading_title(&lines, i));
+        }
+    }
+    titles
+}
+
+/// Byte offset just past the last sentence end in `prefix`, falling back to
+/// the last word boundary; `None` for a single word
+fn inline_cut(prefix: &str) -> Option<usize> {
+    let sentence = prefix
+        .rmatch_indices(['.', '!', '?'])
+        .map(|(i, _)| i + 1)
+        .find(|&end| prefix[end..].starts_with(char::is_whitespace));
+    sentence
+        .or_else(|| prefix.rfind(char::is_whitespace))
+        .filter(|&end| end > 0)
+}
+
+/// Byte offset at which a part of at most `budget` characters ends
+///
+/// That is the last section boundary that fits, falling back to paragraph
+/// and line boundaries when the first section alone exceeds the budget, then
+/// to the last sentence end or word boundary, and to exactly `budget`
+/// characters for a single overlong word.
+fn cut_offset(content: &str, budget: usize, format: Format) -> usize {
+    // Byte offset of the first character past the budget
+    let Some((limit, _)) = content.char_indices().nth(budget) else {
+        return content.len();
+    };
+    cut_points(content, format)
+        .iter()
+        .find_map(|points| points.iter().rev().find(|&&p| p <= limit).copied())
+        .or_else(|| inline_cut(&content[..limit]))
+        .unwrap_or(limit)
+}
+
+/// Opening line of the code fence left open at the end of `content`
+///
+/// A cut inside a code block that exceeds the budget on its own must close
+/// the fence, or the note would render as code.
+fn open_fence(content: &str, format: Format) -> Option<&str> {
+    if !matches!(format, Format::Markdown | Format::Llm) {
+        return None;
+    }
+    content
+        .lines()
+        .fold(None, |open, line| match (open, is_fence(line)) {
+            (None, true) => Some(line.trim()),
+            (Some(_), true) => None,
+            (open, false) => open,
+        })
+}
+
+/// `kept` with its open code fence, if any, closed
+fn close_fence(kept: &str, format: Format) -> String {
+    match open_fence(kept, format) {
+        Some(_) => format!("{kept}\n```"),
+        None => kept.to_string(),
+    }
+}
+
+/// `rest` of a document after `before`, reopening the code block that
+/// `before` leaves open
+fn reopen_fence(before: &str, rest: &str, format: Format) -> String {
+    match open_fence(before, format) {
+        Some(fence) => format!("{fence}\n{rest}"),
+        None => rest.to_string(),
+    }
+}
+
+/// ` N more sections: "A", "B".` for the sections of `content`
+///
+/// Markdown sections are listed by title; HTML ones are only counted.
+fn more_sections(content: &str, format: Format) -> String {
+    let titles = match format {
+        Format::Markdown | Format::Llm => section_titles(content),
+        Format::Html => {
+            let count = cut_points(&format!("\n{content}"), format)[0].len();
+            return match count {
+                0 => String::new(),
+                1 => " 1 more section.".to_string(),
+                n => format!(" {n} more sections."),
+            };
+        }
+        _ => Vec::new(),
+    };
+    if titles.is_empty() {
+        return String::new();
+    }
+    let mut listed: Vec<String> = titles
+        .iter()
+        .take(MAX_LISTED_SECTIONS)
+        .map(|title| format!("\"{title}\""))
+        .collect();
+    if titles.len() > MAX_LISTED_SECTIONS {
+        listed.push(format!("{} more", titles.len() - MAX_LISTED_SECTIONS));
+    }
+    let noun = if titles.len() == 1 {
+        "section"
+    } else {
+        "sections"
+    };
+    format!(" {} more {noun}: {}.", titles.len(), listed.join(", "))
+}
+
+/// A note after the content, in the markup of `format`
+fn note(format: Format, label: &str, body: &str) -> String {
+    match format {
+        Format::Html => format!("<p><em>{label}: {body}</em></p>"),
+        Format::Markdown => format!("---\n\n> **{label}**: {body}"),
+        _ => format!("[{label}: {body}]"),
+    }
+}
+
+/// Cut `content` to at most `budget` characters of documentation, ending at
+/// the last section boundary that fits, and explain what was left out
+///
+/// The note is appended after the kept content, so the result can exceed
+/// the budget by its length.
+#[must_use]
+pub fn truncate_at_headings(content: &str, budget: usize, format: Format) -> String {
+    let total = content.chars().count();
+    if total <= budget {
+        return content.to_string();
+    }
+    let (kept_raw, omitted) = content.split_at(cut_offset(content, budget, format));
+    let kept = close_fence(kept_raw.trim_end(), format);
+
+    let shown = kept.chars().count();
+    let sections = more_sections(&reopen_fence(kept_raw, omitted, format), format);
+    let note = note(
+        format,
+        "Truncated",
+        &format!("showing {shown} of {total} characters.{sections} Call `get_toc` for the section anchors and pass one as `section` to fetch it, or raise `max_tokens`/`max_length`."),
+    );
+    format!("{kept}\n\n{note}\n")
+}
+
+/// Apply an optional budget to `content`
+#[must_use]
+pub fn apply(content: String, budget: Option<usize>, format: Format) -> String {
+    match budget {
+        Some(budget) => truncate_at_headings(&content, budget, format),
+        None => content,
+    }
+}
+
+/// Cut `content` to at most `budget` characters at the best boundary, like
+/// [`truncate_at_headings`] but marking the cut with `...` instead of a note
+///
+/// For outputs that are shrunk before they are cut, such as the crate
+/// summary, and for short excerpts. A budget too small for any content
+/// leaves only as much of the `...` as fits.
+#[must_use]
+pub fn shorten(content: &str, budget: usize, format: Format) -> String {
+    if content.chars().count() <= budget {
+        return content.to_string();
+    }
+    let cut = |room: usize| content[..cut_offset(content, room, format)].trim_end();
+    // Room for the ` ...` marker, and for a fence closer if the cut lands
+    // inside a code block
+    let mut kept = cut(budget.saturating_sub(4));
+    if open_fence(kept, format).is_some() {
+        kept = cut(budget.saturating_sub(8));
+    }
+    if kept.is_empty() {
+        return "...".chars().take(budget).collect();
+    }
+    let kept = close_fence(kept, format);
+    let separator = if kept.ends_with('`') { "\n" } else { " " };
+    format!("{kept}{separator}...")
+}
+
+/// One part of a paginated document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// The part, followed by a note when the document does not fit one part
+    pub text: String,
+    /// Cursor of the next part; `None` on the last part
+    pub next_cursor: Option<String>,
+    /// Crate version the document was rendered for, if known
+    pub version: Option<String>,
+}
+
+impl Page {
+    /// Record the crate version that was served, as a header line before
+    /// the part and as `version` in `_meta`
+    ///
+    /// Agents that omit `version` get whatever docs.rs considers latest;
+    /// this lets them cite and cache the concrete version.
+    #[must_use]
+    pub fn with_version(
+        mut self,
+        crate_name: &str,
+        version: Option<String>,
+        format: Format,
+    ) -> Self {
+        if let Some(version) = &version {
+            let header = match format {
+                Format::Html => format!("<p><em>{crate_name} {version}</em></p>"),
+                Format::Markdown => format!("> **Version**: {crate_name} {version}"),
+                Format::Llm => format!("Version: {crate_name} {version}"),
+                _ => format!("[Version: {crate_name} {version}]"),
+            };
+            self.text = format!("{header}\n\n{}", self.text);
+        }
+        self.version = version;
+        self
+    }
+
+    /// Tool result with the page as text and the next cursor and served
+    /// version in `_meta`
+    #[must_use]
+    pub fn into_result(self) -> CallToolResult {
+        let mut result = CallToolResult::text_content(vec![self.text.into()]);
+        let mut meta = serde_json::Map::new();
+        if let Some(cursor) = self.next_cursor {
+            meta.insert("next_cursor".to_string(), cursor.into());
+        }
+        if let Some(version) = self.version {
+            meta.insert("version".to_string(), version.into());
+        }
+        if !meta.is_empty() {
+            result.meta = Some(meta);
+        }
+        result
+    }
+}
+
+/// Cursor of the part starting at byte `offset` of a `len`-byte document
+///
+/// The length ties the cursor to the document it was issued for, so a
+/// cursor is rejected once the page behind it has changed.
+fn encode_cursor(offset: usize, len: usize) -> String {
+    format!("{offset}:{len}")
+}
+
+fn decode_cursor(tool_name: &str, cursor: &str, content: &str) -> Result<usize, CallToolError> {
+    let offset = cursor
+        .trim()
+        .split_once(':')
+        .and_then(|(offset, len)| Some((offset.parse::<usize>().ok()?, len.parse::<usize>().ok()?)))
+        .filter(|&(offset, len)| {
+            len == content.len() && offset > 0 && offset < len && content.is_char_boundary(offset)
+        })
+        .map(|(offset, _)| offset);
+    offset.ok_or_else(|| {
+        CallToolError::invalid_arguments(
+            tool_name,
+            Some(format!(
+                "Invalid cursor '{cursor}': it does not belong to this document, which may have changed since. Repeat the request without a cursor"
+            )),
+        )
+    })
+}
+
+/// The part of `content` that `cursor` points to (the first part without
+/// one), at most `budget` characters long
+///
+/// Parts end at section boundaries like [`truncate_at_headings`]; every part
+/// but the last carries a note with the cursor of the next one. A cursor
+/// without a budget pages through `DEFAULT_PAGE_TOKENS`-sized parts, and
+/// with neither the whole document is returned.
+///
+/// # Errors
+///
+/// Returns an invalid-arguments error if the cursor was not issued for this
+/// document
+pub fn paginate(
+    tool_name: &str,
+    content: &str,
+    budget: Option<usize>,
+    cursor: Option<&str>,
+    format: Format,
+) -> Result<Page, CallToolError> {
+    let start = match cursor {
+        Some(cursor) => decode_cursor(tool_name, cursor, content)?,
+        None => 0,
+    };
+    let Some(budget) = budget.or(cursor.map(|_| DEFAULT_PAGE_TOKENS * CHARS_PER_TOKEN)) else {
+        return Ok(Page {
+            text: content.to_string(),
+            next_cursor: None,
+            version: None,
+        });
+    };
+
+    let rest = &content[start..];
+    let end = start + cut_offset(rest, budget, format);
+    if start == 0 && end == content.len() {
+        return Ok(Page {
+            text: content.to_string(),
+            next_cursor: None,
+            version: None,
+        });
+    }
+    // A part starting inside a code block reopens it
+    let kept = reopen_fence(&content[..start], content[start..end].trim_end(), format);
+    let kept = close_fence(&kept, format);
+    let from = content[..start].chars().count() + 1;
+    let to = from + content[start..end].chars().count() - 1;
+    let total = content.chars().count();
+
+    if end == content.len() {
+        let note = note(
+            format,
+            "Last part",
+            &format!("characters {from}-{to} of {total}."),
+        );
+        return Ok(Page {
+            text: format!("{kept}\n\n{note}\n"),
+            next_cursor: None,
+            version: None,
+        });
+    }
+
+    let next_cursor = encode_cursor(end, content.len());
+    let sections = more_sections(
+        &reopen_fence(&content[..end], &content[end..], format),
+        format,
+    );
+    let note = note(
+        format,
+        "Truncated",
+        &format!("showing characters {from}-{to} of {total}.{sections} Pass `\"cursor\": \"{next_cursor}\"` to get the next part, or call `get_toc` and pass one section anchor as `section`."),
+    );
+    Ok(Page {
+        text: format!("{kept}\n\n{note}\n"),
+        next_cursor: Some(next_cursor),
+        version: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_budget() {
+        assert_eq!(char_budget(None, None), None);
+        assert_eq!(char_budget(Some(1000), None), Some(4000));
+        assert_eq!(char_budget(Some(1000), Some(500)), Some(500));
+        assert_eq!(char_budget(Some(1), None), Some(400));
+        assert_eq!(char_budget(None, Some(1)), Some(400));
+    }
+
+    #[test]
+    fn test_truncate_markdown_at_section_boundary() {
+        let content = format!(
+            "# Crate demo\n\nIntro.\n\n## Usage\n\n```rust\n# hidden\nlet x = 1;\n```\n\n## Modules\n\n{}\n\n## Structs\n\nMore.\n",
+            "text ".repeat(40)
+        );
+        let out = truncate_at_headings(&content, 70, Format::Markdown);
+        assert!(
+            out.starts_with("# Crate demo\n\nIntro.\n\n## Usage"),
+            "{out}"
+        );
+        assert!(out.contains("let x = 1;\n```"), "{out}");
+        assert!(!out.contains("## Modules\n"), "{out}");
+        assert!(
+            out.contains("2 more sections: \"Modules\", \"Structs\"."),
+            "{out}"
+        );
+        assert!(out.contains("`get_toc`"), "{out}");
+
+        assert_eq!(truncate_at_headings("short", 60, Format::Markdown), "short");
+
+        // html2md renders h1/h2 as setext headings
+        let setext = format!(
+            "Crate demo\n==========\n\nIntro.\n\nModules\n-------\n\n{}\n",
+            "x ".repeat(40)
+        );
+        let out = truncate_at_headings(&setext, 40, Format::Markdown);
+        assert!(
+            out.starts_with("Crate demo\n==========\n\nIntro.\n\n---"),
+            "{out}"
+        );
+        assert!(out.contains("1 more section: \"Modules\"."), "{out}");
+    }
+
+    #[test]
+    fn test_truncate_falls_back_to_paragraphs_and_lines() {
+        let content = format!("{}\n\n{}\n", "a".repeat(30), "b".repeat(30));
+        let out = truncate_at_headings(&content, 40, Format::Text);
+        assert!(
+            out.starts_with(&format!("{}\n\n[Truncated", "a".repeat(30))),
+            "{out}"
+        );
+
+        let single = "x".repeat(50);
+        let out = truncate_at_headings(&single, 20, Format::Text);
+        assert!(out.starts_with(&format!(
+            "{}\n\n[Truncated: showing 20 of 50",
+            "x".repeat(20)
+        )));
+    }
+
+    #[test]
+    fn test_truncate_never_leaves_a_fence_or_sentence_open() {
+        let code = (1..=40)
+            .map(|i| format!("let x{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let content = format!("```rust\n{code}\n```\n\n## Next\n\nMore.\n");
+        let out = truncate_at_headings(&content, 100, Format::Markdown);
+        let (kept, note) = out.split_once("\n\n---").expect("note");
+        assert!(kept.starts_with("```rust\nlet x1 = 1;"), "{out}");
+        assert!(kept.ends_with(";\n```"), "{out}");
+        assert!(note.contains("1 more section: \"Next\"."), "{out}");
+
+        let prose = "First sentence here. Second one is a good deal longer than that.";
+        let out = truncate_at_headings(prose, 40, Format::Text);
+        assert!(
+            out.starts_with("First sentence here.\n\n[Truncated"),
+            "{out}"
+        );
+        assert_eq!(shorten(prose, 30, Format::Text), "First sentence here. ...");
+        assert_eq!(
+            shorten("one two three four", 12, Format::Text),
+            "one two ..."
+        );
+        assert_eq!(shorten(prose, 100, Format::Text), prose);
+        assert_eq!(shorten(prose, 2, Format::Text), "..");
+    }
+
+    #[test]
+    fn test_shorten_inside_a_fence_stays_within_budget() {
+        let code = (1..=20)
+            .map(|i| format!("let x{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let content = format!("```rust\n{code}\n```\n\nMore.\n");
+        let mut cut_in_fence = 0;
+        for budget in 0..content.chars().count() {
+            let out = shorten(&content, budget, Format::Markdown);
+            assert!(out.chars().count() <= budget, "{budget}: {out}");
+            if out.starts_with("```rust\nlet") && !out.contains("More") {
+                assert!(out.ends_with(";\n```\n..."), "{budget}: {out}");
+                cut_in_fence += 1;
+            }
+        }
+        assert!(cut_in_fence > 0);
+
+        let html = format!(
+            "<h2>A</h2><p>{}</p>\n<h2>B</h2>\n<h2>C</h2>\n",
+            "x ".repeat(40)
+        );
+        let out = truncate_at_headings(&html, 50, Format::Html);
+        assert!(out.contains("2 more sections."), "{out}");
+    }
+
+    #[test]
+    fn test_paginate_reopens_a_split_code_block() {
+        let code = (1..=60)
+            .map(|i| format!("let x{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let content = format!("```rust\n{code}\n```\n");
+        let first = paginate("t", &content, Some(400), None, Format::Markdown).unwrap();
+        assert!(first.text.contains(";\n```\n\n---"), "{}", first.text);
+        let second = paginate(
+            "t",
+            &content,
+            Some(400),
+            first.next_cursor.as_deref(),
+            Format::Markdown,
+        )
+        .unwrap();
+        assert!(second.text.starts_with("```rust\nlet x"), "{}", second.text);
+    }
+
+    #[test]
+    fn test_paginate_walks_the_document() {
+        let content = (1..=6)
+            .map(|i| format!("## Part {i}\n\n{}\n", "word ".repeat(20)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let whole = paginate("t", &content, None, None, Format::Markdown).unwrap();
+        assert_eq!(whole.text, content);
+        assert_eq!(whole.next_cursor, None);
+
+        let mut cursor = None;
+        let mut parts = Vec::new();
+        loop {
+            let page = paginate(
+                "t",
+                &content,
+                Some(250),
+                cursor.as_deref(),
+                Format::Markdown,
+            )
+            .unwrap();
+            parts.push(page.text);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert!(parts.len() > 1);
+        assert!(parts[0].starts_with("## Part 1"));
+        assert!(parts[0].contains("more sections:"), "{}", parts[0]);
+        assert!(parts[1].starts_with("## Part"), "{}", parts[1]);
+        assert!(parts.last().unwrap().contains("**Last part**"));
+        for i in 1..=6 {
+            let heading = format!("## Part {i}\n");
+            assert_eq!(parts.iter().filter(|p| p.contains(&heading)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_paginate_rejects_foreign_cursors() {
+        let content = "x".repeat(1000);
+        let page = paginate("t", &content, Some(400), None, Format::Text).unwrap();
+        let cursor = page.next_cursor.expect("document spans several parts");
+        assert!(paginate("t", &content, None, Some(&cursor), Format::Text).is_ok());
+        assert!(paginate("t", &"y".repeat(999), None, Some(&cursor), Format::Text).is_err());
+        assert!(paginate("t", &content, None, Some("garbage"), Format::Text).is_err());
+        assert!(paginate("t", &content, None, Some("0:1000"), Format::Text).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+//! Crate build statistics tool
+//!
+//! Summarizes how heavy a crate version is to build and document: the
+//! docs.rs documentation size and build duration, the rustc and docs.rs
+//! versions of the latest build, the documented targets, and the source
+//! size, edition and minimum Rust version published on crates.io.
+//!
+//! Build records come from the same docs.rs endpoints as
+//! `get_docsrs_build_status`; the crates.io metadata is supplementary and
+//! left out of the report when crates.io cannot be reached.
+
+#![allow(missing_docs)]
+
+use super::build_status::{BuildOutcome, BuildStatusReport, DocsRsBuildStatusToolImpl};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "crate_build_info";
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "crate_build_info",
+    title = "Crate Build Info",
+    description = "Get build statistics for a crate version: docs.rs documentation size and build duration, rustc and docs.rs versions used, targets documented, plus source size, edition and minimum Rust version from crates.io. Useful to understand how heavy a dependency is.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `crate_build_info` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct CrateBuildInfoTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to inspect, e.g.: serde, tokio, aws-sdk-s3"
+    )]
+    pub crate_name: String,
+
+    /// Crate version (optional, defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured build statistics)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Response of the crates.io version endpoint
+#[derive(Debug, Deserialize)]
+struct CratesIoVersionResponse {
+    version: CratesIoVersion,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CratesIoVersion {
+    #[serde(default)]
+    crate_size: Option<u64>,
+    #[serde(default)]
+    rust_version: Option<String>,
+    #[serde(default)]
+    edition: Option<String>,
+}
+
+/// Build statistics of a crate version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateBuildInfo {
+    /// Crate name
+    pub crate_name: String,
+    /// Concrete version the statistics apply to
+    pub version: String,
+    /// Whether documentation is available on docs.rs
+    pub documentation_built: bool,
+    /// Documentation size as displayed by docs.rs, if published
+    pub documentation_size: Option<String>,
+    /// Outcome of the latest docs.rs build
+    pub build_status: Option<BuildOutcome>,
+    /// Duration of the latest docs.rs build in seconds, if reported
+    pub build_duration_secs: Option<u64>,
+    /// Time of the latest docs.rs build
+    pub build_time: Option<String>,
+    /// rustc version of the latest docs.rs build
+    pub rustc_version: Option<String>,
+    /// docs.rs builder version of the latest build
+    pub docsrs_version: Option<String>,
+    /// Targets documentation was built for
+    pub targets: Vec<String>,
+    /// Size of the published `.crate` archive in bytes
+    pub crate_size: Option<u64>,
+    /// Rust edition of the crate
+    pub edition: Option<String>,
+    /// Minimum supported Rust version (`rust-version`)
+    pub rust_version: Option<String>,
+    /// docs.rs builds overview URL
+    pub builds_url: String,
+}
+
+impl CrateBuildInfo {
+    fn new(report: BuildStatusReport, metadata: Option<CratesIoVersion>) -> Self {
+        let latest = report.builds.into_iter().next();
+        let metadata = metadata.unwrap_or_default();
+        Self {
+            crate_name: report.crate_name,
+            version: report.version,
+            documentation_built: report.documentation_built,
+            documentation_size: report.documentation_size,
+            build_status: latest.as_ref().map(|build| build.status),
+            build_duration_secs: latest.as_ref().and_then(|build| build.duration_secs),
+            build_time: latest.as_ref().and_then(|build| build.build_time.clone()),
+            rustc_version: latest
+                .as_ref()
+                .and_then(|build| build.rustc_version.clone()),
+            docsrs_version: latest.and_then(|build| build.docsrs_version),
+            targets: report.targets,
+            crate_size: metadata.crate_size,
+            edition: metadata.edition,
+            rust_version: metadata.rust_version,
+            builds_url: report.builds_url,
+        }
+    }
+}
+
+/// Human-readable byte size, e.g. `1.5 MiB`
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Human-readable duration, e.g. `2m 5s`
+fn format_duration(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, secs) => format!("{secs}s"),
+        (mins, secs) => format!("{mins}m {secs}s"),
+    }
+}
+
+fn format_info(info: &CrateBuildInfo, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let unknown = || "unknown".to_string();
+    let rows = [
+        (
+            "Documentation",
+            if info.documentation_built {
+                "built".to_string()
+            } else {
+                "not available (build failed or pending)".to_string()
+            },
+        ),
+        (
+            "Documentation size",
+            info.documentation_size.clone().unwrap_or_else(unknown),
+        ),
+        (
+            "Latest build",
+            info.build_status
+                .map_or_else(unknown, |status| status.to_string()),
+        ),
+        (
+            "Build duration",
+            info.build_duration_secs
+                .map_or_else(unknown, format_duration),
+        ),
+        (
+            "Build time",
+            info.build_time.clone().unwrap_or_else(unknown),
+        ),
+        ("rustc", info.rustc_version.clone().unwrap_or_else(unknown)),
+        (
+            "docs.rs",
+            info.docsrs_version.clone().unwrap_or_else(unknown),
+        ),
+        (
+            "Targets",
+            if info.targets.is_empty() {
+                unknown()
+            } else {
+                info.targets.join(", ")
+            },
+        ),
+        (
+            "Crate size",
+            info.crate_size.map_or_else(unknown, format_bytes),
+        ),
+        ("Edition", info.edition.clone().unwrap_or_else(unknown)),
+        (
+            "Rust version",
+            info.rust_version
+                .clone()
+                .unwrap_or_else(|| "not declared".to_string()),
+        ),
+    ];
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# Build info: {} {}\n",
+            info.crate_name, info.version
+        )
+        .unwrap();
+        writeln!(output, "| | |\n|---|---|").unwrap();
+        for (label, value) in rows {
+            writeln!(output, "| **{label}** | {value} |").unwrap();
+        }
+        writeln!(output, "\nBuilds: <{}>", info.builds_url).unwrap();
+    } else {
+        writeln!(output, "Build info: {} {}", info.crate_name, info.version).unwrap();
+        for (label, value) in rows {
+            writeln!(output, "{label}: {value}").unwrap();
+        }
+        writeln!(output, "Builds: {}", info.builds_url).unwrap();
+    }
+    output
+}
+
+/// Implementation of the crate build statistics tool
+pub struct CrateBuildInfoToolImpl {
+    /// Shared document service for HTTP requests
+    service: Arc<super::DocService>,
+}
+
+impl CrateBuildInfoToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Fetch crates.io metadata of a version; failures only degrade the report
+    async fn fetch_metadata(&self, crate_name: &str, version: &str) -> Option<CratesIoVersion> {
+        let url = super::build_crates_io_version_url(crate_name, version);
+        let body = match self
+            .service
+            .fetch_html_optional(&url, Some(TOOL_NAME))
+            .await
+        {
+            Ok(body) => body?,
+            Err(e) => {
+                tracing::warn!(
+                    "[{TOOL_NAME}] failed to fetch crates.io metadata (continuing): {e}"
+                );
+                return None;
+            }
+        };
+        match serde_json::from_str::<CratesIoVersionResponse>(&body) {
+            Ok(response) => Some(response.version),
+            Err(e) => {
+                tracing::warn!(
+                    "[{TOOL_NAME}] failed to parse crates.io metadata (continuing): {e}"
+                );
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CrateBuildInfoToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        CrateBuildInfoTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: CrateBuildInfoTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' is part of the Rust distribution and is not built on docs.rs"
+                )),
+            ));
+        }
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?
+            .unwrap_or_else(|| "latest".to_string());
+
+        let report = DocsRsBuildStatusToolImpl::new(self.service.clone())
+            .fetch_report(crate_name, &version)
+            .await?;
+        let metadata = self.fetch_metadata(crate_name, &report.version).await;
+        let info = CrateBuildInfo::new(report, metadata);
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&info).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_info(&info, false),
+            _ => format_info(&info, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for CrateBuildInfoToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes_and_duration() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(125), "2m 5s");
+    }
+}
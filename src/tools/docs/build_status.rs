@@ -0,0 +1,569 @@
+//! docs.rs build status tool
+//!
+//! Reports docs.rs build metadata for a crate version: whether documentation
+//! was built, the recent build history with links to the build logs, the
+//! targets that were documented, and the documentation size when docs.rs
+//! publishes it. This explains "docs failed to build" situations instead of
+//! returning the docs.rs error page as documentation.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "get_docsrs_build_status";
+
+/// Maximum number of builds listed in the build history
+const MAX_LISTED_BUILDS: usize = 5;
+
+/// Matches the platform links on a docs.rs crate page, e.g.
+/// `/crate/serde/1.0.0/target-redirect/x86_64-unknown-linux-gnu/serde/`
+static TARGET_REDIRECT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"/target-redirect/([A-Za-z0-9_.\-]+)/").expect("hardcoded valid regex pattern")
+});
+
+/// Matches the documentation size shown in the docs.rs crate page metadata
+static DOC_SIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)documentation\s+size:?\s*([0-9][0-9.,]*\s*[kmgt]?i?b)\b")
+        .expect("hardcoded valid regex pattern")
+});
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "get_docsrs_build_status",
+    title = "Get docs.rs Build Status",
+    description = "Get docs.rs build metadata for a crate version: whether documentation built successfully, recent builds with rustc versions and build log links, targets built, and documentation size. Use this to explain why documentation is missing or failed to build.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `get_docsrs_build_status` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct DocsRsBuildStatusTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to check, e.g.: serde, tokio, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Crate version (optional, defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured build metadata)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Response of the docs.rs `status.json` endpoint
+#[derive(Debug, Deserialize)]
+struct DocsRsStatusResponse {
+    /// Concrete version (resolves `latest`)
+    version: String,
+    /// Whether documentation was built successfully
+    doc_status: bool,
+}
+
+/// Single entry of the docs.rs `builds.json` endpoint
+#[derive(Debug, Deserialize)]
+struct DocsRsBuildRecord {
+    id: u64,
+    #[serde(default)]
+    rustc_version: Option<String>,
+    #[serde(default)]
+    docsrs_version: Option<String>,
+    /// `"success"`/`"failure"`/`"in_progress"`, or a boolean on older docs.rs
+    #[serde(default)]
+    build_status: serde_json::Value,
+    #[serde(default)]
+    build_time: Option<String>,
+    /// Start of the build, reported by newer docs.rs versions
+    #[serde(default)]
+    build_started: Option<String>,
+}
+
+/// Outcome of a single docs.rs build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildOutcome {
+    /// Documentation built successfully
+    Success,
+    /// The build failed
+    Failure,
+    /// The build is queued or running
+    InProgress,
+    /// docs.rs reported a status this tool does not recognise
+    Unknown,
+}
+
+impl BuildOutcome {
+    fn from_status(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Bool(true) => Self::Success,
+            serde_json::Value::Bool(false) => Self::Failure,
+            serde_json::Value::String(s) => match s.as_str() {
+                "success" => Self::Success,
+                "failure" => Self::Failure,
+                "in_progress" | "in-progress" | "queued" => Self::InProgress,
+                _ => Self::Unknown,
+            },
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for BuildOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Success => write!(f, "success"),
+            Self::Failure => write!(f, "failure"),
+            Self::InProgress => write!(f, "in progress"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Summary of a single docs.rs build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildSummary {
+    /// docs.rs build ID
+    pub id: u64,
+    /// Build outcome
+    pub status: BuildOutcome,
+    /// rustc version used for the build
+    pub rustc_version: Option<String>,
+    /// docs.rs builder version
+    pub docsrs_version: Option<String>,
+    /// Build timestamp as reported by docs.rs
+    pub build_time: Option<String>,
+    /// Build duration in seconds, when docs.rs reports the build start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    /// Link to the build log
+    pub log_url: String,
+}
+
+/// docs.rs build report for a crate version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildStatusReport {
+    /// Crate name
+    pub crate_name: String,
+    /// Concrete version the report applies to
+    pub version: String,
+    /// Whether documentation is available on docs.rs
+    pub documentation_built: bool,
+    /// Most recent builds, newest first
+    pub builds: Vec<BuildSummary>,
+    /// Targets documentation was built for
+    pub targets: Vec<String>,
+    /// Documentation size as displayed by docs.rs, if published
+    pub documentation_size: Option<String>,
+    /// docs.rs documentation URL
+    pub docs_url: String,
+    /// docs.rs builds overview URL
+    pub builds_url: String,
+}
+
+/// Implementation of the docs.rs build status tool
+pub struct DocsRsBuildStatusToolImpl {
+    /// Shared document service for HTTP requests
+    service: Arc<super::DocService>,
+}
+
+impl DocsRsBuildStatusToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Fetch the build report of `version` (`latest` for the newest release)
+    ///
+    /// Also used by the `crate_build_info` tool.
+    pub(super) async fn fetch_report(
+        &self,
+        crate_name: &str,
+        version: &str,
+    ) -> std::result::Result<BuildStatusReport, CallToolError> {
+        let status_url =
+            self.service
+                .crate_details_url(crate_name, version, "status.json", TOOL_NAME)?;
+        let Some(body) = self
+            .service
+            .fetch_html_optional(&status_url, Some(TOOL_NAME))
+            .await?
+        else {
+            let version = (version != "latest").then_some(version);
+            return Err(super::crate_not_found(crate_name, version).into());
+        };
+        let status: DocsRsStatusResponse = serde_json::from_str(&body).map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+        })?;
+        let version = status.version;
+
+        // The crate details page; the build lists are below it
+        let page_url = self
+            .service
+            .crate_details_url(crate_name, &version, "", TOOL_NAME)?;
+        let builds_url = format!("{page_url}builds.json");
+        let builds = match self
+            .service
+            .fetch_html_optional(&builds_url, Some(TOOL_NAME))
+            .await?
+        {
+            Some(body) => {
+                let records: Vec<DocsRsBuildRecord> = serde_json::from_str(&body).map_err(|e| {
+                    CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+                })?;
+                summarize_builds(&page_url, records)
+            }
+            None => Vec::new(),
+        };
+
+        // Targets and size come from the crate page. They are supplementary,
+        // so a failure here degrades the report instead of failing it.
+        let page = match self
+            .service
+            .fetch_html_optional(&page_url, Some(TOOL_NAME))
+            .await
+        {
+            Ok(page) => page.unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("[{TOOL_NAME}] failed to fetch crate page (continuing): {e}");
+                String::new()
+            }
+        };
+
+        Ok(BuildStatusReport {
+            crate_name: crate_name.to_string(),
+            documentation_built: status.doc_status,
+            builds,
+            targets: extract_targets(&page),
+            documentation_size: extract_documentation_size(&page),
+            docs_url: self.service.crate_docs_url(crate_name, Some(&version)),
+            builds_url: format!("{page_url}builds"),
+            version,
+        })
+    }
+}
+
+/// Newest builds first, with log URLs below the crate details page at
+/// `page_url`
+fn summarize_builds(page_url: &str, records: Vec<DocsRsBuildRecord>) -> Vec<BuildSummary> {
+    let mut records = records;
+    // docs.rs lists newest builds first today; sort anyway so the "latest
+    // build" shown to the caller never depends on upstream ordering.
+    records.sort_by_key(|record| std::cmp::Reverse(record.id));
+    records
+        .into_iter()
+        .take(MAX_LISTED_BUILDS)
+        .map(|record| BuildSummary {
+            duration_secs: build_duration(
+                record.build_started.as_deref(),
+                record.build_time.as_deref(),
+            ),
+            id: record.id,
+            status: BuildOutcome::from_status(&record.build_status),
+            rustc_version: record.rustc_version,
+            docsrs_version: record.docsrs_version,
+            build_time: record.build_time,
+            log_url: format!("{page_url}builds/{}", record.id),
+        })
+        .collect()
+}
+
+/// Seconds between the start and end timestamps (RFC 3339) of a build
+fn build_duration(started: Option<&str>, finished: Option<&str>) -> Option<u64> {
+    let started = chrono::DateTime::parse_from_rfc3339(started?).ok()?;
+    let finished = chrono::DateTime::parse_from_rfc3339(finished?).ok()?;
+    u64::try_from((finished - started).num_seconds()).ok()
+}
+
+/// Collect the distinct targets linked from a docs.rs crate page
+fn extract_targets(page: &str) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for caps in TARGET_REDIRECT_REGEX.captures_iter(page) {
+        let target = &caps[1];
+        if !targets.iter().any(|t| t == target) {
+            targets.push(target.to_string());
+        }
+    }
+    targets
+}
+
+fn extract_documentation_size(page: &str) -> Option<String> {
+    if page.is_empty() {
+        return None;
+    }
+    let text = super::html::html_to_text(page);
+    DOC_SIZE_REGEX
+        .captures(&text)
+        .map(|caps| caps[1].trim().to_string())
+}
+
+fn format_markdown_report(report: &BuildStatusReport) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    writeln!(
+        output,
+        "# docs.rs build status: {} {}\n",
+        report.crate_name, report.version
+    )
+    .unwrap();
+
+    if report.documentation_built {
+        writeln!(output, "**Documentation**: built successfully").unwrap();
+    } else {
+        writeln!(
+            output,
+            "**Documentation**: not available (build failed or pending)"
+        )
+        .unwrap();
+    }
+    if !report.targets.is_empty() {
+        writeln!(output, "**Targets**: {}", report.targets.join(", ")).unwrap();
+    }
+    if let Some(size) = &report.documentation_size {
+        writeln!(output, "**Documentation size**: {size}").unwrap();
+    }
+    writeln!(output, "**Docs**: <{}>", report.docs_url).unwrap();
+    writeln!(output, "**Builds**: <{}>", report.builds_url).unwrap();
+
+    output.push_str("\n## Recent builds\n\n");
+    if report.builds.is_empty() {
+        output.push_str("No builds recorded by docs.rs.\n");
+    } else {
+        output.push_str("| Build | Status | rustc | Time | Log |\n");
+        output.push_str("|---|---|---|---|---|\n");
+        for build in &report.builds {
+            writeln!(
+                output,
+                "| {} | {} | {} | {} | [log]({}) |",
+                build.id,
+                build.status,
+                build.rustc_version.as_deref().unwrap_or("-"),
+                build.build_time.as_deref().unwrap_or("-"),
+                build.log_url
+            )
+            .unwrap();
+        }
+    }
+    output
+}
+
+fn format_text_report(report: &BuildStatusReport) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    writeln!(
+        output,
+        "docs.rs build status: {} {}",
+        report.crate_name, report.version
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "Documentation: {}",
+        if report.documentation_built {
+            "built successfully"
+        } else {
+            "not available (build failed or pending)"
+        }
+    )
+    .unwrap();
+    if !report.targets.is_empty() {
+        writeln!(output, "Targets: {}", report.targets.join(", ")).unwrap();
+    }
+    if let Some(size) = &report.documentation_size {
+        writeln!(output, "Documentation size: {size}").unwrap();
+    }
+    writeln!(output, "Docs: {}", report.docs_url).unwrap();
+    writeln!(output, "Builds: {}", report.builds_url).unwrap();
+    writeln!(output).unwrap();
+
+    if report.builds.is_empty() {
+        writeln!(output, "No builds recorded by docs.rs.").unwrap();
+    }
+    for build in &report.builds {
+        writeln!(
+            output,
+            "Build {}: {} ({}, {})",
+            build.id,
+            build.status,
+            build.rustc_version.as_deref().unwrap_or("unknown rustc"),
+            build.build_time.as_deref().unwrap_or("unknown time")
+        )
+        .unwrap();
+        writeln!(output, "   Log: {}", build.log_url).unwrap();
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for DocsRsBuildStatusToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        DocsRsBuildStatusTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<
+        rust_mcp_sdk::schema::CallToolResult,
+        rust_mcp_sdk::schema::CallToolError,
+    > {
+        let params: DocsRsBuildStatusTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' is part of the Rust distribution and is not built on docs.rs"
+                )),
+            ));
+        }
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?
+            .unwrap_or_else(|| "latest".to_string());
+
+        let report = self.fetch_report(crate_name, &version).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&report).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_text_report(&report),
+            _ => format_markdown_report(&report),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for DocsRsBuildStatusToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_outcome_from_status() {
+        use serde_json::json;
+        assert_eq!(
+            BuildOutcome::from_status(&json!("success")),
+            BuildOutcome::Success
+        );
+        assert_eq!(
+            BuildOutcome::from_status(&json!("failure")),
+            BuildOutcome::Failure
+        );
+        assert_eq!(
+            BuildOutcome::from_status(&json!("in_progress")),
+            BuildOutcome::InProgress
+        );
+        assert_eq!(
+            BuildOutcome::from_status(&json!(true)),
+            BuildOutcome::Success
+        );
+        assert_eq!(
+            BuildOutcome::from_status(&json!(false)),
+            BuildOutcome::Failure
+        );
+        assert_eq!(
+            BuildOutcome::from_status(&json!(null)),
+            BuildOutcome::Unknown
+        );
+    }
+
+    #[test]
+    fn test_extract_targets_dedupes_in_order() {
+        let page = r#"
+            <a href="/crate/serde/1.0.0/target-redirect/x86_64-unknown-linux-gnu/serde/">linux</a>
+            <a href="/crate/serde/1.0.0/target-redirect/i686-pc-windows-msvc/serde/">windows</a>
+            <a href="/crate/serde/1.0.0/target-redirect/x86_64-unknown-linux-gnu/serde/">again</a>
+        "#;
+        assert_eq!(
+            extract_targets(page),
+            vec!["x86_64-unknown-linux-gnu", "i686-pc-windows-msvc"]
+        );
+        assert!(extract_targets("").is_empty());
+    }
+
+    #[test]
+    fn test_extract_documentation_size() {
+        let page = "<ul><li>Documentation size: 1.2 MB</li></ul>";
+        assert_eq!(extract_documentation_size(page).as_deref(), Some("1.2 MB"));
+        assert_eq!(extract_documentation_size("<p>nothing</p>"), None);
+    }
+
+    #[test]
+    fn test_summarize_builds_newest_first_and_capped() {
+        let records = (1..=7)
+            .map(|id| DocsRsBuildRecord {
+                id,
+                rustc_version: None,
+                docsrs_version: None,
+                build_status: serde_json::json!("success"),
+                build_time: None,
+                build_started: None,
+            })
+            .collect();
+        let builds = summarize_builds("https://docs.rs/crate/serde/1.0.0/", records);
+        assert_eq!(builds.len(), MAX_LISTED_BUILDS);
+        assert_eq!(builds[0].id, 7);
+        assert!(builds[0].log_url.ends_with("/crate/serde/1.0.0/builds/7"));
+    }
+
+    #[test]
+    fn test_build_duration() {
+        assert_eq!(
+            build_duration(Some("2024-05-01T10:00:00Z"), Some("2024-05-01T10:02:05.5Z")),
+            Some(125)
+        );
+        assert_eq!(build_duration(None, Some("2024-05-01T10:00:00Z")), None);
+        // A finish before the start is not a duration
+        assert_eq!(
+            build_duration(Some("2024-05-01T10:00:00Z"), Some("2024-05-01T09:00:00Z")),
+            None
+        );
+    }
+}
@@ -65,7 +65,7 @@ fn escape_key_segment(segment: &str) -> String {
 /// builder maps `None` to `latest`. Keying them separately would duplicate
 /// entries and trigger redundant network fetches.
 #[inline]
-fn normalize_cache_version(version: Option<&str>) -> Option<String> {
+pub(super) fn normalize_cache_version(version: Option<&str>) -> Option<String> {
     version
         .map(|v| v.trim().to_lowercase())
         .filter(|v| !v.is_empty() && v != "latest")
@@ -139,6 +139,246 @@ impl CacheKeyGenerator {
         format!("search:{normalized_query}:{normalized_sort}:{limit}")
     }
 
+    /// Build category listing cache key with normalization
+    ///
+    /// Key format: `category:{slug}:{sort}:{limit}`. Category slugs contain
+    /// `::` for subcategories (e.g. `web-programming::http-server`), so the
+    /// slug is escaped like other free-form segments.
+    #[must_use]
+    pub fn category_cache_key(category: &str, limit: u32, sort: &str) -> String {
+        let normalized_category = escape_key_segment(&category.trim().to_lowercase());
+        let normalized_sort = escape_key_segment(&sort.trim().to_lowercase());
+        format!("category:{normalized_category}:{normalized_sort}:{limit}")
+    }
+
+    /// Build trending listing cache key with normalization
+    ///
+    /// Key format: `trending:{sort}:{category}:{keyword}:{limit}`, with `*`
+    /// standing in for an absent filter.
+    #[must_use]
+    pub fn trending_cache_key(
+        sort: &str,
+        category: Option<&str>,
+        keyword: Option<&str>,
+        limit: u32,
+    ) -> String {
+        let segment = |value: Option<&str>| {
+            value.map_or_else(
+                || "*".to_string(),
+                |v| escape_key_segment(&v.trim().to_lowercase()),
+            )
+        };
+        format!(
+            "trending:{}:{}:{}:{limit}",
+            escape_key_segment(&sort.trim().to_lowercase()),
+            segment(category),
+            segment(keyword)
+        )
+    }
+
+    /// Build crates.io index cache key
+    ///
+    /// Key format: `index:{crate}`, lowercased like the index paths themselves.
+    #[must_use]
+    pub fn index_cache_key(crate_name: &str) -> String {
+        format!(
+            "index:{}",
+            escape_key_segment(&crate_name.trim().to_lowercase())
+        )
+    }
+
+    /// Build feature-gate report cache key
+    ///
+    /// Key format: `gates:{crate key}:{max_modules}`, reusing the crate key
+    /// normalization (see [`Self::crate_cache_key`]).
+    #[must_use]
+    pub fn feature_gates_cache_key(
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("gates:{base_key}:{max_modules}")
+    }
+
+    /// Build deprecated-items report cache key
+    ///
+    /// Key format: `deprecated:{crate key}:{max_modules}`.
+    #[must_use]
+    pub fn deprecated_cache_key(
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("deprecated:{base_key}:{max_modules}")
+    }
+
+    /// Build macro documentation cache key
+    ///
+    /// Key format: `macro:{crate key}:{spec}`, where `spec` is the normalized
+    /// macro reference (e.g. `json!`, `#[main]`), escaped like other
+    /// free-form segments.
+    #[must_use]
+    pub fn macro_cache_key(crate_name: &str, version: Option<&str>, spec: &str) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("macro:{base_key}:{}", escape_key_segment(spec.trim()))
+    }
+
+    /// Build rustc error index cache key
+    ///
+    /// Key format: `rustc_error:{code}`; `code` is already normalized
+    /// (`E0308`).
+    #[must_use]
+    pub fn rustc_error_cache_key(code: &str) -> String {
+        format!("rustc_error:{}", escape_key_segment(code.trim()))
+    }
+
+    /// Build Clippy lint cache key
+    ///
+    /// Key format: `clippy_lint:{name}`; `name` is already normalized
+    /// (`needless_borrow`).
+    #[must_use]
+    pub fn clippy_lint_cache_key(name: &str) -> String {
+        format!("clippy_lint:{}", escape_key_segment(name.trim()))
+    }
+
+    /// Build symbol index cache key
+    ///
+    /// Key format: `symbols:{crate key}`, reusing the crate key normalization
+    /// (see [`Self::crate_cache_key`]). Only latest docs are indexed.
+    #[must_use]
+    pub fn symbol_index_cache_key(crate_name: &str) -> String {
+        format!("symbols:{}", Self::crate_cache_key(crate_name, None))
+    }
+
+    /// Build crate downloads cache key
+    ///
+    /// Key format: `downloads:{a,b,...}` for an explicit (sorted, lowercased)
+    /// crate list, or `downloads:*:{limit}` for the most downloaded crates.
+    #[must_use]
+    pub fn crate_downloads_cache_key(crates: &[String], limit: u32) -> String {
+        if crates.is_empty() {
+            return format!("downloads:*:{limit}");
+        }
+        let mut names: Vec<String> = crates
+            .iter()
+            .map(|name| escape_key_segment(&name.trim().to_lowercase()))
+            .collect();
+        names.sort();
+        names.dedup();
+        format!("downloads:{}", names.join(","))
+    }
+
+    /// Build lookup counter key
+    ///
+    /// Key format: `stats:lookups:{kind}`, where `kind` is `crates` or
+    /// `items`. The counter is never expired.
+    #[must_use]
+    pub fn lookup_counter_key(kind: &str) -> String {
+        format!("stats:lookups:{kind}")
+    }
+
+    /// Build the key of the cached crate docs index
+    ///
+    /// Key format: `stats:docs:crates`, a counter whose members are
+    /// `{name}` or `{name}@{version}`. The index is never expired; its
+    /// members are checked against the docs cache when read.
+    #[must_use]
+    pub fn docs_index_key() -> String {
+        "stats:docs:crates".to_string()
+    }
+
+    /// Build dependency tree cache key
+    ///
+    /// Key format: `deptree:{crate key}:{options hash}`, where `options`
+    /// describes the requested features and dependency kinds. The options
+    /// are hashed since feature lists have no length bound.
+    #[must_use]
+    pub fn dependency_tree_cache_key(
+        crate_name: &str,
+        version: Option<&str>,
+        options: &str,
+    ) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        let mut hasher = DefaultHasher::new();
+        options.hash(&mut hasher);
+        format!("deptree:{base_key}:{:x}", hasher.finish())
+    }
+
+    /// Build crates.io crate summary cache key
+    ///
+    /// Key format: `summary:{crate key}`.
+    #[must_use]
+    pub fn crate_summary_cache_key(crate_name: &str) -> String {
+        let base_key = Self::crate_cache_key(crate_name, None);
+        format!("summary:{base_key}")
+    }
+
+    /// Build crate alternatives cache key
+    ///
+    /// Key format: `alternatives:{crate key}:{limit}`.
+    #[must_use]
+    pub fn alternatives_cache_key(crate_name: &str, limit: u32) -> String {
+        let base_key = Self::crate_cache_key(crate_name, None);
+        format!("alternatives:{base_key}:{limit}")
+    }
+
+    /// Build security advisories cache key
+    ///
+    /// Key format: `advisories:{crate key}`, the version being the one the
+    /// advisories were queried for.
+    #[must_use]
+    pub fn advisories_cache_key(crate_name: &str, version: Option<&str>) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("advisories:{base_key}")
+    }
+
+    /// Build repository README cache key
+    ///
+    /// Key format: `readme:{crate key}`, the version being the one whose
+    /// documentation the README stands in for.
+    #[must_use]
+    pub fn readme_cache_key(crate_name: &str, version: Option<&str>) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("readme:{base_key}")
+    }
+
+    /// Build the key marking a crate as missing from every provider
+    ///
+    /// Key format: `missing:{crate key}`
+    #[must_use]
+    pub fn missing_crate_cache_key(crate_name: &str, version: Option<&str>) -> String {
+        let base_key = Self::crate_cache_key(crate_name, version);
+        format!("missing:{base_key}")
+    }
+
+    /// Build the key marking the entry under `key` as fresh
+    ///
+    /// Key format: `fresh:{key}`. With stale-while-revalidate enabled, an
+    /// entry outlives this marker by the stale window; see
+    /// [`super::DocCacheTtl::stale_secs`].
+    #[must_use]
+    pub fn fresh_marker_key(key: &str) -> String {
+        format!("fresh:{key}")
+    }
+
+    /// Build the key of the HTTP validators of the page cached under `key`
+    ///
+    /// Key format: `validators:{key}`
+    #[must_use]
+    pub fn validators_key(key: &str) -> String {
+        format!("validators:{key}")
+    }
+
+    /// Build the lease key of a background refresh of the entry under `key`
+    ///
+    /// Key format: `refresh:{key}`
+    #[must_use]
+    pub fn refresh_lease_key(key: &str) -> String {
+        format!("refresh:{key}")
+    }
+
     /// Build item cache key with normalization
     ///
     /// # Normalization rules
@@ -340,6 +580,30 @@ mod tests {
         assert_ne!(pct, escaped);
     }
 
+    #[test]
+    fn test_category_cache_key_escapes_subcategories() {
+        assert_eq!(
+            CacheKeyGenerator::category_cache_key(" Asynchronous ", 10, "downloads"),
+            "category:asynchronous:downloads:10"
+        );
+        assert_eq!(
+            CacheKeyGenerator::category_cache_key("web-programming::http-server", 5, "new"),
+            "category:web-programming%3a%3ahttp-server:new:5"
+        );
+    }
+
+    #[test]
+    fn test_trending_cache_key_filters() {
+        assert_eq!(
+            CacheKeyGenerator::trending_cache_key("new", None, Some("Async"), 10),
+            "trending:new:*:async:10"
+        );
+        assert_ne!(
+            CacheKeyGenerator::trending_cache_key("new", Some("parsing"), None, 10),
+            CacheKeyGenerator::trending_cache_key("new", None, Some("parsing"), 10)
+        );
+    }
+
     #[test]
     fn test_item_cache_key_no_version_path_collision() {
         // path "1.0:Serialize" (no version) must NOT collide with
@@ -23,14 +23,77 @@
 mod key;
 mod stats;
 mod ttl;
+mod validators;
 
 use crate::cache::Cache;
+use std::collections::HashSet;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 // Re-export public types
 pub use key::CacheKeyGenerator;
 pub use stats::CacheStats;
 pub use ttl::DocCacheTtl;
+pub use validators::Validators;
+
+/// How long a background refresh holds its lease if it never releases it
+///
+/// Matches the default upstream request timeout, so the lease outlives any
+/// refresh that can still succeed.
+const REFRESH_LEASE_TTL: Duration = Duration::from_secs(30);
+
+/// Keys written while serving a request that bypasses cached entries
+#[derive(Default)]
+struct Bypass {
+    written: std::sync::Mutex<HashSet<String>>,
+}
+
+tokio::task_local! {
+    static BYPASS: Bypass;
+}
+
+/// Run `future` with cached entries ignored if `no_cache` is set
+///
+/// [`DocCache`] lookups made while `future` runs then miss every entry the
+/// request has not written itself, so it fetches fresh pages from upstream
+/// once. Fetched pages are still cached, which refreshes the entries for
+/// later requests.
+pub async fn with_no_cache<F: Future>(no_cache: bool, future: F) -> F::Output {
+    if no_cache {
+        BYPASS.scope(Bypass::default(), future).await
+    } else {
+        future.await
+    }
+}
+
+/// Whether the request currently being served may read the entry under `key`
+fn readable(key: &str) -> bool {
+    BYPASS
+        .try_with(|bypass| lock_written(bypass).contains(key))
+        .unwrap_or(true)
+}
+
+/// Note that the request currently being served wrote `key`
+fn record_written(key: &str) {
+    let _ = BYPASS.try_with(|bypass| lock_written(bypass).insert(key.to_string()));
+}
+
+fn lock_written(bypass: &Bypass) -> std::sync::MutexGuard<'_, HashSet<String>> {
+    bypass
+        .written
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Member of the cached docs index: `{name}` or `{name}@{version}`
+fn docs_index_member(crate_name: &str, version: Option<&str>) -> String {
+    let crate_name = crate_name.trim().to_lowercase();
+    match key::normalize_cache_version(version) {
+        Some(version) => format!("{crate_name}@{version}"),
+        None => crate_name,
+    }
+}
 
 /// Document cache service
 ///
@@ -104,212 +167,1164 @@ impl DocCache {
         }
     }
 
-    /// Get cached crate documentation
-    ///
-    /// # Arguments
-    ///
-    /// * `crate_name` - crate name
-    /// * `version` - Optional version
+    /// Entry under `key`, `None` if the current request bypasses it (see
+    /// [`with_no_cache`])
+    async fn read(&self, key: &str) -> Option<Arc<str>> {
+        if !readable(key) {
+            return None;
+        }
+        self.cache.get(key).await
+    }
+
+    /// Store `value` under `key`
+    async fn write(
+        &self,
+        key: String,
+        value: Arc<str>,
+        ttl: Option<Duration>,
+    ) -> crate::error::Result<()> {
+        self.cache.set(key.clone(), value, ttl).await?;
+        record_written(&key);
+        Ok(())
+    }
+
+    /// Get cached crate documentation
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - crate name
+    /// * `version` - Optional version
+    ///
+    /// # Returns
+    ///
+    /// Returns document content if cache hit; otherwise returns `None`
+    #[tracing::instrument(skip(self), fields(crate = crate_name, version = version), level = "trace")]
+    pub async fn get_crate_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::crate_cache_key(crate_name, version);
+        let result = self.read(&key).await;
+        let is_hit = result.is_some();
+        if is_hit {
+            self.stats.record_hit();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get",
+                hit = true,
+                crate = crate_name
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache hit for crate docs");
+            });
+        } else {
+            self.stats.record_miss();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get",
+                hit = false,
+                crate = crate_name
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache miss for crate docs");
+            });
+        }
+        result
+    }
+
+    /// Set crate document cache
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - crate name
+    /// * `version` - Optional version
+    /// * `content` - Document content
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate = crate_name, version = version), err, level = "trace")]
+    pub async fn set_crate_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::crate_cache_key(crate_name, version);
+        let ttl = self.ttl.crate_docs_duration_for(version);
+        self.set_revalidated(key, content.into(), ttl).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate docs cached");
+        // The index only feeds the resource list; the docs are cached anyway
+        if let Err(e) = self
+            .cache
+            .increment_counter(
+                &CacheKeyGenerator::docs_index_key(),
+                &docs_index_member(crate_name, version),
+            )
+            .await
+        {
+            tracing::debug!("Failed to index cached crate docs: {e}");
+        }
+        Ok(())
+    }
+
+    /// Crates whose documentation is cached, most often stored first
+    ///
+    /// Returns at most `limit` `(crate name, version)` pairs; `None` is the
+    /// latest version. Entries that have expired since are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    pub async fn cached_crate_docs(
+        &self,
+        limit: usize,
+    ) -> crate::error::Result<Vec<(String, Option<String>)>> {
+        let members = self
+            .cache
+            .top_counters(&CacheKeyGenerator::docs_index_key(), limit)
+            .await?;
+        let mut cached = Vec::with_capacity(members.len());
+        for (member, _) in members {
+            let (name, version) = match member.split_once('@') {
+                Some((name, version)) => (name.to_string(), Some(version.to_string())),
+                None => (member, None),
+            };
+            let key = CacheKeyGenerator::crate_cache_key(&name, version.as_deref());
+            if self.cache.exists(&key).await {
+                cached.push((name, version));
+            }
+        }
+        Ok(cached)
+    }
+
+    /// Drop the cached front page of a crate, markdown and HTML
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    pub async fn forget_crate_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> crate::error::Result<()> {
+        self.cache
+            .delete(&CacheKeyGenerator::crate_cache_key(crate_name, version))
+            .await?;
+        self.cache
+            .delete(&CacheKeyGenerator::crate_html_cache_key(
+                crate_name, version,
+            ))
+            .await
+    }
+
+    /// Get cached crate HTML
+    ///
+    /// Returns `Arc<str>` to avoid unnecessary cloning on cache hits.
+    /// The caller can clone if an owned String is needed.
+    #[tracing::instrument(skip(self), fields(crate = crate_name, version = version), level = "trace")]
+    pub async fn get_crate_html(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::crate_html_cache_key(crate_name, version);
+        let result = self.read(&key).await;
+        let is_hit = result.is_some();
+        if is_hit {
+            self.stats.record_hit();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get_html",
+                hit = true,
+                crate = crate_name
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache hit for crate HTML");
+            });
+        } else {
+            self.stats.record_miss();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get_html",
+                hit = false,
+                crate = crate_name
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache miss for crate HTML");
+            });
+        }
+        result
+    }
+
+    /// Set crate HTML cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate = crate_name, version = version), err, level = "trace")]
+    pub async fn set_crate_html(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::crate_html_cache_key(crate_name, version);
+        let ttl = self.ttl.crate_docs_duration_for(version);
+        self.set_revalidated(key, content.into(), ttl).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate HTML cached");
+        Ok(())
+    }
+
+    /// HTTP validators of the cached crate page, used to revalidate it
+    pub async fn get_crate_validators(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> Option<Validators> {
+        let key = CacheKeyGenerator::crate_html_cache_key(crate_name, version);
+        let json = self.read(&CacheKeyGenerator::validators_key(&key)).await?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Store the HTTP validators of a freshly fetched crate page; `None`
+    /// forgets the previous ones
+    ///
+    /// Cached pages are only revalidated with stale-while-revalidate
+    /// enabled, so validators are not kept otherwise. They live as long as
+    /// the page they describe.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    pub async fn set_crate_validators(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        validators: Option<&Validators>,
+    ) -> crate::error::Result<()> {
+        let Some(stale) = self.ttl.stale_duration() else {
+            return Ok(());
+        };
+        let key = CacheKeyGenerator::validators_key(&CacheKeyGenerator::crate_html_cache_key(
+            crate_name, version,
+        ));
+        let Some(validators) = validators else {
+            return self.cache.delete(&key).await;
+        };
+        let json = serde_json::to_string(validators).map_err(|e| {
+            crate::error::Error::cache("set_crate_validators", Some(key.clone()), e.to_string())
+        })?;
+        let ttl = self.ttl.crate_docs_duration_for(version) + stale;
+        self.write(key, Arc::from(json), Some(ttl)).await
+    }
+
+    /// Whether the cached crate documentation is past its TTL, and only
+    /// served until a refresh replaces it (stale-while-revalidate)
+    pub async fn is_crate_docs_stale(&self, crate_name: &str, version: Option<&str>) -> bool {
+        self.is_stale(&CacheKeyGenerator::crate_cache_key(crate_name, version))
+            .await
+    }
+
+    /// Whether the cached crate HTML is past its TTL (see
+    /// [`DocCache::is_crate_docs_stale`])
+    pub async fn is_crate_html_stale(&self, crate_name: &str, version: Option<&str>) -> bool {
+        self.is_stale(&CacheKeyGenerator::crate_html_cache_key(
+            crate_name, version,
+        ))
+        .await
+    }
+
+    /// Claim the background refresh of the entry under `key` for `holder`
+    ///
+    /// Returns `false` if another task (or replica) is already refreshing
+    /// it, or if the lease cannot be taken.
+    pub async fn try_claim_refresh(&self, key: &str, holder: &str) -> bool {
+        let lease = CacheKeyGenerator::refresh_lease_key(key);
+        match self
+            .cache
+            .try_acquire_lease(&lease, holder, REFRESH_LEASE_TTL)
+            .await
+        {
+            Ok(claimed) => claimed,
+            Err(e) => {
+                tracing::warn!("failed to claim refresh of '{key}': {e}");
+                false
+            }
+        }
+    }
+
+    /// Release a refresh claimed with [`DocCache::try_claim_refresh`]
+    pub async fn release_refresh(&self, key: &str, holder: &str) {
+        let lease = CacheKeyGenerator::refresh_lease_key(key);
+        if let Err(e) = self.cache.release_lease(&lease, holder).await {
+            tracing::warn!("failed to release refresh of '{key}': {e}");
+        }
+    }
+
+    /// Store `content` for `ttl`, and with stale-while-revalidate enabled
+    /// keep it for the stale window beyond, marking it fresh only for `ttl`
+    async fn set_revalidated(
+        &self,
+        key: String,
+        content: Arc<str>,
+        ttl: Duration,
+    ) -> crate::error::Result<()> {
+        let Some(stale) = self.ttl.stale_duration() else {
+            return self.write(key, content, Some(ttl)).await;
+        };
+        self.write(
+            CacheKeyGenerator::fresh_marker_key(&key),
+            Arc::from(""),
+            Some(ttl),
+        )
+        .await?;
+        self.write(key, content, Some(ttl + stale)).await
+    }
+
+    /// Whether the entry under `key` lost its fresh marker; always `false`
+    /// with stale-while-revalidate disabled
+    async fn is_stale(&self, key: &str) -> bool {
+        self.ttl.stale_duration().is_some()
+            && !self
+                .cache
+                .exists(&CacheKeyGenerator::fresh_marker_key(key))
+                .await
+    }
+
+    /// Get cached search results
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search query
+    /// * `limit` - Result count limit
+    /// * `sort` - Optional search sort order
+    ///
+    /// # Returns
+    ///
+    /// Returns search results if cache hit;otherwise returns `None`
+    #[tracing::instrument(skip(self), fields(query, limit, sort), level = "trace")]
+    pub async fn get_search_results(
+        &self,
+        query: &str,
+        limit: u32,
+        sort: Option<&str>,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::search_cache_key(query, limit, sort);
+        let result = self.read(&key).await;
+        let is_hit = result.is_some();
+        if is_hit {
+            self.stats.record_hit();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get_search",
+                hit = true
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache hit for search results");
+            });
+        } else {
+            self.stats.record_miss();
+            tracing::span!(
+                tracing::Level::TRACE,
+                "cache",
+                op = "get_search",
+                hit = false
+            )
+            .in_scope(|| {
+                tracing::trace!("Cache miss for search results");
+            });
+        }
+        result
+    }
+
+    /// Set search results cache
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search query
+    /// * `limit` - Result count limit
+    /// * `sort` - Optional search sort order
+    /// * `content` - search result content
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(query, limit, sort), err, level = "trace")]
+    pub async fn set_search_results(
+        &self,
+        query: &str,
+        limit: u32,
+        sort: Option<&str>,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::search_cache_key(query, limit, sort);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Search results cached");
+        Ok(())
+    }
+
+    /// Get cached category listing
+    ///
+    /// Category listings share the search results TTL: both are crates.io
+    /// rankings that drift slowly.
+    #[tracing::instrument(skip(self), fields(category, limit, sort), level = "trace")]
+    pub async fn get_category_results(
+        &self,
+        category: &str,
+        limit: u32,
+        sort: &str,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::category_cache_key(category, limit, sort);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for category listing");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for category listing");
+        }
+        result
+    }
+
+    /// Set category listing cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(
+        skip(self, content),
+        fields(category, limit, sort),
+        err,
+        level = "trace"
+    )]
+    pub async fn set_category_results(
+        &self,
+        category: &str,
+        limit: u32,
+        sort: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::category_cache_key(category, limit, sort);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Category listing cached");
+        Ok(())
+    }
+
+    /// Get cached trending listing
+    #[tracing::instrument(skip(self), fields(sort, category, keyword, limit), level = "trace")]
+    pub async fn get_trending_results(
+        &self,
+        sort: &str,
+        category: Option<&str>,
+        keyword: Option<&str>,
+        limit: u32,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::trending_cache_key(sort, category, keyword, limit);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for trending listing");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for trending listing");
+        }
+        result
+    }
+
+    /// Set trending listing cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(
+        skip(self, content),
+        fields(sort, category, keyword, limit),
+        err,
+        level = "trace"
+    )]
+    pub async fn set_trending_results(
+        &self,
+        sort: &str,
+        category: Option<&str>,
+        keyword: Option<&str>,
+        limit: u32,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::trending_cache_key(sort, category, keyword, limit);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Trending listing cached");
+        Ok(())
+    }
+
+    /// Get cached crates.io index versions
+    ///
+    /// Shares the search results TTL so newly published versions are picked up
+    /// within minutes.
+    #[tracing::instrument(skip(self), fields(crate_name), level = "trace")]
+    pub async fn get_index_versions(&self, crate_name: &str) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::index_cache_key(crate_name);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for index versions");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for index versions");
+        }
+        result
+    }
+
+    /// Set crates.io index versions cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name), err, level = "trace")]
+    pub async fn set_index_versions(
+        &self,
+        crate_name: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::index_cache_key(crate_name);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Index versions cached");
+        Ok(())
+    }
+
+    /// Get cached feature-gate report
+    #[tracing::instrument(skip(self), fields(crate_name, version, max_modules), level = "trace")]
+    pub async fn get_feature_gates(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::feature_gates_cache_key(crate_name, version, max_modules);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for feature gates");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for feature gates");
+        }
+        result
+    }
+
+    /// Set feature-gate report cache
+    ///
+    /// Uses the crate docs TTL since the report is derived from crate pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(
+        skip(self, content),
+        fields(crate_name, version, max_modules),
+        err,
+        level = "trace"
+    )]
+    pub async fn set_feature_gates(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::feature_gates_cache_key(crate_name, version, max_modules);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Feature gates cached");
+        Ok(())
+    }
+
+    /// Get cached deprecated-items report
+    #[tracing::instrument(skip(self), fields(crate_name, version, max_modules), level = "trace")]
+    pub async fn get_deprecated_items(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::deprecated_cache_key(crate_name, version, max_modules);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for deprecated items");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for deprecated items");
+        }
+        result
+    }
+
+    /// Set deprecated-items report cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(
+        skip(self, content),
+        fields(crate_name, version, max_modules),
+        err,
+        level = "trace"
+    )]
+    pub async fn set_deprecated_items(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::deprecated_cache_key(crate_name, version, max_modules);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Deprecated items cached");
+        Ok(())
+    }
+
+    /// Get cached macro documentation
+    #[tracing::instrument(skip(self), fields(crate_name, version, spec), level = "trace")]
+    pub async fn get_macro_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        spec: &str,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::macro_cache_key(crate_name, version, spec);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for macro docs");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for macro docs");
+        }
+        result
+    }
+
+    /// Set macro documentation cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(
+        skip(self, content),
+        fields(crate_name, version, spec),
+        err,
+        level = "trace"
+    )]
+    pub async fn set_macro_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        spec: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::macro_cache_key(crate_name, version, spec);
+        let ttl = self.ttl.item_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Macro docs cached");
+        Ok(())
+    }
+
+    /// Get a cached rustc error index page
+    #[tracing::instrument(skip(self), fields(code), level = "trace")]
+    pub async fn get_rustc_error(&self, code: &str) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::rustc_error_cache_key(code);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for rustc error");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for rustc error");
+        }
+        result
+    }
+
+    /// Set rustc error index page cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(code), err, level = "trace")]
+    pub async fn set_rustc_error(
+        &self,
+        code: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::rustc_error_cache_key(code);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Rustc error cached");
+        Ok(())
+    }
+
+    /// Get cached Clippy lint documentation
+    #[tracing::instrument(skip(self), fields(name), level = "trace")]
+    pub async fn get_clippy_lint(&self, name: &str) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::clippy_lint_cache_key(name);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for clippy lint");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for clippy lint");
+        }
+        result
+    }
+
+    /// Set Clippy lint documentation cache
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(name), err, level = "trace")]
+    pub async fn set_clippy_lint(
+        &self,
+        name: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::clippy_lint_cache_key(name);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Clippy lint cached");
+        Ok(())
+    }
+
+    /// Get cached cross-crate symbol index of a crate
+    #[tracing::instrument(skip(self), fields(crate_name), level = "trace")]
+    pub async fn get_symbol_index(&self, crate_name: &str) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::symbol_index_cache_key(crate_name);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for symbol index");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for symbol index");
+        }
+        result
+    }
+
+    /// Set cross-crate symbol index cache of a crate
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name), err, level = "trace")]
+    pub async fn set_symbol_index(
+        &self,
+        crate_name: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::symbol_index_cache_key(crate_name);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Symbol index cached");
+        Ok(())
+    }
+
+    /// Get cached crate download counts
+    ///
+    /// `crates` empty means the most downloaded crates listing.
+    #[tracing::instrument(skip(self, crates), fields(limit), level = "trace")]
+    pub async fn get_crate_downloads(&self, crates: &[String], limit: u32) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::crate_downloads_cache_key(crates, limit);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for crate downloads");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for crate downloads");
+        }
+        result
+    }
+
+    /// Set crate download counts cache
+    ///
+    /// Shares the search results TTL, like other crates.io listings.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, crates, content), fields(limit), err, level = "trace")]
+    pub async fn set_crate_downloads(
+        &self,
+        crates: &[String],
+        limit: u32,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::crate_downloads_cache_key(crates, limit);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate downloads cached");
+        Ok(())
+    }
+
+    /// Count a successful lookup of a crate, or of an item when `item_path`
+    /// is given
+    ///
+    /// Items are counted as `crate::path` and also count towards their
+    /// crate. Names are lowercased so spellings of one crate add up.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self), err, level = "trace")]
+    pub async fn record_lookup(
+        &self,
+        crate_name: &str,
+        item_path: Option<&str>,
+    ) -> crate::error::Result<()> {
+        let crate_name = crate_name.trim().to_lowercase();
+        self.cache
+            .increment_counter(
+                &CacheKeyGenerator::lookup_counter_key("crates"),
+                &crate_name,
+            )
+            .await?;
+        if let Some(item_path) = item_path {
+            let root = crate_name.replace('-', "_");
+            let item_path = item_path.trim();
+            let item = match item_path.strip_prefix(&format!("{root}::")) {
+                Some(rest) => format!("{root}::{rest}"),
+                None => format!("{root}::{item_path}"),
+            };
+            self.cache
+                .increment_counter(&CacheKeyGenerator::lookup_counter_key("items"), &item)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Most looked up crates and items, each highest count first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    pub async fn top_lookups(&self, limit: usize) -> crate::error::Result<TopLookups> {
+        Ok(TopLookups {
+            crates: self
+                .cache
+                .top_counters(&CacheKeyGenerator::lookup_counter_key("crates"), limit)
+                .await?,
+            items: self
+                .cache
+                .top_counters(&CacheKeyGenerator::lookup_counter_key("items"), limit)
+                .await?,
+        })
+    }
+
+    /// Get cached dependency tree
+    #[tracing::instrument(skip(self), fields(crate_name, version), level = "trace")]
+    pub async fn get_dependency_tree(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        options: &str,
+    ) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::dependency_tree_cache_key(crate_name, version, options);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for dependency tree");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for dependency tree");
+        }
+        result
+    }
+
+    /// Set dependency tree cache
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns document content if cache hit; otherwise returns `None`
-    #[tracing::instrument(skip(self), fields(crate = crate_name, version = version), level = "trace")]
-    pub async fn get_crate_docs(
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name, version), err, level = "trace")]
+    pub async fn set_dependency_tree(
         &self,
         crate_name: &str,
         version: Option<&str>,
-    ) -> Option<Arc<str>> {
-        let key = CacheKeyGenerator::crate_cache_key(crate_name, version);
-        let result = self.cache.get(&key).await;
-        let is_hit = result.is_some();
-        if is_hit {
+        options: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::dependency_tree_cache_key(crate_name, version, options);
+        let ttl = self.ttl.crate_docs_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Dependency tree cached");
+        Ok(())
+    }
+
+    /// Get cached crates.io crate summary
+    #[tracing::instrument(skip(self), fields(crate_name), level = "trace")]
+    pub async fn get_crate_summary(&self, crate_name: &str) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::crate_summary_cache_key(crate_name);
+        let result = self.read(&key).await;
+        if result.is_some() {
             self.stats.record_hit();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get",
-                hit = true,
-                crate = crate_name
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache hit for crate docs");
-            });
+            tracing::trace!("Cache hit for crate summary");
         } else {
             self.stats.record_miss();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get",
-                hit = false,
-                crate = crate_name
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache miss for crate docs");
-            });
+            tracing::trace!("Cache miss for crate summary");
         }
         result
     }
 
-    /// Set crate document cache
+    /// Set crates.io crate summary cache
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `crate_name` - crate name
-    /// * `version` - Optional version
-    /// * `content` - Document content
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name), err, level = "trace")]
+    pub async fn set_crate_summary(
+        &self,
+        crate_name: &str,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::crate_summary_cache_key(crate_name);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate summary cached");
+        Ok(())
+    }
+
+    /// Get the cached crates.io summaries of several crates with one cache
+    /// lookup, in the order of `crate_names`
+    #[tracing::instrument(skip(self), level = "trace")]
+    pub async fn get_crate_summaries(&self, crate_names: &[String]) -> Vec<Option<Arc<str>>> {
+        let keys: Vec<String> = crate_names
+            .iter()
+            .map(|crate_name| CacheKeyGenerator::crate_summary_cache_key(crate_name))
+            .collect();
+        let mut values = self.cache.get_many(&keys).await;
+        for (value, key) in values.iter_mut().zip(&keys) {
+            if !readable(key) {
+                *value = None;
+            }
+        }
+        for value in &values {
+            if value.is_some() {
+                self.stats.record_hit();
+            } else {
+                self.stats.record_miss();
+            }
+        }
+        values
+    }
+
+    /// Cache the crates.io summaries of several crates, given as
+    /// `(crate name, summary)` pairs, with one cache write
     ///
     /// # Errors
     ///
     /// Returns error if cache operation fails
-    #[tracing::instrument(skip(self, content), fields(crate = crate_name, version = version), err, level = "trace")]
-    pub async fn set_crate_docs(
+    #[tracing::instrument(skip(self, summaries), err, level = "trace")]
+    pub async fn set_crate_summaries(
+        &self,
+        summaries: Vec<(String, Arc<str>)>,
+    ) -> crate::error::Result<()> {
+        let count = summaries.len();
+        let entries: Vec<(String, Arc<str>)> = summaries
+            .into_iter()
+            .map(|(crate_name, summary)| {
+                (
+                    CacheKeyGenerator::crate_summary_cache_key(&crate_name),
+                    summary,
+                )
+            })
+            .collect();
+        let ttl = self.ttl.search_results_duration();
+        let keys: Vec<String> = entries.iter().map(|(key, _)| key.clone()).collect();
+        self.cache.set_many(entries, Some(ttl)).await?;
+        for key in &keys {
+            record_written(key);
+        }
+        for _ in 0..count {
+            self.stats.record_set();
+        }
+        tracing::trace!(ttl_secs = ttl.as_secs(), count, "Crate summaries cached");
+        Ok(())
+    }
+
+    /// Get cached crate alternatives
+    #[tracing::instrument(skip(self), fields(crate_name, limit), level = "trace")]
+    pub async fn get_alternatives(&self, crate_name: &str, limit: u32) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::alternatives_cache_key(crate_name, limit);
+        let result = self.read(&key).await;
+        if result.is_some() {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for crate alternatives");
+        } else {
+            self.stats.record_miss();
+            tracing::trace!("Cache miss for crate alternatives");
+        }
+        result
+    }
+
+    /// Set crate alternatives cache
+    ///
+    /// Shares the search results TTL, since the ranking follows download
+    /// counts.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name, limit), err, level = "trace")]
+    pub async fn set_alternatives(
         &self,
         crate_name: &str,
-        version: Option<&str>,
-        content: String,
+        limit: u32,
+        content: impl Into<Arc<str>>,
     ) -> crate::error::Result<()> {
-        let key = CacheKeyGenerator::crate_cache_key(crate_name, version);
-        let ttl = self.ttl.crate_docs_duration();
-        self.cache.set(key, content, Some(ttl)).await?;
+        let key = CacheKeyGenerator::alternatives_cache_key(crate_name, limit);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
         self.stats.record_set();
-        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate docs cached");
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate alternatives cached");
         Ok(())
     }
 
-    /// Get cached crate HTML
-    ///
-    /// Returns `Arc<str>` to avoid unnecessary cloning on cache hits.
-    /// The caller can clone if an owned String is needed.
-    #[tracing::instrument(skip(self), fields(crate = crate_name, version = version), level = "trace")]
-    pub async fn get_crate_html(
+    /// Get cached security advisories
+    #[tracing::instrument(skip(self), fields(crate_name, version), level = "trace")]
+    pub async fn get_advisories(
         &self,
         crate_name: &str,
         version: Option<&str>,
     ) -> Option<Arc<str>> {
-        let key = CacheKeyGenerator::crate_html_cache_key(crate_name, version);
-        let result = self.cache.get(&key).await;
-        let is_hit = result.is_some();
-        if is_hit {
+        let key = CacheKeyGenerator::advisories_cache_key(crate_name, version);
+        let result = self.read(&key).await;
+        if result.is_some() {
             self.stats.record_hit();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get_html",
-                hit = true,
-                crate = crate_name
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache hit for crate HTML");
-            });
+            tracing::trace!("Cache hit for advisories");
         } else {
             self.stats.record_miss();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get_html",
-                hit = false,
-                crate = crate_name
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache miss for crate HTML");
-            });
+            tracing::trace!("Cache miss for advisories");
         }
         result
     }
 
-    /// Set crate HTML cache
+    /// Set security advisories cache
+    ///
+    /// Shares the search results TTL so new advisories show up quickly.
     ///
     /// # Errors
     ///
     /// Returns error if cache operation fails
-    #[tracing::instrument(skip(self, content), fields(crate = crate_name, version = version), err, level = "trace")]
-    pub async fn set_crate_html(
+    #[tracing::instrument(skip(self, content), fields(crate_name, version), err, level = "trace")]
+    pub async fn set_advisories(
         &self,
         crate_name: &str,
         version: Option<&str>,
-        content: String,
+        content: impl Into<Arc<str>>,
     ) -> crate::error::Result<()> {
-        let key = CacheKeyGenerator::crate_html_cache_key(crate_name, version);
-        let ttl = self.ttl.crate_docs_duration();
-        self.cache.set(key, content, Some(ttl)).await?;
+        let key = CacheKeyGenerator::advisories_cache_key(crate_name, version);
+        let ttl = self.ttl.search_results_duration();
+        self.write(key, content.into(), Some(ttl)).await?;
         self.stats.record_set();
-        tracing::trace!(ttl_secs = ttl.as_secs(), "Crate HTML cached");
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Advisories cached");
         Ok(())
     }
 
-    /// Get cached search results
-    ///
-    /// # Arguments
-    ///
-    /// * `query` - Search query
-    /// * `limit` - Result count limit
-    /// * `sort` - Optional search sort order
-    ///
-    /// # Returns
-    ///
-    /// Returns search results if cache hit;otherwise returns `None`
-    #[tracing::instrument(skip(self), fields(query, limit, sort), level = "trace")]
-    pub async fn get_search_results(
-        &self,
-        query: &str,
-        limit: u32,
-        sort: Option<&str>,
-    ) -> Option<Arc<str>> {
-        let key = CacheKeyGenerator::search_cache_key(query, limit, sort);
-        let result = self.cache.get(&key).await;
-        let is_hit = result.is_some();
-        if is_hit {
+    /// Get a cached repository README
+    #[tracing::instrument(skip(self), fields(crate_name, version), level = "trace")]
+    pub async fn get_readme(&self, crate_name: &str, version: Option<&str>) -> Option<Arc<str>> {
+        let key = CacheKeyGenerator::readme_cache_key(crate_name, version);
+        let result = self.read(&key).await;
+        if result.is_some() {
             self.stats.record_hit();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get_search",
-                hit = true
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache hit for search results");
-            });
+            tracing::trace!("Cache hit for README");
         } else {
             self.stats.record_miss();
-            tracing::span!(
-                tracing::Level::TRACE,
-                "cache",
-                op = "get_search",
-                hit = false
-            )
-            .in_scope(|| {
-                tracing::trace!("Cache miss for search results");
-            });
+            tracing::trace!("Cache miss for README");
         }
         result
     }
 
-    /// Set search results cache
+    /// Set repository README cache
     ///
-    /// # Arguments
+    /// Shares the crate documentation TTL, as the README stands in for it.
     ///
-    /// * `query` - Search query
-    /// * `limit` - Result count limit
-    /// * `sort` - Optional search sort order
-    /// * `content` - search result content
+    /// # Errors
+    ///
+    /// Returns error if cache operation fails
+    #[tracing::instrument(skip(self, content), fields(crate_name, version), err, level = "trace")]
+    pub async fn set_readme(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        content: impl Into<Arc<str>>,
+    ) -> crate::error::Result<()> {
+        let key = CacheKeyGenerator::readme_cache_key(crate_name, version);
+        let ttl = self.ttl.crate_docs_duration_for(version);
+        self.write(key, content.into(), Some(ttl)).await?;
+        self.stats.record_set();
+        tracing::trace!(ttl_secs = ttl.as_secs(), "README cached");
+        Ok(())
+    }
+
+    /// Whether `crate_name` was recently found missing from every provider
+    #[tracing::instrument(skip(self), fields(crate_name, version), level = "trace")]
+    pub async fn is_crate_missing(&self, crate_name: &str, version: Option<&str>) -> bool {
+        let key = CacheKeyGenerator::missing_crate_cache_key(crate_name, version);
+        let missing = readable(&key) && self.cache.exists(&key).await;
+        if missing {
+            self.stats.record_hit();
+            tracing::trace!("Cache hit for missing crate");
+        }
+        missing
+    }
+
+    /// Remember that `crate_name` is missing from every provider
+    ///
+    /// Uses the short "crate not found" TTL, and does nothing when that is
+    /// disabled.
     ///
     /// # Errors
     ///
     /// Returns error if cache operation fails
-    #[tracing::instrument(skip(self, content), fields(query, limit, sort), err, level = "trace")]
-    pub async fn set_search_results(
+    #[tracing::instrument(skip(self), fields(crate_name, version), err, level = "trace")]
+    pub async fn set_crate_missing(
         &self,
-        query: &str,
-        limit: u32,
-        sort: Option<&str>,
-        content: String,
+        crate_name: &str,
+        version: Option<&str>,
     ) -> crate::error::Result<()> {
-        let key = CacheKeyGenerator::search_cache_key(query, limit, sort);
-        let ttl = self.ttl.search_results_duration();
-        self.cache.set(key, content, Some(ttl)).await?;
+        let Some(ttl) = self.ttl.missing_crate_duration() else {
+            return Ok(());
+        };
+        let key = CacheKeyGenerator::missing_crate_cache_key(crate_name, version);
+        self.write(key, Arc::from(""), Some(ttl)).await?;
         self.stats.record_set();
-        tracing::trace!(ttl_secs = ttl.as_secs(), "Search results cached");
+        tracing::trace!(ttl_secs = ttl.as_secs(), "Missing crate cached");
         Ok(())
     }
 
@@ -332,7 +1347,7 @@ impl DocCache {
         version: Option<&str>,
     ) -> Option<Arc<str>> {
         let key = CacheKeyGenerator::item_cache_key(crate_name, item_path, version);
-        let result = self.cache.get(&key).await;
+        let result = self.read(&key).await;
         let is_hit = result.is_some();
         if is_hit {
             self.stats.record_hit();
@@ -370,11 +1385,11 @@ impl DocCache {
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
-        content: String,
+        content: impl Into<Arc<str>>,
     ) -> crate::error::Result<()> {
         let key = CacheKeyGenerator::item_cache_key(crate_name, item_path, version);
-        let ttl = self.ttl.item_docs_duration();
-        self.cache.set(key, content, Some(ttl)).await?;
+        let ttl = self.ttl.item_docs_duration_for(version);
+        self.write(key, content.into(), Some(ttl)).await?;
         self.stats.record_set();
         tracing::trace!(ttl_secs = ttl.as_secs(), "Item docs cached");
         Ok(())
@@ -392,7 +1407,7 @@ impl DocCache {
         version: Option<&str>,
     ) -> Option<Arc<str>> {
         let key = CacheKeyGenerator::item_html_cache_key(crate_name, item_path, version);
-        let result = self.cache.get(&key).await;
+        let result = self.read(&key).await;
         let is_hit = result.is_some();
         if is_hit {
             self.stats.record_hit();
@@ -431,11 +1446,11 @@ impl DocCache {
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
-        content: String,
+        content: impl Into<Arc<str>>,
     ) -> crate::error::Result<()> {
         let key = CacheKeyGenerator::item_html_cache_key(crate_name, item_path, version);
-        let ttl = self.ttl.item_docs_duration();
-        self.cache.set(key, content, Some(ttl)).await?;
+        let ttl = self.ttl.item_docs_duration_for(version);
+        self.write(key, content.into(), Some(ttl)).await?;
         self.stats.record_set();
         tracing::trace!(ttl_secs = ttl.as_secs(), "Item HTML cached");
         Ok(())
@@ -465,6 +1480,15 @@ impl DocCache {
     }
 }
 
+/// Lookup counts recorded by [`DocCache::record_lookup`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct TopLookups {
+    /// `(crate, lookups)`, highest first
+    pub crates: Vec<(String, u64)>,
+    /// `(crate::item, lookups)`, highest first
+    pub items: Vec<(String, u64)>,
+}
+
 impl Default for DocCache {
     fn default() -> Self {
         let cache = Arc::new(crate::cache::memory::MemoryCache::new(1000));
@@ -477,6 +1501,72 @@ mod tests {
     use super::*;
     use crate::cache::memory::MemoryCache;
 
+    #[tokio::test]
+    async fn test_doc_cache_no_cache_reads_only_own_writes() {
+        let doc_cache = DocCache::new(Arc::new(MemoryCache::new(100)));
+        doc_cache
+            .set_crate_docs("serde", None, "old")
+            .await
+            .unwrap();
+        doc_cache
+            .set_crate_docs("tokio", None, "old")
+            .await
+            .unwrap();
+
+        with_no_cache(true, async {
+            assert_eq!(doc_cache.get_crate_docs("serde", None).await, None);
+            doc_cache
+                .set_crate_docs("serde", None, "fresh")
+                .await
+                .unwrap();
+            assert_eq!(
+                doc_cache.get_crate_docs("serde", None).await.as_deref(),
+                Some("fresh")
+            );
+            assert_eq!(doc_cache.get_crate_docs("tokio", None).await, None);
+        })
+        .await;
+
+        with_no_cache(false, async {
+            assert_eq!(
+                doc_cache.get_crate_docs("tokio", None).await.as_deref(),
+                Some("old")
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_cached_crate_docs_lists_stored_versions() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(100));
+        let doc_cache = DocCache::new(Arc::clone(&cache));
+        doc_cache.set_crate_docs("Serde", None, "a").await.unwrap();
+        doc_cache
+            .set_crate_docs("serde", Some("latest"), "b")
+            .await
+            .unwrap();
+        doc_cache
+            .set_crate_docs("tokio", Some("1.40.0"), "c")
+            .await
+            .unwrap();
+
+        let cached = doc_cache.cached_crate_docs(10).await.unwrap();
+        assert_eq!(
+            cached,
+            vec![
+                ("serde".to_string(), None),
+                ("tokio".to_string(), Some("1.40.0".to_string())),
+            ]
+        );
+
+        // Expired entries are left out
+        cache
+            .delete(&CacheKeyGenerator::crate_cache_key("tokio", Some("1.40.0")))
+            .await
+            .unwrap();
+        assert_eq!(doc_cache.cached_crate_docs(10).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_doc_cache() {
         let memory_cache = MemoryCache::new(100);
@@ -577,6 +1667,52 @@ mod tests {
         assert_eq!(doc_cache.ttl().item_docs_secs, 3600);
     }
 
+    #[tokio::test]
+    async fn test_doc_cache_stale_while_revalidate() {
+        let memory_cache = Arc::new(MemoryCache::new(100));
+        let mut ttl = DocCacheTtl::default();
+        ttl.stale_secs = Some(600);
+        let doc_cache = DocCache::with_ttl(memory_cache.clone(), ttl);
+
+        doc_cache
+            .set_crate_html("serde", None, "<html>")
+            .await
+            .unwrap();
+        assert!(!doc_cache.is_crate_html_stale("serde", None).await);
+
+        // Past its TTL the entry is still served, but reported stale
+        let key = CacheKeyGenerator::crate_html_cache_key("serde", None);
+        memory_cache
+            .delete(&CacheKeyGenerator::fresh_marker_key(&key))
+            .await
+            .unwrap();
+        assert!(doc_cache.is_crate_html_stale("serde", None).await);
+        assert!(doc_cache.get_crate_html("serde", None).await.is_some());
+
+        // Only one refresh at a time
+        assert!(doc_cache.try_claim_refresh(&key, "a").await);
+        assert!(!doc_cache.try_claim_refresh(&key, "b").await);
+        doc_cache.release_refresh(&key, "a").await;
+        assert!(doc_cache.try_claim_refresh(&key, "b").await);
+    }
+
+    #[tokio::test]
+    async fn test_doc_cache_never_stale_without_window() {
+        let doc_cache = DocCache::new(Arc::new(MemoryCache::new(100)));
+        doc_cache
+            .set_crate_docs("serde", None, "docs")
+            .await
+            .unwrap();
+        let key = CacheKeyGenerator::crate_cache_key("serde", None);
+        assert!(
+            !doc_cache
+                .cache
+                .exists(&CacheKeyGenerator::fresh_marker_key(&key))
+                .await
+        );
+        assert!(!doc_cache.is_crate_docs_stale("serde", None).await);
+    }
+
     #[tokio::test]
     async fn test_doc_cache_stats() {
         let memory_cache = MemoryCache::new(100);
@@ -598,6 +1734,39 @@ mod tests {
         assert_eq!(doc_cache.stats().sets(), 1);
     }
 
+    #[tokio::test]
+    async fn test_doc_cache_top_lookups() {
+        let doc_cache = DocCache::new(Arc::new(MemoryCache::new(100)));
+
+        doc_cache.record_lookup("Serde", None).await.unwrap();
+        doc_cache
+            .record_lookup("serde", Some("serde::Serialize"))
+            .await
+            .unwrap();
+        doc_cache
+            .record_lookup("serde", Some("Serialize"))
+            .await
+            .unwrap();
+        doc_cache
+            .record_lookup("serde-json", Some("Value"))
+            .await
+            .unwrap();
+
+        let top = doc_cache.top_lookups(10).await.unwrap();
+        assert_eq!(
+            top.crates,
+            [("serde".to_string(), 3), ("serde-json".to_string(), 1)]
+        );
+        assert_eq!(
+            top.items,
+            [
+                ("serde::Serialize".to_string(), 2),
+                ("serde_json::Value".to_string(), 1)
+            ]
+        );
+        assert_eq!(doc_cache.top_lookups(1).await.unwrap().items.len(), 1);
+    }
+
     #[test]
     fn test_doc_cache_default() {
         let doc_cache = DocCache::default();
@@ -12,7 +12,7 @@ use std::time::Duration;
 ///
 /// A 10% jitter helps prevent cache stampede when multiple requests expire simultaneously.
 /// This spreads the load over time while maintaining reasonable cache consistency.
-/// Configurable via `DocCacheTtl::jitter_ratio` field.
+/// Configurable via `CacheConfig::ttl_jitter_ratio`.
 const DEFAULT_JITTER_RATIO: f64 = 0.1;
 
 /// Minimum valid jitter ratio
@@ -64,6 +64,20 @@ const DEFAULT_SEARCH_RESULTS_TTL_SECS: u64 = 300;
 /// Configurable via `CacheConfig::item_docs_ttl_secs`.
 const DEFAULT_ITEM_DOCS_TTL_SECS: u64 = 1800;
 
+/// Default TTL in seconds of a "crate not found" result
+///
+/// # Value
+///
+/// 60 seconds (1 minute)
+///
+/// # Rationale
+///
+/// Agents often retry a lookup of a misspelled or nonexistent crate several
+/// times in a row; a short TTL answers the retries without asking docs.rs
+/// again, yet lets a newly published crate show up within a minute.
+/// Configurable via `CacheConfig::missing_crate_ttl_secs`.
+const DEFAULT_MISSING_CRATE_TTL_SECS: u64 = 60;
+
 /// Document cache TTL configuration
 ///
 /// Configure independent TTL for different document types.
@@ -73,6 +87,9 @@ const DEFAULT_ITEM_DOCS_TTL_SECS: u64 = 1800;
 /// - `crate_docs_secs`: Crate document cache duration (seconds)
 /// - `search_results_secs`: search results cache duration (seconds)
 /// - `item_docs_secs`: item docs cache duration (seconds)
+/// - `versioned_docs_secs`: cache duration of docs for an exact version (seconds)
+/// - `missing_crate_secs`: cache duration of "crate not found" results (seconds)
+/// - `stale_secs`: how long an expired crate page is still served while it is refreshed (seconds)
 /// - `jitter_ratio`: TTL jitter ratio(0.0-1.0),used to prevent cache stampede
 #[derive(Debug, Clone, Copy)]
 pub struct DocCacheTtl {
@@ -82,6 +99,18 @@ pub struct DocCacheTtl {
     pub search_results_secs: u64,
     /// Item documentation TTL (seconds)
     pub item_docs_secs: u64,
+    /// TTL of crate and item docs for an exact version such as `1.0.0`
+    /// (seconds); `None` uses `crate_docs_secs`/`item_docs_secs`
+    ///
+    /// A published version never changes, so these entries can live far
+    /// longer than `latest` or version-requirement lookups.
+    pub versioned_docs_secs: Option<u64>,
+    /// TTL of "crate not found" results (seconds); `0` disables caching them
+    pub missing_crate_secs: u64,
+    /// How long an expired crate page is still served, while a background
+    /// task refreshes it (seconds); `None` or `0` disables
+    /// stale-while-revalidate
+    pub stale_secs: Option<u64>,
     /// TTL jitter ratio (0.0-1.0), default 0.1 (10%)
     ///
     /// Actual TTL = `base_ttl * (1 + random(-jitter_ratio, jitter_ratio))`
@@ -97,6 +126,9 @@ impl Default for DocCacheTtl {
             crate_docs_secs: DEFAULT_CRATE_DOCS_TTL_SECS,
             search_results_secs: DEFAULT_SEARCH_RESULTS_TTL_SECS,
             item_docs_secs: DEFAULT_ITEM_DOCS_TTL_SECS,
+            versioned_docs_secs: None,
+            missing_crate_secs: DEFAULT_MISSING_CRATE_TTL_SECS,
+            stale_secs: None,
             jitter_ratio: DEFAULT_JITTER_RATIO,
         }
     }
@@ -124,7 +156,14 @@ impl DocCacheTtl {
             item_docs_secs: config
                 .item_docs_ttl_secs
                 .unwrap_or(DEFAULT_ITEM_DOCS_TTL_SECS),
-            jitter_ratio: DEFAULT_JITTER_RATIO,
+            versioned_docs_secs: config.versioned_docs_ttl_secs,
+            missing_crate_secs: config
+                .missing_crate_ttl_secs
+                .unwrap_or(DEFAULT_MISSING_CRATE_TTL_SECS),
+            stale_secs: config.stale_while_revalidate_secs,
+            jitter_ratio: config
+                .ttl_jitter_ratio
+                .map_or(DEFAULT_JITTER_RATIO, Self::validate_jitter_ratio),
         }
     }
 
@@ -151,6 +190,9 @@ impl DocCacheTtl {
             crate_docs_secs,
             search_results_secs,
             item_docs_secs,
+            versioned_docs_secs: None,
+            missing_crate_secs: DEFAULT_MISSING_CRATE_TTL_SECS,
+            stale_secs: None,
             jitter_ratio: Self::validate_jitter_ratio(jitter_ratio),
         }
     }
@@ -254,6 +296,52 @@ impl DocCacheTtl {
     pub fn item_docs_duration(&self) -> Duration {
         Duration::from_secs(self.apply_jitter(self.item_docs_secs))
     }
+
+    /// Get TTL duration for "crate not found" results with jitter applied,
+    /// `None` if they are not cached
+    #[must_use]
+    pub fn missing_crate_duration(&self) -> Option<Duration> {
+        (self.missing_crate_secs > 0)
+            .then(|| Duration::from_secs(self.apply_jitter(self.missing_crate_secs)))
+    }
+
+    /// Get the stale-while-revalidate window, `None` if disabled
+    #[must_use]
+    pub fn stale_duration(&self) -> Option<Duration> {
+        self.stale_secs
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// Get TTL duration for crate docs of `version` with jitter applied
+    ///
+    /// Uses `versioned_docs_secs` for an exact version, `crate_docs_secs`
+    /// otherwise.
+    #[must_use]
+    pub fn crate_docs_duration_for(&self, version: Option<&str>) -> Duration {
+        self.versioned_duration(version)
+            .unwrap_or_else(|| self.crate_docs_duration())
+    }
+
+    /// Get TTL duration for item docs of `version` with jitter applied
+    ///
+    /// Uses `versioned_docs_secs` for an exact version, `item_docs_secs`
+    /// otherwise.
+    #[must_use]
+    pub fn item_docs_duration_for(&self, version: Option<&str>) -> Duration {
+        self.versioned_duration(version)
+            .unwrap_or_else(|| self.item_docs_duration())
+    }
+
+    /// `versioned_docs_secs` with jitter, if set and `version` is exact
+    ///
+    /// `latest` and requirements such as `^1.2` resolve to different
+    /// releases over time, so only a full `major.minor.patch` qualifies.
+    fn versioned_duration(&self, version: Option<&str>) -> Option<Duration> {
+        let secs = self.versioned_docs_secs?;
+        semver::Version::parse(version?.trim()).ok()?;
+        Some(Duration::from_secs(self.apply_jitter(secs)))
+    }
 }
 
 #[cfg(test)]
@@ -274,17 +362,87 @@ mod tests {
         let config = crate::cache::CacheConfig {
             cache_type: "memory".to_string(),
             memory_size: Some(1000),
+            memory_max_bytes: None,
             redis_url: None,
+            redis_l1_ttl_secs: None,
+            redis_key_prefix: None,
+            sqlite_path: None,
+            sqlite_max_size_mb: None,
             key_prefix: String::new(),
             default_ttl: Some(DEFAULT_CRATE_DOCS_TTL_SECS),
             crate_docs_ttl_secs: Some(7200),
             item_docs_ttl_secs: Some(DEFAULT_CRATE_DOCS_TTL_SECS),
             search_results_ttl_secs: Some(600),
+            versioned_docs_ttl_secs: None,
+            missing_crate_ttl_secs: Some(30),
+            stale_while_revalidate_secs: Some(600),
+            ttl_jitter_ratio: None,
+            warmup: crate::cache::CacheWarmupConfig::default(),
         };
         let ttl = DocCacheTtl::from_cache_config(&config);
         assert_eq!(ttl.crate_docs_secs, 7200);
         assert_eq!(ttl.item_docs_secs, DEFAULT_CRATE_DOCS_TTL_SECS);
         assert_eq!(ttl.search_results_secs, 600);
+        assert_eq!(ttl.versioned_docs_secs, None);
+        assert_eq!(ttl.missing_crate_secs, 30);
+        assert_eq!(ttl.stale_duration(), Some(Duration::from_mins(10)));
+    }
+
+    #[test]
+    fn test_doc_cache_ttl_jitter_from_config() {
+        let mut config = crate::cache::CacheConfig::default();
+        let ttl = DocCacheTtl::from_cache_config(&config);
+        assert!((ttl.jitter_ratio() - DEFAULT_JITTER_RATIO).abs() < f64::EPSILON);
+
+        config.ttl_jitter_ratio = Some(0.0);
+        let ttl = DocCacheTtl::from_cache_config(&config);
+        assert_eq!(ttl.crate_docs_duration(), Duration::from_hours(1));
+
+        config.ttl_jitter_ratio = Some(0.5);
+        let ttl = DocCacheTtl::from_cache_config(&config);
+        assert!((ttl.jitter_ratio() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_missing_crate_duration() {
+        let mut ttl = DocCacheTtl::default();
+        ttl.set_jitter_ratio(0.0);
+        assert_eq!(
+            ttl.missing_crate_duration(),
+            Some(Duration::from_secs(DEFAULT_MISSING_CRATE_TTL_SECS))
+        );
+
+        ttl.missing_crate_secs = 0;
+        assert_eq!(ttl.missing_crate_duration(), None);
+    }
+
+    #[test]
+    fn test_versioned_docs_duration() {
+        let mut ttl = DocCacheTtl::default();
+        ttl.set_jitter_ratio(0.0);
+        ttl.versioned_docs_secs = Some(86_400 * 365);
+
+        let pinned = Duration::from_hours(24 * 365);
+        assert_eq!(ttl.crate_docs_duration_for(Some("1.0.0")), pinned);
+        assert_eq!(ttl.item_docs_duration_for(Some("1.0.0-rc.1")), pinned);
+
+        // Lookups that can resolve to a newer release keep the regular TTLs
+        for version in [None, Some("latest"), Some("^1.0"), Some("1")] {
+            assert_eq!(
+                ttl.crate_docs_duration_for(version),
+                Duration::from_secs(DEFAULT_CRATE_DOCS_TTL_SECS)
+            );
+            assert_eq!(
+                ttl.item_docs_duration_for(version),
+                Duration::from_secs(DEFAULT_ITEM_DOCS_TTL_SECS)
+            );
+        }
+
+        ttl.versioned_docs_secs = None;
+        assert_eq!(
+            ttl.crate_docs_duration_for(Some("1.0.0")),
+            Duration::from_secs(DEFAULT_CRATE_DOCS_TTL_SECS)
+        );
     }
 
     #[test]
@@ -0,0 +1,81 @@
+//! HTTP cache validators
+//!
+//! An upstream page is cached together with the `ETag` and `Last-Modified`
+//! headers of its response. Revalidating it then sends them back as
+//! `If-None-Match` and `If-Modified-Since`, and an unchanged docs.rs page
+//! costs a `304 Not Modified` instead of a full download.
+
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+/// `ETag` and `Last-Modified` of an upstream response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    /// URL the response was fetched from
+    pub url: String,
+    /// `ETag` header of the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// `Last-Modified` header of the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Validators of a response from `url`, `None` if it has neither header
+    #[must_use]
+    pub fn from_headers(url: &str, headers: &HeaderMap) -> Option<Self> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        (etag.is_some() || last_modified.is_some()).then(|| Self {
+            url: url.to_string(),
+            etag,
+            last_modified,
+        })
+    }
+
+    /// Make `request` conditional on the page having changed
+    pub fn apply(
+        &self,
+        mut request: reqwest_middleware::RequestBuilder,
+    ) -> reqwest_middleware::RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            Validators::from_headers("https://docs.rs/serde/", &headers),
+            None
+        );
+
+        headers.insert(ETAG, "\"v1\"".parse().unwrap());
+        let validators = Validators::from_headers("https://docs.rs/serde/", &headers).unwrap();
+        assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(validators.last_modified, None);
+
+        let json = serde_json::to_string(&validators).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Validators>(&json).unwrap(),
+            validators
+        );
+    }
+}
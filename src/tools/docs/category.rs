@@ -0,0 +1,387 @@
+//! List crates by category tool
+//!
+//! Lists the top crates in a crates.io category (e.g. `asynchronous`,
+//! `web-programming::http-server`) so callers can survey the ecosystem for a
+//! problem domain rather than guessing search keywords.
+
+#![allow(missing_docs)]
+
+use super::search::{self, CrateInfo, SearchCratesResponse};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "list_crates_by_category";
+
+const DEFAULT_CATEGORY_LIMIT: u32 = 10;
+
+/// Longest category slug accepted (crates.io slugs are far shorter)
+const MAX_CATEGORY_LEN: usize = 128;
+
+const DEFAULT_CATEGORY_SORT: &str = "downloads";
+const VALID_CATEGORY_SORTS: &[&str] = &[
+    DEFAULT_CATEGORY_SORT,
+    "recent-downloads",
+    "recent-updates",
+    "new",
+    "alpha",
+];
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "list_crates_by_category",
+    title = "List Crates by Category",
+    description = "List the top crates in a crates.io category (e.g. asynchronous, web-programming, web-programming::http-server, command-line-utilities), with sorting options. Use this to discover the established crates for a problem domain.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `list_crates_by_category` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct ListCratesByCategoryTool {
+    /// crates.io category slug (e.g., `asynchronous`, `web-programming::http-server`)
+    #[json_schema(
+        title = "Category",
+        description = "crates.io category slug, e.g.: asynchronous, web-programming, web-programming::http-server, cryptography, parsing"
+    )]
+    pub category: String,
+
+    /// Maximum number of crates to return (range 1-100, defaults to 10)
+    #[json_schema(
+        title = "Result Limit",
+        description = "Maximum number of crates to return, range 1-100",
+        minimum = 1,
+        maximum = 100,
+        default = 10
+    )]
+    pub limit: Option<u32>,
+
+    /// Sort order: "downloads", "recent-downloads", "recent-updates", "new", "alpha"
+    #[json_schema(
+        title = "Sort Order",
+        description = "Sort order: downloads (default), recent-downloads, recent-updates, new, alpha",
+        default = "downloads"
+    )]
+    pub sort: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (category metadata and crate list)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Response of the crates.io `categories/{slug}` endpoint
+#[derive(Debug, Deserialize)]
+struct CategoryResponse {
+    category: CategoryRecord,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoryRecord {
+    slug: String,
+    category: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    crates_cnt: Option<u64>,
+    #[serde(default)]
+    subcategories: Vec<SubcategoryRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubcategoryRecord {
+    slug: String,
+}
+
+/// Category metadata and its top crates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryListing {
+    /// Category slug
+    pub slug: String,
+    /// Display name (e.g. "Asynchronous")
+    pub name: String,
+    /// Category description
+    pub description: Option<String>,
+    /// Total number of crates in the category
+    pub total_crates: Option<u64>,
+    /// Slugs of the direct subcategories
+    pub subcategories: Vec<String>,
+    /// Sort order applied to `crates`
+    pub sort: String,
+    /// Top crates, in `sort` order
+    crates: Vec<CrateInfo>,
+}
+
+/// Implementation of the list crates by category tool
+pub struct ListCratesByCategoryToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+pub(super) fn normalize_category(
+    tool_name: &str,
+    raw: &str,
+) -> std::result::Result<String, CallToolError> {
+    let category = raw.trim().to_lowercase();
+    let valid_chars = category
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b':'));
+    let valid_separators = !category.replace("::", "").contains(':')
+        && !category.starts_with(':')
+        && !category.ends_with(':');
+    if category.is_empty() || category.len() > MAX_CATEGORY_LEN || !valid_chars || !valid_separators
+    {
+        return Err(CallToolError::invalid_arguments(
+            tool_name,
+            Some(format!(
+                "Invalid category '{raw}'. Use a crates.io category slug such as 'asynchronous' or 'web-programming::http-server'"
+            )),
+        ));
+    }
+    Ok(category)
+}
+
+fn normalize_category_sort(sort: Option<&str>) -> std::result::Result<String, CallToolError> {
+    let Some(raw) = sort else {
+        return Ok(DEFAULT_CATEGORY_SORT.to_string());
+    };
+    let normalized = raw.trim().to_lowercase();
+    if VALID_CATEGORY_SORTS.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(CallToolError::invalid_arguments(
+            TOOL_NAME,
+            Some(format!(
+                "Invalid sort option '{raw}', expected one of: {}",
+                VALID_CATEGORY_SORTS.join(", ")
+            )),
+        ))
+    }
+}
+
+impl ListCratesByCategoryToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn list_category(
+        &self,
+        category: &str,
+        limit: u32,
+        sort: &str,
+    ) -> std::result::Result<CategoryListing, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache.get_category_results(category, limit, sort).await {
+            return serde_json::from_str(&cached).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Cache parsing failed: {e}"))
+            });
+        }
+
+        // crates.io silently returns an empty crate list for unknown
+        // categories, so resolve the category first to report a typo as such.
+        let category_url = super::build_crates_io_category_url(category);
+        let Some(body) = self
+            .service
+            .fetch_html_optional(&category_url, Some(TOOL_NAME))
+            .await?
+        else {
+            return Err(crate::error::Error::not_found("category", category, Vec::new()).into());
+        };
+        let record = serde_json::from_str::<CategoryResponse>(&body)
+            .map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+            })?
+            .category;
+
+        let crates_url =
+            super::build_crates_io_category_crates_url(&record.slug, sort, limit as usize);
+        let body = self
+            .service
+            .fetch_html(&crates_url, Some(TOOL_NAME))
+            .await?;
+        let response: SearchCratesResponse = serde_json::from_str(&body).map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+        })?;
+
+        let listing = CategoryListing {
+            slug: record.slug,
+            name: record.category,
+            description: record.description,
+            total_crates: record.crates_cnt,
+            subcategories: record.subcategories.into_iter().map(|s| s.slug).collect(),
+            sort: sort.to_string(),
+            crates: search::parse_crates_response(response, limit as usize),
+        };
+
+        match serde_json::to_string(&listing) {
+            Ok(value) => {
+                if let Err(e) = doc_cache
+                    .set_category_results(category, limit, sort, value)
+                    .await
+                {
+                    tracing::warn!(
+                        "[{TOOL_NAME}] failed to cache category listing (continuing uncached): {e}"
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize category listing: {e}"),
+        }
+
+        Ok(listing)
+    }
+}
+
+fn format_markdown_listing(listing: &CategoryListing) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    writeln!(
+        output,
+        "# Category: {} (`{}`)\n",
+        search::escape_markdown_text(&listing.name),
+        listing.slug
+    )
+    .unwrap();
+    if let Some(desc) = &listing.description {
+        writeln!(
+            output,
+            "{}\n",
+            search::escape_markdown_text(&search::normalize_description(desc))
+        )
+        .unwrap();
+    }
+    if let Some(total) = listing.total_crates {
+        writeln!(output, "**Crates in category**: {total}").unwrap();
+    }
+    writeln!(output, "**Sorted by**: {}", listing.sort).unwrap();
+    if !listing.subcategories.is_empty() {
+        writeln!(
+            output,
+            "**Subcategories**: {}",
+            listing.subcategories.join(", ")
+        )
+        .unwrap();
+    }
+    output.push('\n');
+
+    if listing.crates.is_empty() {
+        output.push_str("No crates found in this category.\n");
+    } else {
+        search::write_markdown_entries(&mut output, &listing.crates);
+    }
+    output
+}
+
+fn format_text_listing(listing: &CategoryListing) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    writeln!(output, "Category: {} ({})", listing.name, listing.slug).unwrap();
+    if let Some(desc) = &listing.description {
+        writeln!(output, "{}", search::normalize_description(desc)).unwrap();
+    }
+    if let Some(total) = listing.total_crates {
+        writeln!(output, "Crates in category: {total}").unwrap();
+    }
+    writeln!(output, "Sorted by: {}", listing.sort).unwrap();
+    if !listing.subcategories.is_empty() {
+        writeln!(
+            output,
+            "Subcategories: {}",
+            listing.subcategories.join(", ")
+        )
+        .unwrap();
+    }
+    output.push('\n');
+
+    if listing.crates.is_empty() {
+        output.push_str("No crates found in this category.\n");
+    } else {
+        output.push_str(&search::format_text_results(&listing.crates));
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for ListCratesByCategoryToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        ListCratesByCategoryTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: ListCratesByCategoryTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let category = normalize_category(TOOL_NAME, &params.category)?;
+        let limit = params.limit.unwrap_or(DEFAULT_CATEGORY_LIMIT).clamp(1, 100);
+        let sort = normalize_category_sort(params.sort.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::SEARCH_FORMATS)?;
+
+        let listing = self.list_category(&category, limit, &sort).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&listing).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_text_listing(&listing),
+            _ => format_markdown_listing(&listing),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for ListCratesByCategoryToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_category_accepts_slugs() {
+        assert_eq!(
+            normalize_category(TOOL_NAME, " Asynchronous ").unwrap(),
+            "asynchronous"
+        );
+        assert_eq!(
+            normalize_category(TOOL_NAME, "web-programming::http-server").unwrap(),
+            "web-programming::http-server"
+        );
+        assert!(normalize_category(TOOL_NAME, "").is_err());
+        assert!(normalize_category(TOOL_NAME, "web programming").is_err());
+        assert!(normalize_category(TOOL_NAME, "web:http").is_err());
+        assert!(normalize_category(TOOL_NAME, "::async").is_err());
+        assert!(normalize_category(TOOL_NAME, "../admin").is_err());
+    }
+
+    #[test]
+    fn test_normalize_category_sort() {
+        assert_eq!(normalize_category_sort(None).unwrap(), "downloads");
+        assert_eq!(normalize_category_sort(Some(" New ")).unwrap(), "new");
+        assert!(normalize_category_sort(Some("relevance")).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+//! Clippy lint lookup tool
+//!
+//! Returns the documentation of a single Clippy lint from the Clippy lint
+//! index: what it checks, why it matters, examples, its group and default
+//! level, plus the ways to allow it.
+//!
+//! The index is one large page with an `<article>` per lint; only the
+//! requested lint is extracted and cached.
+
+#![allow(missing_docs)]
+
+use crate::tools::docs::html;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "lookup_clippy_lint";
+
+/// Clippy lint index, one article per lint
+const CLIPPY_LINT_INDEX_URL: &str = "https://rust-lang.github.io/rust-clippy/master/index.html";
+
+/// Maximum accepted lint name length
+const MAX_LINT_NAME_LEN: usize = 100;
+
+/// Maximum number of "did you mean" suggestions for an unknown lint
+const MAX_SUGGESTIONS: usize = 5;
+
+static ARTICLE_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("article[id]").expect("hardcoded valid selector"));
+static DOCS_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".lint-doc-md").expect("hardcoded valid selector"));
+static GROUP_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".label-lint-group").expect("hardcoded valid selector"));
+static LEVEL_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".label-lint-level").expect("hardcoded valid selector"));
+static VERSION_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".label-version").expect("hardcoded valid selector"));
+static APPLICABILITY_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".label-applicability").expect("hardcoded valid selector"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "lookup_clippy_lint",
+    title = "Lookup Clippy Lint",
+    description = "Get the documentation of a Clippy lint from the Clippy lint index: what it checks, why it is bad, examples of the fix, its lint group and default level, and how to allow it. Accepts names like needless_borrow or clippy::needless_borrow.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://rust-lang.github.io/rust-clippy/master/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://rust-lang.github.io/rust-clippy/master/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `lookup_clippy_lint` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct LookupClippyLintTool {
+    /// Lint name (e.g., "`needless_borrow`", "`clippy::needless_borrow`")
+    #[json_schema(
+        title = "Lint Name",
+        description = "Clippy lint name, with or without the clippy:: prefix, e.g.: needless_borrow, clippy::unwrap_used"
+    )]
+    pub lint_name: String,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured lint data)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Documentation of one Clippy lint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClippyLint {
+    pub name: String,
+    /// Lint group, e.g. `style`, `pedantic`
+    pub group: Option<String>,
+    /// Default level, e.g. `warn`, `allow`
+    pub level: Option<String>,
+    /// Clippy version the lint was added in
+    pub version: Option<String>,
+    /// Whether suggestions can be applied by `cargo clippy --fix`
+    pub applicability: Option<String>,
+    pub url: String,
+    /// Lint description as markdown
+    pub docs: String,
+    /// Lint description as plain text
+    pub docs_text: String,
+}
+
+/// Normalize a lint name to the form used as the index anchor
+///
+/// Accepts an optional `clippy::` prefix, any case and `-` for `_`.
+fn normalize_lint_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    let name = name.strip_prefix("clippy::").unwrap_or(name);
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    let valid = !name.is_empty()
+        && name.len() <= MAX_LINT_NAME_LEN
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    valid.then_some(name)
+}
+
+fn label(article: ElementRef<'_>, selector: &Selector) -> Option<String> {
+    let text = article
+        .select(selector)
+        .next()?
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Extract lint `name` from the index page
+///
+/// `sanitize` is applied to the lint description (wrapped as a rustdoc-like
+/// page) before it is converted.
+fn parse_lint(page: &str, name: &str, sanitize: impl Fn(String) -> String) -> Option<ClippyLint> {
+    let document = Html::parse_document(page);
+    let article = document
+        .select(&ARTICLE_SELECTOR)
+        .find(|article| article.value().attr("id") == Some(name))?;
+    let docs_html = article
+        .select(&DOCS_SELECTOR)
+        .next()
+        .map(|docs| docs.inner_html())
+        .unwrap_or_default();
+    let wrapped = sanitize(format!(
+        "<html><body><section id=\"main-content\">{docs_html}</section></body></html>"
+    ));
+
+    Some(ClippyLint {
+        name: name.to_string(),
+        group: label(article, &GROUP_SELECTOR),
+        level: label(article, &LEVEL_SELECTOR),
+        version: label(article, &VERSION_SELECTOR),
+        applicability: label(article, &APPLICABILITY_SELECTOR),
+        url: format!("{CLIPPY_LINT_INDEX_URL}#{name}"),
+        docs: html::extract_documentation(&wrapped).trim().to_string(),
+        docs_text: html::extract_documentation_as_text(&wrapped)
+            .trim()
+            .to_string(),
+    })
+}
+
+/// Lint names sharing a word with `name`, closest first
+fn similar_lints(page: &str, name: &str) -> Vec<String> {
+    let document = Html::parse_document(page);
+    let words: Vec<&str> = name.split('_').filter(|w| w.len() > 2).collect();
+    let mut scored: Vec<(usize, String)> = document
+        .select(&ARTICLE_SELECTOR)
+        .filter_map(|article| article.value().attr("id"))
+        .filter_map(|id| {
+            let shared = id.split('_').filter(|w| words.contains(w)).count();
+            let contains = id.contains(name) || name.contains(id);
+            let score = shared + if contains { words.len() + 1 } else { 0 };
+            (score > 0).then(|| (score, id.to_string()))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, id)| id)
+        .collect()
+}
+
+fn format_lint(lint: &ClippyLint, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut facts = Vec::new();
+    if let Some(group) = &lint.group {
+        facts.push(format!("group: {group}"));
+    }
+    if let Some(level) = &lint.level {
+        facts.push(format!("default level: {level}"));
+    }
+    if let Some(version) = &lint.version {
+        facts.push(format!("added in: {version}"));
+    }
+    if let Some(applicability) = &lint.applicability {
+        facts.push(format!("applicability: {applicability}"));
+    }
+
+    let name = &lint.name;
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# `clippy::{name}`\n").unwrap();
+        if !facts.is_empty() {
+            writeln!(output, "{}\n", facts.join(" · ")).unwrap();
+        }
+        writeln!(output, "{}\n", lint.docs).unwrap();
+        writeln!(output, "## Allowing this lint\n").unwrap();
+        writeln!(output, "- On an item: `#[allow(clippy::{name})]`").unwrap();
+        writeln!(output, "- Crate-wide: `#![allow(clippy::{name})]`").unwrap();
+        writeln!(
+            output,
+            "- Command line: `cargo clippy -- -A clippy::{name}`"
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "- In `Cargo.toml`:\n\n```toml\n[lints.clippy]\n{name} = \"allow\"\n```"
+        )
+        .unwrap();
+        if is_machine_applicable(lint) {
+            writeln!(
+                output,
+                "\nSuggestions can be applied automatically with `cargo clippy --fix`."
+            )
+            .unwrap();
+        }
+        writeln!(output, "\n[Lint index]({})", lint.url).unwrap();
+    } else {
+        writeln!(output, "clippy::{name}").unwrap();
+        if !facts.is_empty() {
+            writeln!(output, "{}", facts.join(", ")).unwrap();
+        }
+        writeln!(output, "\n{}\n", lint.docs_text).unwrap();
+        writeln!(output, "Allowing this lint:").unwrap();
+        writeln!(output, "  On an item: #[allow(clippy::{name})]").unwrap();
+        writeln!(output, "  Crate-wide: #![allow(clippy::{name})]").unwrap();
+        writeln!(output, "  Command line: cargo clippy -- -A clippy::{name}").unwrap();
+        writeln!(output, "  In Cargo.toml: [lints.clippy] {name} = \"allow\"").unwrap();
+        if is_machine_applicable(lint) {
+            writeln!(
+                output,
+                "\nSuggestions can be applied automatically with cargo clippy --fix."
+            )
+            .unwrap();
+        }
+        writeln!(output, "\n{}", lint.url).unwrap();
+    }
+    output
+}
+
+fn is_machine_applicable(lint: &ClippyLint) -> bool {
+    lint.applicability
+        .as_deref()
+        .is_some_and(|a| a.eq_ignore_ascii_case("MachineApplicable"))
+}
+
+/// Implementation of the Clippy lint lookup tool
+pub struct LookupClippyLintToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl LookupClippyLintToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn fetch_lint(&self, name: &str) -> Result<ClippyLint, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache.get_clippy_lint(name).await {
+            if let Ok(lint) = serde_json::from_str(&cached) {
+                return Ok(lint);
+            }
+        }
+
+        let page = self
+            .service
+            .fetch_html(CLIPPY_LINT_INDEX_URL, Some(TOOL_NAME))
+            .await?;
+        let Some(lint) = parse_lint(&page, name, |html| {
+            self.service.sanitize_page(html, TOOL_NAME)
+        }) else {
+            return Err(crate::error::Error::not_found(
+                "clippy lint",
+                name,
+                similar_lints(&page, name),
+            )
+            .into());
+        };
+
+        match serde_json::to_string(&lint) {
+            Ok(json) => {
+                if let Err(e) = doc_cache.set_clippy_lint(name, json).await {
+                    tracing::warn!("[{TOOL_NAME}] failed to cache lint docs: {e}");
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize lint docs: {e}"),
+        }
+        Ok(lint)
+    }
+}
+
+#[async_trait]
+impl Tool for LookupClippyLintToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        LookupClippyLintTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: LookupClippyLintTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let Some(name) = normalize_lint_name(&params.lint_name) else {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "Invalid lint name '{}'. Expected a name such as needless_borrow",
+                    params.lint_name
+                )),
+            ));
+        };
+
+        let lint = self.fetch_lint(&name).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&lint).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] JSON serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_lint(&lint, false),
+            _ => format_lint(&lint, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for LookupClippyLintToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = concat!(
+        "<html><body>",
+        "<article id=\"needless_borrow\"><h2 class=\"lint-title\">",
+        "<span>needless_borrow</span>",
+        "<span class=\"label label-lint-group label-group-style\">style</span>",
+        "<span class=\"label label-lint-level\">warn</span></h2>",
+        "<div class=\"lint-docs\"><div class=\"lint-doc-md\">",
+        "<h3>What it does</h3><p>Checks for address of operations.</p>",
+        "<h3>Example</h3><pre><code class=\"language-rust\">let x: &amp;i32 = &amp;&amp;&amp;5;</code></pre>",
+        "</div><div class=\"lint-additional-info\">",
+        "<div>Applicability: <span class=\"label label-applicability\">MachineApplicable</span></div>",
+        "<div>Added in: <span class=\"label label-version\">pre 1.29.0</span></div>",
+        "</div></div></article>",
+        "<article id=\"needless_borrowed_reference\"><div class=\"lint-doc-md\"><p>x</p></div></article>",
+        "<article id=\"unwrap_used\"><div class=\"lint-doc-md\"><p>y</p></div></article>",
+        "</body></html>"
+    );
+
+    #[test]
+    fn test_normalize_lint_name() {
+        assert_eq!(
+            normalize_lint_name("clippy::needless_borrow").as_deref(),
+            Some("needless_borrow")
+        );
+        assert_eq!(
+            normalize_lint_name(" Needless-Borrow ").as_deref(),
+            Some("needless_borrow")
+        );
+        assert_eq!(normalize_lint_name("").as_deref(), None);
+        assert_eq!(normalize_lint_name("clippy::").as_deref(), None);
+        assert_eq!(normalize_lint_name("a b").as_deref(), None);
+        assert_eq!(normalize_lint_name("../index").as_deref(), None);
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        let lint = parse_lint(INDEX, "needless_borrow", |html| html).unwrap();
+        assert_eq!(lint.group.as_deref(), Some("style"));
+        assert_eq!(lint.level.as_deref(), Some("warn"));
+        assert_eq!(lint.version.as_deref(), Some("pre 1.29.0"));
+        assert_eq!(lint.applicability.as_deref(), Some("MachineApplicable"));
+        assert!(lint.docs.contains("What it does"), "{}", lint.docs);
+        assert!(lint.docs.contains("&&&5"), "{}", lint.docs);
+        assert!(lint.url.ends_with("#needless_borrow"));
+
+        assert!(parse_lint(INDEX, "needless", |html| html).is_none());
+    }
+
+    #[test]
+    fn test_similar_lints() {
+        assert_eq!(
+            similar_lints(INDEX, "needless_borow"),
+            vec!["needless_borrow", "needless_borrowed_reference"]
+        );
+        assert_eq!(similar_lints(INDEX, "unwrap"), vec!["unwrap_used"]);
+        assert!(similar_lints(INDEX, "zzz").is_empty());
+    }
+
+    #[test]
+    fn test_format_lint() {
+        let lint = parse_lint(INDEX, "needless_borrow", |html| html).unwrap();
+        let markdown = format_lint(&lint, true);
+        assert!(markdown.starts_with("# `clippy::needless_borrow`"));
+        assert!(markdown.contains("group: style · default level: warn"));
+        assert!(markdown.contains("`#[allow(clippy::needless_borrow)]`"));
+        assert!(markdown.contains("needless_borrow = \"allow\""));
+        assert!(markdown.contains("cargo clippy --fix"));
+
+        let text = format_lint(&lint, false);
+        assert!(text.starts_with("clippy::needless_borrow\n"));
+        assert!(!text.contains('`'), "{text}");
+    }
+}
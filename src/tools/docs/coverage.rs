@@ -0,0 +1,392 @@
+//! Documentation coverage tool
+//!
+//! Reports how much of a crate's public API is documented, as measured by
+//! docs.rs while building the documentation: the share of documented items
+//! and the number of items with examples. Helps judge documentation quality
+//! before adopting a crate.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "doc_coverage";
+
+/// Matches the coverage percentage in the docs.rs crate page sidebar
+static PERCENTAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)coverage\s*([0-9]+(?:\.[0-9]+)?)\s*%").expect("hardcoded valid regex pattern")
+});
+
+/// Matches e.g. `26 out of 28 items documented`
+static DOCUMENTED_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)([0-9]+)\s+out\s+of\s+([0-9]+)\s+items\s+documented")
+        .expect("hardcoded valid regex pattern")
+});
+
+/// Matches e.g. `6 out of 16 items with examples`
+static EXAMPLES_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)([0-9]+)\s+out\s+of\s+([0-9]+)\s+items\s+with\s+examples")
+        .expect("hardcoded valid regex pattern")
+});
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "doc_coverage",
+    title = "Documentation Coverage",
+    description = "Report what fraction of a crate's public items are documented and how many have examples, as measured by docs.rs. Use this to judge documentation quality before adopting a crate.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `doc_coverage` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct DocCoverageTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to check, e.g.: serde, tokio, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Crate version (optional, defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured coverage data)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Response of the docs.rs `status.json` endpoint
+#[derive(Debug, Deserialize)]
+struct DocsRsStatusResponse {
+    /// Concrete version (resolves `latest`)
+    version: String,
+    /// Whether documentation was built successfully
+    doc_status: bool,
+}
+
+/// How well a crate is documented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageRating {
+    /// At least 90% of the items are documented
+    Thorough,
+    /// At least 60%
+    Good,
+    /// At least 30%
+    Partial,
+    /// Less than 30%
+    Sparse,
+}
+
+impl CoverageRating {
+    fn from_percentage(percentage: f64) -> Self {
+        if percentage >= 90.0 {
+            Self::Thorough
+        } else if percentage >= 60.0 {
+            Self::Good
+        } else if percentage >= 30.0 {
+            Self::Partial
+        } else {
+            Self::Sparse
+        }
+    }
+}
+
+impl std::fmt::Display for CoverageRating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Thorough => write!(f, "thorough"),
+            Self::Good => write!(f, "good"),
+            Self::Partial => write!(f, "partial"),
+            Self::Sparse => write!(f, "sparse"),
+        }
+    }
+}
+
+/// Documentation coverage of a crate version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocCoverage {
+    pub crate_name: String,
+    /// Concrete version the report applies to
+    pub version: String,
+    /// Whether documentation is available on docs.rs
+    pub documentation_built: bool,
+    /// Share of documented items, in percent
+    pub percentage: Option<f64>,
+    pub documented_items: Option<u64>,
+    pub total_items: Option<u64>,
+    pub items_with_examples: Option<u64>,
+    /// Items that could carry an example
+    pub example_candidates: Option<u64>,
+    pub rating: Option<CoverageRating>,
+    /// docs.rs documentation URL
+    pub docs_url: String,
+}
+
+/// `(part, total)` item counts
+type ItemCounts = (u64, u64);
+
+/// Parse the coverage section of a docs.rs crate page: the percentage, the
+/// documented items and the items with examples
+fn parse_coverage(page: &str) -> (Option<f64>, Option<ItemCounts>, Option<ItemCounts>) {
+    let text = super::html::html_to_text(page);
+    let pair = |regex: &Regex| {
+        regex
+            .captures(&text)
+            .and_then(|caps| Some((caps[1].parse().ok()?, caps[2].parse().ok()?)))
+    };
+    let percentage = PERCENTAGE_REGEX
+        .captures(&text)
+        .and_then(|caps| caps[1].parse().ok());
+    (percentage, pair(&DOCUMENTED_REGEX), pair(&EXAMPLES_REGEX))
+}
+
+/// Percentage of `part` in `total`, rounded to two decimals
+#[allow(clippy::cast_precision_loss)]
+fn percentage_of(part: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| (part as f64 * 10_000.0 / total as f64).round() / 100.0)
+}
+
+/// Implementation of the documentation coverage tool
+pub struct DocCoverageToolImpl {
+    /// Shared document service for HTTP requests
+    service: Arc<super::DocService>,
+}
+
+impl DocCoverageToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Fetch the coverage of `version` (`latest` for the newest release)
+    async fn fetch_coverage(
+        &self,
+        crate_name: &str,
+        version: &str,
+    ) -> std::result::Result<DocCoverage, CallToolError> {
+        let status_url =
+            self.service
+                .crate_details_url(crate_name, version, "status.json", TOOL_NAME)?;
+        let Some(body) = self
+            .service
+            .fetch_html_optional(&status_url, Some(TOOL_NAME))
+            .await?
+        else {
+            let version = (version != "latest").then_some(version);
+            return Err(super::crate_not_found(crate_name, version).into());
+        };
+        let status: DocsRsStatusResponse = serde_json::from_str(&body).map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+        })?;
+        let version = status.version;
+
+        let mut coverage = DocCoverage {
+            crate_name: crate_name.to_string(),
+            documentation_built: status.doc_status,
+            percentage: None,
+            documented_items: None,
+            total_items: None,
+            items_with_examples: None,
+            example_candidates: None,
+            rating: None,
+            docs_url: self.service.crate_docs_url(crate_name, Some(&version)),
+            version,
+        };
+        if !coverage.documentation_built {
+            return Ok(coverage);
+        }
+
+        let page_url =
+            self.service
+                .crate_details_url(crate_name, &coverage.version, "", TOOL_NAME)?;
+        let page = self
+            .service
+            .fetch_html_optional(&page_url, Some(TOOL_NAME))
+            .await?
+            .unwrap_or_default();
+        let (percentage, documented, examples) = parse_coverage(&page);
+        // The counts give a more precise figure than the rounded percentage
+        coverage.percentage = documented
+            .and_then(|(documented, total)| percentage_of(documented, total))
+            .or(percentage);
+        coverage.documented_items = documented.map(|(documented, _)| documented);
+        coverage.total_items = documented.map(|(_, total)| total);
+        coverage.items_with_examples = examples.map(|(with_examples, _)| with_examples);
+        coverage.example_candidates = examples.map(|(_, total)| total);
+        coverage.rating = coverage.percentage.map(CoverageRating::from_percentage);
+        Ok(coverage)
+    }
+}
+
+fn format_coverage(coverage: &DocCoverage, markdown: bool) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    let (heading, bold) = if markdown { ("# ", "**") } else { ("", "") };
+    writeln!(
+        output,
+        "{heading}Documentation coverage: {} {}",
+        coverage.crate_name, coverage.version
+    )
+    .unwrap();
+    if markdown {
+        writeln!(output).unwrap();
+    }
+
+    if !coverage.documentation_built {
+        writeln!(
+            output,
+            "{bold}Documentation{bold}: not available (build failed or pending), so docs.rs has no coverage data"
+        )
+        .unwrap();
+    } else if let (Some(percentage), Some(rating)) = (coverage.percentage, coverage.rating) {
+        writeln!(output, "{bold}Coverage{bold}: {percentage:.2}% ({rating})").unwrap();
+        if let (Some(documented), Some(total)) = (coverage.documented_items, coverage.total_items) {
+            writeln!(
+                output,
+                "{bold}Documented items{bold}: {documented} of {total}"
+            )
+            .unwrap();
+        }
+        if let (Some(with_examples), Some(total)) =
+            (coverage.items_with_examples, coverage.example_candidates)
+        {
+            writeln!(
+                output,
+                "{bold}Items with examples{bold}: {with_examples} of {total}"
+            )
+            .unwrap();
+        }
+    } else {
+        writeln!(
+            output,
+            "{bold}Coverage{bold}: not reported by docs.rs for this build (builds before coverage was recorded have none)"
+        )
+        .unwrap();
+    }
+    if markdown {
+        writeln!(output, "**Docs**: <{}>", coverage.docs_url).unwrap();
+    } else {
+        writeln!(output, "Docs: {}", coverage.docs_url).unwrap();
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for DocCoverageToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        DocCoverageTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<
+        rust_mcp_sdk::schema::CallToolResult,
+        rust_mcp_sdk::schema::CallToolError,
+    > {
+        let params: DocCoverageTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' is part of the Rust distribution and is not built on docs.rs"
+                )),
+            ));
+        }
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?
+            .unwrap_or_else(|| "latest".to_string());
+
+        let coverage = self.fetch_coverage(crate_name, &version).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&coverage).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_coverage(&coverage, false),
+            _ => format_coverage(&coverage, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for DocCoverageToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coverage_sidebar() {
+        let page = r#"<ul class="pure-menu-list">
+            <li class="pure-menu-heading">Coverage</li>
+            <li class="pure-menu-item text-center"><b>92.86%</b><br>
+                26 out of 28 items documented<span class="documented-info">6 out of 16 items with examples</span>
+            </li></ul>"#;
+        let (percentage, documented, examples) = parse_coverage(page);
+        assert_eq!(percentage, Some(92.86));
+        assert_eq!(documented, Some((26, 28)));
+        assert_eq!(examples, Some((6, 16)));
+        assert_eq!(parse_coverage("<p>Crate serde</p>"), (None, None, None));
+    }
+
+    #[test]
+    fn test_percentage_and_rating() {
+        assert_eq!(percentage_of(26, 28), Some(92.86));
+        assert_eq!(percentage_of(0, 0), None);
+        assert_eq!(
+            CoverageRating::from_percentage(92.86),
+            CoverageRating::Thorough
+        );
+        assert_eq!(CoverageRating::from_percentage(60.0), CoverageRating::Good);
+        assert_eq!(
+            CoverageRating::from_percentage(45.0),
+            CoverageRating::Partial
+        );
+        assert_eq!(CoverageRating::from_percentage(5.0), CoverageRating::Sparse);
+    }
+}
@@ -0,0 +1,178 @@
+//! Module page crawler
+//!
+//! Several tools need to look at every module of a crate rather than a single
+//! page: rustdoc only records per-item markers (feature gates, deprecation,
+//! ...) in the item table of the module that contains the item. This walks a
+//! crate's module pages breadth-first from the root, up to a page budget.
+
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{HashSet, VecDeque};
+use std::sync::LazyLock;
+
+static ITEM_LINK_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("a[href]").expect("hardcoded valid selector"));
+
+static MODULE_LINK_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".item-table a.mod[href]").expect("hardcoded valid selector"));
+
+/// A fetched module page
+pub(super) struct ModulePage {
+    /// Module path, e.g. `tokio::net`
+    pub path: String,
+    /// Page URL (ends in `/`); item hrefs on the page are relative to it
+    pub url: String,
+    pub html: String,
+}
+
+/// Result of a crawl
+pub(super) struct ModuleCrawl {
+    pub pages: Vec<ModulePage>,
+    /// Whether known modules were left unvisited because of the page budget
+    pub truncated: bool,
+}
+
+/// An entry of a module's item table
+pub(super) struct TableItem {
+    pub name: String,
+    /// rustdoc item kind (`struct`, `fn`, `mod`, ...)
+    pub kind: String,
+    /// Link relative to the module page
+    pub href: String,
+}
+
+/// The item-table entry a badge (`<span class="stab ...">`) belongs to
+///
+/// The badge sits next to the item link, in the same `dt` / `.item-name`
+/// cell depending on the rustdoc version.
+pub(super) fn badged_item(badge: ElementRef<'_>) -> Option<TableItem> {
+    let cell = badge.parent().and_then(ElementRef::wrap)?;
+    let link = cell.select(&ITEM_LINK_SELECTOR).next()?;
+    let name: String = link.text().collect();
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some(TableItem {
+        name: name.to_string(),
+        kind: link.value().classes().next().unwrap_or("item").to_string(),
+        href: link.value().attr("href")?.to_string(),
+    })
+}
+
+/// Child modules linked from a module page, as `(path, relative dir)`
+fn child_modules(html: &str, module_path: &str) -> Vec<(String, String)> {
+    let document = Html::parse_document(html);
+    document
+        .select(&MODULE_LINK_SELECTOR)
+        .filter_map(|link| {
+            let href = link.value().attr("href")?;
+            // Only descend into child modules; re-exports of other crates'
+            // modules link out of the crate directory.
+            let dir = href.strip_suffix("/index.html")?;
+            if dir.is_empty() || dir.contains(['/', '.', ':', '?', '#']) {
+                return None;
+            }
+            Some((format!("{module_path}::{dir}"), format!("{dir}/")))
+        })
+        .collect()
+}
+
+/// Crawl a crate's modules breadth-first from the root
+///
+/// The first provider that has the root module page serves the whole crawl.
+/// Child modules that fail to load are skipped.
+///
+/// # Errors
+///
+/// Returns a not-found error if no provider has the crate, or the last
+/// provider error if they all failed
+pub(super) async fn crawl_modules(
+    service: &super::DocService,
+    crate_name: &str,
+    version: Option<&str>,
+    max_modules: usize,
+    tool_name: &str,
+) -> Result<ModuleCrawl, CallToolError> {
+    let mut root: Option<(String, String)> = None;
+    let mut last_error: Option<String> = None;
+    for provider in service.providers_for(crate_name) {
+        let root_url = provider.root_url(crate_name, version);
+        match service
+            .fetch_html_optional(&root_url, Some(tool_name))
+            .await
+        {
+            Ok(Some(html)) => {
+                root = Some((root_url, html));
+                break;
+            }
+            Ok(None) => {}
+            Err(e) => last_error = Some(e.to_string()),
+        }
+    }
+    let Some((root_url, root_html)) = root else {
+        return Err(last_error.map_or_else(
+            || super::crate_not_found(crate_name, version).into(),
+            CallToolError::from_message,
+        ));
+    };
+
+    let krate = crate_name.replace('-', "_");
+    let mut pages: Vec<ModulePage> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::from([krate.clone()]);
+    let mut queue: VecDeque<(String, String)> = VecDeque::new();
+    let mut next = Some(ModulePage {
+        path: krate,
+        url: root_url,
+        html: root_html,
+    });
+
+    while let Some(page) = next.take() {
+        for (child_path, child_dir) in child_modules(&page.html, &page.path) {
+            if seen.insert(child_path.clone()) {
+                queue.push_back((child_path, format!("{}{child_dir}", page.url)));
+            }
+        }
+        pages.push(page);
+        if pages.len() >= max_modules {
+            break;
+        }
+
+        while let Some((path, url)) = queue.pop_front() {
+            match service.fetch_html_optional(&url, Some(tool_name)).await {
+                Ok(Some(html)) => {
+                    next = Some(ModulePage { path, url, html });
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("[{tool_name}] skipping module {path}: {e}"),
+            }
+        }
+    }
+
+    Ok(ModuleCrawl {
+        pages,
+        truncated: !queue.is_empty(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_modules_stay_in_crate() {
+        let html = concat!(
+            "<html><body><dl class=\"item-table\">",
+            "<dt><a class=\"mod\" href=\"fs/index.html\">fs</a></dt>",
+            "<dt><a class=\"mod\" href=\"../other/index.html\">other</a></dt>",
+            "<dt><a class=\"mod\" href=\"https://docs.rs/x/latest/x/index.html\">x</a></dt>",
+            "<dt><a class=\"struct\" href=\"struct.Plain.html\">Plain</a></dt>",
+            "</dl></body></html>"
+        );
+        assert_eq!(
+            child_modules(html, "demo"),
+            vec![("demo::fs".to_string(), "fs/".to_string())]
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Suggest dependency line tool
+//!
+//! Produces a ready-to-paste `cargo add` command and `Cargo.toml` entry for a
+//! crate. The version defaults to the latest stable release and requested
+//! features are checked against the features that version actually declares
+//! in the crates.io index.
+
+#![allow(missing_docs)]
+
+use super::resolve_version::{self, VersionSpec};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "suggest_dependency_line";
+
+/// Upper bound on requested features; real crates rarely need more than a few
+const MAX_FEATURES: usize = 64;
+
+/// How many available features are listed in an unknown-feature error
+const MAX_LISTED_FEATURES: usize = 40;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "suggest_dependency_line",
+    title = "Suggest Dependency Line",
+    description = "Produce a ready-to-paste `cargo add` command and Cargo.toml dependency line for a crate. Uses the latest stable version unless a version or requirement is given, and validates the requested features against the crate's actual feature list.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `suggest_dependency_line` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct SuggestDependencyLineTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate to add, e.g.: serde, tokio, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Version or semver requirement (defaults to the latest stable version)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.100, ^1.2. Uses the latest stable version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Features to enable
+    #[json_schema(
+        title = "Features",
+        description = "Features to enable, e.g.: [\"derive\"] for serde or [\"full\"] for tokio. Each must be declared by the crate"
+    )]
+    pub features: Option<Vec<String>>,
+
+    /// Whether to keep the crate's default features (defaults to true)
+    #[json_schema(
+        title = "Default Features",
+        description = "Set to false to disable the crate's default features",
+        default = true
+    )]
+    pub default_features: Option<bool>,
+
+    /// Add as a dev-dependency (defaults to false)
+    #[json_schema(
+        title = "Dev Dependency",
+        description = "Set to true to add the crate under [dev-dependencies]",
+        default = false
+    )]
+    pub dev: Option<bool>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured suggestion)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// A suggested dependency declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencySuggestion {
+    /// Crate name
+    pub crate_name: String,
+    /// Concrete version used in the suggestion
+    pub version: String,
+    /// Whether that version is yanked
+    pub yanked: bool,
+    /// Enabled features, in the order requested
+    pub features: Vec<String>,
+    /// Whether default features stay enabled
+    pub default_features: bool,
+    /// `[dependencies]` or `[dev-dependencies]`
+    pub section: String,
+    /// `cargo add` command
+    pub cargo_add: String,
+    /// `Cargo.toml` line
+    pub toml: String,
+    /// Every feature the version declares
+    pub available_features: Vec<String>,
+}
+
+/// Trim, drop empties and de-duplicate the requested features
+pub(super) fn normalize_features(
+    tool_name: &str,
+    features: &[String],
+) -> Result<Vec<String>, CallToolError> {
+    if features.len() > MAX_FEATURES {
+        return Err(CallToolError::invalid_arguments(
+            tool_name,
+            Some(format!("too many features (max {MAX_FEATURES})")),
+        ));
+    }
+    let mut normalized: Vec<String> = Vec::new();
+    for feature in features {
+        let feature = feature.trim();
+        if !feature.is_empty() && !normalized.iter().any(|f| f == feature) {
+            normalized.push(feature.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+/// Reject features the selected version does not declare
+pub(super) fn check_features(
+    tool_name: &str,
+    crate_name: &str,
+    version: &str,
+    requested: &[String],
+    available: &[String],
+) -> Result<(), CallToolError> {
+    let unknown: Vec<&str> = requested
+        .iter()
+        .filter(|feature| !available.contains(feature))
+        .map(String::as_str)
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let listed = if available.is_empty() {
+        "it declares no features".to_string()
+    } else {
+        let mut listed = available
+            .iter()
+            .take(MAX_LISTED_FEATURES)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if available.len() > MAX_LISTED_FEATURES {
+            let more = available.len() - MAX_LISTED_FEATURES;
+            listed = format!("{listed}, ... ({more} more)");
+        }
+        format!("available features: {listed}")
+    };
+    Err(CallToolError::invalid_arguments(
+        tool_name,
+        Some(format!(
+            "{crate_name} {version} has no feature {}; {listed}",
+            unknown
+                .iter()
+                .map(|f| format!("'{f}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    ))
+}
+
+fn cargo_add_command(
+    crate_name: &str,
+    version: &str,
+    features: &[String],
+    default_features: bool,
+    dev: bool,
+) -> String {
+    let mut command = format!("cargo add {crate_name}@{version}");
+    if dev {
+        command.push_str(" --dev");
+    }
+    if !default_features {
+        command.push_str(" --no-default-features");
+    }
+    if !features.is_empty() {
+        command.push_str(" --features ");
+        command.push_str(&features.join(","));
+    }
+    command
+}
+
+fn toml_line(
+    crate_name: &str,
+    version: &str,
+    features: &[String],
+    default_features: bool,
+) -> String {
+    if features.is_empty() && default_features {
+        return format!("{crate_name} = \"{version}\"");
+    }
+    let mut line = format!("{crate_name} = {{ version = \"{version}\"");
+    if !default_features {
+        line.push_str(", default-features = false");
+    }
+    if !features.is_empty() {
+        let quoted: Vec<String> = features.iter().map(|f| format!("\"{f}\"")).collect();
+        line.push_str(", features = [");
+        line.push_str(&quoted.join(", "));
+        line.push(']');
+    }
+    line.push_str(" }");
+    line
+}
+
+fn format_suggestion(suggestion: &DependencySuggestion, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# {} {}\n",
+            suggestion.crate_name, suggestion.version
+        )
+        .unwrap();
+        writeln!(output, "```sh\n{}\n```\n", suggestion.cargo_add).unwrap();
+        writeln!(
+            output,
+            "```toml\n{}\n{}\n```",
+            suggestion.section, suggestion.toml
+        )
+        .unwrap();
+        if !suggestion.available_features.is_empty() {
+            let features: Vec<String> = suggestion
+                .available_features
+                .iter()
+                .map(|f| format!("`{f}`"))
+                .collect();
+            writeln!(output, "\n**Available features**: {}", features.join(", ")).unwrap();
+        }
+        if suggestion.yanked {
+            writeln!(output, "\n> ⚠️ This version has been yanked.").unwrap();
+        }
+    } else {
+        writeln!(output, "{}", suggestion.cargo_add).unwrap();
+        writeln!(output, "\n{}\n{}", suggestion.section, suggestion.toml).unwrap();
+        if !suggestion.available_features.is_empty() {
+            writeln!(
+                output,
+                "\nAvailable features: {}",
+                suggestion.available_features.join(", ")
+            )
+            .unwrap();
+        }
+        if suggestion.yanked {
+            writeln!(output, "Warning: this version has been yanked").unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the suggest dependency line tool
+pub struct SuggestDependencyLineToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl SuggestDependencyLineToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for SuggestDependencyLineToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SuggestDependencyLineTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SuggestDependencyLineTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let crate_name = params.crate_name.trim();
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' ships with the Rust toolchain and is not added as a dependency"
+                )),
+            ));
+        }
+        let requirement = params
+            .version
+            .as_deref()
+            .map_or_else(|| "latest".to_string(), super::normalize_version);
+        let Some(spec) = VersionSpec::parse(&requirement) else {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Invalid version '{requirement}'")),
+            ));
+        };
+        let features =
+            normalize_features(TOOL_NAME, params.features.as_deref().unwrap_or_default())?;
+        let default_features = params.default_features.unwrap_or(true);
+        let dev = params.dev.unwrap_or(false);
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let versions =
+            resolve_version::fetch_index_versions(&self.service, crate_name, TOOL_NAME).await?;
+        let Some(selected) = resolve_version::select_version(&versions, &spec) else {
+            return Err(
+                resolve_version::no_matching_version(crate_name, &requirement, &versions).into(),
+            );
+        };
+        let version = selected.version.to_string();
+        check_features(
+            TOOL_NAME,
+            crate_name,
+            &version,
+            &features,
+            &selected.features,
+        )?;
+
+        let suggestion = DependencySuggestion {
+            crate_name: crate_name.to_string(),
+            cargo_add: cargo_add_command(crate_name, &version, &features, default_features, dev),
+            toml: toml_line(crate_name, &version, &features, default_features),
+            version,
+            yanked: selected.yanked,
+            features,
+            default_features,
+            section: if dev {
+                "[dev-dependencies]"
+            } else {
+                "[dependencies]"
+            }
+            .to_string(),
+            available_features: selected.features.clone(),
+        };
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&suggestion).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_suggestion(&suggestion, false),
+            _ => format_suggestion(&suggestion, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for SuggestDependencyLineToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_dependency_lines() {
+        assert_eq!(
+            toml_line("serde", "1.0.200", &[], true),
+            r#"serde = "1.0.200""#
+        );
+        assert_eq!(
+            toml_line("tokio", "1.40.0", &strings(&["rt", "macros"]), false),
+            r#"tokio = { version = "1.40.0", default-features = false, features = ["rt", "macros"] }"#
+        );
+        assert_eq!(
+            cargo_add_command("tokio", "1.40.0", &strings(&["rt", "macros"]), false, true),
+            "cargo add tokio@1.40.0 --dev --no-default-features --features rt,macros"
+        );
+    }
+
+    #[test]
+    fn test_features_normalized_and_checked() {
+        let features =
+            normalize_features(TOOL_NAME, &strings(&[" derive ", "", "derive", "std"])).unwrap();
+        assert_eq!(features, strings(&["derive", "std"]));
+
+        let available = strings(&["default", "derive", "std"]);
+        assert!(check_features(TOOL_NAME, "serde", "1.0.0", &features, &available).is_ok());
+        let err = check_features(
+            TOOL_NAME,
+            "serde",
+            "1.0.0",
+            &strings(&["derives"]),
+            &available,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("'derives'"), "got: {err}");
+        assert!(err.contains("default, derive, std"), "got: {err}");
+    }
+}
@@ -0,0 +1,858 @@
+//! Transitive dependency tree tool
+//!
+//! Resolves the dependency graph of a crate version from the crates.io
+//! sparse index and renders it like `cargo tree`. Every requirement resolves
+//! to the newest non-yanked version matching it, and features are unified
+//! per package: a package reached from several parents carries every
+//! feature any of them activates. Each package's index file is fetched once,
+//! however often the package appears in the graph.
+//!
+//! Dev-dependencies are left out. Platform-specific dependencies are kept
+//! for every target and marked with their `cfg`.
+
+#![allow(missing_docs)]
+
+use super::dependency::{check_features, normalize_features};
+use super::resolve_version::{self, VersionSpec};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+const TOOL_NAME: &str = "dependency_tree";
+
+/// Packages resolved before the graph is cut short
+const MAX_PACKAGES: usize = 400;
+
+/// Index files fetched at the same time
+const MAX_CONCURRENT_FETCHES: usize = 5;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "dependency_tree",
+    title = "Dependency Tree",
+    description = "Resolve and render the full transitive dependency tree of a crate version, like `cargo tree`: each dependency with its version requirement, the version it resolves to and the features it activates. Resolution uses the crates.io sparse index; dev-dependencies are not included.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    execution(task_support = "optional"),
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `dependency_tree` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct DependencyTreeTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate whose dependencies to resolve, e.g.: reqwest, tokio"
+    )]
+    pub crate_name: String,
+
+    /// Version or semver requirement (defaults to the latest stable version)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 0.12.5, ^1.2. Uses the latest stable version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Features to enable on the crate
+    #[json_schema(
+        title = "Features",
+        description = "Features to enable on the crate, e.g.: [\"json\", \"gzip\"]. Each must be declared by the crate"
+    )]
+    pub features: Option<Vec<String>>,
+
+    /// Whether to enable the crate's default features (defaults to true)
+    #[json_schema(
+        title = "Default Features",
+        description = "Set to false to resolve without the crate's default features",
+        default = true
+    )]
+    pub default_features: Option<bool>,
+
+    /// Include build-dependencies (defaults to true)
+    #[json_schema(
+        title = "Include Build Dependencies",
+        description = "Set to false to leave out build-dependencies",
+        default = true
+    )]
+    pub include_build: Option<bool>,
+
+    /// Maximum depth rendered (the whole graph is still resolved)
+    #[json_schema(
+        title = "Max Depth",
+        description = "Maximum tree depth to render; deeper dependencies are elided. Renders the full tree if not specified",
+        minimum = 1
+    )]
+    pub max_depth: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text tree), json (every package with its dependency edges)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// One line of a sparse index file, with the dependency metadata
+#[derive(Debug, Clone, Deserialize)]
+struct IndexRelease {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    features2: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default)]
+    deps: Vec<IndexDep>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct IndexDep {
+    /// Name used in the manifest (the rename, if any)
+    name: String,
+    req: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default = "default_true")]
+    default_features: bool,
+    #[serde(default)]
+    target: Option<String>,
+    /// `normal`, `build` or `dev`; absent means `normal`
+    #[serde(default)]
+    kind: Option<String>,
+    /// Actual crate name when the dependency is renamed
+    #[serde(default)]
+    package: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl IndexDep {
+    fn package(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+
+    fn is_build(&self) -> bool {
+        self.kind.as_deref() == Some("build")
+    }
+
+    fn is_dev(&self) -> bool {
+        self.kind.as_deref() == Some("dev")
+    }
+}
+
+/// Published releases of a package, newest last is not guaranteed
+type PackageIndex = Vec<(Version, IndexRelease)>;
+
+/// A dependency edge of a resolved package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Crate name
+    pub name: String,
+    /// Name the dependent uses for it, when renamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    pub req: String,
+    /// Resolved version, `None` when no published version matches
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub build: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Platform `cfg` the dependency is limited to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// A resolved package with its unified features
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedPackage {
+    pub name: String,
+    pub version: String,
+    /// Enabled features, after unification
+    pub features: Vec<String>,
+    pub dependencies: Vec<DependencyEdge>,
+}
+
+/// The resolved dependency graph of a crate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyTree {
+    pub crate_name: String,
+    pub version: String,
+    /// Every resolved package, the root first
+    pub packages: Vec<ResolvedPackage>,
+    /// Whether resolution stopped at the package limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Resolution state of one package version
+#[derive(Debug, Default)]
+struct NodeState {
+    /// Features requested by dependents, unified
+    requested: BTreeSet<String>,
+    default_features: bool,
+    /// Features enabled once `requested` is expanded
+    enabled: BTreeSet<String>,
+    edges: Vec<DependencyEdge>,
+}
+
+/// Parse a sparse index file, skipping lines that are not valid entries
+fn parse_index(body: &str) -> PackageIndex {
+    body.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<IndexRelease>(line).ok())
+        .filter_map(|release| {
+            Version::parse(&release.vers)
+                .ok()
+                .map(|version| (version, release))
+        })
+        .collect()
+}
+
+/// Newest non-yanked release matching `req`, as Cargo would select it
+fn select_release<'a>(index: &'a PackageIndex, req: &VersionReq) -> Option<&'a Version> {
+    index
+        .iter()
+        .filter(|(version, release)| !release.yanked && req.matches(version))
+        .map(|(version, _)| version)
+        .max()
+}
+
+/// Enabled dependencies of `release` with the features each must enable
+///
+/// Follows Cargo's feature syntax: `dep:name`, `name/feature`, weak
+/// `name?/feature` and optional dependencies as implicit features.
+/// Returns the enabled features alongside, indices into `release.deps`.
+fn activate(
+    release: &IndexRelease,
+    requested: &BTreeSet<String>,
+    default_features: bool,
+    include_build: bool,
+) -> (BTreeSet<String>, Vec<(usize, BTreeSet<String>)>) {
+    let mut table = release.features.clone();
+    table.extend(release.features2.clone().unwrap_or_default());
+
+    let mut queue: Vec<String> = requested.iter().cloned().collect();
+    if default_features {
+        queue.push("default".to_string());
+    }
+    let mut features = BTreeSet::new();
+    let mut enabled_deps: BTreeSet<String> = BTreeSet::new();
+    let mut dep_features: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut weak = Vec::new();
+    while let Some(feature) = queue.pop() {
+        if let Some(dep) = feature.strip_prefix("dep:") {
+            enabled_deps.insert(dep.to_string());
+        } else if let Some((dep, dep_feature)) = feature.split_once('/') {
+            if let Some(dep) = dep.strip_suffix('?') {
+                weak.push((dep.to_string(), dep_feature.to_string()));
+            } else {
+                enabled_deps.insert(dep.to_string());
+                dep_features
+                    .entry(dep.to_string())
+                    .or_default()
+                    .insert(dep_feature.to_string());
+            }
+        } else if let Some(values) = table.get(&feature) {
+            if features.insert(feature.clone()) {
+                queue.extend(values.iter().cloned());
+            }
+        } else if release.deps.iter().any(|d| d.optional && d.name == feature) {
+            // Implicit feature of an optional dependency
+            features.insert(feature.clone());
+            enabled_deps.insert(feature);
+        }
+    }
+    // A weak feature only applies to a dependency enabled some other way
+    for (dep, dep_feature) in weak {
+        if enabled_deps.contains(&dep) {
+            dep_features.entry(dep).or_default().insert(dep_feature);
+        }
+    }
+
+    let deps = release
+        .deps
+        .iter()
+        .enumerate()
+        .filter(|(_, dep)| !dep.is_dev() && (include_build || !dep.is_build()))
+        .filter(|(_, dep)| !dep.optional || enabled_deps.contains(&dep.name))
+        .map(|(i, dep)| {
+            let mut enabled: BTreeSet<String> = dep.features.iter().cloned().collect();
+            enabled.extend(dep_features.get(&dep.name).cloned().unwrap_or_default());
+            (i, enabled)
+        })
+        .collect();
+    (features, deps)
+}
+
+/// Fetch and parse the index file of a package; `Ok(None)` if it does not exist
+///
+/// Errors are plain strings so the future can run on a [`JoinSet`].
+async fn load_index(
+    service: Arc<super::DocService>,
+    crate_name: String,
+) -> Result<Option<PackageIndex>, String> {
+    let url = super::build_crates_io_index_url(&crate_name);
+    let body = service
+        .fetch_html_optional(&url, Some(TOOL_NAME))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(body.map(|body| parse_index(&body)))
+}
+
+/// Load the index files of `names` not loaded yet, a few at a time
+async fn fetch_indexes(
+    service: &Arc<super::DocService>,
+    names: &BTreeSet<String>,
+    indexes: &mut HashMap<String, Option<PackageIndex>>,
+) -> Result<(), CallToolError> {
+    let missing: Vec<&String> = names
+        .iter()
+        .filter(|name| !indexes.contains_key(*name))
+        .collect();
+    for chunk in missing.chunks(MAX_CONCURRENT_FETCHES) {
+        let mut tasks = JoinSet::new();
+        for name in chunk {
+            let service = Arc::clone(service);
+            let name = (*name).clone();
+            tasks.spawn(async move {
+                let index = load_index(service, name.clone()).await;
+                (name, index)
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((name, index)) = joined else {
+                continue;
+            };
+            let index = index.map_err(|e| {
+                CallToolError::from_message(format!(
+                    "[{TOOL_NAME}] Failed to fetch the index of {name}: {e}"
+                ))
+            })?;
+            indexes.insert(name, index);
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the graph rooted at `crate_name` `version`
+async fn resolve_tree(
+    service: &Arc<super::DocService>,
+    crate_name: &str,
+    version: &Version,
+    features: &[String],
+    default_features: bool,
+    include_build: bool,
+) -> Result<DependencyTree, CallToolError> {
+    let root_name = crate_name.to_ascii_lowercase();
+    let mut indexes: HashMap<String, Option<PackageIndex>> = HashMap::new();
+    let mut nodes: BTreeMap<(String, Version), NodeState> = BTreeMap::new();
+    let root = (root_name.clone(), version.clone());
+    nodes.insert(
+        root.clone(),
+        NodeState {
+            requested: features.iter().cloned().collect(),
+            default_features,
+            ..NodeState::default()
+        },
+    );
+    let mut pending = vec![root.clone()];
+    let mut needed: BTreeSet<String> = BTreeSet::from([root_name]);
+    let mut truncated = false;
+
+    while !pending.is_empty() {
+        fetch_indexes(service, &needed, &mut indexes).await?;
+        needed.clear();
+
+        let mut next = Vec::new();
+        for key in std::mem::take(&mut pending) {
+            let Some(release) = indexes
+                .get(&key.0)
+                .and_then(Option::as_ref)
+                .and_then(|index| index.iter().find(|(v, _)| *v == key.1))
+                .map(|(_, release)| release)
+            else {
+                continue;
+            };
+            let node = &nodes[&key];
+            let (enabled, deps) = activate(
+                release,
+                &node.requested,
+                node.default_features,
+                include_build,
+            );
+
+            let mut edges = Vec::with_capacity(deps.len());
+            let mut waiting = false;
+            for (i, dep_features) in deps {
+                let dep = &release.deps[i];
+                let package = dep.package().to_ascii_lowercase();
+                let Some(index) = indexes.get(&package) else {
+                    // Resolve this package again once the index is loaded
+                    needed.insert(package);
+                    waiting = true;
+                    continue;
+                };
+                let resolved = index
+                    .as_ref()
+                    .zip(VersionReq::parse(&dep.req).ok())
+                    .and_then(|(index, req)| select_release(index, &req).cloned());
+                edges.push(DependencyEdge {
+                    name: package.clone(),
+                    rename: dep.package.as_ref().map(|_| dep.name.clone()),
+                    req: dep.req.clone(),
+                    version: resolved.as_ref().map(Version::to_string),
+                    build: dep.is_build(),
+                    optional: dep.optional,
+                    target: dep.target.clone(),
+                });
+                let Some(resolved) = resolved else {
+                    continue;
+                };
+
+                let child_key = (package, resolved);
+                if !nodes.contains_key(&child_key) && nodes.len() >= MAX_PACKAGES {
+                    truncated = true;
+                    continue;
+                }
+                let is_new = !nodes.contains_key(&child_key);
+                let child = nodes.entry(child_key.clone()).or_default();
+                let before = (child.requested.len(), child.default_features);
+                child.requested.extend(dep_features);
+                child.default_features |= dep.default_features;
+                if is_new || before != (child.requested.len(), child.default_features) {
+                    next.push(child_key);
+                }
+            }
+
+            if let Some(node) = nodes.get_mut(&key) {
+                node.enabled = enabled;
+                node.edges = edges;
+            }
+            if waiting {
+                next.push(key);
+            }
+        }
+        next.sort();
+        next.dedup();
+        pending = next;
+    }
+
+    Ok(DependencyTree {
+        crate_name: crate_name.to_string(),
+        version: version.to_string(),
+        packages: collect_packages(nodes, &root),
+        truncated,
+    })
+}
+
+/// Flatten resolved nodes, the root first and the rest alphabetically
+fn collect_packages(
+    nodes: BTreeMap<(String, Version), NodeState>,
+    root: &(String, Version),
+) -> Vec<ResolvedPackage> {
+    let mut packages = Vec::with_capacity(nodes.len());
+    for (key, node) in nodes {
+        let package = ResolvedPackage {
+            name: key.0.clone(),
+            version: key.1.to_string(),
+            features: node.enabled.into_iter().collect(),
+            dependencies: node.edges,
+        };
+        if key == *root {
+            packages.insert(0, package);
+        } else {
+            packages.push(package);
+        }
+    }
+    packages
+}
+
+/// Render the tree like `cargo tree`, marking repeated subtrees with `(*)`
+fn render_tree(tree: &DependencyTree, max_depth: Option<usize>) -> String {
+    let packages: HashMap<(&str, &str), &ResolvedPackage> = tree
+        .packages
+        .iter()
+        .map(|p| ((p.name.as_str(), p.version.as_str()), p))
+        .collect();
+    let mut output = String::new();
+    let Some(root) = tree.packages.first() else {
+        return output;
+    };
+    output.push_str(&package_label(root));
+    output.push('\n');
+    let mut expanded = BTreeSet::from([(root.name.as_str(), root.version.as_str())]);
+    render_children(
+        root,
+        &packages,
+        &mut expanded,
+        "",
+        1,
+        max_depth,
+        &mut output,
+    );
+    output
+}
+
+fn package_label(package: &ResolvedPackage) -> String {
+    if package.features.is_empty() {
+        format!("{} v{}", package.name, package.version)
+    } else {
+        format!(
+            "{} v{} [features: {}]",
+            package.name,
+            package.version,
+            package.features.join(", ")
+        )
+    }
+}
+
+fn render_children<'a>(
+    package: &'a ResolvedPackage,
+    packages: &HashMap<(&str, &str), &'a ResolvedPackage>,
+    expanded: &mut BTreeSet<(&'a str, &'a str)>,
+    prefix: &str,
+    depth: usize,
+    max_depth: Option<usize>,
+    output: &mut String,
+) {
+    use std::fmt::Write;
+
+    let count = package.dependencies.len();
+    // SAFETY: write! to String never fails
+    for (i, edge) in package.dependencies.iter().enumerate() {
+        let last = i + 1 == count;
+        let branch = if last { "└── " } else { "├── " };
+        let mut line = String::new();
+        let child = edge
+            .version
+            .as_deref()
+            .and_then(|v| packages.get(&(edge.name.as_str(), v)).copied());
+        match (&edge.version, child) {
+            (Some(_), Some(child)) => line.push_str(&package_label(child)),
+            (Some(version), None) => write!(line, "{} v{version}", edge.name).unwrap(),
+            (None, _) => write!(line, "{} {} (no matching version)", edge.name, edge.req).unwrap(),
+        }
+        if let Some(rename) = &edge.rename {
+            write!(line, " (as {rename})").unwrap();
+        }
+        if edge.build {
+            line.push_str(" (build)");
+        }
+        if let Some(target) = &edge.target {
+            write!(line, " ({target})").unwrap();
+        }
+
+        let Some(child) = child else {
+            writeln!(output, "{prefix}{branch}{line}").unwrap();
+            continue;
+        };
+        let child_key = (child.name.as_str(), child.version.as_str());
+        let has_dependencies = !child.dependencies.is_empty();
+        let elided = has_dependencies && max_depth.is_some_and(|max| depth >= max);
+        let repeated = has_dependencies && !elided && !expanded.insert(child_key);
+        if repeated {
+            line.push_str(" (*)");
+        } else if elided {
+            line.push_str(" …");
+        }
+        writeln!(output, "{prefix}{branch}{line}").unwrap();
+        if !repeated && !elided {
+            let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            render_children(
+                child,
+                packages,
+                expanded,
+                &child_prefix,
+                depth + 1,
+                max_depth,
+                output,
+            );
+        }
+    }
+}
+
+fn format_tree(tree: &DependencyTree, max_depth: Option<usize>, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let rendered = render_tree(tree, max_depth);
+    let dependencies = tree.packages.len().saturating_sub(1);
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# Dependency tree: `{} v{}`\n",
+            tree.crate_name, tree.version
+        )
+        .unwrap();
+        writeln!(output, "{dependencies} transitive dependencies.\n").unwrap();
+        writeln!(output, "```text\n{rendered}```").unwrap();
+    } else {
+        writeln!(
+            output,
+            "Dependency tree: {} v{} ({dependencies} transitive dependencies)\n",
+            tree.crate_name, tree.version
+        )
+        .unwrap();
+        output.push_str(&rendered);
+    }
+    if tree.truncated {
+        writeln!(
+            output,
+            "\nResolution stopped after {MAX_PACKAGES} packages; the tree is incomplete."
+        )
+        .unwrap();
+    }
+    writeln!(
+        output,
+        "\n(*) dependencies already shown above. Dev-dependencies are not included."
+    )
+    .unwrap();
+    output
+}
+
+/// Implementation of the dependency tree tool
+pub struct DependencyTreeToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl DependencyTreeToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for DependencyTreeToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        DependencyTreeTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: DependencyTreeTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let crate_name = params.crate_name.trim();
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' ships with the Rust toolchain and is not published to crates.io"
+                )),
+            ));
+        }
+        let requirement = params
+            .version
+            .as_deref()
+            .map_or_else(|| "latest".to_string(), super::normalize_version);
+        let Some(spec) = VersionSpec::parse(&requirement) else {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Invalid version '{requirement}'")),
+            ));
+        };
+        let features =
+            normalize_features(TOOL_NAME, params.features.as_deref().unwrap_or_default())?;
+        let default_features = params.default_features.unwrap_or(true);
+        let include_build = params.include_build.unwrap_or(true);
+        let max_depth = params.max_depth.filter(|d| *d > 0).map(|d| d as usize);
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let versions =
+            resolve_version::fetch_index_versions(&self.service, crate_name, TOOL_NAME).await?;
+        let Some(selected) = resolve_version::select_version(&versions, &spec) else {
+            return Err(
+                resolve_version::no_matching_version(crate_name, &requirement, &versions).into(),
+            );
+        };
+        let version = selected.version.clone();
+        check_features(
+            TOOL_NAME,
+            crate_name,
+            &version.to_string(),
+            &features,
+            &selected.features,
+        )?;
+
+        let version_str = version.to_string();
+        let sorted_features: BTreeSet<&str> = features.iter().map(String::as_str).collect();
+        let options = format!(
+            "features={};default={default_features};build={include_build}",
+            sorted_features.into_iter().collect::<Vec<_>>().join(",")
+        );
+        let doc_cache = self.service.doc_cache();
+        let cached = doc_cache
+            .get_dependency_tree(crate_name, Some(&version_str), &options)
+            .await
+            .and_then(|cached| serde_json::from_str::<DependencyTree>(&cached).ok());
+        let tree = if let Some(tree) = cached {
+            tree
+        } else {
+            let tree = resolve_tree(
+                &self.service,
+                crate_name,
+                &version,
+                &features,
+                default_features,
+                include_build,
+            )
+            .await?;
+            match serde_json::to_string(&tree) {
+                Ok(json) => {
+                    if let Err(e) = doc_cache
+                        .set_dependency_tree(crate_name, Some(&version_str), &options, json)
+                        .await
+                    {
+                        tracing::warn!(
+                            "[{TOOL_NAME}] failed to cache dependency tree (continuing uncached): {e}"
+                        );
+                    }
+                }
+                Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize dependency tree: {e}"),
+            }
+            tree
+        };
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&tree).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_tree(&tree, max_depth, false),
+            _ => format_tree(&tree, max_depth, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for DependencyTreeToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(line: &str) -> IndexRelease {
+        serde_json::from_str(line).unwrap()
+    }
+
+    fn set(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_activate_follows_feature_syntax() {
+        let release = release(
+            r#"{"vers":"1.0.0","deps":[
+                {"name":"serde","req":"^1","optional":true},
+                {"name":"log","req":"^0.4","optional":true},
+                {"name":"libc","req":"^0.2","target":"cfg(unix)"},
+                {"name":"cc","req":"^1","kind":"build"},
+                {"name":"tempfile","req":"^3","kind":"dev"}
+            ],"features":{"default":["std"],"std":["serde?/std"],"derive":["dep:serde","serde/derive"]}}"#,
+        );
+
+        let (features, deps) = activate(&release, &BTreeSet::new(), true, true);
+        assert_eq!(features, set(&["default", "std"]));
+        let names: Vec<&str> = deps
+            .iter()
+            .map(|(i, _)| release.deps[*i].name.as_str())
+            .collect();
+        // The weak `serde?/std` does not enable serde on its own
+        assert_eq!(names, ["libc", "cc"]);
+
+        let (features, deps) = activate(&release, &set(&["derive", "log"]), true, false);
+        assert_eq!(features, set(&["default", "derive", "log", "std"]));
+        let serde = deps
+            .iter()
+            .find(|(i, _)| release.deps[*i].name == "serde")
+            .unwrap();
+        assert_eq!(serde.1, set(&["derive", "std"]));
+        assert!(deps.iter().any(|(i, _)| release.deps[*i].name == "log"));
+        assert!(!deps.iter().any(|(i, _)| release.deps[*i].name == "cc"));
+    }
+
+    #[test]
+    fn test_select_release_skips_yanked() {
+        let index = parse_index(
+            "{\"vers\":\"1.0.0\"}\n{\"vers\":\"1.2.0\",\"yanked\":true}\n{\"vers\":\"1.1.0\"}\nnot json\n",
+        );
+        assert_eq!(index.len(), 3);
+        let req = VersionReq::parse("^1").unwrap();
+        assert_eq!(select_release(&index, &req).unwrap().to_string(), "1.1.0");
+        let req = VersionReq::parse("^2").unwrap();
+        assert!(select_release(&index, &req).is_none());
+    }
+
+    #[test]
+    fn test_render_tree_marks_repeated_packages() {
+        let edge = |name: &str, version: &str| DependencyEdge {
+            name: name.to_string(),
+            rename: None,
+            req: "^1".to_string(),
+            version: Some(version.to_string()),
+            build: false,
+            optional: false,
+            target: None,
+        };
+        let package = |name: &str, deps: Vec<DependencyEdge>| ResolvedPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            features: Vec::new(),
+            dependencies: deps,
+        };
+        let tree = DependencyTree {
+            crate_name: "app".to_string(),
+            version: "1.0.0".to_string(),
+            packages: vec![
+                package("app", vec![edge("a", "1.0.0"), edge("b", "1.0.0")]),
+                package("a", vec![edge("c", "1.0.0")]),
+                package("b", vec![edge("a", "1.0.0")]),
+                package("c", Vec::new()),
+            ],
+            truncated: false,
+        };
+
+        let rendered = render_tree(&tree, None);
+        assert_eq!(
+            rendered,
+            "app v1.0.0\n├── a v1.0.0\n│   └── c v1.0.0\n└── b v1.0.0\n    └── a v1.0.0 (*)\n"
+        );
+        let shallow = render_tree(&tree, Some(1));
+        assert_eq!(shallow, "app v1.0.0\n├── a v1.0.0 …\n└── b v1.0.0 …\n");
+    }
+}
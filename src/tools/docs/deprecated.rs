@@ -0,0 +1,495 @@
+//! Deprecated items tool
+//!
+//! rustdoc flags `#[deprecated]` items with a "Deprecated" badge in their
+//! module's item table, and shows the full notice (`since` version and note)
+//! at the top of the item's own page. This tool crawls a crate's module pages
+//! for those badges, then fetches the flagged items' pages to read the notes
+//! and pick out the suggested replacement.
+//!
+//! Like `list_feature_gated_items`, only module item tables are scanned:
+//! deprecated methods are listed on their type's page and are not reported.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "list_deprecated_items";
+
+const DEFAULT_MAX_MODULES: u32 = 20;
+
+/// Modules are fetched one by one, so keep the crawl bounded
+const MAX_MODULES: u32 = 60;
+
+/// Item pages fetched for deprecation notes; later items are listed without one
+const MAX_NOTE_PAGES: usize = 40;
+
+static DEPRECATED_BADGE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(".item-table .stab.deprecated").expect("hardcoded valid selector")
+});
+
+static ITEM_NOTICE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("#main-content > .item-info .stab.deprecated")
+        .expect("hardcoded valid selector")
+});
+
+static ANY_NOTICE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("#main-content .stab.deprecated").expect("hardcoded valid selector")
+});
+
+static CODE_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("code").expect("hardcoded valid selector"));
+
+/// Wording that introduces a replacement, e.g. "use `foo` instead"
+static REPLACEMENT_HINT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(use|replaced|instead|favou?r|prefer|see|moved|renamed|superseded)\b")
+        .expect("hardcoded valid regex")
+});
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "list_deprecated_items",
+    title = "List Deprecated Items",
+    description = "List the deprecated public items of a crate version with their deprecation notes (since which version, and why) and the suggested replacement when the note names one. Use this when upgrading a dependency or before relying on an API that may be on its way out.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `list_deprecated_items` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct ListDeprecatedItemsTool {
+    /// Crate name (e.g., "tokio", "serde")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate to inspect, e.g.: tokio, serde, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Maximum number of module pages to scan (range 1-60, defaults to 20)
+    #[json_schema(
+        title = "Max Modules",
+        description = "Maximum number of module pages to scan, starting from the crate root, range 1-60",
+        minimum = 1,
+        maximum = 60,
+        default = 20
+    )]
+    pub max_modules: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (items with their deprecation notes)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// A deprecated item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeprecatedItem {
+    /// Full path, e.g. `demo::old_api`
+    pub path: String,
+    /// rustdoc item kind (`struct`, `fn`, `mod`, ...)
+    pub kind: String,
+    /// Version the item was deprecated in, if stated
+    pub since: Option<String>,
+    /// Deprecation note, if any
+    pub note: Option<String>,
+    /// Suggested replacement named in the note
+    pub replacement: Option<String>,
+}
+
+/// Result of scanning a crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationReport {
+    pub crate_name: String,
+    pub version: String,
+    pub modules_scanned: usize,
+    /// Whether modules were left unscanned because of `max_modules`
+    pub truncated: bool,
+    pub items: Vec<DeprecatedItem>,
+}
+
+/// Deprecation notice parsed from an item page
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Notice {
+    since: Option<String>,
+    note: Option<String>,
+    replacement: Option<String>,
+}
+
+/// Split notice text such as `Deprecated since 1.2.0: use new instead`
+/// into the version and the note
+fn split_notice(text: &str) -> (Option<String>, Option<String>) {
+    let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+    let Some(rest) = text.strip_prefix("Deprecated") else {
+        // "Deprecation planned", "Deprecated in a future version", ...
+        return (None, non_empty(text));
+    };
+    let rest = rest.trim_start();
+    match rest.strip_prefix("since ") {
+        Some(rest) => match rest.split_once(':') {
+            Some((since, note)) => (non_empty(since), non_empty(note)),
+            None => (non_empty(rest), None),
+        },
+        None => (None, non_empty(rest.trim_start_matches(':'))),
+    }
+}
+
+fn parse_notice(element: ElementRef<'_>) -> Notice {
+    let text: String = element.text().collect();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = text.trim_start_matches('👎').trim_start();
+    let (since, note) = split_notice(text);
+    let replacement = note
+        .as_deref()
+        .filter(|note| REPLACEMENT_HINT_RE.is_match(note))
+        .and_then(|_| element.select(&CODE_SELECTOR).next())
+        .map(|code| code.text().collect::<String>().trim().to_string())
+        .filter(|code| !code.is_empty());
+    Notice {
+        since,
+        note,
+        replacement,
+    }
+}
+
+/// The deprecation notice of the item a page documents
+///
+/// The item's own notice sits in the `.item-info` right under the heading;
+/// the fallback covers older rustdoc layouts.
+fn item_notice(html: &str) -> Option<Notice> {
+    let document = Html::parse_document(html);
+    let element = document
+        .select(&ITEM_NOTICE_SELECTOR)
+        .next()
+        .or_else(|| document.select(&ANY_NOTICE_SELECTOR).next())?;
+    Some(parse_notice(element))
+}
+
+/// Deprecated entries of one module page, with their hrefs
+fn scan_module_page(html: &str, module_path: &str) -> Vec<(DeprecatedItem, String)> {
+    let document = Html::parse_document(html);
+    document
+        .select(&DEPRECATED_BADGE_SELECTOR)
+        .filter_map(|badge| {
+            let item = super::crawl::badged_item(badge)?;
+            Some((
+                DeprecatedItem {
+                    path: format!("{module_path}::{}", item.name),
+                    kind: item.kind,
+                    since: None,
+                    note: None,
+                    replacement: None,
+                },
+                item.href,
+            ))
+        })
+        .collect()
+}
+
+fn format_report(report: &DeprecationReport, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let scanned = format!(
+        "Scanned {} module{}{}.",
+        report.modules_scanned,
+        if report.modules_scanned == 1 { "" } else { "s" },
+        if report.truncated {
+            " (limit reached; raise max_modules to scan more)"
+        } else {
+            ""
+        }
+    );
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# Deprecated items in {} {}\n\n{scanned}",
+            report.crate_name, report.version
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            output,
+            "Deprecated items in {} {}\n{scanned}",
+            report.crate_name, report.version
+        )
+        .unwrap();
+    }
+    if report.items.is_empty() {
+        writeln!(output, "\nNo deprecated items found.").unwrap();
+        return output;
+    }
+    if report.items.len() > MAX_NOTE_PAGES {
+        writeln!(
+            output,
+            "Deprecation notes were fetched for the first {MAX_NOTE_PAGES} items only."
+        )
+        .unwrap();
+    }
+    output.push('\n');
+
+    for item in &report.items {
+        let since = item
+            .since
+            .as_ref()
+            .map(|since| format!(" (since {since})"))
+            .unwrap_or_default();
+        if markdown {
+            writeln!(output, "- {} `{}`{since}", item.kind, item.path).unwrap();
+        } else {
+            writeln!(output, "{} {}{since}", item.kind, item.path).unwrap();
+        }
+        if let Some(note) = &item.note {
+            writeln!(output, "  {note}").unwrap();
+        }
+        if let Some(replacement) = &item.replacement {
+            if markdown {
+                writeln!(output, "  Replacement: `{replacement}`").unwrap();
+            } else {
+                writeln!(output, "  Replacement: {replacement}").unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the deprecated items tool
+pub struct ListDeprecatedItemsToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl ListDeprecatedItemsToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn scan(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Result<DeprecationReport, CallToolError> {
+        let crawl =
+            super::crawl::crawl_modules(&self.service, crate_name, version, max_modules, TOOL_NAME)
+                .await?;
+
+        let mut items = Vec::new();
+        let mut notes_fetched = 0;
+        for page in &crawl.pages {
+            for (mut item, href) in scan_module_page(&page.html, &page.path) {
+                if notes_fetched < MAX_NOTE_PAGES {
+                    notes_fetched += 1;
+                    if let Some(notice) = self.fetch_notice(&page.url, &href).await {
+                        item.since = notice.since;
+                        item.note = notice.note;
+                        item.replacement = notice.replacement;
+                    }
+                }
+                items.push(item);
+            }
+        }
+
+        Ok(DeprecationReport {
+            crate_name: crate_name.to_string(),
+            version: version.unwrap_or("latest").to_string(),
+            modules_scanned: crawl.pages.len(),
+            truncated: crawl.truncated,
+            items,
+        })
+    }
+
+    /// Read the notice from an item page; failures leave the item without one
+    async fn fetch_notice(&self, page_url: &str, href: &str) -> Option<Notice> {
+        let url = url::Url::parse(page_url).ok()?.join(href).ok()?;
+        match self
+            .service
+            .fetch_html_optional(url.as_str(), Some(TOOL_NAME))
+            .await
+        {
+            Ok(html) => item_notice(&html?),
+            Err(e) => {
+                tracing::warn!("[{TOOL_NAME}] skipping notice for {url}: {e}");
+                None
+            }
+        }
+    }
+
+    async fn fetch_report(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Result<DeprecationReport, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache
+            .get_deprecated_items(crate_name, version, max_modules)
+            .await
+        {
+            if let Ok(report) = serde_json::from_str(&cached) {
+                return Ok(report);
+            }
+        }
+
+        let report = self.scan(crate_name, version, max_modules).await?;
+        if let Ok(serialized) = serde_json::to_string(&report) {
+            if let Err(e) = doc_cache
+                .set_deprecated_items(crate_name, version, max_modules, serialized)
+                .await
+            {
+                tracing::warn!("[{TOOL_NAME}] failed to cache deprecated items: {e}");
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[async_trait]
+impl Tool for ListDeprecatedItemsToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        ListDeprecatedItemsTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: ListDeprecatedItemsTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let max_modules = params
+            .max_modules
+            .unwrap_or(DEFAULT_MAX_MODULES)
+            .clamp(1, MAX_MODULES) as usize;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let report = self
+            .fetch_report(crate_name, version.as_deref(), max_modules)
+            .await?;
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&report).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_report(&report, false),
+            _ => format_report(&report, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for ListDeprecatedItemsToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_notice() {
+        assert_eq!(
+            split_notice("Deprecated since 1.2.0: use new instead"),
+            (Some("1.2.0".into()), Some("use new instead".into()))
+        );
+        assert_eq!(
+            split_notice("Deprecated since 0.3"),
+            (Some("0.3".into()), None)
+        );
+        assert_eq!(split_notice("Deprecated"), (None, None));
+        assert_eq!(
+            split_notice("Deprecated: no longer needed"),
+            (None, Some("no longer needed".into()))
+        );
+        assert_eq!(
+            split_notice("Deprecation planned"),
+            (None, Some("Deprecation planned".into()))
+        );
+    }
+
+    #[test]
+    fn test_item_notice() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\">",
+            "<div class=\"main-heading\"><h1>Function demo::old</h1></div>",
+            "<span class=\"item-info\"><div class=\"stab deprecated\"><span class=\"emoji\">👎</span>",
+            "<span>Deprecated since 1.1.0: use <code>new_api</code> instead</span></div></span>",
+            "</section></body></html>"
+        );
+        assert_eq!(
+            item_notice(html),
+            Some(Notice {
+                since: Some("1.1.0".into()),
+                note: Some("use new_api instead".into()),
+                replacement: Some("new_api".into()),
+            })
+        );
+
+        // A code span that is not introduced as a replacement is not one.
+        let html = concat!(
+            "<html><body><section id=\"main-content\"><span class=\"item-info\">",
+            "<div class=\"stab deprecated\">Deprecated: <code>unsafe</code> and unsound</div>",
+            "</span></section></body></html>"
+        );
+        assert_eq!(item_notice(html).unwrap().replacement, None);
+        assert_eq!(item_notice("<html><body></body></html>"), None);
+    }
+
+    #[test]
+    fn test_scan_module_page() {
+        let html = concat!(
+            "<html><body><ul class=\"item-table\">",
+            "<li><div class=\"item-name\"><a class=\"fn\" href=\"fn.old.html\">old</a>",
+            "<span class=\"stab deprecated\" title=\"\">Deprecated</span></div></li>",
+            "<li><div class=\"item-name\"><a class=\"fn\" href=\"fn.new_api.html\">new_api</a></div></li>",
+            "</ul></body></html>"
+        );
+        let items = scan_module_page(html, "demo::util");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].0.path, "demo::util::old");
+        assert_eq!(items[0].1, "fn.old.html");
+    }
+}
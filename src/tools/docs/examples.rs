@@ -0,0 +1,430 @@
+//! Documentation code example extraction tool
+//!
+//! Returns only the code examples of a crate front page or an item page,
+//! without the surrounding prose. Each example carries the path of the item
+//! whose documentation it comes from, so examples in method docs are
+//! attributed to the method (`serde_json::Value::as_str`) rather than the
+//! page.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "extract_doc_examples";
+
+/// Doctest attributes rustdoc renders as classes on the example
+const EXAMPLE_ATTRIBUTES: &[&str] = &["ignore", "compile_fail", "should_panic"];
+
+/// Implementation lists whose docs are generic rather than about the item
+const SKIPPED_LISTS: &[&str] = &[
+    "synthetic-implementations-list",
+    "blanket-implementations-list",
+];
+
+static EXAMPLE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("#main-content .docblock pre").expect("hardcoded valid selector")
+});
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "extract_doc_examples",
+    title = "Extract Documentation Examples",
+    description = "Get only the code examples from a crate's documentation front page, or from an item page when item_path is given, without the prose. Each example is labelled with the item it documents (e.g. the method it belongs to). Use this when you need copy-pastable usage code.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `extract_doc_examples` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct ExtractDocExamplesTool {
+    /// Crate name (e.g., "serde", "tokio", "std")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to lookup, e.g.: serde, tokio, std"
+    )]
+    pub crate_name: String,
+
+    /// Item path; the crate front page is used when omitted
+    #[json_schema(
+        title = "Item Path",
+        description = "Optional item path, e.g.: serde_json::Value, std::vec::Vec. Uses the crate front page if not specified"
+    )]
+    pub item_path: Option<String>,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (examples with item path and language)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// A code example from a documentation page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocExample {
+    /// Path of the documented item, e.g. `serde_json::Value::as_str`
+    pub item: String,
+    /// Fence language (`rust`, `toml`, ...), if rustdoc recorded one
+    pub language: Option<String>,
+    /// Doctest attributes such as `ignore` or `should_panic`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
+    pub code: String,
+}
+
+fn has_class(element: ElementRef<'_>, class: &str) -> bool {
+    element.value().classes().any(|c| c == class)
+}
+
+/// Fence language of an example `<pre>`
+fn example_language(pre: ElementRef<'_>) -> Option<String> {
+    let classes: Vec<&str> = pre.value().classes().collect();
+    classes
+        .iter()
+        .find_map(|c| c.strip_prefix("language-"))
+        .filter(|lang| !lang.is_empty())
+        .map(str::to_string)
+        .or_else(|| classes.contains(&"rust").then(|| "rust".to_string()))
+}
+
+/// Doctest attributes, set by rustdoc on the `<pre>` or its `.example-wrap`
+fn example_attributes(pre: ElementRef<'_>) -> Vec<String> {
+    let wrap = pre.parent().and_then(ElementRef::wrap);
+    EXAMPLE_ATTRIBUTES
+        .iter()
+        .filter(|attr| has_class(pre, attr) || wrap.is_some_and(|w| has_class(w, attr)))
+        .map(|attr| (*attr).to_string())
+        .collect()
+}
+
+/// Name of the member a rustdoc member id refers to (`method.as_str` ->
+/// `as_str`), or `None` for impl blocks and other ids
+fn member_name(id: &str) -> Option<&str> {
+    let (kind, name) = id.split_once('.')?;
+    matches!(
+        kind,
+        "method" | "tymethod" | "associatedtype" | "associatedconstant" | "variant" | "structfield"
+    )
+    .then_some(name)
+}
+
+/// The member whose docs contain `docblock`, if any
+///
+/// Methods and associated items sit in a `<details>` toggle whose summary
+/// holds the member's `<section id>`; variants and fields are a heading with
+/// an id directly followed by their docblock.
+fn documented_member(docblock: ElementRef<'_>) -> Option<String> {
+    for ancestor in docblock.ancestors().filter_map(ElementRef::wrap) {
+        if ancestor.value().name() != "details" {
+            continue;
+        }
+        let id = ancestor
+            .children()
+            .filter_map(ElementRef::wrap)
+            .find(|child| child.value().name() == "summary")
+            .and_then(|summary| summary.children().find_map(ElementRef::wrap))
+            .and_then(|section| section.value().id());
+        if let Some(name) = id.and_then(member_name) {
+            return Some(name.to_string());
+        }
+    }
+    docblock
+        .prev_siblings()
+        .find_map(ElementRef::wrap)
+        .and_then(|heading| heading.value().id())
+        .and_then(member_name)
+        .map(str::to_string)
+}
+
+/// Extract the code examples of a rustdoc page
+///
+/// `page_item` is the path the page documents; members' examples get the
+/// member name appended.
+#[must_use]
+pub fn extract_examples(html: &str, page_item: &str) -> Vec<DocExample> {
+    let document = Html::parse_document(html);
+    let mut examples = Vec::new();
+    for pre in document.select(&EXAMPLE_SELECTOR) {
+        let ancestors: Vec<ElementRef<'_>> = pre.ancestors().filter_map(ElementRef::wrap).collect();
+        if ancestors
+            .iter()
+            .any(|a| a.value().id().is_some_and(|id| SKIPPED_LISTS.contains(&id)))
+        {
+            continue;
+        }
+        let code: String = pre.text().collect();
+        let code = code.trim_end();
+        if code.trim().is_empty() {
+            continue;
+        }
+        let item = ancestors
+            .iter()
+            .find(|a| has_class(**a, "docblock"))
+            .and_then(|docblock| documented_member(*docblock))
+            .map_or_else(|| page_item.to_string(), |m| format!("{page_item}::{m}"));
+        examples.push(DocExample {
+            item,
+            language: example_language(pre),
+            attributes: example_attributes(pre),
+            code: code.to_string(),
+        });
+    }
+    examples
+}
+
+/// Path of the documented page: the item path, qualified with the crate
+fn page_item_path(crate_name: &str, item_path: Option<&str>) -> String {
+    let root = crate_name.replace('-', "_");
+    match item_path {
+        Some(path) if path == root || path.starts_with(&format!("{root}::")) => path.to_string(),
+        Some(path) => format!("{root}::{path}"),
+        None => root,
+    }
+}
+
+fn format_examples(page_item: &str, examples: &[DocExample], markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Examples: `{page_item}` ({})\n", examples.len()).unwrap();
+    } else {
+        writeln!(output, "Examples: {page_item} ({})\n", examples.len()).unwrap();
+    }
+    if examples.is_empty() {
+        writeln!(output, "No code examples found.").unwrap();
+        return output;
+    }
+
+    let mut current_item = None;
+    for example in examples {
+        let attributes = example.attributes.join(", ");
+        if markdown {
+            if current_item != Some(&example.item) {
+                writeln!(output, "## `{}`\n", example.item).unwrap();
+                current_item = Some(&example.item);
+            }
+            if !attributes.is_empty() {
+                writeln!(output, "_Doctest: {attributes}_\n").unwrap();
+            }
+            let language = example.language.as_deref().unwrap_or_default();
+            writeln!(output, "```{language}\n{}\n```\n", example.code).unwrap();
+        } else {
+            let language = example
+                .language
+                .as_deref()
+                .map(|l| format!(" [{l}]"))
+                .unwrap_or_default();
+            let attributes = if attributes.is_empty() {
+                attributes
+            } else {
+                format!(" ({attributes})")
+            };
+            writeln!(
+                output,
+                "--- {}{language}{attributes} ---\n{}\n",
+                example.item, example.code
+            )
+            .unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the documentation example extraction tool
+pub struct ExtractDocExamplesToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl ExtractDocExamplesToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Examples of the crate front page, or of `item_path` when given
+    ///
+    /// Shares the lookup tools' page caches.
+    pub(super) async fn fetch_examples(
+        &self,
+        crate_name: &str,
+        item_path: Option<&str>,
+        version: Option<&str>,
+    ) -> Result<Vec<DocExample>, CallToolError> {
+        let html = match item_path {
+            Some(item_path) => {
+                super::lookup_item::LookupItemToolImpl::new(self.service.clone())
+                    .fetch_item_html(crate_name, item_path, version)
+                    .await?
+            }
+            None => {
+                super::lookup_crate::LookupCrateToolImpl::new(self.service.clone())
+                    .fetch_crate_html(crate_name, version)
+                    .await?
+            }
+        };
+        Ok(extract_examples(
+            &html,
+            &page_item_path(crate_name, item_path),
+        ))
+    }
+}
+
+#[async_trait]
+impl Tool for ExtractDocExamplesToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        ExtractDocExamplesTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: ExtractDocExamplesTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let item_path = params
+            .item_path
+            .as_deref()
+            .map(|p| p.trim().trim_end_matches('!'))
+            .filter(|p| !p.is_empty());
+        if let Some(item_path) = item_path {
+            super::validate_item_path(TOOL_NAME, item_path)?;
+        }
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let examples = self
+            .fetch_examples(crate_name, item_path, version.as_deref())
+            .await?;
+        let page_item = page_item_path(crate_name, item_path);
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&examples).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_examples(&page_item, &examples, false),
+            _ => format_examples(&page_item, &examples, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for ExtractDocExamplesToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = concat!(
+        "<html><body><section id=\"main-content\"><h1>Enum demo::Value</h1>",
+        "<pre class=\"rust item-decl\"><code>pub enum Value { Null }</code></pre>",
+        "<details class=\"toggle top-doc\" open><div class=\"docblock\">",
+        "<p>A value.</p>",
+        "<div class=\"example-wrap\"><pre class=\"rust rust-example-rendered\"><code>",
+        "<span class=\"kw\">let</span> v = Value::Null;</code></pre></div>",
+        "<div class=\"example-wrap\"><pre class=\"language-toml\"><code>[dependencies]\ndemo = \"1\"</code></pre></div>",
+        "</div></details>",
+        "<section id=\"variant.Null\" class=\"variant\"><h3 class=\"code-header\">Null</h3></section>",
+        "<div class=\"docblock\"><div class=\"example-wrap should_panic\">",
+        "<pre class=\"rust rust-example-rendered should_panic\"><code>panic!()</code></pre></div></div>",
+        "<div id=\"implementations-list\"><details class=\"toggle implementors-toggle\" open>",
+        "<summary><section id=\"impl-Value\" class=\"impl\"><h3 class=\"code-header\">impl Value</h3></section></summary>",
+        "<div class=\"impl-items\"><details class=\"toggle method-toggle\" open>",
+        "<summary><section id=\"method.is_null\" class=\"method\"><h4 class=\"code-header\">pub fn is_null(&amp;self) -&gt; bool</h4></section></summary>",
+        "<div class=\"docblock\"><p>Checks.</p><pre class=\"rust\"><code>assert!(v.is_null());</code></pre></div>",
+        "</details></div></details></div>",
+        "<div id=\"blanket-implementations-list\"><details class=\"toggle method-toggle\" open>",
+        "<summary><section id=\"method.from\" class=\"method\"></section></summary>",
+        "<div class=\"docblock\"><pre class=\"rust\"><code>T::from(t)</code></pre></div></details></div>",
+        "</section></body></html>"
+    );
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = extract_examples(PAGE, "demo::Value");
+        let items: Vec<&str> = examples.iter().map(|e| e.item.as_str()).collect();
+        assert_eq!(
+            items,
+            [
+                "demo::Value",
+                "demo::Value",
+                "demo::Value::Null",
+                "demo::Value::is_null"
+            ]
+        );
+        assert_eq!(examples[0].code, "let v = Value::Null;");
+        assert_eq!(examples[0].language.as_deref(), Some("rust"));
+        assert_eq!(examples[1].language.as_deref(), Some("toml"));
+        assert_eq!(examples[2].attributes, ["should_panic"]);
+        assert!(examples[3].attributes.is_empty());
+    }
+
+    #[test]
+    fn test_page_item_path() {
+        assert_eq!(page_item_path("serde-json", None), "serde_json");
+        assert_eq!(
+            page_item_path("serde_json", Some("Value")),
+            "serde_json::Value"
+        );
+        assert_eq!(
+            page_item_path("serde_json", Some("serde_json::Value")),
+            "serde_json::Value"
+        );
+    }
+
+    #[test]
+    fn test_format_examples_groups_by_item() {
+        let examples = extract_examples(PAGE, "demo::Value");
+        let markdown = format_examples("demo::Value", &examples, true);
+        assert_eq!(markdown.matches("## `demo::Value`").count(), 1);
+        assert!(markdown.contains("```toml\n[dependencies]\ndemo = \"1\"\n```"));
+        assert!(markdown.contains("_Doctest: should_panic_"));
+
+        let text = format_examples("demo::Value", &[], false);
+        assert!(text.contains("No code examples found."));
+    }
+}
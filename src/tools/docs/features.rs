@@ -0,0 +1,423 @@
+//! Feature-gated items tool
+//!
+//! rustdoc marks items built under `#[doc(cfg(...))]` (or `doc_auto_cfg`) with
+//! a "portability" badge in every module's item table, e.g. *Available on
+//! crate feature `derive` only*. This tool crawls a crate's module pages,
+//! collects those badges and groups the items by the cargo features they
+//! need, so callers can enable the right feature before using an item.
+//!
+//! Only module item tables are scanned: gates on individual methods or trait
+//! impls appear on the type's own page and are not reported.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "list_feature_gated_items";
+
+const DEFAULT_MAX_MODULES: u32 = 20;
+
+/// Modules are fetched one by one, so keep the crawl bounded
+const MAX_MODULES: u32 = 60;
+
+static PORTABILITY_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(".item-table .stab.portability").expect("hardcoded valid selector")
+});
+
+static FEATURE_NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`([^`]+)`").expect("hardcoded valid regex"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "list_feature_gated_items",
+    title = "List Feature-Gated Items",
+    description = "List the public items of a crate that are only available with certain cargo features (from the #[doc(cfg)] badges on docs.rs), grouped by feature. Use this before calling an API to find out which feature must be enabled, or pass a feature to see everything it unlocks.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `list_feature_gated_items` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct ListFeatureGatedItemsTool {
+    /// Crate name (e.g., "tokio", "serde")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate to inspect, e.g.: tokio, serde, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Only list items gated on this feature
+    #[json_schema(
+        title = "Feature",
+        description = "Only list items that require this cargo feature, e.g.: fs for tokio"
+    )]
+    pub feature: Option<String>,
+
+    /// Maximum number of module pages to scan (range 1-60, defaults to 20)
+    #[json_schema(
+        title = "Max Modules",
+        description = "Maximum number of module pages to scan, starting from the crate root, range 1-60",
+        minimum = 1,
+        maximum = 60,
+        default = 20
+    )]
+    pub max_modules: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (items with their required features)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// An item carrying a portability badge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatedItem {
+    /// Full path, e.g. `tokio::fs::File`
+    pub path: String,
+    /// rustdoc item kind (`struct`, `fn`, `mod`, ...)
+    pub kind: String,
+    /// Cargo features named in the badge
+    pub features: Vec<String>,
+    /// Full condition as shown by rustdoc, e.g. `crate feature fs`
+    pub condition: String,
+}
+
+/// Result of scanning a crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureGateReport {
+    pub crate_name: String,
+    pub version: String,
+    pub modules_scanned: usize,
+    /// Whether modules were left unscanned because of `max_modules`
+    pub truncated: bool,
+    pub items: Vec<GatedItem>,
+}
+
+impl FeatureGateReport {
+    /// Items grouped by feature; items gated only on other conditions (target
+    /// OS, `docsrs`, ...) are grouped under an empty key
+    fn by_feature(&self) -> BTreeMap<&str, Vec<&GatedItem>> {
+        let mut groups: BTreeMap<&str, Vec<&GatedItem>> = BTreeMap::new();
+        for item in &self.items {
+            if item.features.is_empty() {
+                groups.entry("").or_default().push(item);
+            }
+            for feature in &item.features {
+                groups.entry(feature).or_default().push(item);
+            }
+        }
+        groups
+    }
+
+    fn retain_feature(&mut self, feature: &str) {
+        self.items
+            .retain(|item| item.features.iter().any(|f| f == feature));
+    }
+}
+
+/// Cargo features named in a portability badge title
+///
+/// Titles read like ``Available on crate feature `fs` only`` or ``Available
+/// on Unix and crate features `net` and `rt` only``; only backticked names
+/// after the first "crate feature" count, which skips bare cfgs such as
+/// `` `docsrs` ``.
+fn badge_features(title: &str) -> Vec<String> {
+    let Some(start) = title.find("crate feature") else {
+        return Vec::new();
+    };
+    let mut features: Vec<String> = Vec::new();
+    for capture in FEATURE_NAME_RE.captures_iter(&title[start..]) {
+        let name = capture[1].to_string();
+        if !features.contains(&name) {
+            features.push(name);
+        }
+    }
+    features
+}
+
+/// Badge title without the "Available on ... only" framing and backticks
+fn badge_condition(title: &str) -> String {
+    let condition = title.trim();
+    let condition = condition.strip_prefix("Available on ").unwrap_or(condition);
+    let condition = condition.strip_suffix(" only").unwrap_or(condition);
+    condition.replace('`', "")
+}
+
+/// Collect gated items from one module page
+fn scan_module_page(html: &str, module_path: &str) -> Vec<GatedItem> {
+    let document = Html::parse_document(html);
+    document
+        .select(&PORTABILITY_SELECTOR)
+        .filter_map(|badge| {
+            let title = badge.value().attr("title")?;
+            let item = super::crawl::badged_item(badge)?;
+            Some(GatedItem {
+                path: format!("{module_path}::{}", item.name),
+                kind: item.kind,
+                features: badge_features(title),
+                condition: badge_condition(title),
+            })
+        })
+        .collect()
+}
+
+fn format_report(report: &FeatureGateReport, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let scanned = format!(
+        "Scanned {} module{}{}.",
+        report.modules_scanned,
+        if report.modules_scanned == 1 { "" } else { "s" },
+        if report.truncated {
+            " (limit reached; raise max_modules to scan more)"
+        } else {
+            ""
+        }
+    );
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# Feature-gated items in {} {}\n\n{scanned}",
+            report.crate_name, report.version
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            output,
+            "Feature-gated items in {} {}\n{scanned}",
+            report.crate_name, report.version
+        )
+        .unwrap();
+    }
+    if report.items.is_empty() {
+        writeln!(output, "\nNo feature-gated items found.").unwrap();
+        return output;
+    }
+
+    for (feature, items) in report.by_feature() {
+        let heading = if feature.is_empty() {
+            "Other cfg conditions".to_string()
+        } else if markdown {
+            format!("Feature `{feature}`")
+        } else {
+            format!("Feature {feature}")
+        };
+        if markdown {
+            writeln!(output, "\n## {heading}\n").unwrap();
+        } else {
+            writeln!(output, "\n{heading}:").unwrap();
+        }
+        for item in items {
+            // Show the full condition when it is more than this one feature.
+            let extra = if item.condition == format!("crate feature {feature}") {
+                String::new()
+            } else {
+                format!(" ({})", item.condition)
+            };
+            if markdown {
+                writeln!(output, "- {} `{}`{extra}", item.kind, item.path).unwrap();
+            } else {
+                writeln!(output, "  {} {}{extra}", item.kind, item.path).unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the feature-gated items tool
+pub struct ListFeatureGatedItemsToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl ListFeatureGatedItemsToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn scan(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Result<FeatureGateReport, CallToolError> {
+        let crawl =
+            super::crawl::crawl_modules(&self.service, crate_name, version, max_modules, TOOL_NAME)
+                .await?;
+        Ok(FeatureGateReport {
+            crate_name: crate_name.to_string(),
+            version: version.unwrap_or("latest").to_string(),
+            modules_scanned: crawl.pages.len(),
+            truncated: crawl.truncated,
+            items: crawl
+                .pages
+                .iter()
+                .flat_map(|page| scan_module_page(&page.html, &page.path))
+                .collect(),
+        })
+    }
+
+    async fn fetch_report(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        max_modules: usize,
+    ) -> Result<FeatureGateReport, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache
+            .get_feature_gates(crate_name, version, max_modules)
+            .await
+        {
+            if let Ok(report) = serde_json::from_str(&cached) {
+                return Ok(report);
+            }
+        }
+
+        let report = self.scan(crate_name, version, max_modules).await?;
+        if let Ok(serialized) = serde_json::to_string(&report) {
+            if let Err(e) = doc_cache
+                .set_feature_gates(crate_name, version, max_modules, serialized)
+                .await
+            {
+                tracing::warn!("[{TOOL_NAME}] failed to cache feature gates: {e}");
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[async_trait]
+impl Tool for ListFeatureGatedItemsToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        ListFeatureGatedItemsTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: ListFeatureGatedItemsTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let max_modules = params
+            .max_modules
+            .unwrap_or(DEFAULT_MAX_MODULES)
+            .clamp(1, MAX_MODULES) as usize;
+        let feature = params
+            .feature
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty());
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let mut report = self
+            .fetch_report(crate_name, version.as_deref(), max_modules)
+            .await?;
+        if let Some(feature) = feature {
+            report.retain_feature(feature);
+        }
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&report).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_report(&report, false),
+            _ => format_report(&report, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for ListFeatureGatedItemsToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_features() {
+        assert_eq!(
+            badge_features("Available on crate feature `fs` only"),
+            vec!["fs"]
+        );
+        assert_eq!(
+            badge_features("Available on Unix and crate features `net` and `rt` only"),
+            vec!["net", "rt"]
+        );
+        assert!(badge_features("Available on `docsrs` only").is_empty());
+        assert_eq!(
+            badge_condition("Available on Unix and crate feature `net` only"),
+            "Unix and crate feature net"
+        );
+    }
+
+    #[test]
+    fn test_scan_module_page() {
+        let html = concat!(
+            "<html><body><dl class=\"item-table\">",
+            "<dt><a class=\"mod\" href=\"fs/index.html\" title=\"mod demo::fs\">fs</a><wbr>",
+            "<span class=\"stab portability\" title=\"Available on crate feature `fs` only\"><code>fs</code></span></dt>",
+            "<dt><a class=\"mod\" href=\"../other/index.html\">other</a></dt>",
+            "<dt><a class=\"struct\" href=\"struct.Plain.html\">Plain</a></dt>",
+            "</dl><ul class=\"item-table\"><li><div class=\"item-name\">",
+            "<a class=\"fn\" href=\"fn.spawn.html\">spawn</a>",
+            "<span class=\"stab portability\" title=\"Available on crate features `rt` and `net` only\"><code>rt and net</code></span>",
+            "</div></li></ul></body></html>"
+        );
+        let items = scan_module_page(html, "demo");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].path, "demo::fs");
+        assert_eq!(items[0].kind, "mod");
+        assert_eq!(items[1].path, "demo::spawn");
+        assert_eq!(items[1].features, vec!["rt", "net"]);
+    }
+}
@@ -0,0 +1,285 @@
+//! Content filters for rustdoc pages
+//!
+//! rustdoc appends long, mostly boilerplate impl sections to every type page:
+//! auto trait impls (`Send`, `Sync`, `Unpin`, ...), blanket impls (`From<T>`,
+//! `Any`, `Borrow<T>`, ...) with all of their methods, and on trait pages the
+//! full list of implementors. These sections frequently outweigh the item's
+//! own documentation. A [`ContentFilters`] set decides, per section, whether
+//! it is kept as is, collapsed to a list of its `impl` headers, or stripped.
+//!
+//! Filters are off by default. They are configured globally in the `[docs]`
+//! section and can be overridden per request with the lookup tools' `filters`
+//! parameter. Source-code links are always removed during extraction and need
+//! no filter.
+
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+/// What to do with a filtered section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterMode {
+    /// Leave the section untouched
+    #[default]
+    Keep,
+    /// Replace the section body with a list of its `impl` headers
+    Collapse,
+    /// Remove the section and its heading
+    Strip,
+}
+
+impl FilterMode {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "keep" => Some(Self::Keep),
+            "collapse" => Some(Self::Collapse),
+            "strip" => Some(Self::Strip),
+            _ => None,
+        }
+    }
+}
+
+/// A rustdoc section that can be filtered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    AutoTraitImpls,
+    BlanketImpls,
+    TraitImpls,
+    Implementors,
+}
+
+impl Section {
+    const ALL: [Self; 4] = [
+        Self::AutoTraitImpls,
+        Self::BlanketImpls,
+        Self::TraitImpls,
+        Self::Implementors,
+    ];
+
+    /// Name used in configuration and the `filters` tool parameter
+    fn name(self) -> &'static str {
+        match self {
+            Self::AutoTraitImpls => "auto_trait_impls",
+            Self::BlanketImpls => "blanket_impls",
+            Self::TraitImpls => "trait_impls",
+            Self::Implementors => "implementors",
+        }
+    }
+
+    /// `id` of the section's `<h2>`; its body is the `{id}-list` element
+    fn anchor(self) -> &'static str {
+        match self {
+            Self::AutoTraitImpls => "synthetic-implementations",
+            Self::BlanketImpls => "blanket-implementations",
+            Self::TraitImpls => "trait-implementations",
+            Self::Implementors => "implementors",
+        }
+    }
+}
+
+static IMPL_HEADER_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("h3.code-header").expect("hardcoded valid selector"));
+
+/// Per-section filter settings
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - like the rest of `[docs]`, filters are
+/// read once when the server starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContentFilters {
+    /// Auto trait implementations (`Send`, `Sync`, `Unpin`, ...)
+    pub auto_trait_impls: FilterMode,
+    /// Blanket implementations (`From<T>`, `Any`, `Borrow<T>`, ...)
+    pub blanket_impls: FilterMode,
+    /// Explicit trait implementations
+    pub trait_impls: FilterMode,
+    /// Implementors listed on trait pages
+    pub implementors: FilterMode,
+}
+
+impl ContentFilters {
+    fn mode(self, section: Section) -> FilterMode {
+        match section {
+            Section::AutoTraitImpls => self.auto_trait_impls,
+            Section::BlanketImpls => self.blanket_impls,
+            Section::TraitImpls => self.trait_impls,
+            Section::Implementors => self.implementors,
+        }
+    }
+
+    fn mode_mut(&mut self, section: Section) -> &mut FilterMode {
+        match section {
+            Section::AutoTraitImpls => &mut self.auto_trait_impls,
+            Section::BlanketImpls => &mut self.blanket_impls,
+            Section::TraitImpls => &mut self.trait_impls,
+            Section::Implementors => &mut self.implementors,
+        }
+    }
+
+    /// Whether any section is filtered
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        Section::ALL
+            .iter()
+            .any(|&section| self.mode(section) != FilterMode::Keep)
+    }
+
+    /// Apply per-request overrides on top of these filters
+    ///
+    /// Each entry is `section` (strip) or `section=mode`, where `mode` is
+    /// `keep`, `collapse` or `strip`, e.g. `["auto_trait_impls",
+    /// "blanket_impls=collapse"]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first unknown section or mode
+    pub fn with_overrides(mut self, overrides: &[String]) -> Result<Self, String> {
+        for entry in overrides {
+            let entry = entry.trim().to_ascii_lowercase();
+            let (name, mode) = entry.split_once('=').unwrap_or((&entry, "strip"));
+            let section = Section::ALL
+                .into_iter()
+                .find(|section| section.name() == name.trim())
+                .ok_or_else(|| {
+                    let names: Vec<&str> = Section::ALL.iter().map(|s| s.name()).collect();
+                    format!(
+                        "Unknown filter '{}'. Expected one of: {}",
+                        name.trim(),
+                        names.join(", ")
+                    )
+                })?;
+            *self.mode_mut(section) = FilterMode::parse(mode.trim()).ok_or_else(|| {
+                format!(
+                    "Unknown filter mode '{}'. Expected one of: keep, collapse, strip",
+                    mode.trim()
+                )
+            })?;
+        }
+        Ok(self)
+    }
+
+    /// Filter a rustdoc page, returning it unchanged when no filter is active
+    #[must_use]
+    pub fn apply<'a>(&self, html: &'a str) -> Cow<'a, str> {
+        if !self.is_active() {
+            return Cow::Borrowed(html);
+        }
+
+        let document = Html::parse_document(html);
+        let mut replacements: Vec<(String, String)> = Vec::new();
+        for section in Section::ALL {
+            let mode = self.mode(section);
+            if mode == FilterMode::Keep {
+                continue;
+            }
+            let Ok(heading) = Selector::parse(&format!("h2#{}", section.anchor())) else {
+                continue;
+            };
+            let Ok(list) = Selector::parse(&format!("#{}-list", section.anchor())) else {
+                continue;
+            };
+            let Some(body) = document.select(&list).next() else {
+                continue;
+            };
+
+            if mode == FilterMode::Strip {
+                if let Some(heading) = document.select(&heading).next() {
+                    replacements.push((heading.html(), String::new()));
+                }
+                replacements.push((body.html(), String::new()));
+            } else {
+                let mut collapsed = String::from("<ul>");
+                for header in body.select(&IMPL_HEADER_SELECTOR) {
+                    let text: String = header.text().collect();
+                    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    collapsed.push_str("<li><code>");
+                    collapsed.push_str(&super::html::escape_html_text(&text));
+                    collapsed.push_str("</code></li>");
+                }
+                collapsed.push_str("</ul>");
+                replacements.push((body.html(), collapsed));
+            }
+        }
+
+        // Replace against the parser's own serialization, which the
+        // `element.html()` fragments are guaranteed to appear in verbatim.
+        let mut result = document.root_element().html();
+        for (fragment, replacement) in replacements {
+            result = result.replacen(&fragment, &replacement, 1);
+        }
+        Cow::Owned(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = concat!(
+        "<html><body><section id=\"main-content\">",
+        "<div class=\"docblock\"><p>A demo type.</p></div>",
+        "<h2 id=\"synthetic-implementations\" class=\"section-header\">Auto Trait Implementations</h2>",
+        "<div id=\"synthetic-implementations-list\">",
+        "<section id=\"impl-Send-for-Demo\" class=\"impl\"><h3 class=\"code-header\">impl Send for Demo</h3></section>",
+        "<section id=\"impl-Sync-for-Demo\" class=\"impl\"><h3 class=\"code-header\">impl Sync for Demo</h3></section>",
+        "</div>",
+        "<h2 id=\"blanket-implementations\" class=\"section-header\">Blanket Implementations</h2>",
+        "<div id=\"blanket-implementations-list\"><details><summary>",
+        "<section id=\"impl-Any-for-T\" class=\"impl\"><h3 class=\"code-header\">impl&lt;T&gt; Any for T\n<div class=\"where\">where T: 'static</div></h3></section>",
+        "</summary><div class=\"impl-items\"><h4 class=\"code-header\">fn type_id(&amp;self) -&gt; TypeId</h4>",
+        "<div class=\"docblock\"><p>Gets the TypeId of self.</p></div></div></details></div>",
+        "</section></body></html>"
+    );
+
+    #[test]
+    fn test_inactive_filters_borrow_input() {
+        let filters = ContentFilters::default();
+        assert!(!filters.is_active());
+        assert!(matches!(filters.apply(PAGE), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_strip_and_collapse_sections() {
+        let filters = ContentFilters {
+            auto_trait_impls: FilterMode::Strip,
+            blanket_impls: FilterMode::Collapse,
+            ..ContentFilters::default()
+        };
+        let filtered = filters.apply(PAGE);
+
+        assert!(filtered.contains("A demo type."));
+        assert!(!filtered.contains("Auto Trait Implementations"));
+        assert!(!filtered.contains("impl Send for Demo"));
+        assert!(filtered.contains("Blanket Implementations"));
+        assert!(filtered.contains("<li><code>impl&lt;T&gt; Any for T where T: 'static</code></li>"));
+        assert!(!filtered.contains("Gets the TypeId"));
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let filters = ContentFilters {
+            trait_impls: FilterMode::Collapse,
+            ..ContentFilters::default()
+        }
+        .with_overrides(&[
+            "auto_trait_impls".to_string(),
+            " Blanket_Impls = collapse ".to_string(),
+            "trait_impls=keep".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(filters.auto_trait_impls, FilterMode::Strip);
+        assert_eq!(filters.blanket_impls, FilterMode::Collapse);
+        assert_eq!(filters.trait_impls, FilterMode::Keep);
+
+        assert!(ContentFilters::default()
+            .with_overrides(&["source_links".to_string()])
+            .is_err());
+        assert!(ContentFilters::default()
+            .with_overrides(&["implementors=hide".to_string()])
+            .is_err());
+    }
+}
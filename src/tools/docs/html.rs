@@ -4,7 +4,7 @@
 //! Uses the `scraper` crate for robust HTML5 parsing.
 
 use regex::Regex;
-use scraper::{Html, Selector};
+use scraper::{node::Node, ElementRef, Html, Selector};
 use std::borrow::Cow;
 use std::sync::LazyLock;
 
@@ -339,31 +339,39 @@ static BODY_SELECTOR: LazyLock<Selector> =
 static ALL_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("*").expect("hardcoded valid selector"));
 
-/// Cached selectors for skip tags (script, style, noscript, iframe)
-static SCRIPT_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("script").expect("hardcoded valid selector"));
-static STYLE_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("style").expect("hardcoded valid selector"));
-static NOSCRIPT_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("noscript").expect("hardcoded valid selector"));
-static IFRAME_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("iframe").expect("hardcoded valid selector"));
-
-/// Cached selectors for nav tags (nav, header, footer, aside)
-static NAV_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("nav").expect("hardcoded valid selector"));
-static HEADER_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("header").expect("hardcoded valid selector"));
-static FOOTER_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("footer").expect("hardcoded valid selector"));
-static ASIDE_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("aside").expect("hardcoded valid selector"));
-
-/// Cached selectors for UI tags (button, summary)
-static BUTTON_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("button").expect("hardcoded valid selector"));
-static SUMMARY_SELECTOR: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("summary").expect("hardcoded valid selector"));
+/// Elements [`clean_html`] drops together with their content: executable,
+/// embedded and styling content, page chrome (navigation, headers, footers,
+/// sidebars, buttons, forms and rustdoc's search/toolbar/topbar web
+/// components) and metadata. Chrome marked up with ordinary elements is
+/// matched by [`CHROME_SELECTOR`] instead.
+const DROPPED_TAGS: &[&str] = &[
+    "script",
+    "style",
+    "noscript",
+    "iframe",
+    "template",
+    "nav",
+    "header",
+    "footer",
+    "aside",
+    "button",
+    "link",
+    "meta",
+    "form",
+    "rustdoc-search",
+    "rustdoc-toolbar",
+    "rustdoc-topbar",
+];
+
+/// Elements [`clean_html`] replaces by their children: rustdoc's collapsible
+/// toggles, which `html2md` would otherwise leave as raw tags
+const UNWRAPPED_TAGS: &[&str] = &["details"];
+
+/// HTML void elements, serialized without an end tag
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
 
 /// Regex to strip rustdoc source-code links (`<a class="src ...">Source</a>`)
 /// from raw HTML *before* parsing.
@@ -455,41 +463,6 @@ static JS_ANCHOR_HTML_REGEX: LazyLock<Regex> = LazyLock::new(|| {
         .expect("hardcoded valid regex pattern")
 });
 
-/// Regex to remove `<script>`, `<style>`, `<noscript>` and `<iframe>` elements
-/// (including their contents) from raw HTML *before* parsing.
-///
-/// The DOM-based pass in [`remove_unwanted_elements`] re-serializes each node
-/// via `ElementRef::html()` and string-replaces it in the original markup. That
-/// match is fragile: html5ever normalizes attribute whitespace and quoting, so
-/// markup like `<script  defer >` is serialized as `<script defer>` and the
-/// replacement silently misses, leaking executable/style content into the
-/// `html` output format. Stripping these tags with a tolerant regex first
-/// guarantees they are removed regardless of the original formatting. (Back-
-/// references are unsupported by the `regex` crate, so each tag is listed
-/// explicitly rather than captured once.)
-static DANGEROUS_ELEMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"(?is)<script\b[^>]*>.*?</script\s*>|<style\b[^>]*>.*?</style\s*>|<noscript\b[^>]*>.*?</noscript\s*>|<iframe\b[^>]*>.*?</iframe\s*>|<iframe\b[^>]*/>",
-    )
-    .expect("hardcoded valid regex pattern")
-});
-
-/// Regex to remove rustdoc UI web-components from raw HTML before parsing.
-///
-/// Modern rustdoc emits custom elements for its chrome: `<rustdoc-toolbar>`
-/// (the settings/options toolbar, rendered empty in static HTML) and
-/// `<rustdoc-topbar>` (a duplicate breadcrumb such as
-/// `<h2><a href="#">Iterator</a></h2>`). The toolbar sits inside
-/// `#main-content`, so it leaks into the `html` output as a stray empty tag;
-/// the topbar can leak a redundant heading. Neither carries documentation
-/// value, so both are stripped (paired and self-closing forms).
-static RUSTDOC_UI_ELEMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"(?is)<rustdoc-(?:toolbar|topbar)\b[^>]*>.*?</rustdoc-(?:toolbar|topbar)\s*>|<rustdoc-(?:toolbar|topbar)\b[^>]*/>",
-    )
-    .expect("hardcoded valid regex pattern")
-});
-
 /// Regex to remove the rustdoc navigation breadcrumb element.
 ///
 /// rustdoc renders a breadcrumb above each item title, e.g.
@@ -562,7 +535,7 @@ static HIDEME_SUMMARY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 ///
 /// rustdoc renders an impl block's own documentation (e.g. a "Basic API"
 /// heading) as `<div class="docblock">...</div>` *inside* the `<summary>` that
-/// also holds the `impl ...` declaration. Because [`remove_unwanted_elements`]
+/// also holds the `impl ...` declaration. Because [`serialize_cleaned`]
 /// flattens `<summary>` nodes to their decoded text, that docblock glues onto
 /// the declaration (e.g. `impl ArgBasic API`). Group 1 captures the docblock
 /// contents so the wrapper can be relocated *after* the `</summary>`, where it
@@ -687,6 +660,21 @@ static MAIN_CONTENT_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("#main-content").expect("hardcoded valid selector"));
 static RUSTDOC_BODY_WRAPPER_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("#rustdoc_body_wrapper").expect("hardcoded valid selector"));
+static MAIN_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("main, [role=\"main\"]").expect("hardcoded valid selector"));
+
+/// docs.rs and rustdoc navigation chrome that is not marked up as `<nav>`:
+/// the docs.rs top bar, the rustdoc sidebar and mobile top bar, the search
+/// form and results and the settings and help menus. Ids are qualified by
+/// element so headings that happen to share them (`## Search`) survive.
+static CHROME_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        ".nav-container, .sidebar, .sidebar-resizer, .mobile-topbar, .search-form, \
+         .search-container, section#search, div#settings, div#settings-menu, div#help, \
+         div#help-button",
+    )
+    .expect("hardcoded valid selector")
+});
 static H1_SELECTOR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("h1").expect("hardcoded valid selector"));
 
@@ -914,11 +902,10 @@ fn rewrite_stab_badges(html: &str) -> String {
 
 /// Clean HTML by removing unwanted tags and their content
 ///
-/// Uses the `scraper` crate for robust HTML5 parsing, which handles
-/// malformed HTML better than manual parsing.
-///
-/// This function performs a single-pass HTML parsing and removal of all
-/// unwanted elements to minimize parsing overhead.
+/// After a few rustdoc-specific rewrites, the HTML is parsed with `scraper`
+/// (a spec-compliant HTML5 parser) and the parsed tree is serialized back
+/// without the unwanted elements (see [`serialize_cleaned`]), so the result
+/// holds well-formed markup whatever the input looked like.
 #[must_use]
 pub fn clean_html(html: &str) -> String {
     // Strip source-code anchors from the raw HTML first so their "Source" label
@@ -939,13 +926,6 @@ pub fn clean_html(html: &str) -> String {
     // flattened <summary> does not fuse it onto the next signature
     // (`1.0.0fn clone_from`). See SINCE_BADGE_GLUED_REGEX.
     let html = SINCE_BADGE_GLUED_REGEX.replace_all(&html, "${1} <");
-    // Guarantee removal of executable/style/embedded content regardless of how
-    // the source markup was formatted (see DANGEROUS_ELEMENT_REGEX docs).
-    let html = DANGEROUS_ELEMENT_REGEX.replace_all(&html, "");
-    // Strip rustdoc UI web-components (toolbar/topbar chrome) so they do not
-    // leak into the html output or as a redundant heading (see
-    // RUSTDOC_UI_ELEMENT_REGEX).
-    let html = RUSTDOC_UI_ELEMENT_REGEX.replace_all(&html, "");
     // Remove the rustdoc navigation breadcrumb above the item title; its
     // page-relative links would otherwise be downgraded to a dangling bare
     // line (e.g. `std::vec`, or a lone `std` on macro pages) that merely
@@ -1009,7 +989,12 @@ pub fn clean_html(html: &str) -> String {
         r#"</h3></section></summary><div class="docblock">${1}</div>"#,
     );
     let document = Html::parse_document(&html);
-    remove_unwanted_elements(&document, &html)
+    let mut cleaned = String::with_capacity(html.len());
+    match document.select(&BODY_SELECTOR).next() {
+        Some(body) => serialize_cleaned_children(body, &mut cleaned),
+        None => serialize_cleaned(document.root_element(), &mut cleaned),
+    }
+    apply_regex_patterns(&cleaned)
 }
 
 /// HTML-escape the special characters `&`, `<`, and `>` in plain text.
@@ -1020,102 +1005,84 @@ pub fn clean_html(html: &str) -> String {
 /// silently dropped. `&` is escaped first so the replacement is idempotent for a
 /// single pass.
 #[must_use]
-fn escape_html_text(text: &str) -> String {
+pub(crate) fn escape_html_text(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
 }
 
-/// Remove unwanted elements from HTML using scraper for parsing
-///
-/// This function performs optimized single-pass removal of all unwanted elements
-/// using cached selectors for better performance.
-///
-/// Removes: script, style, noscript, iframe, nav, header, footer, aside, button
-/// Preserves summary content while removing the tag itself.
-#[inline]
-fn remove_unwanted_elements(document: &Html, original_html: &str) -> String {
-    // Collect all elements to process with their positions for efficient replacement
-    let mut replacements: Vec<(String, Option<String>)> = Vec::new();
-
-    // Process script, style, noscript, iframe - remove completely (using cached selectors)
-    for element in document.select(&SCRIPT_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
-    for element in document.select(&STYLE_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
-    for element in document.select(&NOSCRIPT_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
-    for element in document.select(&IFRAME_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
+/// Whether [`clean_html`] leaves `element` out with its content
+fn is_dropped(element: ElementRef<'_>) -> bool {
+    DROPPED_TAGS.contains(&element.value().name()) || CHROME_SELECTOR.matches(&element)
+}
 
-    // Process nav, header, footer, aside - remove completely (using cached selectors)
-    for element in document.select(&NAV_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
-    for element in document.select(&HEADER_SELECTOR) {
-        replacements.push((element.html(), None));
-    }
-    for element in document.select(&FOOTER_SELECTOR) {
-        replacements.push((element.html(), None));
+/// Serialize a parsed node, leaving out what [`clean_html`] removes
+///
+/// Works on the parsed tree rather than on markup, so comments, attribute
+/// values containing `>` and malformed markup cannot hide content from it:
+/// - [`DROPPED_TAGS`] and [`CHROME_SELECTOR`] elements, comments, doctypes and
+///   processing instructions are left out with everything they contain
+/// - [`UNWRAPPED_TAGS`] elements are replaced by their children
+/// - `<summary>` elements are replaced by their (re-escaped) text, since
+///   `html2md` renders rustdoc's signature toggles poorly
+fn serialize_cleaned(element: ElementRef<'_>, out: &mut String) {
+    let name = element.value().name();
+    if is_dropped(element) {
+        return;
     }
-    for element in document.select(&ASIDE_SELECTOR) {
-        replacements.push((element.html(), None));
+    if name == "summary" {
+        // The text is decoded (`Option<usize>`); re-escape it so the markup
+        // survives being parsed again by `html2md`.
+        let mut text = String::new();
+        collect_kept_text(element, &mut text);
+        out.push_str(&escape_html_text(&text));
+        return;
     }
-
-    // Process button and summary - special handling for summary (using cached selectors)
-    for element in document.select(&BUTTON_SELECTOR) {
-        replacements.push((element.html(), None));
+    let unwrapped = UNWRAPPED_TAGS.contains(&name);
+    if !unwrapped {
+        out.push('<');
+        out.push_str(name);
+        for (attr, value) in element.value().attrs() {
+            out.push(' ');
+            out.push_str(attr);
+            out.push_str("=\"");
+            out.push_str(&escape_html_attribute(value));
+            out.push('"');
+        }
+        out.push('>');
+        if VOID_TAGS.contains(&name) {
+            return;
+        }
     }
-    for element in document.select(&SUMMARY_SELECTOR) {
-        let element_html = element.html();
-        // For summary tags, extract and keep the text content. `text()` returns
-        // *decoded* text, so generic markup such as `Option&lt;usize&gt;`
-        // becomes literal `Option<usize>`. This string is later re-parsed by
-        // `html2md`/`scraper`, which would treat `<usize>` as an unknown tag and
-        // drop it; re-escape the markup so it survives the second parse.
-        let text_content: String = element.text().collect();
-        replacements.push((element_html, Some(escape_html_text(&text_content))));
+    serialize_cleaned_children(element, out);
+    if !unwrapped {
+        out.push_str("</");
+        out.push_str(name);
+        out.push('>');
     }
+}
 
-    // If no replacements needed, just apply regex patterns
-    if replacements.is_empty() {
-        return apply_regex_patterns(original_html);
+fn serialize_cleaned_children(element: ElementRef<'_>, out: &mut String) {
+    for child in element.children() {
+        if let Node::Text(text) = child.value() {
+            out.push_str(&escape_html_text(text));
+        } else if let Some(child) = ElementRef::wrap(child) {
+            serialize_cleaned(child, out);
+        }
     }
+}
 
-    // Sort by length descending (longer first) to avoid partial replacements
-    // This ensures we replace parent elements before children
-    replacements.sort_by_key(|b| std::cmp::Reverse(b.0.len()));
-
-    // Build result using string slices for O(n) total complexity.
-    //
-    // Use the parsed document's own serialized form (the body's inner HTML) as
-    // the replacement base rather than `original_html`. Each `element.html()`
-    // is produced by the same html5ever serializer, so it is guaranteed to be a
-    // substring here. Matching against the raw `original_html` instead would
-    // miss elements whose source formatting differs from the serialized form
-    // (e.g. extra whitespace inside a tag like `<nav  class=...>` or differing
-    // attribute quoting), silently leaking navigation, headers, footers and
-    // asides into the cleaned output. The body's inner HTML keeps the prior
-    // fragment shape (no synthetic `<html>`/`<head>` wrappers).
-    let mut result = document
-        .select(&BODY_SELECTOR)
-        .next()
-        .map_or_else(|| document.root_element().html(), |body| body.inner_html());
-    for (element_html, replacement) in replacements {
-        // Use replace_all for safety, but since we sorted by length,
-        // we should handle nested elements correctly
-        result = if let Some(text) = replacement {
-            result.replace(&element_html, &text)
-        } else {
-            result.replace(&element_html, "")
-        };
+/// Collect the text under `element`, skipping dropped elements
+fn collect_kept_text(element: ElementRef<'_>, out: &mut String) {
+    for child in element.children() {
+        if let Node::Text(text) = child.value() {
+            out.push_str(text);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            if !is_dropped(child) {
+                collect_kept_text(child, out);
+            }
+        }
     }
-
-    apply_regex_patterns(&result)
 }
 
 /// Combined regex pattern for HTML cleanup optimization
@@ -1125,18 +1092,14 @@ fn remove_unwanted_elements(document: &Html, original_html: &str) -> String {
 /// string traversal overhead compared to chained `replace_all()` calls.
 ///
 /// Pattern components:
-/// - `<link[^>]*>` - Link tags
-/// - `<meta[^>]*>` - Meta tags
 /// - `Copy item path` - UI copy path text
-/// - `</?details[^>]*>` - rustdoc collapsible toggle wrappers (html2md leaves
-///   these as raw tags); children are preserved
 /// - `Expand description` / `Expand attributes` - docs.rs toggle labels
 /// - `\[\§\]\([^)]*\)` - Anchor links like [§](#xxx)
 /// - `\[(?:Source|de|en|fr|ja)\]\([^)]*\)` - Source/language badges
 /// - `\[[^\]]*\]\([a-zA-Z][^)]*\.html\)` - Relative documentation links
 static COMBINED_CLEANUP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"(?:<link[^>]*>|<meta[^>]*>|</?details[^>]*>|Copy item path|Expand description|Expand attributes|\[§\]\([^)]*\)|\[Source\]\([^)]*\)|\[[^\]]*\]\([a-zA-Z][^)]*\.html\))",
+        r"(?:Copy item path|Expand description|Expand attributes|\[§\]\([^)]*\)|\[Source\]\([^)]*\)|\[[^\]]*\]\([a-zA-Z][^)]*\.html\))",
     )
     .expect("hardcoded valid regex pattern")
 });
@@ -1265,28 +1228,33 @@ fn extract_text_excluding_skip_tags(
                         }
                         continue;
                     }
-                    // Surround block-level elements with a `BLOCK_SEP`
+                    // Table cells get a single *leading* CELL_SEP marker
+                    // (rendered as ` | `) so a row's columns stay on one line;
+                    // keeping one separator between adjacent cells preserves
+                    // empty cells (see collapse_block_whitespace) so columns
+                    // stay aligned. Paragraphs, lists and line breaks inside a
+                    // cell would split the row across lines, so the cell's own
+                    // block markers become spaces.
+                    if name == "td" || name == "th" {
+                        let mut cell_parts = Vec::new();
+                        extract_text_excluding_skip_tags(&child_ref, &mut cell_parts);
+                        text_parts.push(CELL_SEP.to_string());
+                        text_parts.push(cell_parts.join("").replace(BLOCK_SEP, " "));
+                        continue;
+                    }
+                    // Surround other block-level elements with a `BLOCK_SEP`
                     // marker so adjacent blocks do not glue together (e.g.
                     // item-index entries) and each renders on its own line.
                     // `collapse_block_whitespace` turns the markers into
                     // newlines. Inline elements get no separator to preserve
                     // intra-word runs.
-                    // Table cells use a CELL_SEP marker (rendered as ` | `) so a
-                    // row's columns stay on one line; every other block element
-                    // uses BLOCK_SEP (rendered as a newline).
-                    let is_cell = name == "td" || name == "th";
-                    let is_block = !is_cell && BLOCK_TAGS.contains(&name.as_str());
-                    let sep = if is_cell { CELL_SEP } else { BLOCK_SEP };
-                    if is_cell || is_block {
-                        text_parts.push(sep.to_string());
+                    let is_block = BLOCK_TAGS.contains(&name.as_str());
+                    if is_block {
+                        text_parts.push(BLOCK_SEP.to_string());
                     }
                     extract_text_excluding_skip_tags(&child_ref, text_parts);
-                    // A cell pushes only a *leading* CELL_SEP delimiter; a block
-                    // is wrapped on both sides. This keeps a single separator
-                    // between adjacent cells so empty cells can be preserved
-                    // (see collapse_block_whitespace) and columns stay aligned.
                     if is_block {
-                        text_parts.push(sep.to_string());
+                        text_parts.push(BLOCK_SEP.to_string());
                     }
                 }
             }
@@ -1308,6 +1276,67 @@ pub fn extract_documentation_html(html: &str) -> String {
     clean_html(&main_content)
 }
 
+/// Matches a double-quoted `href` or `src` attribute; group 1 is the attribute
+/// prefix and group 2 the URL. rustdoc always quotes attributes with `"`.
+static HREF_ATTRIBUTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(\s(?:href|src)\s*=\s*)"([^"]*)""#).expect("hardcoded valid regex pattern")
+});
+
+/// Rewrite relative links of a page into absolute URLs under `page_url`.
+///
+/// rustdoc links items relative to the current page (`struct.Foo.html`,
+/// `../de/index.html`) and to its own anchors (`#method.new`). Those targets
+/// mean nothing to an MCP client, so [`clean_markdown`] would reduce them to
+/// their labels; resolved against the page URL they become canonical deep
+/// links the agent can hand to the user. Image sources are resolved the same
+/// way so HTML output does not embed broken relative images. Absolute, `javascript:` and bare `#`
+/// targets are left alone, as is the whole page if `page_url` is not a URL.
+#[must_use]
+pub fn absolutize_links(html: &str, page_url: &str) -> String {
+    let Ok(base) = url::Url::parse(page_url) else {
+        return html.to_string();
+    };
+    HREF_ATTRIBUTE_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let target = caps[2].trim();
+            let has_scheme = target.split_once(':').is_some_and(|(scheme, _)| {
+                !scheme.is_empty()
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            });
+            if target.is_empty() || target == "#" || has_scheme {
+                return caps[0].to_string();
+            }
+            match base.join(&decode_html_entities(target)) {
+                Ok(url) => format!("{}\"{}\"", &caps[1], escape_html_attribute(url.as_str())),
+                Err(_) => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Decode the character references (`&amp;`, `&#39;`, `&#x2019;`, `&rarr;`,
+/// ...) in raw HTML text or a raw attribute value
+///
+/// Runs the text through the HTML5 parser, so the full named set and numeric
+/// references decode exactly as in the parsed documents the extractors work
+/// on, rather than through a hand-kept table.
+#[must_use]
+pub(crate) fn decode_html_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    // Escape markup so the parser sees one text node
+    let fragment = Html::parse_fragment(&text.replace('<', "&lt;"));
+    fragment.root_element().text().collect()
+}
+
+/// Escape a value for a double-quoted HTML attribute
+fn escape_html_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
+
 /// Matches an inline `<code>...</code>` element (non-greedy). Used by
 /// [`flatten_links_in_inline_code`] to drop anchor wrappers that markdown
 /// cannot render inside a code span.
@@ -1365,19 +1394,30 @@ static PRE_CLASS_REGEX: LazyLock<Regex> =
 /// `html2md` inside the code block (STX bytes are never present in docs text).
 const CODE_FENCE_SENTINEL: char = '\u{2}';
 
-/// Determine the syntax-highlighting language for a rustdoc `<pre>` block from
-/// its class attribute. rustdoc marks Rust examples with the `rust` class
-/// (`rust rust-example-rendered`) and other fenced languages with
-/// `language-<name>` (e.g. `language-toml`). Returns `None` when no language can
-/// be determined so the fence stays bare.
+/// Determine the syntax-highlighting language for a `<pre>` block from the
+/// class attribute of the `<pre>` or of its `<code>` child. rustdoc marks Rust
+/// examples with the `rust` class (`rust rust-example-rendered`) and other
+/// fenced languages with `language-<name>` (e.g. `language-toml`) on the
+/// `<pre>`; rendered READMEs and the clippy lint list put `language-<name>`
+/// (or highlight.js's `lang-<name>`) on the `<code>`. Returns `None` when no
+/// language can be determined so the fence stays bare.
 #[must_use]
-fn detect_pre_language(pre_attrs: &str) -> Option<String> {
-    let class = PRE_CLASS_REGEX.captures(pre_attrs)?.get(1)?.as_str();
+fn detect_pre_language(pre_attrs: &str, code_tag: Option<&str>) -> Option<String> {
+    [Some(pre_attrs), code_tag]
+        .into_iter()
+        .flatten()
+        .find_map(detect_class_language)
+}
+
+fn detect_class_language(attrs: &str) -> Option<String> {
+    let class = PRE_CLASS_REGEX.captures(attrs)?.get(1)?.as_str();
     for tok in class.split_whitespace() {
-        if let Some(lang) = tok.strip_prefix("language-") {
-            if !lang.is_empty() {
-                return Some(lang.to_string());
-            }
+        let lang = tok
+            .strip_prefix("language-")
+            .or_else(|| tok.strip_prefix("lang-"))
+            .map(|lang| lang.split(',').next().unwrap_or_default());
+        if let Some(lang) = lang.filter(|lang| !lang.is_empty()) {
+            return Some(lang.to_ascii_lowercase());
         }
     }
     if class.split_whitespace().any(|t| t == "rust") {
@@ -1400,7 +1440,7 @@ fn inject_code_fence_language(html: &str) -> String {
     PRE_LANG_OPEN_REGEX
         .replace_all(html, |caps: &regex::Captures| {
             let whole = &caps[0];
-            match detect_pre_language(&caps[1]) {
+            match detect_pre_language(&caps[1], caps.get(2).map(|m| m.as_str())) {
                 Some(lang) => {
                     format!("{whole}{CODE_FENCE_SENTINEL}{lang}{CODE_FENCE_SENTINEL}\n")
                 }
@@ -1410,6 +1450,131 @@ fn inject_code_fence_language(html: &str) -> String {
         .into_owned()
 }
 
+/// Sentinel standing in for a `|` inside a table cell while it travels
+/// through `html2md` (ENQ bytes are never present in docs text).
+const CELL_PIPE_SENTINEL: char = '\u{5}';
+
+/// Matches a table cell with its content (group 2). See
+/// [`mark_table_cell_pipes`].
+static TABLE_CELL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)(<t[dh]\b[^>]*>)(.*?)(</t[dh]\s*>)").expect("valid regex"));
+
+/// Mark the `|` characters in table cell text (markdown path only).
+///
+/// `html2md` emits GitHub-flavored tables but does not escape a `|` inside a
+/// cell (typically in code such as `a|b` or `Fn(T) -> U | V`), which splits
+/// the cell and shifts the rest of the row. The pipes are replaced with
+/// [`CELL_PIPE_SENTINEL`] here and restored as `\|` by [`clean_markdown`].
+fn mark_table_cell_pipes(html: &str) -> String {
+    TABLE_CELL_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let mut content = String::with_capacity(caps[2].len());
+            let mut in_tag = false;
+            for c in caps[2].chars() {
+                match c {
+                    '<' => in_tag = true,
+                    '>' => in_tag = false,
+                    _ => {}
+                }
+                content.push(if c == '|' && !in_tag {
+                    CELL_PIPE_SENTINEL
+                } else {
+                    c
+                });
+            }
+            format!("{}{content}{}", &caps[1], &caps[3])
+        })
+        .into_owned()
+}
+
+/// Matches an `<img>` tag (attributes in group 1). See [`rewrite_images`].
+static IMG_TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<img\b([^>]*)>").expect("valid regex"));
+
+/// Matches an `alt`/`src` attribute of an `<img>` tag as serialized by
+/// [`clean_html`] (double-quoted).
+static IMG_ATTR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(alt|src)="([^"]*)""#).expect("valid regex"));
+
+/// Hosts serving status badges
+const BADGE_HOSTS: &[&str] = &[
+    "img.shields.io",
+    "badgen.net",
+    "badge.fury.io",
+    "codecov.io",
+    "coveralls.io",
+    "deps.rs",
+    "travis-ci.org",
+    "travis-ci.com",
+    "ci.appveyor.com",
+];
+
+/// What a badge shows, from keywords of its URL (checked in order)
+const BADGE_KINDS: &[(&[&str], &str)] = &[
+    (&["docs.rs", "docsrs"], "docs.rs badge"),
+    (&["crates"], "crates.io badge"),
+    (&["codecov", "coverage", "coveralls"], "coverage badge"),
+    (
+        &["workflow", "actions", "travis", "appveyor", "build", "/ci"],
+        "CI badge",
+    ),
+    (&["license"], "license badge"),
+    (&["msrv", "rustc"], "MSRV badge"),
+    (&["deps"], "dependency status badge"),
+    (&["download"], "downloads badge"),
+];
+
+/// Label of a status badge image, `None` for other images
+fn badge_label(src: &str) -> Option<&'static str> {
+    let url = url::Url::parse(src).ok()?;
+    let host = url.host_str()?;
+    let is_badge = BADGE_HOSTS.contains(&host)
+        || url.path().ends_with("badge.svg")
+        || url.path().starts_with("/badge")
+        || url.path().contains("/badge/");
+    if !is_badge {
+        return None;
+    }
+    let src = src.to_ascii_lowercase();
+    Some(
+        BADGE_KINDS
+            .iter()
+            .find(|(keywords, _)| keywords.iter().any(|k| src.contains(k)))
+            .map_or("badge", |(_, label)| label),
+    )
+}
+
+/// Replace `<img>` elements with text (markdown and text paths only).
+///
+/// Embedded images mean nothing to a text consumer, and relative `src`
+/// attributes turn into broken image links. Badges become their alt text or,
+/// without one, a label naming what they show (`crates.io badge`), so a
+/// linked badge stays a readable link; other images with alt text become
+/// `[image: alt]`, and images without alt text are dropped as decorative.
+fn rewrite_images(html: &str) -> String {
+    IMG_TAG_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let mut alt = String::new();
+            let mut src = String::new();
+            for attr in IMG_ATTR_REGEX.captures_iter(&caps[1]) {
+                let value = decode_html_entities(&attr[2]);
+                if attr[1].eq_ignore_ascii_case("alt") {
+                    alt = value.trim().to_string();
+                } else {
+                    src = value.trim().to_string();
+                }
+            }
+            let text = match badge_label(&src) {
+                Some(label) if alt.is_empty() => label.to_string(),
+                Some(_) => alt,
+                None if alt.is_empty() => return String::new(),
+                None => format!("[image: {alt}]"),
+            };
+            escape_html_text(&text)
+        })
+        .into_owned()
+}
+
 /// Collapse newline-containing whitespace on either side of inline elements to
 /// a single space, leaving `<pre>` blocks untouched.
 ///
@@ -1457,6 +1622,11 @@ pub fn extract_documentation(html: &str) -> String {
     // Preserve rustdoc code-block language hints (html2md drops class info);
     // see inject_code_fence_language / restore_code_fence_language.
     let cleaned_html = inject_code_fence_language(&cleaned_html);
+    // Turn images into alt text and badge labels; see rewrite_images.
+    let cleaned_html = rewrite_images(&cleaned_html);
+    // Keep `|` inside table cells from splitting them; see
+    // mark_table_cell_pipes.
+    let cleaned_html = mark_table_cell_pipes(&cleaned_html);
     // Restore whitespace html2md would otherwise drop before inline elements.
     let cleaned_html = normalize_inline_leading_whitespace(&cleaned_html);
     let markdown = html2md::parse_html(&cleaned_html);
@@ -1556,6 +1726,8 @@ fn clean_markdown(markdown: &str) -> String {
     // First strip html2md's backslash escaping from non-code text so escaped
     // identifiers/generics (`serde\_json`, `Vec\<u8\>`) read naturally.
     let unescaped = unescape_markdown(&markdown);
+    // Table-cell pipes carried through html2md (see mark_table_cell_pipes)
+    let unescaped = unescaped.replace(CELL_PIPE_SENTINEL, "\\|");
     // html2md leaves `<sup>`/`<sub>` as raw HTML (e.g. footnote references in
     // tables). Convert them to plain-text `^(...)`/`_(...)` notation, stripping
     // any nested tags (such as a footnote `<a>` link) from the inner content.
@@ -1691,7 +1863,9 @@ fn tidy_blockquotes(markdown: &str) -> String {
 /// Extract main content from docs.rs HTML
 ///
 /// Looks for `<section id="main-content">` which contains the actual documentation.
-/// Falls back to full HTML if main content section is not found.
+/// Falls back to the `<main>` landmark of pages without it (e.g. docs.rs
+/// build-failure pages), then to the full HTML; [`clean_html`] strips the
+/// navigation chrome left in either.
 #[inline]
 fn extract_main_content(html: &str) -> String {
     let document = Html::parse_document(html);
@@ -1705,6 +1879,9 @@ fn extract_main_content(html: &str) -> String {
     if let Some(wrapper) = document.select(&RUSTDOC_BODY_WRAPPER_SELECTOR).next() {
         return wrapper.html();
     }
+    if let Some(main) = document.select(&MAIN_SELECTOR).next() {
+        return main.html();
+    }
 
     // Last resort: return original HTML
     html.to_string()
@@ -1743,6 +1920,44 @@ fn heading_contains_identifier(heading: &str, ident: &str) -> bool {
         .any(|token| token == ident)
 }
 
+/// The crate version in the rustdoc sidebar (`<span class="version">` on
+/// current rustdoc, `Version 1.2.3` on older builds)
+static SIDEBAR_VERSION_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(".sidebar-crate .version, .sidebar .version").expect("hardcoded valid selector")
+});
+
+/// A docs.rs crate page link (`/crate/serde/1.0.210`) in the top bar
+static CRATE_PAGE_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"href="[^"]*/crate/[A-Za-z0-9_-]+/(\d+\.\d+\.\d+[0-9A-Za-z.+-]*)["/]"#)
+        .expect("hardcoded valid regex pattern")
+});
+
+/// The concrete crate version a documentation page was rendered for
+///
+/// docs.rs serves the latest build when no version is requested, so the
+/// version has to come from the page itself: the rustdoc sidebar, or the
+/// docs.rs top bar links of pages without one. `None` when neither names a
+/// valid semver version.
+#[must_use]
+pub fn page_version(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let sidebar = document
+        .select(&SIDEBAR_VERSION_SELECTOR)
+        .find_map(|element| {
+            let text = element.text().collect::<String>();
+            let text = text.trim();
+            let text = text.strip_prefix("Version").unwrap_or(text).trim();
+            semver::Version::parse(text).ok()
+        });
+    sidebar
+        .or_else(|| {
+            CRATE_PAGE_LINK_REGEX
+                .captures_iter(html)
+                .find_map(|caps| semver::Version::parse(&caps[1]).ok())
+        })
+        .map(|version| version.to_string())
+}
+
 /// Determine whether a resolved rustdoc page is a *fallback* rather than the
 /// dedicated page for `item_path`.
 ///
@@ -1781,6 +1996,11 @@ pub fn extract_search_results(html: &str, item_path: &str) -> String {
     // Preserve rustdoc code-block language hints (html2md drops class info);
     // see inject_code_fence_language / restore_code_fence_language.
     let cleaned_html = inject_code_fence_language(&cleaned_html);
+    // Turn images into alt text and badge labels; see rewrite_images.
+    let cleaned_html = rewrite_images(&cleaned_html);
+    // Keep `|` inside table cells from splitting them; see
+    // mark_table_cell_pipes.
+    let cleaned_html = mark_table_cell_pipes(&cleaned_html);
     // Restore whitespace html2md would otherwise drop before inline elements.
     let cleaned_html = normalize_inline_leading_whitespace(&cleaned_html);
     let markdown = html2md::parse_html(&cleaned_html);
@@ -1815,7 +2035,7 @@ pub fn extract_search_results(html: &str, item_path: &str) -> String {
 #[must_use]
 pub fn extract_documentation_as_text(html: &str) -> String {
     let main_content = extract_main_content(html);
-    let cleaned_html = clean_html(&main_content);
+    let cleaned_html = rewrite_images(&clean_html(&main_content));
     // Use the raw extraction so `<pre>` content stays encoded through the
     // line-normalisation pass; decode it back to real whitespace at the end.
     let text = html_to_text_raw(&cleaned_html);
@@ -2719,6 +2939,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tables_keep_their_rows() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\"><table>",
+            "<thead><tr><th>Feature</th><th>Description</th></tr></thead><tbody>",
+            "<tr><td><code>a|b</code></td><td><p>First para.</p><p>Second</p></td></tr>",
+            "<tr><td>std</td><td><ul><li>x</li><li>y</li></ul></td></tr>",
+            "</tbody></table></section></body></html>"
+        );
+        let md = extract_documentation(html);
+        let rows: Vec<&str> = md.lines().filter(|l| l.starts_with('|')).collect();
+        assert_eq!(rows.len(), 4, "{md}");
+        assert!(rows[2].contains(r"`a\|b`"), "{md}");
+        assert!(rows[2].contains("First para."), "{md}");
+        assert!(rows[2].contains("Second"), "{md}");
+        assert!(!md.contains('\u{5}'), "{md}");
+
+        let text = extract_documentation_as_text(html);
+        assert_eq!(
+            text.trim(),
+            "Feature | Description\na|b | First para. Second\nstd | x y"
+        );
+        assert!(!extract_documentation_html(html).contains(r"\|"));
+    }
+
+    #[test]
+    fn test_page_version() {
+        let current = r#"<nav class="sidebar"><div class="sidebar-crate"><h2><a href="../demo/index.html">demo</a><span class="version">1.2.3</span></h2></div></nav>"#;
+        assert_eq!(page_version(current).as_deref(), Some("1.2.3"));
+        let old = r#"<nav class="sidebar"><div class="block version"><p>Version 0.4.0-beta.1</p></div></nav>"#;
+        assert_eq!(page_version(old).as_deref(), Some("0.4.0-beta.1"));
+        let docs_rs = r#"<a href="https://docs.rs/crate/demo/2.0.1/source/">Source</a>"#;
+        assert_eq!(page_version(docs_rs).as_deref(), Some("2.0.1"));
+        assert_eq!(
+            page_version(r#"<a href="/crate/demo/latest">demo</a>"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_images_become_alt_text_and_badge_labels() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\"><div class=\"docblock\">",
+            "<p><a href=\"https://crates.io/crates/demo\">",
+            "<img src=\"https://img.shields.io/crates/v/demo.svg\"></a> ",
+            "<a href=\"https://github.com/o/demo/actions\"><img alt=\"Build &amp; test\" ",
+            "src=\"https://github.com/o/demo/actions/workflows/ci.yml/badge.svg\"></a></p>",
+            "<p><img src=\"logo.png\" alt=\"Project logo\"> <img src=\"spacer.gif\"></p>",
+            "</div></section></body></html>"
+        );
+        let md = extract_documentation(html);
+        assert!(
+            md.contains("[crates.io badge](https://crates.io/crates/demo)"),
+            "{md}"
+        );
+        assert!(
+            md.contains("[Build & test](https://github.com/o/demo/actions)"),
+            "{md}"
+        );
+        assert!(md.contains("[image: Project logo]"), "{md}");
+        assert!(!md.contains("!["), "{md}");
+        assert!(!md.contains(".png") && !md.contains(".gif"), "{md}");
+
+        let text = extract_documentation_as_text(html);
+        assert!(text.contains("crates.io badge"), "{text}");
+        assert!(text.contains("[image: Project logo]"), "{text}");
+
+        assert_eq!(
+            badge_label("https://docs.rs/demo/badge.svg"),
+            Some("docs.rs badge")
+        );
+        assert_eq!(badge_label("https://example.com/logo.png"), None);
+        assert_eq!(badge_label("logo.png"), None);
+
+        // HTML output keeps the images with resolvable sources
+        let out = absolutize_links(
+            "<img src=\"logo.png\">",
+            "https://docs.rs/demo/latest/demo/",
+        );
+        assert!(
+            out.contains("src=\"https://docs.rs/demo/latest/demo/logo.png\""),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_code_fence_language_from_code_class() {
+        // Rendered READMEs and the clippy lint list annotate the `<code>`
+        // child rather than the `<pre>`.
+        let html = concat!(
+            "<div class=\"docblock\">",
+            "<pre><code class=\"language-rust\">let x = 1;</code></pre>",
+            "<pre>\n<code class=\"hljs lang-Shell\">cargo add demo</code></pre>",
+            "<pre class=\"language-toml\"><code class=\"language-ini\">v = 1</code></pre>",
+            "<pre><code class=\"language-rust,ignore\">todo!()</code></pre>",
+            "</div>"
+        );
+        let md = extract_documentation(html);
+        assert_eq!(md.matches("```rust\n").count(), 2, "{md:?}");
+        assert!(md.contains("```shell\n"), "{md:?}");
+        // The `<pre>` class wins
+        assert!(md.contains("```toml\n"), "{md:?}");
+        assert!(!md.contains("ini"), "{md:?}");
+    }
+
     #[test]
     fn test_portability_badge_feature_with_underscore_not_escaped() {
         // A feature name containing an underscore is embedded in the badge
@@ -3260,6 +3585,65 @@ mod tests {
         assert!(cleaned.contains("Hello"));
     }
 
+    #[test]
+    fn test_extract_documentation_strips_navigation_chrome() {
+        let html = concat!(
+            "<html><body>",
+            "<div class=\"nav-container\"><div class=\"container\">",
+            "<a href=\"/releases\">Releases</a>",
+            "<form action=\"/releases/search\"><input name=\"query\">Find crate</form>",
+            "</div></div>",
+            "<div class=\"sidebar\"><a href=\"#structs\">All Items</a></div>",
+            "<main><div id=\"settings-menu\"><a href=\"settings.html\">Settings</a></div>",
+            "<h2 id=\"search\">Search</h2><p>Use <code>find</code> to search.</p>",
+            "<section id=\"search\" class=\"content hidden\">Search results</section>",
+            "</main><div class=\"footer\"><footer>Hosted by</footer></div>",
+            "</body></html>"
+        );
+        let md = extract_documentation(html);
+        for chrome in [
+            "Releases",
+            "Find crate",
+            "All Items",
+            "Settings",
+            "Hosted by",
+        ] {
+            assert!(!md.contains(chrome), "{chrome} leaked: {md}");
+        }
+        assert!(!md.contains("Search results"), "{md}");
+        assert!(md.contains("Search"), "{md}");
+        assert!(md.contains("to search."), "{md}");
+    }
+
+    #[test]
+    fn test_clean_html_works_on_the_parsed_tree() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\">",
+            "<!-- <p>commented out</p> -->",
+            "<p title=\"a > b\">Kept &amp; escaped &lt;T&gt;</p>",
+            "<script data-x=\"</script>\">alert(1)</script>",
+            "<nav data-x='>'><p>Sidebar</p></nav>",
+            "<p>Unclosed <b>bold",
+            "<div>after<br>break</section></body></html>"
+        );
+        let cleaned = clean_html(html);
+        assert!(!cleaned.contains("commented out"), "{cleaned}");
+        assert!(!cleaned.contains("<!--"), "{cleaned}");
+        assert!(!cleaned.contains("alert"), "{cleaned}");
+        assert!(!cleaned.contains("Sidebar"), "{cleaned}");
+        assert!(cleaned.contains("title=\"a > b\""), "{cleaned}");
+        assert!(
+            cleaned.contains("Kept &amp; escaped &lt;T&gt;"),
+            "{cleaned}"
+        );
+        // Malformed markup comes out balanced
+        assert!(cleaned.contains("<b>bold</b></p>"), "{cleaned}");
+        assert!(
+            cleaned.ends_with("after<br>break</b></div></section>"),
+            "{cleaned}"
+        );
+    }
+
     #[test]
     fn test_clean_html_strips_details_toggle_wrappers() {
         let html = r#"<html><body><section id="main-content"><details class="toggle top-doc" open=""><summary>Expand description</summary><h2>MyCrate</h2><p>Useful docs.</p></details></section></body></html>"#;
@@ -3399,6 +3783,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entities_decode_in_every_format() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\">",
+            "<p>It&#39;s &#x2019;quoted&#x2019; &hellip; &rarr; &amp;amp; &lt;T&gt;</p>",
+            "<pre class=\"rust\"><code>let s = &quot;&#39;&quot;;</code></pre>",
+            "</section></body></html>"
+        );
+        for out in [
+            extract_documentation(html),
+            extract_documentation_as_text(html),
+        ] {
+            assert!(
+                out.contains("It's \u{2019}quoted\u{2019} \u{2026} \u{2192} &amp; <T>"),
+                "{out}"
+            );
+            assert!(out.contains("let s = \"'\";"), "{out}");
+        }
+        let out = extract_documentation_html(html);
+        assert!(
+            out.contains("It's \u{2019}quoted\u{2019} \u{2026} \u{2192} &amp;amp; &lt;T&gt;"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_decode_html_entities() {
+        assert_eq!(
+            decode_html_entities("a=1&amp;b=&#x32;&#51;&nbsp;&lt;x&gt; &copy; <i>"),
+            "a=1&b=23\u{a0}<x> \u{a9} <i>"
+        );
+        assert_eq!(decode_html_entities("plain"), "plain");
+        assert_eq!(
+            absolutize_links(
+                "<a href=\"s.html?a=1&amp;b=&#x32;\">",
+                "https://docs.rs/a/1/a/"
+            ),
+            "<a href=\"https://docs.rs/a/1/a/s.html?a=1&amp;b=2\">"
+        );
+    }
+
     #[test]
     fn test_html_to_text_handles_entities() {
         // Test that HTML entities are converted to their character equivalents
@@ -3546,6 +3971,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_absolutize_links() {
+        let page = "https://docs.rs/serde/1.0.0/serde/index.html";
+        let html = r##"<a href="struct.Foo.html">Foo</a>
+<a href="../de/index.html">de</a>
+<a href="#method.new">new</a>
+<a href="#">top</a>
+<a href="https://example.com/x">ext</a>
+<a href="javascript:void(0)">js</a>
+<a href="fn.f.html?a=1&amp;b=2">f</a>"##;
+        let out = absolutize_links(html, page);
+        assert!(out.contains(r#"href="https://docs.rs/serde/1.0.0/serde/struct.Foo.html""#));
+        assert!(out.contains(r#"href="https://docs.rs/serde/1.0.0/de/index.html""#));
+        assert!(out.contains(r#"href="https://docs.rs/serde/1.0.0/serde/index.html#method.new""#));
+        assert!(out.contains(r##"href="#""##));
+        assert!(out.contains(r#"href="https://example.com/x""#));
+        assert!(out.contains(r#"href="javascript:void(0)""#));
+        assert!(out.contains(r#"href="https://docs.rs/serde/1.0.0/serde/fn.f.html?a=1&amp;b=2""#));
+
+        assert_eq!(absolutize_links(html, "not a url"), html);
+    }
+
     #[test]
     fn test_relative_link_regex() {
         // Test that RELATIVE_LINK_REGEX only matches relative .html links
@@ -0,0 +1,435 @@
+//! Compact `llm` output format
+//!
+//! The markdown format is written for people: setext headings, emphasis,
+//! rules, blockquotes and the full URL of every link. `format: "llm"` renders
+//! the same documentation for a model's context window instead:
+//!
+//! - no decorative markup: emphasis, rules, blockquote markers and table
+//!   separator rows are dropped, headings become compact `#` lines
+//! - links are deduplicated: the first link to a URL keeps it as
+//!   `label <url>`, later links to it (and same-page anchors) keep only the
+//!   label
+//! - in every section, declarations (item signatures) come before prose, and
+//!   "Examples" sections move to the end of the document
+//! - prose is compressed: wrapped lines are joined and blank lines between
+//!   paragraphs and list items are dropped
+//!
+//! [`compact`] post-processes the markdown conversion, so every tool that
+//! renders documentation gets the format from its markdown output.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Markdown link or image: `[label](url)` or `![alt](url "title")`
+static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(!?)\[([^\]\n]*)\]\(([^)\s]*)(?:\s+"[^"]*")?\)"#).expect("hardcoded valid regex")
+});
+
+/// `**strong**` or `__strong__`
+static STRONG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*\n]+)\*\*|__([^_\n]+)__").expect("hardcoded valid regex")
+});
+
+/// `*emphasis*` or `_emphasis_` (not inside words like `snake_case`)
+static EMPHASIS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(^|[^\w*])\*([^*\s][^*\n]*?)\*|(^|[^\w])_([^_\s][^_\n]*?)_($|[^\w])")
+        .expect("hardcoded valid regex")
+});
+
+static ATX_HEADING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.*?)\s*#*\s*$").expect("hardcoded valid regex"));
+
+static LIST_ITEM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:[*+-]|\d+[.)])\s+").expect("hardcoded valid regex"));
+
+static TABLE_SEPARATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\|?[\s:|-]*-[\s:|-]*$").expect("hardcoded valid regex"));
+
+static WHITESPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s+").expect("hardcoded valid regex"));
+
+/// Leading words of a Rust declaration
+const DECLARATION_STARTS: &[&str] = &[
+    "pub ",
+    "pub(",
+    "fn ",
+    "const ",
+    "static ",
+    "struct ",
+    "enum ",
+    "union ",
+    "trait ",
+    "type ",
+    "impl",
+    "unsafe ",
+    "async ",
+    "extern ",
+    "macro_rules!",
+    "mod ",
+    "#[",
+];
+
+#[derive(Debug)]
+enum Block {
+    Heading {
+        level: usize,
+        title: String,
+    },
+    /// A fenced code block, fences included
+    Code(Vec<String>),
+    /// Prose lines; each list item, table row or paragraph is one line
+    Prose(Vec<String>),
+}
+
+struct Section {
+    heading: Option<(usize, String)>,
+    blocks: Vec<Block>,
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+fn setext_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim();
+    if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '=') {
+        Some(1)
+    } else if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Split markdown into headings, code blocks and prose paragraphs
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    // Lines of the paragraph being read, and whether each starts a new line
+    // (list item, table row) rather than continuing the previous one
+    let mut paragraph: Vec<(bool, String)> = Vec::new();
+    let mut lines = markdown.lines();
+
+    let flush = |paragraph: &mut Vec<(bool, String)>, blocks: &mut Vec<Block>| {
+        let mut prose: Vec<String> = Vec::new();
+        for (starts_line, line) in paragraph.drain(..) {
+            match prose.last_mut() {
+                Some(last) if !starts_line => {
+                    last.push(' ');
+                    last.push_str(&line);
+                }
+                _ => prose.push(line),
+            }
+        }
+        if !prose.is_empty() {
+            blocks.push(Block::Prose(prose));
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        if is_fence(line) {
+            flush(&mut paragraph, &mut blocks);
+            let mut code = vec![line.trim().to_string()];
+            for line in lines.by_ref() {
+                code.push(line.to_string());
+                if is_fence(line) {
+                    break;
+                }
+            }
+            blocks.push(Block::Code(code));
+            continue;
+        }
+        if let Some(level) = setext_level(line) {
+            if paragraph.is_empty() {
+                // A thematic break
+                continue;
+            }
+            let title = paragraph
+                .drain(..)
+                .map(|(_, l)| l)
+                .collect::<Vec<_>>()
+                .join(" ");
+            blocks.push(Block::Heading { level, title });
+            continue;
+        }
+        if let Some(caps) = ATX_HEADING_RE.captures(line) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading {
+                level: caps[1].len(),
+                title: caps[2].to_string(),
+            });
+            continue;
+        }
+        let line = line.trim_start();
+        let line = line.trim_start_matches('>').trim();
+        if line.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+        if TABLE_SEPARATOR_RE.is_match(line) && line.contains('|') {
+            continue;
+        }
+        let starts_line = LIST_ITEM_RE.is_match(line) || line.starts_with('|');
+        paragraph.push((starts_line, line.to_string()));
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Whether a code block holds a declaration rather than an example
+fn is_declaration(code: &[String]) -> bool {
+    let lang = code[0].trim_start_matches('`').trim();
+    if !(lang.is_empty() || lang == "rust") {
+        return false;
+    }
+    code[1..]
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !is_fence(line))
+        .is_some_and(|first| DECLARATION_STARTS.iter().any(|s| first.starts_with(s)))
+}
+
+fn is_examples_title(title: &str) -> bool {
+    title.trim().to_lowercase().starts_with("example")
+}
+
+fn into_sections(blocks: Vec<Block>) -> Vec<Section> {
+    let mut sections = vec![Section {
+        heading: None,
+        blocks: Vec::new(),
+    }];
+    for block in blocks {
+        match block {
+            Block::Heading { level, title } => sections.push(Section {
+                heading: Some((level, title)),
+                blocks: Vec::new(),
+            }),
+            block => sections
+                .last_mut()
+                .expect("sections start non-empty")
+                .blocks
+                .push(block),
+        }
+    }
+    sections
+}
+
+/// Apply `f` to the parts of `line` outside inline code spans
+fn map_outside_code(line: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(line.len());
+    for (i, part) in line.split('`').enumerate() {
+        if i > 0 {
+            out.push('`');
+        }
+        if i % 2 == 0 {
+            out.push_str(&f(part));
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+/// Rewrite the links of `text`, keeping the URL of the first link to each
+fn dedup_links(text: &str, seen: &mut HashSet<String>, keep_urls: bool) -> String {
+    LINK_RE
+        .replace_all(text, |caps: &regex::Captures| {
+            let (image, label, url) = (&caps[1], caps[2].trim(), &caps[3]);
+            if !image.is_empty() {
+                return String::new();
+            }
+            let label = if label.is_empty() { url } else { label };
+            if !keep_urls || url.is_empty() || url.starts_with('#') || !seen.insert(url.to_string())
+            {
+                label.to_string()
+            } else if label == url {
+                format!("<{url}>")
+            } else {
+                format!("{label} <{url}>")
+            }
+        })
+        .into_owned()
+}
+
+fn strip_emphasis(text: &str) -> String {
+    let text = STRONG_RE.replace_all(text, "$1$2");
+    EMPHASIS_RE.replace_all(&text, "$1$2$3$4$5").into_owned()
+}
+
+/// Compress one prose line
+fn compact_line(line: &str, seen: &mut HashSet<String>, keep_urls: bool) -> String {
+    // Links first: a label may span code spans (`[`Vec`](...)`)
+    let line = dedup_links(line, seen, keep_urls);
+    let line = map_outside_code(&line, strip_emphasis);
+    let line = WHITESPACE_RE.replace_all(line.trim(), " ");
+    match line.strip_prefix("* ").or_else(|| line.strip_prefix("+ ")) {
+        Some(item) => format!("- {item}"),
+        None => line.into_owned(),
+    }
+}
+
+fn render_block(block: &Block, seen: &mut HashSet<String>, out: &mut Vec<String>) {
+    match block {
+        Block::Code(code) => out.extend(code.iter().cloned()),
+        Block::Prose(lines) => out.extend(
+            lines
+                .iter()
+                .map(|line| compact_line(line, seen, true))
+                .filter(|line| !line.is_empty()),
+        ),
+        Block::Heading { .. } => {}
+    }
+}
+
+fn render_section(
+    section: &Section,
+    hoist_declarations: bool,
+    seen: &mut HashSet<String>,
+    out: &mut Vec<String>,
+) {
+    if let Some((level, title)) = &section.heading {
+        let title = compact_line(title, seen, false);
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        out.push(format!("{} {title}", "#".repeat(*level)));
+    }
+    let (declarations, rest): (Vec<&Block>, Vec<&Block>) = section.blocks.iter().partition(|b| {
+        hoist_declarations && matches!(b, Block::Code(code) if is_declaration(code))
+    });
+    for block in declarations.into_iter().chain(rest) {
+        render_block(block, seen, out);
+    }
+}
+
+/// Render markdown documentation in the compact `llm` format
+#[must_use]
+pub fn compact(markdown: &str) -> String {
+    let sections = into_sections(parse_blocks(markdown));
+
+    // An examples section takes its subsections along to the end
+    let mut main = Vec::new();
+    let mut examples = Vec::new();
+    let mut examples_level = None;
+    for section in sections {
+        let level = section.heading.as_ref().map(|(level, _)| *level);
+        if let (Some(open), Some(level)) = (examples_level, level) {
+            if level <= open {
+                examples_level = None;
+            }
+        }
+        if examples_level.is_none() {
+            if let Some((level, title)) = &section.heading {
+                if is_examples_title(title) {
+                    examples_level = Some(*level);
+                }
+            }
+        }
+        if examples_level.is_some() {
+            examples.push(section);
+        } else {
+            main.push(section);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for section in &main {
+        render_section(section, true, &mut seen, &mut out);
+    }
+    for section in &examples {
+        render_section(section, false, &mut seen, &mut out);
+    }
+    let mut text = out.join("\n");
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "## Documentation: demo::Thing
+
+Struct [demo](https://docs.rs/demo/1.0.0/demo/index.html)::Thing
+==========
+
+A **thing** that does *stuff*, see [`X`](https://docs.rs/other/x.html) and [X again](https://docs.rs/other/x.html).
+Wrapped snake_case line.
+
+```rust
+pub struct Thing { /* private fields */ }
+```
+
+* one
+* two
+
+> Quoted note
+
+---
+
+Examples
+----------
+
+Make one:
+
+```rust
+let t = Thing::new();
+```
+
+### Caveats
+
+Careful & slow.
+
+Features
+----------
+
+| a | b |
+|---|---|
+| 1 | 2 |
+
+See [new](#method.new) ![badge](https://img.shields.io/x.svg)
+";
+
+    #[test]
+    fn test_compact() {
+        let out = compact(PAGE);
+        assert_eq!(
+            out,
+            "## Documentation: demo::Thing
+
+# Struct demo::Thing
+```rust
+pub struct Thing { /* private fields */ }
+```
+A thing that does stuff, see `X` <https://docs.rs/other/x.html> and X again. Wrapped snake_case line.
+- one
+- two
+Quoted note
+
+## Features
+| a | b |
+| 1 | 2 |
+See new
+
+## Examples
+Make one:
+```rust
+let t = Thing::new();
+```
+
+### Caveats
+Careful & slow.
+"
+        );
+    }
+
+    #[test]
+    fn test_compact_leaves_code_alone() {
+        let markdown = "Use `**not bold**` here.\n\n```toml\n[dependencies]\n\n**x** = 1\n```\n";
+        assert_eq!(
+            compact(markdown),
+            "Use `**not bold**` here.\n```toml\n[dependencies]\n\n**x** = 1\n```\n"
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Cargo.lock analysis tool
+//!
+//! Reports the exact versions a `Cargo.lock` pins, flags pinned versions that
+//! have since been yanked and links the docs.rs pages of those exact
+//! versions, so follow-up `lookup_item` calls can ask for the version the
+//! project actually builds against. Yanked status and the newest release come
+//! from the crates.io sparse index, one request per crate.
+//!
+//! Only packages of crates.io (or of the registry configured in
+//! `[registry]`) are checked; workspace members, git and other registry
+//! packages are listed as they are.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+use super::profile::{CargoLock, MAX_LOCKFILE_BYTES};
+use super::resolve_version::{IndexVersion, VersionSpec};
+
+const TOOL_NAME: &str = "analyze_cargo_lock";
+
+/// Maximum number of distinct crates.io crates checked in one call
+const MAX_CHECKED_CRATES: usize = 500;
+
+/// Index files fetched at the same time
+const MAX_CONCURRENT_FETCHES: usize = 5;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "analyze_cargo_lock",
+    title = "Analyze Cargo.lock",
+    description = "Audit a Cargo.lock: list the exact version of every locked crates.io package, flag versions in use that have been yanked, show newer releases, and link the docs.rs pages of the exact locked versions. Pass the file contents, not a path. Use the reported versions for lookup_crate/lookup_item to read the docs the project builds against.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    execution(task_support = "optional"),
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `analyze_cargo_lock` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct AnalyzeCargoLockTool {
+    /// Contents of the `Cargo.lock` to analyze
+    #[json_schema(
+        title = "Cargo.lock",
+        description = "Contents of the Cargo.lock file to audit"
+    )]
+    pub cargo_lock: String,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (one report per locked package)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Where a locked package comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockedSource {
+    /// crates.io, or the registry configured in `[registry]`
+    CratesIo,
+    /// A registry other than crates.io
+    Registry,
+    Git,
+    /// A workspace member or path dependency
+    Local,
+}
+
+impl LockedSource {
+    fn parse(source: Option<&str>) -> Self {
+        match source {
+            None => Self::Local,
+            Some(source) if super::registry::is_registry_source(source) => Self::CratesIo,
+            Some(source) if source.starts_with("git+") => Self::Git,
+            Some(_) => Self::Registry,
+        }
+    }
+}
+
+/// Audit result of one locked package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    /// Exact locked version
+    pub version: String,
+    pub source: LockedSource,
+    /// Whether the locked version has been yanked
+    pub yanked: Option<bool>,
+    /// Newest stable release on crates.io
+    pub latest: Option<String>,
+    /// docs.rs URL of the locked version
+    pub docs_rs: Option<String>,
+    /// Why the package could not be checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Audit result of a lockfile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockfileAnalysis {
+    /// Locked crates.io packages
+    pub crates_io_packages: usize,
+    /// Locked versions that have been yanked
+    pub yanked: usize,
+    /// Locked packages with a newer stable release
+    pub outdated: usize,
+    pub packages: Vec<LockedPackage>,
+}
+
+impl LockfileAnalysis {
+    fn new(packages: Vec<LockedPackage>) -> Self {
+        let crates_io = packages
+            .iter()
+            .filter(|p| p.source == LockedSource::CratesIo);
+        Self {
+            crates_io_packages: crates_io.clone().count(),
+            yanked: crates_io.clone().filter(|p| p.yanked == Some(true)).count(),
+            outdated: crates_io.filter(|p| is_outdated(p)).count(),
+            packages,
+        }
+    }
+}
+
+fn is_outdated(package: &LockedPackage) -> bool {
+    let (Ok(locked), Some(Ok(latest))) = (
+        Version::parse(&package.version),
+        package.latest.as_deref().map(Version::parse),
+    ) else {
+        return false;
+    };
+    latest > locked
+}
+
+/// Fill in the yanked status and newest release from the index versions
+fn check_package(package: &mut LockedPackage, versions: &[IndexVersion]) {
+    package.latest = super::resolve_version::select_version(versions, &VersionSpec::Latest)
+        .map(|v| v.version.to_string());
+    let locked = Version::parse(&package.version).ok();
+    match versions
+        .iter()
+        .find(|v| Some(&v.version) == locked.as_ref())
+    {
+        Some(found) => package.yanked = Some(found.yanked),
+        None => {
+            package.error = Some(format!(
+                "version {} is not in the crates.io index",
+                package.version
+            ));
+        }
+    }
+}
+
+fn format_analysis(analysis: &LockfileAnalysis, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Cargo.lock analysis\n").unwrap();
+    } else {
+        writeln!(output, "Cargo.lock analysis\n").unwrap();
+    }
+    writeln!(
+        output,
+        "{} packages ({} from crates.io), {} yanked, {} with newer releases\n",
+        analysis.packages.len(),
+        analysis.crates_io_packages,
+        analysis.yanked,
+        analysis.outdated
+    )
+    .unwrap();
+
+    let yanked: Vec<&LockedPackage> = analysis
+        .packages
+        .iter()
+        .filter(|p| p.yanked == Some(true))
+        .collect();
+    if !yanked.is_empty() {
+        if markdown {
+            writeln!(output, "## Yanked versions in use\n").unwrap();
+        } else {
+            writeln!(output, "Yanked versions in use:").unwrap();
+        }
+        for package in yanked {
+            let latest = package.latest.as_deref().unwrap_or("-");
+            writeln!(
+                output,
+                "- {} {} (latest {latest})",
+                package.name, package.version
+            )
+            .unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+
+    if markdown {
+        writeln!(output, "## Packages\n").unwrap();
+        writeln!(output, "| Crate | Locked | Latest | Status | Docs |").unwrap();
+        writeln!(output, "|---|---|---|---|---|").unwrap();
+    } else {
+        writeln!(output, "Packages:").unwrap();
+    }
+    for package in &analysis.packages {
+        let status = match (package.source, package.yanked, &package.error) {
+            (_, _, Some(error)) => format!("error: {error}"),
+            (LockedSource::Local, _, _) => "workspace member, not checked".to_string(),
+            (LockedSource::Git, _, _) => "git, not checked".to_string(),
+            (LockedSource::Registry, _, _) => "other registry, not checked".to_string(),
+            (LockedSource::CratesIo, Some(true), _) => "yanked".to_string(),
+            (LockedSource::CratesIo, _, _) if is_outdated(package) => "newer release".to_string(),
+            (LockedSource::CratesIo, _, _) => "ok".to_string(),
+        };
+        let latest = package.latest.as_deref().unwrap_or("-");
+        if markdown {
+            let docs = package
+                .docs_rs
+                .as_deref()
+                .map_or_else(|| "-".to_string(), |url| format!("<{url}>"));
+            writeln!(
+                output,
+                "| {} | {} | {latest} | {} | {docs} |",
+                package.name,
+                package.version,
+                status.replace('|', "\\|")
+            )
+            .unwrap();
+        } else {
+            let docs = package.docs_rs.as_deref().unwrap_or("-");
+            writeln!(
+                output,
+                "- {} {} (latest {latest}): {status} - {docs}",
+                package.name, package.version
+            )
+            .unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the Cargo.lock analysis tool
+pub struct AnalyzeCargoLockToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl AnalyzeCargoLockToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Check the crates.io packages, fetching each crate's index once
+    async fn check_all(&self, packages: &mut [LockedPackage]) {
+        let mut names: Vec<String> = packages
+            .iter()
+            .filter(|p| p.source == LockedSource::CratesIo)
+            .map(|p| p.name.clone())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut indexes: HashMap<String, Result<Vec<IndexVersion>, String>> =
+            HashMap::with_capacity(names.len());
+        for chunk in names.chunks(MAX_CONCURRENT_FETCHES) {
+            let mut tasks = JoinSet::new();
+            for name in chunk {
+                let service = Arc::clone(&self.service);
+                let name = name.clone();
+                tasks.spawn(async move {
+                    // CallToolError is not Send; keep only its message
+                    let versions =
+                        super::resolve_version::fetch_index_versions(&service, &name, TOOL_NAME)
+                            .await
+                            .map_err(|e| e.to_string());
+                    (name, versions)
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                if let Ok((name, versions)) = joined {
+                    indexes.insert(name, versions);
+                }
+            }
+        }
+
+        for package in packages
+            .iter_mut()
+            .filter(|p| p.source == LockedSource::CratesIo)
+        {
+            package.docs_rs = Some(
+                self.service
+                    .crate_docs_url(&package.name, Some(&package.version)),
+            );
+            match indexes.get(&package.name) {
+                Some(Ok(versions)) => check_package(package, versions),
+                Some(Err(error)) => package.error = Some(error.clone()),
+                None => package.error = Some("check did not complete".to_string()),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AnalyzeCargoLockToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        AnalyzeCargoLockTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: AnalyzeCargoLockTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let invalid = |message: String| CallToolError::invalid_arguments(TOOL_NAME, Some(message));
+
+        if params.cargo_lock.len() > MAX_LOCKFILE_BYTES {
+            return Err(invalid(format!(
+                "Cargo.lock is too large (max {MAX_LOCKFILE_BYTES} bytes)"
+            )));
+        }
+        let lock: CargoLock = toml::from_str(&params.cargo_lock)
+            .map_err(|e| invalid(format!("Invalid Cargo.lock: {e}")))?;
+
+        let mut packages: Vec<LockedPackage> = lock
+            .package
+            .into_iter()
+            .map(|package| LockedPackage {
+                source: LockedSource::parse(package.source.as_deref()),
+                name: package.name,
+                version: package.version,
+                yanked: None,
+                latest: None,
+                docs_rs: None,
+                error: None,
+            })
+            .collect();
+        let mut crates: Vec<&str> = packages
+            .iter()
+            .filter(|p| p.source == LockedSource::CratesIo)
+            .map(|p| p.name.as_str())
+            .collect();
+        crates.sort_unstable();
+        crates.dedup();
+        if crates.len() > MAX_CHECKED_CRATES {
+            return Err(invalid(format!(
+                "Too many crates.io packages (max {MAX_CHECKED_CRATES} distinct crates)"
+            )));
+        }
+        for name in crates {
+            super::validate_crate_name(TOOL_NAME, name)?;
+        }
+
+        self.check_all(&mut packages).await;
+        let analysis = LockfileAnalysis::new(packages);
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&analysis).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_analysis(&analysis, false),
+            _ => format_analysis(&analysis, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for AnalyzeCargoLockToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str, yanked: bool) -> IndexVersion {
+        IndexVersion {
+            version: Version::parse(version).unwrap(),
+            yanked,
+            features: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_locked_source() {
+        assert_eq!(
+            LockedSource::parse(Some(
+                "registry+https://github.com/rust-lang/crates.io-index"
+            )),
+            LockedSource::CratesIo
+        );
+        assert_eq!(
+            LockedSource::parse(Some("sparse+https://index.crates.io/")),
+            LockedSource::CratesIo
+        );
+        assert_eq!(
+            LockedSource::parse(Some("git+https://github.com/a/b#0123abc")),
+            LockedSource::Git
+        );
+        assert_eq!(
+            LockedSource::parse(Some("sparse+https://example.com/index/")),
+            LockedSource::Registry
+        );
+        assert_eq!(LockedSource::parse(None), LockedSource::Local);
+    }
+
+    #[test]
+    fn test_check_package_flags_yanked_version() {
+        let versions = [
+            version("1.0.0", false),
+            version("1.1.0", true),
+            version("1.2.0", false),
+            version("2.0.0-rc.1", false),
+        ];
+        let mut package = LockedPackage {
+            name: "demo".to_string(),
+            version: "1.1.0".to_string(),
+            source: LockedSource::CratesIo,
+            yanked: None,
+            latest: None,
+            docs_rs: None,
+            error: None,
+        };
+        check_package(&mut package, &versions);
+        assert_eq!(package.yanked, Some(true));
+        assert_eq!(package.latest.as_deref(), Some("1.2.0"));
+        assert!(is_outdated(&package));
+
+        package.version = "0.9.0".to_string();
+        package.error = None;
+        check_package(&mut package, &versions);
+        assert!(package.error.is_some());
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Provides functionality to retrieve complete documentation for a Rust crate
 //! from docs.rs. Returns the main documentation page content including modules,
-//! structs, functions, etc.
+//! structs, functions, etc. When docs.rs has no build of the crate, the
+//! repository README is returned instead.
 
 #![allow(missing_docs)]
 
@@ -12,8 +13,9 @@
 //! for a specific Rust crate, including the crate name, optional version,
 //! and desired output format.
 
+use crate::tools::docs::filter::ContentFilters;
 use crate::tools::docs::html;
-use crate::tools::docs::DocService;
+use crate::tools::docs::{DocService, Revalidation};
 use crate::tools::Tool;
 use async_trait::async_trait;
 use rust_mcp_sdk::schema::CallToolError;
@@ -52,17 +54,68 @@ pub struct LookupCrateTool {
     /// Crate version (optional, defaults to latest)
     #[json_schema(
         title = "Version",
-        description = "Crate version, e.g.: 1.0.0. Uses latest version if not specified"
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2, ~0.4. Uses latest version if not specified"
     )]
     pub version: Option<String>,
 
-    /// Output format: "markdown", "text", or "html" (defaults to "markdown")
+    /// Output format: "markdown", "text", "html", or "llm" (defaults to "markdown")
     #[json_schema(
         title = "Output Format",
-        description = "Output format: markdown (default), text (plain text), html",
+        description = "Output format: markdown (default), text (plain text), html, llm (compact markdown for language models: no decoration, deduplicated links, signatures first, examples last)",
         default = "markdown"
     )]
     pub format: Option<String>,
+
+    /// Content filters overriding the server defaults
+    #[json_schema(
+        title = "Content Filters",
+        description = "Optional filters for boilerplate impl sections: auto_trait_impls, blanket_impls, trait_impls, implementors. 'name' strips a section, 'name=collapse' keeps only its impl headers, 'name=keep' disables a server-side filter. E.g.: [\"auto_trait_impls\", \"blanket_impls=collapse\"]"
+    )]
+    pub filters: Option<Vec<String>>,
+
+    /// Only return the section behind this anchor (see `get_toc`)
+    #[json_schema(
+        title = "Section",
+        description = "Optional anchor of a single section to return, as listed by get_toc, e.g.: examples, method.new"
+    )]
+    pub section: Option<String>,
+
+    /// Only return the sections with these headings (see `get_toc`)
+    #[json_schema(
+        title = "Sections",
+        description = "Optional heading names of the sections to return, matched case-insensitively (exactly, else as a substring), e.g.: [\"Examples\", \"Methods\", \"Trait Implementations\"]. Cannot be combined with section"
+    )]
+    pub sections: Option<Vec<String>>,
+
+    /// Approximate token budget of the output (at least 100)
+    #[json_schema(
+        title = "Max Tokens",
+        description = "Optional size limit in tokens (about 4 characters each, at least 100). Long pages are cut at the last section that fits, with a note on how to fetch the rest",
+        minimum = 100
+    )]
+    pub max_tokens: Option<u32>,
+
+    /// Character budget of the output, overriding `max_tokens`
+    #[json_schema(
+        title = "Max Length",
+        description = "Optional size limit in characters (at least 400); takes precedence over max_tokens",
+        minimum = 400
+    )]
+    pub max_length: Option<u32>,
+
+    /// Cursor of the next part of a long document
+    #[json_schema(
+        title = "Cursor",
+        description = "Cursor returned in the note of a truncated result (and as next_cursor in its _meta); returns the next part of the document. Keep the other arguments unchanged"
+    )]
+    pub cursor: Option<String>,
+
+    /// Ignore cached entries and fetch fresh documentation
+    #[json_schema(
+        title = "No Cache",
+        description = "Skip the cache and fetch fresh documentation, e.g. right after publishing a new version. The result is still cached for later requests"
+    )]
+    pub no_cache: Option<bool>,
 }
 
 /// Implementation of the lookup crate documentation tool
@@ -81,40 +134,124 @@ impl LookupCrateToolImpl {
         Self { service }
     }
 
-    /// Build docs.rs URL for crate
-    fn build_url(crate_name: &str, version: Option<&str>) -> String {
-        super::build_docs_url(crate_name, version)
+    /// Get the (sanitized) crate front page HTML
+    ///
+    /// The page is shared with the cache rather than copied, on hits and
+    /// misses alike. A missing crate is reported with similarly named crates
+    /// from crates.io, to catch typos such as `tokioutil`.
+    pub(super) async fn fetch_crate_html(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> std::result::Result<Arc<str>, CallToolError> {
+        if let Some(html) = self.fetch_crate_html_optional(crate_name, version).await? {
+            return Ok(html);
+        }
+        let suggestions = super::search::suggest_crate_names(&self.service, crate_name).await;
+        Err(super::crate_not_found_suggesting(crate_name, version, suggestions).into())
     }
 
-    async fn fetch_crate_html(
+    /// Get the (sanitized) crate front page HTML, `None` if no provider has
+    /// the crate
+    async fn fetch_crate_html_optional(
         &self,
         crate_name: &str,
         version: Option<&str>,
-    ) -> std::result::Result<String, CallToolError> {
+    ) -> std::result::Result<Option<Arc<str>>, CallToolError> {
+        if let Some(cached) = self
+            .service
+            .doc_cache()
+            .get_crate_html(crate_name, version)
+            .await
+        {
+            if self
+                .service
+                .doc_cache()
+                .is_crate_html_stale(crate_name, version)
+                .await
+            {
+                self.spawn_revalidate(crate_name, version);
+            }
+            return Ok(Some(cached));
+        }
+
+        // Popular pages are often requested by many sessions (and replicas)
+        // at once: let one of them fetch while the others wait for the cache
+        let _permit = self
+            .service
+            .fetches()
+            .begin(&super::cache::CacheKeyGenerator::crate_html_cache_key(
+                crate_name, version,
+            ))
+            .await;
         if let Some(cached) = self
             .service
             .doc_cache()
             .get_crate_html(crate_name, version)
             .await
         {
-            return Ok(cached.to_string());
+            return Ok(Some(cached));
         }
 
-        let url = Self::build_url(crate_name, version);
-        let html = self.service.fetch_html(&url, Some(TOOL_NAME)).await?;
+        let Some(html) = self
+            .service
+            .fetch_crate_page(crate_name, version, TOOL_NAME)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let html: Arc<str> = self.service.sanitize_page(html, TOOL_NAME).into();
 
         // Cache write failures must not fail the request (see fetch_crate_docs):
         // the HTML was fetched successfully, so log and continue uncached.
         if let Err(e) = self
             .service
             .doc_cache()
-            .set_crate_html(crate_name, version, html.clone())
+            .set_crate_html(crate_name, version, Arc::clone(&html))
             .await
         {
             tracing::warn!("[{TOOL_NAME}] failed to cache crate HTML (continuing uncached): {e}");
         }
 
-        Ok(html)
+        Ok(Some(html))
+    }
+
+    /// Refresh a stale crate page in the background
+    ///
+    /// The stale copy has already been served; the refresh replaces both the
+    /// cached HTML and the markdown rendered from it.
+    fn spawn_revalidate(&self, crate_name: &str, version: Option<&str>) {
+        let service = Arc::clone(&self.service);
+        let crate_name = crate_name.to_string();
+        let version = version.map(str::to_string);
+        tokio::spawn(async move {
+            revalidate_crate_page(&service, &crate_name, version.as_deref()).await;
+        });
+    }
+
+    /// Repository README standing in for missing docs.rs documentation
+    ///
+    /// Returns `None` when the crate has documentation, or when no README
+    /// can be found; the regular lookup (and its errors) applies then.
+    async fn fetch_readme_fallback(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> std::result::Result<Option<Arc<str>>, CallToolError> {
+        let page = self.fetch_crate_html_optional(crate_name, version).await?;
+        if page
+            .as_deref()
+            .is_some_and(|html| !super::readme::is_missing_build_page(html))
+        {
+            return Ok(None);
+        }
+        match super::readme::fetch_readme(&self.service, crate_name, version, TOOL_NAME).await {
+            Ok(readme) => Ok(readme),
+            Err(e) => {
+                tracing::warn!("[{TOOL_NAME}] README fallback failed: {e}");
+                Ok(None)
+            }
+        }
     }
 
     /// Get crate documentation (markdown format)
@@ -125,7 +262,17 @@ impl LookupCrateToolImpl {
         &self,
         crate_name: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<Arc<str>, CallToolError> {
+        // Cached markdown is unfiltered, so filtered requests are rendered
+        // from the (cached) HTML instead.
+        if filters.is_active() {
+            let html = self.fetch_crate_html(crate_name, version).await?;
+            return Ok(Arc::from(
+                html::extract_documentation(&filters.apply(&html)).into_boxed_str(),
+            ));
+        }
+
         // Try cache first - returns Arc<str> directly without cloning
         if let Some(cached) = self
             .service
@@ -133,6 +280,14 @@ impl LookupCrateToolImpl {
             .get_crate_docs(crate_name, version)
             .await
         {
+            if self
+                .service
+                .doc_cache()
+                .is_crate_docs_stale(crate_name, version)
+                .await
+            {
+                self.spawn_revalidate(crate_name, version);
+            }
             return Ok(cached);
         }
 
@@ -147,7 +302,7 @@ impl LookupCrateToolImpl {
         if let Err(e) = self
             .service
             .doc_cache()
-            .set_crate_docs(crate_name, version, docs.to_string())
+            .set_crate_docs(crate_name, version, Arc::clone(&docs))
             .await
         {
             tracing::warn!("[{TOOL_NAME}] failed to cache crate docs (continuing uncached): {e}");
@@ -161,9 +316,10 @@ impl LookupCrateToolImpl {
         &self,
         crate_name: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<String, CallToolError> {
         let html = self.fetch_crate_html(crate_name, version).await?;
-        Ok(html::extract_documentation_as_text(&html))
+        Ok(html::extract_documentation_as_text(&filters.apply(&html)))
     }
 
     /// Get crate documentation as raw HTML
@@ -171,32 +327,88 @@ impl LookupCrateToolImpl {
         &self,
         crate_name: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<String, CallToolError> {
         let html = self.fetch_crate_html(crate_name, version).await?;
-        Ok(html::extract_documentation_html(&html))
+        Ok(html::extract_documentation_html(&filters.apply(&html)))
     }
-}
 
-#[async_trait]
-impl Tool for LookupCrateToolImpl {
-    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
-        LookupCrateTool::tool()
+    /// Get the crate documentation in `format`
+    async fn fetch_formatted_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        filters: ContentFilters,
+        format: super::Format,
+    ) -> std::result::Result<String, CallToolError> {
+        match format {
+            super::Format::Text => {
+                self.fetch_crate_docs_as_text(crate_name, version, filters)
+                    .await
+            }
+            super::Format::Html => {
+                self.fetch_crate_docs_as_html(crate_name, version, filters)
+                    .await
+            }
+            super::Format::Json => Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(
+                    "Invalid format 'json'. This tool supports: markdown, text, html, llm"
+                        .to_string(),
+                ),
+            )),
+            super::Format::Markdown => self
+                .fetch_crate_docs(crate_name, version, filters)
+                .await
+                .map(|arc| arc.to_string()),
+            super::Format::Llm => self
+                .fetch_crate_docs(crate_name, version, filters)
+                .await
+                .map(|docs| super::llm::compact(&docs)),
+        }
     }
 
-    async fn execute(
+    /// Get the selected sections of the crate front page
+    async fn fetch_section(
         &self,
-        arguments: serde_json::Value,
-    ) -> std::result::Result<
-        rust_mcp_sdk::schema::CallToolResult,
-        rust_mcp_sdk::schema::CallToolError,
-    > {
-        let mut params: LookupCrateTool = serde_json::from_value(arguments).map_err(|e| {
-            rust_mcp_sdk::schema::CallToolError::invalid_arguments(
-                "lookup_crate",
-                Some(format!("Parameter parsing failed: {e}")),
-            )
-        })?;
+        crate_name: &str,
+        version: Option<&str>,
+        selection: &super::toc::SectionSelection,
+        filters: &ContentFilters,
+        format: super::Format,
+    ) -> std::result::Result<String, CallToolError> {
+        let html = self.fetch_crate_html(crate_name, version).await?;
+        let html = selection.extract(TOOL_NAME, &html)?;
+        let html = filters.apply(&html);
+        Ok(match format {
+            super::Format::Text => html::extract_documentation_as_text(&html),
+            super::Format::Html => html::extract_documentation_html(&html),
+            super::Format::Llm => super::llm::compact(&html::extract_documentation(&html)),
+            _ => html::extract_documentation(&html),
+        })
+    }
 
+    /// Concrete version of the documentation served for `version`
+    ///
+    /// A concrete version is served as requested; otherwise (no version,
+    /// `latest`) it is read from the page, which is cached by then.
+    async fn served_version(&self, crate_name: &str, version: Option<&str>) -> Option<String> {
+        if let Some(version) = version.filter(|v| semver::Version::parse(v).is_ok()) {
+            return Some(version.to_string());
+        }
+        let html = self
+            .fetch_crate_html_optional(crate_name, version)
+            .await
+            .ok()
+            .flatten();
+        html.and_then(|html| html::page_version(&html))
+    }
+
+    /// Look up the crate documentation for parsed `params`
+    async fn lookup(
+        &self,
+        mut params: LookupCrateTool,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
         // Propagate the detailed parse error (e.g. "Invalid format 'xml'. Expected
         // one of: ...") rather than masking it with a generic message, so callers
         // get actionable feedback.
@@ -210,33 +422,155 @@ impl Tool for LookupCrateToolImpl {
         }
 
         let format = super::parse_format(TOOL_NAME, params.format.as_deref(), super::DOC_FORMATS)?;
-        let content = match format {
-            super::Format::Text => {
-                self.fetch_crate_docs_as_text(&params.crate_name, params.version.as_deref())
+        let budget = super::budget::char_budget(params.max_tokens, params.max_length);
+        let filters = self
+            .service
+            .request_filters(params.filters.as_deref(), TOOL_NAME)?;
+        let selection = super::toc::SectionSelection::from_params(
+            TOOL_NAME,
+            params.section.as_deref(),
+            params.sections.as_deref(),
+        )?;
+        params.crate_name = self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        params.version = self
+            .service
+            .resolve_version_param(&params.crate_name, params.version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let content = if let Some(selection) = selection {
+            self.fetch_section(
+                &params.crate_name,
+                params.version.as_deref(),
+                &selection,
+                &filters,
+                format,
+            )
+            .await?
+        } else {
+            // docs.rs has no documentation for this version: the README is
+            // the closest thing, in every format
+            let readme = if format == super::Format::Json {
+                None
+            } else {
+                self.fetch_readme_fallback(&params.crate_name, params.version.as_deref())
                     .await?
-            }
-            super::Format::Html => {
-                self.fetch_crate_docs_as_html(&params.crate_name, params.version.as_deref())
+            };
+            match readme {
+                Some(readme) if format == super::Format::Llm => super::llm::compact(&readme),
+                Some(readme) => readme.to_string(),
+                None => {
+                    self.fetch_formatted_docs(
+                        &params.crate_name,
+                        params.version.as_deref(),
+                        filters,
+                        format,
+                    )
                     .await?
+                }
             }
-            super::Format::Json => {
-                return Err(rust_mcp_sdk::schema::CallToolError::invalid_arguments(
-                    "lookup_crate",
-                    Some(
-                        "Invalid format 'json'. This tool supports: markdown, text, html"
-                            .to_string(),
-                    ),
-                ))
-            }
-            super::Format::Markdown => self
-                .fetch_crate_docs(&params.crate_name, params.version.as_deref())
-                .await
-                .map(|arc| arc.to_string())?,
         };
+        self.service
+            .record_lookup(&params.crate_name, None, TOOL_NAME)
+            .await;
+
+        let page = super::budget::paginate(
+            TOOL_NAME,
+            &content,
+            budget,
+            params.cursor.as_deref(),
+            format,
+        )?;
+        let version = self
+            .served_version(&params.crate_name, params.version.as_deref())
+            .await;
+        Ok(page
+            .with_version(&params.crate_name, version, format)
+            .into_result())
+    }
+}
+
+/// Fetch a crate page again and replace its (stale) cached HTML and markdown
+///
+/// Only one task across replicas refreshes a page at a time, and an
+/// unchanged page is confirmed with a conditional request instead of being
+/// downloaded again. A failed refresh leaves the stale copy in place until
+/// the stale window ends.
+async fn revalidate_crate_page(service: &DocService, crate_name: &str, version: Option<&str>) {
+    let doc_cache = service.doc_cache();
+    let key = super::cache::CacheKeyGenerator::crate_html_cache_key(crate_name, version);
+    let holder = uuid::Uuid::new_v4().to_string();
+    if !doc_cache.try_claim_refresh(&key, &holder).await {
+        return;
+    }
+    // Another refresh may have finished since the stale copy was served
+    if !doc_cache.is_crate_html_stale(crate_name, version).await
+        && !doc_cache.is_crate_docs_stale(crate_name, version).await
+    {
+        doc_cache.release_refresh(&key, &holder).await;
+        return;
+    }
+
+    let revalidated = service
+        .revalidate_crate_page(crate_name, version, TOOL_NAME)
+        .await
+        .map_err(|e| e.to_string());
+    let html: Option<Arc<str>> = match revalidated {
+        Ok(Revalidation::Modified(html)) => Some(service.sanitize_page(html, TOOL_NAME).into()),
+        // Storing the cached copy again restarts its TTL
+        Ok(Revalidation::NotModified) => {
+            tracing::debug!("[{TOOL_NAME}] page of '{crate_name}' not modified");
+            doc_cache.get_crate_html(crate_name, version).await
+        }
+        Ok(Revalidation::NotFound) => {
+            tracing::debug!("[{TOOL_NAME}] '{crate_name}' no longer found, keeping stale page");
+            None
+        }
+        Err(e) => {
+            tracing::warn!("[{TOOL_NAME}] refresh of '{crate_name}' failed: {e}");
+            None
+        }
+    };
+    if let Some(html) = html {
+        let docs = html::extract_documentation(&html);
+        let stored = match doc_cache
+            .set_crate_html(crate_name, version, Arc::clone(&html))
+            .await
+        {
+            Ok(()) => doc_cache.set_crate_docs(crate_name, version, docs).await,
+            Err(e) => Err(e),
+        };
+        match stored {
+            Ok(()) => tracing::debug!("[{TOOL_NAME}] refreshed stale page of '{crate_name}'"),
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to cache refreshed page: {e}"),
+        }
+    }
+    doc_cache.release_refresh(&key, &holder).await;
+}
+
+#[async_trait]
+impl Tool for LookupCrateToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        LookupCrateTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<
+        rust_mcp_sdk::schema::CallToolResult,
+        rust_mcp_sdk::schema::CallToolError,
+    > {
+        let params: LookupCrateTool = serde_json::from_value(arguments).map_err(|e| {
+            rust_mcp_sdk::schema::CallToolError::invalid_arguments(
+                "lookup_crate",
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
 
-        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
-            content.into(),
-        ]))
+        super::cache::with_no_cache(params.no_cache.unwrap_or(false), self.lookup(params)).await
     }
 }
 
@@ -248,14 +582,14 @@ impl Default for LookupCrateToolImpl {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::super::provider::{DocsProvider, DocsRsProvider};
     use serial_test::serial;
 
     #[test]
     #[serial]
     fn test_build_url_without_version() {
         std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = LookupCrateToolImpl::build_url("serde", None);
+        let url = DocsRsProvider.crate_url("serde", None);
         assert_eq!(url, "https://docs.rs/serde/");
         std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
     }
@@ -264,7 +598,7 @@ mod tests {
     #[serial]
     fn test_build_url_with_version() {
         std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = LookupCrateToolImpl::build_url("serde", Some("1.0.0"));
+        let url = DocsRsProvider.crate_url("serde", Some("1.0.0"));
         assert_eq!(url, "https://docs.rs/serde/1.0.0/");
         std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
     }
@@ -273,7 +607,7 @@ mod tests {
     #[serial]
     fn test_build_url_with_custom_base() {
         std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "http://mock-server");
-        let url = LookupCrateToolImpl::build_url("serde", None);
+        let url = DocsRsProvider.crate_url("serde", None);
         assert_eq!(url, "http://mock-server/serde/");
         std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
     }
@@ -6,7 +6,9 @@
 
 #![allow(missing_docs)]
 
+use crate::tools::docs::filter::ContentFilters;
 use crate::tools::docs::html;
+use crate::tools::docs::provider::DocsProvider;
 use crate::tools::docs::DocService;
 use crate::tools::Tool;
 use async_trait::async_trait;
@@ -55,17 +57,68 @@ pub struct LookupItemTool {
     /// Crate version (optional, defaults to latest)
     #[json_schema(
         title = "Version",
-        description = "Crate version. Uses latest version if not specified"
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
     )]
     pub version: Option<String>,
 
-    /// Output format: "markdown", "text", or "html" (defaults to "markdown")
+    /// Output format: "markdown", "text", "html", or "llm" (defaults to "markdown")
     #[json_schema(
         title = "Output Format",
-        description = "Output format: markdown (default), text (plain text), html",
+        description = "Output format: markdown (default), text (plain text), html, llm (compact markdown for language models: no decoration, deduplicated links, signatures first, examples last)",
         default = "markdown"
     )]
     pub format: Option<String>,
+
+    /// Content filters overriding the server defaults
+    #[json_schema(
+        title = "Content Filters",
+        description = "Optional filters for boilerplate impl sections: auto_trait_impls, blanket_impls, trait_impls, implementors. 'name' strips a section, 'name=collapse' keeps only its impl headers, 'name=keep' disables a server-side filter. E.g.: [\"auto_trait_impls\", \"blanket_impls=collapse\"]"
+    )]
+    pub filters: Option<Vec<String>>,
+
+    /// Only return the section behind this anchor (see `get_toc`)
+    #[json_schema(
+        title = "Section",
+        description = "Optional anchor of a single section to return, as listed by get_toc, e.g.: examples, method.new"
+    )]
+    pub section: Option<String>,
+
+    /// Only return the sections with these headings (see `get_toc`)
+    #[json_schema(
+        title = "Sections",
+        description = "Optional heading names of the sections to return, matched case-insensitively (exactly, else as a substring), e.g.: [\"Examples\", \"Methods\", \"Trait Implementations\"]. Cannot be combined with section"
+    )]
+    pub sections: Option<Vec<String>>,
+
+    /// Approximate token budget of the output (at least 100)
+    #[json_schema(
+        title = "Max Tokens",
+        description = "Optional size limit in tokens (about 4 characters each, at least 100). Long pages are cut at the last section that fits, with a note on how to fetch the rest",
+        minimum = 100
+    )]
+    pub max_tokens: Option<u32>,
+
+    /// Character budget of the output, overriding `max_tokens`
+    #[json_schema(
+        title = "Max Length",
+        description = "Optional size limit in characters (at least 400); takes precedence over max_tokens",
+        minimum = 400
+    )]
+    pub max_length: Option<u32>,
+
+    /// Cursor of the next part of a long document
+    #[json_schema(
+        title = "Cursor",
+        description = "Cursor returned in the note of a truncated result (and as next_cursor in its _meta); returns the next part of the document. Keep the other arguments unchanged"
+    )]
+    pub cursor: Option<String>,
+
+    /// Ignore cached entries and fetch fresh documentation
+    #[json_schema(
+        title = "No Cache",
+        description = "Skip the cache and fetch fresh documentation, e.g. right after publishing a new version. The result is still cached for later requests"
+    )]
+    pub no_cache: Option<bool>,
 }
 
 /// Implementation of the lookup item documentation tool
@@ -88,6 +141,80 @@ enum AllHtmlMemo {
     Fetched(Option<String>),
 }
 
+/// Candidates listed in an ambiguity error
+const MAX_CANDIDATES: usize = 10;
+
+/// Outcome of looking an item path up in a crate's `all.html` index
+#[derive(Debug, PartialEq, Eq)]
+enum IndexMatch {
+    /// No item of that name (and module)
+    Missing,
+    /// Exactly one item; its href relative to the crate root
+    Unique(String),
+    /// Several items, as `path (kind)` candidates
+    Ambiguous(Vec<String>),
+}
+
+/// Find `item_path` in a crate's `all.html` index
+///
+/// The last segment is the item name; the segments before it (without the
+/// crate name) must appear, in order, in the item's module path. Items whose
+/// module path ends with them are preferred, so `mpsc::channel` picks
+/// `sync::mpsc::channel` while a bare `channel` matches every `channel`.
+fn match_in_index(all_html: &str, crate_name: &str, item_path: &str) -> IndexMatch {
+    let root = crate_name.replace('-', "_");
+    let mut segments: Vec<&str> = item_path.split("::").map(str::trim).collect();
+    if segments.len() > 1 && segments[0].replace('-', "_") == root {
+        segments.remove(0);
+    }
+    let Some((name, qualifier)) = segments.split_last() else {
+        return IndexMatch::Missing;
+    };
+    let qualifier = qualifier.join("::");
+    let entries = super::symbol::parse_all_items(all_html);
+    let module_of =
+        |entry: &super::symbol::IndexEntry| format!("::{}::", entry.module.replace('/', "::"));
+    let mut matches: Vec<&super::symbol::IndexEntry> = entries
+        .iter()
+        .filter(|entry| entry.name == *name)
+        .filter(|entry| {
+            qualifier.is_empty() || module_of(entry).contains(&format!("::{qualifier}::"))
+        })
+        .collect();
+    if matches.len() > 1 && !qualifier.is_empty() {
+        let suffix = format!("::{qualifier}::");
+        if matches
+            .iter()
+            .any(|entry| module_of(entry).ends_with(&suffix))
+        {
+            matches.retain(|entry| module_of(entry).ends_with(&suffix));
+        }
+    }
+    match matches.as_slice() {
+        [] => IndexMatch::Missing,
+        [entry] => IndexMatch::Unique(entry.href()),
+        _ => {
+            let mut candidates: Vec<String> = matches
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|entry| format!("{} ({})", entry.path(crate_name), entry.kind))
+                .collect();
+            if matches.len() > MAX_CANDIDATES {
+                candidates.push(format!("{} more", matches.len() - MAX_CANDIDATES));
+            }
+            IndexMatch::Ambiguous(candidates)
+        }
+    }
+}
+
+/// Whether a tool error reports an ambiguous item path
+fn is_ambiguity(error: &CallToolError) -> bool {
+    matches!(
+        error.0.downcast_ref::<crate::error::Error>(),
+        Some(crate::error::Error::Ambiguous { .. })
+    )
+}
+
 impl LookupItemToolImpl {
     /// Create a new lookup item tool instance
     #[must_use]
@@ -95,36 +222,54 @@ impl LookupItemToolImpl {
         Self { service }
     }
 
-    /// Build docs.rs search URL for item
-    fn build_search_url(crate_name: &str, item_path: &str, version: Option<&str>) -> String {
-        super::build_docs_item_url(crate_name, version, item_path)
-    }
-
-    async fn fetch_item_html(
+    /// Get the (sanitized) item page HTML
+    ///
+    /// The page is shared with the cache rather than copied, on hits and
+    /// misses alike.
+    pub(super) async fn fetch_item_html(
         &self,
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
-    ) -> std::result::Result<String, CallToolError> {
+    ) -> std::result::Result<Arc<str>, CallToolError> {
+        if let Some(cached) = self
+            .service
+            .doc_cache()
+            .get_item_html(crate_name, item_path, version)
+            .await
+        {
+            return Ok(cached);
+        }
+
+        // Popular pages are often requested by many sessions (and replicas)
+        // at once: let one of them fetch while the others wait for the cache
+        let _permit = self
+            .service
+            .fetches()
+            .begin(&super::cache::CacheKeyGenerator::item_html_cache_key(
+                crate_name, item_path, version,
+            ))
+            .await;
         if let Some(cached) = self
             .service
             .doc_cache()
             .get_item_html(crate_name, item_path, version)
             .await
         {
-            return Ok(cached.to_string());
+            return Ok(cached);
         }
 
         let html = self
             .resolve_item_html(crate_name, item_path, version)
             .await?;
+        let html: Arc<str> = self.service.sanitize_page(html, TOOL_NAME).into();
 
         // Cache write failures must not fail the request (see fetch_item_docs):
         // the HTML was fetched successfully, so log and continue uncached.
         if let Err(e) = self
             .service
             .doc_cache()
-            .set_item_html(crate_name, item_path, version, html.clone())
+            .set_item_html(crate_name, item_path, version, Arc::clone(&html))
             .await
         {
             tracing::warn!("[{TOOL_NAME}] failed to cache item HTML (continuing uncached): {e}");
@@ -135,28 +280,83 @@ impl LookupItemToolImpl {
 
     /// Resolve and fetch the HTML for a specific item.
     ///
-    /// Probes the candidate rustdoc item URLs (`struct.`, `trait.`, `fn.`, ...)
-    /// and returns the first that exists. docs.rs renders in-page search with
-    /// client-side JavaScript, so the `?search=` URL only ever returns the
-    /// crate landing page server-side; therefore, if no direct item page is
-    /// found, it falls back to that crate page so the caller still gets useful
-    /// context instead of a hard error.
+    /// Each configured provider is tried in turn: probe the candidate rustdoc
+    /// item URLs (`struct.`, `trait.`, `fn.`, ..., module `index.html`), then
+    /// look the item up in the crate's `all.html` index. If no provider has
+    /// an item page, the crate landing page stands in so the caller still
+    /// gets useful context instead of a hard error. (rustdoc's `?search=`
+    /// runs in the browser; server-side it only returns that same page.)
+    ///
+    /// A provider that fails (rather than reporting 404) is skipped; its error
+    /// surfaces only if no provider resolves the item or the crate page.
+    ///
+    /// Relative links in the returned page are made absolute against the URL
+    /// it was served from, so the rendered docs carry deep links.
     async fn resolve_item_html(
         &self,
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
     ) -> std::result::Result<String, CallToolError> {
+        // `CallToolError` is not `Send`, so only its message is kept across
+        // the following awaits.
+        let mut last_error: Option<String> = None;
+        for provider in self.service.providers_for(crate_name) {
+            match self
+                .resolve_item_html_with(provider.as_ref(), crate_name, item_path, version)
+                .await
+            {
+                Ok(Some(html)) => return Ok(html),
+                Ok(None) => {}
+                // The caller has to pick: another provider or the crate page
+                // would only hide the candidates
+                Err(e) if is_ambiguity(&e) => return Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        provider = provider.name(),
+                        "[{TOOL_NAME}] provider failed, trying next: {e}"
+                    );
+                    last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        // Fallback: the crate landing page, shared with `lookup_crate`'s
+        // provider chain
+        match self
+            .service
+            .fetch_crate_page(crate_name, version, TOOL_NAME)
+            .await
+        {
+            Ok(Some(html)) => return Ok(html),
+            Ok(None) => {}
+            Err(e) => last_error = Some(e.to_string()),
+        }
+
+        Err(last_error.map_or_else(
+            || super::crate_not_found(crate_name, version).into(),
+            CallToolError::from_message,
+        ))
+    }
+
+    /// Resolve an item's page from a single provider
+    async fn resolve_item_html_with(
+        &self,
+        provider: &dyn DocsProvider,
+        crate_name: &str,
+        item_path: &str,
+        version: Option<&str>,
+    ) -> std::result::Result<Option<String>, CallToolError> {
         // Reuse a single `all.html` fetch across the full-path and parent-path
         // resolution attempts. Both attempts consult the same crate-level
         // `all.html` index, so memoizing it here avoids a duplicate network
         // round trip when neither path resolves via a direct item page.
         let mut all_html_memo = AllHtmlMemo::Unfetched;
         if let Some(html) = self
-            .try_resolve_item_path(crate_name, item_path, version, &mut all_html_memo)
+            .try_resolve_item_path(provider, crate_name, item_path, version, &mut all_html_memo)
             .await?
         {
-            return Ok(html);
+            return Ok(Some(html));
         }
 
         // Method / associated-item fallback: `Type::member` and trait methods
@@ -167,18 +367,19 @@ impl LookupItemToolImpl {
         if let Some((parent, _member)) = item_path.rsplit_once("::") {
             let parent = parent.trim();
             if !parent.is_empty() {
-                if let Some(html) = self
-                    .try_resolve_item_path(crate_name, parent, version, &mut all_html_memo)
-                    .await?
-                {
-                    return Ok(html);
-                }
+                return self
+                    .try_resolve_item_path(
+                        provider,
+                        crate_name,
+                        parent,
+                        version,
+                        &mut all_html_memo,
+                    )
+                    .await;
             }
         }
 
-        // Fallback: the crate page (legacy `?search=` behaviour).
-        let url = Self::build_search_url(crate_name, item_path, version);
-        self.service.fetch_html(&url, Some(TOOL_NAME)).await
+        Ok(None)
     }
 
     /// Probe the candidate rustdoc item pages and the crate `all.html`
@@ -186,19 +387,20 @@ impl LookupItemToolImpl {
     /// or `None` if neither path resolves.
     async fn try_resolve_item_path(
         &self,
+        provider: &dyn DocsProvider,
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
         all_html_memo: &mut AllHtmlMemo,
     ) -> std::result::Result<Option<String>, CallToolError> {
-        let candidates = super::build_docs_item_url_candidates(crate_name, version, item_path);
+        let candidates = provider.item_url_candidates(crate_name, version, item_path);
         for url in candidates {
             if let Some(html) = self
                 .service
                 .fetch_html_optional(&url, Some(TOOL_NAME))
                 .await?
             {
-                return Ok(Some(html));
+                return Ok(Some(html::absolutize_links(&html, &url)));
             }
         }
 
@@ -212,7 +414,7 @@ impl LookupItemToolImpl {
             // resolution attempt for the parent path reuses it instead of
             // issuing a duplicate request.
             if matches!(all_html_memo, AllHtmlMemo::Unfetched) {
-                let all_url = super::build_docs_all_items_url(crate_name, version);
+                let all_url = provider.all_items_url(crate_name, version);
                 // Bind the fallible await to a `let` so the `?` temporary is
                 // dropped at the statement boundary and not held across a later
                 // await (which would make the future non-`Send`).
@@ -224,14 +426,20 @@ impl LookupItemToolImpl {
             }
             // Compute the resolved URL in a scope that ends before the next
             // await so the borrow of `all_html_memo` is not held across it.
-            let item_url = {
-                let all_html = match &*all_html_memo {
-                    AllHtmlMemo::Fetched(html) => html.as_deref(),
-                    AllHtmlMemo::Unfetched => None,
-                };
-                all_html.and_then(|html| {
-                    super::find_item_url_in_all_html(crate_name, version, html, item_name)
-                })
+            let found = match &*all_html_memo {
+                AllHtmlMemo::Fetched(Some(html)) => match_in_index(html, crate_name, item_path),
+                _ => IndexMatch::Missing,
+            };
+            let item_url = match found {
+                IndexMatch::Missing => None,
+                IndexMatch::Unique(href) => {
+                    Some(provider.item_url_from_href(crate_name, version, &href))
+                }
+                IndexMatch::Ambiguous(candidates) => {
+                    return Err(
+                        crate::error::Error::ambiguous("item", item_path, candidates).into(),
+                    )
+                }
             };
             if let Some(item_url) = item_url {
                 let resolved = self
@@ -239,7 +447,7 @@ impl LookupItemToolImpl {
                     .fetch_html_optional(&item_url, Some(TOOL_NAME))
                     .await?;
                 if let Some(html) = resolved {
-                    return Ok(Some(html));
+                    return Ok(Some(html::absolutize_links(&html, &item_url)));
                 }
             }
         }
@@ -256,7 +464,17 @@ impl LookupItemToolImpl {
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<Arc<str>, CallToolError> {
+        // Cached markdown is unfiltered, so filtered requests are rendered
+        // from the (cached) HTML instead.
+        if filters.is_active() {
+            let html = self.fetch_item_html(crate_name, item_path, version).await?;
+            return Ok(Arc::from(
+                html::extract_search_results(&filters.apply(&html), item_path).into_boxed_str(),
+            ));
+        }
+
         // Try cache first - returns Arc<str> directly without cloning
         if let Some(cached) = self
             .service
@@ -279,7 +497,7 @@ impl LookupItemToolImpl {
         if let Err(e) = self
             .service
             .doc_cache()
-            .set_item_docs(crate_name, item_path, version, docs.to_string())
+            .set_item_docs(crate_name, item_path, version, Arc::clone(&docs))
             .await
         {
             tracing::warn!("[{TOOL_NAME}] failed to cache item docs (continuing uncached): {e}");
@@ -294,9 +512,10 @@ impl LookupItemToolImpl {
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<String, CallToolError> {
         let html = self.fetch_item_html(crate_name, item_path, version).await?;
-        let body = html::extract_documentation_as_text(&html);
+        let body = html::extract_documentation_as_text(&filters.apply(&html));
         // Mirror the markdown fallback note. `is_item_fallback_page` inspects
         // the page `<h1>` so it catches both the containing-type fallback
         // (e.g. the `Value` enum page for `Value::is_null`) and the crate
@@ -317,9 +536,10 @@ impl LookupItemToolImpl {
         crate_name: &str,
         item_path: &str,
         version: Option<&str>,
+        filters: ContentFilters,
     ) -> std::result::Result<String, CallToolError> {
         let html = self.fetch_item_html(crate_name, item_path, version).await?;
-        let body = html::extract_documentation_html(&html);
+        let body = html::extract_documentation_html(&filters.apply(&html));
         // Mirror the markdown/text fallback note so all three formats are
         // consistent. `is_item_fallback_page` inspects the page `<h1>` to catch
         // both the containing-type fallback and the crate overview fallback,
@@ -337,30 +557,103 @@ impl LookupItemToolImpl {
         }
         Ok(body)
     }
-}
 
-#[async_trait]
-impl Tool for LookupItemToolImpl {
-    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
-        LookupItemTool::tool()
+    /// Get the selected sections of an item page
+    async fn fetch_section(
+        &self,
+        crate_name: &str,
+        item_path: &str,
+        version: Option<&str>,
+        selection: &super::toc::SectionSelection,
+        filters: &ContentFilters,
+        format: super::Format,
+    ) -> std::result::Result<String, CallToolError> {
+        let html = self.fetch_item_html(crate_name, item_path, version).await?;
+        let html = selection.extract(TOOL_NAME, &html)?;
+        let html = filters.apply(&html);
+        Ok(match format {
+            super::Format::Text => html::extract_documentation_as_text(&html),
+            super::Format::Html => html::extract_documentation_html(&html),
+            super::Format::Llm => {
+                super::llm::compact(&html::extract_search_results(&html, item_path))
+            }
+            _ => html::extract_search_results(&html, item_path),
+        })
     }
 
-    async fn execute(
+    /// Get the whole item page in `format`
+    async fn fetch_formatted_docs(
         &self,
-        arguments: serde_json::Value,
-    ) -> std::result::Result<
-        rust_mcp_sdk::schema::CallToolResult,
-        rust_mcp_sdk::schema::CallToolError,
-    > {
-        let mut params: LookupItemTool = serde_json::from_value(arguments).map_err(|e| {
-            rust_mcp_sdk::schema::CallToolError::invalid_arguments(
-                "lookup_item",
-                Some(format!("Parameter parsing failed: {e}")),
-            )
-        })?;
+        crate_name: &str,
+        item_path: &str,
+        version: Option<&str>,
+        filters: ContentFilters,
+        format: super::Format,
+    ) -> std::result::Result<String, CallToolError> {
+        Ok(match format {
+            super::Format::Text => {
+                self.fetch_item_docs_as_text(crate_name, item_path, version, filters)
+                    .await?
+            }
+            super::Format::Html => {
+                self.fetch_item_docs_as_html(crate_name, item_path, version, filters)
+                    .await?
+            }
+            super::Format::Json => {
+                return Err(rust_mcp_sdk::schema::CallToolError::invalid_arguments(
+                    "lookup_item",
+                    Some(
+                        "Invalid format 'json'. This tool supports: markdown, text, html, llm"
+                            .to_string(),
+                    ),
+                ))
+            }
+            super::Format::Markdown | super::Format::Llm => {
+                let docs = self
+                    .fetch_item_docs(crate_name, item_path, version, filters)
+                    .await?;
+                if format == super::Format::Llm {
+                    super::llm::compact(&docs)
+                } else {
+                    docs.to_string()
+                }
+            }
+        })
+    }
+
+    /// Concrete version of the documentation served for `version`
+    ///
+    /// A concrete version is served as requested; otherwise (no version,
+    /// `latest`) it is read from the page, which is cached by then.
+    async fn served_version(
+        &self,
+        crate_name: &str,
+        item_path: &str,
+        version: Option<&str>,
+    ) -> Option<String> {
+        if let Some(version) = version.filter(|v| semver::Version::parse(v).is_ok()) {
+            return Some(version.to_string());
+        }
+        let html = self
+            .fetch_item_html(crate_name, item_path, version)
+            .await
+            .ok();
+        html.and_then(|html| html::page_version(&html))
+    }
 
+    /// Look up the item documentation for parsed `params`
+    async fn lookup(
+        &self,
+        mut params: LookupItemTool,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
         super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
         super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        // Macros are usually written with their bang (`serde_json::json!`),
+        // which is not part of the rustdoc path; `lookup_macro` handles the
+        // other macro spellings.
+        if let Some(path) = params.item_path.trim().strip_suffix('!') {
+            params.item_path = path.to_string();
+        }
         super::validate_item_path(TOOL_NAME, &params.item_path)?;
         // Normalise surrounding whitespace so it does not leak into headings or
         // candidate URL construction.
@@ -374,45 +667,89 @@ impl Tool for LookupItemToolImpl {
         // one of: ...") rather than masking it with a generic message, so callers
         // get actionable feedback.
         let format = super::parse_format(TOOL_NAME, params.format.as_deref(), super::DOC_FORMATS)?;
-        let content = match format {
-            super::Format::Text => {
-                self.fetch_item_docs_as_text(
-                    &params.crate_name,
-                    &params.item_path,
-                    params.version.as_deref(),
-                )
-                .await?
-            }
-            super::Format::Html => {
-                self.fetch_item_docs_as_html(
-                    &params.crate_name,
-                    &params.item_path,
-                    params.version.as_deref(),
-                )
-                .await?
-            }
-            super::Format::Json => {
-                return Err(rust_mcp_sdk::schema::CallToolError::invalid_arguments(
-                    "lookup_item",
-                    Some(
-                        "Invalid format 'json'. This tool supports: markdown, text, html"
-                            .to_string(),
-                    ),
-                ))
-            }
-            super::Format::Markdown => self
-                .fetch_item_docs(
-                    &params.crate_name,
-                    &params.item_path,
-                    params.version.as_deref(),
-                )
-                .await
-                .map(|arc| arc.to_string())?,
+        let budget = super::budget::char_budget(params.max_tokens, params.max_length);
+        let filters = self
+            .service
+            .request_filters(params.filters.as_deref(), TOOL_NAME)?;
+        let selection = super::toc::SectionSelection::from_params(
+            TOOL_NAME,
+            params.section.as_deref(),
+            params.sections.as_deref(),
+        )?;
+        params.crate_name = self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        params.version = self
+            .service
+            .resolve_version_param(&params.crate_name, params.version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let content = if let Some(selection) = selection {
+            self.fetch_section(
+                &params.crate_name,
+                &params.item_path,
+                params.version.as_deref(),
+                &selection,
+                &filters,
+                format,
+            )
+            .await?
+        } else {
+            self.fetch_formatted_docs(
+                &params.crate_name,
+                &params.item_path,
+                params.version.as_deref(),
+                filters,
+                format,
+            )
+            .await?
         };
+        self.service
+            .record_lookup(&params.crate_name, Some(&params.item_path), TOOL_NAME)
+            .await;
+
+        let page = super::budget::paginate(
+            TOOL_NAME,
+            &content,
+            budget,
+            params.cursor.as_deref(),
+            format,
+        )?;
+        let version = self
+            .served_version(
+                &params.crate_name,
+                &params.item_path,
+                params.version.as_deref(),
+            )
+            .await;
+        Ok(page
+            .with_version(&params.crate_name, version, format)
+            .into_result())
+    }
+}
 
-        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
-            content.into(),
-        ]))
+#[async_trait]
+impl Tool for LookupItemToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        LookupItemTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<
+        rust_mcp_sdk::schema::CallToolResult,
+        rust_mcp_sdk::schema::CallToolError,
+    > {
+        let params: LookupItemTool = serde_json::from_value(arguments).map_err(|e| {
+            rust_mcp_sdk::schema::CallToolError::invalid_arguments(
+                "lookup_item",
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::cache::with_no_cache(params.no_cache.unwrap_or(false), self.lookup(params)).await
     }
 }
 
@@ -424,33 +761,38 @@ impl Default for LookupItemToolImpl {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use serial_test::serial;
-
-    #[test]
-    #[serial]
-    fn test_build_search_url_without_version() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = LookupItemToolImpl::build_search_url("serde", "Serialize", None);
-        assert_eq!(url, "https://docs.rs/serde/?search=Serialize");
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
-    }
-
-    #[test]
-    #[serial]
-    fn test_build_search_url_with_version() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = LookupItemToolImpl::build_search_url("serde", "Serialize", Some("1.0.0"));
-        assert_eq!(url, "https://docs.rs/serde/1.0.0/?search=Serialize");
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
-    }
-
     #[test]
-    #[serial]
-    fn test_build_search_url_encodes_special_chars() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = LookupItemToolImpl::build_search_url("std", "collections::HashMap", None);
-        assert!(url.contains("collections%3A%3AHashMap"));
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
+    fn test_match_in_index() {
+        use super::{match_in_index, IndexMatch};
+        let all = concat!(
+            r#"<a href="sync/mpsc/fn.channel.html">sync::mpsc::channel</a>"#,
+            r#"<a href="sync/oneshot/fn.channel.html">sync::oneshot::channel</a>"#,
+            r#"<a href="task/fn.spawn.html">task::spawn</a>"#,
+            r#"<a href="struct.Runtime.html">Runtime</a>"#,
+        );
+        assert_eq!(
+            match_in_index(all, "tokio", "tokio::spawn"),
+            IndexMatch::Unique("task/fn.spawn.html".to_string())
+        );
+        assert_eq!(
+            match_in_index(all, "tokio", "channel"),
+            IndexMatch::Ambiguous(vec![
+                "tokio::sync::mpsc::channel (fn)".to_string(),
+                "tokio::sync::oneshot::channel (fn)".to_string(),
+            ])
+        );
+        assert_eq!(
+            match_in_index(all, "tokio", "tokio::sync::channel"),
+            match_in_index(all, "tokio", "channel")
+        );
+        assert_eq!(
+            match_in_index(all, "tokio", "mpsc::channel"),
+            IndexMatch::Unique("sync/mpsc/fn.channel.html".to_string())
+        );
+        // Not a module: left to the parent-path fallback
+        assert_eq!(
+            match_in_index(all, "tokio", "Runtime::spawn"),
+            IndexMatch::Missing
+        );
     }
 }
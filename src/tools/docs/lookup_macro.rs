@@ -0,0 +1,683 @@
+//! Lookup macro documentation tool
+//!
+//! Macros do not fit `lookup_item` well: callers naturally write them as
+//! `json!`, `#[tokio::main]` or `#[derive(Serialize)]`, rustdoc files them
+//! under `macro.`, `attr.` and `derive.` pages, and `#[macro_export]` hoists
+//! declarative macros to the crate root whatever module defines them. This
+//! tool accepts those spellings, probes the matching pages and returns the
+//! macro's syntax (its `macro_rules!` arms or attribute form) plus the code
+//! examples from its documentation.
+
+#![allow(missing_docs)]
+
+use crate::tools::docs::provider::DocsProvider;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "lookup_macro";
+
+/// Examples beyond this are left out; the rest of the page has them all
+const MAX_EXAMPLES: usize = 5;
+
+static DECL_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("#main-content pre.item-decl").expect("hardcoded valid selector")
+});
+
+static TOP_DOC_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "#main-content .top-doc .docblock, #main-content .docblock:not(.sanitize-notice)",
+    )
+    .expect("hardcoded valid selector")
+});
+
+static PARAGRAPH_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("p").expect("hardcoded valid selector"));
+
+static EXAMPLE_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("pre.rust").expect("hardcoded valid selector"));
+
+static NOTICE_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".sanitize-notice").expect("hardcoded valid selector"));
+
+static WHITESPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s+").expect("hardcoded valid regex"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "lookup_macro",
+    title = "Lookup Macro Documentation",
+    description = "Get the documentation of a macro: its invocation syntax (macro_rules! arms, attribute or derive form), a short description and code examples. Accepts macros as written in code, e.g. json!, serde_json::json!, #[tokio::main] or #[derive(Serialize)], and finds them even when #[macro_export] moved them to the crate root.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `lookup_macro` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct LookupMacroTool {
+    /// Crate name (e.g., "tokio", "`serde_json`", "std")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate that defines the macro, e.g.: serde_json, tokio, std"
+    )]
+    pub crate_name: String,
+
+    /// Macro as written in code (e.g., `json!`, `#[tokio::main]`)
+    #[json_schema(
+        title = "Macro",
+        description = "Macro as written in code, e.g.: json!, serde_json::json!, vec!, #[tokio::main], #[derive(Serialize)]. A bare name (e.g. select) matches any macro kind"
+    )]
+    pub macro_name: String,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (syntax, summary and examples as fields)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+
+    /// Approximate token budget of the output (at least 100)
+    #[json_schema(
+        title = "Max Tokens",
+        description = "Optional size limit in tokens (about 4 characters each, at least 100). Long documentation is cut at the last section that fits; json output is never cut",
+        minimum = 100
+    )]
+    pub max_tokens: Option<u32>,
+
+    /// Character budget of the output, overriding `max_tokens`
+    #[json_schema(
+        title = "Max Length",
+        description = "Optional size limit in characters (at least 400); takes precedence over max_tokens",
+        minimum = 400
+    )]
+    pub max_length: Option<u32>,
+}
+
+/// Kind of macro, matching rustdoc's page prefixes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroKind {
+    /// `name!(...)`: `macro_rules!` or function-like proc macro (`macro.` pages)
+    FunctionLike,
+    /// `#[name]` (`attr.` pages)
+    Attribute,
+    /// `#[derive(Name)]` (`derive.` pages)
+    Derive,
+}
+
+impl MacroKind {
+    const ALL: [Self; 3] = [Self::FunctionLike, Self::Attribute, Self::Derive];
+
+    fn page_prefix(self) -> &'static str {
+        match self {
+            Self::FunctionLike => "macro",
+            Self::Attribute => "attr",
+            Self::Derive => "derive",
+        }
+    }
+
+    fn from_page_prefix(prefix: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.page_prefix() == prefix)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::FunctionLike => "Macro",
+            Self::Attribute => "Attribute macro",
+            Self::Derive => "Derive macro",
+        }
+    }
+}
+
+/// A macro reference parsed from the caller's spelling
+#[derive(Debug, PartialEq, Eq)]
+struct MacroSpec {
+    /// Module path below the crate root
+    modules: Vec<String>,
+    name: String,
+    /// Kind implied by the spelling (`!`, `#[...]`, `derive(...)`)
+    kind: Option<MacroKind>,
+}
+
+impl MacroSpec {
+    /// Parse `json!`, `serde_json::json!(...)`, `#[tokio::main(...)]`,
+    /// `#[derive(Serialize)]`, `derive(Serialize)` or a bare path
+    fn parse(input: &str, crate_name: &str) -> Result<Self, String> {
+        let mut text = input.trim();
+        let mut kind = None;
+        if let Some(inner) = text.strip_prefix("#[").and_then(|t| t.strip_suffix(']')) {
+            text = inner.trim();
+            kind = Some(MacroKind::Attribute);
+        }
+        if let Some(inner) = text
+            .strip_prefix("derive(")
+            .and_then(|t| t.strip_suffix(')'))
+        {
+            text = inner.trim();
+            kind = Some(MacroKind::Derive);
+        }
+        if let Some((path, _invocation)) = text.split_once('!') {
+            text = path.trim_end();
+            kind = Some(MacroKind::FunctionLike);
+        }
+        // Attribute arguments, e.g. `tokio::main(flavor = "current_thread")`
+        if let Some((path, _args)) = text.split_once('(') {
+            text = path.trim_end();
+        }
+
+        if text.is_empty()
+            || text.contains("..")
+            || !text
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b':')
+        {
+            return Err(format!(
+                "Invalid macro '{input}'. Expected a macro as written in code, e.g. json!, #[tokio::main] or #[derive(Serialize)]"
+            ));
+        }
+        let krate = crate_name.replace('-', "_");
+        let mut segments: Vec<String> = text.split("::").map(|s| s.trim().to_string()).collect();
+        if segments.iter().any(|s| s.is_empty() || s.contains(':')) {
+            return Err(format!(
+                "Invalid macro '{input}'. Path segments must be separated by '::'"
+            ));
+        }
+        if segments.len() > 1 && segments[0].replace('-', "_") == krate {
+            segments.remove(0);
+        }
+        let name = segments.pop().unwrap_or_default();
+        Ok(Self {
+            modules: segments,
+            name,
+            kind,
+        })
+    }
+
+    /// Normalized spelling, used as the cache key
+    fn canonical(&self) -> String {
+        let mut path = self.modules.join("::");
+        if !path.is_empty() {
+            path.push_str("::");
+        }
+        path.push_str(&self.name);
+        match self.kind {
+            Some(MacroKind::FunctionLike) => format!("{path}!"),
+            Some(MacroKind::Attribute) => format!("#[{path}]"),
+            Some(MacroKind::Derive) => format!("#[derive({path})]"),
+            None => path,
+        }
+    }
+
+    fn kinds(&self) -> Vec<MacroKind> {
+        self.kind
+            .map_or_else(|| MacroKind::ALL.to_vec(), |k| vec![k])
+    }
+
+    /// Candidate page URLs below a crate root URL (ending in `/`)
+    ///
+    /// The stated module comes first; declarative macros are also tried at
+    /// the crate root, where `#[macro_export]` puts them.
+    fn candidates(&self, root: &str) -> Vec<String> {
+        let mut dirs = vec![String::new()];
+        if !self.modules.is_empty() {
+            dirs.insert(0, format!("{}/", self.modules.join("/")));
+        }
+        let mut urls = Vec::new();
+        for dir in &dirs {
+            for kind in self.kinds() {
+                if dir.is_empty() && !self.modules.is_empty() && kind != MacroKind::FunctionLike {
+                    continue;
+                }
+                urls.push(format!(
+                    "{root}{dir}{}.{}.html",
+                    kind.page_prefix(),
+                    self.name
+                ));
+            }
+        }
+        urls
+    }
+
+    /// Href of a matching macro page in a crate's `all.html` index
+    fn find_in_all_html(&self, all_html: &str) -> Option<String> {
+        let prefixes: Vec<&str> = self
+            .kinds()
+            .into_iter()
+            .map(MacroKind::page_prefix)
+            .collect();
+        let pattern = format!(
+            "href=\"((?:[^\"]*/)?(?:{})\\.{}\\.html)\"",
+            prefixes.join("|"),
+            regex::escape(&self.name)
+        );
+        let re = Regex::new(&pattern).ok()?;
+        Some(re.captures(all_html)?.get(1)?.as_str().to_string())
+    }
+}
+
+/// Documentation extracted from a macro page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroDoc {
+    pub crate_name: String,
+    pub version: String,
+    /// Full path, e.g. `serde_json::json`
+    pub path: String,
+    pub kind: MacroKind,
+    pub url: String,
+    /// Declaration as rendered by rustdoc (`macro_rules!` arms, `#[main]`, ...)
+    pub syntax: Option<String>,
+    /// First paragraph of the documentation
+    pub summary: Option<String>,
+    pub examples: Vec<String>,
+    /// Set when the page was sanitized (see the `sanitize` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
+}
+
+fn element_text(element: scraper::ElementRef<'_>) -> String {
+    element.text().collect::<String>()
+}
+
+fn collapse(text: &str) -> String {
+    WHITESPACE_RE.replace_all(text.trim(), " ").into_owned()
+}
+
+/// Build a [`MacroDoc`] from a macro page
+///
+/// `url` is the page URL below the crate `root` URL; its file name
+/// (`macro.json.html`) gives the kind and its directories the module path.
+fn parse_macro_page(
+    html: &str,
+    root: &str,
+    url: &str,
+    crate_name: &str,
+    version: &str,
+) -> Option<MacroDoc> {
+    let relative = url.strip_prefix(root)?;
+    let (modules, file) = relative.rsplit_once('/').unwrap_or(("", relative));
+    let (prefix, rest) = file.split_once('.')?;
+    let kind = MacroKind::from_page_prefix(prefix)?;
+    let name = rest.strip_suffix(".html")?;
+
+    let document = Html::parse_document(html);
+    let syntax = document
+        .select(&DECL_SELECTOR)
+        .next()
+        .map(|decl| element_text(decl).trim().to_string())
+        .filter(|s| !s.is_empty());
+    let top_doc = document.select(&TOP_DOC_SELECTOR).next();
+    let summary = top_doc
+        .and_then(|doc| doc.select(&PARAGRAPH_SELECTOR).next())
+        .map(|p| collapse(&element_text(p)))
+        .filter(|s| !s.is_empty());
+    let examples = top_doc
+        .map(|doc| {
+            doc.select(&EXAMPLE_SELECTOR)
+                .map(|pre| element_text(pre).trim_end().to_string())
+                .filter(|code| !code.trim().is_empty())
+                .take(MAX_EXAMPLES)
+                .collect()
+        })
+        .unwrap_or_default();
+    let notice = document
+        .select(&NOTICE_SELECTOR)
+        .next()
+        .map(|n| collapse(&element_text(n)));
+
+    let mut path = crate_name.replace('-', "_");
+    for module in modules.split('/').filter(|m| !m.is_empty()) {
+        path.push_str("::");
+        path.push_str(module);
+    }
+    path.push_str("::");
+    path.push_str(name);
+
+    Some(MacroDoc {
+        crate_name: crate_name.to_string(),
+        version: version.to_string(),
+        path,
+        kind,
+        url: url.to_string(),
+        syntax,
+        summary,
+        examples,
+        notice,
+    })
+}
+
+fn format_doc(doc: &MacroDoc, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let invocation = match doc.kind {
+        MacroKind::FunctionLike => format!("{}!", doc.path),
+        MacroKind::Attribute => format!("#[{}]", doc.path),
+        MacroKind::Derive => format!("#[derive({})]", doc.path.rsplit("::").next().unwrap_or("")),
+    };
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# {} `{invocation}`\n", doc.kind.label()).unwrap();
+        writeln!(
+            output,
+            "{} {} · [docs]({})",
+            doc.crate_name, doc.version, doc.url
+        )
+        .unwrap();
+    } else {
+        writeln!(output, "{} {invocation}", doc.kind.label()).unwrap();
+        writeln!(output, "{} {} - {}", doc.crate_name, doc.version, doc.url).unwrap();
+    }
+    if let Some(notice) = &doc.notice {
+        writeln!(output, "\n{notice}").unwrap();
+    }
+    if let Some(summary) = &doc.summary {
+        writeln!(output, "\n{summary}").unwrap();
+    }
+    if let Some(syntax) = &doc.syntax {
+        if markdown {
+            writeln!(output, "\n## Syntax\n\n```rust\n{syntax}\n```").unwrap();
+        } else {
+            writeln!(output, "\nSyntax:\n{syntax}").unwrap();
+        }
+    }
+    if !doc.examples.is_empty() {
+        if markdown {
+            writeln!(output, "\n## Examples").unwrap();
+        } else {
+            writeln!(output, "\nExamples:").unwrap();
+        }
+        for example in &doc.examples {
+            if markdown {
+                writeln!(output, "\n```rust\n{example}\n```").unwrap();
+            } else {
+                writeln!(output, "\n{example}").unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the lookup macro tool
+pub struct LookupMacroToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl LookupMacroToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Find the macro page with one provider: direct candidates first, then
+    /// the crate's `all.html` index
+    async fn resolve_with(
+        &self,
+        provider: &dyn DocsProvider,
+        crate_name: &str,
+        version: Option<&str>,
+        spec: &MacroSpec,
+    ) -> Result<Option<(String, String)>, CallToolError> {
+        for url in spec.candidates(&provider.root_url(crate_name, version)) {
+            if let Some(html) = self
+                .service
+                .fetch_html_optional(&url, Some(TOOL_NAME))
+                .await?
+            {
+                return Ok(Some((url, html)));
+            }
+        }
+
+        let all_url = provider.all_items_url(crate_name, version);
+        let all_html = self
+            .service
+            .fetch_html_optional(&all_url, Some(TOOL_NAME))
+            .await?;
+        let Some(href) = all_html.and_then(|html| spec.find_in_all_html(&html)) else {
+            return Ok(None);
+        };
+        let url = provider.item_url_from_href(crate_name, version, &href);
+        let html = self
+            .service
+            .fetch_html_optional(&url, Some(TOOL_NAME))
+            .await?;
+        Ok(html.map(|html| (url, html)))
+    }
+
+    async fn resolve(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        spec: &MacroSpec,
+    ) -> Result<MacroDoc, CallToolError> {
+        let mut last_error: Option<String> = None;
+        for provider in self.service.providers_for(crate_name) {
+            match self
+                .resolve_with(provider.as_ref(), crate_name, version, spec)
+                .await
+            {
+                Ok(Some((url, html))) => {
+                    let html = self.service.sanitize_page(html, TOOL_NAME);
+                    return parse_macro_page(
+                        &html,
+                        &provider.root_url(crate_name, version),
+                        &url,
+                        crate_name,
+                        version.unwrap_or("latest"),
+                    )
+                    .ok_or_else(|| {
+                        CallToolError::from_message(format!(
+                            "[{TOOL_NAME}] Unexpected macro page URL: {url}"
+                        ))
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        provider = provider.name(),
+                        "[{TOOL_NAME}] provider failed, trying next: {e}"
+                    );
+                    last_error = Some(e.to_string());
+                }
+            }
+        }
+        Err(last_error.map_or_else(
+            || {
+                crate::error::Error::not_found(
+                    "macro",
+                    format!("{crate_name}: {}", spec.canonical()),
+                    Vec::new(),
+                )
+                .into()
+            },
+            CallToolError::from_message,
+        ))
+    }
+
+    async fn fetch_doc(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        spec: &MacroSpec,
+    ) -> Result<MacroDoc, CallToolError> {
+        let key = spec.canonical();
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache.get_macro_docs(crate_name, version, &key).await {
+            if let Ok(doc) = serde_json::from_str(&cached) {
+                return Ok(doc);
+            }
+        }
+
+        let doc = self.resolve(crate_name, version, spec).await?;
+        if let Ok(serialized) = serde_json::to_string(&doc) {
+            if let Err(e) = doc_cache
+                .set_macro_docs(crate_name, version, &key, serialized)
+                .await
+            {
+                tracing::warn!("[{TOOL_NAME}] failed to cache macro docs: {e}");
+            }
+        }
+        Ok(doc)
+    }
+}
+
+#[async_trait]
+impl Tool for LookupMacroToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        LookupMacroTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: LookupMacroTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let budget = super::budget::char_budget(params.max_tokens, params.max_length);
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let spec = MacroSpec::parse(&params.macro_name, crate_name)
+            .map_err(|e| CallToolError::invalid_arguments(TOOL_NAME, Some(e)))?;
+
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let doc = self
+            .fetch_doc(crate_name, version.as_deref(), &spec)
+            .await?;
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&doc).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => super::budget::apply(format_doc(&doc, false), budget, format),
+            _ => super::budget::apply(format_doc(&doc, true), budget, format),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for LookupMacroToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_macro_spec() {
+        let spec = MacroSpec::parse("serde_json::json!", "serde_json").unwrap();
+        assert_eq!(spec.modules, Vec::<String>::new());
+        assert_eq!(spec.name, "json");
+        assert_eq!(spec.kind, Some(MacroKind::FunctionLike));
+        assert_eq!(spec.canonical(), "json!");
+
+        let spec =
+            MacroSpec::parse("#[tokio::main(flavor = \"current_thread\")]", "tokio").unwrap();
+        assert_eq!(spec.name, "main");
+        assert_eq!(spec.kind, Some(MacroKind::Attribute));
+
+        let spec = MacroSpec::parse("#[derive(Serialize)]", "serde").unwrap();
+        assert_eq!(spec.canonical(), "#[derive(Serialize)]");
+
+        let spec = MacroSpec::parse("vec![1, 2]", "alloc").unwrap();
+        assert_eq!(spec.name, "vec");
+
+        let spec = MacroSpec::parse("ext::select", "demo").unwrap();
+        assert_eq!(spec.kind, None);
+        assert_eq!(spec.modules, vec!["ext"]);
+
+        assert!(MacroSpec::parse("#[]", "demo").is_err());
+        assert!(MacroSpec::parse("a::::b!", "demo").is_err());
+        assert!(MacroSpec::parse("../etc!", "demo").is_err());
+    }
+
+    #[test]
+    fn test_macro_candidates() {
+        let spec = MacroSpec::parse("ext::select!", "demo").unwrap();
+        assert_eq!(
+            spec.candidates("https://docs.rs/demo/latest/demo/"),
+            vec![
+                "https://docs.rs/demo/latest/demo/ext/macro.select.html",
+                "https://docs.rs/demo/latest/demo/macro.select.html",
+            ]
+        );
+        let spec = MacroSpec::parse("Serialize", "serde").unwrap();
+        assert_eq!(spec.candidates("r/").len(), 3);
+
+        let all =
+            r#"<a href="ext/macro.select.html">ext::select</a><a href="fn.select.html">select</a>"#;
+        let spec = MacroSpec::parse("select", "demo").unwrap();
+        assert_eq!(
+            spec.find_in_all_html(all).as_deref(),
+            Some("ext/macro.select.html")
+        );
+    }
+
+    #[test]
+    fn test_parse_macro_page() {
+        let html = concat!(
+            "<html><body><section id=\"main-content\">",
+            "<h1>Macro <a>demo</a>::<a>ext</a>::<span class=\"macro\">pick</span></h1>",
+            "<pre class=\"rust item-decl\"><code>macro_rules! pick {\n    ($x:expr) =&gt; { ... };\n}</code></pre>",
+            "<details class=\"toggle top-doc\" open><summary>Expand description</summary>",
+            "<div class=\"docblock\"><p>Picks   one\nvalue.</p><h2>Examples</h2>",
+            "<div class=\"example-wrap\"><pre class=\"rust rust-example-rendered\"><code>let x = pick!(1);</code></pre></div>",
+            "</div></details></section></body></html>"
+        );
+        let doc = parse_macro_page(
+            html,
+            "https://docs.rs/demo/1.0.0/demo/",
+            "https://docs.rs/demo/1.0.0/demo/ext/macro.pick.html",
+            "demo",
+            "1.0.0",
+        )
+        .unwrap();
+        assert_eq!(doc.path, "demo::ext::pick");
+        assert_eq!(doc.kind, MacroKind::FunctionLike);
+        assert_eq!(
+            doc.syntax.as_deref(),
+            Some("macro_rules! pick {\n    ($x:expr) => { ... };\n}")
+        );
+        assert_eq!(doc.summary.as_deref(), Some("Picks one value."));
+        assert_eq!(doc.examples, vec!["let x = pick!(1);"]);
+        assert!(doc.notice.is_none());
+    }
+}
@@ -0,0 +1,902 @@
+//! Cargo.toml analysis tool
+//!
+//! Audits the dependencies declared in a `Cargo.toml` in one call. For every
+//! crates.io dependency it reports the latest version, whether the pinned
+//! requirement still admits it, the security advisories affecting the lowest
+//! version the requirement allows, and the docs.rs link. Advisories come from
+//! the OSV database, which mirrors the Rust security advisory database.
+//!
+//! Path, git and alternative registry dependencies are listed but not
+//! checked. Dependencies inherited with `workspace = true` are resolved
+//! against `[workspace.dependencies]` when the manifest declares it.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+const TOOL_NAME: &str = "analyze_cargo_toml";
+
+/// OSV vulnerability query endpoint
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// Maximum accepted `Cargo.toml` size
+const MAX_MANIFEST_BYTES: usize = 1024 * 1024;
+
+/// Maximum number of distinct crates.io dependencies checked in one call
+const MAX_CHECKED_DEPENDENCIES: usize = 200;
+
+/// Dependencies checked at the same time
+const MAX_CONCURRENT_CHECKS: usize = 5;
+
+/// Dependency tables of a manifest (or of a `[target.'cfg(..)']` table)
+const DEPENDENCY_TABLES: [(&str, DependencyKind); 5] = [
+    ("dependencies", DependencyKind::Normal),
+    ("dev-dependencies", DependencyKind::Dev),
+    ("dev_dependencies", DependencyKind::Dev),
+    ("build-dependencies", DependencyKind::Build),
+    ("build_dependencies", DependencyKind::Build),
+];
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "analyze_cargo_toml",
+    title = "Analyze Cargo.toml",
+    description = "Audit the dependencies of a Cargo.toml in one call. For each crates.io dependency, returns the latest version, whether the version requirement is outdated, known security advisories (RustSec, via OSV) affecting the versions it allows, and the docs.rs link. Pass the file contents, not a path.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    execution(task_support = "optional"),
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `analyze_cargo_toml` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct AnalyzeCargoTomlTool {
+    /// Contents of the `Cargo.toml` to analyze
+    #[json_schema(
+        title = "Cargo.toml",
+        description = "Contents of the Cargo.toml file to audit (a package or workspace manifest)"
+    )]
+    pub cargo_toml: String,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (one report per dependency)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Dependency table a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl DependencyKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Dev => "dev",
+            Self::Build => "build",
+        }
+    }
+}
+
+/// Where a dependency is taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencySource {
+    CratesIo,
+    Path,
+    Git,
+    /// A registry other than crates.io
+    Registry,
+    /// Inherited from a workspace manifest that was not provided
+    Workspace,
+}
+
+/// A dependency declared in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestDependency {
+    /// Crate name (the `package` of a renamed dependency)
+    pub name: String,
+    /// Name the manifest uses for it, when renamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    pub kind: DependencyKind,
+    /// Platform `cfg` the dependency is limited to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Version requirement, if one is given
+    pub requirement: Option<String>,
+    pub source: DependencySource,
+}
+
+/// A dependency table entry, before workspace inheritance is resolved
+enum DependencySpec {
+    Declared(ManifestDependency),
+    Inherited,
+}
+
+/// Parse one dependency table entry
+fn parse_spec(
+    key: &str,
+    spec: &toml::Value,
+    kind: DependencyKind,
+    target: Option<&str>,
+) -> Option<DependencySpec> {
+    let dependency = |name: Option<&str>, requirement: Option<&str>, source| {
+        DependencySpec::Declared(ManifestDependency {
+            name: name.unwrap_or(key).to_string(),
+            rename: name.map(|_| key.to_string()),
+            kind,
+            target: target.map(str::to_string),
+            requirement: requirement.map(|r| r.trim().to_string()),
+            source,
+        })
+    };
+    match spec {
+        toml::Value::String(requirement) => Some(dependency(
+            None,
+            Some(requirement),
+            DependencySource::CratesIo,
+        )),
+        toml::Value::Table(table) => {
+            if table.get("workspace").and_then(toml::Value::as_bool) == Some(true) {
+                return Some(DependencySpec::Inherited);
+            }
+            let is_other_registry = table.contains_key("registry-index")
+                || table
+                    .get("registry")
+                    .and_then(toml::Value::as_str)
+                    .is_some_and(|registry| registry != super::registry::registry_name());
+            let source = if table.contains_key("path") {
+                DependencySource::Path
+            } else if table.contains_key("git") {
+                DependencySource::Git
+            } else if is_other_registry {
+                DependencySource::Registry
+            } else {
+                DependencySource::CratesIo
+            };
+            Some(dependency(
+                table.get("package").and_then(toml::Value::as_str),
+                table.get("version").and_then(toml::Value::as_str),
+                source,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Append the dependencies of the dependency tables of `table`
+fn collect_dependencies(
+    table: &toml::Table,
+    target: Option<&str>,
+    workspace: Option<&toml::Table>,
+    inherited: &mut HashSet<String>,
+    dependencies: &mut Vec<ManifestDependency>,
+) {
+    for (table_name, kind) in DEPENDENCY_TABLES {
+        let Some(entries) = table.get(table_name).and_then(toml::Value::as_table) else {
+            continue;
+        };
+        for (key, spec) in entries {
+            match parse_spec(key, spec, kind, target) {
+                Some(DependencySpec::Declared(dependency)) => dependencies.push(dependency),
+                Some(DependencySpec::Inherited) => {
+                    let declared = workspace
+                        .and_then(|workspace| workspace.get(key))
+                        .and_then(|spec| parse_spec(key, spec, kind, target));
+                    if let Some(DependencySpec::Declared(dependency)) = declared {
+                        inherited.insert(key.clone());
+                        dependencies.push(dependency);
+                    } else {
+                        dependencies.push(ManifestDependency {
+                            name: key.clone(),
+                            rename: None,
+                            kind,
+                            target: target.map(str::to_string),
+                            requirement: None,
+                            source: DependencySource::Workspace,
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Extract the package name and dependencies of a `Cargo.toml`
+///
+/// Target-specific tables are included. `[workspace.dependencies]` entries
+/// not inherited by the manifest's own package are reported as normal
+/// dependencies, so a virtual workspace manifest is audited too.
+///
+/// # Errors
+///
+/// Returns a message if the manifest is not valid TOML
+pub fn parse_manifest(content: &str) -> Result<(Option<String>, Vec<ManifestDependency>), String> {
+    let manifest: toml::Table =
+        toml::from_str(content).map_err(|e| format!("Invalid Cargo.toml: {e}"))?;
+    let package = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(toml::Value::as_str)
+        .map(str::to_string);
+    let workspace = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(toml::Value::as_table);
+
+    let mut inherited = HashSet::new();
+    let mut dependencies = Vec::new();
+    collect_dependencies(
+        &manifest,
+        None,
+        workspace,
+        &mut inherited,
+        &mut dependencies,
+    );
+    if let Some(targets) = manifest.get("target").and_then(toml::Value::as_table) {
+        for (cfg, table) in targets {
+            if let Some(table) = table.as_table() {
+                collect_dependencies(
+                    table,
+                    Some(cfg),
+                    workspace,
+                    &mut inherited,
+                    &mut dependencies,
+                );
+            }
+        }
+    }
+    for (key, spec) in workspace.into_iter().flatten() {
+        if inherited.contains(key) {
+            continue;
+        }
+        if let Some(DependencySpec::Declared(dependency)) =
+            parse_spec(key, spec, DependencyKind::Normal, None)
+        {
+            dependencies.push(dependency);
+        }
+    }
+    Ok((package, dependencies))
+}
+
+/// Lowest version a requirement allows, `None` if it has no lower bound
+///
+/// `>x` comparators are treated as `>=x`, which errs towards reporting an
+/// advisory rather than missing one.
+fn minimum_version(req: &VersionReq) -> Option<Version> {
+    use semver::Op;
+
+    req.comparators
+        .iter()
+        .filter(|comparator| {
+            matches!(
+                comparator.op,
+                Op::Exact | Op::Greater | Op::GreaterEq | Op::Tilde | Op::Caret | Op::Wildcard
+            )
+        })
+        .map(|comparator| {
+            let mut version = Version::new(
+                comparator.major,
+                comparator.minor.unwrap_or(0),
+                comparator.patch.unwrap_or(0),
+            );
+            version.pre = comparator.pre.clone();
+            version
+        })
+        .max()
+}
+
+/// A security advisory affecting a dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Advisory id, e.g. `RUSTSEC-2020-0071`
+    pub id: String,
+    pub summary: Option<String>,
+    /// Other ids of the advisory (CVE, GHSA)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Versions the issue is fixed in
+    #[serde(default)]
+    pub fixed: Vec<String>,
+    pub url: String,
+}
+
+#[derive(Serialize)]
+struct OsvQuery<'a> {
+    package: OsvPackage<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct OsvPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvResponse {
+    #[serde(default)]
+    vulns: Vec<OsvVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVulnerability {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvAffected {
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRange {
+    #[serde(default)]
+    events: Vec<HashMap<String, String>>,
+}
+
+impl From<OsvVulnerability> for Advisory {
+    fn from(vulnerability: OsvVulnerability) -> Self {
+        let mut fixed: Vec<String> = vulnerability
+            .affected
+            .iter()
+            .flat_map(|affected| &affected.ranges)
+            .flat_map(|range| &range.events)
+            .filter_map(|event| event.get("fixed").cloned())
+            .collect();
+        fixed.dedup();
+        Self {
+            url: format!("https://osv.dev/vulnerability/{}", vulnerability.id),
+            id: vulnerability.id,
+            summary: vulnerability.summary,
+            aliases: vulnerability.aliases,
+            fixed,
+        }
+    }
+}
+
+/// Fetch the advisories affecting `version` of a crate (any version if `None`)
+async fn fetch_advisories(
+    service: &super::DocService,
+    crate_name: &str,
+    version: Option<&str>,
+) -> Result<Vec<Advisory>, CallToolError> {
+    let doc_cache = service.doc_cache();
+    if let Some(cached) = doc_cache.get_advisories(crate_name, version).await {
+        return serde_json::from_str(&cached).map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] Cache parsing failed: {e}"))
+        });
+    }
+
+    let query = OsvQuery {
+        package: OsvPackage {
+            name: crate_name,
+            ecosystem: "crates.io",
+        },
+        version,
+    };
+    let body = serde_json::to_string(&query).map_err(|e| {
+        CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+    })?;
+    let response = service
+        .client()
+        .post(OSV_QUERY_URL)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] HTTP request failed: {e}"))
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(CallToolError::from_message(format!(
+            "[{TOOL_NAME}] Advisory lookup failed: {}",
+            super::summarize_http_status(OSV_QUERY_URL, status, &body)
+        )));
+    }
+    let response: OsvResponse = response.json().await.map_err(|e| {
+        CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+    })?;
+    let advisories: Vec<Advisory> = response
+        .vulns
+        .into_iter()
+        .filter(|vulnerability| vulnerability.withdrawn.is_none())
+        .map(Advisory::from)
+        .collect();
+
+    match serde_json::to_string(&advisories) {
+        Ok(value) => {
+            if let Err(e) = doc_cache.set_advisories(crate_name, version, value).await {
+                tracing::warn!(
+                    "[{TOOL_NAME}] failed to cache advisories (continuing uncached): {e}"
+                );
+            }
+        }
+        Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize advisories: {e}"),
+    }
+    Ok(advisories)
+}
+
+/// Audit result of one dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyReport {
+    #[serde(flatten)]
+    pub dependency: ManifestDependency,
+    /// Latest stable version on crates.io
+    pub latest: Option<String>,
+    /// Whether the requirement does not admit the latest version
+    pub outdated: Option<bool>,
+    /// Advisories affecting the lowest version the requirement allows
+    #[serde(default)]
+    pub advisories: Vec<Advisory>,
+    pub docs_rs: Option<String>,
+    /// Why the dependency could not be checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Audit result of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestAnalysis {
+    /// Package name, absent for virtual workspace manifests
+    pub package: Option<String>,
+    pub dependencies: Vec<DependencyReport>,
+}
+
+/// Result of checking one crate against one requirement
+#[derive(Debug, Default, Clone)]
+struct Check {
+    latest: Option<String>,
+    outdated: Option<bool>,
+    advisories: Vec<Advisory>,
+    docs_rs: Option<String>,
+    error: Option<String>,
+}
+
+/// Check a crates.io dependency; errors are reported in the result
+async fn check_dependency(
+    service: &super::DocService,
+    crate_name: &str,
+    requirement: Option<&str>,
+) -> Check {
+    let mut check = Check::default();
+    let req = match requirement.map(VersionReq::parse).transpose() {
+        Ok(req) => req,
+        Err(e) => {
+            check.error = Some(format!("Invalid version requirement: {e}"));
+            return check;
+        }
+    };
+
+    match super::batch::fetch_crate_summary(service, crate_name, TOOL_NAME).await {
+        Ok(Some(summary)) => {
+            check.outdated = req.as_ref().and_then(|req| {
+                Version::parse(&summary.version)
+                    .ok()
+                    .map(|latest| !req.matches(&latest))
+            });
+            check.latest = Some(summary.version);
+            check.docs_rs = Some(summary.docs_rs);
+        }
+        Ok(None) => {
+            check.error = Some("not found on crates.io".to_string());
+            return check;
+        }
+        Err(e) => {
+            check.error = Some(e.to_string());
+            return check;
+        }
+    }
+
+    let minimum = req
+        .as_ref()
+        .and_then(minimum_version)
+        .map(|v| v.to_string());
+    match fetch_advisories(service, crate_name, minimum.as_deref()).await {
+        Ok(advisories) => check.advisories = advisories,
+        Err(e) => check.error = Some(e.to_string()),
+    }
+    check
+}
+
+fn dependency_status(report: &DependencyReport) -> String {
+    if let Some(error) = &report.error {
+        return format!("error: {error}");
+    }
+    match report.dependency.source {
+        DependencySource::Path => return "path dependency, not checked".to_string(),
+        DependencySource::Git => return "git dependency, not checked".to_string(),
+        DependencySource::Registry => return "other registry, not checked".to_string(),
+        DependencySource::Workspace => {
+            return "inherited from workspace, not checked".to_string();
+        }
+        DependencySource::CratesIo => {}
+    }
+    let mut status = match report.outdated {
+        Some(true) => "outdated".to_string(),
+        Some(false) => "up to date".to_string(),
+        None => "no requirement".to_string(),
+    };
+    if !report.advisories.is_empty() {
+        // SAFETY: write! to String never fails
+        use std::fmt::Write;
+        write!(status, ", {} advisories", report.advisories.len()).unwrap();
+    }
+    status
+}
+
+fn format_analysis(analysis: &ManifestAnalysis, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let reports = &analysis.dependencies;
+    let outdated = reports.iter().filter(|r| r.outdated == Some(true)).count();
+    let vulnerable = reports.iter().filter(|r| !r.advisories.is_empty()).count();
+    let name = analysis.package.as_deref().unwrap_or("workspace");
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Cargo.toml analysis: {name}\n").unwrap();
+    } else {
+        writeln!(output, "Cargo.toml analysis: {name}\n").unwrap();
+    }
+    writeln!(
+        output,
+        "{} dependencies, {outdated} outdated, {vulnerable} with advisories\n",
+        reports.len()
+    )
+    .unwrap();
+
+    if markdown && !reports.is_empty() {
+        writeln!(
+            output,
+            "| Crate | Kind | Requirement | Latest | Status | Docs |"
+        )
+        .unwrap();
+        writeln!(output, "|---|---|---|---|---|---|").unwrap();
+    }
+    for report in reports {
+        let dependency = &report.dependency;
+        let mut kind = dependency.kind.as_str().to_string();
+        if let Some(target) = &dependency.target {
+            write!(kind, " ({target})").unwrap();
+        }
+        let requirement = dependency.requirement.as_deref().unwrap_or("-");
+        let latest = report.latest.as_deref().unwrap_or("-");
+        let status = dependency_status(report);
+        if markdown {
+            let docs = report
+                .docs_rs
+                .as_deref()
+                .map_or_else(|| "-".to_string(), |url| format!("<{url}>"));
+            writeln!(
+                output,
+                "| {} | {} | {} | {latest} | {} | {docs} |",
+                dependency.name,
+                kind.replace('|', "\\|"),
+                requirement.replace('|', "\\|"),
+                status.replace('|', "\\|")
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                output,
+                "- {} [{kind}] {requirement} (latest {latest}): {status}",
+                dependency.name
+            )
+            .unwrap();
+        }
+    }
+
+    if vulnerable > 0 {
+        if markdown {
+            writeln!(output, "\n## Advisories\n").unwrap();
+        } else {
+            writeln!(output, "\nAdvisories:").unwrap();
+        }
+        let mut listed = HashSet::new();
+        for report in reports {
+            for advisory in &report.advisories {
+                if !listed.insert((&report.dependency.name, &advisory.id)) {
+                    continue;
+                }
+                let mut line = format!("{} {}", report.dependency.name, advisory.id);
+                if let Some(summary) = &advisory.summary {
+                    write!(line, ": {summary}").unwrap();
+                }
+                if !advisory.fixed.is_empty() {
+                    write!(line, " (fixed in {})", advisory.fixed.join(", ")).unwrap();
+                }
+                if markdown {
+                    writeln!(output, "- {line} - <{}>", advisory.url).unwrap();
+                } else {
+                    writeln!(output, "- {line} - {}", advisory.url).unwrap();
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the Cargo.toml analysis tool
+pub struct AnalyzeCargoTomlToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl AnalyzeCargoTomlToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Check each distinct crate/requirement pair a few at a time
+    async fn check_all(&self, dependencies: Vec<ManifestDependency>) -> Vec<DependencyReport> {
+        type CheckKey = (String, Option<String>);
+        let mut keys: Vec<CheckKey> = Vec::new();
+        for dependency in &dependencies {
+            let key = (dependency.name.clone(), dependency.requirement.clone());
+            if dependency.source == DependencySource::CratesIo && !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut checks: HashMap<CheckKey, Check> = HashMap::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_CONCURRENT_CHECKS) {
+            let mut tasks = JoinSet::new();
+            for key in chunk {
+                let service = Arc::clone(&self.service);
+                let key = key.clone();
+                tasks.spawn(async move {
+                    let check = check_dependency(&service, &key.0, key.1.as_deref()).await;
+                    (key, check)
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                if let Ok((key, check)) = joined {
+                    checks.insert(key, check);
+                }
+            }
+        }
+
+        dependencies
+            .into_iter()
+            .map(|dependency| {
+                let key = (dependency.name.clone(), dependency.requirement.clone());
+                let check = if dependency.source == DependencySource::CratesIo {
+                    checks.get(&key).cloned().unwrap_or_else(|| Check {
+                        error: Some("check did not complete".to_string()),
+                        ..Check::default()
+                    })
+                } else {
+                    Check::default()
+                };
+                DependencyReport {
+                    dependency,
+                    latest: check.latest,
+                    outdated: check.outdated,
+                    advisories: check.advisories,
+                    docs_rs: check.docs_rs,
+                    error: check.error,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Tool for AnalyzeCargoTomlToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        AnalyzeCargoTomlTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: AnalyzeCargoTomlTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let invalid = |message: String| CallToolError::invalid_arguments(TOOL_NAME, Some(message));
+
+        if params.cargo_toml.len() > MAX_MANIFEST_BYTES {
+            return Err(invalid(format!(
+                "Cargo.toml is too large (max {MAX_MANIFEST_BYTES} bytes)"
+            )));
+        }
+        let (package, dependencies) = parse_manifest(&params.cargo_toml).map_err(invalid)?;
+        let checked: HashSet<&str> = dependencies
+            .iter()
+            .filter(|dependency| dependency.source == DependencySource::CratesIo)
+            .map(|dependency| dependency.name.as_str())
+            .collect();
+        if checked.len() > MAX_CHECKED_DEPENDENCIES {
+            return Err(invalid(format!(
+                "Too many dependencies (max {MAX_CHECKED_DEPENDENCIES} crates.io dependencies)"
+            )));
+        }
+        for dependency in &dependencies {
+            if dependency.source == DependencySource::CratesIo {
+                super::validate_crate_name(TOOL_NAME, &dependency.name)?;
+            }
+        }
+
+        let analysis = ManifestAnalysis {
+            package,
+            dependencies: self.check_all(dependencies).await,
+        };
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&analysis).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_analysis(&analysis, false),
+            _ => format_analysis(&analysis, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for AnalyzeCargoTomlToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest_collects_all_tables() {
+        let manifest = r#"
+            [package]
+            name = "demo"
+
+            [dependencies]
+            serde = "1.0"
+            json = { package = "serde_json", version = "1" }
+            local = { path = "../local" }
+            tokio = { workspace = true }
+
+            [dev-dependencies]
+            wiremock = { version = "0.6", registry = "crates-io" }
+
+            [target.'cfg(unix)'.dependencies]
+            libc = "0.2"
+
+            [workspace.dependencies]
+            tokio = "1.40"
+            anyhow = "1"
+        "#;
+        let (package, dependencies) = parse_manifest(manifest).unwrap();
+        assert_eq!(package.as_deref(), Some("demo"));
+
+        let summary: Vec<_> = dependencies
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.kind,
+                    d.requirement.as_deref(),
+                    d.source,
+                    d.target.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "serde_json",
+                    DependencyKind::Normal,
+                    Some("1"),
+                    DependencySource::CratesIo,
+                    None
+                ),
+                (
+                    "local",
+                    DependencyKind::Normal,
+                    None,
+                    DependencySource::Path,
+                    None
+                ),
+                (
+                    "serde",
+                    DependencyKind::Normal,
+                    Some("1.0"),
+                    DependencySource::CratesIo,
+                    None
+                ),
+                (
+                    "tokio",
+                    DependencyKind::Normal,
+                    Some("1.40"),
+                    DependencySource::CratesIo,
+                    None
+                ),
+                (
+                    "wiremock",
+                    DependencyKind::Dev,
+                    Some("0.6"),
+                    DependencySource::CratesIo,
+                    None
+                ),
+                (
+                    "libc",
+                    DependencyKind::Normal,
+                    Some("0.2"),
+                    DependencySource::CratesIo,
+                    Some("cfg(unix)")
+                ),
+                (
+                    "anyhow",
+                    DependencyKind::Normal,
+                    Some("1"),
+                    DependencySource::CratesIo,
+                    None
+                ),
+            ]
+        );
+        assert_eq!(dependencies[0].rename.as_deref(), Some("json"));
+        assert!(parse_manifest("[dependencies").is_err());
+    }
+
+    #[test]
+    fn test_parse_manifest_without_workspace_table() {
+        let (_, dependencies) =
+            parse_manifest("[dependencies]\ntokio = { workspace = true }\n").unwrap();
+        assert_eq!(dependencies[0].source, DependencySource::Workspace);
+        assert_eq!(dependencies[0].requirement, None);
+    }
+
+    #[test]
+    fn test_minimum_version() {
+        let minimum = |req: &str| minimum_version(&VersionReq::parse(req).unwrap());
+        assert_eq!(minimum("1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(minimum(">=0.4.3, <0.5"), Some(Version::new(0, 4, 3)));
+        assert_eq!(minimum("=2.0.1"), Some(Version::new(2, 0, 1)));
+        assert_eq!(minimum("*"), None);
+        assert_eq!(minimum("<2"), None);
+    }
+}
@@ -4,11 +4,46 @@
 //!
 //! # Submodules
 //!
+//! - `alternatives`: Alternative crates sharing keywords and categories
+//! - `batch`: Crate summaries for many crates at once
+//! - `build_info`: Build statistics from docs.rs and crates.io
+//! - `budget`: Output length budgets
+//! - `build_status`: docs.rs build status
 //! - `cache`: Document cache
+//! - `category`: crates.io category listings
+//! - `clippy_lint`: Clippy lint index lookup
+//! - `coverage`: Documentation coverage from docs.rs
+//! - `crawl`: Module page crawler
+//! - `deprecated`: Deprecated item listing
+//! - `examples`: Code examples of a documentation page
+//! - `dependency`: `cargo add` / `Cargo.toml` suggestions
+//! - `dependency_tree`: Transitive dependency trees from the sparse index
+//! - `features`: Feature-gated item listing
+//! - `filter`: Content filters for rustdoc pages
 //! - `html`: HTML processing
+//! - `llm`: Compact `llm` output format
+//! - `lockfile`: `Cargo.lock` audits
 //! - `lookup_crate`: Crate documentation lookup
 //! - `lookup_item`: Item documentation lookup
+//! - `lookup_macro`: Macro documentation lookup
+//! - `manifest`: `Cargo.toml` dependency audits
+//! - `negotiate`: Per-session default output format
+//! - `playground`: Rust Playground execution
+//! - `profile`: Per-session project version pins
+//! - `provider`: Upstream documentation sources
+//! - `readme`: Repository README fallback for crates without docs
+//! - `registry`: Package registry endpoints (crates.io or an alternative)
+//! - `resolve_version`: Semver requirement resolution
+//! - `rustc_error`: Rustc error index lookup
+//! - `sanitize`: Prompt-injection hardening for fetched pages
 //! - `search`: Crate search
+//! - `search_backend`: crates.io and lib.rs search backends
+//! - `summarize`: Budgeted crate summaries
+//! - `symbol`: Cross-crate symbol search
+//! - `toc`: Page outlines and section extraction
+//! - `top_queries`: Most looked up crates and items
+//! - `trending`: New, updated and fast-growing crates
+//! - `upstream`: Upstream service credentials
 //!
 //! # Examples
 //!
@@ -21,11 +56,47 @@
 //! let service = DocService::new(cache).expect("Failed to create DocService");
 //! ```
 
+pub mod alternatives;
+pub mod batch;
+pub mod budget;
+pub mod build_info;
+pub mod build_status;
 pub mod cache;
+pub mod category;
+pub mod clippy_lint;
+pub mod coverage;
+mod crawl;
+pub mod dependency;
+pub mod dependency_tree;
+pub mod deprecated;
+pub mod examples;
+pub mod features;
+pub mod filter;
 pub mod html;
+pub mod llm;
+pub mod lockfile;
 pub mod lookup_crate;
 pub mod lookup_item;
+pub mod lookup_macro;
+pub mod manifest;
+pub mod negotiate;
+pub mod playground;
+pub mod profile;
+pub mod provider;
+mod readme;
+pub mod registry;
+pub mod resolve_version;
+pub mod rustc_error;
+pub mod rustdoc_json;
+pub mod sanitize;
 pub mod search;
+pub mod search_backend;
+pub mod summarize;
+pub mod symbol;
+pub mod toc;
+pub mod top_queries;
+pub mod trending;
+pub mod upstream;
 
 use crate::cache::{Cache, CacheConfig};
 use crate::config::PerformanceConfig;
@@ -44,6 +115,8 @@ pub enum Format {
     Html,
     /// JSON format (used by search tool)
     Json,
+    /// Compact markdown for language models (see [`llm`])
+    Llm,
 }
 
 impl std::fmt::Display for Format {
@@ -53,42 +126,46 @@ impl std::fmt::Display for Format {
             Self::Text => write!(f, "text"),
             Self::Html => write!(f, "html"),
             Self::Json => write!(f, "json"),
+            Self::Llm => write!(f, "llm"),
         }
     }
 }
 
 /// Formats supported by the documentation lookup tools (`lookup_crate`,
-/// `lookup_item`). JSON is intentionally excluded: these tools render prose
-/// documentation, not structured data.
-pub const DOC_FORMATS: &[Format] = &[Format::Markdown, Format::Text, Format::Html];
+/// `lookup_item`, `lookup_rustc_error`). JSON is intentionally excluded: these
+/// tools render prose documentation, not structured data.
+pub const DOC_FORMATS: &[Format] = &[Format::Markdown, Format::Text, Format::Html, Format::Llm];
 
 /// Formats supported by the `search_crates` tool. HTML is intentionally
 /// excluded: search results are structured records, not an HTML document.
 pub const SEARCH_FORMATS: &[Format] = &[Format::Markdown, Format::Text, Format::Json];
 
+/// Formats supported by tools that return a structured report about a crate
+/// (e.g. `get_docsrs_build_status`): human-readable summaries or raw JSON.
+pub const REPORT_FORMATS: &[Format] = &[Format::Markdown, Format::Text, Format::Json];
+
 /// Parse and validate a format string against the formats a tool supports.
 ///
 /// `allowed` lists the formats the calling tool actually accepts. Both an
 /// unrecognized string and a recognized format outside `allowed` produce an
 /// error that lists only the supported formats, so a caller is never advised to
-/// retry with a format the tool will then reject. `None` defaults to markdown,
-/// which every tool supports.
+/// retry with a format the tool will then reject. `None` defaults to the
+/// format negotiated for the session (see [`negotiate`]) when the tool supports
+/// it, and to markdown, which every tool supports, otherwise.
 pub fn parse_format(
     tool_name: &str,
     format_str: Option<&str>,
     allowed: &[Format],
 ) -> Result<Format, CallToolError> {
     let Some(s) = format_str else {
-        return Ok(Format::Markdown);
-    };
-    let parsed = match s.trim().to_lowercase().as_str() {
-        "markdown" => Some(Format::Markdown),
-        "text" => Some(Format::Text),
-        "html" => Some(Format::Html),
-        "json" => Some(Format::Json),
-        _ => None,
+        let preferred = negotiate::session_default();
+        return Ok(if allowed.contains(&preferred) {
+            preferred
+        } else {
+            Format::Markdown
+        });
     };
-    match parsed {
+    match format_from_name(s) {
         Some(format) if allowed.contains(&format) => Ok(format),
         _ => {
             let supported = allowed
@@ -106,6 +183,18 @@ pub fn parse_format(
     }
 }
 
+/// Parse a format name, ignoring case and surrounding whitespace
+pub(crate) fn format_from_name(name: &str) -> Option<Format> {
+    match name.trim().to_lowercase().as_str() {
+        "markdown" => Some(Format::Markdown),
+        "text" => Some(Format::Text),
+        "html" => Some(Format::Html),
+        "json" => Some(Format::Json),
+        "llm" => Some(Format::Llm),
+        _ => None,
+    }
+}
+
 /// Validate a crate name supplied by a tool caller.
 ///
 /// Crate names on crates.io are restricted to ASCII alphanumerics plus `_` and
@@ -188,15 +277,21 @@ pub fn validate_version(tool_name: &str, version: Option<&str>) -> Result<(), Ca
             Some("version is too long (max 64 characters)".to_string()),
         ));
     }
+    // Semver operators are allowed so requirements such as `^1.2` or
+    // `>=1,<2` can be resolved (see `DocService::resolve_version_param`).
     if ver.contains("..")
-        || !ver
-            .bytes()
-            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'+' | b'_' | b'~'))
+        || !ver.bytes().all(|b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'.' | b'-' | b'+' | b'_' | b'~' | b'^' | b'*' | b'<' | b'>' | b'=' | b','
+                )
+        })
     {
         return Err(CallToolError::invalid_arguments(
             tool_name,
             Some(format!(
-                "Invalid version '{raw}'. Only ASCII letters, digits and '.', '-', '+', '_', '~' are allowed"
+                "Invalid version '{raw}'. Only ASCII letters, digits, '.', '-', '+', '_' and the semver operators '~', '^', '*', '<', '>', '=', ',' are allowed"
             )),
         ));
     }
@@ -283,16 +378,35 @@ pub fn validate_item_path(tool_name: &str, item_path: &str) -> Result<(), CallTo
     Ok(())
 }
 
-/// Summarize a non-success HTTP response from docs.rs into a concise,
-/// actionable error string.
+/// Summarize a non-success HTTP response to a request of `url` into a
+/// concise, actionable error string.
 ///
 /// docs.rs returns a full HTML error page (often several KB) for failures such
 /// as 404. Dumping that entire page into the tool error is noisy and unhelpful,
-/// so this collapses it to the status plus a short hint. HTML bodies are never
-/// echoed back; only short plain-text bodies are included as a snippet.
-fn summarize_http_status(status: reqwest::StatusCode, body: &str) -> String {
+/// so this collapses it to the status plus a short hint naming the upstream
+/// (see [`upstream_name`]). HTML bodies are never echoed back; only short
+/// plain-text bodies are included as a snippet. crates.io JSON error bodies
+/// are reduced to their `detail` messages.
+pub(super) fn summarize_http_status(url: &str, status: reqwest::StatusCode, body: &str) -> String {
+    let upstream = upstream_name(url);
+    if let Some(detail) = crates_io_error_detail(body) {
+        let hint = if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            format!(" (rate limited by {upstream}; wait before retrying)")
+        } else {
+            String::new()
+        };
+        return format!("HTTP {status} - {detail}{hint}");
+    }
     if status == reqwest::StatusCode::NOT_FOUND {
-        return "HTTP 404 Not Found - the requested crate, version, or item does not exist on docs.rs. Verify the crate name, version, and item path.".to_string();
+        return if is_registry_url(url) {
+            format!(
+                "HTTP 404 Not Found - the requested crate or version does not exist on {upstream}. Verify the crate name and version."
+            )
+        } else {
+            format!(
+                "HTTP 404 Not Found - the requested crate, version, or item does not exist on {upstream}. Verify the crate name, version, and item path."
+            )
+        };
     }
 
     let trimmed = body.trim();
@@ -307,12 +421,86 @@ fn summarize_http_status(status: reqwest::StatusCode, body: &str) -> String {
     }
 }
 
+/// Name of the upstream serving `url`, for error messages
+///
+/// crates.io (API and sparse index) and docs.rs are named as such, other
+/// hosts (mirrors, custom registries) by their host name.
+fn upstream_name(url: &str) -> String {
+    let host = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string));
+    match host {
+        Some(host) if host == "crates.io" || host.ends_with(".crates.io") => {
+            "crates.io".to_string()
+        }
+        _ if url.starts_with(&docs_rs_base_url()) => "docs.rs".to_string(),
+        Some(host) => host,
+        None => "the upstream".to_string(),
+    }
+}
+
+/// Whether `url` belongs to the crate registry (API or sparse index) rather
+/// than a documentation host
+fn is_registry_url(url: &str) -> bool {
+    upstream_name(url) == "crates.io" || url.starts_with(&crates_io_base_url())
+}
+
+/// `detail` messages of a crates.io error body
+///
+/// crates.io reports bad requests, missing crates and rate limits as
+/// `{"errors":[{"detail":"..."}]}`.
+fn crates_io_error_detail(body: &str) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct ErrorBody {
+        errors: Vec<ErrorEntry>,
+    }
+    #[derive(serde::Deserialize)]
+    struct ErrorEntry {
+        detail: Option<String>,
+    }
+
+    let parsed: ErrorBody = serde_json::from_str(body.trim()).ok()?;
+    let details: Vec<String> = parsed
+        .errors
+        .into_iter()
+        .filter_map(|entry| entry.detail)
+        .map(|detail| detail.trim().chars().take(200).collect::<String>())
+        .filter(|detail| !detail.is_empty())
+        .collect();
+    (!details.is_empty()).then(|| details.join("; "))
+}
+
+/// Not-found error for a crate (or crate version) missing on docs.rs
+///
+/// Suggestions are left empty here; see [`crate_not_found_suggesting`].
+#[must_use]
+pub fn crate_not_found(crate_name: &str, version: Option<&str>) -> crate::error::Error {
+    crate_not_found_suggesting(crate_name, version, Vec::new())
+}
+
+/// [`crate_not_found`] with "did you mean" crate names, best match first
+#[must_use]
+pub fn crate_not_found_suggesting(
+    crate_name: &str,
+    version: Option<&str>,
+    suggestions: Vec<String>,
+) -> crate::error::Error {
+    let name = match version {
+        Some(ver) => format!("{crate_name}@{ver}"),
+        None => crate_name.to_string(),
+    };
+    crate::error::Error::not_found("crate", name, suggestions)
+}
+
 #[cfg(not(test))]
 const DOCS_RS_BASE_URL: &str = "https://docs.rs";
 
 #[cfg(not(test))]
 const CRATES_IO_BASE_URL: &str = "https://crates.io";
 
+#[cfg(not(test))]
+const CRATES_IO_INDEX_BASE_URL: &str = "https://index.crates.io";
+
 #[must_use]
 #[cfg(test)]
 /// Get the docs.rs base URL (configurable via environment variable for testing)
@@ -336,9 +524,31 @@ pub fn crates_io_base_url() -> String {
 
 #[must_use]
 #[cfg(not(test))]
-/// Get the crates.io base URL
+/// Get the crates.io base URL, or the API URL of the configured registry
 pub fn crates_io_base_url() -> String {
-    CRATES_IO_BASE_URL.to_string()
+    registry::active_registry().map_or_else(
+        || CRATES_IO_BASE_URL.to_string(),
+        registry::RegistryConfig::api_url,
+    )
+}
+
+#[must_use]
+#[cfg(test)]
+/// Get the crates.io sparse index base URL (configurable via environment variable for testing)
+pub fn crates_io_index_base_url() -> String {
+    std::env::var("CRATES_DOCS_CRATES_IO_INDEX_URL")
+        .unwrap_or_else(|_| "https://index.crates.io".to_string())
+}
+
+#[must_use]
+#[cfg(not(test))]
+/// Get the crates.io sparse index base URL, or the index of the configured
+/// registry
+pub fn crates_io_index_base_url() -> String {
+    registry::active_registry().map_or_else(
+        || CRATES_IO_INDEX_BASE_URL.to_string(),
+        registry::RegistryConfig::index_url,
+    )
 }
 /// Standard distribution crates documented on doc.rust-lang.org.
 ///
@@ -385,32 +595,14 @@ pub fn build_docs_url(crate_name: &str, version: Option<&str>) -> String {
     }
 }
 
-/// Build docs.rs search URL for item lookup
-#[must_use]
-pub fn build_docs_item_url(crate_name: &str, version: Option<&str>, item_path: &str) -> String {
-    let encoded_path = urlencoding::encode(item_path);
-    if is_rust_std_crate(crate_name) {
-        // std/core/alloc/etc. are not published to docs.rs; their docs live on
-        // doc.rust-lang.org. Mirror the other URL builders so the last-resort
-        // fallback degrades to the crate overview instead of a hard 404.
-        let krate = crate_name.replace('-', "_");
-        let base = rust_lang_docs_base(&krate, version);
-        return format!("{base}?search={encoded_path}");
-    }
-    let base_url = docs_rs_base_url();
-    match version {
-        Some(ver) => format!("{base_url}/{crate_name}/{ver}/?search={encoded_path}"),
-        None => format!("{base_url}/{crate_name}/?search={encoded_path}"),
-    }
-}
-
 /// Build candidate docs.rs URLs for a specific item, in priority order.
 ///
 /// rustdoc item pages use predictable `{kind}.{name}.html` file names, but the
 /// item kind (struct/trait/fn/...) cannot be derived from the path alone. This
 /// returns the plausible candidate URLs to probe; the caller fetches each in
-/// order and uses the first that exists (HTTP 200). A trailing module candidate
-/// (`{name}/index.html`) covers items that are themselves modules.
+/// order and uses the first that exists (HTTP 200). A module candidate
+/// (`{name}/index.html`) covers items that are themselves modules; statics
+/// and trait aliases come after it.
 ///
 /// The crate's library path component uses the underscore form (docs.rs maps
 /// `-` to `_` for module paths). A leading path segment equal to the crate name
@@ -421,6 +613,16 @@ pub fn build_docs_item_url_candidates(
     version: Option<&str>,
     item_path: &str,
 ) -> Vec<String> {
+    item_url_candidates_under(&crate_root_url(crate_name, version), crate_name, item_path)
+}
+
+/// Build candidate item page URLs below a crate's root module URL
+///
+/// `root` is the rustdoc directory of the crate's root module and must end
+/// in `/` (e.g. `https://docs.rs/serde/latest/serde/`). See
+/// [`build_docs_item_url_candidates`] for the candidate order.
+#[must_use]
+pub fn item_url_candidates_under(root: &str, crate_name: &str, item_path: &str) -> Vec<String> {
     let krate = crate_name.replace('-', "_");
 
     let segments: Vec<&str> = item_path
@@ -439,13 +641,7 @@ pub fn build_docs_item_url_candidates(
         mods
     };
 
-    let mut prefix = if is_rust_std_crate(crate_name) {
-        rust_lang_docs_base(&krate, version)
-    } else {
-        let base_url = docs_rs_base_url();
-        let ver = version.unwrap_or("latest");
-        format!("{base_url}/{crate_name}/{ver}/{krate}/")
-    };
+    let mut prefix = root.to_string();
     for m in mods {
         prefix.push_str(m);
         prefix.push('/');
@@ -471,6 +667,8 @@ pub fn build_docs_item_url_candidates(
         .collect();
     // The item itself may be a module.
     candidates.push(format!("{prefix}{item}/index.html"));
+    // Rare kinds last, so a module lookup does not probe them first
+    candidates.extend(["static", "traitalias"].map(|k| format!("{prefix}{k}.{item}.html")));
     candidates
 }
 
@@ -481,14 +679,32 @@ pub fn build_docs_item_url_candidates(
 /// resolve items that have no stub page at the path implied by their name.
 #[must_use]
 pub fn build_docs_all_items_url(crate_name: &str, version: Option<&str>) -> String {
-    let krate = crate_name.replace('-', "_");
+    format!("{}all.html", crate_root_url(crate_name, version))
+}
+
+/// URL of the rustdoc directory for a crate's root module (ends in `/`)
+///
+/// std-family crates resolve to doc.rust-lang.org, everything else to the
+/// docs.rs layout (see [`docs_rs_layout_root_url`]).
+#[must_use]
+pub fn crate_root_url(crate_name: &str, version: Option<&str>) -> String {
     if is_rust_std_crate(crate_name) {
-        let base = rust_lang_docs_base(&krate, version);
-        return format!("{base}all.html");
+        let krate = crate_name.replace('-', "_");
+        return rust_lang_docs_base(&krate, version);
     }
-    let base_url = docs_rs_base_url();
+    docs_rs_layout_root_url(&docs_rs_base_url(), crate_name, version)
+}
+
+/// Root module URL for a host using the docs.rs path layout
+///
+/// docs.rs (and mirrors of it) serve a crate's rustdoc at
+/// `{base}/{crate}/{version}/{crate_lib_name}/`, with `latest` standing in for
+/// an unspecified version.
+#[must_use]
+pub fn docs_rs_layout_root_url(base_url: &str, crate_name: &str, version: Option<&str>) -> String {
+    let krate = crate_name.replace('-', "_");
     let ver = version.unwrap_or("latest");
-    format!("{base_url}/{crate_name}/{ver}/{krate}/all.html")
+    format!("{base_url}/{crate_name}/{ver}/{krate}/")
 }
 
 /// Resolve an item page URL from a crate's `all.html` index by item name.
@@ -505,6 +721,18 @@ pub fn find_item_url_in_all_html(
     all_html: &str,
     item_name: &str,
 ) -> Option<String> {
+    let href = find_item_href_in_all_html(all_html, item_name)?;
+    // `all.html` links relative to the crate root module, on docs.rs and
+    // doc.rust-lang.org alike.
+    Some(format!("{}{href}", crate_root_url(crate_name, version)))
+}
+
+/// Find the relative href of an item's page in a crate's `all.html` index
+///
+/// Returns the first `{kind}.{item_name}.html` link (possibly under module
+/// directories), or `None` if there is no match or the name is empty.
+#[must_use]
+pub fn find_item_href_in_all_html(all_html: &str, item_name: &str) -> Option<String> {
     let item_name = item_name.trim();
     if item_name.is_empty() {
         return None;
@@ -515,18 +743,17 @@ pub fn find_item_url_in_all_html(
         regex::escape(item_name)
     );
     let re = regex::Regex::new(&pattern).ok()?;
-    let href = re.captures(all_html)?.get(1)?.as_str();
+    Some(re.captures(all_html)?.get(1)?.as_str().to_string())
+}
 
-    let krate = crate_name.replace('-', "_");
-    if is_rust_std_crate(crate_name) {
-        // std/core/alloc docs live on doc.rust-lang.org, not docs.rs; the
-        // all.html index there links relative to the crate root.
-        let base = rust_lang_docs_base(&krate, version);
-        return Some(format!("{base}{href}"));
-    }
+/// Build a docs.rs URL under the crate details path (`/crate/{name}/{version}/`)
+///
+/// `suffix` is appended verbatim, e.g. `status.json`, `builds.json` or
+/// `builds/{id}`; an empty suffix yields the crate details page.
+#[must_use]
+pub fn build_docs_rs_crate_url(crate_name: &str, version: &str, suffix: &str) -> String {
     let base_url = docs_rs_base_url();
-    let ver = version.unwrap_or("latest");
-    Some(format!("{base_url}/{crate_name}/{ver}/{krate}/{href}"))
+    format!("{base_url}/crate/{crate_name}/{version}/{suffix}")
 }
 
 /// Build crates.io API search URL
@@ -544,6 +771,142 @@ pub fn build_crates_io_search_url(query: &str, sort: Option<&str>, limit: Option
     )
 }
 
+/// Build crates.io API URL for a category's metadata
+#[must_use]
+pub fn build_crates_io_category_url(category: &str) -> String {
+    let base_url = crates_io_base_url();
+    format!(
+        "{}/api/v1/categories/{}",
+        base_url,
+        urlencoding::encode(category)
+    )
+}
+
+/// Build crates.io API URL listing the crates in a category
+#[must_use]
+pub fn build_crates_io_category_crates_url(category: &str, sort: &str, limit: usize) -> String {
+    let base_url = crates_io_base_url();
+    format!(
+        "{}/api/v1/crates?category={}&per_page={}&sort={}",
+        base_url,
+        urlencoding::encode(category),
+        limit,
+        urlencoding::encode(sort)
+    )
+}
+
+/// Build crates.io API URL for a filtered crate listing (no search query)
+///
+/// Used by the trending tool; `category` and `keyword` narrow the listing
+/// when given.
+#[must_use]
+pub fn build_crates_io_listing_url(
+    sort: &str,
+    category: Option<&str>,
+    keyword: Option<&str>,
+    limit: usize,
+) -> String {
+    use std::fmt::Write;
+    let base_url = crates_io_base_url();
+    let mut url = format!(
+        "{}/api/v1/crates?per_page={}&sort={}",
+        base_url,
+        limit,
+        urlencoding::encode(sort)
+    );
+    // SAFETY: write! to String never fails
+    if let Some(category) = category {
+        write!(url, "&category={}", urlencoding::encode(category)).unwrap();
+    }
+    if let Some(keyword) = keyword {
+        write!(url, "&keyword={}", urlencoding::encode(keyword)).unwrap();
+    }
+    url
+}
+
+/// Build crates.io API URL listing the given crates by name
+///
+/// Used to look up download counts for an explicit set of crates in one
+/// request.
+#[must_use]
+pub fn build_crates_io_ids_url(crates: &[String]) -> String {
+    use std::fmt::Write;
+    let mut url = format!(
+        "{}/api/v1/crates?per_page={}",
+        crates_io_base_url(),
+        crates.len()
+    );
+    for name in crates {
+        // SAFETY: write! to String never fails
+        write!(url, "&ids%5B%5D={}", urlencoding::encode(name)).unwrap();
+    }
+    url
+}
+
+/// Build crates.io API URL for a crate's metadata (keywords, categories, downloads)
+#[must_use]
+pub fn build_crates_io_crate_url(crate_name: &str) -> String {
+    format!(
+        "{}/api/v1/crates/{}",
+        crates_io_base_url(),
+        urlencoding::encode(crate_name)
+    )
+}
+
+/// Build crates.io API URL for a single published version of a crate
+#[must_use]
+pub fn build_crates_io_version_url(crate_name: &str, version: &str) -> String {
+    format!(
+        "{}/api/v1/crates/{}/{}",
+        crates_io_base_url(),
+        urlencoding::encode(crate_name),
+        urlencoding::encode(version)
+    )
+}
+
+/// Build the crates.io sparse index URL for a crate
+///
+/// Index files live under a prefix derived from the lowercased name:
+/// `1/{name}`, `2/{name}`, `3/{c}/{name}` or `{ab}/{cd}/{name}`.
+#[must_use]
+pub fn build_crates_io_index_url(crate_name: &str) -> String {
+    let name = crate_name.trim().to_ascii_lowercase();
+    let prefix = match name.len() {
+        1 => "1".to_string(),
+        2 => "2".to_string(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    };
+    format!("{}/{prefix}/{name}", crates_io_index_base_url())
+}
+
+/// Read a page of locally built documentation, `Ok(None)` if it does not exist
+async fn read_local_page(
+    url: &str,
+    tool_name: Option<&str>,
+) -> Result<Option<String>, CallToolError> {
+    let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
+    let path = url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .filter(|path| {
+            !path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        })
+        .ok_or_else(|| {
+            CallToolError::from_message(format!("{prefix}Invalid local documentation URL: {url}"))
+        })?;
+    match tokio::fs::read_to_string(&path).await {
+        Ok(html) => Ok(Some(html)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(CallToolError::from_message(format!(
+            "{prefix}Failed to read {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
 /// Document service
 ///
 /// Provides centralized management of HTTP client (with auto-retry), cache, and document cache.
@@ -553,10 +916,64 @@ pub fn build_crates_io_search_url(query: &str, sort: Option<&str>, limit: Option
 /// - `client`: HTTP client with retry middleware (shared reference for connection pool reuse)
 /// - `cache`: Generic cache instance
 /// - `doc_cache`: Document-specific cache
+/// - `providers`: Documentation sources, tried in order
+/// - `filters`: Default content filters for the lookup tools
+/// - `search_backends`: Backends `search_crates` queries, tried in order
+/// - `crates_io_token`: API token sent with crates.io API requests
+/// - `fetches`: Coalesces concurrent fetches of the same uncached page
+/// - `in_flight`: Shares one upstream request among concurrent requests of the same URL
+/// - `json_documents`: rustdoc JSON documents the pages of [`provider::RustdocJsonProvider`] are rendered from
+/// - `request_timeout`: Upper bound on one upstream request, retries included
 pub struct DocService {
     client: Arc<reqwest_middleware::ClientWithMiddleware>,
     cache: Arc<dyn Cache>,
     doc_cache: cache::DocCache,
+    providers: Vec<Arc<dyn provider::DocsProvider>>,
+    filters: filter::ContentFilters,
+    profiles: Arc<profile::ProjectProfiles>,
+    sanitize: sanitize::SanitizeLevel,
+    search_backends: Vec<search_backend::SearchBackend>,
+    crates_io_token: Option<Arc<str>>,
+    fetches: Arc<crate::cache::singleflight::FetchCoordinator>,
+    in_flight: crate::cache::singleflight::InFlight<PageResult>,
+    json_documents: rustdoc_json::Documents,
+    request_timeout: Option<std::time::Duration>,
+}
+
+/// Outcome of an upstream page request shared by [`DocService::request_page`]
+///
+/// Errors carry their message without the tool prefix, since callers of
+/// different tools may share one request.
+type PageResult = Result<Page, String>;
+
+/// Response to an upstream page request
+///
+/// Callers sharing a request share the body too: cloning a page only bumps
+/// the count of the body, which can be several MB.
+#[derive(Clone)]
+enum Page {
+    /// The page body, with the validators of the response
+    Found(Arc<String>, Option<cache::Validators>),
+    /// HTTP 404, with the error body (crates.io explains it in JSON)
+    NotFound(String),
+    /// HTTP 304 to a conditional request
+    NotModified,
+}
+
+/// The body of a shared page, copied only if other callers still hold it
+fn into_owned(body: Arc<String>) -> String {
+    Arc::try_unwrap(body).unwrap_or_else(|shared| shared.as_str().to_owned())
+}
+
+/// Outcome of [`DocService::revalidate_crate_page`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revalidation {
+    /// The upstream confirmed the cached page is current
+    NotModified,
+    /// The page was downloaded again
+    Modified(String),
+    /// Every provider reports the crate missing
+    NotFound,
 }
 
 impl DocService {
@@ -611,12 +1028,25 @@ impl DocService {
     ) -> crate::error::Result<Self> {
         let ttl = cache::DocCacheTtl::from_cache_config(cache_config);
         let doc_cache = cache::DocCache::with_ttl(cache.clone(), ttl);
+        let fetches = Arc::new(crate::cache::singleflight::FetchCoordinator::new(
+            cache.clone(),
+        ));
         // Use global HTTP client singleton for connection pool reuse
         let client = crate::utils::get_or_init_global_http_client()?;
         Ok(Self {
             client,
             cache,
             doc_cache,
+            providers: provider::default_providers(),
+            filters: filter::ContentFilters::default(),
+            profiles: Arc::new(profile::ProjectProfiles::new()),
+            sanitize: sanitize::SanitizeLevel::default(),
+            search_backends: search_backend::default_search_backends(),
+            crates_io_token: None,
+            fetches,
+            in_flight: crate::cache::singleflight::InFlight::new(),
+            json_documents: rustdoc_json::Documents::default(),
+            request_timeout: None,
         })
     }
 
@@ -644,12 +1074,25 @@ impl DocService {
     ) -> crate::error::Result<Self> {
         let ttl = cache::DocCacheTtl::from_cache_config(cache_config);
         let doc_cache = cache::DocCache::with_ttl(cache.clone(), ttl);
+        let fetches = Arc::new(crate::cache::singleflight::FetchCoordinator::new(
+            cache.clone(),
+        ));
         // Use global HTTP client singleton for connection pool reuse
         let client = crate::utils::get_or_init_global_http_client()?;
         Ok(Self {
             client,
             cache,
             doc_cache,
+            providers: provider::default_providers(),
+            filters: filter::ContentFilters::default(),
+            profiles: Arc::new(profile::ProjectProfiles::new()),
+            sanitize: sanitize::SanitizeLevel::default(),
+            search_backends: search_backend::default_search_backends(),
+            crates_io_token: None,
+            fetches,
+            in_flight: crate::cache::singleflight::InFlight::new(),
+            json_documents: rustdoc_json::Documents::default(),
+            request_timeout: None,
         })
     }
 
@@ -665,16 +1108,532 @@ impl DocService {
         &self.cache
     }
 
+    /// Start a GET request, authenticated if `url` targets the configured
+    /// registry (see [`registry::authorization_for`]) or, with a
+    /// [`crates_io_token`](Self::with_crates_io_token), the crates.io API
+    pub fn get(&self, url: &str) -> reqwest_middleware::RequestBuilder {
+        let request = self.client.get(url);
+        let token = registry::authorization_for(url).or_else(|| {
+            self.crates_io_token
+                .as_deref()
+                .filter(|_| upstream::is_crates_io_api(url))
+                .map(str::to_string)
+        });
+        match token {
+            Some(token) => request.header(reqwest::header::AUTHORIZATION, token),
+            None => request,
+        }
+    }
+
+    /// Set the API token sent with crates.io API requests
+    #[must_use]
+    pub fn with_crates_io_token(mut self, token: Option<String>) -> Self {
+        self.crates_io_token = token.map(Into::into);
+        self
+    }
+
+    /// Bound every upstream request, retries included, by `timeout`
+    ///
+    /// The HTTP client's own timeout applies to each attempt; this one caps
+    /// the request as a whole.
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Run the upstream request `request`, bounded by the
+    /// [request timeout](Self::with_request_timeout)
+    ///
+    /// `operation` names the request in the error (e.g. "GET <url>").
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`](crate::error::Error::Timeout) if the request
+    /// did not finish in time
+    pub async fn time_limited<F: std::future::Future>(
+        &self,
+        operation: &str,
+        request: F,
+    ) -> crate::error::Result<F::Output> {
+        match self.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, request)
+                .await
+                .map_err(|_| crate::error::Error::timeout(operation, timeout)),
+            None => Ok(request.await),
+        }
+    }
+
     /// Get document cache
     #[must_use]
     pub fn doc_cache(&self) -> &cache::DocCache {
         &self.doc_cache
     }
 
+    /// Replace the documentation provider chain
+    ///
+    /// Providers are tried in order; an empty list keeps the current chain.
+    #[must_use]
+    pub fn with_providers(mut self, providers: Vec<Arc<dyn provider::DocsProvider>>) -> Self {
+        if !providers.is_empty() {
+            self.providers = providers;
+        }
+        self
+    }
+
+    /// Set the content filters applied when a request does not override them
+    #[must_use]
+    pub fn with_content_filters(mut self, filters: filter::ContentFilters) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Set how fetched documentation pages are sanitized
+    #[must_use]
+    pub fn with_sanitize_level(mut self, level: sanitize::SanitizeLevel) -> Self {
+        self.sanitize = level;
+        self
+    }
+
+    /// Replace the search backends
+    ///
+    /// Backends are tried in order; an empty list keeps the current ones.
+    #[must_use]
+    pub fn with_search_backends(mut self, backends: Vec<search_backend::SearchBackend>) -> Self {
+        if !backends.is_empty() {
+            self.search_backends = backends;
+        }
+        self
+    }
+
+    /// Configured search backends, in fallback order
+    #[must_use]
+    pub fn search_backends(&self) -> &[search_backend::SearchBackend] {
+        &self.search_backends
+    }
+
+    /// Sanitize a freshly fetched documentation page
+    ///
+    /// Called before a page is cached, so cache replays carry the same
+    /// result (including the notice added when something was removed).
+    #[must_use]
+    pub fn sanitize_page(&self, html: String, tool_name: &str) -> String {
+        let (sanitized, report) = sanitize::sanitize(&html, self.sanitize);
+        if report.is_suspicious() {
+            tracing::warn!("[{tool_name}] neutralized suspicious page content: {report:?}");
+        }
+        match sanitized {
+            std::borrow::Cow::Borrowed(_) => html,
+            std::borrow::Cow::Owned(sanitized) => sanitized,
+        }
+    }
+
+    /// Default content filters
+    #[must_use]
+    pub fn content_filters(&self) -> filter::ContentFilters {
+        self.filters
+    }
+
+    /// Content filters for a request: the defaults with `overrides` applied
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-arguments error for an unknown section or mode
+    pub fn request_filters(
+        &self,
+        overrides: Option<&[String]>,
+        tool_name: &str,
+    ) -> Result<filter::ContentFilters, CallToolError> {
+        match overrides {
+            Some(overrides) => self
+                .filters
+                .with_overrides(overrides)
+                .map_err(|e| CallToolError::invalid_arguments(tool_name, Some(e))),
+            None => Ok(self.filters),
+        }
+    }
+
+    /// Configured documentation providers, in fallback order
+    #[must_use]
+    pub fn providers(&self) -> &[Arc<dyn provider::DocsProvider>] {
+        &self.providers
+    }
+
+    /// Providers able to serve `crate_name`, in fallback order
+    pub fn providers_for<'a>(
+        &'a self,
+        crate_name: &'a str,
+    ) -> impl Iterator<Item = &'a Arc<dyn provider::DocsProvider>> + 'a {
+        self.providers
+            .iter()
+            .filter(move |provider| provider.supports(crate_name))
+    }
+
+    /// User-facing documentation URL of a crate, from the first provider
+    /// serving it
+    #[must_use]
+    pub fn crate_docs_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        self.providers_for(crate_name).next().map_or_else(
+            || build_docs_url(crate_name, version),
+            |provider| provider.docs_url(crate_name, version),
+        )
+    }
+
+    /// URL under the crate details path (`/crate/{name}/{version}/{suffix}`)
+    /// of the first provider of the crate with a docs.rs build server
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no provider of the crate has one, e.g. a chain of
+    /// local providers only
+    pub fn crate_details_url(
+        &self,
+        crate_name: &str,
+        version: &str,
+        suffix: &str,
+        tool_name: &str,
+    ) -> Result<String, CallToolError> {
+        self.providers_for(crate_name)
+            .find_map(|provider| provider.crate_details_url(crate_name, version, suffix))
+            .ok_or_else(|| {
+                CallToolError::from_message(format!(
+                    "[{tool_name}] No configured documentation provider reports docs.rs builds of '{crate_name}'"
+                ))
+            })
+    }
+
+    /// Coordinator for fetches of uncached pages
+    ///
+    /// See [`crate::cache::singleflight`].
+    #[must_use]
+    pub fn fetches(&self) -> &crate::cache::singleflight::FetchCoordinator {
+        &self.fetches
+    }
+
+    /// Per-session project profiles
+    #[must_use]
+    pub fn profiles(&self) -> &profile::ProjectProfiles {
+        &self.profiles
+    }
+
+    /// Count a successful crate or item lookup for the `top_queries` report
+    ///
+    /// Counting is best effort: a cache failure is logged, never returned,
+    /// so it cannot fail the lookup being counted.
+    pub async fn record_lookup(&self, crate_name: &str, item_path: Option<&str>, tool_name: &str) {
+        if let Err(e) = self.doc_cache.record_lookup(crate_name, item_path).await {
+            tracing::warn!("[{tool_name}] failed to record lookup: {e}");
+        }
+    }
+
+    /// Resolve a tool's `version` argument to a concrete version
+    ///
+    /// Requirements such as `^1.2` or `>=1, <2` are resolved against the
+    /// crates.io index. A missing version falls back to the session's project
+    /// profile pin, if any, and otherwise, like `latest`, to the newest
+    /// release (see [`Self::resolve_latest`]). Complete versions and anything
+    /// that is not semver (e.g. a toolchain channel for std crates) are
+    /// returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a requirement is given for a std-family crate,
+    /// no published version satisfies it, or the index is unreachable
+    pub async fn resolve_version_param(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        tool_name: &str,
+    ) -> Result<Option<String>, CallToolError> {
+        let Some(raw) = version else {
+            if let Some(pinned) = self.profiles.pinned(crate_name) {
+                tracing::debug!("[{tool_name}] using profile pin {crate_name} {pinned}");
+                return Ok(Some(pinned));
+            }
+            return Ok(self.resolve_latest(crate_name, tool_name).await);
+        };
+        let req = match resolve_version::VersionSpec::parse(raw) {
+            Some(resolve_version::VersionSpec::Requirement(req)) => req,
+            Some(resolve_version::VersionSpec::Latest) => {
+                let latest = self.resolve_latest(crate_name, tool_name).await;
+                return Ok(latest.or_else(|| Some(raw.to_string())));
+            }
+            None if raw.contains(['^', '*', '<', '>', '=', ',']) => {
+                return Err(CallToolError::invalid_arguments(
+                    tool_name,
+                    Some(format!("Invalid version requirement '{raw}'")),
+                ));
+            }
+            _ => return Ok(Some(raw.to_string())),
+        };
+        if is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                tool_name,
+                Some(format!(
+                    "Version requirement '{raw}' is not supported for '{crate_name}'; use a concrete Rust version or channel"
+                )),
+            ));
+        }
+        let spec = resolve_version::VersionSpec::Requirement(req);
+        let resolution = resolve_version::resolve(self, crate_name, raw, &spec, tool_name).await?;
+        tracing::debug!(
+            "[{tool_name}] resolved {crate_name} {raw} to {}",
+            resolution.version
+        );
+        Ok(Some(resolution.version))
+    }
+
+    /// The concrete version `latest` currently points to
+    ///
+    /// Documentation is cached under this version rather than under `latest`,
+    /// so a new release is served once the index versions (cached with the
+    /// search results TTL) expire, and `latest` shares its cache entries with
+    /// lookups of the same explicit version. Returns `None` for std-family
+    /// crates and when the index cannot be read, in which case the lookup
+    /// falls back to docs.rs's own `latest`.
+    async fn resolve_latest(&self, crate_name: &str, tool_name: &str) -> Option<String> {
+        if is_rust_std_crate(crate_name) {
+            return None;
+        }
+        match resolve_version::fetch_index_versions(self, crate_name, tool_name).await {
+            Ok(versions) => {
+                let latest = resolve_version::select_version(
+                    &versions,
+                    &resolve_version::VersionSpec::Latest,
+                )?;
+                tracing::debug!("[{tool_name}] latest {crate_name} is {}", latest.version);
+                Some(latest.version.to_string())
+            }
+            Err(e) => {
+                tracing::debug!("[{tool_name}] cannot resolve latest {crate_name}: {e}");
+                None
+            }
+        }
+    }
+
+    /// The spelling a crate was published under
+    ///
+    /// crates.io treats `-` and `_` in crate names as equivalent
+    /// (`serde-json` is `serde_json`), but docs.rs URLs, the sparse index and
+    /// the cache keys need the published spelling. Names with a separator are
+    /// looked up on crates.io (through the cached crate summary); others, and
+    /// names crates.io does not know, are returned trimmed but unchanged.
+    pub async fn canonical_crate_name(&self, crate_name: &str, tool_name: &str) -> String {
+        let crate_name = crate_name.trim();
+        if !crate_name.contains(['-', '_']) || is_rust_std_crate(crate_name) {
+            return crate_name.to_string();
+        }
+        let key = |name: &str| name.to_ascii_lowercase().replace('-', "_");
+        match batch::fetch_crate_summary(self, crate_name, tool_name).await {
+            Ok(Some(summary)) if key(&summary.name) == key(crate_name) => {
+                if summary.name != crate_name {
+                    tracing::debug!(
+                        "[{tool_name}] crate {crate_name} is published as {}",
+                        summary.name
+                    );
+                }
+                summary.name
+            }
+            Ok(_) => crate_name.to_string(),
+            Err(e) => {
+                tracing::debug!("[{tool_name}] cannot canonicalize crate name {crate_name}: {e}");
+                crate_name.to_string()
+            }
+        }
+    }
+
+    /// Fetch a crate's documentation landing page from the provider chain
+    ///
+    /// Returns the first page found, with its relative links made absolute
+    /// (see [`html::absolutize_links`]). A provider that fails (rather than
+    /// reporting 404) is skipped so a later one can still answer; its error
+    /// is returned only if no provider has the page.
+    ///
+    /// A crate every provider reports missing is remembered for a short TTL
+    /// (see [`cache::DocCacheTtl::missing_crate_secs`]), so retries of the
+    /// same lookup return `None` without another round of requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last provider error if no provider returned the page and at
+    /// least one of them failed
+    pub async fn fetch_crate_page(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        tool_name: &str,
+    ) -> Result<Option<String>, CallToolError> {
+        if self.doc_cache.is_crate_missing(crate_name, version).await {
+            tracing::debug!(
+                "[{tool_name}] crate '{crate_name}' recently not found, skipping fetch"
+            );
+            return Ok(None);
+        }
+        // `CallToolError` is not `Send`, so only its message is kept across
+        // the following awaits.
+        let mut last_error: Option<String> = None;
+        for provider in self.providers_for(crate_name) {
+            match self
+                .fetch_provider_page(provider.as_ref(), crate_name, version, None, tool_name)
+                .await
+            {
+                Ok(Page::Found(html, validators)) => {
+                    self.remember_validators(crate_name, version, validators.as_ref(), tool_name)
+                        .await;
+                    return Ok(Some(into_owned(html)));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        provider = provider.name(),
+                        "[{tool_name}] provider failed, trying next: {e}"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        if let Some(message) = last_error {
+            return Err(CallToolError::from_message(message));
+        }
+        // Only a definite 404 from every provider is cached: a failure may
+        // be transient, and must not hide the crate for the whole TTL
+        if let Err(e) = self.doc_cache.set_crate_missing(crate_name, version).await {
+            tracing::warn!("[{tool_name}] failed to cache missing crate (continuing): {e}");
+        }
+        Ok(None)
+    }
+
+    /// Fetch the crate page again for a background refresh of its cached copy
+    ///
+    /// If the validators of the cached page are known, the provider it came
+    /// from is asked with a conditional request first, so an unchanged page
+    /// costs a `304 Not Modified` instead of a download. Otherwise, or if that
+    /// request fails, this falls back to [`DocService::fetch_crate_page`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the last provider error if no provider returned the page and at
+    /// least one of them failed
+    pub async fn revalidate_crate_page(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        tool_name: &str,
+    ) -> Result<Revalidation, CallToolError> {
+        if let Some(validators) = self
+            .doc_cache
+            .get_crate_validators(crate_name, version)
+            .await
+        {
+            let provider = self
+                .providers_for(crate_name)
+                .find(|provider| provider.crate_url(crate_name, version) == validators.url);
+            if let Some(provider) = provider {
+                let page = self
+                    .fetch_provider_page(
+                        provider.as_ref(),
+                        crate_name,
+                        version,
+                        Some(&validators),
+                        tool_name,
+                    )
+                    .await;
+                match page {
+                    Ok(Page::NotModified) => return Ok(Revalidation::NotModified),
+                    Ok(Page::Found(html, validators)) => {
+                        self.remember_validators(
+                            crate_name,
+                            version,
+                            validators.as_ref(),
+                            tool_name,
+                        )
+                        .await;
+                        return Ok(Revalidation::Modified(into_owned(html)));
+                    }
+                    Ok(Page::NotFound(_)) => {}
+                    Err(e) => {
+                        tracing::debug!("[{tool_name}] conditional request failed: {e}");
+                    }
+                }
+            }
+        }
+        Ok(
+            match self
+                .fetch_crate_page(crate_name, version, tool_name)
+                .await?
+            {
+                Some(html) => Revalidation::Modified(html),
+                None => Revalidation::NotFound,
+            },
+        )
+    }
+
+    /// Fetch the crate page of one provider, conditionally if `validators`
+    /// are given
+    ///
+    /// Errors are returned as messages: `CallToolError` is not `Send`, and
+    /// callers await more work while matching on the result.
+    async fn fetch_provider_page(
+        &self,
+        provider: &dyn provider::DocsProvider,
+        crate_name: &str,
+        version: Option<&str>,
+        validators: Option<&cache::Validators>,
+        tool_name: &str,
+    ) -> Result<Page, String> {
+        let url = provider.crate_url(crate_name, version);
+        let page = if url.starts_with("file://") {
+            match read_local_page(&url, Some(tool_name))
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                Some(html) => Page::Found(Arc::new(html), None),
+                None => Page::NotFound(String::new()),
+            }
+        } else {
+            let page = match validators {
+                // Not shared: an unconditional caller cannot use a 304
+                Some(validators) => self.send_page_request(&url, Some(validators)).await,
+                None => self.request_page(&url).await,
+            };
+            page.map_err(|message| format!("[{tool_name}] {message}"))?
+        };
+        // Relative links resolve against the root module directory: docs.rs
+        // redirects the crate URL to the page served there
+        Ok(match page {
+            Page::Found(html, validators) => Page::Found(
+                Arc::new(html::absolutize_links(
+                    &html,
+                    &provider.root_url(crate_name, version),
+                )),
+                validators,
+            ),
+            page => page,
+        })
+    }
+
+    /// Store the validators of a fetched crate page next to it
+    async fn remember_validators(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        validators: Option<&cache::Validators>,
+        tool_name: &str,
+    ) {
+        if let Err(e) = self
+            .doc_cache
+            .set_crate_validators(crate_name, version, validators)
+            .await
+        {
+            tracing::warn!("[{tool_name}] failed to cache page validators (continuing): {e}");
+        }
+    }
+
     /// Fetch HTML content from a URL
     ///
     /// This is a shared utility method used by multiple tools to fetch HTML
-    /// from docs.rs and crates.io.
+    /// from docs.rs and crates.io. `file://` URLs of a
+    /// [`provider::LocalProvider`] are read from disk.
     ///
     /// # Arguments
     ///
@@ -692,28 +1651,25 @@ impl DocService {
         url: &str,
         tool_name: Option<&str>,
     ) -> Result<String, CallToolError> {
-        let response = self.client.get(url).send().await.map_err(|e| {
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            CallToolError::from_message(format!("{prefix}HTTP request failed: {e}"))
-        })?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response.text().await.map_err(|e| {
-                let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-                CallToolError::from_message(format!("{prefix}Failed to read error response: {e}"))
-            })?;
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            return Err(CallToolError::from_message(format!(
+        let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
+        if url.starts_with("file://") {
+            return read_local_page(url, tool_name).await?.ok_or_else(|| {
+                CallToolError::from_message(format!(
+                    "{prefix}Local documentation page not found: {url}"
+                ))
+            });
+        }
+        match self.request_page(url).await {
+            Ok(Page::Found(body, _)) => Ok(into_owned(body)),
+            Ok(Page::NotFound(error_body)) => Err(CallToolError::from_message(format!(
                 "{prefix}Failed to get documentation: {}",
-                summarize_http_status(status, &error_body)
-            )));
+                summarize_http_status(url, reqwest::StatusCode::NOT_FOUND, &error_body)
+            ))),
+            Ok(Page::NotModified) => Err(CallToolError::from_message(format!(
+                "{prefix}Unexpected 304 Not Modified from {url}"
+            ))),
+            Err(message) => Err(CallToolError::from_message(format!("{prefix}{message}"))),
         }
-
-        response.text().await.map_err(|e| {
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            CallToolError::from_message(format!("{prefix}Failed to read response: {e}"))
-        })
     }
 
     /// Fetch HTML from `url`, returning `Ok(None)` when the resource does not
@@ -731,34 +1687,156 @@ impl DocService {
         url: &str,
         tool_name: Option<&str>,
     ) -> Result<Option<String>, CallToolError> {
-        let response = self.client.get(url).send().await.map_err(|e| {
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            CallToolError::from_message(format!("{prefix}HTTP request failed: {e}"))
-        })?;
+        if url.starts_with("file://") {
+            return read_local_page(url, tool_name).await;
+        }
+        let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
+        match self.request_page(url).await {
+            Ok(Page::Found(body, _)) => Ok(Some(into_owned(body))),
+            Ok(Page::NotFound(_)) => Ok(None),
+            Ok(Page::NotModified) => Err(CallToolError::from_message(format!(
+                "{prefix}Unexpected 304 Not Modified from {url}"
+            ))),
+            Err(message) => Err(CallToolError::from_message(format!("{prefix}{message}"))),
+        }
+    }
+
+    /// GET `url`, sharing one request among concurrent callers of the URL
+    ///
+    /// When many sessions look up the same crate at once, they all miss the
+    /// cache together; only the first one reaches the upstream, the others
+    /// share its response.
+    async fn request_page(&self, url: &str) -> PageResult {
+        self.in_flight
+            .run(url, || self.send_page_request(url, None))
+            .await
+    }
+
+    /// GET `url`, conditionally if `validators` are given
+    ///
+    /// Pages below a rustdoc JSON document are rendered from the document,
+    /// which has no validators of its own page.
+    async fn send_page_request(
+        &self,
+        url: &str,
+        validators: Option<&cache::Validators>,
+    ) -> PageResult {
+        if let Some((document_url, page)) = rustdoc_json::split_url(url) {
+            return self.render_json_page(document_url, page).await;
+        }
+        self.time_limited(
+            &format!("GET {url}"),
+            self.send_page_request_now(url, validators),
+        )
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    /// Render the page `page` of the rustdoc JSON document at `document_url`
+    async fn render_json_page(&self, document_url: &str, page: &str) -> PageResult {
+        let Some(document) = self
+            .json_documents
+            .get(document_url, || self.fetch_json_document(document_url))
+            .await?
+        else {
+            return Ok(Page::NotFound(String::new()));
+        };
+        let page = page.to_string();
+        let rendered = tokio::task::spawn_blocking(move || rustdoc_json::render(&document, &page))
+            .await
+            .map_err(|e| format!("Failed to render rustdoc JSON: {e}"))?;
+        Ok(match rendered {
+            Some(html) => Page::Found(Arc::new(html), None),
+            None => Page::NotFound(String::new()),
+        })
+    }
+
+    /// Download and parse a rustdoc JSON document, `None` on HTTP 404
+    async fn fetch_json_document(
+        &self,
+        url: &str,
+    ) -> Result<Option<Arc<serde_json::Value>>, String> {
+        let body = self
+            .time_limited(&format!("GET {url}"), async {
+                let response = self
+                    .get(url)
+                    .send()
+                    .await
+                    .map_err(|e| format!("HTTP request failed: {e}"))?;
+                let status = response.status();
+                if status == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !status.is_success() {
+                    let error_body = response
+                        .text()
+                        .await
+                        .map_err(|e| format!("Failed to read error response: {e}"))?;
+                    return Err(format!(
+                        "Failed to get documentation: {}",
+                        summarize_http_status(url, status, &error_body)
+                    ));
+                }
+                response
+                    .bytes()
+                    .await
+                    .map(Some)
+                    .map_err(|e| format!("Failed to read response: {e}"))
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        let Some(body) = body else {
+            return Ok(None);
+        };
+        tokio::task::spawn_blocking(move || rustdoc_json::parse_document(&body))
+            .await
+            .map_err(|e| format!("Failed to parse rustdoc JSON: {e}"))?
+            .map(|document| Some(Arc::new(document)))
+    }
+
+    /// [`send_page_request`](Self::send_page_request) without the timeout
+    async fn send_page_request_now(
+        &self,
+        url: &str,
+        validators: Option<&cache::Validators>,
+    ) -> PageResult {
+        let mut request = self.get(url);
+        if let Some(validators) = validators {
+            request = validators.apply(request);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {e}"))?;
 
         let status = response.status();
+        if status == reqwest::StatusCode::NOT_MODIFIED && validators.is_some() {
+            return Ok(Page::NotModified);
+        }
         if status == reqwest::StatusCode::NOT_FOUND {
-            return Ok(None);
+            // Only `fetch_html` reports the body, and a 404 without one is
+            // still a 404
+            return Ok(Page::NotFound(response.text().await.unwrap_or_default()));
         }
         if !status.is_success() {
-            // Surface a body-read failure instead of masking it with an empty
-            // string (matches `fetch_html` and the documented contract).
-            let error_body = response.text().await.map_err(|e| {
-                let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-                CallToolError::from_message(format!("{prefix}Failed to read error response: {e}"))
-            })?;
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            return Err(CallToolError::from_message(format!(
-                "{prefix}Failed to get documentation: {}",
-                summarize_http_status(status, &error_body)
-            )));
+            // Surface a body-read failure instead of masking it with an
+            // empty string
+            let error_body = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read error response: {e}"))?;
+            return Err(format!(
+                "Failed to get documentation: {}",
+                summarize_http_status(url, status, &error_body)
+            ));
         }
 
-        let body = response.text().await.map_err(|e| {
-            let prefix = tool_name.map_or(String::new(), |n| format!("[{n}] "));
-            CallToolError::from_message(format!("{prefix}Failed to read response: {e}"))
-        })?;
-        Ok(Some(body))
+        let validators = cache::Validators::from_headers(url, response.headers());
+        response
+            .text()
+            .await
+            .map(|body| Page::Found(Arc::new(body), validators))
+            .map_err(|e| format!("Failed to read response: {e}"))
     }
 
     /// Create new document service with custom HTTP client (for testing)
@@ -770,10 +1848,23 @@ impl DocService {
     ) -> Self {
         let ttl = cache::DocCacheTtl::from_cache_config(cache_config);
         let doc_cache = cache::DocCache::with_ttl(cache.clone(), ttl);
+        let fetches = Arc::new(crate::cache::singleflight::FetchCoordinator::new(
+            cache.clone(),
+        ));
         Self {
             client,
             cache,
             doc_cache,
+            providers: provider::default_providers(),
+            filters: filter::ContentFilters::default(),
+            profiles: Arc::new(profile::ProjectProfiles::new()),
+            sanitize: sanitize::SanitizeLevel::default(),
+            search_backends: search_backend::default_search_backends(),
+            crates_io_token: None,
+            fetches,
+            in_flight: crate::cache::singleflight::InFlight::new(),
+            json_documents: rustdoc_json::Documents::default(),
+            request_timeout: None,
         }
     }
 }
@@ -815,19 +1906,56 @@ impl DocService {
 
         let ttl = cache::DocCacheTtl::from_cache_config(&cache_config);
         let doc_cache = cache::DocCache::with_ttl(cache.clone(), ttl);
+        let fetches = Arc::new(crate::cache::singleflight::FetchCoordinator::new(
+            cache.clone(),
+        ));
 
         Self {
             client,
             cache,
             doc_cache,
+            providers: provider::default_providers(),
+            filters: filter::ContentFilters::default(),
+            profiles: Arc::new(profile::ProjectProfiles::new()),
+            sanitize: sanitize::SanitizeLevel::default(),
+            search_backends: search_backend::default_search_backends(),
+            crates_io_token: None,
+            fetches,
+            in_flight: crate::cache::singleflight::InFlight::new(),
+            json_documents: rustdoc_json::Documents::default(),
+            request_timeout: None,
         }
     }
 }
 
 /// Re-export tool types
+pub use alternatives::SuggestAlternativesTool;
+pub use batch::BatchLookupCratesTool;
+pub use build_info::CrateBuildInfoTool;
+pub use build_status::DocsRsBuildStatusTool;
+pub use category::ListCratesByCategoryTool;
+pub use clippy_lint::LookupClippyLintTool;
+pub use coverage::DocCoverageTool;
+pub use dependency::SuggestDependencyLineTool;
+pub use dependency_tree::DependencyTreeTool;
+pub use deprecated::ListDeprecatedItemsTool;
+pub use examples::ExtractDocExamplesTool;
+pub use features::ListFeatureGatedItemsTool;
+pub use lockfile::AnalyzeCargoLockTool;
 pub use lookup_crate::LookupCrateTool;
 pub use lookup_item::LookupItemTool;
+pub use lookup_macro::LookupMacroTool;
+pub use manifest::AnalyzeCargoTomlTool;
+pub use playground::RunDocExampleTool;
+pub use profile::SetProjectProfileTool;
+pub use resolve_version::ResolveVersionTool;
+pub use rustc_error::LookupRustcErrorTool;
 pub use search::SearchCratesTool;
+pub use summarize::SummarizeCrateTool;
+pub use symbol::FindSymbolTool;
+pub use toc::GetTocTool;
+pub use top_queries::TopQueriesTool;
+pub use trending::TrendingCratesTool;
 
 /// Re-export cache types
 pub use cache::DocCacheTtl;
@@ -838,7 +1966,24 @@ mod tests {
 
     /// All syntactically valid formats, used to exercise the string->Format
     /// mapping independently of any single tool's allowed set.
-    const ALL: &[Format] = &[Format::Markdown, Format::Text, Format::Html, Format::Json];
+    const ALL: &[Format] = &[
+        Format::Markdown,
+        Format::Text,
+        Format::Html,
+        Format::Json,
+        Format::Llm,
+    ];
+
+    #[test]
+    fn test_shared_page_body_is_copied_only_while_shared() {
+        let body = Arc::new("<html></html>".to_string());
+        let other = Arc::clone(&body);
+        let buffer = other.as_ptr();
+        assert_eq!(into_owned(body), "<html></html>");
+        // The last holder takes the body itself
+        let owned = into_owned(other);
+        assert_eq!(owned.as_ptr(), buffer);
+    }
 
     #[test]
     fn test_validate_crate_name_accepts_valid() {
@@ -880,6 +2025,8 @@ mod tests {
         assert!(validate_version("lookup_crate", Some("1.0.0+build.5")).is_ok());
         assert!(validate_version("lookup_crate", Some("latest")).is_ok());
         assert!(validate_version("lookup_crate", Some("  1.2.3  ")).is_ok());
+        assert!(validate_version("lookup_crate", Some("^1.2")).is_ok());
+        assert!(validate_version("lookup_crate", Some(">=1.2,<2")).is_ok());
     }
 
     #[test]
@@ -955,11 +2102,18 @@ mod tests {
         assert!(c
             .iter()
             .any(|u| u.ends_with("/serde/latest/serde/struct.Serialize.html")));
-        // module fallback candidate is last
+        // module candidate precedes the rare kinds
+        let module = c
+            .iter()
+            .position(|u| u.ends_with("/serde/latest/serde/Serialize/index.html"))
+            .unwrap();
+        assert!(c[module + 1..]
+            .iter()
+            .all(|u| u.contains("/static.") || u.contains("/traitalias.")));
         assert!(c
             .last()
             .unwrap()
-            .ends_with("/serde/latest/serde/Serialize/index.html"));
+            .ends_with("/serde/latest/serde/traitalias.Serialize.html"));
     }
 
     #[test]
@@ -1108,6 +2262,7 @@ mod tests {
     #[test]
     fn test_summarize_http_status_not_found() {
         let msg = summarize_http_status(
+            &format!("{}/serde/latest/serde/", docs_rs_base_url()),
             reqwest::StatusCode::NOT_FOUND,
             "<!DOCTYPE html><html><body>The requested crate does not exist</body></html>",
         );
@@ -1116,11 +2271,34 @@ mod tests {
         // The full HTML body must never be echoed back.
         assert!(!msg.contains("<html"));
         assert!(!msg.contains("<!DOCTYPE"));
+
+        // Other upstreams are named by their host
+        let msg = summarize_http_status(
+            "https://docs.example.com/demo/latest/demo/",
+            reqwest::StatusCode::NOT_FOUND,
+            "",
+        );
+        assert!(msg.contains("does not exist on docs.example.com"));
+    }
+
+    #[test]
+    fn test_summarize_http_status_names_crates_io() {
+        for url in [
+            "https://crates.io/api/v1/crates?q=nope",
+            "https://index.crates.io/no/pe/nope",
+        ] {
+            let msg = summarize_http_status(url, reqwest::StatusCode::NOT_FOUND, "");
+            assert_eq!(
+                msg,
+                "HTTP 404 Not Found - the requested crate or version does not exist on crates.io. Verify the crate name and version."
+            );
+        }
     }
 
     #[test]
     fn test_summarize_http_status_hides_html_body() {
         let msg = summarize_http_status(
+            "https://docs.rs/serde/",
             reqwest::StatusCode::INTERNAL_SERVER_ERROR,
             "<html><body>boom</body></html>",
         );
@@ -1129,13 +2307,46 @@ mod tests {
 
     #[test]
     fn test_summarize_http_status_includes_short_plain_body() {
-        let msg = summarize_http_status(reqwest::StatusCode::BAD_GATEWAY, "upstream timeout");
+        let msg = summarize_http_status(
+            "https://docs.rs/serde/",
+            reqwest::StatusCode::BAD_GATEWAY,
+            "upstream timeout",
+        );
         assert_eq!(msg, "HTTP 502 Bad Gateway - upstream timeout");
     }
 
+    #[test]
+    fn test_summarize_http_status_crates_io_errors() {
+        let url = "https://crates.io/api/v1/crates/nope";
+        let msg = summarize_http_status(
+            url,
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            r#"{"errors":[{"detail":"You have made too many requests. Please try again after 2026-10-16 10:00:00 UTC."}]}"#,
+        );
+        assert_eq!(
+            msg,
+            "HTTP 429 Too Many Requests - You have made too many requests. Please try again after 2026-10-16 10:00:00 UTC. (rate limited by crates.io; wait before retrying)"
+        );
+
+        let msg = summarize_http_status(
+            url,
+            reqwest::StatusCode::NOT_FOUND,
+            r#"{"errors":[{"detail":"crate `nope` does not exist"}]}"#,
+        );
+        assert_eq!(msg, "HTTP 404 Not Found - crate `nope` does not exist");
+
+        // Other JSON bodies fall back to the plain snippet.
+        let msg = summarize_http_status(url, reqwest::StatusCode::BAD_REQUEST, r#"{"errors":[]}"#);
+        assert_eq!(msg, r#"HTTP 400 Bad Request - {"errors":[]}"#);
+    }
+
     #[test]
     fn test_summarize_http_status_empty_body() {
-        let msg = summarize_http_status(reqwest::StatusCode::SERVICE_UNAVAILABLE, "   ");
+        let msg = summarize_http_status(
+            "https://docs.rs/serde/",
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            "   ",
+        );
         assert_eq!(msg, "HTTP 503 Service Unavailable");
     }
 
@@ -1215,6 +2426,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_format_llm() {
+        assert_eq!(
+            parse_format("lookup_crate", Some("LLM"), DOC_FORMATS).unwrap(),
+            Format::Llm
+        );
+        assert!(parse_format("search_crates", Some("llm"), SEARCH_FORMATS).is_err());
+    }
+
     #[test]
     fn test_parse_format_trims_whitespace() {
         // Surrounding whitespace is tolerated (consistent with sort
@@ -1255,7 +2475,7 @@ mod tests {
         let err = parse_format("lookup_crate", Some("json"), DOC_FORMATS).unwrap_err();
         assert!(
             err.to_string()
-                .contains("This tool supports: markdown, text, html"),
+                .contains("This tool supports: markdown, text, html, llm"),
             "got: {err}"
         );
 
@@ -1283,6 +2503,7 @@ mod tests {
         assert_eq!(Format::Text.to_string(), "text");
         assert_eq!(Format::Html.to_string(), "html");
         assert_eq!(Format::Json.to_string(), "json");
+        assert_eq!(Format::Llm.to_string(), "llm");
     }
 
     #[test]
@@ -1307,30 +2528,6 @@ mod tests {
         std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
     }
 
-    #[test]
-    fn test_build_docs_item_url_without_version() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = build_docs_item_url("serde", None, "Serialize");
-        assert_eq!(url, "https://docs.rs/serde/?search=Serialize");
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
-    }
-
-    #[test]
-    fn test_build_docs_item_url_with_version() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = build_docs_item_url("serde", Some("1.0.0"), "Serialize");
-        assert_eq!(url, "https://docs.rs/serde/1.0.0/?search=Serialize");
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
-    }
-
-    #[test]
-    fn test_build_docs_item_url_encodes_special_chars() {
-        std::env::set_var("CRATES_DOCS_DOCS_RS_URL", "https://docs.rs");
-        let url = build_docs_item_url("std", None, "collections::HashMap");
-        assert!(url.contains("collections%3A%3AHashMap"));
-        std::env::remove_var("CRATES_DOCS_DOCS_RS_URL");
-    }
-
     #[test]
     fn test_build_crates_io_search_url_defaults() {
         std::env::set_var("CRATES_DOCS_CRATES_IO_URL", "https://crates.io");
@@ -1353,6 +2550,14 @@ mod tests {
         std::env::remove_var("CRATES_DOCS_CRATES_IO_URL");
     }
 
+    #[test]
+    fn test_build_crates_io_index_url_prefixes() {
+        assert!(build_crates_io_index_url("a").ends_with("/1/a"));
+        assert!(build_crates_io_index_url("io").ends_with("/2/io"));
+        assert!(build_crates_io_index_url("Syn").ends_with("/3/s/syn"));
+        assert!(build_crates_io_index_url("serde").ends_with("/se/rd/serde"));
+    }
+
     #[test]
     fn test_build_crates_io_search_url_encodes_query() {
         std::env::set_var("CRATES_DOCS_CRATES_IO_URL", "https://crates.io");
@@ -0,0 +1,161 @@
+//! Output format negotiation
+//!
+//! Picks the default output format for a session from what the client
+//! declared in its `initialize` request. Chat UIs render markdown, while
+//! programmatic clients (SDK scripts, test harnesses) are better served by
+//! structured JSON. A `format` argument on the request always wins; the
+//! negotiated default only fills in when it is omitted, and only for tools
+//! that support it (see [`super::parse_format`]).
+//!
+//! A client can state its preference explicitly through an experimental
+//! capability, which takes precedence over the name-based heuristics:
+//!
+//! ```json
+//! { "capabilities": { "experimental": { "crates-docs": { "format": "json" } } } }
+//! ```
+//!
+//! Like project profiles, the negotiated format is carried in a task-local
+//! set by the handler (see [`with_default_format`]).
+
+use super::Format;
+use rust_mcp_sdk::schema::InitializeRequestParams;
+use std::future::Future;
+
+/// Experimental capability key a client can use to declare its preference
+pub const CAPABILITY_KEY: &str = "crates-docs";
+
+/// Client name words of chat UIs and editors that render markdown
+const CHAT_CLIENT_HINTS: &[&str] = &[
+    "claude", "cursor", "vscode", "visual", "windsurf", "zed", "cline", "continue", "chatgpt",
+    "copilot", "cherry", "goose",
+];
+
+/// Client name words of programmatic clients that consume structured data
+const PROGRAMMATIC_CLIENT_HINTS: &[&str] = &["sdk", "test", "tests", "script", "harness"];
+
+tokio::task_local! {
+    static DEFAULT_FORMAT: Format;
+}
+
+/// Run `future` with `format` as the default for requests that omit `format`
+pub async fn with_default_format<F: Future>(format: Format, future: F) -> F::Output {
+    DEFAULT_FORMAT.scope(format, future).await
+}
+
+/// Default format of the request currently being served
+#[must_use]
+pub fn session_default() -> Format {
+    DEFAULT_FORMAT.try_with(|f| *f).unwrap_or_default()
+}
+
+/// Preferred output format for a client, based on its `initialize` request
+///
+/// Without client information (e.g. before initialization completed) this is
+/// markdown, the format every tool supports.
+#[must_use]
+pub fn preferred_format(client: Option<&InitializeRequestParams>) -> Format {
+    let Some(client) = client else {
+        return Format::default();
+    };
+
+    let declared = client
+        .capabilities
+        .experimental
+        .as_ref()
+        .and_then(|experimental| experimental.get(CAPABILITY_KEY))
+        .and_then(|capability| capability.get("format"))
+        .and_then(serde_json::Value::as_str)
+        .and_then(super::format_from_name);
+    if let Some(format) = declared {
+        return format;
+    }
+
+    // Match whole words so e.g. "optimized" does not count as "zed"
+    let name = client.client_info.name.to_lowercase();
+    let has_word = |hints: &[&str]| {
+        name.split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| hints.contains(&word))
+    };
+    if has_word(CHAT_CLIENT_HINTS) {
+        Format::Markdown
+    } else if has_word(PROGRAMMATIC_CLIENT_HINTS) {
+        Format::Json
+    } else {
+        Format::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(name: &str, capabilities: &serde_json::Value) -> InitializeRequestParams {
+        serde_json::from_value(serde_json::json!({
+            "capabilities": capabilities,
+            "clientInfo": { "name": name, "version": "1.0.0" },
+            "protocolVersion": "2025-06-18"
+        }))
+        .expect("valid initialize params")
+    }
+
+    #[test]
+    fn test_preferred_format_from_client_name() {
+        assert_eq!(preferred_format(None), Format::Markdown);
+        assert_eq!(
+            preferred_format(Some(&client("claude-ai", &serde_json::json!({})))),
+            Format::Markdown
+        );
+        assert_eq!(
+            preferred_format(Some(&client("Visual Studio Code", &serde_json::json!({})))),
+            Format::Markdown
+        );
+        assert_eq!(
+            preferred_format(Some(&client("python-sdk-client", &serde_json::json!({})))),
+            Format::Json
+        );
+        assert_eq!(
+            preferred_format(Some(&client("optimized-agent", &serde_json::json!({})))),
+            Format::Markdown
+        );
+    }
+
+    #[test]
+    fn test_preferred_format_declared_capability_wins() {
+        let caps = serde_json::json!({ "experimental": { "crates-docs": { "format": "text" } } });
+        assert_eq!(
+            preferred_format(Some(&client("claude-ai", &caps))),
+            Format::Text
+        );
+
+        // An unknown value is ignored rather than rejected
+        let caps = serde_json::json!({ "experimental": { "crates-docs": { "format": "xml" } } });
+        assert_eq!(
+            preferred_format(Some(&client("test-runner", &caps))),
+            Format::Json
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_format_uses_session_default() {
+        use super::super::{parse_format, DOC_FORMATS, REPORT_FORMATS};
+
+        assert_eq!(session_default(), Format::Markdown);
+        with_default_format(Format::Json, async {
+            assert_eq!(
+                parse_format("resolve_version", None, REPORT_FORMATS).unwrap(),
+                Format::Json
+            );
+            // Tools that cannot produce JSON fall back to markdown
+            assert_eq!(
+                parse_format("lookup_crate", None, DOC_FORMATS).unwrap(),
+                Format::Markdown
+            );
+            // An explicit format overrides the session default
+            assert_eq!(
+                parse_format("resolve_version", Some("text"), REPORT_FORMATS).unwrap(),
+                Format::Text
+            );
+        })
+        .await;
+    }
+}
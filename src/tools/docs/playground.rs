@@ -0,0 +1,601 @@
+//! Rust Playground execution tool
+//!
+//! Compiles, and optionally runs, a code snippet on the Rust Playground and
+//! returns the compiler and program output. The snippet is either given
+//! directly or picked from a crate's documentation examples, in which case it
+//! is wrapped in `fn main` the way rustdoc wraps doctests.
+//!
+//! The Playground only provides the most downloaded crates (and their
+//! dependencies), always at the version it ships, so snippets using other
+//! crates fail to resolve them.
+
+#![allow(missing_docs)]
+
+use super::examples::DocExample;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "run_doc_example";
+
+/// Playground endpoint that builds a crate and, for binaries, runs it
+const PLAYGROUND_EXECUTE_URL: &str = "https://play.rust-lang.org/execute";
+
+/// Maximum accepted snippet size in bytes
+const MAX_CODE_LEN: usize = 64 * 1024;
+
+const CHANNELS: &[&str] = &["stable", "beta", "nightly"];
+const EDITIONS: &[&str] = &["2015", "2018", "2021", "2024"];
+const DEFAULT_EDITION: &str = "2021";
+
+/// Compiler messages meaning a crate is not available on the Playground
+const MISSING_CRATE_MARKERS: &[&str] = &[
+    "can't find crate for",
+    "unresolved import",
+    "E0432",
+    "E0433",
+];
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "run_doc_example",
+    title = "Run Documentation Example",
+    description = "Compile and run Rust code on the Rust Playground and return the compiler output and program output. Pass code directly, or crate_name (plus optional item_path and example_index) to run one of that crate's documentation examples. Use mode=build to only check that code compiles. Only the Playground's bundled popular crates can be used as dependencies.",
+    destructive_hint = false,
+    idempotent_hint = false,
+    open_world_hint = true,
+    read_only_hint = true,
+    icons = [
+        (src = "https://play.rust-lang.org/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://play.rust-lang.org/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `run_doc_example` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct RunDocExampleTool {
+    /// Code to run; takes precedence over a documentation example
+    #[json_schema(
+        title = "Code",
+        description = "Rust code to run. Code without fn main is wrapped like a doctest. If omitted, a documentation example of crate_name is run"
+    )]
+    pub code: Option<String>,
+
+    /// Crate whose documentation example is run
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate whose documentation example to run when code is not given, e.g.: serde_json, regex"
+    )]
+    pub crate_name: Option<String>,
+
+    /// Item path whose examples are used; the crate front page when omitted
+    #[json_schema(
+        title = "Item Path",
+        description = "Optional item path whose examples to use, e.g.: serde_json::Value. Uses the crate front page if not specified"
+    )]
+    pub item_path: Option<String>,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version used to pick the documentation example. The Playground always compiles against its own bundled version"
+    )]
+    pub version: Option<String>,
+
+    /// 1-based index of the Rust example on the page (defaults to 1)
+    #[json_schema(
+        title = "Example Index",
+        description = "1-based index among the page's Rust examples, in the order extract_doc_examples lists them (default: 1)",
+        minimum = 1
+    )]
+    pub example_index: Option<u32>,
+
+    /// "run" (default) or "build"
+    #[json_schema(
+        title = "Mode",
+        description = "run (default): build and run the program; build: only compile it",
+        default = "run"
+    )]
+    pub mode: Option<String>,
+
+    /// Toolchain channel (defaults to stable)
+    #[json_schema(
+        title = "Channel",
+        description = "Toolchain channel: stable (default), beta, nightly",
+        default = "stable"
+    )]
+    pub channel: Option<String>,
+
+    /// Rust edition (defaults to 2021)
+    #[json_schema(
+        title = "Edition",
+        description = "Rust edition: 2015, 2018, 2021 (default), 2024",
+        default = "2021"
+    )]
+    pub edition: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured run result)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Request body of the Playground `execute` endpoint
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecuteRequest<'a> {
+    channel: &'a str,
+    mode: &'a str,
+    edition: &'a str,
+    crate_type: &'a str,
+    tests: bool,
+    code: &'a str,
+    backtrace: bool,
+}
+
+/// Response of the Playground `execute` endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecuteResponse {
+    success: bool,
+    #[serde(default)]
+    exit_detail: String,
+    #[serde(default)]
+    stdout: String,
+    #[serde(default)]
+    stderr: String,
+}
+
+/// Outcome of a Playground run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaygroundRun {
+    /// Documented item the example comes from, `None` for a given snippet
+    pub item: Option<String>,
+    pub mode: String,
+    pub channel: String,
+    pub edition: String,
+    /// Code sent to the Playground, after doctest wrapping
+    pub code: String,
+    pub success: bool,
+    pub exit_detail: String,
+    /// Compiler output
+    pub stderr: String,
+    /// Program output
+    pub stdout: String,
+    /// Caveats worth knowing when reading the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Wrap a snippet without `fn main` the way rustdoc wraps doctests
+///
+/// Leading crate attributes stay at the top level, and an example ending in
+/// `Ok::<(), E>(())` runs inside a function returning `Result`.
+fn wrap_doctest(code: &str) -> String {
+    if code.contains("fn main") {
+        return code.to_string();
+    }
+    let mut attributes = String::new();
+    let mut body = Vec::new();
+    for line in code.lines() {
+        if body.is_empty() && line.trim_start().starts_with("#![") {
+            attributes.push_str(line);
+            attributes.push('\n');
+        } else {
+            body.push(line);
+        }
+    }
+    let body = body.join("\n");
+
+    let returns_result = body.contains("Ok::<(),") && body.trim_end().ends_with("(())");
+    if returns_result {
+        format!(
+            "#![allow(unused)]\n{attributes}fn main() {{\n    fn _inner() -> Result<(), impl core::fmt::Debug> {{\n{body}\n    }}\n    _inner().unwrap()\n}}\n"
+        )
+    } else {
+        format!("#![allow(unused)]\n{attributes}fn main() {{\n{body}\n}}\n")
+    }
+}
+
+/// Pick the `index`-th (1-based) Rust example
+fn select_example(examples: Vec<DocExample>, index: usize) -> Result<DocExample, String> {
+    let mut rust_examples: Vec<DocExample> = examples
+        .into_iter()
+        .filter(|e| e.language.as_deref().is_none_or(|l| l == "rust"))
+        .collect();
+    match rust_examples.len() {
+        0 => Err("No Rust code examples found on this documentation page".to_string()),
+        count if index == 0 || index > count => Err(format!(
+            "example_index {index} is out of range: the page has {count} Rust example(s)"
+        )),
+        _ => Ok(rust_examples.swap_remove(index - 1)),
+    }
+}
+
+/// Notes explaining a result that may not mean what it seems
+fn run_notes(example: Option<&DocExample>, crate_name: Option<&str>, stderr: &str) -> Vec<String> {
+    let mut notes = Vec::new();
+    if let Some(example) = example {
+        let attributes = &example.attributes;
+        if attributes.iter().any(|a| a == "ignore") {
+            notes.push(
+                "The example is marked `ignore`: it is not expected to compile or run.".to_string(),
+            );
+        }
+        if attributes.iter().any(|a| a == "compile_fail") {
+            notes.push(
+                "The example is marked `compile_fail`: a compile error is the expected outcome."
+                    .to_string(),
+            );
+        }
+        if attributes.iter().any(|a| a == "should_panic") {
+            notes.push(
+                "The example is marked `should_panic`: a panic is the expected outcome."
+                    .to_string(),
+            );
+        }
+        notes.push(
+            "Lines hidden in the rendered documentation are not included, which can make an example fail to compile."
+                .to_string(),
+        );
+    }
+    if let Some(crate_name) = crate_name {
+        let crate_ident = crate_name.replace('-', "_");
+        let missing = MISSING_CRATE_MARKERS.iter().any(|m| stderr.contains(m))
+            && stderr.contains(crate_ident.as_str());
+        if missing {
+            notes.push(format!(
+                "`{crate_name}` may not be available on the Playground, which only provides the most downloaded crates."
+            ));
+        } else {
+            notes.push(format!(
+                "The Playground compiles against its own version of `{crate_name}`, which may differ from the documented version."
+            ));
+        }
+    }
+    notes
+}
+
+fn format_run(run: &PlaygroundRun, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let source = run.item.as_deref().unwrap_or("snippet");
+    let verdict = if run.success { "success" } else { "failure" };
+    let settings = format!("{} · {} · edition {}", run.mode, run.channel, run.edition);
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Playground {}: `{source}`\n", run.mode).unwrap();
+        writeln!(output, "**Result:** {verdict} ({})\n", run.exit_detail).unwrap();
+        writeln!(output, "{settings}\n").unwrap();
+        for note in &run.notes {
+            writeln!(output, "> {note}").unwrap();
+        }
+        if !run.notes.is_empty() {
+            writeln!(output).unwrap();
+        }
+        writeln!(output, "## Code\n\n```rust\n{}\n```\n", run.code.trim_end()).unwrap();
+        writeln!(
+            output,
+            "## Compiler output\n\n```text\n{}\n```\n",
+            run.stderr.trim_end()
+        )
+        .unwrap();
+        if run.mode == "run" {
+            writeln!(
+                output,
+                "## Program output\n\n```text\n{}\n```",
+                run.stdout.trim_end()
+            )
+            .unwrap();
+        }
+    } else {
+        writeln!(output, "Playground {}: {source}", run.mode).unwrap();
+        writeln!(output, "Result: {verdict} ({})", run.exit_detail).unwrap();
+        writeln!(output, "{}\n", settings.replace(" · ", ", ")).unwrap();
+        for note in &run.notes {
+            writeln!(output, "Note: {}", note.replace('`', "")).unwrap();
+        }
+        if !run.notes.is_empty() {
+            writeln!(output).unwrap();
+        }
+        writeln!(output, "--- code ---\n{}\n", run.code.trim_end()).unwrap();
+        writeln!(
+            output,
+            "--- compiler output ---\n{}\n",
+            run.stderr.trim_end()
+        )
+        .unwrap();
+        if run.mode == "run" {
+            writeln!(output, "--- program output ---\n{}", run.stdout.trim_end()).unwrap();
+        }
+    }
+    output
+}
+
+/// Validate an optional choice parameter, returning it or `default`
+fn parse_choice<'a>(
+    name: &str,
+    value: Option<&'a str>,
+    allowed: &[&str],
+    default: &'a str,
+) -> Result<&'a str, CallToolError> {
+    let value = value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(default);
+    if allowed.contains(&value) {
+        Ok(value)
+    } else {
+        Err(CallToolError::invalid_arguments(
+            TOOL_NAME,
+            Some(format!(
+                "Invalid {name} '{value}'. Expected one of: {}",
+                allowed.join(", ")
+            )),
+        ))
+    }
+}
+
+/// Implementation of the Playground execution tool
+pub struct RunDocExampleToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl RunDocExampleToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn execute_on_playground(
+        &self,
+        request: &ExecuteRequest<'_>,
+    ) -> Result<ExecuteResponse, CallToolError> {
+        let body = serde_json::to_string(request).map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+        })?;
+        let response = self
+            .service
+            .client()
+            .post(PLAYGROUND_EXECUTE_URL)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] HTTP request failed: {e}"))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(CallToolError::from_message(format!(
+                "[{TOOL_NAME}] Playground request failed: {}",
+                super::summarize_http_status(PLAYGROUND_EXECUTE_URL, status, &body)
+            )));
+        }
+        response.json().await.map_err(|e| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for RunDocExampleToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        RunDocExampleTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: RunDocExampleTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let mode = parse_choice("mode", params.mode.as_deref(), &["run", "build"], "run")?;
+        let channel = parse_choice("channel", params.channel.as_deref(), CHANNELS, "stable")?;
+        let edition = parse_choice(
+            "edition",
+            params.edition.as_deref(),
+            EDITIONS,
+            DEFAULT_EDITION,
+        )?;
+        let crate_name = params
+            .crate_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty());
+        if let Some(crate_name) = crate_name {
+            super::validate_crate_name(TOOL_NAME, crate_name)?;
+        }
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let item_path = params
+            .item_path
+            .as_deref()
+            .map(|p| p.trim().trim_end_matches('!'))
+            .filter(|p| !p.is_empty());
+        if let Some(item_path) = item_path {
+            super::validate_item_path(TOOL_NAME, item_path)?;
+        }
+
+        let code = params.code.as_deref().filter(|c| !c.trim().is_empty());
+        let (code, example) = match (code, crate_name) {
+            (Some(code), _) => (code.to_string(), None),
+            (None, Some(crate_name)) => {
+                let version = params.version.as_deref().map(super::normalize_version);
+                let version = self
+                    .service
+                    .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+                    .await?;
+                let examples =
+                    super::examples::ExtractDocExamplesToolImpl::new(self.service.clone())
+                        .fetch_examples(crate_name, item_path, version.as_deref())
+                        .await?;
+                let index = params.example_index.unwrap_or(1) as usize;
+                let example = select_example(examples, index)
+                    .map_err(|e| CallToolError::invalid_arguments(TOOL_NAME, Some(e)))?;
+                (example.code.clone(), Some(example))
+            }
+            (None, None) => {
+                return Err(CallToolError::invalid_arguments(
+                    TOOL_NAME,
+                    Some("Either code or crate_name must be provided".to_string()),
+                ));
+            }
+        };
+        if code.len() > MAX_CODE_LEN {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Code is too long (maximum {MAX_CODE_LEN} bytes)")),
+            ));
+        }
+
+        let code = wrap_doctest(&code);
+        let request = ExecuteRequest {
+            channel,
+            mode: "debug",
+            edition,
+            // The Playground only builds library crates
+            crate_type: if mode == "build" { "lib" } else { "bin" },
+            tests: false,
+            code: &code,
+            backtrace: false,
+        };
+        let response = self.execute_on_playground(&request).await?;
+
+        let run = PlaygroundRun {
+            item: example.as_ref().map(|e| e.item.clone()),
+            mode: mode.to_string(),
+            channel: channel.to_string(),
+            edition: edition.to_string(),
+            notes: run_notes(example.as_ref(), crate_name, &response.stderr),
+            code,
+            success: response.success,
+            exit_detail: response.exit_detail,
+            stderr: response.stderr,
+            stdout: response.stdout,
+        };
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&run).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_run(&run, false),
+            _ => format_run(&run, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for RunDocExampleToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(code: &str, language: Option<&str>, attributes: &[&str]) -> DocExample {
+        DocExample {
+            item: "demo::Thing".to_string(),
+            language: language.map(str::to_string),
+            attributes: attributes.iter().map(|a| (*a).to_string()).collect(),
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_wrap_doctest() {
+        let program = "fn main() {\n    println!(\"hi\");\n}";
+        assert_eq!(wrap_doctest(program), program);
+
+        let wrapped = wrap_doctest("#![feature(test)]\nlet x = 1;\nassert_eq!(x, 1);");
+        assert!(
+            wrapped.starts_with("#![allow(unused)]\n#![feature(test)]\nfn main() {\nlet x = 1;")
+        );
+        assert!(wrapped.ends_with("assert_eq!(x, 1);\n}\n"));
+
+        let wrapped =
+            wrap_doctest("let v: i32 = \"1\".parse()?;\nOk::<(), std::num::ParseIntError>(())");
+        assert!(wrapped.contains("fn _inner() -> Result<(), impl core::fmt::Debug>"));
+        assert!(wrapped.contains("_inner().unwrap()"));
+    }
+
+    #[test]
+    fn test_select_example_skips_non_rust() {
+        let examples = vec![
+            example("[dependencies]", Some("toml"), &[]),
+            example("let a = 1;", Some("rust"), &[]),
+            example("let b = 2;", None, &[]),
+        ];
+        assert_eq!(
+            select_example(examples.clone(), 1).unwrap().code,
+            "let a = 1;"
+        );
+        assert_eq!(
+            select_example(examples.clone(), 2).unwrap().code,
+            "let b = 2;"
+        );
+        let err = select_example(examples.clone(), 3).unwrap_err();
+        assert!(err.contains("2 Rust example(s)"), "{err}");
+        assert!(select_example(examples, 0).is_err());
+        assert!(select_example(Vec::new(), 1).is_err());
+    }
+
+    #[test]
+    fn test_run_notes() {
+        let doc = example("", Some("rust"), &["compile_fail"]);
+        let notes = run_notes(Some(&doc), Some("my-crate"), "");
+        assert!(notes.iter().any(|n| n.contains("compile_fail")));
+        assert!(notes.iter().any(|n| n.contains("hidden")));
+        assert!(notes.iter().any(|n| n.contains("its own version")));
+
+        let stderr = "error[E0432]: unresolved import `my_crate`";
+        let notes = run_notes(None, Some("my-crate"), stderr);
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].contains("may not be available"), "{notes:?}");
+
+        assert!(run_notes(None, None, stderr).is_empty());
+    }
+
+    #[test]
+    fn test_format_run() {
+        let run = PlaygroundRun {
+            item: None,
+            mode: "build".to_string(),
+            channel: "stable".to_string(),
+            edition: "2021".to_string(),
+            code: "fn f() {}".to_string(),
+            success: true,
+            exit_detail: "Exited with status 0".to_string(),
+            stderr: "Compiling playground".to_string(),
+            stdout: String::new(),
+            notes: Vec::new(),
+        };
+        let markdown = format_run(&run, true);
+        assert!(markdown.starts_with("# Playground build: `snippet`"));
+        assert!(markdown.contains("**Result:** success (Exited with status 0)"));
+        assert!(!markdown.contains("Program output"));
+
+        let text = format_run(&run, false);
+        assert!(text.contains("build, stable, edition 2021"));
+        assert!(!text.contains('`'), "{text}");
+    }
+}
@@ -0,0 +1,466 @@
+//! Project profiles
+//!
+//! A project profile is a set of crate→version pins registered by a client,
+//! typically parsed from the project's `Cargo.lock`. Profiles are kept per MCP
+//! session: while one is registered, lookups that omit `version` resolve to
+//! the pinned version instead of the latest release, so answers match what
+//! the user actually builds against.
+//!
+//! The session of the request being served is carried in a task-local set by
+//! the handler (see [`with_session`]). Transports without sessions (stdio)
+//! share a single default profile, which matches their one-client-per-process
+//! model.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+const TOOL_NAME: &str = "set_project_profile";
+
+/// Session key used when the transport does not provide a session id
+const DEFAULT_SESSION: &str = "default";
+
+/// Maximum number of profiles kept; the least recently updated is evicted
+const MAX_PROFILES: usize = 256;
+
+/// Maximum number of pins in one profile
+const MAX_PINS: usize = 10_000;
+
+/// Maximum accepted `Cargo.lock` size
+pub(super) const MAX_LOCKFILE_BYTES: usize = 4 * 1024 * 1024;
+
+/// How many pins are listed in markdown/text output
+const MAX_LISTED_PINS: usize = 50;
+
+tokio::task_local! {
+    static SESSION: String;
+}
+
+/// Run `future` on behalf of the MCP session `session`
+///
+/// Profile lookups made while `future` runs use that session's pins.
+pub async fn with_session<F: Future>(session: Option<String>, future: F) -> F::Output {
+    SESSION.scope(session_key(session), future).await
+}
+
+/// Key of the per-session state of `session`; sessions without an ID
+/// (stdio) share one
+#[must_use]
+pub fn session_key(session: Option<String>) -> String {
+    session.unwrap_or_else(|| DEFAULT_SESSION.to_string())
+}
+
+/// Key of the session whose request is being served, if any
+#[must_use]
+pub fn scoped_session() -> Option<String> {
+    SESSION.try_with(Clone::clone).ok()
+}
+
+/// Session of the request currently being served
+fn current_session() -> String {
+    SESSION
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| DEFAULT_SESSION.to_string())
+}
+
+/// Pins are looked up by normalized name, so `serde_json` and `serde-json`
+/// refer to the same crate just as they do on docs.rs
+fn pin_key(crate_name: &str) -> String {
+    crate_name.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+struct Profile {
+    pins: BTreeMap<String, String>,
+    updated: Instant,
+}
+
+/// Per-session version pins
+#[derive(Default)]
+pub struct ProjectProfiles {
+    profiles: RwLock<HashMap<String, Profile>>,
+}
+
+impl ProjectProfiles {
+    /// Create an empty profile store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pinned version of `crate_name` for the current session
+    #[must_use]
+    pub fn pinned(&self, crate_name: &str) -> Option<String> {
+        let session = current_session();
+        let profiles = self.profiles.read().ok()?;
+        profiles
+            .get(&session)?
+            .pins
+            .get(&pin_key(crate_name))
+            .cloned()
+    }
+
+    /// All pins of the current session
+    #[must_use]
+    pub fn current(&self) -> BTreeMap<String, String> {
+        let session = current_session();
+        self.profiles
+            .read()
+            .ok()
+            .and_then(|profiles| profiles.get(&session).map(|p| p.pins.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Replace the current session's pins
+    pub fn set(&self, pins: BTreeMap<String, String>) {
+        let session = current_session();
+        let Ok(mut profiles) = self.profiles.write() else {
+            return;
+        };
+        if !profiles.contains_key(&session) && profiles.len() >= MAX_PROFILES {
+            let oldest = profiles
+                .iter()
+                .min_by_key(|(_, profile)| profile.updated)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                profiles.remove(&oldest);
+            }
+        }
+        profiles.insert(
+            session,
+            Profile {
+                pins,
+                updated: Instant::now(),
+            },
+        );
+    }
+
+    /// Remove the current session's profile, returning whether one existed
+    pub fn clear(&self) -> bool {
+        let session = current_session();
+        self.profiles
+            .write()
+            .is_ok_and(|mut profiles| profiles.remove(&session).is_some())
+    }
+}
+
+/// The parts of a `Cargo.lock` the tools use
+#[derive(Deserialize)]
+pub(super) struct CargoLock {
+    #[serde(default)]
+    pub(super) package: Vec<LockPackage>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct LockPackage {
+    pub(super) name: String,
+    pub(super) version: String,
+    /// `None` for workspace members
+    pub(super) source: Option<String>,
+}
+
+impl LockPackage {
+    /// Whether the package comes from a registry (not git or the workspace)
+    pub(super) fn is_registry(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+    }
+}
+
+/// Extract registry packages from a `Cargo.lock`
+///
+/// Workspace members and git dependencies are skipped since they have no
+/// published documentation. When a crate appears in several versions the
+/// highest one is pinned.
+///
+/// # Errors
+///
+/// Returns a message if the lockfile is not valid TOML
+pub fn parse_cargo_lock(content: &str) -> Result<BTreeMap<String, String>, String> {
+    let lock: CargoLock =
+        toml::from_str(content).map_err(|e| format!("Invalid Cargo.lock: {e}"))?;
+    let mut pins: BTreeMap<String, String> = BTreeMap::new();
+    for package in lock.package {
+        let from_registry = package.is_registry();
+        let Ok(version) = semver::Version::parse(&package.version) else {
+            continue;
+        };
+        if !from_registry {
+            continue;
+        }
+        let key = pin_key(&package.name);
+        let newer = pins
+            .get(&key)
+            .and_then(|current| semver::Version::parse(current).ok())
+            .is_none_or(|current| version > current);
+        if newer {
+            pins.insert(key, package.version);
+        }
+    }
+    Ok(pins)
+}
+
+/// Parse explicit `crate@version` (or `crate=version`) pins
+///
+/// # Errors
+///
+/// Returns a message naming the first malformed entry
+pub fn parse_pins(entries: &[String]) -> Result<BTreeMap<String, String>, String> {
+    let mut pins = BTreeMap::new();
+    for entry in entries {
+        let entry = entry.trim();
+        let (name, version) = entry
+            .split_once('@')
+            .or_else(|| entry.split_once('='))
+            .ok_or_else(|| format!("Invalid pin '{entry}'. Expected crate@version"))?;
+        let version = version.trim().trim_start_matches('v');
+        if name.trim().is_empty() {
+            return Err(format!("Invalid pin '{entry}': missing crate name"));
+        }
+        if semver::Version::parse(version).is_err() {
+            return Err(format!(
+                "Invalid pin '{entry}': '{version}' is not a complete version"
+            ));
+        }
+        pins.insert(pin_key(name), version.to_string());
+    }
+    Ok(pins)
+}
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "set_project_profile",
+    title = "Set Project Profile",
+    description = "Register the crate versions your project uses, from a Cargo.lock and/or explicit crate@version pins. For the rest of this session, lookup_crate, lookup_item and get_docsrs_build_status use the pinned version when no version is given. Call with no arguments to show the current profile, or with clear=true to remove it.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = false,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `set_project_profile` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct SetProjectProfileTool {
+    /// Contents of the project's Cargo.lock
+    #[json_schema(
+        title = "Cargo.lock",
+        description = "Full contents of the project's Cargo.lock. Registry packages are pinned; workspace members and git dependencies are ignored"
+    )]
+    pub cargo_lock: Option<String>,
+
+    /// Explicit pins, applied on top of the lockfile
+    #[json_schema(
+        title = "Pins",
+        description = "Explicit pins in crate@version form, e.g.: [\"serde@1.0.200\", \"tokio@1.40.0\"]. Applied after the Cargo.lock"
+    )]
+    pub pins: Option<Vec<String>>,
+
+    /// Remove the session's profile
+    #[json_schema(
+        title = "Clear",
+        description = "Set to true to remove the profile and go back to latest versions",
+        default = false
+    )]
+    pub clear: Option<bool>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (pinned versions)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+fn format_profile(pins: &BTreeMap<String, String>, action: &str, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# Project Profile\n\n{action}: {} pinned crates",
+            pins.len()
+        )
+        .unwrap();
+        if !pins.is_empty() {
+            output.push('\n');
+        }
+        for (name, version) in pins.iter().take(MAX_LISTED_PINS) {
+            writeln!(output, "- `{name}` {version}").unwrap();
+        }
+    } else {
+        writeln!(output, "{action}: {} pinned crates", pins.len()).unwrap();
+        for (name, version) in pins.iter().take(MAX_LISTED_PINS) {
+            writeln!(output, "{name} {version}").unwrap();
+        }
+    }
+    if pins.len() > MAX_LISTED_PINS {
+        writeln!(output, "... and {} more", pins.len() - MAX_LISTED_PINS).unwrap();
+    }
+    output
+}
+
+/// Implementation of the set project profile tool
+pub struct SetProjectProfileToolImpl {
+    /// Shared document service holding the profiles
+    service: Arc<super::DocService>,
+}
+
+impl SetProjectProfileToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for SetProjectProfileToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SetProjectProfileTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SetProjectProfileTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let invalid = |message: String| CallToolError::invalid_arguments(TOOL_NAME, Some(message));
+        let profiles = self.service.profiles();
+
+        let (pins, action) = if params.clear.unwrap_or(false) {
+            let action = if profiles.clear() {
+                "Profile cleared"
+            } else {
+                "No profile registered"
+            };
+            (BTreeMap::new(), action)
+        } else if params.cargo_lock.is_none() && params.pins.is_none() {
+            (profiles.current(), "Current profile")
+        } else {
+            let mut pins = BTreeMap::new();
+            if let Some(lock) = params.cargo_lock.as_deref() {
+                if lock.len() > MAX_LOCKFILE_BYTES {
+                    return Err(invalid(format!(
+                        "Cargo.lock is too large (max {MAX_LOCKFILE_BYTES} bytes)"
+                    )));
+                }
+                pins = parse_cargo_lock(lock).map_err(invalid)?;
+            }
+            if let Some(entries) = params.pins.as_deref() {
+                pins.extend(parse_pins(entries).map_err(invalid)?);
+            }
+            if pins.len() > MAX_PINS {
+                return Err(invalid(format!("Too many pins (max {MAX_PINS})")));
+            }
+            profiles.set(pins.clone());
+            (pins, "Profile registered")
+        };
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&pins).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_profile(&pins, action, false),
+            _ => format_profile(&pins, action, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for SetProjectProfileToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = ["serde_json"]
+
+[[package]]
+name = "serde_json"
+version = "1.0.120"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.70"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "forked"
+version = "0.3.0"
+source = "git+https://github.com/example/forked#abc123"
+"#;
+
+    #[test]
+    fn test_parse_cargo_lock() {
+        let pins = parse_cargo_lock(LOCK).unwrap();
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins["serde-json"], "1.0.120");
+        assert_eq!(pins["syn"], "2.0.70");
+        assert!(parse_cargo_lock("[[package]").is_err());
+    }
+
+    #[test]
+    fn test_parse_pins() {
+        let pins = parse_pins(&["Serde@1.0.200".to_string(), "tokio=v1.40.0".to_string()]).unwrap();
+        assert_eq!(pins["serde"], "1.0.200");
+        assert_eq!(pins["tokio"], "1.40.0");
+        assert!(parse_pins(&["serde".to_string()]).is_err());
+        assert!(parse_pins(&["serde@^1".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_profiles_are_per_session() {
+        let profiles = ProjectProfiles::new();
+        with_session(Some("a".to_string()), async {
+            profiles.set(parse_pins(&["serde@1.0.100".to_string()]).unwrap());
+        })
+        .await;
+
+        let pinned = with_session(Some("a".to_string()), async { profiles.pinned("serde") }).await;
+        assert_eq!(pinned.as_deref(), Some("1.0.100"));
+        let other = with_session(Some("b".to_string()), async { profiles.pinned("serde") }).await;
+        assert!(other.is_none());
+        assert!(profiles.pinned("serde").is_none());
+
+        let cleared = with_session(Some("a".to_string()), async { profiles.clear() }).await;
+        assert!(cleared);
+    }
+}
@@ -0,0 +1,615 @@
+//! Upstream documentation providers
+//!
+//! A [`DocsProvider`] knows where a documentation source serves rustdoc
+//! pages: the crate landing page, the per-item pages and the `all.html`
+//! index, plus the build reports of a docs.rs instance. [`super::DocService`]
+//! holds an ordered chain of providers and the tools try them in turn, so a
+//! deployment can put a private mirror in front of docs.rs (or replace
+//! docs.rs entirely) without touching the tools.
+//!
+//! Sources:
+//!
+//! - `docs_rs`: the rendered HTML of docs.rs (std-family crates from
+//!   doc.rust-lang.org)
+//! - `rustdoc_json`: the rustdoc JSON docs.rs publishes for each build,
+//!   rendered locally (see [`super::rustdoc_json`])
+//! - `local`: a `cargo doc` output directory
+//! - `mirror`: a private host with the docs.rs path layout
+//!
+//! The chain is configured through the `[docs]` section, together with the
+//! default [content filters](super::filter):
+//!
+//! ```toml
+//! [docs]
+//! providers = [
+//!     { kind = "local", path = "/work/my-project/target/doc" },
+//!     { kind = "mirror", name = "internal", base_url = "https://docs.example.com" },
+//!     { kind = "docs_rs" },
+//!     { kind = "rustdoc_json" },
+//! ]
+//!
+//! [docs.filters]
+//! auto_trait_impls = "strip"
+//! blanket_impls = "collapse"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A source of rendered rustdoc HTML
+///
+/// Implementations only format URLs; fetching, caching and fallback between
+/// providers are handled by [`super::DocService`] and the tools.
+pub trait DocsProvider: Send + Sync {
+    /// Short name used in logs (e.g. `docs.rs`)
+    fn name(&self) -> &str;
+
+    /// Whether this provider can serve documentation for `crate_name`
+    fn supports(&self, crate_name: &str) -> bool {
+        let _ = crate_name;
+        true
+    }
+
+    /// URL of the crate's documentation landing page
+    fn crate_url(&self, crate_name: &str, version: Option<&str>) -> String;
+
+    /// URL of the crate's documentation to show to users
+    ///
+    /// Defaults to [`crate_url`](Self::crate_url); providers whose pages are
+    /// not meant for browsers point to a rendered copy instead.
+    fn docs_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        self.crate_url(crate_name, version)
+    }
+
+    /// URL under the crate details path of a docs.rs instance
+    /// (`/crate/{name}/{version}/{suffix}`), e.g. `status.json` or
+    /// `builds.json`
+    ///
+    /// `None` if the provider has no build server.
+    fn crate_details_url(&self, crate_name: &str, version: &str, suffix: &str) -> Option<String> {
+        let _ = (crate_name, version, suffix);
+        None
+    }
+
+    /// URL of the rustdoc directory of the crate's root module (ends in `/`)
+    fn root_url(&self, crate_name: &str, version: Option<&str>) -> String;
+
+    /// Candidate item page URLs, in probing order
+    fn item_url_candidates(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        item_path: &str,
+    ) -> Vec<String> {
+        super::item_url_candidates_under(&self.root_url(crate_name, version), crate_name, item_path)
+    }
+
+    /// URL of the crate's `all.html` item index
+    fn all_items_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        format!("{}all.html", self.root_url(crate_name, version))
+    }
+
+    /// Absolute URL for an href taken from the crate's `all.html` index
+    fn item_url_from_href(&self, crate_name: &str, version: Option<&str>, href: &str) -> String {
+        format!("{}{href}", self.root_url(crate_name, version))
+    }
+}
+
+/// docs.rs, with std-family crates served from doc.rust-lang.org
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocsRsProvider;
+
+impl DocsProvider for DocsRsProvider {
+    fn name(&self) -> &'static str {
+        "docs.rs"
+    }
+
+    fn crate_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        super::build_docs_url(crate_name, version)
+    }
+
+    fn root_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        super::crate_root_url(crate_name, version)
+    }
+
+    fn crate_details_url(&self, crate_name: &str, version: &str, suffix: &str) -> Option<String> {
+        Some(super::build_docs_rs_crate_url(crate_name, version, suffix))
+    }
+}
+
+/// The rustdoc JSON of docs.rs builds
+///
+/// Pages are addressed below the document (`{base}/crate/{name}/{version}/
+/// json.gz/{page}`) and rendered by [`super::DocService`], so only the
+/// document is downloaded, once for all pages of a crate. std-family crates
+/// have no JSON on docs.rs and are left to the other providers.
+#[derive(Debug, Clone)]
+pub struct RustdocJsonProvider {
+    name: String,
+    base_url: String,
+}
+
+impl RustdocJsonProvider {
+    /// Create a provider for the docs.rs instance at `base_url`
+    #[must_use]
+    pub fn new(name: impl Into<String>, base_url: &str) -> Self {
+        Self {
+            name: name.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl DocsProvider for RustdocJsonProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, crate_name: &str) -> bool {
+        !super::is_rust_std_crate(crate_name)
+    }
+
+    fn crate_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        format!("{}index.html", self.root_url(crate_name, version))
+    }
+
+    fn root_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        let version = version.unwrap_or("latest");
+        format!("{}/crate/{crate_name}/{version}/json.gz/", self.base_url)
+    }
+
+    fn docs_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        match version {
+            Some(ver) => format!("{}/{crate_name}/{ver}/", self.base_url),
+            None => format!("{}/{crate_name}/", self.base_url),
+        }
+    }
+
+    fn crate_details_url(&self, crate_name: &str, version: &str, suffix: &str) -> Option<String> {
+        Some(format!(
+            "{}/crate/{crate_name}/{version}/{suffix}",
+            self.base_url
+        ))
+    }
+}
+
+/// A host serving rustdoc output with the docs.rs path layout
+///
+/// Suits private docs.rs instances and static mirrors of `cargo doc` output
+/// organised as `{base}/{crate}/{version}/{crate_lib_name}/`. std-family
+/// crates are left to the other providers.
+#[derive(Debug, Clone)]
+pub struct MirrorProvider {
+    name: String,
+    base_url: String,
+}
+
+impl MirrorProvider {
+    /// Create a mirror provider rooted at `base_url`
+    #[must_use]
+    pub fn new(name: impl Into<String>, base_url: &str) -> Self {
+        Self {
+            name: name.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl DocsProvider for MirrorProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, crate_name: &str) -> bool {
+        !super::is_rust_std_crate(crate_name)
+    }
+
+    fn crate_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        match version {
+            Some(ver) => format!("{}/{crate_name}/{ver}/", self.base_url),
+            None => format!("{}/{crate_name}/", self.base_url),
+        }
+    }
+
+    fn root_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        super::docs_rs_layout_root_url(&self.base_url, crate_name, version)
+    }
+
+    fn crate_details_url(&self, crate_name: &str, version: &str, suffix: &str) -> Option<String> {
+        Some(format!(
+            "{}/crate/{crate_name}/{version}/{suffix}",
+            self.base_url
+        ))
+    }
+}
+
+/// Locally built `cargo doc` output
+///
+/// Serves pages from a `target/doc` directory through `file://` URLs, which
+/// [`super::DocService`] reads from disk. rustdoc writes every crate of the
+/// build (the workspace and its dependencies) to `{dir}/{crate_lib_name}/`,
+/// with a single version each, so the requested version is ignored: the
+/// answer is whatever was built, i.e. what the workspace compiles against.
+/// Crates missing from the directory are left to the next provider.
+#[derive(Debug, Clone)]
+pub struct LocalProvider {
+    name: String,
+    doc_dir: PathBuf,
+    crates: Vec<String>,
+}
+
+impl LocalProvider {
+    /// Create a provider reading the rustdoc output in `doc_dir`
+    ///
+    /// A relative `doc_dir` is resolved against the current directory.
+    #[must_use]
+    pub fn new(name: impl Into<String>, doc_dir: impl AsRef<Path>) -> Self {
+        let doc_dir = doc_dir.as_ref();
+        Self {
+            name: name.into(),
+            doc_dir: std::path::absolute(doc_dir).unwrap_or_else(|_| doc_dir.to_path_buf()),
+            crates: Vec::new(),
+        }
+    }
+
+    /// Only serve the listed crates (e.g. unpublished or patched ones)
+    ///
+    /// An empty list serves every crate found in the directory.
+    #[must_use]
+    pub fn with_crates(mut self, crates: &[String]) -> Self {
+        self.crates = crates
+            .iter()
+            .map(|name| lib_name(name).to_ascii_lowercase())
+            .collect();
+        self
+    }
+}
+
+/// Directory rustdoc writes a crate to
+fn lib_name(crate_name: &str) -> String {
+    crate_name.trim().replace('-', "_")
+}
+
+impl DocsProvider for LocalProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, crate_name: &str) -> bool {
+        let krate = lib_name(crate_name);
+        if super::is_rust_std_crate(crate_name)
+            || (!self.crates.is_empty() && !self.crates.contains(&krate.to_ascii_lowercase()))
+        {
+            return false;
+        }
+        self.doc_dir.join(&krate).join("index.html").is_file()
+    }
+
+    fn crate_url(&self, crate_name: &str, version: Option<&str>) -> String {
+        format!("{}index.html", self.root_url(crate_name, version))
+    }
+
+    fn root_url(&self, crate_name: &str, _version: Option<&str>) -> String {
+        let dir = self.doc_dir.join(lib_name(crate_name));
+        url::Url::from_directory_path(&dir)
+            .map_or_else(|()| format!("file://{}/", dir.display()), String::from)
+    }
+}
+
+/// Provider chain used when none is configured: docs.rs only
+#[must_use]
+pub fn default_providers() -> Vec<Arc<dyn DocsProvider>> {
+    vec![Arc::new(DocsRsProvider)]
+}
+
+/// A single entry of the `[docs] providers` chain
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// Public docs.rs (std-family crates via doc.rust-lang.org)
+    DocsRs,
+    /// A host using the docs.rs path layout
+    Mirror {
+        /// Base URL, e.g. `https://docs.example.com`
+        base_url: String,
+        /// Name used in logs (defaults to the base URL)
+        #[serde(default)]
+        name: Option<String>,
+    },
+    /// The rustdoc JSON of docs.rs builds, rendered locally
+    RustdocJson {
+        /// Base URL of the docs.rs instance (defaults to docs.rs)
+        #[serde(default)]
+        base_url: Option<String>,
+        /// Name used in logs (defaults to `rustdoc-json`)
+        #[serde(default)]
+        name: Option<String>,
+    },
+    /// A local `cargo doc` output directory
+    Local {
+        /// Path of the `target/doc` directory
+        path: String,
+        /// Name used in logs (defaults to the path)
+        #[serde(default)]
+        name: Option<String>,
+        /// Crates served from it; empty serves every crate it contains
+        #[serde(default)]
+        crates: Vec<String>,
+    },
+}
+
+/// Documentation source configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the provider chain is built once when
+/// the server starts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DocsConfig {
+    /// Providers tried in order for each lookup
+    #[serde(default = "default_provider_configs")]
+    pub providers: Vec<ProviderConfig>,
+    /// Content filters applied unless a request overrides them
+    #[serde(default)]
+    pub filters: super::filter::ContentFilters,
+    /// Prompt-injection hardening applied to fetched pages
+    #[serde(default)]
+    pub sanitize: super::sanitize::SanitizeLevel,
+    /// Backends `search_crates` queries, tried in order
+    #[serde(default = "super::search_backend::default_search_backends")]
+    pub search_backends: Vec<super::search_backend::SearchBackend>,
+}
+
+fn default_provider_configs() -> Vec<ProviderConfig> {
+    vec![ProviderConfig::DocsRs]
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        Self {
+            providers: default_provider_configs(),
+            filters: super::filter::ContentFilters::default(),
+            sanitize: super::sanitize::SanitizeLevel::default(),
+            search_backends: super::search_backend::default_search_backends(),
+        }
+    }
+}
+
+impl DocsConfig {
+    /// Validate the provider chain
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the chain or the search backends are
+    /// empty, a mirror or rustdoc JSON base URL is not an absolute http(s)
+    /// URL or a local
+    /// provider has no path
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.providers.is_empty() {
+            return Err(crate::error::Error::config(
+                "docs.providers",
+                "at least one provider is required",
+            ));
+        }
+        if self.search_backends.is_empty() {
+            return Err(crate::error::Error::config(
+                "docs.search_backends",
+                "at least one search backend is required",
+            ));
+        }
+        for provider in &self.providers {
+            match provider {
+                ProviderConfig::Mirror { base_url, .. }
+                | ProviderConfig::RustdocJson {
+                    base_url: Some(base_url),
+                    ..
+                } => {
+                    let valid = url::Url::parse(base_url)
+                        .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+                    if !valid {
+                        return Err(crate::error::Error::config(
+                            "docs.providers",
+                            format!(
+                                "invalid {} base_url '{base_url}', expected an http(s) URL",
+                                if matches!(provider, ProviderConfig::Mirror { .. }) {
+                                    "mirror"
+                                } else {
+                                    "rustdoc_json"
+                                }
+                            ),
+                        ));
+                    }
+                }
+                ProviderConfig::Local { path, .. } if path.trim().is_empty() => {
+                    return Err(crate::error::Error::config(
+                        "docs.providers",
+                        "local provider path must not be empty",
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the provider chain described by this configuration
+    #[must_use]
+    pub fn build_providers(&self) -> Vec<Arc<dyn DocsProvider>> {
+        self.providers
+            .iter()
+            .map(|provider| -> Arc<dyn DocsProvider> {
+                match provider {
+                    ProviderConfig::DocsRs => Arc::new(DocsRsProvider),
+                    ProviderConfig::Mirror { base_url, name } => Arc::new(MirrorProvider::new(
+                        name.clone().unwrap_or_else(|| base_url.clone()),
+                        base_url,
+                    )),
+                    ProviderConfig::RustdocJson { base_url, name } => {
+                        Arc::new(RustdocJsonProvider::new(
+                            name.clone().unwrap_or_else(|| "rustdoc-json".to_string()),
+                            base_url.as_deref().unwrap_or(&super::docs_rs_base_url()),
+                        ))
+                    }
+                    ProviderConfig::Local { path, name, crates } => Arc::new(
+                        LocalProvider::new(name.clone().unwrap_or_else(|| path.clone()), path)
+                            .with_crates(crates),
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_provider_urls() {
+        let mirror = MirrorProvider::new("internal", "https://docs.example.com/");
+        assert_eq!(
+            mirror.crate_url("my-crate", Some("1.2.0")),
+            "https://docs.example.com/my-crate/1.2.0/"
+        );
+        assert_eq!(
+            mirror.all_items_url("my-crate", None),
+            "https://docs.example.com/my-crate/latest/my_crate/all.html"
+        );
+        assert_eq!(
+            mirror.item_url_candidates("my-crate", None, "my_crate::io::Reader")[0],
+            "https://docs.example.com/my-crate/latest/my_crate/io/struct.Reader.html"
+        );
+        assert!(!mirror.supports("std"));
+        assert!(mirror.supports("serde"));
+    }
+
+    #[test]
+    fn test_rustdoc_json_provider_urls() {
+        let json = RustdocJsonProvider::new("rustdoc-json", "https://docs.rs/");
+        assert_eq!(
+            json.crate_url("my-crate", Some("1.2.0")),
+            "https://docs.rs/crate/my-crate/1.2.0/json.gz/index.html"
+        );
+        assert_eq!(
+            json.all_items_url("my-crate", None),
+            "https://docs.rs/crate/my-crate/latest/json.gz/all.html"
+        );
+        assert_eq!(
+            json.item_url_candidates("my-crate", None, "my_crate::io::Reader")[0],
+            "https://docs.rs/crate/my-crate/latest/json.gz/io/struct.Reader.html"
+        );
+        // Users are pointed to the rendered documentation
+        assert_eq!(
+            json.docs_url("my-crate", Some("1.2.0")),
+            "https://docs.rs/my-crate/1.2.0/"
+        );
+        assert_eq!(
+            json.crate_details_url("my-crate", "1.2.0", "status.json")
+                .as_deref(),
+            Some("https://docs.rs/crate/my-crate/1.2.0/status.json")
+        );
+        assert!(!json.supports("std"));
+
+        let page = json.crate_url("my-crate", None);
+        let (document, page) = super::super::rustdoc_json::split_url(&page).unwrap();
+        assert_eq!(document, "https://docs.rs/crate/my-crate/latest/json.gz");
+        assert_eq!(page, "index.html");
+    }
+
+    #[test]
+    fn test_local_provider_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("my_crate")).unwrap();
+        std::fs::write(dir.path().join("my_crate/index.html"), "<html></html>").unwrap();
+        let local = LocalProvider::new("local", dir.path());
+
+        let root = local.root_url("my-crate", Some("1.2.0"));
+        assert!(root.starts_with("file://"));
+        assert!(root.ends_with("/my_crate/"));
+        assert_eq!(
+            local.crate_url("my-crate", None),
+            format!("{root}index.html")
+        );
+        assert_eq!(
+            local.item_url_candidates("my-crate", None, "my_crate::io::Reader")[0],
+            format!("{root}io/struct.Reader.html")
+        );
+        assert!(local.supports("my-crate"));
+        assert!(!local.supports("serde"));
+        assert!(!local.supports("std"));
+
+        let restricted = local.with_crates(&["other".to_string()]);
+        assert!(!restricted.supports("my-crate"));
+        assert_eq!(restricted.crate_details_url("my-crate", "1.2.0", ""), None);
+    }
+
+    #[test]
+    fn test_docs_config_validation() {
+        assert!(DocsConfig::default().validate().is_ok());
+        assert!(DocsConfig {
+            providers: vec![],
+            ..DocsConfig::default()
+        }
+        .validate()
+        .is_err());
+        let bad_mirror = DocsConfig {
+            providers: vec![ProviderConfig::Mirror {
+                base_url: "ftp://mirror".to_string(),
+                name: None,
+            }],
+            ..DocsConfig::default()
+        };
+        assert!(bad_mirror.validate().is_err());
+        let empty_local = DocsConfig {
+            providers: vec![ProviderConfig::Local {
+                path: " ".to_string(),
+                name: None,
+                crates: vec![],
+            }],
+            ..DocsConfig::default()
+        };
+        assert!(empty_local.validate().is_err());
+        let bad_json = DocsConfig {
+            providers: vec![ProviderConfig::RustdocJson {
+                base_url: Some("docs.rs".to_string()),
+                name: None,
+            }],
+            ..DocsConfig::default()
+        };
+        assert!(bad_json.validate().is_err());
+        assert!(DocsConfig {
+            search_backends: vec![],
+            ..DocsConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_docs_config_from_toml() {
+        let config: DocsConfig = toml::from_str(
+            r#"
+            providers = [
+                { kind = "mirror", base_url = "https://docs.example.com" },
+                { kind = "docs_rs" },
+                { kind = "rustdoc_json" },
+            ]
+            search_backends = ["lib_rs", "crates_io"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.search_backends,
+            [
+                super::super::search_backend::SearchBackend::LibRs,
+                super::super::search_backend::SearchBackend::CratesIo
+            ]
+        );
+        let providers = config.build_providers();
+        assert_eq!(providers.len(), 3);
+        assert_eq!(providers[0].name(), "https://docs.example.com");
+        assert_eq!(providers[1].name(), "docs.rs");
+        assert_eq!(providers[2].name(), "rustdoc-json");
+    }
+}
@@ -0,0 +1,135 @@
+//! Repository README fallback
+//!
+//! When docs.rs has no documentation for a crate version (the build failed,
+//! is still queued, or the crate is not a library), its page only says so.
+//! Rather than returning that page as documentation, `lookup_crate` serves
+//! the README of the crate's GitHub or GitLab repository, as listed on
+//! crates.io.
+
+use rust_mcp_sdk::schema::CallToolError;
+use std::sync::{Arc, LazyLock};
+
+/// Messages docs.rs shows instead of documentation
+static MISSING_BUILD_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"(?i)docs\.rs\s+failed\s+to\s+build|has\s+not\s+(?:been\s+)?built\s+yet|is\s+not\s+a\s+library|build\s+is\s+queued",
+    )
+    .expect("hardcoded valid regex pattern")
+});
+
+/// Whether a docs.rs crate page reports missing documentation instead of
+/// rendering it
+pub(super) fn is_missing_build_page(html: &str) -> bool {
+    // Rendered rustdoc pages always have a main content section
+    !html.contains("id=\"main-content\"") && MISSING_BUILD_REGEX.is_match(html)
+}
+
+/// Raw README URL for a GitHub or GitLab repository URL
+///
+/// Only the `owner/repo` part of the URL is used, so links into a
+/// subdirectory or a `.git` clone URL work too.
+pub(super) fn readme_url(repository: &str) -> Option<String> {
+    let url = url::Url::parse(repository.trim()).ok()?;
+    let host = url.host_str()?.trim_start_matches("www.");
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    if repo.is_empty() {
+        return None;
+    }
+    match host {
+        "github.com" => Some(format!(
+            "https://raw.githubusercontent.com/{owner}/{repo}/HEAD/README.md"
+        )),
+        "gitlab.com" => Some(format!(
+            "https://gitlab.com/{owner}/{repo}/-/raw/HEAD/README.md"
+        )),
+        _ => None,
+    }
+}
+
+/// Fetch the repository README standing in for a crate's documentation
+///
+/// Returns `None` when the crate has no GitHub/GitLab repository or the
+/// repository has no `README.md`. The README is returned with a note that
+/// it comes from the repository's default branch.
+pub(super) async fn fetch_readme(
+    service: &super::DocService,
+    crate_name: &str,
+    version: Option<&str>,
+    tool_name: &str,
+) -> Result<Option<Arc<str>>, CallToolError> {
+    let doc_cache = service.doc_cache();
+    if let Some(cached) = doc_cache.get_readme(crate_name, version).await {
+        return Ok(Some(cached));
+    }
+
+    let Some(summary) = super::batch::fetch_crate_summary(service, crate_name, tool_name).await?
+    else {
+        return Ok(None);
+    };
+    let Some(repository) = summary.repository.as_deref() else {
+        return Ok(None);
+    };
+    let Some(url) = readme_url(repository) else {
+        return Ok(None);
+    };
+    let Some(readme) = service.fetch_html_optional(&url, Some(tool_name)).await? else {
+        return Ok(None);
+    };
+
+    let name = match version {
+        Some(version) => format!("{crate_name}@{version}"),
+        None => crate_name.to_string(),
+    };
+    let content: Arc<str> = format!(
+        "> **Note**: docs.rs has no documentation for `{name}`, showing the README of {repository} instead. \
+         It is taken from the repository's default branch and may not match this version.\n\n{}",
+        readme.trim()
+    )
+    .into();
+
+    if let Err(e) = doc_cache
+        .set_readme(crate_name, version, Arc::clone(&content))
+        .await
+    {
+        tracing::warn!("[{tool_name}] failed to cache README (continuing uncached): {e}");
+    }
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readme_url_for_supported_hosts() {
+        assert_eq!(
+            readme_url("https://github.com/serde-rs/serde").as_deref(),
+            Some("https://raw.githubusercontent.com/serde-rs/serde/HEAD/README.md")
+        );
+        assert_eq!(
+            readme_url("https://github.com/tokio-rs/tokio.git").as_deref(),
+            Some("https://raw.githubusercontent.com/tokio-rs/tokio/HEAD/README.md")
+        );
+        assert_eq!(
+            readme_url("https://gitlab.com/group/project/-/tree/main/sub").as_deref(),
+            Some("https://gitlab.com/group/project/-/raw/HEAD/README.md")
+        );
+        assert_eq!(readme_url("https://github.com/serde-rs"), None);
+        assert_eq!(readme_url("https://example.com/a/b"), None);
+        assert_eq!(readme_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_is_missing_build_page() {
+        assert!(is_missing_build_page(
+            "<div class=\"warning\">docs.rs failed to build demo-0.1.0<br>Please check the build logs</div>"
+        ));
+        assert!(is_missing_build_page("<p>demo-0.1.0 is not a library.</p>"));
+        assert!(!is_missing_build_page(
+            "<section id=\"main-content\">docs.rs failed to build is quoted here</section>"
+        ));
+        assert!(!is_missing_build_page("<p>Crate demo</p>"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Package registry endpoints
+//!
+//! The metadata tools (search, categories, versions, dependency trees, crate
+//! summaries, ...) read a registry's web API and sparse index. crates.io is
+//! the default; the `[registry]` section points them at an alternative
+//! registry such as Kellnr or Artifactory instead:
+//!
+//! ```toml
+//! [registry]
+//! name = "internal"
+//! api_url = "https://registry.example.com"
+//! index_url = "sparse+https://registry.example.com/api/v1/crates/"
+//! token = "..."
+//! ```
+//!
+//! The token (or the `CRATES_DOCS_REGISTRY_TOKEN` environment variable) is
+//! sent as the `Authorization` header, as Cargo does, and only on requests
+//! to the registry's own endpoints. Documentation lookups are unaffected:
+//! use a `mirror` or `local` [provider](super::provider) for the docs of
+//! private crates.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Registry installed at server startup
+static ACTIVE_REGISTRY: OnceLock<RegistryConfig> = OnceLock::new();
+
+/// `Cargo.lock` source of crates.io packages resolved through the git index
+const CRATES_IO_GIT_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+/// Environment variable holding the registry token
+pub const REGISTRY_TOKEN_ENV: &str = "CRATES_DOCS_REGISTRY_TOKEN";
+
+/// Package registry configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the registry is installed once when
+/// the server starts.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RegistryConfig {
+    /// Registry name, as used by `registry = "..."` in manifests
+    #[serde(default = "default_registry_name")]
+    pub name: String,
+    /// Base URL of the registry web API (serving `/api/v1/crates`)
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    /// Sparse index URL; a Cargo-style `sparse+` prefix is accepted
+    #[serde(default = "default_index_url")]
+    pub index_url: String,
+    /// Token sent as the `Authorization` header to the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl std::fmt::Debug for RegistryConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegistryConfig")
+            .field("name", &self.name)
+            .field("api_url", &self.api_url)
+            .field("index_url", &self.index_url)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+fn default_registry_name() -> String {
+    "crates-io".to_string()
+}
+
+fn default_api_url() -> String {
+    "https://crates.io".to_string()
+}
+
+fn default_index_url() -> String {
+    "https://index.crates.io".to_string()
+}
+
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            name: default_registry_name(),
+            api_url: default_api_url(),
+            index_url: default_index_url(),
+            token: None,
+        }
+    }
+}
+
+impl RegistryConfig {
+    /// Validate the endpoints
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the name is empty or an endpoint is
+    /// not an absolute http(s) URL
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(crate::error::Error::config(
+                "registry.name",
+                "must not be empty",
+            ));
+        }
+        for (field, value) in [
+            ("registry.api_url", self.api_url()),
+            ("registry.index_url", self.index_url()),
+        ] {
+            let valid =
+                url::Url::parse(&value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                return Err(crate::error::Error::config(
+                    field,
+                    format!("invalid URL '{value}', expected an http(s) URL"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether this is the public crates.io registry
+    #[must_use]
+    pub fn is_crates_io(&self) -> bool {
+        self.name == "crates-io"
+    }
+
+    /// API base URL, without a trailing `/`
+    #[must_use]
+    pub fn api_url(&self) -> String {
+        self.api_url.trim().trim_end_matches('/').to_string()
+    }
+
+    /// Sparse index base URL, without `sparse+` or a trailing `/`
+    #[must_use]
+    pub fn index_url(&self) -> String {
+        let url = self.index_url.trim();
+        url.strip_prefix("sparse+")
+            .unwrap_or(url)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Token to authenticate with, from the config or the environment
+    #[must_use]
+    pub fn token(&self) -> Option<String> {
+        self.token
+            .clone()
+            .or_else(|| std::env::var(REGISTRY_TOKEN_ENV).ok())
+            .filter(|token| !token.trim().is_empty())
+    }
+
+    /// Whether a `Cargo.lock` package `source` refers to this registry
+    #[must_use]
+    pub fn matches_lock_source(&self, source: &str) -> bool {
+        // crates.io is also reachable through its original git index
+        if self.is_crates_io() && source == CRATES_IO_GIT_SOURCE {
+            return true;
+        }
+        let Some(index) = source
+            .strip_prefix("sparse+")
+            .or_else(|| source.strip_prefix("registry+"))
+        else {
+            return false;
+        };
+        index.trim_end_matches('/') == self.index_url()
+    }
+
+    /// Whether `url` points at one of this registry's endpoints
+    fn serves(&self, url: &str) -> bool {
+        [self.api_url(), self.index_url()].iter().any(|base| {
+            url.strip_prefix(base.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Install the registry the metadata tools use
+///
+/// Only the first call takes effect; later calls are ignored, like the
+/// global HTTP client.
+pub fn install_registry(config: &RegistryConfig) {
+    if ACTIVE_REGISTRY.set(config.clone()).is_ok() && !config.is_crates_io() {
+        tracing::info!(
+            registry = %config.name,
+            api_url = %config.api_url(),
+            "Using alternative package registry"
+        );
+    }
+}
+
+/// The registry installed by [`install_registry`], if any
+#[must_use]
+pub fn active_registry() -> Option<&'static RegistryConfig> {
+    ACTIVE_REGISTRY.get()
+}
+
+/// Name of the registry in use (`crates-io` unless another is installed)
+#[must_use]
+pub fn registry_name() -> &'static str {
+    active_registry().map_or("crates-io", |registry| registry.name.as_str())
+}
+
+/// Whether a `Cargo.lock` package `source` refers to the registry in use
+#[must_use]
+pub fn is_registry_source(source: &str) -> bool {
+    match active_registry() {
+        Some(registry) => registry.matches_lock_source(source),
+        None => RegistryConfig::default().matches_lock_source(source),
+    }
+}
+
+/// `Authorization` header value for a request to `url`, if it targets the
+/// installed registry and a token is configured
+#[must_use]
+pub fn authorization_for(url: &str) -> Option<String> {
+    active_registry()
+        .filter(|registry| registry.serves(url))
+        .and_then(RegistryConfig::token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn internal() -> RegistryConfig {
+        RegistryConfig {
+            name: "internal".to_string(),
+            api_url: "https://registry.example.com/".to_string(),
+            index_url: "sparse+https://registry.example.com/api/v1/crates/".to_string(),
+            token: Some("secret".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_registry_endpoints_are_normalized() {
+        let registry = internal();
+        assert_eq!(registry.api_url(), "https://registry.example.com");
+        assert_eq!(
+            registry.index_url(),
+            "https://registry.example.com/api/v1/crates"
+        );
+        assert!(registry.validate().is_ok());
+        assert!(!registry.is_crates_io());
+        assert!(!format!("{registry:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_registry_serves_only_its_endpoints() {
+        let registry = internal();
+        assert!(registry.serves("https://registry.example.com/api/v1/crates?q=foo"));
+        assert!(!registry.serves("https://registry.example.com.evil.test/api"));
+        assert!(!registry.serves("https://docs.rs/serde/"));
+
+        assert!(registry.matches_lock_source("sparse+https://registry.example.com/api/v1/crates/"));
+        assert!(!registry.matches_lock_source("sparse+https://index.crates.io/"));
+        assert!(RegistryConfig::default().matches_lock_source(CRATES_IO_GIT_SOURCE));
+        assert!(!registry.matches_lock_source(CRATES_IO_GIT_SOURCE));
+    }
+
+    #[test]
+    fn test_registry_validation() {
+        assert!(RegistryConfig::default().validate().is_ok());
+        let bad = RegistryConfig {
+            api_url: "registry.example.com".to_string(),
+            ..RegistryConfig::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,546 @@
+//! Resolve version tool
+//!
+//! Resolves a semver requirement (`^1.2`, `~0.4`, `>=1, <2`, `latest`) to the
+//! concrete version Cargo would pick, using the crates.io sparse index. The
+//! lookup tools delegate to the same resolver through
+//! [`super::DocService::resolve_version_param`], so their `version`
+//! parameters accept ranges too.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "resolve_version";
+
+/// Requirements longer than this are not meaningful
+const MAX_REQUIREMENT_LEN: usize = 128;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "resolve_version",
+    title = "Resolve Crate Version",
+    description = "Resolve a semver requirement for a crate (e.g. ^1.2, ~0.4, >=1.0, <2.0, latest) to the concrete version Cargo would select, using the crates.io index. Yanked versions are skipped. Use this before looking up documentation for a version range.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `resolve_version` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct ResolveVersionTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to resolve, e.g.: serde, tokio, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Semver requirement (defaults to "latest")
+    #[json_schema(
+        title = "Requirement",
+        description = "Semver requirement, e.g.: ^1.2, ~0.4, 1.0, >=1.2, <2, latest (default). A complete version such as 1.0.100 is matched exactly",
+        default = "latest"
+    )]
+    pub requirement: Option<String>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured resolution result)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// One line of a sparse index file (only the fields used here)
+#[derive(Debug, Deserialize)]
+struct RawIndexEntry {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+    /// Features using newer syntax (`dep:`, `?/`), split out for old Cargo
+    #[serde(default)]
+    features2: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default)]
+    deps: Vec<RawIndexDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawIndexDep {
+    /// Dependency name as used in the manifest (the rename, if any)
+    name: String,
+    #[serde(default)]
+    optional: bool,
+}
+
+/// Compact form of an index entry, as cached
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct IndexEntry {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    features: Vec<String>,
+}
+
+impl From<RawIndexEntry> for IndexEntry {
+    fn from(raw: RawIndexEntry) -> Self {
+        let mut features: BTreeMap<String, Vec<String>> = raw.features;
+        features.extend(raw.features2.unwrap_or_default());
+        // An optional dependency is also a feature of the same name unless
+        // some feature refers to it with the `dep:` syntax.
+        let explicit_deps: BTreeSet<&str> = features
+            .values()
+            .flatten()
+            .filter_map(|value| value.strip_prefix("dep:"))
+            .collect();
+        let implicit: Vec<String> = raw
+            .deps
+            .iter()
+            .filter(|dep| dep.optional && !explicit_deps.contains(dep.name.as_str()))
+            .map(|dep| dep.name.clone())
+            .collect();
+        let mut names: BTreeSet<String> = features.into_keys().collect();
+        names.extend(implicit);
+        Self {
+            vers: raw.vers,
+            yanked: raw.yanked,
+            features: names.into_iter().collect(),
+        }
+    }
+}
+
+/// A published version of a crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexVersion {
+    /// The version number
+    pub version: Version,
+    /// Whether the version was yanked
+    pub yanked: bool,
+    /// Feature names, including those implied by optional dependencies
+    pub features: Vec<String>,
+}
+
+/// A parsed `version` argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSpec {
+    /// The newest stable release
+    Latest,
+    /// A complete version such as `1.0.100`
+    Exact(Version),
+    /// A Cargo requirement such as `^1.2` or `>=1, <2`
+    Requirement(VersionReq),
+}
+
+impl VersionSpec {
+    /// Parse a version argument, returning `None` if it is not valid semver
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if raw.is_empty() || raw.eq_ignore_ascii_case("latest") {
+            return Some(Self::Latest);
+        }
+        if let Ok(version) = Version::parse(raw) {
+            return Some(Self::Exact(version));
+        }
+        VersionReq::parse(raw).ok().map(Self::Requirement)
+    }
+}
+
+/// Outcome of resolving a requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionResolution {
+    /// Crate name
+    pub crate_name: String,
+    /// The requirement as given
+    pub requirement: String,
+    /// The concrete version selected
+    pub version: String,
+    /// Whether the selected version is yanked (only possible for exact versions)
+    pub yanked: bool,
+    /// Number of non-yanked versions satisfying the requirement
+    pub matching_versions: usize,
+    /// Number of versions ever published
+    pub total_versions: usize,
+    /// Newest stable, non-yanked version of the crate
+    pub latest_stable: Option<String>,
+}
+
+/// Parse a sparse index file, skipping lines that are not valid entries
+fn parse_index(body: &str) -> Vec<IndexEntry> {
+    body.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<RawIndexEntry>(line).ok())
+        .map(IndexEntry::from)
+        .collect()
+}
+
+fn to_versions(entries: Vec<IndexEntry>) -> Vec<IndexVersion> {
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            Version::parse(&entry.vers)
+                .ok()
+                .map(|version| IndexVersion {
+                    version,
+                    yanked: entry.yanked,
+                    features: entry.features,
+                })
+        })
+        .collect()
+}
+
+fn latest_stable(versions: &[IndexVersion]) -> Option<&IndexVersion> {
+    versions
+        .iter()
+        .filter(|v| !v.yanked && v.version.pre.is_empty())
+        .max_by(|a, b| a.version.cmp(&b.version))
+}
+
+/// Pick the version Cargo would select for `spec`
+///
+/// Yanked versions are never selected by a range; an exact version is
+/// returned even if yanked so the caller can report it. `Latest` prefers the
+/// newest stable release and falls back to the newest pre-release for crates
+/// that never had a stable one.
+#[must_use]
+pub fn select_version<'a>(
+    versions: &'a [IndexVersion],
+    spec: &VersionSpec,
+) -> Option<&'a IndexVersion> {
+    match spec {
+        VersionSpec::Latest => latest_stable(versions).or_else(|| {
+            versions
+                .iter()
+                .filter(|v| !v.yanked)
+                .max_by(|a, b| a.version.cmp(&b.version))
+        }),
+        VersionSpec::Exact(version) => versions.iter().find(|v| v.version == *version),
+        VersionSpec::Requirement(req) => versions
+            .iter()
+            .filter(|v| !v.yanked && req.matches(&v.version))
+            .max_by(|a, b| a.version.cmp(&b.version)),
+    }
+}
+
+fn count_matching(versions: &[IndexVersion], spec: &VersionSpec) -> usize {
+    versions
+        .iter()
+        .filter(|v| {
+            !v.yanked
+                && match spec {
+                    VersionSpec::Latest => true,
+                    VersionSpec::Exact(version) => v.version == *version,
+                    VersionSpec::Requirement(req) => req.matches(&v.version),
+                }
+        })
+        .count()
+}
+
+/// Fetch the published versions of a crate from the sparse index
+pub(super) async fn fetch_index_versions(
+    service: &super::DocService,
+    crate_name: &str,
+    tool_name: &str,
+) -> std::result::Result<Vec<IndexVersion>, CallToolError> {
+    if let Some(cached) = service.doc_cache().get_index_versions(crate_name).await {
+        if let Ok(entries) = serde_json::from_str::<Vec<IndexEntry>>(&cached) {
+            return Ok(to_versions(entries));
+        }
+    }
+
+    let url = super::build_crates_io_index_url(crate_name);
+    let Some(body) = service.fetch_html_optional(&url, Some(tool_name)).await? else {
+        return Err(super::crate_not_found(crate_name, None).into());
+    };
+    let entries = parse_index(&body);
+
+    // Only versions and feature names are cached; full index files carry
+    // dependency metadata for every release and can run to hundreds of
+    // kilobytes.
+    match serde_json::to_string(&entries) {
+        Ok(compact) => {
+            if let Err(e) = service
+                .doc_cache()
+                .set_index_versions(crate_name, compact)
+                .await
+            {
+                tracing::warn!(
+                    "[{tool_name}] failed to cache index versions (continuing uncached): {e}"
+                );
+            }
+        }
+        Err(e) => tracing::warn!("[{tool_name}] failed to serialize index versions: {e}"),
+    }
+
+    Ok(to_versions(entries))
+}
+
+/// Not-found error for a requirement no published version satisfies
+///
+/// The newest stable version is offered as the suggestion.
+pub(super) fn no_matching_version(
+    crate_name: &str,
+    requirement: &str,
+    versions: &[IndexVersion],
+) -> crate::error::Error {
+    crate::error::Error::not_found(
+        "version",
+        format!("{crate_name}@{requirement}"),
+        latest_stable(versions)
+            .map(|v| v.version.to_string())
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// Resolve `spec` for `crate_name` against the crates.io index
+///
+/// # Errors
+///
+/// Returns a not-found error if the crate does not exist or no published
+/// version satisfies the requirement, or an error if the index is unreachable.
+pub async fn resolve(
+    service: &super::DocService,
+    crate_name: &str,
+    requirement: &str,
+    spec: &VersionSpec,
+    tool_name: &str,
+) -> std::result::Result<VersionResolution, CallToolError> {
+    let versions = fetch_index_versions(service, crate_name, tool_name).await?;
+    let Some(selected) = select_version(&versions, spec) else {
+        return Err(no_matching_version(crate_name, requirement, &versions).into());
+    };
+
+    Ok(VersionResolution {
+        crate_name: crate_name.to_string(),
+        requirement: requirement.to_string(),
+        version: selected.version.to_string(),
+        yanked: selected.yanked,
+        matching_versions: count_matching(&versions, spec),
+        total_versions: versions.len(),
+        latest_stable: latest_stable(&versions).map(|v| v.version.to_string()),
+    })
+}
+
+fn format_resolution(resolution: &VersionResolution, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(
+            output,
+            "# {} `{}` → {}\n",
+            resolution.crate_name, resolution.requirement, resolution.version
+        )
+        .unwrap();
+        writeln!(output, "- **Resolved version**: {}", resolution.version).unwrap();
+        writeln!(
+            output,
+            "- **Matching versions**: {} of {} published",
+            resolution.matching_versions, resolution.total_versions
+        )
+        .unwrap();
+        if let Some(latest) = &resolution.latest_stable {
+            writeln!(output, "- **Latest stable**: {latest}").unwrap();
+        }
+        if resolution.yanked {
+            writeln!(output, "\n> ⚠️ This version has been yanked.").unwrap();
+        }
+    } else {
+        writeln!(
+            output,
+            "{} {} -> {}",
+            resolution.crate_name, resolution.requirement, resolution.version
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "Matching versions: {} of {} published",
+            resolution.matching_versions, resolution.total_versions
+        )
+        .unwrap();
+        if let Some(latest) = &resolution.latest_stable {
+            writeln!(output, "Latest stable: {latest}").unwrap();
+        }
+        if resolution.yanked {
+            writeln!(output, "Warning: this version has been yanked").unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the resolve version tool
+pub struct ResolveVersionToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl ResolveVersionToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for ResolveVersionToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        ResolveVersionTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: ResolveVersionTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        let crate_name = params.crate_name.trim();
+        if super::is_rust_std_crate(crate_name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "'{crate_name}' ships with the Rust toolchain and is not published to crates.io"
+                )),
+            ));
+        }
+
+        let requirement = params.requirement.as_deref().unwrap_or("latest").trim();
+        if requirement.len() > MAX_REQUIREMENT_LEN {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "requirement is too long (max {MAX_REQUIREMENT_LEN} characters)"
+                )),
+            ));
+        }
+        let requirement = super::normalize_version(requirement);
+        let Some(spec) = VersionSpec::parse(&requirement) else {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "Invalid requirement '{requirement}'. Expected a semver requirement such as ^1.2, ~0.4, >=1.0, <2.0 or latest"
+                )),
+            ));
+        };
+        let requirement = if requirement.is_empty() {
+            "latest".to_string()
+        } else {
+            requirement
+        };
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let resolution = resolve(&self.service, crate_name, &requirement, &spec, TOOL_NAME).await?;
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&resolution).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_resolution(&resolution, false),
+            _ => format_resolution(&resolution, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for ResolveVersionToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = r#"{"name":"demo","vers":"0.9.0","deps":[],"cksum":"x","features":{},"yanked":false}
+{"name":"demo","vers":"1.0.0","deps":[],"cksum":"x","features":{},"yanked":false}
+{"name":"demo","vers":"1.2.0","deps":[],"cksum":"x","features":{},"yanked":false}
+{"name":"demo","vers":"1.3.0","deps":[],"cksum":"x","features":{},"yanked":true}
+{"name":"demo","vers":"2.0.0-beta.1","deps":[],"cksum":"x","features":{},"yanked":false}
+not json
+"#;
+
+    fn versions() -> Vec<IndexVersion> {
+        to_versions(parse_index(INDEX))
+    }
+
+    fn pick(raw: &str) -> Option<String> {
+        let versions = versions();
+        select_version(&versions, &VersionSpec::parse(raw).unwrap()).map(|v| v.version.to_string())
+    }
+
+    #[test]
+    fn test_version_spec_parse() {
+        assert_eq!(VersionSpec::parse("latest"), Some(VersionSpec::Latest));
+        assert_eq!(VersionSpec::parse(""), Some(VersionSpec::Latest));
+        assert!(matches!(
+            VersionSpec::parse("1.2.3"),
+            Some(VersionSpec::Exact(_))
+        ));
+        assert!(matches!(
+            VersionSpec::parse("^1.2"),
+            Some(VersionSpec::Requirement(_))
+        ));
+        assert!(matches!(
+            VersionSpec::parse(">=1, <2"),
+            Some(VersionSpec::Requirement(_))
+        ));
+        assert_eq!(VersionSpec::parse("stable"), None);
+    }
+
+    #[test]
+    fn test_select_version_skips_yanked_and_prereleases() {
+        assert_eq!(versions().len(), 5);
+        assert_eq!(pick("latest").as_deref(), Some("1.2.0"));
+        assert_eq!(pick("^1").as_deref(), Some("1.2.0"));
+        assert_eq!(pick("~1.0").as_deref(), Some("1.0.0"));
+        assert_eq!(pick("<1").as_deref(), Some("0.9.0"));
+        assert_eq!(pick(">=2.0.0-beta").as_deref(), Some("2.0.0-beta.1"));
+        assert_eq!(pick("^3"), None);
+    }
+
+    #[test]
+    fn test_index_entry_features_include_optional_deps() {
+        let line = r#"{"name":"demo","vers":"1.0.0","deps":[{"name":"serde","optional":true},{"name":"log","optional":true},{"name":"libc","optional":false}],"features":{"default":["std"],"std":[]},"features2":{"logging":["dep:log"]},"yanked":false}"#;
+        let versions = to_versions(parse_index(line));
+        assert_eq!(
+            versions[0].features,
+            vec!["default", "logging", "serde", "std"]
+        );
+    }
+
+    #[test]
+    fn test_select_exact_version_reports_yanked() {
+        let versions = versions();
+        let selected = select_version(&versions, &VersionSpec::parse("1.3.0").unwrap()).unwrap();
+        assert!(selected.yanked);
+        assert_eq!(
+            count_matching(&versions, &VersionSpec::parse("1.3.0").unwrap()),
+            0
+        );
+    }
+}
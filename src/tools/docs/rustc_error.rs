@@ -0,0 +1,191 @@
+//! Rustc error index tool
+//!
+//! Looks up a compiler error code (`E0308`) in the rustc error index on
+//! doc.rust-lang.org and returns its explanation, including the erroneous and
+//! corrected code examples.
+
+#![allow(missing_docs)]
+
+use crate::tools::docs::html;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "lookup_rustc_error";
+
+/// The error index is an mdbook with one page per code
+const ERROR_INDEX_BASE_URL: &str = "https://doc.rust-lang.org/error_codes";
+
+static MAIN_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("main").expect("hardcoded valid selector"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "lookup_rustc_error",
+    title = "Lookup Rustc Error Code",
+    description = "Get the explanation of a Rust compiler error code (e.g. E0308) from the rustc error index, with the erroneous and corrected code examples. Use this when debugging compile errors such as error[E0502].",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://doc.rust-lang.org/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://doc.rust-lang.org/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `lookup_rustc_error` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct LookupRustcErrorTool {
+    /// Error code (e.g., "E0308", "0308", "error[E0502]")
+    #[json_schema(
+        title = "Error Code",
+        description = "Compiler error code, e.g.: E0308, e0502, 0382, error[E0277]"
+    )]
+    pub code: String,
+
+    /// Output format: "markdown", "text", "html", or "llm" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), html, llm (compact markdown for language models: no decoration, deduplicated links, signatures first, examples last)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Normalize an error code to the `E` + four digits form used by the index
+///
+/// Accepts `E0308`, `e308`, `0308`, `308` and `error[E0308]`.
+fn normalize_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    let code = code
+        .strip_prefix("error[")
+        .and_then(|c| c.strip_suffix(']'))
+        .unwrap_or(code);
+    let digits = code
+        .strip_prefix(['E', 'e'])
+        .unwrap_or(code)
+        .trim_start_matches('0');
+    if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("E{digits:0>4}"))
+}
+
+/// The explanation part of an error index page, as a rustdoc-like page so
+/// the shared extractors apply
+fn error_page_content(page: &str) -> Option<String> {
+    let document = Html::parse_document(page);
+    let main = document.select(&MAIN_SELECTOR).next()?;
+    Some(format!(
+        "<html><body><section id=\"main-content\">{}</section></body></html>",
+        main.inner_html()
+    ))
+}
+
+/// Implementation of the rustc error lookup tool
+pub struct LookupRustcErrorToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl LookupRustcErrorToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn fetch_error_html(&self, code: &str) -> Result<Arc<str>, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache.get_rustc_error(code).await {
+            return Ok(cached);
+        }
+
+        let url = format!("{ERROR_INDEX_BASE_URL}/{code}.html");
+        let Some(page) = self
+            .service
+            .fetch_html_optional(&url, Some(TOOL_NAME))
+            .await?
+        else {
+            return Err(crate::error::Error::not_found("error code", code, Vec::new()).into());
+        };
+        let content = error_page_content(&page).ok_or_else(|| {
+            CallToolError::from_message(format!(
+                "[{TOOL_NAME}] Unexpected error index page layout for {code}"
+            ))
+        })?;
+        let content: Arc<str> = self.service.sanitize_page(content, TOOL_NAME).into();
+
+        if let Err(e) = doc_cache.set_rustc_error(code, Arc::clone(&content)).await {
+            tracing::warn!("[{TOOL_NAME}] failed to cache error explanation: {e}");
+        }
+        Ok(content)
+    }
+}
+
+#[async_trait]
+impl Tool for LookupRustcErrorToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        LookupRustcErrorTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: LookupRustcErrorTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format = super::parse_format(TOOL_NAME, params.format.as_deref(), super::DOC_FORMATS)?;
+        let Some(code) = normalize_code(&params.code) else {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "Invalid error code '{}'. Expected a code such as E0308",
+                    params.code
+                )),
+            ));
+        };
+
+        let page = self.fetch_error_html(&code).await?;
+        let content = match format {
+            super::Format::Text => html::extract_documentation_as_text(&page),
+            super::Format::Html => html::extract_documentation_html(&page),
+            super::Format::Llm => super::llm::compact(&html::extract_documentation(&page)),
+            _ => html::extract_documentation(&page),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for LookupRustcErrorToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_code() {
+        assert_eq!(normalize_code("E0308").as_deref(), Some("E0308"));
+        assert_eq!(normalize_code(" e308 ").as_deref(), Some("E0308"));
+        assert_eq!(normalize_code("0382").as_deref(), Some("E0382"));
+        assert_eq!(normalize_code("error[E0277]").as_deref(), Some("E0277"));
+        assert_eq!(normalize_code("E0").as_deref(), None);
+        assert_eq!(normalize_code("E12345").as_deref(), None);
+        assert_eq!(normalize_code("E03a8").as_deref(), None);
+        assert_eq!(normalize_code("../E0308").as_deref(), None);
+    }
+}
@@ -0,0 +1,920 @@
+//! Documentation pages rendered from rustdoc JSON
+//!
+//! docs.rs publishes the rustdoc JSON output of each build at
+//! `/crate/{name}/{version}/json.gz`. [`super::provider::RustdocJsonProvider`]
+//! addresses the pages of a crate below that document, as rustdoc would lay
+//! them out (`…/json.gz/index.html`, `…/json.gz/de/trait.Deserializer.html`,
+//! `…/json.gz/all.html`), and [`super::DocService`] renders them here as
+//! rustdoc-like HTML, so the tools parse them like any other page.
+//!
+//! Pages carry the documentation text and the item lists; signatures are not
+//! rendered, since rustdoc JSON describes them structurally.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::future::Future;
+use std::io::Read as _;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::cache::singleflight::InFlight;
+
+/// Path segments ending a rustdoc JSON document URL, before the page path
+const DOCUMENT_SUFFIXES: [&str; 2] = ["json.gz/", ".json/"];
+
+/// Documents kept after rendering, so probing several item pages of a crate
+/// downloads its document once
+const RECENT_DOCUMENTS: usize = 4;
+
+/// Split a page URL into the URL of its rustdoc JSON document and the page
+/// path below it (empty for the crate root)
+#[must_use]
+pub fn split_url(url: &str) -> Option<(&str, &str)> {
+    DOCUMENT_SUFFIXES.iter().find_map(|suffix| {
+        let end = url.find(suffix)? + suffix.len();
+        Some((&url[..end - 1], &url[end..]))
+    })
+}
+
+/// Parse a rustdoc JSON document, gzip-compressed or not
+///
+/// The compression is detected from the body: hosts may serve `json.gz`
+/// with `Content-Encoding: gzip`, which the HTTP client already decodes.
+///
+/// # Errors
+///
+/// Returns a message if the body cannot be decompressed or is not JSON
+pub fn parse_document(body: &[u8]) -> Result<Value, String> {
+    let parsed = if body.starts_with(&[0x1f, 0x8b]) {
+        let mut json = Vec::new();
+        flate2::read::GzDecoder::new(body)
+            .read_to_end(&mut json)
+            .map_err(|e| format!("Failed to decompress rustdoc JSON: {e}"))?;
+        serde_json::from_slice(&json)
+    } else {
+        serde_json::from_slice(body)
+    };
+    parsed.map_err(|e| format!("Invalid rustdoc JSON: {e}"))
+}
+
+/// A fetched document, or `None` if the upstream has none (HTTP 404)
+type Fetched = Result<Option<Arc<Value>>, String>;
+
+/// Recently used rustdoc JSON documents
+///
+/// Concurrent requests of one document share its download, and the last
+/// [`RECENT_DOCUMENTS`] documents are kept for the pages requested next.
+#[derive(Default)]
+pub struct Documents {
+    in_flight: InFlight<Fetched>,
+    recent: Mutex<VecDeque<(String, Arc<Value>)>>,
+}
+
+impl Documents {
+    /// The document at `url`, fetched with `fetch` unless kept or in flight
+    ///
+    /// # Errors
+    ///
+    /// Returns the message of a failed fetch
+    pub async fn get<F, Fut>(&self, url: &str, fetch: F) -> Fetched
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Fetched>,
+    {
+        if let Some(document) = self.recent(url) {
+            return Ok(Some(document));
+        }
+        let fetched = self.in_flight.run(url, fetch).await?;
+        if let Some(document) = &fetched {
+            let mut recent = self.lock();
+            recent.retain(|(kept, _)| kept != url);
+            recent.push_front((url.to_string(), Arc::clone(document)));
+            recent.truncate(RECENT_DOCUMENTS);
+        }
+        Ok(fetched)
+    }
+
+    fn recent(&self, url: &str) -> Option<Arc<Value>> {
+        self.lock()
+            .iter()
+            .find(|(kept, _)| kept == url)
+            .map(|(_, document)| Arc::clone(document))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<(String, Arc<Value>)>> {
+        self.recent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Render the page at `page` (relative to the crate root) of a document
+///
+/// Returns `None` if the crate has no such page.
+#[must_use]
+pub fn render(document: &Value, page: &str) -> Option<String> {
+    let krate = Crate::new(document)?;
+    let page = page.split(['#', '?']).next().unwrap_or_default();
+    if page == "all.html" {
+        return Some(krate.all_items_page());
+    }
+    let module = page
+        .strip_suffix("index.html")
+        .or_else(|| (page.is_empty() || page.ends_with('/')).then_some(page));
+    if let Some(dir) = module {
+        let mut path = vec![krate.name.clone()];
+        path.extend(dir.split('/').filter(|s| !s.is_empty()).map(str::to_string));
+        let id = krate.find(&path, Kind::Module)?;
+        return Some(krate.item_page(id, Kind::Module, &path));
+    }
+
+    let (dir, file) = page.rsplit_once('/').unwrap_or(("", page));
+    let (prefix, name) = file.strip_suffix(".html")?.split_once('.')?;
+    let kind = Kind::from_file_prefix(prefix)?;
+    let mut path = vec![krate.name.clone()];
+    path.extend(dir.split('/').filter(|s| !s.is_empty()).map(str::to_string));
+    path.push(name.to_string());
+    let id = krate.find(&path, kind)?;
+    Some(krate.item_page(id, kind, &path))
+}
+
+/// Kinds of items that have their own page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Module,
+    Macro,
+    Struct,
+    Enum,
+    Union,
+    Primitive,
+    Trait,
+    TraitAlias,
+    Function,
+    TypeAlias,
+    Constant,
+    Static,
+    Attribute,
+    Derive,
+}
+
+impl Kind {
+    /// Kind of a `paths` entry (`ItemKind` of rustdoc JSON)
+    fn from_item_kind(kind: &str) -> Option<Self> {
+        Some(match kind {
+            "module" => Self::Module,
+            "macro" => Self::Macro,
+            "struct" => Self::Struct,
+            "enum" => Self::Enum,
+            "union" => Self::Union,
+            "primitive" => Self::Primitive,
+            "trait" => Self::Trait,
+            "trait_alias" => Self::TraitAlias,
+            "function" => Self::Function,
+            "type_alias" => Self::TypeAlias,
+            "constant" => Self::Constant,
+            "static" => Self::Static,
+            "proc_attribute" => Self::Attribute,
+            "proc_derive" => Self::Derive,
+            _ => return None,
+        })
+    }
+
+    /// Kind of an item of the `index`, from its `inner` variant
+    fn of_item(item: &Value) -> Option<Self> {
+        let (variant, inner) = item["inner"].as_object()?.iter().next()?;
+        match variant.as_str() {
+            "proc_macro" => match inner["kind"].as_str() {
+                Some("attr") => Some(Self::Attribute),
+                Some("derive") => Some(Self::Derive),
+                _ => Some(Self::Macro),
+            },
+            variant => Self::from_item_kind(variant),
+        }
+    }
+
+    fn from_file_prefix(prefix: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| *kind != Self::Module && kind.class() == prefix)
+    }
+
+    const ALL: [Self; 14] = [
+        Self::Module,
+        Self::Macro,
+        Self::Struct,
+        Self::Enum,
+        Self::Union,
+        Self::Primitive,
+        Self::Trait,
+        Self::TraitAlias,
+        Self::Function,
+        Self::TypeAlias,
+        Self::Constant,
+        Self::Static,
+        Self::Attribute,
+        Self::Derive,
+    ];
+
+    /// rustdoc's CSS class, also the file name prefix of the item's page
+    fn class(self) -> &'static str {
+        match self {
+            Self::Module => "mod",
+            Self::Macro => "macro",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Union => "union",
+            Self::Primitive => "primitive",
+            Self::Trait => "trait",
+            Self::TraitAlias => "traitalias",
+            Self::Function => "fn",
+            Self::TypeAlias => "type",
+            Self::Constant => "constant",
+            Self::Static => "static",
+            Self::Attribute => "attr",
+            Self::Derive => "derive",
+        }
+    }
+
+    /// Word in the page title, e.g. `Struct`
+    fn title(self) -> &'static str {
+        match self {
+            Self::Module => "Module",
+            Self::Macro => "Macro",
+            Self::Struct => "Struct",
+            Self::Enum => "Enum",
+            Self::Union => "Union",
+            Self::Primitive => "Primitive Type",
+            Self::Trait => "Trait",
+            Self::TraitAlias => "Trait Alias",
+            Self::Function => "Function",
+            Self::TypeAlias => "Type Alias",
+            Self::Constant => "Constant",
+            Self::Static => "Static",
+            Self::Attribute => "Attribute Macro",
+            Self::Derive => "Derive Macro",
+        }
+    }
+
+    /// Heading of the item list section, e.g. `Structs`
+    fn section(self) -> &'static str {
+        match self {
+            Self::Module => "Modules",
+            Self::Macro => "Macros",
+            Self::Struct => "Structs",
+            Self::Enum => "Enums",
+            Self::Union => "Unions",
+            Self::Primitive => "Primitive Types",
+            Self::Trait => "Traits",
+            Self::TraitAlias => "Trait Aliases",
+            Self::Function => "Functions",
+            Self::TypeAlias => "Type Aliases",
+            Self::Constant => "Constants",
+            Self::Static => "Statics",
+            Self::Attribute => "Attribute Macros",
+            Self::Derive => "Derive Macros",
+        }
+    }
+
+    /// Page of the item at `path` relative to the crate root
+    fn href(self, path: &[String]) -> String {
+        let modules = path
+            .get(1..path.len().saturating_sub(1))
+            .unwrap_or_default();
+        let name = path.last().map_or("", String::as_str);
+        if self == Self::Module {
+            return path
+                .get(1..)
+                .unwrap_or_default()
+                .iter()
+                .map(|segment| format!("{segment}/"))
+                .chain(std::iter::once("index.html".to_string()))
+                .collect();
+        }
+        modules
+            .iter()
+            .map(|segment| format!("{segment}/"))
+            .chain(std::iter::once(format!("{}.{name}.html", self.class())))
+            .collect()
+    }
+}
+
+/// The local crate of a rustdoc JSON document
+struct Crate<'a> {
+    document: &'a Value,
+    /// Library name, the first segment of item paths
+    name: String,
+}
+
+impl<'a> Crate<'a> {
+    fn new(document: &'a Value) -> Option<Self> {
+        let root = document["index"].get(id_key(&document["root"])?)?;
+        Some(Self {
+            name: root["name"].as_str()?.to_string(),
+            document,
+        })
+    }
+
+    fn item(&self, id: &Value) -> Option<&'a Value> {
+        self.document["index"].get(id_key(id)?)
+    }
+
+    /// Items of the crate with a page: their ID, kind and path
+    fn paths(&self) -> impl Iterator<Item = (&'a str, Kind, Vec<String>)> + 'a {
+        self.document["paths"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(_, summary)| summary["crate_id"].as_u64() == Some(0))
+            .filter_map(|(id, summary)| {
+                let kind = Kind::from_item_kind(summary["kind"].as_str()?)?;
+                let path = summary["path"]
+                    .as_array()?
+                    .iter()
+                    .map(|segment| segment.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()?;
+                Some((id.as_str(), kind, path))
+            })
+    }
+
+    /// ID of the item of `kind` at `path`
+    fn find(&self, path: &[String], kind: Kind) -> Option<&'a str> {
+        self.paths()
+            .find(|(_, found, found_path)| *found == kind && found_path == path)
+            .map(|(id, _, _)| id)
+    }
+
+    fn version(&self) -> Option<&'a str> {
+        self.document["crate_version"].as_str()
+    }
+
+    fn page(&self, title: &str, body: &str) -> String {
+        let mut html = String::from("<html><body>");
+        if let Some(version) = self.version() {
+            let _ = write!(
+                html,
+                "<div class=\"sidebar-crate\"><span class=\"version\">{}</span></div>",
+                escape(version)
+            );
+        }
+        let _ = write!(
+            html,
+            "<section id=\"main-content\"><h1>{title}</h1>{body}</section></body></html>"
+        );
+        html
+    }
+
+    fn all_items_page(&self) -> String {
+        let mut items: BTreeMap<Kind, Vec<(String, String)>> = BTreeMap::new();
+        for (_, kind, path) in self.paths() {
+            if kind == Kind::Module {
+                continue;
+            }
+            items
+                .entry(kind)
+                .or_default()
+                .push((path[1..].join("::"), kind.href(&path)));
+        }
+        let mut body = String::new();
+        for (kind, mut entries) in items {
+            entries.sort();
+            let _ = write!(
+                body,
+                "<h3 id=\"{}\">{}</h3><ul class=\"all-items\">",
+                kind.class(),
+                kind.section()
+            );
+            for (name, href) in entries {
+                let _ = write!(
+                    body,
+                    "<li><a href=\"{}\">{}</a></li>",
+                    escape(&href),
+                    escape(&name)
+                );
+            }
+            body.push_str("</ul>");
+        }
+        self.page("List of all items", &body)
+    }
+
+    fn item_page(&self, id: &str, kind: Kind, path: &[String]) -> String {
+        let item = self.document["index"].get(id).unwrap_or(&Value::Null);
+        let title = if kind == Kind::Module && path.len() == 1 {
+            format!("Crate <span>{}</span>", escape(&path[0]))
+        } else {
+            format!("{} <span>{}</span>", kind.title(), escape(&path.join("::")))
+        };
+
+        let mut body = docblock(item);
+        let inner = item["inner"]
+            .as_object()
+            .and_then(|inner| inner.values().next())
+            .unwrap_or(&Value::Null);
+        match kind {
+            Kind::Module => self.module_items(&inner["items"], &mut body),
+            Kind::Enum => {
+                self.members("Variants", "variant", &inner["variants"], &mut body);
+                self.implementations(&inner["impls"], &mut body);
+            }
+            Kind::Struct | Kind::Union | Kind::Primitive => {
+                self.implementations(&inner["impls"], &mut body);
+            }
+            Kind::Trait => self.trait_items(&inner["items"], &mut body),
+            _ => {}
+        }
+        self.page(&title, &body)
+    }
+
+    /// Item tables of a module, by kind
+    fn module_items(&self, ids: &Value, body: &mut String) {
+        let mut items: BTreeMap<Kind, Vec<(&str, &Value)>> = BTreeMap::new();
+        for item in ids
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.item(id))
+        {
+            if let (Some(kind), Some(name)) = (Kind::of_item(item), item["name"].as_str()) {
+                items.entry(kind).or_default().push((name, item));
+            }
+        }
+        for (kind, mut entries) in items {
+            entries.sort_by_key(|(name, _)| *name);
+            let _ = write!(
+                body,
+                "<h2 id=\"{}\" class=\"section-header\">{}</h2><dl class=\"item-table\">",
+                kind.class(),
+                kind.section()
+            );
+            for (name, item) in entries {
+                let href = kind.href(&[String::new(), name.to_string()]);
+                let _ = write!(
+                    body,
+                    "<dt><a class=\"{}\" href=\"{}\">{}</a></dt><dd>{}</dd>",
+                    kind.class(),
+                    escape(&href),
+                    escape(name),
+                    summary(item)
+                );
+            }
+            body.push_str("</dl>");
+        }
+    }
+
+    /// Inherent methods, then the implemented traits
+    fn implementations(&self, ids: &Value, body: &mut String) {
+        let impls: Vec<&Value> = ids
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.item(id))
+            .map(|item| &item["inner"]["impl"])
+            .filter(|inner| {
+                inner["is_synthetic"].as_bool() != Some(true) && inner["blanket_impl"].is_null()
+            })
+            .collect();
+
+        let inherent: Vec<Value> = impls
+            .iter()
+            .filter(|inner| inner["trait"].is_null())
+            .flat_map(|inner| inner["items"].as_array().cloned().unwrap_or_default())
+            .collect();
+        self.members("Implementations", "method", &Value::Array(inherent), body);
+
+        let mut traits: Vec<&str> = impls
+            .iter()
+            .filter_map(|inner| {
+                let implemented = &inner["trait"];
+                implemented["path"]
+                    .as_str()
+                    .or_else(|| implemented["name"].as_str())
+            })
+            .collect();
+        traits.sort_unstable();
+        traits.dedup();
+        if !traits.is_empty() {
+            body.push_str(
+                "<h2 id=\"trait-implementations\" class=\"section-header\">Trait Implementations</h2>",
+            );
+            for name in traits {
+                let _ = write!(body, "<h3 class=\"code-header\">impl {}</h3>", escape(name));
+            }
+        }
+    }
+
+    /// Associated items of a trait, split as rustdoc does
+    fn trait_items(&self, ids: &Value, body: &mut String) {
+        let mut required = Vec::new();
+        let mut provided = Vec::new();
+        let mut associated = Vec::new();
+        for id in ids.as_array().into_iter().flatten() {
+            let Some(item) = self.item(id) else {
+                continue;
+            };
+            match item["inner"]["function"]["has_body"].as_bool() {
+                Some(true) => provided.push(id.clone()),
+                Some(false) => required.push(id.clone()),
+                None => associated.push(id.clone()),
+            }
+        }
+        self.members(
+            "Associated Items",
+            "associatedtype",
+            &Value::Array(associated),
+            body,
+        );
+        self.members(
+            "Required Methods",
+            "tymethod",
+            &Value::Array(required),
+            body,
+        );
+        self.members("Provided Methods", "method", &Value::Array(provided), body);
+    }
+
+    /// A section documenting the items `ids` one by one
+    fn members(&self, heading: &str, anchor: &str, ids: &Value, body: &mut String) {
+        let members: Vec<&Value> = ids
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.item(id))
+            .filter(|item| item["name"].is_string())
+            .collect();
+        if members.is_empty() {
+            return;
+        }
+        let _ = write!(
+            body,
+            "<h2 id=\"{}\" class=\"section-header\">{heading}</h2>",
+            heading.to_lowercase().replace(' ', "-")
+        );
+        for item in members {
+            let name = escape(item["name"].as_str().unwrap_or_default());
+            let keyword = if item["inner"]["function"].is_null() {
+                ""
+            } else {
+                "fn "
+            };
+            let _ = write!(
+                body,
+                "<section id=\"{anchor}.{name}\"><h4 class=\"code-header\">{keyword}{name}</h4></section>{}",
+                docblock(item)
+            );
+        }
+    }
+}
+
+/// Key of an item ID in the `index` and `paths` maps
+///
+/// IDs are numbers in current rustdoc and strings in older format versions.
+fn id_key(id: &Value) -> Option<String> {
+    match id {
+        Value::Number(id) => Some(id.to_string()),
+        Value::String(id) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+/// The item's documentation as a rustdoc docblock
+fn docblock(item: &Value) -> String {
+    match item["docs"].as_str().map(str::trim) {
+        Some(docs) if !docs.is_empty() => {
+            format!("<div class=\"docblock\">{}</div>", markdown_html(docs))
+        }
+        _ => String::new(),
+    }
+}
+
+/// First paragraph of the item's documentation, for item tables
+fn summary(item: &Value) -> String {
+    let docs = item["docs"].as_str().unwrap_or_default().trim();
+    let first = docs.split("\n\n").next().unwrap_or_default();
+    inline_html(&first.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Render Markdown documentation: paragraphs, headings, lists and code blocks
+///
+/// Lines rustdoc hides in Rust examples (`# use …;`) are dropped.
+fn markdown_html(docs: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<String> = Vec::new();
+    let mut code: Option<(bool, Vec<&str>)> = None;
+
+    let flush = |html: &mut String, paragraph: &mut Vec<&str>, list: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            let _ = write!(html, "<p>{}</p>", inline_html(&paragraph.join(" ")));
+            paragraph.clear();
+        }
+        if !list.is_empty() {
+            html.push_str("<ul>");
+            for entry in list.drain(..) {
+                let _ = write!(html, "<li>{}</li>", inline_html(&entry));
+            }
+            html.push_str("</ul>");
+        }
+    };
+
+    for line in docs.lines() {
+        let trimmed = line.trim();
+        if let Some(fence) = trimmed.strip_prefix("```") {
+            if let Some((_, lines)) = code.take() {
+                code_block(&mut html, &lines);
+            } else {
+                flush(&mut html, &mut paragraph, &mut list);
+                code = Some((is_rust_block(fence), Vec::new()));
+            }
+            continue;
+        }
+        if let Some((is_rust, lines)) = &mut code {
+            let hidden = *is_rust && (trimmed == "#" || trimmed.starts_with("# "));
+            if !hidden {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph, &mut list);
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush(&mut html, &mut paragraph, &mut list);
+            let level = (level + 1).min(6);
+            let _ = write!(html, "<h{level}>{}</h{level}>", inline_html(heading));
+        } else if let Some(entry) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            if !paragraph.is_empty() {
+                flush(&mut html, &mut paragraph, &mut Vec::new());
+            }
+            list.push(entry.to_string());
+        } else if let (Some(last), true) = (list.last_mut(), line.starts_with(' ')) {
+            last.push(' ');
+            last.push_str(trimmed);
+        } else {
+            if !list.is_empty() {
+                flush(&mut html, &mut Vec::new(), &mut list);
+            }
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some((_, lines)) = code {
+        code_block(&mut html, &lines);
+    }
+    flush(&mut html, &mut paragraph, &mut list);
+    html
+}
+
+fn code_block(html: &mut String, lines: &[&str]) {
+    let _ = write!(
+        html,
+        "<pre class=\"rust\"><code>{}</code></pre>",
+        escape(&lines.join("\n"))
+    );
+}
+
+/// Whether a code fence with the info string `fence` holds Rust, as rustdoc
+/// assumes unless another language is given
+fn is_rust_block(fence: &str) -> bool {
+    fence.trim().is_empty()
+        || fence.split(',').any(|attr| {
+            matches!(
+                attr.trim(),
+                "rust"
+                    | "no_run"
+                    | "should_panic"
+                    | "ignore"
+                    | "compile_fail"
+                    | "edition2018"
+                    | "edition2021"
+                    | "edition2024"
+            )
+        })
+}
+
+/// Level and text of an ATX heading (`## Examples`)
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, text.trim().trim_end_matches('#').trim()))
+}
+
+/// Inline links: `[text](url)`, and intra-doc links `[text]` left as text
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\](?:\(([^)\s]+)\))?").expect("hardcoded valid regex"));
+
+/// Strong and emphasized text
+static EMPHASIS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*|\*([^*\s][^*]*)\*|\b_([^_]+)_\b").expect("hardcoded valid regex")
+});
+
+/// Render inline Markdown: code spans, links and emphasis
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    // Odd segments are inside backticks
+    for (i, segment) in text.split('`').enumerate() {
+        if i % 2 == 1 {
+            let _ = write!(html, "<code>{}</code>", escape(segment));
+            continue;
+        }
+        let escaped = escape(segment);
+        let linked = LINK_RE.replace_all(&escaped, |caps: &regex::Captures| match caps.get(2) {
+            Some(url) if url.as_str().starts_with("http") => {
+                format!("<a href=\"{}\">{}</a>", url.as_str(), &caps[1])
+            }
+            _ => caps[1].to_string(),
+        });
+        let emphasized = EMPHASIS_RE.replace_all(&linked, |caps: &regex::Captures| {
+            match (caps.get(1), caps.get(2), caps.get(3)) {
+                (Some(strong), _, _) => format!("<strong>{}</strong>", strong.as_str()),
+                (_, Some(em), _) | (_, _, Some(em)) => format!("<em>{}</em>", em.as_str()),
+                _ => caps[0].to_string(),
+            }
+        });
+        html.push_str(&emphasized);
+    }
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Value {
+        serde_json::json!({
+            "root": 0,
+            "crate_version": "1.2.0",
+            "format_version": 45,
+            "index": {
+                "0": {
+                    "name": "demo",
+                    "docs": "A demo crate.\n\n# Examples\n\n```\n# use demo::Reader;\nlet r = Reader::new();\n```",
+                    "inner": { "module": { "is_crate": true, "items": [1, 2, 5] } }
+                },
+                "1": {
+                    "name": "io",
+                    "docs": "I/O helpers.",
+                    "inner": { "module": { "is_crate": false, "items": [3] } }
+                },
+                "2": {
+                    "name": "Config",
+                    "docs": "Settings with `serde` support.\n\n- **fast**\n- safe",
+                    "inner": { "struct": { "impls": [6] } }
+                },
+                "3": {
+                    "name": "Reader",
+                    "docs": "Reads things.",
+                    "inner": { "trait": { "items": [4] } }
+                },
+                "4": {
+                    "name": "read",
+                    "docs": "Read once.",
+                    "inner": { "function": { "has_body": false } }
+                },
+                "5": {
+                    "name": "demo",
+                    "inner": { "proc_macro": { "kind": "derive" } }
+                },
+                "6": {
+                    "inner": { "impl": { "trait": null, "items": [7], "is_synthetic": false, "blanket_impl": null } }
+                },
+                "7": {
+                    "name": "new",
+                    "docs": "Create the default configuration.",
+                    "inner": { "function": { "has_body": true } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "1": { "crate_id": 0, "path": ["demo", "io"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "Config"], "kind": "struct" },
+                "3": { "crate_id": 0, "path": ["demo", "io", "Reader"], "kind": "trait" },
+                "5": { "crate_id": 0, "path": ["demo", "Demo"], "kind": "proc_derive" },
+                "9": { "crate_id": 1, "path": ["std", "string", "String"], "kind": "struct" }
+            }
+        })
+    }
+
+    #[test]
+    fn test_split_url() {
+        assert_eq!(
+            split_url("https://docs.rs/crate/demo/1.2.0/json.gz/io/trait.Reader.html"),
+            Some((
+                "https://docs.rs/crate/demo/1.2.0/json.gz",
+                "io/trait.Reader.html"
+            ))
+        );
+        assert_eq!(
+            split_url("https://docs.example.com/demo.json/"),
+            Some(("https://docs.example.com/demo.json", ""))
+        );
+        assert_eq!(
+            split_url("https://docs.rs/crate/demo/1.2.0/status.json"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_crate_page() {
+        let html = render(&document(), "index.html").unwrap();
+        assert!(html.contains("<h1>Crate <span>demo</span></h1>"));
+        assert!(html.contains("<span class=\"version\">1.2.0</span>"));
+        assert!(html
+            .contains("<a class=\"mod\" href=\"io/index.html\">io</a></dt><dd>I/O helpers.</dd>"));
+        assert!(html.contains("href=\"struct.Config.html\""));
+        // Hidden example lines are dropped
+        assert!(html.contains("<pre class=\"rust\"><code>let r = Reader::new();</code></pre>"));
+        assert!(!html.contains("use demo::Reader"));
+        assert_eq!(render(&document(), "").unwrap(), html);
+        assert_eq!(
+            super::super::html::page_version(&html).as_deref(),
+            Some("1.2.0")
+        );
+    }
+
+    #[test]
+    fn test_item_pages() {
+        let html = render(&document(), "io/trait.Reader.html").unwrap();
+        assert!(html.contains("<h1>Trait <span>demo::io::Reader</span></h1>"));
+        assert!(html.contains("Required Methods"));
+        assert!(html.contains("<h4 class=\"code-header\">fn read</h4>"));
+
+        let html = render(&document(), "struct.Config.html").unwrap();
+        assert!(html.contains("<code>serde</code>"));
+        assert!(html.contains("<li><strong>fast</strong></li>"));
+        assert!(html.contains("Implementations"));
+        assert!(html.contains("Create the default configuration."));
+
+        assert!(render(&document(), "io/")
+            .unwrap()
+            .contains("Module <span>demo::io</span>"));
+        assert!(render(&document(), "derive.Demo.html").is_some());
+        assert!(render(&document(), "struct.Reader.html").is_none());
+        assert!(render(&document(), "missing/index.html").is_none());
+    }
+
+    #[test]
+    fn test_all_items_page_lists_local_items() {
+        let html = render(&document(), "all.html").unwrap();
+        assert!(html.contains("<a href=\"io/trait.Reader.html\">io::Reader</a>"));
+        assert!(html.contains("<a href=\"derive.Demo.html\">Demo</a>"));
+        assert!(!html.contains("String"));
+        let index = super::super::symbol::parse_all_items(&html);
+        assert_eq!(index.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_compressed_document() {
+        use std::io::Write as _;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(document().to_string().as_bytes())
+            .unwrap();
+        let body = encoder.finish().unwrap();
+        assert_eq!(parse_document(&body).unwrap(), document());
+        assert_eq!(
+            parse_document(document().to_string().as_bytes()).unwrap(),
+            document()
+        );
+        assert!(parse_document(b"\x1f\x8bnot gzip").is_err());
+        assert!(parse_document(b"<html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_documents_are_kept_for_later_pages() {
+        let documents = Documents::default();
+        let url = "https://docs.rs/crate/demo/1.2.0/json.gz";
+        let first = documents
+            .get(url, || async { Ok(Some(Arc::new(document()))) })
+            .await
+            .unwrap();
+        let second = documents
+            .get(url, || async { Err("fetched twice".to_string()) })
+            .await
+            .unwrap();
+        assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+
+        // Missing documents are not kept
+        let missing = "https://docs.rs/crate/missing/1.0.0/json.gz";
+        assert!(documents
+            .get(missing, || async { Ok(None) })
+            .await
+            .unwrap()
+            .is_none());
+        assert!(documents
+            .get(missing, || async { Err("asked again".to_string()) })
+            .await
+            .is_err());
+    }
+}
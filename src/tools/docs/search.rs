@@ -1,8 +1,8 @@
 //! Search crates tool
 //!
-//! Provides functionality to search for Rust crates from crates.io.
-//! Returns a list of matching crates with metadata like name, description,
-//! version, downloads, etc.
+//! Provides functionality to search for Rust crates from crates.io (or lib.rs,
+//! see [`search_backend`](super::search_backend)). Returns a list of matching
+//! crates with metadata like name, description, version, downloads, etc.
 
 #![allow(missing_docs)]
 
@@ -17,6 +17,18 @@ const DEFAULT_SEARCH_LIMIT: u32 = 10;
 const ESTIMATED_MARKDOWN_ENTRY_SIZE: usize = 200;
 const ESTIMATED_TEXT_ENTRY_SIZE: usize = 100;
 
+/// Search results considered for "did you mean" suggestions
+const SUGGESTION_CANDIDATES: u32 = 20;
+
+/// Maximum "did you mean" suggestions
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Search results considered for crate name completions
+const COMPLETION_CANDIDATES: u32 = 50;
+
+/// Standard distribution crates, which crates.io does not know
+const STD_CRATES: &[&str] = &["alloc", "core", "proc_macro", "std", "test"];
+
 /// Search crates tool parameters
 ///
 /// Used to specify search criteria for finding Rust crates on crates.io.
@@ -71,6 +83,13 @@ pub struct SearchCratesTool {
         default = "markdown"
     )]
     pub format: Option<String>,
+
+    /// Ignore cached results and query crates.io again
+    #[json_schema(
+        title = "No Cache",
+        description = "Skip cached results and query crates.io again, e.g. to find a crate published minutes ago. The results are still cached for later requests"
+    )]
+    pub no_cache: Option<bool>,
 }
 
 const DEFAULT_SEARCH_SORT: &str = "relevance";
@@ -84,7 +103,7 @@ const VALID_SEARCH_SORTS: &[&str] = &[
 
 /// Crates.io search response (typed deserialization)
 #[derive(Debug, Deserialize)]
-struct SearchCratesResponse {
+pub(super) struct SearchCratesResponse {
     crates: Vec<SearchCrateRecord>,
 }
 
@@ -156,7 +175,7 @@ impl SearchCratesToolImpl {
     }
 
     /// Search crates
-    async fn search_crates(
+    pub(super) async fn search_crates(
         &self,
         query: &str,
         limit: u32,
@@ -174,47 +193,41 @@ impl SearchCratesToolImpl {
             });
         }
 
-        // Build URL using helper function
-        let url = super::build_crates_io_search_url(query, Some(sort), Some(limit as usize));
-
-        let response = self
-            .service
-            .client()
-            .get(&url)
-            .header("User-Agent", crate::user_agent())
-            .send()
-            .await
-            .map_err(|e| {
-                CallToolError::from_message(format!("[search_crates] HTTP request failed: {e}"))
-            })?;
-
-        if !response.status().is_success() {
-            // Surface crates.io diagnostics (e.g. rate-limit explanations) from
-            // the response body instead of returning a bare status code. HTML
-            // error pages are suppressed to avoid dumping noise.
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            let trimmed = body.trim();
-            let detail = if trimmed.is_empty()
-                || trimmed.starts_with('<')
-                || trimmed.to_ascii_lowercase().contains("<html")
-            {
-                String::new()
-            } else {
-                let snippet: String = trimmed.chars().take(200).collect();
-                format!(" - {snippet}")
+        // Backends are tried in order; lib.rs is skipped for sorts it cannot
+        // serve, leaving crates.io as the last resort.
+        let mut crates = None;
+        let mut last_error: Option<String> = None;
+        for backend in self.service.search_backends() {
+            if !backend.supports_sort(sort) {
+                continue;
+            }
+            let result = match backend {
+                super::search_backend::SearchBackend::CratesIo => {
+                    self.search_crates_io(query, limit, sort).await
+                }
+                super::search_backend::SearchBackend::LibRs => {
+                    super::search_backend::search_lib_rs(&self.service, query, limit as usize).await
+                }
             };
-            return Err(CallToolError::from_message(format!(
-                "[search_crates] crates.io search failed: HTTP {status}{detail}"
-            )));
+            match result {
+                Ok(found) => {
+                    crates = Some(found);
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        backend = backend.name(),
+                        "[search_crates] search backend failed, trying next: {e}"
+                    );
+                    last_error = Some(e.to_string());
+                }
+            }
         }
-
-        // Use typed deserialization instead of serde_json::Value
-        let search_response: SearchCratesResponse = response.json().await.map_err(|e| {
-            CallToolError::from_message(format!("[search_crates] JSON parsing failed: {e}"))
-        })?;
-
-        let crates = parse_crates_response(search_response, limit as usize);
+        let crates = match (crates, last_error) {
+            (Some(crates), _) => crates,
+            (None, Some(message)) => return Err(CallToolError::from_message(message)),
+            (None, None) => self.search_crates_io(query, limit, sort).await?,
+        };
 
         let cache_value = serde_json::to_string(&crates).map_err(|e| {
             CallToolError::from_message(format!("[search_crates] Serialization failed: {e}"))
@@ -236,37 +249,197 @@ impl SearchCratesToolImpl {
 
         Ok(crates)
     }
+
+    /// Search the registry API
+    async fn search_crates_io(
+        &self,
+        query: &str,
+        limit: u32,
+        sort: &str,
+    ) -> std::result::Result<Vec<CrateInfo>, CallToolError> {
+        // Build URL using helper function
+        let url = super::build_crates_io_search_url(query, Some(sort), Some(limit as usize));
+
+        let request = async {
+            let response = self
+                .service
+                .get(&url)
+                .header("User-Agent", crate::user_agent())
+                .send()
+                .await
+                .map_err(|e| {
+                    CallToolError::from_message(format!("[search_crates] HTTP request failed: {e}"))
+                })?;
+
+            if !response.status().is_success() {
+                // Surface crates.io diagnostics (e.g. rate-limit explanations) from
+                // the response body instead of returning a bare status code.
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(CallToolError::from_message(format!(
+                    "[search_crates] crates.io search failed: {}",
+                    super::summarize_http_status(&url, status, &body)
+                )));
+            }
+
+            // Use typed deserialization instead of serde_json::Value
+            response.json::<SearchCratesResponse>().await.map_err(|e| {
+                CallToolError::from_message(format!("[search_crates] JSON parsing failed: {e}"))
+            })
+        };
+        let search_response = self
+            .service
+            .time_limited(&format!("GET {url}"), request)
+            .await??;
+
+        Ok(parse_crates_response(search_response, limit as usize))
+    }
+}
+
+/// Crate names close to `crate_name`, a name that does not exist, best
+/// match first
+///
+/// Candidates come from a crates.io search for the name; a failing search
+/// yields no suggestions rather than hiding the not-found error.
+pub(super) async fn suggest_crate_names(
+    service: &Arc<super::DocService>,
+    crate_name: &str,
+) -> Vec<String> {
+    if super::is_rust_std_crate(crate_name) {
+        return Vec::new();
+    }
+    let found = SearchCratesToolImpl::new(Arc::clone(service))
+        .search_crates(crate_name, SUGGESTION_CANDIDATES, "relevance")
+        .await;
+    match found {
+        Ok(crates) => similar_names(crate_name, crates.iter().map(|c| c.name.as_str())),
+        Err(e) => {
+            tracing::warn!("crate name suggestions for '{crate_name}' unavailable: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Crate names starting with `prefix`, for argument completion
+///
+/// Matching standard library crates come first, then crates.io search
+/// results by relevance; a failing search yields only the former.
+pub async fn complete_crate_names(service: &Arc<super::DocService>, prefix: &str) -> Vec<String> {
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let found = SearchCratesToolImpl::new(Arc::clone(service))
+        .search_crates(prefix, COMPLETION_CANDIDATES, "relevance")
+        .await;
+    let found = match found {
+        Ok(crates) => crates.into_iter().map(|c| c.name).collect(),
+        Err(e) => {
+            tracing::warn!("crate name completions for '{prefix}' unavailable: {e}");
+            Vec::new()
+        }
+    };
+    names_with_prefix(
+        prefix,
+        STD_CRATES.iter().map(ToString::to_string).chain(found),
+    )
+}
+
+/// The `candidates` starting with `prefix`, ignoring case, `-` and `_`, in
+/// order and without duplicates
+fn names_with_prefix(prefix: &str, candidates: impl Iterator<Item = String>) -> Vec<String> {
+    let prefix = name_key(prefix);
+    let mut names: Vec<String> = Vec::new();
+    for name in candidates {
+        if name_key(&name).starts_with(&prefix) && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// A crate name compared the way people misremember it: case, `-` and `_`
+/// ignored
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The `candidates` that look like misspellings of `name`, closest first
+///
+/// Names equal up to case and separators come first, then those within a
+/// third of the name's length in edits; ties keep the candidates' order
+/// (search relevance).
+fn similar_names<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let key = name_key(name);
+    let max_distance = (key.chars().count() / 3).max(1);
+    let mut scored: Vec<(usize, &str)> = candidates
+        .filter(|candidate| !candidate.eq_ignore_ascii_case(name))
+        .map(|candidate| (edit_distance(&key, &name_key(candidate)), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    // Stable: equal distances keep the search order
+    scored.sort_by_key(|&(distance, _)| distance);
+    let mut names: Vec<String> = Vec::new();
+    for (_, candidate) in scored {
+        if !names.iter().any(|n| n == candidate) {
+            names.push(candidate.to_string());
+        }
+    }
+    names.truncate(MAX_SUGGESTIONS);
+    names
 }
 
 /// Crate information from search results
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct CrateInfo {
+pub(super) struct CrateInfo {
     /// Crate name
-    name: String,
+    pub(super) name: String,
     /// Crate description
-    description: Option<String>,
+    pub(super) description: Option<String>,
     /// Latest version
-    version: String,
+    pub(super) version: String,
     /// Total downloads
-    downloads: u64,
+    pub(super) downloads: u64,
     /// Recent downloads (last 90 days), when reported by crates.io. Shown next
     /// to the total so `recent-downloads`-sorted results are not confusing.
     #[serde(default)]
-    recent_downloads: Option<u64>,
+    pub(super) recent_downloads: Option<u64>,
     /// Repository URL
-    repository: Option<String>,
+    pub(super) repository: Option<String>,
     /// Documentation URL (as provided by crates.io, if any)
-    documentation: Option<String>,
+    pub(super) documentation: Option<String>,
     /// Canonical docs.rs URL for the crate (always present on fresh results).
     /// Tolerate cache entries written by older binaries that predate this
     /// field so a stale cache hit degrades to an empty value instead of a
     /// fatal "Cache parsing failed" error.
     #[serde(default)]
-    docs_rs: String,
+    pub(super) docs_rs: String,
 }
 
 #[inline]
-fn parse_crates_response(response: SearchCratesResponse, limit: usize) -> Vec<CrateInfo> {
+pub(super) fn parse_crates_response(
+    response: SearchCratesResponse,
+    limit: usize,
+) -> Vec<CrateInfo> {
     response
         .crates
         .into_iter()
@@ -313,7 +486,7 @@ fn format_search_results(crates: &[CrateInfo], format: super::Format) -> String
         // `html` is rejected before formatting (see `execute`); list both
         // variants explicitly so adding a new `Format` variant becomes a
         // compile error here rather than a silent fall-through to markdown.
-        super::Format::Markdown | super::Format::Html => {
+        super::Format::Markdown | super::Format::Html | super::Format::Llm => {
             if crates.is_empty() {
                 "# Search Results\n\nNo crates found matching the query.".to_string()
             } else {
@@ -328,7 +501,7 @@ fn format_search_results(crates: &[CrateInfo], format: super::Format) -> String
 /// clean field line. crates.io descriptions frequently carry a trailing
 /// newline, which otherwise splits a record with a blank line between
 /// `Description` and the following field.
-fn normalize_description(s: &str) -> String {
+pub(super) fn normalize_description(s: &str) -> String {
     s.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
@@ -336,7 +509,7 @@ fn normalize_description(s: &str) -> String {
 /// description set by its publisher) inject markdown links, inline HTML, or
 /// code spans into the rendered output. Only structural characters are escaped
 /// so ordinary prose renders unchanged.
-fn escape_markdown_text(s: &str) -> String {
+pub(super) fn escape_markdown_text(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     for c in s.chars() {
         match c {
@@ -377,12 +550,17 @@ fn render_markdown_url(label: &str, url: &str) -> String {
 }
 
 fn format_markdown_results(crates: &[CrateInfo]) -> String {
-    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
-    use std::fmt::Write;
     let estimated_size = crates.len().saturating_mul(ESTIMATED_MARKDOWN_ENTRY_SIZE) + 20;
     let mut output = String::with_capacity(estimated_size);
     output.push_str("# Search Results\n\n");
+    write_markdown_entries(&mut output, crates);
+    output
+}
 
+/// Append one markdown section per crate (shared with category listings)
+pub(super) fn write_markdown_entries(output: &mut String, crates: &[CrateInfo]) {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
     for (i, crate_info) in crates.iter().enumerate() {
         writeln!(output, "## {}. {}", i + 1, crate_info.name).unwrap();
         writeln!(output, "**Version**: {}", crate_info.version).unwrap();
@@ -425,11 +603,9 @@ fn format_markdown_results(crates: &[CrateInfo]) -> String {
         )
         .unwrap();
     }
-
-    output
 }
 
-fn format_text_results(crates: &[CrateInfo]) -> String {
+pub(super) fn format_text_results(crates: &[CrateInfo]) -> String {
     // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
     use std::fmt::Write;
     let estimated_size = crates.len().saturating_mul(ESTIMATED_TEXT_ENTRY_SIZE);
@@ -508,9 +684,11 @@ impl Tool for SearchCratesToolImpl {
         // query like "  tokio  " is sent verbatim to crates.io (poorer results)
         // yet cached/looked-up under the trimmed key, letting a whitespace-laden
         // first request poison the cache for every later "tokio" caller.
-        let crates = self
-            .search_crates(params.query.trim(), limit, &sort)
-            .await?;
+        let crates = super::cache::with_no_cache(
+            params.no_cache.unwrap_or(false),
+            self.search_crates(params.query.trim(), limit, &sort),
+        )
+        .await?;
         let content = format_search_results(&crates, format);
 
         Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
@@ -529,6 +707,34 @@ impl Default for SearchCratesToolImpl {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_similar_names() {
+        let candidates = ["tokio", "tokio-util", "tokio-utils", "tokio_util2", "serde"];
+        assert_eq!(
+            similar_names("tokioutil", candidates.into_iter()),
+            ["tokio-util", "tokio-utils", "tokio_util2"]
+        );
+        assert_eq!(
+            similar_names("Serde", ["serde", "serd"].into_iter()),
+            ["serd"]
+        );
+        assert!(similar_names("xyz", candidates.into_iter()).is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_names_with_prefix() {
+        let candidates = ["std", "serde", "serde_json", "Serde-Value", "toml", "serde"];
+        assert_eq!(
+            names_with_prefix("serde-", candidates.into_iter().map(String::from)),
+            ["serde", "serde_json", "Serde-Value"]
+        );
+        assert_eq!(
+            names_with_prefix("St", candidates.into_iter().map(String::from)),
+            ["std"]
+        );
+    }
+
     #[test]
     fn test_format_search_results_empty_emits_message() {
         use crate::tools::docs::Format;
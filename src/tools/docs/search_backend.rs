@@ -0,0 +1,218 @@
+//! Search backends
+//!
+//! `search_crates` can query crates.io, lib.rs, or both in fallback order:
+//!
+//! ```toml
+//! [docs]
+//! search_backends = ["lib_rs", "crates_io"]
+//! ```
+//!
+//! lib.rs ranks results by relevance, popularity and maintenance rather than
+//! by text match alone, but has no API: its search page is scraped for the
+//! ranking, and the crates.io API fills in the metadata of the ranked crates
+//! so every backend returns the same [`CrateInfo`](super::search::CrateInfo)
+//! records. lib.rs only indexes crates.io and cannot sort, so it is skipped
+//! for the other sort orders and when an alternative registry is configured.
+
+use super::search::{CrateInfo, SearchCratesResponse};
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+const LIB_RS_BASE_URL: &str = "https://lib.rs";
+
+static RESULT_LINK_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("ol li a[href^=\"/crates/\"]").expect("hardcoded valid selector")
+});
+
+static DESCRIPTION_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".desc").expect("hardcoded valid selector"));
+
+static VERSION_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(".version").expect("hardcoded valid selector"));
+
+/// A crate search backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchBackend {
+    /// The registry API (crates.io unless `[registry]` says otherwise)
+    CratesIo,
+    /// lib.rs search, relevance sort only
+    LibRs,
+}
+
+impl SearchBackend {
+    /// Name used in logs
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CratesIo => "crates.io",
+            Self::LibRs => "lib.rs",
+        }
+    }
+
+    /// Whether this backend can serve a search with `sort`
+    #[must_use]
+    pub fn supports_sort(self, sort: &str) -> bool {
+        match self {
+            Self::CratesIo => true,
+            Self::LibRs => sort == "relevance" && super::registry::registry_name() == "crates-io",
+        }
+    }
+}
+
+/// Backends used when none are configured: crates.io only
+#[must_use]
+pub fn default_search_backends() -> Vec<SearchBackend> {
+    vec![SearchBackend::CratesIo]
+}
+
+/// Build the lib.rs search page URL
+fn build_lib_rs_search_url(query: &str) -> String {
+    format!("{LIB_RS_BASE_URL}/search?q={}", urlencoding::encode(query))
+}
+
+/// Build the crates.io API URL fetching the given crates in one request
+fn build_crates_io_ids_url(names: &[String]) -> String {
+    use std::fmt::Write;
+    let mut url = format!(
+        "{}/api/v1/crates?per_page={}",
+        super::crates_io_base_url(),
+        names.len()
+    );
+    for name in names {
+        // SAFETY: write! to String never fails
+        write!(url, "&ids%5B%5D={}", urlencoding::encode(name)).unwrap();
+    }
+    url
+}
+
+/// Parse the ranked results of a lib.rs search page
+///
+/// Only the fields the page shows are filled in; download counts are left
+/// at zero for [`search_lib_rs`] to complete from crates.io.
+fn parse_lib_rs_results(html: &str, limit: usize) -> Vec<CrateInfo> {
+    let document = Html::parse_document(html);
+    let mut crates: Vec<CrateInfo> = Vec::new();
+    for link in document.select(&RESULT_LINK_SELECTOR) {
+        let Some(name) = link
+            .value()
+            .attr("href")
+            .and_then(|href| href.strip_prefix("/crates/"))
+            .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default())
+            .filter(|name| super::validate_crate_name("search_crates", name).is_ok())
+        else {
+            continue;
+        };
+        if crates.iter().any(|info| info.name == name) {
+            continue;
+        }
+        let text_of = |selector: &Selector| {
+            link.select(selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+        crates.push(CrateInfo {
+            name: name.to_string(),
+            description: text_of(&DESCRIPTION_SELECTOR),
+            version: text_of(&VERSION_SELECTOR).map_or_else(
+                || "0.0.0".to_string(),
+                |v| v.trim_start_matches('v').to_string(),
+            ),
+            downloads: 0,
+            recent_downloads: None,
+            repository: None,
+            documentation: None,
+            docs_rs: format!("https://docs.rs/{name}/"),
+        });
+        if crates.len() >= limit {
+            break;
+        }
+    }
+    crates
+}
+
+/// Search lib.rs, keeping its ranking
+///
+/// The metadata of the ranked crates comes from crates.io when available;
+/// if that request fails, the fields shown on the lib.rs page are returned.
+pub(super) async fn search_lib_rs(
+    service: &super::DocService,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<CrateInfo>, CallToolError> {
+    let url = build_lib_rs_search_url(query);
+    let html = service.fetch_html(&url, Some("search_crates")).await?;
+    let mut ranked = parse_lib_rs_results(&html, limit);
+    if ranked.is_empty() {
+        return Ok(ranked);
+    }
+
+    let names: Vec<String> = ranked.iter().map(|info| info.name.clone()).collect();
+    let url = build_crates_io_ids_url(&names);
+    let details = match service.fetch_html(&url, Some("search_crates")).await {
+        Ok(body) => serde_json::from_str::<SearchCratesResponse>(&body)
+            .map(|response| super::search::parse_crates_response(response, names.len()))
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    match details {
+        Ok(details) => {
+            for info in &mut ranked {
+                if let Some(detail) = details.iter().find(|d| d.name == info.name) {
+                    *info = detail.clone();
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[search_crates] crates.io metadata for lib.rs results failed: {e}");
+        }
+    }
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_PAGE: &str = r#"<html><body><ol>
+        <li><a href="/crates/axum"><div class="h"><h4>axum</h4>
+            <p class="desc">Web framework that focuses on ergonomics</p></div>
+            <div class="meta"><span class="version">v0.8.1</span></div></a></li>
+        <li><a href="/crates/actix-web"><div class="h"><h4>actix-web</h4></div></a></li>
+        <li><a href="/crates/axum/versions">duplicate</a></li>
+        <li><a href="/crates/rocket"><h4>rocket</h4></a></li>
+    </ol></body></html>"#;
+
+    #[test]
+    fn test_parse_lib_rs_results_keeps_ranking() {
+        let crates = parse_lib_rs_results(SEARCH_PAGE, 10);
+        let names: Vec<&str> = crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["axum", "actix-web", "rocket"]);
+        assert_eq!(crates[0].version, "0.8.1");
+        assert_eq!(
+            crates[0].description.as_deref(),
+            Some("Web framework that focuses on ergonomics")
+        );
+        assert_eq!(crates[1].version, "0.0.0");
+        assert_eq!(parse_lib_rs_results(SEARCH_PAGE, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_lib_rs_only_serves_relevance() {
+        assert!(SearchBackend::LibRs.supports_sort("relevance"));
+        assert!(!SearchBackend::LibRs.supports_sort("downloads"));
+        assert!(SearchBackend::CratesIo.supports_sort("downloads"));
+    }
+
+    #[test]
+    fn test_build_crates_io_ids_url() {
+        let url = build_crates_io_ids_url(&["serde".to_string(), "serde_json".to_string()]);
+        assert!(
+            url.ends_with("/api/v1/crates?per_page=2&ids%5B%5D=serde&ids%5B%5D=serde_json"),
+            "{url}"
+        );
+    }
+}
@@ -0,0 +1,592 @@
+//! Crate summary tool
+//!
+//! Condenses a crate into what an agent needs to start using it: its
+//! purpose, the key items of the root module, a typical usage snippet and
+//! the feature flags. Full documentation pages easily exceed a small
+//! context window, so the summary is trimmed to a caller-specified budget,
+//! dropping the least important parts first.
+//!
+//! Everything comes from data the other tools already fetch and cache: the
+//! crate front page (`lookup_crate`), the crates.io summary
+//! (`batch_lookup_crates`) and the sparse index (`resolve_version`).
+
+#![allow(missing_docs)]
+
+use super::resolve_version::{self, VersionSpec};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "summarize_crate";
+
+const DEFAULT_MAX_TOKENS: u32 = 1000;
+
+const MAX_MAX_TOKENS: u32 = 20_000;
+
+const MAX_MAX_LENGTH: u32 = 80_000;
+
+/// Key items and features kept before the usage snippet is dropped
+const MIN_LISTED: usize = 5;
+
+/// Items collected from the front page before any budget is applied
+const MAX_KEY_ITEMS: usize = 40;
+
+/// Maximum characters of an item summary
+const MAX_ITEM_SUMMARY_CHARS: usize = 100;
+
+/// Maximum characters of the overview paragraph
+const MAX_OVERVIEW_CHARS: usize = 600;
+
+/// Maximum lines of the usage snippet
+const MAX_USAGE_LINES: usize = 25;
+
+static OVERVIEW_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("#main-content .docblock > p").expect("hardcoded valid selector")
+});
+
+static ITEM_CELL_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(".item-table dt, .item-table .item-name").expect("hardcoded valid selector")
+});
+
+static ITEM_LINK_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("a[href]").expect("hardcoded valid selector"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "summarize_crate",
+    title = "Summarize Crate",
+    description = "Get a condensed overview of a Rust crate: its purpose, key types and functions, a typical usage snippet and its feature flags, trimmed to a token budget. Use this instead of lookup_crate when the full documentation page would not fit the context window.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `summarize_crate` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct SummarizeCrateTool {
+    /// Crate name (e.g., "serde", "tokio")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to summarize, e.g.: serde, tokio, reqwest"
+    )]
+    pub crate_name: String,
+
+    /// Crate version (optional, defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Approximate token budget of the summary (range 100-20000, defaults to 1000)
+    #[json_schema(
+        title = "Max Tokens",
+        description = "Approximate size limit of the summary in tokens (about 4 characters each), range 100-20000",
+        minimum = 100,
+        maximum = 20000,
+        default = 1000
+    )]
+    pub max_tokens: Option<u32>,
+
+    /// Exact character budget, overriding `max_tokens`
+    #[json_schema(
+        title = "Max Length",
+        description = "Size limit of the summary in characters; takes precedence over max_tokens",
+        minimum = 400,
+        maximum = 80000
+    )]
+    pub max_length: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured summary)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// An item listed on the crate front page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyItem {
+    pub name: String,
+    /// rustdoc item kind (`struct`, `trait`, `fn`, ...)
+    pub kind: String,
+    /// One-line summary from the item table
+    pub summary: Option<String>,
+    /// Documentation URL of the item
+    pub url: String,
+}
+
+/// Condensed overview of a crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateSummaryReport {
+    pub crate_name: String,
+    /// Version summarized (`latest` when it could not be determined)
+    pub version: String,
+    /// crates.io description
+    pub description: Option<String>,
+    /// First paragraph of the crate documentation
+    pub overview: Option<String>,
+    /// Key items, most important kinds (traits, structs, ...) first
+    pub key_items: Vec<KeyItem>,
+    /// Key items left out to fit the budget
+    #[serde(default)]
+    pub omitted_items: usize,
+    /// First runnable example of the crate documentation
+    pub usage: Option<String>,
+    /// Feature flags of the version
+    pub features: Vec<String>,
+    /// Feature flags left out to fit the budget
+    #[serde(default)]
+    pub omitted_features: usize,
+    /// Documentation URL of the crate
+    pub docs_url: String,
+    /// Whether anything was left out to fit the budget
+    pub truncated: bool,
+}
+
+impl CrateSummaryReport {
+    /// Leave out the least important remaining part; `false` once there is
+    /// nothing left to drop
+    ///
+    /// Long item and feature lists are shortened first, then the usage
+    /// snippet goes, then the rest of the lists and the overview paragraph.
+    /// The description and the links always stay.
+    fn shrink(&mut self) -> bool {
+        if self.key_items.len() > MIN_LISTED {
+            self.key_items.pop();
+            self.omitted_items += 1;
+        } else if self.features.len() > MIN_LISTED {
+            self.features.pop();
+            self.omitted_features += 1;
+        } else if self.usage.is_some() {
+            self.usage = None;
+        } else if !self.key_items.is_empty() {
+            self.key_items.pop();
+            self.omitted_items += 1;
+        } else if !self.features.is_empty() {
+            self.features.pop();
+            self.omitted_features += 1;
+        } else if self.overview.is_some() {
+            self.overview = None;
+        } else {
+            return false;
+        }
+        self.truncated = true;
+        true
+    }
+}
+
+/// Render `report` with `render`, shrinking it until the output fits
+/// `budget` characters
+///
+/// The output can still exceed the budget when even the minimal summary
+/// does not fit; callers cut prose output, JSON is returned whole.
+fn fit_to_budget(
+    report: &mut CrateSummaryReport,
+    budget: usize,
+    render: impl Fn(&CrateSummaryReport) -> String,
+) -> String {
+    let mut output = render(report);
+    while output.chars().count() > budget && report.shrink() {
+        output = render(report);
+    }
+    output
+}
+
+/// Sort rank of an item kind: what a new user of the crate reaches for first
+fn kind_rank(kind: &str) -> u8 {
+    match kind {
+        "trait" => 0,
+        "struct" => 1,
+        "enum" => 2,
+        "macro" | "attr" | "derive" => 3,
+        "fn" => 4,
+        "type" | "union" | "traitalias" => 5,
+        "mod" => 6,
+        _ => 7,
+    }
+}
+
+fn element_text(element: ElementRef<'_>) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// First paragraph of the crate documentation
+fn parse_overview(document: &Html) -> Option<String> {
+    document
+        .select(&OVERVIEW_SELECTOR)
+        .map(element_text)
+        .find(|text| !text.is_empty())
+        .map(|text| super::budget::shorten(&text, MAX_OVERVIEW_CHARS, super::Format::Text))
+}
+
+/// Items of the front page item tables, by kind rank
+///
+/// Re-exports are skipped: their tables list `pub use` paths rather than
+/// documented items.
+fn parse_key_items(document: &Html) -> Vec<KeyItem> {
+    let mut items: Vec<KeyItem> = Vec::new();
+    for cell in document.select(&ITEM_CELL_SELECTOR) {
+        let in_reexports = cell
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|a| a.value().classes().any(|c| c == "reexports"));
+        if in_reexports {
+            continue;
+        }
+        let Some(link) = cell.select(&ITEM_LINK_SELECTOR).next() else {
+            continue;
+        };
+        let name = element_text(link);
+        if name.is_empty() || items.iter().any(|item| item.name == name) {
+            continue;
+        }
+        // The summary is the next cell: `<dd>` or `<div class="desc">`
+        let summary = cell
+            .next_siblings()
+            .find_map(ElementRef::wrap)
+            .filter(|next| {
+                next.value().name() == "dd" || next.value().classes().any(|c| c == "desc")
+            })
+            .map(element_text)
+            .filter(|text| !text.is_empty())
+            .map(|text| super::budget::shorten(&text, MAX_ITEM_SUMMARY_CHARS, super::Format::Text));
+        items.push(KeyItem {
+            name,
+            kind: link.value().classes().next().unwrap_or("item").to_string(),
+            summary,
+            url: link.value().attr("href").unwrap_or_default().to_string(),
+        });
+    }
+    // Stable: keeps the page order within a kind
+    items.sort_by_key(|item| kind_rank(&item.kind));
+    items.truncate(MAX_KEY_ITEMS);
+    items
+}
+
+/// First example of the page that is meant to compile and run
+fn pick_usage(html: &str, crate_name: &str) -> Option<String> {
+    super::examples::extract_examples(html, &crate_name.replace('-', "_"))
+        .into_iter()
+        .find(|example| {
+            example.attributes.is_empty()
+                && example
+                    .language
+                    .as_deref()
+                    .is_none_or(|lang| lang == "rust")
+        })
+        .map(|example| {
+            let lines: Vec<&str> = example.code.lines().collect();
+            if lines.len() > MAX_USAGE_LINES {
+                format!("{}\n// ...", lines[..MAX_USAGE_LINES].join("\n"))
+            } else {
+                example.code
+            }
+        })
+}
+
+fn format_summary(report: &CrateSummaryReport, markdown: bool) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    let (heading, subheading) = if markdown { ("# ", "## ") } else { ("", "") };
+    writeln!(output, "{heading}{} {}", report.crate_name, report.version).unwrap();
+    if let Some(description) = &report.description {
+        writeln!(output, "\n{description}").unwrap();
+    }
+    if let Some(overview) = &report.overview {
+        writeln!(output, "\n{overview}").unwrap();
+    }
+
+    if !report.key_items.is_empty() {
+        writeln!(output, "\n{subheading}Key items").unwrap();
+        for item in &report.key_items {
+            let name = if markdown {
+                format!("`{}`", item.name)
+            } else {
+                item.name.clone()
+            };
+            match &item.summary {
+                Some(summary) => writeln!(output, "- {name} ({}): {summary}", item.kind).unwrap(),
+                None => writeln!(output, "- {name} ({})", item.kind).unwrap(),
+            }
+        }
+        if report.omitted_items > 0 {
+            writeln!(output, "- ... and {} more", report.omitted_items).unwrap();
+        }
+    }
+
+    if let Some(usage) = &report.usage {
+        writeln!(output, "\n{subheading}Usage").unwrap();
+        if markdown {
+            writeln!(output, "```rust\n{usage}\n```").unwrap();
+        } else {
+            writeln!(output, "{usage}").unwrap();
+        }
+    }
+
+    if !report.features.is_empty() || report.omitted_features > 0 {
+        writeln!(output, "\n{subheading}Features").unwrap();
+        let mut features: Vec<String> = if markdown {
+            report.features.iter().map(|f| format!("`{f}`")).collect()
+        } else {
+            report.features.clone()
+        };
+        if report.omitted_features > 0 {
+            features.push(format!("... and {} more", report.omitted_features));
+        }
+        writeln!(output, "{}", features.join(", ")).unwrap();
+    }
+
+    if markdown {
+        writeln!(output, "\n**Docs**: <{}>", report.docs_url).unwrap();
+    } else {
+        writeln!(output, "\nDocs: {}", report.docs_url).unwrap();
+    }
+    output
+}
+
+/// Implementation of the crate summary tool
+pub struct SummarizeCrateToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl SummarizeCrateToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Feature flags of `version`; empty if the index cannot tell
+    async fn fetch_features(&self, crate_name: &str, version: Option<&str>) -> Vec<String> {
+        let spec = version
+            .and_then(VersionSpec::parse)
+            .unwrap_or(VersionSpec::Latest);
+        match resolve_version::fetch_index_versions(&self.service, crate_name, TOOL_NAME).await {
+            Ok(versions) => resolve_version::select_version(&versions, &spec)
+                .map(|selected| selected.features.clone())
+                .unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("[{TOOL_NAME}] feature flags unavailable for {crate_name}: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Collect the full, untrimmed summary
+    async fn fetch_summary(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+    ) -> std::result::Result<CrateSummaryReport, CallToolError> {
+        let html = super::lookup_crate::LookupCrateToolImpl::new(self.service.clone())
+            .fetch_crate_html(crate_name, version)
+            .await?;
+        let (overview, key_items) = {
+            let document = Html::parse_document(&html);
+            (parse_overview(&document), parse_key_items(&document))
+        };
+        let usage = pick_usage(&html, crate_name);
+
+        // The Rust distribution crates are neither on crates.io nor in the index
+        let (description, latest, features) = if super::is_rust_std_crate(crate_name) {
+            (None, None, Vec::new())
+        } else {
+            let summary =
+                match super::batch::fetch_crate_summary(&self.service, crate_name, TOOL_NAME).await
+                {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        tracing::warn!(
+                            "[{TOOL_NAME}] crates.io summary unavailable for {crate_name}: {e}"
+                        );
+                        None
+                    }
+                };
+            let features = self.fetch_features(crate_name, version).await;
+            let (description, latest) =
+                summary.map_or((None, None), |s| (s.description, Some(s.version)));
+            (description, latest, features)
+        };
+
+        Ok(CrateSummaryReport {
+            crate_name: crate_name.to_string(),
+            version: version
+                .map(str::to_string)
+                .or(latest)
+                .unwrap_or_else(|| "latest".to_string()),
+            description: description
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty()),
+            overview,
+            key_items,
+            omitted_items: 0,
+            usage,
+            features,
+            omitted_features: 0,
+            docs_url: self.service.crate_docs_url(crate_name, version),
+            truncated: false,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for SummarizeCrateToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SummarizeCrateTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SummarizeCrateTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        // Unlike the lookup tools, the summary always has a budget
+        let max_tokens = params
+            .max_tokens
+            .unwrap_or(DEFAULT_MAX_TOKENS)
+            .min(MAX_MAX_TOKENS);
+        let max_length = params.max_length.map(|length| length.min(MAX_MAX_LENGTH));
+        let budget = super::budget::char_budget(Some(max_tokens), max_length)
+            .unwrap_or(max_tokens as usize * super::budget::CHARS_PER_TOKEN);
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        let mut report = self.fetch_summary(crate_name, version.as_deref()).await?;
+        let content = match format {
+            super::Format::Json => fit_to_budget(&mut report, budget, |report| {
+                serde_json::to_string_pretty(report).unwrap_or_default()
+            }),
+            super::Format::Text => super::budget::shorten(
+                &fit_to_budget(&mut report, budget, |r| format_summary(r, false)),
+                budget,
+                format,
+            ),
+            _ => super::budget::shorten(
+                &fit_to_budget(&mut report, budget, |r| format_summary(r, true)),
+                budget,
+                super::Format::Markdown,
+            ),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for SummarizeCrateToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRONT_PAGE: &str = concat!(
+        "<html><body><section id=\"main-content\">",
+        "<details class=\"toggle top-doc\"><div class=\"docblock\">",
+        "<p>A  <em>demo</em> crate.</p><p>Second paragraph.</p></div></details>",
+        "<dl class=\"item-table reexports\"><dt><code>pub use other::Thing;</code></dt></dl>",
+        "<ul class=\"item-table\"><li><div class=\"item-name\"><a class=\"mod\" href=\"de/index.html\">de</a></div>",
+        "<div class=\"desc docblock-short\">Deserialization.</div></li></ul>",
+        "<dl class=\"item-table\"><dt><a class=\"struct\" href=\"struct.Config.html\">Config</a></dt>",
+        "<dd>Settings.</dd>",
+        "<dt><a class=\"trait\" href=\"trait.Run.html\">Run</a></dt></dl>",
+        "</section></body></html>"
+    );
+
+    fn report() -> CrateSummaryReport {
+        CrateSummaryReport {
+            crate_name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            description: Some("A demo crate".to_string()),
+            overview: Some("Overview.".to_string()),
+            key_items: (0..10)
+                .map(|i| KeyItem {
+                    name: format!("Item{i}"),
+                    kind: "struct".to_string(),
+                    summary: Some("Does something useful.".to_string()),
+                    url: format!("https://docs.rs/demo/1.0.0/demo/struct.Item{i}.html"),
+                })
+                .collect(),
+            omitted_items: 0,
+            usage: Some("let x = demo::Item0::new();".to_string()),
+            features: vec!["default".to_string(), "std".to_string()],
+            omitted_features: 0,
+            docs_url: "https://docs.rs/demo/1.0.0/".to_string(),
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_front_page() {
+        let document = Html::parse_document(FRONT_PAGE);
+        assert_eq!(parse_overview(&document).as_deref(), Some("A demo crate."));
+        let items = parse_key_items(&document);
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Run", "Config", "de"]);
+        assert_eq!(items[1].summary.as_deref(), Some("Settings."));
+        assert_eq!(items[2].summary.as_deref(), Some("Deserialization."));
+        assert_eq!(items[0].summary, None);
+        assert_eq!(items[0].url, "trait.Run.html");
+    }
+
+    #[test]
+    fn test_fit_to_budget_drops_least_important_first() {
+        let mut full = report();
+        let output = fit_to_budget(&mut full, 10_000, |r| format_summary(r, true));
+        assert!(!full.truncated);
+        assert!(output.contains("`Item9` (struct): Does something useful."));
+
+        let mut small = report();
+        let output = fit_to_budget(&mut small, 400, |r| format_summary(r, true));
+        assert!(output.chars().count() <= 400, "{output}");
+        assert!(small.truncated);
+        assert!(small.omitted_items > 0);
+        assert!(output.contains("A demo crate"));
+        assert!(output.contains("https://docs.rs/demo/1.0.0/"));
+        assert!(output.contains(&format!("... and {} more", small.omitted_items)));
+
+        let mut tiny = report();
+        fit_to_budget(&mut tiny, 10, |r| format_summary(r, false));
+        assert!(tiny.key_items.is_empty() && tiny.features.is_empty());
+        assert!(tiny.usage.is_none() && tiny.overview.is_none());
+        assert!(tiny.description.is_some());
+    }
+}
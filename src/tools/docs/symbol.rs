@@ -0,0 +1,679 @@
+//! Cross-crate symbol search tool
+//!
+//! Answers "where is `DeserializeOwned` defined?" by searching the `all.html`
+//! item index of several crates for a type, trait, function or macro name.
+//! Candidates are ranked by how closely the name matches and then by crate
+//! popularity (crates.io downloads).
+//!
+//! Each crate's index is reduced to a compact list of item paths and cached,
+//! so repeated searches over popular crates do not refetch anything.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use tokio::task::JoinSet;
+
+use super::search::{parse_crates_response, SearchCratesResponse};
+
+const TOOL_NAME: &str = "find_symbol";
+
+/// Number of most downloaded crates searched when no crates are given
+const DEFAULT_CANDIDATE_CRATES: u32 = 25;
+
+/// Maximum number of crates a caller may name explicitly
+const MAX_CRATES: usize = 25;
+
+/// Default and maximum number of returned matches
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 50;
+
+/// Crate indexes fetched at the same time
+const MAX_CONCURRENT_FETCHES: usize = 5;
+
+/// Item links in `all.html`: optional module directories, kind and name
+static ITEM_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"href="((?:[A-Za-z0-9_]+/)*)(struct|trait|enum|fn|type|macro|attr|constant|derive|union|static|traitalias|primitive)\.([A-Za-z0-9_]+)\.html""#,
+    )
+    .expect("hardcoded valid regex")
+});
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "find_symbol",
+    title = "Find Symbol Across Crates",
+    description = "Find which crate and module define a type, trait, function or macro name (e.g. DeserializeOwned, Bytes, json!). Searches the item indexes of the given crates, or of the most downloaded crates when none are given, and ranks candidates by name match and crate popularity.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `find_symbol` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct FindSymbolTool {
+    /// Symbol name or path tail (e.g., "`DeserializeOwned`", "`de::DeserializeOwned`")
+    #[json_schema(
+        title = "Symbol",
+        description = "Item name to find, optionally with a trailing path, e.g.: DeserializeOwned, de::DeserializeOwned, json!"
+    )]
+    pub symbol: String,
+
+    /// Crates to search (defaults to the most downloaded crates)
+    #[json_schema(
+        title = "Crates",
+        description = "Crates to search (max 25). Defaults to the 25 most downloaded crates on crates.io"
+    )]
+    pub crates: Option<Vec<String>>,
+
+    /// Maximum number of matches (1-50, defaults to 10)
+    #[json_schema(
+        title = "Result Limit",
+        description = "Maximum number of matches to return",
+        minimum = 1,
+        maximum = 50,
+        default = 10
+    )]
+    pub limit: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured candidates)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// One item of a crate's index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct IndexEntry {
+    /// Module path below the crate root, without the item name (`de`)
+    pub(super) module: String,
+    /// Item kind as used in rustdoc file names (`trait`)
+    pub(super) kind: String,
+    /// Item name (`DeserializeOwned`)
+    pub(super) name: String,
+}
+
+impl IndexEntry {
+    /// Page of the item relative to the crate root, as linked from `all.html`
+    pub(super) fn href(&self) -> String {
+        if self.module.is_empty() {
+            format!("{}.{}.html", self.kind, self.name)
+        } else {
+            format!("{}/{}.{}.html", self.module, self.kind, self.name)
+        }
+    }
+
+    /// Full Rust path of the item within `crate_name`
+    pub(super) fn path(&self, crate_name: &str) -> String {
+        let root = crate_name.replace('-', "_");
+        if self.module.is_empty() {
+            format!("{root}::{}", self.name)
+        } else {
+            format!("{root}::{}::{}", self.module.replace('/', "::"), self.name)
+        }
+    }
+}
+
+/// How closely a candidate name matches the searched symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MatchQuality {
+    /// Name contains the symbol, ignoring case
+    Partial,
+    /// Same name, different case
+    CaseInsensitive,
+    /// Same name
+    Exact,
+}
+
+/// A candidate definition of the searched symbol
+#[derive(Debug, Clone, Serialize)]
+struct SymbolMatch {
+    #[serde(rename = "crate")]
+    crate_name: String,
+    path: String,
+    kind: String,
+    url: String,
+    #[serde(rename = "match")]
+    quality: MatchQuality,
+    downloads: Option<u64>,
+}
+
+/// Result of a cross-crate symbol search
+#[derive(Debug, Serialize)]
+struct SymbolSearch {
+    symbol: String,
+    crates_searched: Vec<String>,
+    /// Crates whose index could not be fetched
+    unavailable: Vec<String>,
+    matches: Vec<SymbolMatch>,
+}
+
+/// Split a symbol into its name and optional module path suffix
+///
+/// `de::DeserializeOwned` yields `("DeserializeOwned", "de")` and a trailing
+/// `!` (as in `json!`) is dropped.
+fn split_symbol(symbol: &str) -> (&str, &str) {
+    let symbol = symbol.trim().trim_end_matches('!');
+    match symbol.rsplit_once("::") {
+        Some((module, name)) => (name, module),
+        None => (symbol, ""),
+    }
+}
+
+/// Parse a crate's `all.html` into its item index
+pub(super) fn parse_all_items(all_html: &str) -> Vec<IndexEntry> {
+    let mut entries: Vec<IndexEntry> = ITEM_LINK_RE
+        .captures_iter(all_html)
+        .map(|caps| IndexEntry {
+            module: caps[1].trim_end_matches('/').to_string(),
+            kind: caps[2].to_string(),
+            name: caps[3].to_string(),
+        })
+        .collect();
+    entries.dedup();
+    entries
+}
+
+/// Match quality of an index entry, or `None` if it does not match
+///
+/// A module suffix (`de` in `de::DeserializeOwned`) must match the end of the
+/// entry's module path, which starts at the crate root (`serde::de`).
+fn match_quality(
+    entry: &IndexEntry,
+    crate_name: &str,
+    name: &str,
+    module: &str,
+) -> Option<MatchQuality> {
+    if !module.is_empty() {
+        let path = entry.path(crate_name);
+        let entry_module = path.rsplit_once("::").map_or("", |(module, _)| module);
+        if entry_module != module && !entry_module.ends_with(&format!("::{module}")) {
+            return None;
+        }
+    }
+    if entry.name == name {
+        Some(MatchQuality::Exact)
+    } else if entry.name.eq_ignore_ascii_case(name) {
+        Some(MatchQuality::CaseInsensitive)
+    } else if name.len() >= 3
+        && entry
+            .name
+            .to_ascii_lowercase()
+            .contains(&name.to_ascii_lowercase())
+    {
+        Some(MatchQuality::Partial)
+    } else {
+        None
+    }
+}
+
+/// Order candidates: best match first, then most downloaded crate, then the
+/// shortest (most public-looking) path
+fn rank_matches(matches: &mut [SymbolMatch]) {
+    matches.sort_by(|a, b| {
+        b.quality
+            .cmp(&a.quality)
+            .then_with(|| b.downloads.unwrap_or(0).cmp(&a.downloads.unwrap_or(0)))
+            .then_with(|| a.path.len().cmp(&b.path.len()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+}
+
+/// Load the item index of the latest docs of a crate, from cache or upstream
+///
+/// Returns `Ok(None)` when no provider has docs for the crate. Errors are
+/// plain strings so the future can run on a [`JoinSet`].
+async fn load_index(
+    service: Arc<super::DocService>,
+    crate_name: String,
+) -> Result<Option<Vec<IndexEntry>>, String> {
+    let doc_cache = service.doc_cache();
+    if let Some(cached) = doc_cache.get_symbol_index(&crate_name).await {
+        if let Ok(entries) = serde_json::from_str(&cached) {
+            return Ok(Some(entries));
+        }
+    }
+
+    for provider in service.providers_for(&crate_name) {
+        let url = provider.all_items_url(&crate_name, None);
+        let page = service
+            .fetch_html_optional(&url, Some(TOOL_NAME))
+            .await
+            .map_err(|e| e.to_string())?;
+        let Some(page) = page else {
+            continue;
+        };
+        let entries = parse_all_items(&page);
+        match serde_json::to_string(&entries) {
+            Ok(value) => {
+                if let Err(e) = doc_cache.set_symbol_index(&crate_name, value).await {
+                    tracing::warn!("[{TOOL_NAME}] failed to cache symbol index: {e}");
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize symbol index: {e}"),
+        }
+        return Ok(Some(entries));
+    }
+    Ok(None)
+}
+
+/// Item paths in the latest docs of `crate_name` starting with `prefix`, for
+/// argument completion
+///
+/// `prefix` is the start of a path (`serde::de::Deser`) or, without `::`,
+/// of an item name (`deser`, ignoring case). Empty when the crate has no
+/// item index.
+pub async fn complete_item_paths(
+    service: &Arc<super::DocService>,
+    crate_name: &str,
+    prefix: &str,
+) -> Vec<String> {
+    match load_index(Arc::clone(service), crate_name.to_string()).await {
+        Ok(Some(entries)) => item_paths_with_prefix(crate_name, &entries, prefix.trim()),
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            tracing::warn!("item path completions for '{crate_name}' unavailable: {e}");
+            Vec::new()
+        }
+    }
+}
+
+fn item_paths_with_prefix(crate_name: &str, entries: &[IndexEntry], prefix: &str) -> Vec<String> {
+    let by_path = prefix.contains("::");
+    let name_prefix = prefix.to_lowercase();
+    let mut paths: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path(crate_name);
+            let matches = if by_path {
+                path.starts_with(prefix)
+            } else {
+                entry.name.to_lowercase().starts_with(&name_prefix)
+            };
+            matches.then_some(path)
+        })
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn format_search(search: &SymbolSearch, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let searched = search.crates_searched.len();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Symbol `{}`\n", search.symbol).unwrap();
+        writeln!(
+            output,
+            "Searched {searched} crate(s), ranked by name match and downloads.\n"
+        )
+        .unwrap();
+    } else {
+        writeln!(output, "Symbol {}", search.symbol).unwrap();
+        writeln!(output, "Searched {searched} crate(s)\n").unwrap();
+    }
+
+    if search.matches.is_empty() {
+        writeln!(output, "No matching items found.").unwrap();
+    }
+    for (i, candidate) in search.matches.iter().enumerate() {
+        let downloads = candidate
+            .downloads
+            .map(|d| format!(" · {d} downloads"))
+            .unwrap_or_default();
+        if markdown {
+            writeln!(
+                output,
+                "{}. [`{}`]({}) ({}) — crate `{}`{downloads}",
+                i + 1,
+                candidate.path,
+                candidate.url,
+                candidate.kind,
+                candidate.crate_name
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                output,
+                "{}. {} ({}) - {}{downloads}\n   {}",
+                i + 1,
+                candidate.path,
+                candidate.kind,
+                candidate.crate_name,
+                candidate.url
+            )
+            .unwrap();
+        }
+    }
+
+    if !search.unavailable.is_empty() {
+        let list = search.unavailable.join(", ");
+        if markdown {
+            writeln!(output, "\n_Index unavailable for: {list}_").unwrap();
+        } else {
+            writeln!(output, "\nIndex unavailable for: {list}").unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the cross-crate symbol search tool
+pub struct FindSymbolToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl FindSymbolToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    /// Download counts of `crates`, or of the most downloaded crates when
+    /// `crates` is empty
+    async fn fetch_downloads(
+        &self,
+        crates: &[String],
+    ) -> Result<HashMap<String, u64>, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        let limit = DEFAULT_CANDIDATE_CRATES;
+        if let Some(cached) = doc_cache.get_crate_downloads(crates, limit).await {
+            if let Ok(downloads) = serde_json::from_str(&cached) {
+                return Ok(downloads);
+            }
+        }
+
+        let url = if crates.is_empty() {
+            super::build_crates_io_listing_url("downloads", None, None, limit as usize)
+        } else {
+            super::build_crates_io_ids_url(crates)
+        };
+        let body = self.service.fetch_html(&url, Some(TOOL_NAME)).await?;
+        let response: SearchCratesResponse = serde_json::from_str(&body).map_err(|e| {
+            CallToolError::from_message(format!(
+                "[{TOOL_NAME}] Failed to parse crates.io response: {e}"
+            ))
+        })?;
+        let downloads: HashMap<String, u64> = parse_crates_response(response, usize::MAX)
+            .into_iter()
+            .map(|info| (info.name, info.downloads))
+            .collect();
+
+        match serde_json::to_string(&downloads) {
+            Ok(value) => {
+                if let Err(e) = doc_cache.set_crate_downloads(crates, limit, value).await {
+                    tracing::warn!("[{TOOL_NAME}] failed to cache crate downloads: {e}");
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize crate downloads: {e}"),
+        }
+        Ok(downloads)
+    }
+
+    async fn search(
+        &self,
+        symbol: &str,
+        crates: Vec<String>,
+        limit: usize,
+    ) -> Result<SymbolSearch, CallToolError> {
+        let (downloads, crates) = if crates.is_empty() {
+            // Without explicit crates the popularity listing is the candidate
+            // list, so it cannot be skipped.
+            let downloads = self.fetch_downloads(&[]).await?;
+            let mut crates: Vec<String> = downloads.keys().cloned().collect();
+            crates.sort_by(|a, b| downloads[b].cmp(&downloads[a]).then_with(|| a.cmp(b)));
+            (downloads, crates)
+        } else {
+            // Popularity only orders the results; an outage should not hide them
+            let downloads = self.fetch_downloads(&crates).await.unwrap_or_else(|e| {
+                tracing::warn!("[{TOOL_NAME}] crate downloads unavailable: {e:?}");
+                HashMap::new()
+            });
+            (downloads, crates)
+        };
+
+        let mut indexes = Vec::with_capacity(crates.len());
+        let mut unavailable = Vec::new();
+        for chunk in crates.chunks(MAX_CONCURRENT_FETCHES) {
+            let mut tasks = JoinSet::new();
+            for crate_name in chunk {
+                let service = Arc::clone(&self.service);
+                let crate_name = crate_name.clone();
+                tasks.spawn(async move {
+                    let index = load_index(service, crate_name.clone()).await;
+                    (crate_name, index)
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                let Ok((crate_name, index)) = joined else {
+                    continue;
+                };
+                match index {
+                    Ok(Some(entries)) => indexes.push((crate_name, entries)),
+                    Ok(None) => unavailable.push(crate_name),
+                    Err(e) => {
+                        tracing::warn!("[{TOOL_NAME}] failed to index {crate_name}: {e}");
+                        unavailable.push(crate_name);
+                    }
+                }
+            }
+        }
+        unavailable.sort();
+
+        let (name, module) = split_symbol(symbol);
+        let mut matches = Vec::new();
+        for (crate_name, entries) in &indexes {
+            let provider = self.service.providers_for(crate_name).next();
+            for entry in entries {
+                let Some(quality) = match_quality(entry, crate_name, name, module) else {
+                    continue;
+                };
+                let href = entry.href();
+                let url = provider.map_or_else(
+                    || href.clone(),
+                    |provider| provider.item_url_from_href(crate_name, None, &href),
+                );
+                matches.push(SymbolMatch {
+                    crate_name: crate_name.clone(),
+                    path: entry.path(crate_name),
+                    kind: entry.kind.clone(),
+                    url,
+                    quality,
+                    downloads: downloads.get(crate_name).copied(),
+                });
+            }
+        }
+        rank_matches(&mut matches);
+        matches.truncate(limit);
+
+        Ok(SymbolSearch {
+            symbol: symbol.trim().to_string(),
+            crates_searched: crates,
+            unavailable,
+            matches,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for FindSymbolToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        FindSymbolTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: FindSymbolTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let symbol = params.symbol.trim();
+        super::validate_item_path(TOOL_NAME, symbol.trim_end_matches('!'))?;
+
+        let mut crates = Vec::new();
+        for crate_name in params.crates.unwrap_or_default() {
+            super::validate_crate_name(TOOL_NAME, &crate_name)?;
+            let crate_name = crate_name.trim().to_string();
+            if !crates.contains(&crate_name) {
+                crates.push(crate_name);
+            }
+        }
+        if crates.len() > MAX_CRATES {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "At most {MAX_CRATES} crates can be searched at once"
+                )),
+            ));
+        }
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+
+        let search = self.search(symbol, crates, limit).await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&search).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] JSON serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_search(&search, false),
+            _ => format_search(&search, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for FindSymbolToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_HTML: &str = concat!(
+        "<ul class=\"all-items\">",
+        "<li><a href=\"de/trait.DeserializeOwned.html\">de::DeserializeOwned</a></li>",
+        "<li><a href=\"de/trait.Deserialize.html\">de::Deserialize</a></li>",
+        "<li><a href=\"trait.Deserialize.html\">Deserialize</a></li>",
+        "<li><a href=\"macro.forward_to_deserialize_any.html\">forward_to_deserialize_any</a></li>",
+        "</ul>"
+    );
+
+    #[test]
+    fn test_parse_all_items() {
+        let entries = parse_all_items(ALL_HTML);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].module, "de");
+        assert_eq!(entries[0].kind, "trait");
+        assert_eq!(entries[0].name, "DeserializeOwned");
+        assert_eq!(entries[0].path("serde"), "serde::de::DeserializeOwned");
+        assert_eq!(entries[0].href(), "de/trait.DeserializeOwned.html");
+        assert_eq!(entries[2].path("serde-json"), "serde_json::Deserialize");
+    }
+
+    #[test]
+    fn test_item_paths_with_prefix() {
+        let entries = parse_all_items(ALL_HTML);
+        assert_eq!(
+            item_paths_with_prefix("serde", &entries, "serde::de::"),
+            ["serde::de::Deserialize", "serde::de::DeserializeOwned"]
+        );
+        assert_eq!(
+            item_paths_with_prefix("serde", &entries, "deserialize"),
+            [
+                "serde::Deserialize",
+                "serde::de::Deserialize",
+                "serde::de::DeserializeOwned"
+            ]
+        );
+        assert!(item_paths_with_prefix("serde", &entries, "serde::ser::").is_empty());
+    }
+
+    #[test]
+    fn test_match_quality() {
+        let entries = parse_all_items(ALL_HTML);
+        assert_eq!(
+            match_quality(&entries[1], "serde", "Deserialize", ""),
+            Some(MatchQuality::Exact)
+        );
+        assert_eq!(
+            match_quality(&entries[1], "serde", "deserialize", ""),
+            Some(MatchQuality::CaseInsensitive)
+        );
+        assert_eq!(
+            match_quality(&entries[0], "serde", "Deserialize", ""),
+            Some(MatchQuality::Partial)
+        );
+        assert_eq!(match_quality(&entries[0], "serde", "De", ""), None);
+        // A module suffix must match the item's module
+        assert_eq!(
+            match_quality(&entries[2], "serde", "Deserialize", "de"),
+            None
+        );
+        assert_eq!(
+            match_quality(&entries[1], "serde", "Deserialize", "de"),
+            Some(MatchQuality::Exact)
+        );
+        assert_eq!(
+            match_quality(&entries[1], "serde", "Deserialize", "serde::de"),
+            Some(MatchQuality::Exact)
+        );
+    }
+
+    #[test]
+    fn test_split_symbol() {
+        assert_eq!(split_symbol("DeserializeOwned"), ("DeserializeOwned", ""));
+        assert_eq!(
+            split_symbol("serde::de::DeserializeOwned"),
+            ("DeserializeOwned", "serde::de")
+        );
+        assert_eq!(split_symbol("json!"), ("json", ""));
+    }
+
+    #[test]
+    fn test_rank_matches_by_quality_then_downloads() {
+        let candidate = |crate_name: &str, quality, downloads| SymbolMatch {
+            crate_name: crate_name.to_string(),
+            path: format!("{crate_name}::Bytes"),
+            kind: "struct".to_string(),
+            url: String::new(),
+            quality,
+            downloads,
+        };
+        let mut matches = vec![
+            candidate("small", MatchQuality::Exact, Some(10)),
+            candidate("huge", MatchQuality::Partial, Some(1_000_000)),
+            candidate("bytes", MatchQuality::Exact, Some(500_000)),
+            candidate("unknown", MatchQuality::Exact, None),
+        ];
+        rank_matches(&mut matches);
+        let order: Vec<&str> = matches.iter().map(|m| m.crate_name.as_str()).collect();
+        assert_eq!(order, ["bytes", "small", "unknown", "huge"]);
+    }
+}
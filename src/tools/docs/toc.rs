@@ -0,0 +1,681 @@
+//! Table of contents tool and section extraction
+//!
+//! Crate front pages and item pages can be long. `get_toc` returns just the
+//! heading outline of a page, each heading with its rustdoc anchor, and
+//! `lookup_crate` / `lookup_item` accept one of those anchors as `section` to
+//! return only that part of the page, or heading names as `sections` (e.g.
+//! `["Examples", "Methods"]`) to return just those parts.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use regex::Regex;
+use rust_mcp_sdk::schema::CallToolError;
+use scraper::{node::Node, ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+
+const TOOL_NAME: &str = "get_toc";
+
+/// Longest accepted anchor; rustdoc impl anchors can be long but not this long
+const MAX_ANCHOR_LEN: usize = 256;
+
+/// Most heading names accepted in one `sections` parameter
+const MAX_SECTION_NAMES: usize = 20;
+
+/// Common names for headings rustdoc titles differently
+const SECTION_ALIASES: &[(&str, &str)] = &[("methods", "implementations")];
+
+static MAIN_CONTENT_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("#main-content").expect("hardcoded valid selector"));
+
+static HEADING_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("h1, h2, h3, h4, h5, h6").expect("hardcoded valid selector"));
+
+static ID_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("[id]").expect("hardcoded valid selector"));
+
+static WHITESPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s+").expect("hardcoded valid regex"));
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "get_toc",
+    title = "Get Documentation Table of Contents",
+    description = "Get the heading outline (with anchors) of a crate's documentation front page, or of an item page when item_path is given, without the page content. Pass an anchor as the section parameter of lookup_crate or lookup_item to fetch only that part of the page.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `get_toc` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct GetTocTool {
+    /// Crate name (e.g., "serde", "tokio", "std")
+    #[json_schema(
+        title = "Crate Name",
+        description = "Crate name to lookup, e.g.: serde, tokio, std"
+    )]
+    pub crate_name: String,
+
+    /// Item path; the crate front page is used when omitted
+    #[json_schema(
+        title = "Item Path",
+        description = "Optional item path, e.g.: serde::Serialize, tokio::runtime::Runtime. Uses the crate front page if not specified"
+    )]
+    pub item_path: Option<String>,
+
+    /// Crate version or semver requirement (defaults to latest)
+    #[json_schema(
+        title = "Version",
+        description = "Crate version or semver requirement, e.g.: 1.0.0, ^1.2. Uses latest version if not specified"
+    )]
+    pub version: Option<String>,
+
+    /// Also list methods, associated items and impl blocks
+    #[json_schema(
+        title = "Include Members",
+        description = "Also list methods, associated items and impl blocks of a type page (defaults to false)",
+        default = false
+    )]
+    pub include_members: Option<bool>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (headings with level and anchor)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// A heading of a documentation page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    /// Heading level (`2` for `<h2>`, ...)
+    pub level: u8,
+    pub title: String,
+    /// Anchor to pass as `section`
+    pub anchor: String,
+}
+
+/// Outline of a documentation page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Toc {
+    /// Page heading, e.g. `Crate serde` or `Trait serde::Serialize`
+    pub title: Option<String>,
+    pub entries: Vec<TocEntry>,
+}
+
+fn heading_level(element: ElementRef<'_>) -> Option<u8> {
+    match element.value().name() {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+fn has_class(element: ElementRef<'_>, class: &str) -> bool {
+    element.value().classes().any(|c| c == class)
+}
+
+fn heading_title(element: ElementRef<'_>) -> String {
+    let text: String = element.text().collect();
+    WHITESPACE_RE
+        .replace_all(text.replace('\u{00a7}', " ").trim(), " ")
+        .into_owned()
+}
+
+/// The anchor of a heading: its own id, or for a rustdoc code header
+/// (`<h4 class="code-header">`) the id of its `<section>`
+fn heading_anchor(element: ElementRef<'_>) -> Option<String> {
+    if let Some(id) = element.value().id() {
+        return Some(id.to_string());
+    }
+    if !has_class(element, "code-header") {
+        return None;
+    }
+    let parent = element.parent().and_then(ElementRef::wrap)?;
+    parent.value().id().map(str::to_string)
+}
+
+/// Whether a heading belongs to a method, associated item or impl block
+fn is_member_heading(element: ElementRef<'_>) -> bool {
+    has_class(element, "code-header")
+        || element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|a| has_class(a, "impl-items") || has_class(a, "method-toggle"))
+}
+
+/// Extract the heading outline of a rustdoc page
+#[must_use]
+pub fn extract_toc(html: &str, include_members: bool) -> Toc {
+    let document = Html::parse_document(html);
+    let Some(main) = document.select(&MAIN_CONTENT_SELECTOR).next() else {
+        return Toc {
+            title: None,
+            entries: Vec::new(),
+        };
+    };
+    let mut title = None;
+    let mut entries = Vec::new();
+    for heading in main.select(&HEADING_SELECTOR) {
+        let Some(level) = heading_level(heading) else {
+            continue;
+        };
+        if level == 1 {
+            title.get_or_insert_with(|| heading_title(heading));
+            continue;
+        }
+        if !include_members && is_member_heading(heading) {
+            continue;
+        }
+        let Some(anchor) = heading_anchor(heading) else {
+            continue;
+        };
+        let heading_text = heading_title(heading);
+        if heading_text.is_empty() {
+            continue;
+        }
+        entries.push(TocEntry {
+            level,
+            title: heading_text,
+            anchor,
+        });
+    }
+    Toc { title, entries }
+}
+
+/// Validate a `section` parameter, returning the bare anchor (without `#`)
+///
+/// # Errors
+///
+/// Returns an invalid-arguments error for an empty or oversized anchor
+pub fn validate_section<'a>(tool_name: &str, section: &'a str) -> Result<&'a str, CallToolError> {
+    let anchor = section.trim().trim_start_matches('#');
+    if anchor.is_empty() || anchor.len() > MAX_ANCHOR_LEN {
+        return Err(CallToolError::invalid_arguments(
+            tool_name,
+            Some(format!(
+                "Invalid section '{section}'. Expected an anchor from get_toc, e.g.: examples"
+            )),
+        ));
+    }
+    Ok(anchor)
+}
+
+/// The section behind the element with id `anchor`, as HTML
+fn section_body(main: ElementRef<'_>, anchor: &str) -> Option<String> {
+    let target = main
+        .select(&ID_SELECTOR)
+        .find(|e| e.value().id() == Some(anchor))?;
+
+    let mut body = String::new();
+    if let Some(level) = heading_level(target) {
+        body.push_str(&target.html());
+        for sibling in target.next_siblings() {
+            if let Some(element) = ElementRef::wrap(sibling) {
+                if heading_level(element).is_some_and(|l| l <= level) {
+                    break;
+                }
+                body.push_str(&element.html());
+            } else if let Node::Text(text) = sibling.value() {
+                body.push_str(&super::html::escape_html_text(text));
+            }
+        }
+    } else {
+        // `<details class="toggle"><summary><section id=...>` holds a member's
+        // header and its docs.
+        let toggle = target
+            .parent()
+            .and_then(ElementRef::wrap)
+            .filter(|p| p.value().name() == "summary")
+            .and_then(|summary| summary.parent())
+            .and_then(ElementRef::wrap)
+            .filter(|d| d.value().name() == "details");
+        body.push_str(&toggle.unwrap_or(target).html());
+    }
+    Some(body)
+}
+
+/// A minimal page holding the page `<h1>` of `main` and `body`
+fn section_page(main: ElementRef<'_>, body: &str) -> String {
+    let title = main
+        .select(&HEADING_SELECTOR)
+        .find(|h| h.value().name() == "h1")
+        .map(|h| h.html())
+        .unwrap_or_default();
+    format!("<html><body><section id=\"main-content\">{title}{body}</section></body></html>")
+}
+
+/// Narrow a rustdoc page to the section behind `anchor`
+///
+/// For a heading this is the heading plus its following siblings up to the
+/// next heading of the same or a higher level; for a method or impl header,
+/// its whole toggle (header and docs). The result is a minimal page holding
+/// the page `<h1>` and the section, so the usual extractors apply to it.
+/// Returns `None` when the page has no element with that id.
+#[must_use]
+pub fn extract_section(html: &str, anchor: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let main = document.select(&MAIN_CONTENT_SELECTOR).next()?;
+    let body = section_body(main, anchor)?;
+    Some(section_page(main, &body))
+}
+
+fn normalize_title(title: &str) -> String {
+    WHITESPACE_RE.replace_all(title.trim(), " ").to_lowercase()
+}
+
+/// Indices of the outline entries whose title matches one of `names`
+///
+/// A name matches headings with that exact title (ignoring case), or failing
+/// that, headings containing it. Sections nested in another selected section
+/// are dropped since they come with it. Returns the first name nothing
+/// matches as the error.
+fn match_sections<'a>(toc: &Toc, names: &'a [String]) -> Result<Vec<usize>, &'a str> {
+    let titles: Vec<String> = toc
+        .entries
+        .iter()
+        .map(|e| normalize_title(&e.title))
+        .collect();
+    let mut selected = Vec::new();
+    for name in names {
+        let wanted = normalize_title(name);
+        let wanted = SECTION_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == wanted)
+            .map_or(wanted.clone(), |(_, title)| (*title).to_string());
+        let exact: Vec<usize> = (0..titles.len()).filter(|&i| titles[i] == wanted).collect();
+        let matched = if exact.is_empty() {
+            (0..titles.len())
+                .filter(|&i| titles[i].contains(&wanted))
+                .collect()
+        } else {
+            exact
+        };
+        if matched.is_empty() {
+            return Err(name);
+        }
+        selected.extend(matched);
+    }
+    selected.sort_unstable();
+    selected.dedup();
+
+    let mut kept = Vec::new();
+    let mut covered_until = 0;
+    for index in selected {
+        if index < covered_until {
+            continue;
+        }
+        let level = toc.entries[index].level;
+        covered_until = toc.entries[index + 1..]
+            .iter()
+            .position(|e| e.level <= level)
+            .map_or(toc.entries.len(), |offset| index + 1 + offset);
+        kept.push(index);
+    }
+    Ok(kept)
+}
+
+/// Which part of a page `lookup_crate` / `lookup_item` return
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionSelection {
+    /// The section behind one anchor (`section`)
+    Anchor(String),
+    /// The sections titled by these heading names (`sections`)
+    Titles(Vec<String>),
+}
+
+impl SectionSelection {
+    /// Validate the `section` and `sections` parameters; `None` selects the
+    /// whole page
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-arguments error when both are given, or for an
+    /// invalid anchor, an empty or oversized name list or an empty name
+    pub fn from_params(
+        tool_name: &str,
+        section: Option<&str>,
+        sections: Option<&[String]>,
+    ) -> Result<Option<Self>, CallToolError> {
+        match (section, sections) {
+            (Some(_), Some(_)) => Err(CallToolError::invalid_arguments(
+                tool_name,
+                Some(
+                    "Pass either section (an anchor) or sections (heading names), not both"
+                        .to_string(),
+                ),
+            )),
+            (Some(section), None) => {
+                let anchor = validate_section(tool_name, section)?;
+                Ok(Some(Self::Anchor(anchor.to_string())))
+            }
+            (None, Some(names)) => {
+                if names.is_empty()
+                    || names.len() > MAX_SECTION_NAMES
+                    || names
+                        .iter()
+                        .any(|n| n.trim().is_empty() || n.len() > MAX_ANCHOR_LEN)
+                {
+                    return Err(CallToolError::invalid_arguments(
+                        tool_name,
+                        Some(format!(
+                            "Invalid sections. Expected 1 to {MAX_SECTION_NAMES} heading names, e.g.: [\"Examples\", \"Methods\"]"
+                        )),
+                    ));
+                }
+                Ok(Some(Self::Titles(
+                    names.iter().map(|n| n.trim().to_string()).collect(),
+                )))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Narrow a rustdoc page to the selected sections
+    ///
+    /// The result is a minimal page holding the page `<h1>` and the sections
+    /// in page order, like [`extract_section`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-arguments error when the page lacks the anchor or a
+    /// heading matching one of the names
+    pub fn extract(&self, tool_name: &str, html: &str) -> Result<String, CallToolError> {
+        let names = match self {
+            Self::Anchor(anchor) => {
+                return extract_section(html, anchor)
+                    .ok_or_else(|| section_not_found(tool_name, anchor));
+            }
+            Self::Titles(names) => names,
+        };
+        let toc = extract_toc(html, false);
+        let indices = match_sections(&toc, names).map_err(|name| {
+            let available: Vec<&str> = toc.entries.iter().map(|e| e.title.as_str()).collect();
+            CallToolError::invalid_arguments(
+                tool_name,
+                Some(format!(
+                    "No section titled '{name}' on this page. Available sections: {}",
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                )),
+            )
+        })?;
+
+        let document = Html::parse_document(html);
+        let Some(main) = document.select(&MAIN_CONTENT_SELECTOR).next() else {
+            return Err(section_not_found(tool_name, &names.join(", ")));
+        };
+        let body: String = indices
+            .iter()
+            .filter_map(|&i| section_body(main, &toc.entries[i].anchor))
+            .collect();
+        Ok(section_page(main, &body))
+    }
+}
+
+/// Error for a `section` anchor the page does not have
+#[must_use]
+pub fn section_not_found(tool_name: &str, anchor: &str) -> CallToolError {
+    CallToolError::invalid_arguments(
+        tool_name,
+        Some(format!(
+            "Section '{anchor}' not found on this page. Use get_toc to list the available sections"
+        )),
+    )
+}
+
+fn format_toc(toc: &Toc, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let title = toc.title.as_deref().unwrap_or("Documentation");
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Contents: {title}\n").unwrap();
+    } else {
+        writeln!(output, "Contents: {title}\n").unwrap();
+    }
+    if toc.entries.is_empty() {
+        writeln!(output, "No sections found.").unwrap();
+        return output;
+    }
+    let base = toc.entries.iter().map(|e| e.level).min().unwrap_or(2);
+    for entry in &toc.entries {
+        let indent = "  ".repeat(usize::from(entry.level - base));
+        if markdown {
+            writeln!(output, "{indent}- {} (`{}`)", entry.title, entry.anchor).unwrap();
+        } else {
+            writeln!(output, "{indent}{} [{}]", entry.title, entry.anchor).unwrap();
+        }
+    }
+    output
+}
+
+/// Implementation of the table of contents tool
+pub struct GetTocToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+impl GetTocToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for GetTocToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        GetTocTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: GetTocTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        super::validate_crate_name(TOOL_NAME, &params.crate_name)?;
+        super::validate_version(TOOL_NAME, params.version.as_deref())?;
+        let item_path = params
+            .item_path
+            .as_deref()
+            .map(|p| p.trim().trim_end_matches('!'))
+            .filter(|p| !p.is_empty());
+        if let Some(item_path) = item_path {
+            super::validate_item_path(TOOL_NAME, item_path)?;
+        }
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+
+        let crate_name = &self
+            .service
+            .canonical_crate_name(&params.crate_name, TOOL_NAME)
+            .await;
+        let version = params.version.as_deref().map(super::normalize_version);
+        let version = self
+            .service
+            .resolve_version_param(crate_name, version.as_deref(), TOOL_NAME)
+            .await?;
+
+        // The lookup tools' page caches are shared, so a follow-up
+        // `lookup_*` call with `section` does not refetch the page.
+        let html = match item_path {
+            Some(item_path) => {
+                super::lookup_item::LookupItemToolImpl::new(self.service.clone())
+                    .fetch_item_html(crate_name, item_path, version.as_deref())
+                    .await?
+            }
+            None => {
+                super::lookup_crate::LookupCrateToolImpl::new(self.service.clone())
+                    .fetch_crate_html(crate_name, version.as_deref())
+                    .await?
+            }
+        };
+        let toc = extract_toc(&html, params.include_members.unwrap_or(false));
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&toc).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_toc(&toc, false),
+            _ => format_toc(&toc, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for GetTocToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = concat!(
+        "<html><body><nav><h2 id=\"sidebar\">Sidebar</h2></nav>",
+        "<section id=\"main-content\"><h1>Struct demo::Thing</h1>",
+        "<details class=\"toggle top-doc\" open><div class=\"docblock\">",
+        "<p>A thing.</p>",
+        "<h2 id=\"examples\"><a class=\"doc-anchor\" href=\"#examples\">\u{00a7}</a>Examples</h2>",
+        "<p>Make one:</p><pre class=\"rust\"><code>Thing::new()</code></pre>",
+        "<h3 id=\"caveats\">Caveats</h3><p>Careful &amp; slow.</p>",
+        "<h2 id=\"features\">Features</h2><p>None.</p>",
+        "</div></details>",
+        "<h2 id=\"implementations\" class=\"section-header\">Implementations</h2>",
+        "<div id=\"implementations-list\"><details class=\"toggle implementors-toggle\" open>",
+        "<summary><section id=\"impl-Thing\" class=\"impl\"><h3 class=\"code-header\">impl Thing</h3></section></summary>",
+        "<div class=\"impl-items\"><details class=\"toggle method-toggle\" open>",
+        "<summary><section id=\"method.new\" class=\"method\"><h4 class=\"code-header\">pub fn new() -&gt; Thing</h4></section></summary>",
+        "<div class=\"docblock\"><p>Creates a thing.</p><h5 id=\"panics\">Panics</h5><p>Never.</p></div>",
+        "</details></div></details></div>",
+        "</section></body></html>"
+    );
+
+    #[test]
+    fn test_extract_toc() {
+        let toc = extract_toc(PAGE, false);
+        assert_eq!(toc.title.as_deref(), Some("Struct demo::Thing"));
+        let anchors: Vec<&str> = toc.entries.iter().map(|e| e.anchor.as_str()).collect();
+        assert_eq!(
+            anchors,
+            ["examples", "caveats", "features", "implementations"]
+        );
+        assert_eq!(toc.entries[0].title, "Examples");
+
+        let toc = extract_toc(PAGE, true);
+        let anchors: Vec<&str> = toc.entries.iter().map(|e| e.anchor.as_str()).collect();
+        assert_eq!(
+            anchors,
+            [
+                "examples",
+                "caveats",
+                "features",
+                "implementations",
+                "impl-Thing",
+                "method.new",
+                "panics"
+            ]
+        );
+        assert_eq!(toc.entries[5].title, "pub fn new() -> Thing");
+    }
+
+    #[test]
+    fn test_extract_section() {
+        let section = extract_section(PAGE, "examples").unwrap();
+        assert!(section.contains("Struct demo::Thing"));
+        assert!(section.contains("Thing::new()"));
+        // The nested h3 belongs to the section; the next h2 ends it.
+        assert!(section.contains("Careful &amp; slow."));
+        assert!(!section.contains("Features"));
+
+        let section = extract_section(PAGE, "method.new").unwrap();
+        assert!(section.contains("pub fn new()"));
+        assert!(section.contains("Creates a thing."));
+        assert!(!section.contains("A thing."));
+
+        assert!(extract_section(PAGE, "sidebar").is_none());
+        assert!(extract_section(PAGE, "missing").is_none());
+    }
+
+    #[test]
+    fn test_validate_section() {
+        assert_eq!(validate_section("t", " #examples ").unwrap(), "examples");
+        assert!(validate_section("t", "#").is_err());
+    }
+
+    #[test]
+    fn test_select_sections_by_title() {
+        let names = |n: &[&str]| n.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+        let selection = SectionSelection::Titles(names(&["methods", " EXAMPLES ", "caveats"]));
+        let page = selection.extract("t", PAGE).unwrap();
+        assert!(page.contains("Struct demo::Thing"));
+        assert!(page.contains("Thing::new()"));
+        assert!(page.contains("Creates a thing."));
+        // Caveats comes with Examples, once.
+        assert_eq!(page.matches("Careful &amp; slow.").count(), 1);
+        assert!(!page.contains("None."));
+        assert!(page.find("Thing::new()") < page.find("Creates a thing."));
+
+        // Substring match when no heading has the exact title
+        let page = SectionSelection::Titles(names(&["feat"]))
+            .extract("t", PAGE)
+            .unwrap();
+        assert!(page.contains("None."));
+
+        let err = SectionSelection::Titles(names(&["Panics in space"]))
+            .extract("t", PAGE)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Examples, Caveats, Features, Implementations"));
+    }
+
+    #[test]
+    fn test_section_selection_from_params() {
+        let names = vec!["Examples".to_string()];
+        assert_eq!(
+            SectionSelection::from_params("t", Some("#examples"), None).unwrap(),
+            Some(SectionSelection::Anchor("examples".to_string()))
+        );
+        assert_eq!(
+            SectionSelection::from_params("t", None, Some(&names)).unwrap(),
+            Some(SectionSelection::Titles(names.clone()))
+        );
+        assert_eq!(
+            SectionSelection::from_params("t", None, None).unwrap(),
+            None
+        );
+        assert!(SectionSelection::from_params("t", Some("examples"), Some(&names)).is_err());
+        assert!(SectionSelection::from_params("t", None, Some(&[])).is_err());
+        assert!(SectionSelection::from_params("t", None, Some(&[" ".to_string()])).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Lookup popularity report tool
+//!
+//! Reports the crates and items looked up most often through `lookup_crate`
+//! and `lookup_item`, so operators know which crates to warm, mirror or pin.
+//! Counts live in the cache backend: with Redis they cover every replica
+//! sharing it, with the memory cache only this process since it started.
+
+#![allow(missing_docs)]
+
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::cache::TopLookups;
+
+const TOOL_NAME: &str = "top_queries";
+
+/// Default number of crates and items listed
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Maximum number of crates and items listed
+const MAX_LIMIT: u32 = 100;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "top_queries",
+    title = "Top Queries",
+    description = "Report the crates and items looked up most often on this server (via lookup_crate and lookup_item), with their lookup counts. Useful to decide which crates to warm, mirror or pin, and to see which documentation agents rely on.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://docs.rs/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `top_queries` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct TopQueriesTool {
+    /// Number of crates and of items to list (defaults to 10, max 100)
+    #[json_schema(
+        title = "Limit",
+        description = "Number of crates and of items to list, 1-100 (default: 10)",
+        minimum = 1,
+        maximum = 100,
+        default = 10
+    )]
+    pub limit: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (lookup counts)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+fn format_report(top: &TopLookups, markdown: bool) -> String {
+    use std::fmt::Write;
+
+    let sections = [("Crates", &top.crates), ("Items", &top.items)];
+    let mut output = String::new();
+    // SAFETY: write! to String never fails
+    if markdown {
+        writeln!(output, "# Top queries").unwrap();
+        for (title, entries) in sections {
+            writeln!(output, "\n## {title}\n").unwrap();
+            if entries.is_empty() {
+                writeln!(output, "No lookups recorded yet.").unwrap();
+                continue;
+            }
+            writeln!(output, "| # | Name | Lookups |\n|---|------|---------|").unwrap();
+            for (rank, (name, count)) in entries.iter().enumerate() {
+                writeln!(output, "| {} | `{name}` | {count} |", rank + 1).unwrap();
+            }
+        }
+    } else {
+        writeln!(output, "Top queries").unwrap();
+        for (title, entries) in sections {
+            writeln!(output, "\n{title}:").unwrap();
+            if entries.is_empty() {
+                writeln!(output, "  No lookups recorded yet.").unwrap();
+                continue;
+            }
+            for (rank, (name, count)) in entries.iter().enumerate() {
+                writeln!(output, "  {}. {name} ({count})", rank + 1).unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// Implementation of the lookup popularity report tool
+pub struct TopQueriesToolImpl {
+    /// Shared document service holding the lookup counters
+    service: Arc<super::DocService>,
+}
+
+impl TopQueriesToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Tool for TopQueriesToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        TopQueriesTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: TopQueriesTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::REPORT_FORMATS)?;
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("limit must be between 1 and {MAX_LIMIT}")),
+            ));
+        }
+
+        let top = self
+            .service
+            .doc_cache()
+            .top_lookups(limit as usize)
+            .await
+            .map_err(|e| {
+                CallToolError::from_message(format!(
+                    "[{TOOL_NAME}] Failed to read lookup counts: {e}"
+                ))
+            })?;
+
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&top).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_report(&top, false),
+            _ => format_report(&top, true),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for TopQueriesToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report() {
+        let top = TopLookups {
+            crates: vec![("serde".to_string(), 3), ("tokio".to_string(), 1)],
+            items: Vec::new(),
+        };
+        let markdown = format_report(&top, true);
+        assert!(markdown.contains("| 1 | `serde` | 3 |"), "{markdown}");
+        assert!(markdown.contains("| 2 | `tokio` | 1 |"));
+        assert!(markdown.contains("## Items\n\nNo lookups recorded yet."));
+
+        let text = format_report(&top, false);
+        assert!(text.contains("  1. serde (3)"), "{text}");
+        assert!(!text.contains('`'));
+    }
+}
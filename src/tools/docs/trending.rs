@@ -0,0 +1,430 @@
+//! Trending crates tool
+//!
+//! Surfaces crates that are newly published, recently updated or growing
+//! fastest (by downloads over the last 90 days) on crates.io, optionally
+//! narrowed to a category or keyword.
+
+#![allow(missing_docs)]
+
+use super::search::{self, CrateInfo, SearchCratesResponse};
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOOL_NAME: &str = "get_trending_crates";
+
+const DEFAULT_TRENDING_LIMIT: u32 = 10;
+
+/// crates.io keywords are at most 20 characters; leave some headroom
+const MAX_KEYWORD_LEN: usize = 64;
+
+#[rust_mcp_sdk::macros::mcp_tool(
+    name = "get_trending_crates",
+    title = "Get Trending Crates",
+    description = "Get trending Rust crates from crates.io: fast-growing (most downloads in the last 90 days), newly published, or recently updated crates. Optionally filter by category (e.g. asynchronous) or keyword (e.g. async). Use this to discover what is new or gaining traction in the ecosystem.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = true,
+    icons = [
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "light"),
+        (src = "https://crates.io/favicon.ico", mime_type = "image/x-icon", sizes = ["32x32"], theme = "dark")
+    ]
+)]
+/// Parameters for the `get_trending_crates` tool
+#[derive(Debug, Clone, Deserialize, Serialize, rust_mcp_sdk::macros::JsonSchema)]
+pub struct TrendingCratesTool {
+    /// Trend kind: "growing", "new" or "updated" (defaults to "growing")
+    #[json_schema(
+        title = "Trend",
+        description = "growing (default): most downloads in the last 90 days; new: newly published crates; updated: recently updated crates",
+        default = "growing"
+    )]
+    pub trend: Option<String>,
+
+    /// crates.io category slug to filter by (e.g., `asynchronous`)
+    #[json_schema(
+        title = "Category",
+        description = "Optional crates.io category slug, e.g.: asynchronous, web-programming::http-server"
+    )]
+    pub category: Option<String>,
+
+    /// crates.io keyword to filter by (e.g., "async")
+    #[json_schema(
+        title = "Keyword",
+        description = "Optional crates.io keyword, e.g.: async, serde, cli"
+    )]
+    pub keyword: Option<String>,
+
+    /// Maximum number of crates to return (range 1-100, defaults to 10)
+    #[json_schema(
+        title = "Result Limit",
+        description = "Maximum number of crates to return, range 1-100",
+        minimum = 1,
+        maximum = 100,
+        default = 10
+    )]
+    pub limit: Option<u32>,
+
+    /// Output format: "markdown", "text", or "json" (defaults to "markdown")
+    #[json_schema(
+        title = "Output Format",
+        description = "Output format: markdown (default), text (plain text), json (structured crate list with publish/update dates)",
+        default = "markdown"
+    )]
+    pub format: Option<String>,
+}
+
+/// Kind of trend to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trend {
+    Growing,
+    New,
+    Updated,
+}
+
+impl Trend {
+    const NAMES: &'static [&'static str] = &["growing", "new", "updated"];
+
+    fn parse(raw: Option<&str>) -> std::result::Result<Self, CallToolError> {
+        let Some(raw) = raw else {
+            return Ok(Self::Growing);
+        };
+        match raw.trim().to_lowercase().as_str() {
+            "growing" => Ok(Self::Growing),
+            "new" => Ok(Self::New),
+            "updated" => Ok(Self::Updated),
+            _ => Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!(
+                    "Invalid trend '{raw}', expected one of: {}",
+                    Self::NAMES.join(", ")
+                )),
+            )),
+        }
+    }
+
+    /// crates.io `sort` value producing this trend
+    fn sort(self) -> &'static str {
+        match self {
+            Self::Growing => "recent-downloads",
+            Self::New => "new",
+            Self::Updated => "recent-updates",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Growing => "Fast-growing crates",
+            Self::New => "Newly published crates",
+            Self::Updated => "Recently updated crates",
+        }
+    }
+}
+
+/// Publish/update dates, read alongside the shared crate record
+#[derive(Debug, Deserialize)]
+struct TrendingDatesResponse {
+    crates: Vec<TrendingDates>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrendingDates {
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    updated_at: Option<String>,
+}
+
+/// A crate in a trending listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrendingCrate {
+    #[serde(flatten)]
+    info: CrateInfo,
+    /// First publish time (RFC 3339)
+    #[serde(default)]
+    created_at: Option<String>,
+    /// Last update time (RFC 3339)
+    #[serde(default)]
+    updated_at: Option<String>,
+}
+
+/// Implementation of the trending crates tool
+pub struct TrendingCratesToolImpl {
+    /// Shared document service for HTTP requests and caching
+    service: Arc<super::DocService>,
+}
+
+fn normalize_keyword(raw: &str) -> std::result::Result<String, CallToolError> {
+    let keyword = raw.trim().to_lowercase();
+    let valid = !keyword.is_empty()
+        && keyword.len() <= MAX_KEYWORD_LEN
+        && keyword
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'+'));
+    if valid {
+        Ok(keyword)
+    } else {
+        Err(CallToolError::invalid_arguments(
+            TOOL_NAME,
+            Some(format!(
+                "Invalid keyword '{raw}'. Keywords contain only letters, digits, '-', '_' and '+'"
+            )),
+        ))
+    }
+}
+
+/// Date part of an RFC 3339 timestamp (`2024-08-01T12:00:00Z` -> `2024-08-01`)
+fn date_only(timestamp: &str) -> &str {
+    timestamp.split('T').next().unwrap_or(timestamp)
+}
+
+impl TrendingCratesToolImpl {
+    /// Create a new tool instance
+    #[must_use]
+    pub fn new(service: Arc<super::DocService>) -> Self {
+        Self { service }
+    }
+
+    async fn fetch_trending(
+        &self,
+        trend: Trend,
+        category: Option<&str>,
+        keyword: Option<&str>,
+        limit: u32,
+    ) -> std::result::Result<Vec<TrendingCrate>, CallToolError> {
+        let doc_cache = self.service.doc_cache();
+        if let Some(cached) = doc_cache
+            .get_trending_results(trend.sort(), category, keyword, limit)
+            .await
+        {
+            return serde_json::from_str(&cached).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Cache parsing failed: {e}"))
+            });
+        }
+
+        let url =
+            super::build_crates_io_listing_url(trend.sort(), category, keyword, limit as usize);
+        let body = self.service.fetch_html(&url, Some(TOOL_NAME)).await?;
+        let parse_error = |e: serde_json::Error| {
+            CallToolError::from_message(format!("[{TOOL_NAME}] JSON parsing failed: {e}"))
+        };
+        let response: SearchCratesResponse = serde_json::from_str(&body).map_err(parse_error)?;
+        let dates: TrendingDatesResponse = serde_json::from_str(&body).map_err(parse_error)?;
+
+        let crates: Vec<TrendingCrate> = search::parse_crates_response(response, limit as usize)
+            .into_iter()
+            .zip(dates.crates)
+            .map(|(info, dates)| TrendingCrate {
+                info,
+                created_at: dates.created_at,
+                updated_at: dates.updated_at,
+            })
+            .collect();
+
+        match serde_json::to_string(&crates) {
+            Ok(value) => {
+                if let Err(e) = doc_cache
+                    .set_trending_results(trend.sort(), category, keyword, limit, value)
+                    .await
+                {
+                    tracing::warn!(
+                        "[{TOOL_NAME}] failed to cache trending listing (continuing uncached): {e}"
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("[{TOOL_NAME}] failed to serialize trending listing: {e}"),
+        }
+
+        Ok(crates)
+    }
+}
+
+/// Human-readable description of the active filters, if any
+fn describe_filters(category: Option<&str>, keyword: Option<&str>) -> Option<String> {
+    let mut filters = Vec::new();
+    if let Some(category) = category {
+        filters.push(format!("category {category}"));
+    }
+    if let Some(keyword) = keyword {
+        filters.push(format!("keyword {keyword}"));
+    }
+    (!filters.is_empty()).then(|| filters.join(", "))
+}
+
+/// The date that matters for the trend (publish date for new crates, update
+/// date otherwise)
+fn trend_date(trend: Trend, krate: &TrendingCrate) -> Option<String> {
+    let (label, value) = match trend {
+        Trend::New => ("published", krate.created_at.as_deref()),
+        Trend::Growing | Trend::Updated => ("updated", krate.updated_at.as_deref()),
+    };
+    value.map(|v| format!("{label} {}", date_only(v)))
+}
+
+fn format_trending(
+    trend: Trend,
+    category: Option<&str>,
+    keyword: Option<&str>,
+    crates: &[TrendingCrate],
+    markdown: bool,
+) -> String {
+    // SAFETY: writeln! to String never fails (writes to memory buffer). unwrap() is safe here.
+    use std::fmt::Write;
+    let mut output = String::new();
+    if markdown {
+        writeln!(output, "# {}\n", trend.title()).unwrap();
+    } else {
+        writeln!(output, "{}", trend.title()).unwrap();
+    }
+    if let Some(filters) = describe_filters(category, keyword) {
+        writeln!(output, "Filtered by {filters}\n").unwrap();
+    } else if !markdown {
+        output.push('\n');
+    }
+
+    if crates.is_empty() {
+        output.push_str("No crates found for these filters.\n");
+        return output;
+    }
+
+    for (i, krate) in crates.iter().enumerate() {
+        let info = &krate.info;
+        let mut details = vec![info.version.clone()];
+        if let Some(date) = trend_date(trend, krate) {
+            details.push(date);
+        }
+        if let Some(recent) = info.recent_downloads {
+            details.push(format!("{recent} recent downloads"));
+        }
+        if markdown {
+            writeln!(
+                output,
+                "{}. **{}** ({}) - <{}>",
+                i + 1,
+                info.name,
+                details.join(", "),
+                info.docs_rs
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                output,
+                "{}. {} ({}) - {}",
+                i + 1,
+                info.name,
+                details.join(", "),
+                info.docs_rs
+            )
+            .unwrap();
+        }
+        if let Some(desc) = &info.description {
+            let desc = search::normalize_description(desc);
+            if markdown {
+                writeln!(output, "   {}", search::escape_markdown_text(&desc)).unwrap();
+            } else {
+                writeln!(output, "   {desc}").unwrap();
+            }
+        }
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for TrendingCratesToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        TrendingCratesTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: TrendingCratesTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        let trend = Trend::parse(params.trend.as_deref())?;
+        let category = params
+            .category
+            .as_deref()
+            .map(|c| super::category::normalize_category(TOOL_NAME, c))
+            .transpose()?;
+        let keyword = params
+            .keyword
+            .as_deref()
+            .map(normalize_keyword)
+            .transpose()?;
+        let limit = params.limit.unwrap_or(DEFAULT_TRENDING_LIMIT).clamp(1, 100);
+        let format =
+            super::parse_format(TOOL_NAME, params.format.as_deref(), super::SEARCH_FORMATS)?;
+
+        let crates = self
+            .fetch_trending(trend, category.as_deref(), keyword.as_deref(), limit)
+            .await?;
+        let content = match format {
+            super::Format::Json => serde_json::to_string_pretty(&crates).map_err(|e| {
+                CallToolError::from_message(format!("[{TOOL_NAME}] Serialization failed: {e}"))
+            })?,
+            super::Format::Text => format_trending(
+                trend,
+                category.as_deref(),
+                keyword.as_deref(),
+                &crates,
+                false,
+            ),
+            _ => format_trending(
+                trend,
+                category.as_deref(),
+                keyword.as_deref(),
+                &crates,
+                true,
+            ),
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+}
+
+impl Default for TrendingCratesToolImpl {
+    fn default() -> Self {
+        Self::new(Arc::new(super::DocService::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trend_parse_and_sort() {
+        assert_eq!(Trend::parse(None).unwrap(), Trend::Growing);
+        assert_eq!(Trend::parse(Some(" New ")).unwrap().sort(), "new");
+        assert_eq!(
+            Trend::parse(Some("updated")).unwrap().sort(),
+            "recent-updates"
+        );
+        assert!(Trend::parse(Some("hot")).is_err());
+    }
+
+    #[test]
+    fn test_normalize_keyword() {
+        assert_eq!(normalize_keyword(" Async ").unwrap(), "async");
+        assert_eq!(normalize_keyword("no-std").unwrap(), "no-std");
+        assert!(normalize_keyword("").is_err());
+        assert!(normalize_keyword("web framework").is_err());
+    }
+
+    #[test]
+    fn test_date_only() {
+        assert_eq!(date_only("2024-08-01T12:30:00.123+00:00"), "2024-08-01");
+        assert_eq!(date_only("2024-08-01"), "2024-08-01");
+    }
+}
@@ -0,0 +1,86 @@
+//! Upstream service credentials
+//!
+//! crates.io rate-limits anonymous API clients, which a deployment serving
+//! many agents reaches quickly. An API token (created at
+//! <https://crates.io/settings/tokens>, no scopes needed) raises the limits:
+//!
+//! ```toml
+//! [upstream]
+//! crates_io_token = "..."
+//! ```
+//!
+//! The token (or the `CRATES_DOCS_CRATES_IO_TOKEN` environment variable) is
+//! sent as the `Authorization` header on crates.io API requests only.
+
+use serde::{Deserialize, Serialize};
+
+/// Environment variable holding the crates.io API token
+pub const CRATES_IO_TOKEN_ENV: &str = "CRATES_DOCS_CRATES_IO_TOKEN";
+
+/// Prefix of the crates.io API endpoints the token is sent to
+const CRATES_IO_API_PREFIX: &str = "https://crates.io/api/";
+
+/// Upstream service configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the token is handed to the document
+/// service when the server starts.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct UpstreamConfig {
+    /// crates.io API token sent with crates.io API requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crates_io_token: Option<String>,
+}
+
+impl std::fmt::Debug for UpstreamConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpstreamConfig")
+            .field(
+                "crates_io_token",
+                &self.crates_io_token.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+impl UpstreamConfig {
+    /// crates.io token to authenticate with, from the config or the
+    /// environment
+    #[must_use]
+    pub fn crates_io_token(&self) -> Option<String> {
+        self.crates_io_token
+            .clone()
+            .or_else(|| std::env::var(CRATES_IO_TOKEN_ENV).ok())
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Whether `url` is a crates.io API endpoint
+#[must_use]
+pub fn is_crates_io_api(url: &str) -> bool {
+    url.starts_with(CRATES_IO_API_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_token_is_redacted_and_trimmed() {
+        let config = UpstreamConfig {
+            crates_io_token: Some(" secret \n".to_string()),
+        };
+        assert_eq!(config.crates_io_token().as_deref(), Some("secret"));
+        assert!(!format!("{config:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_is_crates_io_api() {
+        assert!(is_crates_io_api("https://crates.io/api/v1/crates?q=serde"));
+        assert!(!is_crates_io_api("https://index.crates.io/se/rd/serde"));
+        assert!(!is_crates_io_api("https://crates.io.evil.test/api/v1"));
+        assert!(!is_crates_io_api("https://docs.rs/serde/"));
+    }
+}
@@ -6,10 +6,12 @@
 
 #![allow(missing_docs)]
 
+use crate::cache::Cache;
 use crate::tools::Tool;
 use async_trait::async_trait;
 use rust_mcp_sdk::macros;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// The set of valid `check_type` values accepted by the `health_check` tool.
@@ -87,6 +89,8 @@ struct HealthCheck {
 pub struct HealthCheckToolImpl {
     /// Server start time for uptime calculation
     start_time: Instant,
+    /// Cache whose store integrity and statistics are reported by the internal check
+    cache: Option<Arc<dyn Cache>>,
 }
 
 impl HealthCheckToolImpl {
@@ -98,9 +102,17 @@ impl HealthCheckToolImpl {
     pub fn new() -> Self {
         Self {
             start_time: Instant::now(),
+            cache: None,
         }
     }
 
+    /// Include the cache store integrity and statistics in the internal checks
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<dyn Cache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     async fn check_http_service(
         name: &'static str,
@@ -224,19 +236,56 @@ impl HealthCheckToolImpl {
         Some(resident_pages.saturating_mul(page_size))
     }
 
+    /// Report the cache store integrity and statistics, if a cache is attached
+    #[allow(clippy::cast_possible_truncation)]
+    async fn check_cache(&self) -> Option<HealthCheck> {
+        let cache = self.cache.as_ref()?;
+        let start = Instant::now();
+        let result = cache.check_integrity().await;
+        let stats = cache.stats().await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+        Some(match result {
+            Ok(integrity) => HealthCheck {
+                name: "cache".to_string(),
+                status: "healthy".to_string(),
+                duration_ms,
+                message: Some(format!("{integrity}; {stats}")),
+                error: None,
+            },
+            Err(e) => HealthCheck {
+                name: "cache".to_string(),
+                status: "unhealthy".to_string(),
+                duration_ms,
+                message: None,
+                error: Some(e.to_string()),
+            },
+        })
+    }
+
+    async fn internal_checks(&self) -> Vec<HealthCheck> {
+        let mut checks = vec![Self::check_memory()];
+        checks.extend(self.check_cache().await);
+        checks
+    }
+
     async fn perform_checks(&self, check_type: &str, verbose: bool) -> HealthStatus {
         let checks = match check_type {
             "all" => {
-                let (docs_rs, crates_io) =
-                    tokio::join!(self.check_docs_rs(), self.check_crates_io());
-                vec![docs_rs, crates_io, Self::check_memory()]
+                let (docs_rs, crates_io, internal) = tokio::join!(
+                    self.check_docs_rs(),
+                    self.check_crates_io(),
+                    self.internal_checks()
+                );
+                let mut checks = vec![docs_rs, crates_io];
+                checks.extend(internal);
+                checks
             }
             "external" => {
                 let (docs_rs, crates_io) =
                     tokio::join!(self.check_docs_rs(), self.check_crates_io());
                 vec![docs_rs, crates_io]
             }
-            "internal" => vec![Self::check_memory()],
+            "internal" => self.internal_checks().await,
             "docs_rs" => vec![self.check_docs_rs().await],
             "crates_io" => vec![self.check_crates_io().await],
             _ => vec![HealthCheck {
@@ -321,6 +370,19 @@ impl HealthCheckToolImpl {
         let is_healthy = health_status.status == "healthy";
         (Self::render_report(&health_status, verbose), is_healthy)
     }
+
+    /// Run a health check and return the report as JSON.
+    ///
+    /// The report lists every check, not only failing ones. The boolean is
+    /// the same as in [`run_check_report`](Self::run_check_report). Used by
+    /// the `/healthz` and `/readyz` HTTP probes.
+    pub async fn run_check_json(&self, check_type: &str) -> (serde_json::Value, bool) {
+        let health_status = self.perform_checks(check_type, true).await;
+        let is_healthy = health_status.status == "healthy";
+        let report = serde_json::to_value(&health_status)
+            .unwrap_or_else(|e| serde_json::json!({ "status": "unknown", "error": e.to_string() }));
+        (report, is_healthy)
+    }
 }
 
 #[async_trait]
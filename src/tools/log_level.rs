@@ -0,0 +1,120 @@
+//! Runtime log level tool
+//!
+//! Admin tool reading or changing the server's active log level without a
+//! restart, e.g. to turn on debug logging while a production issue is being
+//! investigated. MCP `logging/setLevel` only changes the level of the log
+//! messages sent to the requesting client.
+
+#![allow(missing_docs)]
+
+use crate::logging::LogLevelHandle;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use rust_mcp_sdk::macros;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+
+const TOOL_NAME: &str = "set_log_level";
+
+/// Parameters for the `set_log_level` tool
+#[macros::mcp_tool(
+    name = "set_log_level",
+    title = "Set Log Level",
+    description = "Admin: show or change the server's active log level at runtime, without restart. Accepts a level (trace, debug, info, warn, error) or a tracing filter directive such as info,crates_docs=debug. Omit level to show the current one.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = false,
+    icons = [
+        (src = "https://img.icons8.com/color/96/000000/heart-health.png", mime_type = "image/png", sizes = ["96x96"], theme = "light"),
+        (src = "https://img.icons8.com/color/96/000000/heart-health.png", mime_type = "image/png", sizes = ["96x96"], theme = "dark")
+    ]
+)]
+#[derive(Debug, Clone, Deserialize, Serialize, macros::JsonSchema)]
+pub struct SetLogLevelTool {
+    /// New level or filter directive; omitted to show the current level
+    #[json_schema(
+        title = "Level",
+        description = "Level (trace, debug, info, warn, error) or filter directive, e.g.: debug, info,crates_docs=debug. Omit to show the current level"
+    )]
+    pub level: Option<String>,
+}
+
+/// Implementation of the runtime log level tool
+pub struct SetLogLevelToolImpl {
+    /// Handle to use instead of the global one
+    handle: Option<LogLevelHandle>,
+}
+
+impl SetLogLevelToolImpl {
+    /// Create a tool changing the globally installed subscriber
+    ///
+    /// See [`crate::logging::global_log_level`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self { handle: None }
+    }
+
+    /// Change the level through `handle` instead
+    #[must_use]
+    pub fn with_handle(mut self, handle: LogLevelHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for SetLogLevelToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SetLogLevelTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SetLogLevelTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+
+        // Resolved per call: logging may be initialized after the registry
+        let Some(handle) = self
+            .handle
+            .clone()
+            .or_else(crate::logging::global_log_level)
+        else {
+            return Err(CallToolError::from_message(format!(
+                "[{TOOL_NAME}] Runtime log level changes are not enabled: logging was not initialized by this server"
+            )));
+        };
+
+        let content = match params.level.as_deref() {
+            None => format!("Current log level: {}", handle.level()),
+            Some(level) => {
+                let previous = handle.level();
+                handle.set_level(level).map_err(|e| {
+                    CallToolError::invalid_arguments(TOOL_NAME, Some(e.to_string()))
+                })?;
+                format!("Log level changed from {previous} to {}", handle.level())
+            }
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+
+    /// The level applies to the whole process, not to the calling session
+    fn requires_admin(&self) -> bool {
+        true
+    }
+}
+
+impl Default for SetLogLevelToolImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
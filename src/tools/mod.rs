@@ -7,6 +7,32 @@
 //! - `docs::lookup_crate::LookupCrateToolImpl`: Lookup crate documentation
 //! - `docs::search::SearchCratesToolImpl`: Search crates
 //! - `docs::lookup_item::LookupItemToolImpl`: Lookup specific items
+//! - `docs::build_status::DocsRsBuildStatusToolImpl`: docs.rs build status
+//! - `docs::category::ListCratesByCategoryToolImpl`: crates.io category listings
+//! - `docs::trending::TrendingCratesToolImpl`: Trending crates
+//! - `docs::resolve_version::ResolveVersionToolImpl`: Semver requirement resolution
+//! - `docs::dependency::SuggestDependencyLineToolImpl`: Dependency line suggestions
+//! - `docs::profile::SetProjectProfileToolImpl`: Per-session version pins
+//! - `docs::features::ListFeatureGatedItemsToolImpl`: Feature-gated items
+//! - `docs::deprecated::ListDeprecatedItemsToolImpl`: Deprecated items
+//! - `docs::lookup_macro::LookupMacroToolImpl`: Macro documentation
+//! - `docs::toc::GetTocToolImpl`: Page table of contents
+//! - `docs::rustc_error::LookupRustcErrorToolImpl`: Rustc error index
+//! - `docs::clippy_lint::LookupClippyLintToolImpl`: Clippy lint index
+//! - `docs::symbol::FindSymbolToolImpl`: Cross-crate symbol search
+//! - `docs::examples::ExtractDocExamplesToolImpl`: Documentation code examples
+//! - `docs::playground::RunDocExampleToolImpl`: Rust Playground execution
+//! - `docs::top_queries::TopQueriesToolImpl`: Most looked up crates and items
+//! - `docs::dependency_tree::DependencyTreeToolImpl`: Transitive dependency trees
+//! - `docs::build_info::CrateBuildInfoToolImpl`: Crate build statistics
+//! - `docs::alternatives::SuggestAlternativesToolImpl`: Alternative crates
+//! - `log_level::SetLogLevelToolImpl`: Runtime log level changes
+//! - `tool_admin::SetToolEnabledToolImpl`: Runtime tool enabling and disabling
+//! - `docs::batch::BatchLookupCratesToolImpl`: Summaries of many crates at once
+//! - `docs::manifest::AnalyzeCargoTomlToolImpl`: `Cargo.toml` dependency audits
+//! - `docs::lockfile::AnalyzeCargoLockToolImpl`: `Cargo.lock` audits
+//! - `docs::coverage::DocCoverageToolImpl`: Documentation coverage
+//! - `docs::summarize::SummarizeCrateToolImpl`: Budgeted crate summary
 //! - `health::HealthCheckToolImpl`: Health check
 //!
 //! # Examples
@@ -24,11 +50,14 @@
 
 pub mod docs;
 pub mod health;
+pub mod log_level;
+pub mod tool_admin;
 
 use async_trait::async_trait;
 use rust_mcp_sdk::schema::{CallToolError, CallToolResult, Tool as McpTool};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
 
 /// Tool trait
 ///
@@ -57,6 +86,15 @@ pub trait Tool: Send + Sync {
         &self,
         arguments: serde_json::Value,
     ) -> std::result::Result<CallToolResult, CallToolError>;
+
+    /// Whether only admin clients may call the tool
+    ///
+    /// Admin tools change state shared by every session, such as the log
+    /// level. MCP clients must be listed in `server.admin_clients` to call
+    /// them; the registry itself does not check the caller.
+    fn requires_admin(&self) -> bool {
+        false
+    }
 }
 
 /// Tool registry
@@ -66,8 +104,12 @@ pub trait Tool: Send + Sync {
 /// # Fields
 ///
 /// - `tools`: Dictionary storing tools, keyed by tool name
+/// - `disabled`: Names of the tools disabled at runtime
+/// - `changes`: Bumped whenever the set of enabled tools changes
 pub struct ToolRegistry {
     tools: HashMap<String, Box<dyn Tool>>,
+    disabled: RwLock<HashSet<String>>,
+    changes: watch::Sender<u64>,
 }
 
 impl ToolRegistry {
@@ -85,6 +127,8 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            disabled: RwLock::new(HashSet::new()),
+            changes: watch::Sender::new(0),
         }
     }
 
@@ -119,10 +163,39 @@ impl ToolRegistry {
     ///
     /// # Returns
     ///
-    /// Returns a list of metadata for all registered tools
+    /// Returns a list of metadata for all enabled tools, sorted by name
     #[must_use]
     pub fn get_tools(&self) -> Vec<McpTool> {
-        self.tools.values().map(|t| t.definition()).collect()
+        let disabled = self.read_disabled();
+        let mut tools: Vec<McpTool> = self
+            .tools
+            .iter()
+            .filter(|(name, _)| !disabled.contains(*name))
+            .map(|(_, t)| t.definition())
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Get the page of tool definitions after `cursor`
+    ///
+    /// See [`crate::utils::pagination::paginate`].
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if `cursor` is not a cursor of this registry
+    #[must_use]
+    pub fn get_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: usize,
+    ) -> Option<crate::utils::pagination::Page<McpTool>> {
+        crate::utils::pagination::paginate(
+            self.get_tools(),
+            |tool| tool.name.as_str(),
+            cursor,
+            page_size,
+        )
     }
 
     /// Execute tool by name
@@ -134,19 +207,110 @@ impl ToolRegistry {
     ///
     /// # Returns
     ///
-    /// Returns tool execution result, or error if tool not found
+    /// Returns tool execution result, or error if tool not found or disabled
     pub async fn execute_tool(
         &self,
         name: &str,
         arguments: serde_json::Value,
     ) -> std::result::Result<CallToolResult, CallToolError> {
         match self.tools.get(name) {
-            Some(tool) => tool.execute(arguments).await,
-            None => Err(CallToolError::unknown_tool(name.to_string())),
+            Some(tool) if self.is_enabled(name) => tool.execute(arguments).await,
+            _ => Err(CallToolError::unknown_tool(name.to_string())),
+        }
+    }
+
+    fn read_disabled(&self) -> std::sync::RwLockReadGuard<'_, HashSet<String>> {
+        self.disabled
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn write_disabled(&self) -> std::sync::RwLockWriteGuard<'_, HashSet<String>> {
+        self.disabled
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Check if a registered tool is enabled
+    ///
+    /// Disabled tools are left out of [`get_tools`](Self::get_tools) and
+    /// cannot be executed.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.tools.contains_key(name) && !self.read_disabled().contains(name)
+    }
+
+    /// Enable or disable a registered tool
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(true)` if the tool changed state, `Some(false)` if it
+    /// already was in that state, or `None` if no such tool is registered
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> Option<bool> {
+        if !self.tools.contains_key(name) {
+            return None;
+        }
+        let changed = {
+            let mut disabled = self.write_disabled();
+            if enabled {
+                disabled.remove(name)
+            } else {
+                disabled.insert(name.to_string())
+            }
+        };
+        if changed {
+            self.changes.send_modify(|generation| *generation += 1);
+        }
+        Some(changed)
+    }
+
+    /// Disable exactly the tools in `names`, enabling all others
+    ///
+    /// Used to apply the `server.disabled_tools` configuration.
+    ///
+    /// # Returns
+    ///
+    /// Returns the names that are not registered tools, which are ignored
+    pub fn set_disabled<S: AsRef<str>>(&self, names: &[S]) -> Vec<String> {
+        let mut known = HashSet::new();
+        let mut unknown = Vec::new();
+        for name in names.iter().map(AsRef::as_ref) {
+            if self.tools.contains_key(name) {
+                known.insert(name.to_string());
+            } else {
+                unknown.push(name.to_string());
+            }
         }
+        let changed = {
+            let mut disabled = self.write_disabled();
+            let changed = *disabled != known;
+            *disabled = known;
+            changed
+        };
+        if changed {
+            self.changes.send_modify(|generation| *generation += 1);
+        }
+        unknown
+    }
+
+    /// Names of the disabled tools, sorted
+    #[must_use]
+    pub fn disabled_tools(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.read_disabled().iter().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Watch for changes of the set of enabled tools
+    ///
+    /// The receiver sees a new value after every change, e.g. to send
+    /// `notifications/tools/list_changed` to clients.
+    #[must_use]
+    pub fn watch_changes(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
     }
 
-    /// Check if tool exists
+    /// Check if tool is registered, whether enabled or not
     ///
     /// # Arguments
     ///
@@ -160,6 +324,14 @@ impl ToolRegistry {
         self.tools.contains_key(name)
     }
 
+    /// Check if a tool is an admin tool (see [`Tool::requires_admin`])
+    #[must_use]
+    pub fn requires_admin(&self, name: &str) -> bool {
+        self.tools
+            .get(name)
+            .is_some_and(|tool| tool.requires_admin())
+    }
+
     /// Get number of registered tools
     #[must_use]
     pub fn len(&self) -> usize {
@@ -185,6 +357,31 @@ impl Default for ToolRegistry {
 /// - `lookup_crate`: Lookup crate documentation
 /// - `search_crates`: Search crates
 /// - `lookup_item`: Lookup specific items
+/// - `get_docsrs_build_status`: docs.rs build status
+/// - `list_crates_by_category`: Top crates in a crates.io category
+/// - `get_trending_crates`: New, updated and fast-growing crates
+/// - `resolve_version`: Resolve a semver requirement to a concrete version
+/// - `suggest_dependency_line`: `cargo add` command and `Cargo.toml` line for a crate
+/// - `set_project_profile`: Pin crate versions for the session, e.g. from a `Cargo.lock`
+/// - `list_feature_gated_items`: Items that require a cargo feature
+/// - `list_deprecated_items`: Deprecated items with their notes and replacements
+/// - `lookup_macro`: Macro syntax and examples
+/// - `get_toc`: Heading outline of a crate or item page
+/// - `lookup_rustc_error`: Explanation of a rustc error code
+/// - `lookup_clippy_lint`: Documentation of a Clippy lint
+/// - `find_symbol`: Crates and modules defining a type, trait or function name
+/// - `extract_doc_examples`: Code examples of a crate or item page, without prose
+/// - `run_doc_example`: Compile and run a snippet or documentation example on the Rust Playground
+/// - `top_queries`: Most looked up crates and items, with lookup counts
+/// - `dependency_tree`: Transitive dependency tree of a crate version, like `cargo tree`
+/// - `crate_build_info`: docs.rs build statistics, source size and MSRV of a crate version
+/// - `suggest_alternatives`: Crates sharing keywords and categories with a crate, ranked by overlap
+/// - `set_log_level`: Show or change the server's log level at runtime
+/// - `batch_lookup_crates`: crates.io summaries of up to 50 crates in one call
+/// - `analyze_cargo_toml`: Latest versions, outdated requirements and advisories of a `Cargo.toml`'s dependencies
+/// - `analyze_cargo_lock`: Locked versions, yanked versions in use and exact-version docs links of a `Cargo.lock`
+/// - `doc_coverage`: Share of documented items and items with examples, as measured by docs.rs
+/// - `summarize_crate`: Condensed crate overview trimmed to a token budget
 /// - `health_check`: Health check
 ///
 /// # Arguments
@@ -211,5 +408,66 @@ pub fn create_default_registry(service: &Arc<docs::DocService>) -> ToolRegistry
         ))
         .register(docs::search::SearchCratesToolImpl::new(service.clone()))
         .register(docs::lookup_item::LookupItemToolImpl::new(service.clone()))
-        .register(health::HealthCheckToolImpl::new())
+        .register(docs::build_status::DocsRsBuildStatusToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::category::ListCratesByCategoryToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::trending::TrendingCratesToolImpl::new(service.clone()))
+        .register(docs::resolve_version::ResolveVersionToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::dependency::SuggestDependencyLineToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::profile::SetProjectProfileToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::features::ListFeatureGatedItemsToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::deprecated::ListDeprecatedItemsToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::lookup_macro::LookupMacroToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::toc::GetTocToolImpl::new(service.clone()))
+        .register(docs::rustc_error::LookupRustcErrorToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::clippy_lint::LookupClippyLintToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::symbol::FindSymbolToolImpl::new(service.clone()))
+        .register(docs::examples::ExtractDocExamplesToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::playground::RunDocExampleToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::top_queries::TopQueriesToolImpl::new(service.clone()))
+        .register(docs::dependency_tree::DependencyTreeToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::build_info::CrateBuildInfoToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::alternatives::SuggestAlternativesToolImpl::new(
+            service.clone(),
+        ))
+        .register(log_level::SetLogLevelToolImpl::new())
+        .register(docs::batch::BatchLookupCratesToolImpl::new(service.clone()))
+        .register(docs::manifest::AnalyzeCargoTomlToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::lockfile::AnalyzeCargoLockToolImpl::new(
+            service.clone(),
+        ))
+        .register(docs::coverage::DocCoverageToolImpl::new(service.clone()))
+        .register(docs::summarize::SummarizeCrateToolImpl::new(
+            service.clone(),
+        ))
+        .register(health::HealthCheckToolImpl::new().with_cache(service.cache().clone()))
 }
@@ -0,0 +1,154 @@
+//! Runtime tool enabling and disabling
+//!
+//! Admin tool turning other tools off and on without a restart, e.g. to take
+//! a tool whose upstream is down out of clients' tool lists. Connected
+//! clients are sent `notifications/tools/list_changed` after each change.
+//! The `server.disabled_tools` configuration sets the same state at startup
+//! and on configuration reload.
+//!
+//! Disabling a tool hides it from every session, so only admin clients
+//! (`server.admin_clients`, or stdio) may call this tool.
+
+#![allow(missing_docs)]
+
+use crate::tools::{Tool, ToolRegistry};
+use async_trait::async_trait;
+use rust_mcp_sdk::macros;
+use rust_mcp_sdk::schema::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::Weak;
+
+const TOOL_NAME: &str = "set_tool_enabled";
+
+/// Parameters for the `set_tool_enabled` tool
+#[macros::mcp_tool(
+    name = "set_tool_enabled",
+    title = "Enable or Disable Tool",
+    description = "Admin: enable or disable one of this server's tools at runtime, without restart. Disabled tools disappear from tools/list and cannot be called; connected clients are notified of the change. Omit enabled to show whether a tool is enabled, or omit both parameters to list the disabled tools.",
+    destructive_hint = false,
+    idempotent_hint = true,
+    open_world_hint = false,
+    read_only_hint = false,
+    icons = [
+        (src = "https://img.icons8.com/color/96/000000/toggle-on.png", mime_type = "image/png", sizes = ["96x96"], theme = "light"),
+        (src = "https://img.icons8.com/color/96/000000/toggle-on.png", mime_type = "image/png", sizes = ["96x96"], theme = "dark")
+    ]
+)]
+#[derive(Debug, Clone, Deserialize, Serialize, macros::JsonSchema)]
+pub struct SetToolEnabledTool {
+    /// Tool to change; omitted to list the disabled tools
+    #[json_schema(
+        title = "Tool Name",
+        description = "Name of the tool to enable or disable, e.g.: run_doc_example. Omit to list the disabled tools"
+    )]
+    pub name: Option<String>,
+
+    /// New state; omitted to show the current one
+    #[json_schema(
+        title = "Enabled",
+        description = "true to enable the tool, false to disable it. Omit to show whether it is enabled"
+    )]
+    pub enabled: Option<bool>,
+}
+
+/// Implementation of the runtime tool enabling tool
+///
+/// Holds a weak reference to the registry it is registered in, so it is
+/// registered by [`crate::server::CratesDocsServer`] rather than
+/// [`crate::tools::create_default_registry`].
+pub struct SetToolEnabledToolImpl {
+    registry: Weak<ToolRegistry>,
+}
+
+impl SetToolEnabledToolImpl {
+    /// Create a tool changing the tools of `registry`
+    #[must_use]
+    pub fn new(registry: Weak<ToolRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for SetToolEnabledToolImpl {
+    fn definition(&self) -> rust_mcp_sdk::schema::Tool {
+        SetToolEnabledTool::tool()
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
+        let params: SetToolEnabledTool = serde_json::from_value(arguments).map_err(|e| {
+            CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Parameter parsing failed: {e}")),
+            )
+        })?;
+        let Some(registry) = self.registry.upgrade() else {
+            return Err(CallToolError::from_message(format!(
+                "[{TOOL_NAME}] The tool registry is gone"
+            )));
+        };
+
+        let Some(name) = params.name.as_deref().map(str::trim) else {
+            return Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+                list_disabled(&registry).into(),
+            ]));
+        };
+        if !registry.has_tool(name) {
+            return Err(CallToolError::invalid_arguments(
+                TOOL_NAME,
+                Some(format!("Unknown tool '{name}'")),
+            ));
+        }
+
+        let content = match params.enabled {
+            None => {
+                let state = if registry.is_enabled(name) {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                format!("Tool {name} is {state}")
+            }
+            // Disabling this tool would leave no way back short of a restart
+            Some(false) if name == TOOL_NAME => {
+                return Err(CallToolError::invalid_arguments(
+                    TOOL_NAME,
+                    Some(format!("{TOOL_NAME} cannot disable itself")),
+                ));
+            }
+            Some(enabled) => {
+                let state = if enabled { "enabled" } else { "disabled" };
+                if registry.set_enabled(name, enabled) == Some(true) {
+                    tracing::info!("Tool {name} {state} at runtime");
+                    format!("Tool {name} {state}")
+                } else {
+                    format!("Tool {name} is already {state}")
+                }
+            }
+        };
+
+        Ok(rust_mcp_sdk::schema::CallToolResult::text_content(vec![
+            content.into(),
+        ]))
+    }
+
+    /// Tools are enabled and disabled for every session at once
+    fn requires_admin(&self) -> bool {
+        true
+    }
+}
+
+fn list_disabled(registry: &ToolRegistry) -> String {
+    let disabled = registry.disabled_tools();
+    if disabled.is_empty() {
+        return "All tools are enabled".to_string();
+    }
+    let mut content = String::from("Disabled tools:\n");
+    for name in disabled {
+        let _ = writeln!(content, "- {name}");
+    }
+    content
+}
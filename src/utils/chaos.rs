@@ -0,0 +1,228 @@
+//! Upstream failure injection for resilience testing
+//!
+//! [`ChaosMiddleware`] sits inside the retry middleware of the shared HTTP
+//! client and, at configured probabilities, delays upstream requests or
+//! answers them with an error or rate-limit response without contacting the
+//! upstream. Retries, circuit breaking and stale serving can then be
+//! exercised in integration tests and staging while docs.rs and crates.io
+//! see normal traffic.
+//!
+//! Chaos is meant for development and staging only and is off by default.
+
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Failure injection configuration
+///
+/// # Hot Reload Support
+///
+/// ⚠️ **Does not support hot reload** - the middleware is installed when the
+/// shared HTTP client is created at startup.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChaosConfig {
+    /// Whether failures are injected
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Upstream hosts affected (e.g. `docs.rs`); empty means every host
+    #[serde(default)]
+    pub hosts: Vec<String>,
+
+    /// Probability (0.0-1.0) that a request is delayed
+    #[serde(default)]
+    pub latency_probability: f64,
+
+    /// Delay added to a delayed request, in milliseconds
+    #[serde(default = "default_latency_ms")]
+    pub latency_ms: u64,
+
+    /// Probability (0.0-1.0) that a request fails with `error_status`
+    #[serde(default)]
+    pub error_probability: f64,
+
+    /// HTTP status of injected errors
+    #[serde(default = "default_error_status")]
+    pub error_status: u16,
+
+    /// Probability (0.0-1.0) that a request is answered with 429 Too Many Requests
+    #[serde(default)]
+    pub rate_limit_probability: f64,
+
+    /// `Retry-After` value of injected rate-limit responses, in seconds
+    #[serde(default = "default_retry_after_secs")]
+    pub retry_after_secs: u64,
+}
+
+fn default_latency_ms() -> u64 {
+    ChaosConfig::default().latency_ms
+}
+
+fn default_error_status() -> u16 {
+    ChaosConfig::default().error_status
+}
+
+fn default_retry_after_secs() -> u64 {
+    ChaosConfig::default().retry_after_secs
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hosts: Vec::new(),
+            latency_probability: 0.0,
+            latency_ms: 500,
+            error_probability: 0.0,
+            error_status: 503,
+            rate_limit_probability: 0.0,
+            retry_after_secs: 1,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Validate the chaos configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a probability is outside 0.0-1.0 or
+    /// `error_status` is not a 4xx or 5xx status
+    pub fn validate(&self) -> crate::error::Result<()> {
+        for (key, probability) in [
+            ("chaos.latency_probability", self.latency_probability),
+            ("chaos.error_probability", self.error_probability),
+            ("chaos.rate_limit_probability", self.rate_limit_probability),
+        ] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(crate::error::Error::config(
+                    key,
+                    format!("must be between 0.0 and 1.0, got {probability}"),
+                ));
+            }
+        }
+        if !(400..600).contains(&self.error_status) {
+            return Err(crate::error::Error::config(
+                "chaos.error_status",
+                format!("must be a 4xx or 5xx status, got {}", self.error_status),
+            ));
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, host: Option<&str>) -> bool {
+        self.hosts.is_empty()
+            || host.is_some_and(|host| self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)))
+    }
+}
+
+/// Middleware injecting the failures described by a [`ChaosConfig`]
+#[derive(Debug, Clone)]
+pub struct ChaosMiddleware {
+    config: ChaosConfig,
+}
+
+impl ChaosMiddleware {
+    /// Create the middleware
+    #[must_use]
+    pub fn new(config: ChaosConfig) -> Self {
+        Self { config }
+    }
+
+    fn injected_response(status: u16, retry_after_secs: Option<u64>) -> Response {
+        let mut builder = http::Response::builder().status(status);
+        if let Some(secs) = retry_after_secs {
+            builder = builder.header(http::header::RETRY_AFTER, secs.to_string());
+        }
+        let response = builder
+            .body(format!("chaos: injected {status} response"))
+            // Both the status (validated) and the header value are valid
+            .unwrap_or_default();
+        Response::from(response)
+    }
+}
+
+/// Whether an event of the given probability happens on this request
+fn roll(probability: f64) -> bool {
+    probability > 0.0 && fastrand::f64() < probability
+}
+
+#[async_trait::async_trait]
+impl Middleware for ChaosMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let config = &self.config;
+        if !config.enabled || !config.applies_to(req.url().host_str()) {
+            return next.run(req, extensions).await;
+        }
+
+        if roll(config.latency_probability) {
+            tracing::debug!(url = %req.url(), delay_ms = config.latency_ms, "chaos: delaying request");
+            tokio::time::sleep(Duration::from_millis(config.latency_ms)).await;
+        }
+        if roll(config.rate_limit_probability) {
+            tracing::debug!(url = %req.url(), "chaos: injecting rate limit");
+            return Ok(Self::injected_response(429, Some(config.retry_after_secs)));
+        }
+        if roll(config.error_probability) {
+            tracing::debug!(url = %req.url(), status = config.error_status, "chaos: injecting error");
+            return Ok(Self::injected_response(config.error_status, None));
+        }
+        next.run(req, extensions).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_out_of_range_values() {
+        assert!(ChaosConfig::default().validate().is_ok());
+
+        let config = ChaosConfig {
+            error_probability: 1.5,
+            ..ChaosConfig::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("chaos.error_probability"), "got: {err}");
+
+        let config = ChaosConfig {
+            error_status: 200,
+            ..ChaosConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_host_filter() {
+        let mut config = ChaosConfig::default();
+        assert!(config.applies_to(Some("docs.rs")));
+        config.hosts = vec!["Docs.rs".to_string()];
+        assert!(config.applies_to(Some("docs.rs")));
+        assert!(!config.applies_to(Some("index.crates.io")));
+        assert!(!config.applies_to(None));
+    }
+
+    #[test]
+    fn test_roll_extremes() {
+        assert!(!roll(0.0));
+        assert!(roll(1.0));
+    }
+
+    #[test]
+    fn test_injected_response() {
+        let response = ChaosMiddleware::injected_response(429, Some(7));
+        assert_eq!(response.status().as_u16(), 429);
+        assert_eq!(
+            response.headers().get(http::header::RETRY_AFTER).unwrap(),
+            "7"
+        );
+    }
+}
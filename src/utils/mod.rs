@@ -1,5 +1,8 @@
 //! Utility functions module
 
+pub mod chaos;
+pub mod pagination;
+
 use crate::error::{Error, Result};
 use reqwest::Client;
 use reqwest_middleware::ClientBuilder;
@@ -33,13 +36,30 @@ static GLOBAL_HTTP_CLIENT: OnceLock<Arc<reqwest_middleware::ClientWithMiddleware
 /// creation can fail for transient reasons (e.g. TLS backend or resource
 /// pressure), a subsequent call is free to retry and may succeed.
 pub fn init_global_http_client(config: &crate::config::PerformanceConfig) -> Result<()> {
+    init_global_http_client_with_chaos(config, &chaos::ChaosConfig::default())
+}
+
+/// Initialize the global HTTP client singleton with failure injection
+///
+/// Like [`init_global_http_client`], additionally installing
+/// [`chaos::ChaosMiddleware`] when `chaos` is enabled.
+///
+/// # Errors
+///
+/// Returns an error if HTTP client creation fails
+pub fn init_global_http_client_with_chaos(
+    config: &crate::config::PerformanceConfig,
+    chaos: &chaos::ChaosConfig,
+) -> Result<()> {
     // Fast path: already initialized
     if GLOBAL_HTTP_CLIENT.get().is_some() {
         return Ok(());
     }
 
     // Slow path: try to initialize
-    let client_result = create_http_client_from_config(config).build();
+    let client_result = create_http_client_from_config(config)
+        .chaos(chaos.clone())
+        .build();
 
     match client_result {
         Ok(client) => {
@@ -121,6 +141,7 @@ pub struct HttpClientBuilder {
     max_retries: u32,
     retry_initial_delay: Duration,
     retry_max_delay: Duration,
+    chaos: chaos::ChaosConfig,
 }
 
 impl Default for HttpClientBuilder {
@@ -137,6 +158,7 @@ impl Default for HttpClientBuilder {
             max_retries: 3,
             retry_initial_delay: Duration::from_millis(100),
             retry_max_delay: Duration::from_secs(10),
+            chaos: chaos::ChaosConfig::default(),
         }
     }
 }
@@ -225,6 +247,15 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Inject upstream failures (see [`chaos::ChaosConfig`])
+    ///
+    /// Injected failures pass through the retry middleware like real ones.
+    #[must_use]
+    pub fn chaos(mut self, chaos: chaos::ChaosConfig) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
     /// Build HTTP client with middleware chain
     ///
     /// This method builds a `reqwest_middleware::ClientWithMiddleware` that includes
@@ -262,10 +293,19 @@ impl HttpClientBuilder {
             .retry_bounds(self.retry_initial_delay, self.retry_max_delay)
             .build_with_max_retries(self.max_retries);
 
-        // Build client with retry middleware
-        Ok(ClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build())
+        // Build client with retry middleware; chaos runs inside it so that
+        // injected failures are retried like real ones
+        let builder = ClientBuilder::new(client)
+            .with(RetryTransientMiddleware::new_with_policy(retry_policy));
+        if self.chaos.enabled {
+            tracing::warn!(
+                "Upstream failure injection is enabled ([chaos]); do not use in production"
+            );
+            return Ok(builder
+                .with(chaos::ChaosMiddleware::new(self.chaos))
+                .build());
+        }
+        Ok(builder.build())
     }
 
     /// Build HTTP client without retry support
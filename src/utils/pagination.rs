@@ -0,0 +1,70 @@
+//! Cursor-based pagination of MCP list results
+//!
+//! A cursor is the key of the last item of the previous page, so pages stay
+//! consistent when items are added elsewhere in the list. Clients treat it
+//! as opaque.
+
+/// One page of a list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// Items of this page
+    pub items: Vec<T>,
+    /// Cursor of the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Take the page of `items` after `cursor`
+///
+/// `items` must be in a stable order and `key` unique per item. Returns
+/// `None` if `cursor` is not the key of an item, e.g. a cursor from another
+/// list or one whose item has since been removed.
+///
+/// A `page_size` of 0 is treated as 1.
+pub fn paginate<T>(
+    items: Vec<T>,
+    key: impl Fn(&T) -> &str,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Option<Page<T>> {
+    let start = match cursor {
+        Some(cursor) => items.iter().position(|item| key(item) == cursor)? + 1,
+        None => 0,
+    };
+    let end = start.saturating_add(page_size.max(1)).min(items.len());
+    let next_cursor = (end < items.len()).then(|| key(&items[end - 1]).to_string());
+    Some(Page {
+        items: items.into_iter().skip(start).take(end - start).collect(),
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        ["a", "b", "c", "d", "e"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn test_paginate_walks_all_pages() {
+        let first = paginate(names(), String::as_str, None, 2).unwrap();
+        assert_eq!(first.items, ["a", "b"]);
+        assert_eq!(first.next_cursor.as_deref(), Some("b"));
+
+        let second = paginate(names(), String::as_str, Some("b"), 2).unwrap();
+        assert_eq!(second.items, ["c", "d"]);
+
+        let last = paginate(names(), String::as_str, Some("d"), 2).unwrap();
+        assert_eq!(last.items, ["e"]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn test_paginate_rejects_unknown_cursor() {
+        assert_eq!(paginate(names(), String::as_str, Some("zz"), 2), None);
+        let everything = paginate(names(), String::as_str, None, 10).unwrap();
+        assert_eq!(everything.items.len(), 5);
+        assert_eq!(everything.next_cursor, None);
+    }
+}
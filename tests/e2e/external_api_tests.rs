@@ -43,7 +43,7 @@ async fn test_doc_service_with_config() {
     let cache = doc_service.cache();
     // Cache should be available
     cache
-        .set("test_key".to_string(), "test_value".to_string(), None)
+        .set("test_key".to_string(), "test_value".into(), None)
         .await
         .expect("Cache set failed");
     let value = cache.get("test_key").await;
@@ -65,7 +65,7 @@ async fn test_doc_service_cache_operations() {
 
     // Set cache
     cache
-        .set("crate:serde".to_string(), "serde docs".to_string(), None)
+        .set("crate:serde".to_string(), "serde docs".into(), None)
         .await
         .expect("Cache set failed");
 
@@ -97,7 +97,7 @@ async fn test_doc_service_cache_ttl() {
     cache
         .set(
             "expiring_key".to_string(),
-            "expiring_value".to_string(),
+            "expiring_value".into(),
             Some(std::time::Duration::from_secs(1)),
         )
         .await
@@ -144,7 +144,7 @@ async fn test_tool_registry_with_doc_service() {
 
     // Verify tools are registered
     let tools = registry.get_tools();
-    assert_eq!(tools.len(), 4, "Should have 4 tools registered");
+    assert_eq!(tools.len(), 29, "Should have 29 tools registered");
 
     let tool_names: std::collections::HashSet<String> =
         tools.iter().map(|t| t.name.clone()).collect();
@@ -199,7 +199,7 @@ async fn test_cache_key_formats() {
     for key in keys {
         let expected_value = format!("value_for_{}", key);
         cache
-            .set(key.to_string(), expected_value.clone(), None)
+            .set(key.to_string(), expected_value.as_str().into(), None)
             .await
             .expect("Cache set failed");
 
@@ -227,7 +227,7 @@ async fn test_cache_clear() {
     // Add multiple cache entries
     for i in 0..10 {
         cache
-            .set(format!("key_{}", i), format!("value_{}", i), None)
+            .set(format!("key_{}", i), format!("value_{}", i).into(), None)
             .await
             .expect("Cache set failed");
     }
@@ -268,7 +268,7 @@ async fn test_concurrent_cache_access() {
             let value = format!("concurrent_value_{}", i);
 
             cache
-                .set(key.clone(), value.clone(), None)
+                .set(key.clone(), value.as_str().into(), None)
                 .await
                 .expect("Set failed");
             let retrieved = cache.get(&key).await;
@@ -359,11 +359,11 @@ async fn test_cache_statistics() {
 
     // Perform some cache operations
     cache
-        .set("stats_key1".to_string(), "value1".to_string(), None)
+        .set("stats_key1".to_string(), "value1".into(), None)
         .await
         .expect("Set failed");
     cache
-        .set("stats_key2".to_string(), "value2".to_string(), None)
+        .set("stats_key2".to_string(), "value2".into(), None)
         .await
         .expect("Set failed");
     cache.get("stats_key1").await;
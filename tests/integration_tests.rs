@@ -17,7 +17,7 @@ async fn test_cache_functionality() {
 
     // Test basic cache operations
     cache
-        .set("test_key".to_string(), "test_value".to_string(), None)
+        .set("test_key".to_string(), "test_value".into(), None)
         .await
         .expect("set should succeed");
     let value = cache.get("test_key").await;
@@ -28,7 +28,7 @@ async fn test_cache_functionality() {
     cache
         .set(
             "expiring_key".to_string(),
-            "expiring_value".to_string(),
+            "expiring_value".into(),
             Some(std::time::Duration::from_secs(1)),
         )
         .await
@@ -52,11 +52,11 @@ async fn test_cache_functionality() {
 
     // Test clear
     cache
-        .set("key1".to_string(), "value1".to_string(), None)
+        .set("key1".to_string(), "value1".into(), None)
         .await
         .expect("set should succeed");
     cache
-        .set("key2".to_string(), "value2".to_string(), None)
+        .set("key2".to_string(), "value2".into(), None)
         .await
         .expect("set should succeed");
     cache.clear().await.expect("clear should succeed");
@@ -112,13 +112,38 @@ async fn test_tool_registry() {
 
     // Verify expected tools are registered
     let tools = registry.get_tools();
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 29);
     let tool_names: std::collections::HashSet<String> =
         tools.iter().map(|t| t.name.clone()).collect();
     assert!(tool_names.contains("lookup_crate"));
     assert!(tool_names.contains("lookup_item"));
     assert!(tool_names.contains("search_crates"));
     assert!(tool_names.contains("health_check"));
+    assert!(tool_names.contains("get_docsrs_build_status"));
+    assert!(tool_names.contains("list_crates_by_category"));
+    assert!(tool_names.contains("get_trending_crates"));
+    assert!(tool_names.contains("resolve_version"));
+    assert!(tool_names.contains("suggest_dependency_line"));
+    assert!(tool_names.contains("set_project_profile"));
+    assert!(tool_names.contains("list_feature_gated_items"));
+    assert!(tool_names.contains("list_deprecated_items"));
+    assert!(tool_names.contains("lookup_macro"));
+    assert!(tool_names.contains("get_toc"));
+    assert!(tool_names.contains("lookup_rustc_error"));
+    assert!(tool_names.contains("lookup_clippy_lint"));
+    assert!(tool_names.contains("find_symbol"));
+    assert!(tool_names.contains("extract_doc_examples"));
+    assert!(tool_names.contains("run_doc_example"));
+    assert!(tool_names.contains("top_queries"));
+    assert!(tool_names.contains("dependency_tree"));
+    assert!(tool_names.contains("crate_build_info"));
+    assert!(tool_names.contains("suggest_alternatives"));
+    assert!(tool_names.contains("set_log_level"));
+    assert!(tool_names.contains("batch_lookup_crates"));
+    assert!(tool_names.contains("analyze_cargo_toml"));
+    assert!(tool_names.contains("analyze_cargo_lock"));
+    assert!(tool_names.contains("doc_coverage"));
+    assert!(tool_names.contains("summarize_crate"));
 }
 
 /// Test server creation
@@ -382,6 +407,7 @@ fn test_oauth_config() {
         token_endpoint: Some("https://github.com/login/oauth/access_token".to_string()),
         scopes: vec!["read:user".to_string()],
         provider: OAuthProvider::GitHub,
+        ..Default::default()
     };
 
     assert!(config.enabled);
@@ -406,6 +432,7 @@ fn test_oauth_config() {
         token_endpoint: Some("https://github.com/login/oauth/access_token".to_string()),
         scopes: vec!["read:user".to_string()],
         provider: OAuthProvider::GitHub,
+        ..Default::default()
     };
 
     let validation_result = invalid_config.validate();
@@ -77,6 +77,7 @@ fn test_oauth_config_validation_missing_client_id() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec!["read".to_string()],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -93,6 +94,7 @@ fn test_oauth_config_validation_missing_client_secret() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec!["read".to_string()],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -109,6 +111,7 @@ fn test_oauth_config_validation_disabled() {
         token_endpoint: None,
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_ok());
@@ -125,6 +128,7 @@ fn test_oauth_config_validate_missing_redirect_uri() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     let result = config.validate();
     assert!(result.is_err());
@@ -142,6 +146,7 @@ fn test_oauth_config_validate_invalid_urls() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     assert!(config
         .validate()
@@ -28,12 +28,22 @@ fn test_cache_config_custom_values() {
     let config = CacheConfig {
         cache_type: "redis".to_string(),
         memory_size: Some(500),
+        memory_max_bytes: None,
         default_ttl: Some(7200),
         redis_url: Some("redis://localhost:6379".to_string()),
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: "myapp".to_string(),
         crate_docs_ttl_secs: Some(1800),
         item_docs_ttl_secs: Some(900),
         search_results_ttl_secs: Some(150),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
     assert_eq!(config.cache_type, "redis");
     assert_eq!(config.memory_size, Some(500));
@@ -45,6 +55,40 @@ fn test_cache_config_custom_values() {
     assert_eq!(config.search_results_ttl_secs, Some(150));
 }
 
+#[test]
+fn test_cache_config_redis_key_prefix() {
+    let mut config = CacheConfig::default();
+    assert_eq!(
+        config.resolved_redis_key_prefix(),
+        crates_docs::cache::DEFAULT_REDIS_KEY_PREFIX
+    );
+
+    config.key_prefix = "myapp".to_string();
+    assert_eq!(config.resolved_redis_key_prefix(), "myapp");
+
+    config.redis_key_prefix = Some("docs-staging".to_string());
+    assert_eq!(config.resolved_redis_key_prefix(), "docs-staging");
+    assert!(config.validate().is_ok());
+
+    config.redis_key_prefix = Some(String::new());
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("cache.redis_key_prefix"));
+}
+
+#[test]
+fn test_cache_config_ttl_jitter_ratio() {
+    let mut config = CacheConfig::default();
+    for ratio in [0.0, 0.25, 1.0] {
+        config.ttl_jitter_ratio = Some(ratio);
+        assert!(config.validate().is_ok());
+    }
+    for ratio in [-0.1, 1.5, f64::NAN] {
+        config.ttl_jitter_ratio = Some(ratio);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("cache.ttl_jitter_ratio"));
+    }
+}
+
 #[test]
 fn test_cache_config_serialization() {
     let config = CacheConfig::default();
@@ -109,6 +153,37 @@ fn test_cache_config_with_missing_optional_fields() {
 // DocCache tests
 // ============================================================================
 
+#[tokio::test]
+async fn test_doc_cache_crate_summaries_batch() {
+    let cache: Arc<dyn crates_docs::cache::Cache> = Arc::new(MemoryCache::new(100));
+    let doc_cache = DocCache::new(cache);
+
+    doc_cache
+        .set_crate_summaries(vec![
+            ("serde".to_string(), Arc::from("serde summary")),
+            ("tokio".to_string(), Arc::from("tokio summary")),
+        ])
+        .await
+        .expect("set_crate_summaries should succeed");
+    let names = [
+        "tokio".to_string(),
+        "anyhow".to_string(),
+        "serde".to_string(),
+    ];
+    let summaries = doc_cache.get_crate_summaries(&names).await;
+    assert_eq!(summaries[0].as_deref(), Some("tokio summary"));
+    assert_eq!(summaries[1], None);
+    assert_eq!(summaries[2].as_deref(), Some("serde summary"));
+    assert_eq!(
+        doc_cache.get_crate_summary("serde").await.as_deref(),
+        Some("serde summary")
+    );
+    assert_eq!(
+        (doc_cache.stats().hits(), doc_cache.stats().misses()),
+        (3, 1)
+    );
+}
+
 #[tokio::test]
 async fn test_doc_cache_crate_docs() {
     let config = CacheConfig::default();
@@ -204,12 +279,22 @@ fn test_create_cache_unsupported_type() {
     let config = CacheConfig {
         cache_type: "unsupported".to_string(),
         memory_size: Some(100),
+        memory_max_bytes: None,
         default_ttl: Some(3600),
         redis_url: None,
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         crate_docs_ttl_secs: Some(3600),
         item_docs_ttl_secs: Some(1800),
         search_results_ttl_secs: Some(300),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
     let result = create_cache(&config);
     assert!(result.is_err());
@@ -225,12 +310,22 @@ fn test_create_cache_redis_sync_error() {
     let config = CacheConfig {
         cache_type: "redis".to_string(),
         memory_size: Some(100),
+        memory_max_bytes: None,
         default_ttl: Some(3600),
         redis_url: Some("redis://invalid:6379".to_string()),
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         crate_docs_ttl_secs: Some(3600),
         item_docs_ttl_secs: Some(1800),
         search_results_ttl_secs: Some(300),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
 
     let result = create_cache(&config);
@@ -247,12 +342,73 @@ fn test_create_cache_redis_sync_error() {
     let config = CacheConfig {
         cache_type: "redis".to_string(),
         memory_size: Some(100),
+        memory_max_bytes: None,
         default_ttl: Some(3600),
         redis_url: Some("redis://invalid:6379".to_string()),
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         crate_docs_ttl_secs: Some(3600),
         item_docs_ttl_secs: Some(1800),
         search_results_ttl_secs: Some(300),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
+    };
+
+    let result = create_cache(&config);
+    assert!(result.is_err());
+
+    if let Err(e) = result {
+        assert!(e.to_string().contains("feature is not enabled"));
+    }
+}
+
+#[cfg(feature = "cache-sqlite")]
+#[tokio::test]
+async fn test_create_cache_sqlite() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = CacheConfig {
+        cache_type: "sqlite".to_string(),
+        sqlite_path: Some(dir.path().join("cache.db").display().to_string()),
+        ..CacheConfig::default()
+    };
+
+    let cache = create_cache(&config).expect("sqlite cache should open");
+    cache
+        .set("key".to_string(), "value".into(), None)
+        .await
+        .unwrap();
+    assert_eq!(cache.get("key").await.as_deref(), Some("value"));
+}
+
+#[cfg(feature = "cache-sqlite")]
+#[test]
+fn test_create_cache_sqlite_requires_path() {
+    let config = CacheConfig {
+        cache_type: "sqlite".to_string(),
+        ..CacheConfig::default()
+    };
+
+    let result = create_cache(&config);
+    assert!(result.is_err());
+
+    if let Err(e) = result {
+        assert!(e.to_string().contains("sqlite_path is required"));
+    }
+}
+
+#[cfg(not(feature = "cache-sqlite"))]
+#[test]
+fn test_create_cache_sqlite_feature_disabled() {
+    let config = CacheConfig {
+        cache_type: "sqlite".to_string(),
+        sqlite_path: Some("cache.db".to_string()),
+        ..CacheConfig::default()
     };
 
     let result = create_cache(&config);
@@ -691,12 +847,22 @@ fn test_doc_cache_ttl_from_cache_config_none_defaults() {
     let config = CacheConfig {
         cache_type: "memory".to_string(),
         memory_size: Some(1000),
+        memory_max_bytes: None,
         redis_url: None,
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         default_ttl: None,
         crate_docs_ttl_secs: None,
         item_docs_ttl_secs: None,
         search_results_ttl_secs: None,
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
     let ttl = DocCacheTtl::from_cache_config(&config);
     assert_eq!(ttl.crate_docs_secs, 3600);
@@ -764,3 +930,189 @@ fn test_item_cache_key_invalid_path_no_version() {
     // Should not have version in the key format
     assert!(!key.contains(":1.0"));
 }
+
+// ============================================================================
+// LeaseCoordinator tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_lease_coordinator_runs_job_once_across_holders() {
+    use crates_docs::cache::lease::LeaseCoordinator;
+    use std::time::Duration;
+
+    let cache: Arc<dyn crates_docs::cache::Cache> = Arc::new(MemoryCache::new(100));
+    let replica_a = LeaseCoordinator::with_holder_id(cache.clone(), "replica-a");
+    let replica_b = LeaseCoordinator::with_holder_id(cache, "replica-b");
+    let ttl = Duration::from_secs(30);
+
+    assert!(replica_a.try_acquire("warmup", ttl).await);
+    let skipped = replica_b
+        .run_exclusive("warmup", ttl, || async { "ran" })
+        .await;
+    assert_eq!(skipped, None);
+
+    replica_a.release("warmup").await;
+    let ran = replica_b
+        .run_exclusive("warmup", ttl, || async { "ran" })
+        .await;
+    assert_eq!(ran, Some("ran"));
+
+    // run_exclusive releases the lease when the job completes
+    assert!(replica_a.try_acquire("warmup", ttl).await);
+}
+
+#[tokio::test]
+async fn test_lease_coordinator_holder_ids_are_unique() {
+    use crates_docs::cache::lease::LeaseCoordinator;
+
+    let cache: Arc<dyn crates_docs::cache::Cache> = Arc::new(MemoryCache::new(100));
+    let a = LeaseCoordinator::new(cache.clone());
+    let b = LeaseCoordinator::new(cache);
+    assert_ne!(a.holder_id(), b.holder_id());
+    assert_eq!(LeaseCoordinator::lease_key("sync"), "lease:sync");
+}
+
+// ============================================================================
+// FetchCoordinator tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_fetch_coordinator_serializes_local_fetches() {
+    use crates_docs::cache::singleflight::FetchCoordinator;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    let fetches = Arc::new(FetchCoordinator::new(Arc::new(MemoryCache::new(100))));
+    let active = Arc::new(AtomicUsize::new(0));
+    let mut tasks = Vec::new();
+    for _ in 0..4 {
+        let fetches = Arc::clone(&fetches);
+        let active = Arc::clone(&active);
+        tasks.push(tokio::spawn(async move {
+            let _permit = fetches.begin("crate:serde").await;
+            assert_eq!(active.fetch_add(1, Ordering::SeqCst), 0);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_coordinator_replicas_wait_for_marker() {
+    use crates_docs::cache::singleflight::FetchCoordinator;
+    use std::time::Duration;
+
+    let cache: Arc<dyn crates_docs::cache::Cache> = Arc::new(MemoryCache::new(100));
+    let timing =
+        |c: FetchCoordinator| c.with_timing(Duration::from_secs(5), Duration::from_millis(5));
+    let replica_a = timing(FetchCoordinator::new(cache.clone()));
+    let replica_b = timing(FetchCoordinator::new(cache.clone()));
+
+    let permit = replica_a.begin("crate:serde").await;
+    assert!(permit.holds_marker());
+    assert!(
+        cache
+            .exists(&FetchCoordinator::marker_key("crate:serde"))
+            .await
+    );
+
+    let waiter = tokio::spawn(async move { replica_b.begin("crate:serde").await.holds_marker() });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(!waiter.is_finished(), "replica B must wait for replica A");
+
+    drop(permit);
+    // Replica B proceeds once the marker is released, and takes it over
+    let took_over = tokio::time::timeout(Duration::from_secs(2), waiter)
+        .await
+        .expect("waiter should finish")
+        .unwrap();
+    assert!(took_over);
+}
+
+#[tokio::test]
+async fn test_fetch_coordinator_stops_waiting_for_stale_marker() {
+    use crates_docs::cache::singleflight::FetchCoordinator;
+    use std::time::Duration;
+
+    let cache: Arc<dyn crates_docs::cache::Cache> = Arc::new(MemoryCache::new(100));
+    cache
+        .try_acquire_lease(
+            &FetchCoordinator::marker_key("crate:serde"),
+            "crashed-replica",
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+    let fetches = FetchCoordinator::new(cache)
+        .with_timing(Duration::from_millis(50), Duration::from_millis(5));
+    let permit = tokio::time::timeout(Duration::from_secs(2), fetches.begin("crate:serde"))
+        .await
+        .expect("waiting is bounded by the marker TTL");
+    assert!(!permit.holds_marker());
+}
+
+#[tokio::test]
+async fn test_in_flight_shares_concurrent_results() {
+    use crates_docs::cache::singleflight::InFlight;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    let in_flight = Arc::new(InFlight::<String>::new());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut tasks = Vec::new();
+    for _ in 0..8 {
+        let in_flight = Arc::clone(&in_flight);
+        let runs = Arc::clone(&runs);
+        tasks.push(tokio::spawn(async move {
+            in_flight
+                .run("https://docs.rs/tokio/", || async {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    "tokio docs".to_string()
+                })
+                .await
+        }));
+    }
+    for task in tasks {
+        assert_eq!(task.await.unwrap(), "tokio docs");
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert!(in_flight.is_empty());
+
+    // A finished run is not reused
+    let value = in_flight
+        .run("https://docs.rs/tokio/", || async {
+            "refetched".to_string()
+        })
+        .await;
+    assert_eq!(value, "refetched");
+}
+
+#[tokio::test]
+async fn test_in_flight_waiter_takes_over_cancelled_run() {
+    use crates_docs::cache::singleflight::InFlight;
+    use std::time::Duration;
+
+    let in_flight = Arc::new(InFlight::<u32>::new());
+    let first = {
+        let in_flight = Arc::clone(&in_flight);
+        tokio::spawn(async move {
+            in_flight
+                .run("key", || async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    1
+                })
+                .await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    first.abort();
+
+    let value = tokio::time::timeout(Duration::from_secs(2), in_flight.run("key", || async { 2 }))
+        .await
+        .expect("the cancelled run must not block later callers");
+    assert_eq!(value, 2);
+}
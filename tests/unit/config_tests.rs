@@ -17,6 +17,52 @@ fn test_config_validation_empty_host() {
     assert!(result.unwrap_err().to_string().contains("host"));
 }
 
+#[test]
+fn test_config_validation_empty_hosts_entry() {
+    let mut config = AppConfig::default();
+    config.server.hosts = vec!["127.0.0.1".to_string(), " ".to_string()];
+    let result = config.validate();
+    assert!(result.unwrap_err().to_string().contains("hosts"));
+}
+
+#[test]
+fn test_config_validation_base_path() {
+    let mut config = AppConfig::default();
+    config.server.base_path = "/mcp/crates-docs".to_string();
+    assert!(config.validate().is_ok());
+
+    config.server.base_path = "/mcp/{name}".to_string();
+    let result = config.validate();
+    assert!(result.unwrap_err().to_string().contains("base_path"));
+}
+
+#[test]
+fn test_server_bind_hosts() {
+    let mut server = ServerConfig::default();
+    assert_eq!(server.bind_hosts(), ["127.0.0.1"]);
+    assert_eq!(server.listen_addresses(), ["127.0.0.1:8080"]);
+
+    server.hosts = vec![
+        "127.0.0.1".to_string(),
+        "[::1]".to_string(),
+        "::1".to_string(),
+    ];
+    assert_eq!(server.bind_hosts(), ["127.0.0.1", "::1"]);
+    assert_eq!(server.listen_addresses(), ["127.0.0.1:8080", "[::1]:8080"]);
+}
+
+#[test]
+fn test_server_set_bind_hosts() {
+    let mut server = ServerConfig::default();
+    server.set_bind_hosts("0.0.0.0");
+    assert_eq!(server.host, "0.0.0.0");
+    assert!(server.hosts.is_empty());
+
+    server.set_bind_hosts("127.0.0.1, 192.168.1.5");
+    assert_eq!(server.host, "127.0.0.1");
+    assert_eq!(server.bind_hosts(), ["127.0.0.1", "192.168.1.5"]);
+}
+
 #[test]
 fn test_config_validation_zero_port() {
     let mut config = AppConfig::default();
@@ -57,6 +103,52 @@ fn test_config_validation_zero_max_connections() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_config_validation_zero_server_timeouts() {
+    let mut config = AppConfig::default();
+    config.server.request_timeout_secs = 0;
+    assert!(config.validate().is_err());
+
+    let mut config = AppConfig::default();
+    config.server.response_timeout_secs = 0;
+    assert!(config.validate().is_err());
+
+    let mut config = AppConfig::default();
+    config.server.task_ttl_secs = 0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_config_validation_admin_clients() {
+    let mut config = AppConfig::default();
+    config.server.admin_clients = vec!["client:ops".to_string(), "user:alice".to_string()];
+    assert!(config.validate().is_ok());
+
+    for entry in ["ops", "client:", "session:s1"] {
+        config.server.admin_clients = vec![entry.to_string()];
+        assert!(config.validate().is_err(), "{entry}");
+    }
+}
+
+#[test]
+fn test_config_validation_zero_page_size() {
+    let mut config = AppConfig::default();
+    config.server.page_size = 0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_config_validation_subscription_poll_interval() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.server.subscription_poll_interval_secs, 900);
+
+    config.server.subscription_poll_interval_secs = 10;
+    assert!(config.validate().is_err());
+
+    config.server.subscription_poll_interval_secs = 60;
+    assert!(config.validate().is_ok());
+}
+
 #[test]
 fn test_config_validation_zero_pool_size() {
     let mut config = AppConfig::default();
@@ -187,6 +279,11 @@ fn test_config_merge() {
             host: None,
             port: Some(9000),
             transport_mode: None,
+            allowed_hosts: None,
+            allowed_origins: None,
+            dns_rebinding_protection: None,
+            base_path: None,
+            trust_forwarded_headers: None,
         },
         logging: Default::default(),
         #[cfg(feature = "api-key")]
@@ -280,6 +377,19 @@ fn test_config_validation_zero_read_timeout() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_config_validation_client_rate_limit_needs_burst() {
+    let mut config = AppConfig::default();
+    config.performance.client_rate_limit_burst = 0;
+    assert!(config.validate().is_ok(), "burst is unused while disabled");
+
+    config.performance.client_rate_limit_per_second = 5;
+    assert!(config.validate().is_err());
+
+    config.performance.client_rate_limit_burst = 10;
+    assert!(config.validate().is_ok());
+}
+
 // ============================================================================
 // Environment variable logging tests
 // ============================================================================
@@ -433,6 +543,7 @@ fn test_config_merge_api_key_env_overrides() {
         auth_api_key: EnvApiKeyConfig {
             enabled: Some(true),
             keys: Some(vec!["env-key".to_string()]),
+            keys_file: Some("/run/secrets/api-keys".to_string()),
             header_name: Some("X-Env-Key".to_string()),
             query_param_name: Some("api_token".to_string()),
             allow_query_param: Some(true),
@@ -443,6 +554,10 @@ fn test_config_merge_api_key_env_overrides() {
     let merged = AppConfig::merge(None, Some(env_config));
     assert!(merged.auth.api_key.enabled);
     assert_eq!(merged.auth.api_key.keys, vec!["env-key"]);
+    assert_eq!(
+        merged.auth.api_key.keys_file.as_deref(),
+        Some("/run/secrets/api-keys")
+    );
     assert_eq!(merged.auth.api_key.header_name, "X-Env-Key");
     assert_eq!(merged.auth.api_key.query_param_name, "api_token");
     assert!(merged.auth.api_key.allow_query_param);
@@ -466,6 +581,7 @@ fn test_config_merge_api_key_partial_override() {
         auth_api_key: EnvApiKeyConfig {
             enabled: Some(false),
             keys: None,
+            keys_file: None,
             header_name: None,
             query_param_name: None,
             allow_query_param: None,
@@ -549,6 +665,20 @@ fn test_config_validation_memory_size_zero_rejected() {
     );
 }
 
+#[test]
+fn test_config_validation_memory_max_bytes_zero_rejected() {
+    let mut config = AppConfig::default();
+    config.cache.cache_type = "memory".to_string();
+    config.cache.memory_max_bytes = Some(0);
+    let result = config.validate();
+    assert!(result.is_err(), "memory_max_bytes=0 must be rejected");
+    let msg = result.unwrap_err().to_string();
+    assert!(
+        msg.contains("memory_max_bytes"),
+        "error should mention memory_max_bytes: {msg}"
+    );
+}
+
 #[test]
 fn test_config_validation_memory_size_none_ok() {
     let mut config = AppConfig::default();
@@ -557,6 +687,67 @@ fn test_config_validation_memory_size_none_ok() {
     assert!(config.validate().is_ok());
 }
 
+#[test]
+fn test_config_validation_redis_l1_ttl_zero_rejected() {
+    let mut config = AppConfig::default();
+    config.cache.cache_type = "redis".to_string();
+    config.cache.redis_l1_ttl_secs = Some(0);
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("redis_l1_ttl_secs"), "{msg}");
+
+    // The memory tier is a memory cache too, so it cannot be empty either
+    config.cache.redis_l1_ttl_secs = Some(30);
+    config.cache.memory_size = Some(0);
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("memory_size"), "{msg}");
+
+    config.cache.memory_size = Some(100);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_config_cache_warmup_targets() {
+    use crates_docs::cache::WarmupTarget;
+
+    let toml_str = r#"
+[cache.warmup]
+crates = ["serde", "tokio@1.40.0"]
+items = ["serde::Serialize", "tokio@1.40.0::sync::Mutex"]
+"#;
+    let config: AppConfig = toml::from_str(toml_str).expect("warmup config should parse");
+    assert!(config.validate().is_ok());
+
+    let target = |crate_name: &str, version: Option<&str>, item_path: Option<&str>| WarmupTarget {
+        crate_name: crate_name.to_string(),
+        version: version.map(str::to_string),
+        item_path: item_path.map(str::to_string),
+    };
+    assert_eq!(
+        config.cache.warmup.targets().unwrap(),
+        vec![
+            target("serde", None, None),
+            target("tokio", Some("1.40.0"), None),
+            target("serde", None, Some("serde::Serialize")),
+            target("tokio", Some("1.40.0"), Some("tokio::sync::Mutex")),
+        ]
+    );
+}
+
+#[test]
+fn test_config_validation_malformed_warmup_entry_rejected() {
+    let mut config = AppConfig::default();
+    assert!(config.cache.warmup.is_empty());
+
+    config.cache.warmup.items = vec!["serde".to_string()];
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("cache.warmup.items"), "{msg}");
+
+    config.cache.warmup.items.clear();
+    config.cache.warmup.crates = vec!["tokio@".to_string()];
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("cache.warmup.crates"), "{msg}");
+}
+
 #[test]
 fn test_config_validation_invalid_cache_type_rejected() {
     let mut config = AppConfig::default();
@@ -589,3 +780,49 @@ fn test_dns_rebinding_protection_toml_roundtrip() {
     let cfg: AppConfig = toml::from_str("[server]\ndns_rebinding_protection = true\n").unwrap();
     assert!(cfg.server.dns_rebinding_protection);
 }
+
+#[test]
+fn test_dns_rebinding_protection_rejects_wildcard_entries() {
+    let mut config = AppConfig::default();
+    // Not enforced, so the wildcard default is accepted
+    assert!(config.validate().is_ok());
+
+    config.server.dns_rebinding_protection = true;
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("allowed_origins"), "{err}");
+
+    config.server.allowed_hosts = vec!["docs.example.com".to_string(), "*.example.com".to_string()];
+    config.server.allowed_origins = vec!["https://docs.example.com".to_string()];
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("allowed_hosts"), "{err}");
+
+    config.server.allowed_hosts = vec!["docs.example.com".to_string()];
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_allowed_hosts_and_origins_from_env() {
+    temp_env::with_vars(
+        [
+            (
+                "CRATES_DOCS_ALLOWED_HOSTS",
+                Some("docs.example.com, docs.example.com:443"),
+            ),
+            (
+                "CRATES_DOCS_ALLOWED_ORIGINS",
+                Some("https://docs.example.com"),
+            ),
+            ("CRATES_DOCS_DNS_REBINDING_PROTECTION", Some("true")),
+        ],
+        || {
+            let config = AppConfig::merge(None, Some(AppConfig::from_env().unwrap()));
+            assert_eq!(
+                config.server.allowed_hosts,
+                ["docs.example.com", "docs.example.com:443"]
+            );
+            assert_eq!(config.server.allowed_origins, ["https://docs.example.com"]);
+            assert!(config.server.dns_rebinding_protection);
+            assert!(config.validate().is_ok());
+        },
+    );
+}
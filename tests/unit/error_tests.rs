@@ -108,3 +108,61 @@ fn test_result_type() {
     let result = returns_result();
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_not_found_error_display_and_data() {
+    let err = Error::not_found(
+        "crate",
+        "serd",
+        vec!["serde".to_string(), "serde_json".to_string()],
+    );
+    assert_eq!(
+        err.to_string(),
+        "crate 'serd' not found. Did you mean: serde, serde_json?"
+    );
+
+    let data = err.data().expect("not-found errors carry data");
+    assert_eq!(data["kind"], "not_found");
+    assert_eq!(data["resource"], "crate");
+    assert_eq!(data["name"], "serd");
+    assert_eq!(data["suggestions"][1], "serde_json");
+
+    let plain = Error::not_found("crate", "nope", Vec::new());
+    assert_eq!(plain.to_string(), "crate 'nope' not found");
+    assert!(Error::Other("x".to_string()).data().is_none());
+}
+
+#[test]
+fn test_rate_limited_error_rounds_retry_after_up() {
+    let err = Error::rate_limited("client:ci", std::time::Duration::from_millis(1200));
+    assert_eq!(
+        err.to_string(),
+        "Rate limit exceeded for client 'client:ci'; retry after 2s"
+    );
+
+    let data = err.data().expect("rate-limit errors carry data");
+    assert_eq!(data["kind"], "rate_limited");
+    assert_eq!(data["retry_after_secs"], 2);
+}
+
+#[test]
+fn test_timeout_error_display_and_data() {
+    let err = Error::timeout("Tool 'lookup_crate'", std::time::Duration::from_secs(60));
+    assert_eq!(err.to_string(), "Tool 'lookup_crate' timed out after 60s");
+
+    let data = err.data().expect("timeout errors carry data");
+    assert_eq!(data["kind"], "timeout");
+    assert_eq!(data["operation"], "Tool 'lookup_crate'");
+    assert_eq!(data["timeout_secs"], 60);
+}
+
+#[test]
+fn test_tool_error_data_downcasts_crate_errors() {
+    use rust_mcp_sdk::schema::CallToolError;
+
+    let err: CallToolError = Error::not_found("item", "Foo", vec!["Bar".to_string()]).into();
+    let data = crates_docs::error::tool_error_data(&err).expect("downcast succeeds");
+    assert_eq!(data["suggestions"][0], "Bar");
+
+    assert!(crates_docs::error::tool_error_data(&CallToolError::from_message("boom")).is_none());
+}
@@ -110,6 +110,47 @@ async fn test_internal_check_non_verbose() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_internal_check_reports_cache_integrity() {
+    use crates_docs::cache::memory::MemoryCache;
+    use std::sync::Arc;
+
+    let tool = HealthCheckToolImpl::new().with_cache(Arc::new(MemoryCache::new(10)));
+    let (report, healthy) = tool.run_check_report("internal", true).await;
+
+    assert!(healthy, "volatile cache should be healthy: {report}");
+    assert!(
+        report.contains("\"cache\""),
+        "missing cache check: {report}"
+    );
+    assert!(
+        report.contains("volatile store"),
+        "missing integrity result: {report}"
+    );
+}
+
+#[tokio::test]
+async fn test_internal_check_reports_cache_stats() {
+    use crates_docs::cache::{memory::MemoryCache, stats::StatsCache, Cache};
+    use std::sync::Arc;
+
+    let cache = Arc::new(StatsCache::new(Arc::new(MemoryCache::new(10))));
+    cache
+        .set("key".to_string(), "value".into(), None)
+        .await
+        .unwrap();
+    let _ = cache.get("key").await;
+    let _ = cache.get("missing").await;
+
+    let tool = HealthCheckToolImpl::new().with_cache(cache);
+    let (report, _) = tool.run_check_report("internal", true).await;
+
+    assert!(
+        report.contains("1 hits, 1 misses (50.0% hit rate)"),
+        "missing cache statistics: {report}"
+    );
+}
+
 /// An unrecognized `check_type` must fail fast with an invalid-arguments error
 /// (consistent with the other tools) instead of returning a misleading
 /// "degraded" report containing a synthetic "unknown_check".
@@ -61,10 +61,12 @@ fn test_server_config_reexport() {
 }
 
 // ============================================================================
-// init_logging_with_config tests
+// init_logging_with_config tests (deprecated wrapper of
+// logging::init_reloadable_logging)
 // ============================================================================
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_console_only() {
     let config = crates_docs::config::LoggingConfig {
         level: "info".to_string(),
@@ -79,6 +81,7 @@ fn test_init_logging_with_console_only() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_debug_level() {
     let config = crates_docs::config::LoggingConfig {
         level: "debug".to_string(),
@@ -92,6 +95,7 @@ fn test_init_logging_with_debug_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_trace_level() {
     let config = crates_docs::config::LoggingConfig {
         level: "trace".to_string(),
@@ -105,6 +109,7 @@ fn test_init_logging_with_trace_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_warn_level() {
     let config = crates_docs::config::LoggingConfig {
         level: "warn".to_string(),
@@ -118,6 +123,7 @@ fn test_init_logging_with_warn_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_error_level() {
     let config = crates_docs::config::LoggingConfig {
         level: "error".to_string(),
@@ -131,6 +137,7 @@ fn test_init_logging_with_error_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_invalid_level() {
     // Invalid level should default to info
     let config = crates_docs::config::LoggingConfig {
@@ -145,6 +152,7 @@ fn test_init_logging_with_invalid_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_no_console_no_file() {
     // Neither console nor file logging enabled
     let config = crates_docs::config::LoggingConfig {
@@ -159,6 +167,7 @@ fn test_init_logging_no_console_no_file() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_file_only() {
     // File logging only - use temp directory
     let temp_dir = tempfile::tempdir().unwrap();
@@ -180,6 +189,7 @@ fn test_init_logging_with_file_only() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_with_console_and_file() {
     // Enable both console and file logging
     let temp_dir = tempfile::tempdir().unwrap();
@@ -201,6 +211,7 @@ fn test_init_logging_with_console_and_file() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_init_logging_file_only_no_path() {
     // File logging only but no path - use default path
     let config = crates_docs::config::LoggingConfig {
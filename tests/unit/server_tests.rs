@@ -13,6 +13,7 @@ fn test_tool_execution_result_into_call_tool_result_ok() {
 
     let ok_result = CallToolResult::text_content(vec![]);
     let ter = ToolExecutionResult {
+        request_id: "req-1".to_string(),
         tool_name: "test_tool".to_string(),
         duration: std::time::Duration::from_millis(100),
         success: true,
@@ -30,6 +31,7 @@ fn test_tool_execution_result_into_call_tool_result_err() {
 
     let err_result = CallToolError::from_message("test error message");
     let ter = ToolExecutionResult {
+        request_id: "req-1".to_string(),
         tool_name: "test_tool".to_string(),
         duration: std::time::Duration::from_millis(100),
         success: false,
@@ -47,6 +49,7 @@ fn test_tool_execution_result_into_result_from_server_ok() {
 
     let ok_result = CallToolResult::text_content(vec![]);
     let ter = ToolExecutionResult {
+        request_id: "req-1".to_string(),
         tool_name: "test_tool".to_string(),
         duration: std::time::Duration::from_millis(100),
         success: true,
@@ -63,6 +66,7 @@ fn test_tool_execution_result_into_result_from_server_err() {
 
     let err_result = CallToolError::from_message("test error message");
     let ter = ToolExecutionResult {
+        request_id: "req-1".to_string(),
         tool_name: "test_tool".to_string(),
         duration: std::time::Duration::from_millis(100),
         success: false,
@@ -121,7 +125,7 @@ fn test_server_new_async_and_accessors() {
     let cache = server.cache();
     rt.block_on(async {
         cache
-            .set("server-cache-key".to_string(), "value".to_string(), None)
+            .set("server-cache-key".to_string(), "value".into(), None)
             .await
             .expect("cache set should succeed");
         let cached_value = cache.get("server-cache-key").await;
@@ -130,6 +134,41 @@ fn test_server_new_async_and_accessors() {
     });
 }
 
+#[tokio::test]
+async fn test_server_cache_stats_count_lookups() {
+    let server = CratesDocsServer::new(AppConfig::default()).unwrap();
+    let cache = server.cache();
+    cache
+        .set("stats-key".to_string(), "value".into(), None)
+        .await
+        .unwrap();
+    let _ = cache.get("stats-key").await;
+    let _ = cache.get("missing-key").await;
+
+    let stats = server.cache_stats().await;
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert!(stats.entries.is_some());
+    assert!(stats.evictions.is_some());
+}
+
+#[tokio::test]
+async fn test_server_warm_cache() {
+    let server = CratesDocsServer::new(AppConfig::default()).unwrap();
+    assert_eq!(server.warm_cache().await.unwrap(), None);
+
+    // Rejected by lookup_crate's name validation, before any request is made
+    let mut config = AppConfig::default();
+    config.cache.warmup.crates = vec!["not/a/crate".to_string()];
+    let server = CratesDocsServer::new(config).unwrap();
+    let report = server
+        .warm_cache()
+        .await
+        .unwrap()
+        .expect("warm-up should run");
+    assert_eq!((report.warmed, report.failed), (0, 1));
+    assert_eq!(report.to_string(), "0 warmed, 1 failed");
+}
+
 #[test]
 fn test_server_info_content() {
     let server = CratesDocsServer::new(AppConfig::default()).unwrap();
@@ -143,6 +182,8 @@ fn test_server_info_content() {
     assert!(info.server_info.description.is_some());
     assert_eq!(info.server_info.icons.len(), 2);
     assert!(info.capabilities.tools.is_some());
+    assert!(info.capabilities.logging.is_some());
+    assert!(info.capabilities.completions.is_some());
     assert!(info
         .instructions
         .unwrap()
@@ -169,6 +210,10 @@ fn test_transport_mode_from_str() {
     let mode = crates_docs::server::transport::TransportMode::from_str("hybrid").unwrap();
     assert_eq!(mode, crates_docs::server::transport::TransportMode::Hybrid);
 
+    let mode = crates_docs::server::transport::TransportMode::from_str("all").unwrap();
+    assert_eq!(mode, crates_docs::server::transport::TransportMode::All);
+    assert!(mode.to_hyper_config().unwrap().sse_support());
+
     let result = crates_docs::server::transport::TransportMode::from_str("invalid");
     assert!(result.is_err());
 }
@@ -181,4 +226,5 @@ fn test_transport_mode_display() {
     assert_eq!(format!("{}", TransportMode::Http), "http");
     assert_eq!(format!("{}", TransportMode::Sse), "sse");
     assert_eq!(format!("{}", TransportMode::Hybrid), "hybrid");
+    assert_eq!(format!("{}", TransportMode::All), "all");
 }
@@ -32,7 +32,7 @@ fn test_tool_registry_has_tool() {
 fn test_tool_registry_len() {
     let service = Arc::new(DocService::default());
     let registry = create_default_registry(&service);
-    assert_eq!(registry.len(), 4);
+    assert_eq!(registry.len(), 29);
 
     // Empty registry
     let empty_registry = ToolRegistry::new();
@@ -71,6 +71,13 @@ fn test_lookup_crate_tool_params() {
         crate_name: "serde".to_string(),
         version: Some("1.0.0".to_string()),
         format: Some("markdown".to_string()),
+        filters: None,
+        section: None,
+        max_tokens: None,
+        max_length: None,
+        cursor: None,
+        sections: None,
+        no_cache: None,
     };
 
     assert_eq!(params.crate_name, "serde");
@@ -87,6 +94,13 @@ fn test_lookup_item_tool_params() {
         item_path: "serde::Serialize".to_string(),
         version: None,
         format: Some("text".to_string()),
+        filters: None,
+        section: None,
+        max_tokens: None,
+        max_length: None,
+        cursor: None,
+        sections: None,
+        no_cache: None,
     };
 
     assert_eq!(params.crate_name, "serde");
@@ -104,6 +118,7 @@ fn test_search_crates_tool_params() {
         limit: Some(20),
         sort: Some("downloads".to_string()),
         format: Some("json".to_string()),
+        no_cache: None,
     };
 
     assert_eq!(params.query, "web framework");
@@ -140,11 +155,36 @@ fn test_tool_registry_default_and_unknown_tool() {
     let service = Arc::new(DocService::default());
     let registry = create_default_registry(&service);
     let tools = registry.get_tools();
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 29);
     assert!(tools.iter().any(|t| t.name == "lookup_crate"));
     assert!(tools.iter().any(|t| t.name == "lookup_item"));
     assert!(tools.iter().any(|t| t.name == "search_crates"));
     assert!(tools.iter().any(|t| t.name == "health_check"));
+    assert!(tools.iter().any(|t| t.name == "get_docsrs_build_status"));
+    assert!(tools.iter().any(|t| t.name == "list_crates_by_category"));
+    assert!(tools.iter().any(|t| t.name == "get_trending_crates"));
+    assert!(tools.iter().any(|t| t.name == "resolve_version"));
+    assert!(tools.iter().any(|t| t.name == "suggest_dependency_line"));
+    assert!(tools.iter().any(|t| t.name == "set_project_profile"));
+    assert!(tools.iter().any(|t| t.name == "list_feature_gated_items"));
+    assert!(tools.iter().any(|t| t.name == "list_deprecated_items"));
+    assert!(tools.iter().any(|t| t.name == "lookup_macro"));
+    assert!(tools.iter().any(|t| t.name == "get_toc"));
+    assert!(tools.iter().any(|t| t.name == "lookup_rustc_error"));
+    assert!(tools.iter().any(|t| t.name == "lookup_clippy_lint"));
+    assert!(tools.iter().any(|t| t.name == "find_symbol"));
+    assert!(tools.iter().any(|t| t.name == "extract_doc_examples"));
+    assert!(tools.iter().any(|t| t.name == "run_doc_example"));
+    assert!(tools.iter().any(|t| t.name == "top_queries"));
+    assert!(tools.iter().any(|t| t.name == "dependency_tree"));
+    assert!(tools.iter().any(|t| t.name == "crate_build_info"));
+    assert!(tools.iter().any(|t| t.name == "suggest_alternatives"));
+    assert!(tools.iter().any(|t| t.name == "set_log_level"));
+    assert!(tools.iter().any(|t| t.name == "batch_lookup_crates"));
+    assert!(tools.iter().any(|t| t.name == "analyze_cargo_toml"));
+    assert!(tools.iter().any(|t| t.name == "analyze_cargo_lock"));
+    assert!(tools.iter().any(|t| t.name == "doc_coverage"));
+    assert!(tools.iter().any(|t| t.name == "summarize_crate"));
 
     let rt = tokio::runtime::Runtime::new().unwrap();
     let err = rt
@@ -171,6 +211,144 @@ fn test_health_check_tool_invalid_arguments() {
     assert!(err.to_string().contains("health_check"));
 }
 
+#[tokio::test]
+async fn test_set_log_level_tool_changes_level() {
+    use crates_docs::logging::LogLevelHandle;
+    use crates_docs::tools::log_level::SetLogLevelToolImpl;
+
+    let (_layer, handle) = LogLevelHandle::layer("info").unwrap();
+    let tool = SetLogLevelToolImpl::new().with_handle(handle.clone());
+    assert!(tool.requires_admin());
+    let registry = create_default_registry(&Arc::new(DocService::default()));
+    assert!(registry.requires_admin("set_log_level"));
+    assert!(!registry.requires_admin("lookup_crate"));
+
+    let result = tool.execute(serde_json::json!({})).await.unwrap();
+    assert!(format!("{:?}", result.content).contains("Current log level: info"));
+
+    let result = tool
+        .execute(serde_json::json!({ "level": "debug,hyper=warn" }))
+        .await
+        .unwrap();
+    assert!(format!("{:?}", result.content).contains("from info to debug,hyper=warn"));
+    assert_eq!(handle.level(), "debug,hyper=warn");
+
+    let err = tool
+        .execute(serde_json::json!({ "level": "crates_docs=loud" }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("set_log_level"), "got: {err}");
+    assert_eq!(handle.level(), "debug,hyper=warn");
+}
+
+#[test]
+fn test_long_running_tools_support_tasks() {
+    use rust_mcp_sdk::schema::ToolExecutionTaskSupport;
+
+    let service = Arc::new(DocService::default());
+    let registry = create_default_registry(&service);
+    let task_tools: Vec<String> = registry
+        .get_tools()
+        .into_iter()
+        .filter(|tool| {
+            tool.execution
+                .as_ref()
+                .and_then(|execution| execution.task_support)
+                == Some(ToolExecutionTaskSupport::Optional)
+        })
+        .map(|tool| tool.name)
+        .collect();
+    assert_eq!(
+        task_tools,
+        [
+            "analyze_cargo_lock",
+            "analyze_cargo_toml",
+            "batch_lookup_crates",
+            "dependency_tree"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_tools_are_hidden_and_refused() {
+    let service = Arc::new(DocService::default());
+    let registry = create_default_registry(&service);
+    let mut changes = registry.watch_changes();
+
+    assert_eq!(registry.set_enabled("health_check", false), Some(true));
+    assert_eq!(registry.set_enabled("health_check", false), Some(false));
+    assert_eq!(registry.set_enabled("nonexistent_tool", false), None);
+    assert!(changes.has_changed().unwrap());
+    changes.mark_unchanged();
+
+    assert!(registry.has_tool("health_check"));
+    assert!(!registry.is_enabled("health_check"));
+    assert_eq!(registry.get_tools().len(), 28);
+    let err = registry
+        .execute_tool("health_check", serde_json::json!({}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("health_check"), "got: {err}");
+
+    let unknown = registry.set_disabled(&["lookup_item", "nonexistent_tool"]);
+    assert_eq!(unknown, ["nonexistent_tool"]);
+    assert_eq!(registry.disabled_tools(), ["lookup_item"]);
+    assert!(registry.is_enabled("health_check"));
+    assert!(changes.has_changed().unwrap());
+    changes.mark_unchanged();
+
+    // Applying the same configuration again is not a change
+    registry.set_disabled(&["lookup_item"]);
+    assert!(!changes.has_changed().unwrap());
+}
+
+#[tokio::test]
+async fn test_set_tool_enabled_tool() {
+    use crates_docs::tools::tool_admin::SetToolEnabledToolImpl;
+
+    let registry = Arc::new_cyclic(|registry| {
+        ToolRegistry::new()
+            .register(HealthCheckToolImpl::new())
+            .register(SetToolEnabledToolImpl::new(registry.clone()))
+    });
+    assert!(registry.requires_admin("set_tool_enabled"));
+    assert!(!registry.requires_admin("health_check"));
+    let call = |arguments: serde_json::Value| {
+        let registry = registry.clone();
+        async move { registry.execute_tool("set_tool_enabled", arguments).await }
+    };
+
+    let result = call(serde_json::json!({ "name": "health_check", "enabled": false }))
+        .await
+        .unwrap();
+    assert!(format!("{:?}", result.content).contains("Tool health_check disabled"));
+    assert!(!registry.is_enabled("health_check"));
+
+    let result = call(serde_json::json!({})).await.unwrap();
+    assert!(format!("{:?}", result.content).contains("- health_check"));
+
+    let result = call(serde_json::json!({ "name": "health_check" }))
+        .await
+        .unwrap();
+    assert!(format!("{:?}", result.content).contains("is disabled"));
+
+    call(serde_json::json!({ "name": "health_check", "enabled": true }))
+        .await
+        .unwrap();
+    assert!(registry.is_enabled("health_check"));
+
+    assert!(
+        call(serde_json::json!({ "name": "nonexistent_tool", "enabled": true }))
+            .await
+            .is_err()
+    );
+    assert!(
+        call(serde_json::json!({ "name": "set_tool_enabled", "enabled": false }))
+            .await
+            .is_err()
+    );
+}
+
 #[test]
 fn test_lookup_and_search_tools_invalid_arguments() {
     let service = Arc::new(DocService::default());
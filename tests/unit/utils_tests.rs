@@ -126,6 +126,8 @@ fn test_create_http_client_from_config() {
         cache_default_ttl_secs: 3600,
         rate_limit_per_second: 10,
         concurrent_request_limit: 100,
+        client_rate_limit_per_second: 0,
+        client_rate_limit_burst: 20,
         enable_response_compression: true,
         enable_metrics: false,
         metrics_port: 0,
@@ -135,6 +137,61 @@ fn test_create_http_client_from_config() {
     assert!(client.is_ok());
 }
 
+#[tokio::test]
+async fn test_http_client_builder_chaos_injects_failures() {
+    use crates_docs::utils::chaos::ChaosConfig;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(matchers::method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Injected errors are retried like real ones and never reach the upstream
+    let client = HttpClientBuilder::new()
+        .max_retries(1)
+        .retry_initial_delay(Duration::from_millis(1))
+        .retry_max_delay(Duration::from_millis(1))
+        .chaos(ChaosConfig {
+            enabled: true,
+            error_probability: 1.0,
+            ..ChaosConfig::default()
+        })
+        .build()
+        .unwrap();
+    let response = client.get(mock_server.uri()).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 503);
+
+    let client = HttpClientBuilder::new()
+        .max_retries(0)
+        .chaos(ChaosConfig {
+            enabled: true,
+            rate_limit_probability: 1.0,
+            retry_after_secs: 5,
+            ..ChaosConfig::default()
+        })
+        .build()
+        .unwrap();
+    let response = client.get(mock_server.uri()).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 429);
+    assert_eq!(response.headers()["retry-after"], "5");
+
+    // Hosts outside the filter reach the upstream untouched
+    let client = HttpClientBuilder::new()
+        .chaos(ChaosConfig {
+            enabled: true,
+            hosts: vec!["docs.rs".to_string()],
+            error_probability: 1.0,
+            ..ChaosConfig::default()
+        })
+        .build()
+        .unwrap();
+    let response = client.get(mock_server.uri()).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
 // ============================================================================
 // RateLimiter tests
 // ============================================================================
@@ -256,6 +256,7 @@ fn test_oauth_config_validation_missing_client_id() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec!["read".to_string()],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -276,6 +277,7 @@ fn test_oauth_config_validation_missing_client_secret() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec!["read".to_string()],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -296,6 +298,7 @@ fn test_oauth_config_validation_disabled() {
         token_endpoint: None,
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -351,6 +354,13 @@ fn test_lookup_crate_tool_params() {
         crate_name: "serde".to_string(),
         version: Some("1.0.0".to_string()),
         format: Some("markdown".to_string()),
+        filters: None,
+        section: None,
+        max_tokens: None,
+        max_length: None,
+        cursor: None,
+        sections: None,
+        no_cache: None,
     };
 
     assert_eq!(params.crate_name, "serde");
@@ -368,6 +378,13 @@ fn test_lookup_item_tool_params() {
         item_path: "serde::Serialize".to_string(),
         version: None,
         format: Some("text".to_string()),
+        filters: None,
+        section: None,
+        max_tokens: None,
+        max_length: None,
+        cursor: None,
+        sections: None,
+        no_cache: None,
     };
 
     assert_eq!(params.crate_name, "serde");
@@ -386,6 +403,7 @@ fn test_search_crates_tool_params() {
         limit: Some(20),
         sort: Some("downloads".to_string()),
         format: Some("json".to_string()),
+        no_cache: None,
     };
 
     assert_eq!(params.query, "web framework");
@@ -833,12 +851,22 @@ fn test_create_cache_unsupported_type() {
     let config = CacheConfig {
         cache_type: "unsupported".to_string(),
         memory_size: Some(100),
+        memory_max_bytes: None,
         default_ttl: Some(3600),
         redis_url: None,
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         crate_docs_ttl_secs: Some(3600),
         item_docs_ttl_secs: Some(1800),
         search_results_ttl_secs: Some(300),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
 
     let result = create_cache(&config);
@@ -857,12 +885,22 @@ fn test_create_cache_redis_sync_error() {
     let config = CacheConfig {
         cache_type: "redis".to_string(),
         memory_size: Some(100),
+        memory_max_bytes: None,
         default_ttl: Some(3600),
         redis_url: Some("redis://localhost:6379".to_string()),
+        redis_l1_ttl_secs: None,
+        redis_key_prefix: None,
+        sqlite_path: None,
+        sqlite_max_size_mb: None,
         key_prefix: String::new(),
         crate_docs_ttl_secs: Some(3600),
         item_docs_ttl_secs: Some(1800),
         search_results_ttl_secs: Some(300),
+        versioned_docs_ttl_secs: None,
+        missing_crate_ttl_secs: Some(60),
+        stale_while_revalidate_secs: None,
+        ttl_jitter_ratio: None,
+        warmup: crates_docs::cache::CacheWarmupConfig::default(),
     };
 
     // Synchronous Redis cache creation should return error (requires async initialization)
@@ -1449,6 +1487,11 @@ fn test_config_merge_env_overrides_file() {
             host: Some("0.0.0.0".to_string()),
             port: Some(9000),
             transport_mode: Some("http".to_string()),
+            allowed_hosts: None,
+            allowed_origins: None,
+            dns_rebinding_protection: None,
+            base_path: None,
+            trust_forwarded_headers: None,
         },
         logging: EnvLoggingConfig {
             level: Some("debug".to_string()),
@@ -1480,6 +1523,7 @@ fn test_oauth_config_validate_missing_redirect_uri() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -1500,6 +1544,7 @@ fn test_oauth_config_validate_invalid_urls() {
         token_endpoint: Some("https://example.com/token".to_string()),
         scopes: vec![],
         provider: OAuthProvider::Custom,
+        ..Default::default()
     };
     assert!(config
         .validate()
@@ -1586,11 +1631,36 @@ fn test_tool_registry_default_and_unknown_tool() {
     let service = Arc::new(DocService::default());
     let registry = create_default_registry(&service);
     let tools = registry.get_tools();
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 29);
     assert!(tools.iter().any(|t| t.name == "lookup_crate"));
     assert!(tools.iter().any(|t| t.name == "lookup_item"));
     assert!(tools.iter().any(|t| t.name == "search_crates"));
     assert!(tools.iter().any(|t| t.name == "health_check"));
+    assert!(tools.iter().any(|t| t.name == "get_docsrs_build_status"));
+    assert!(tools.iter().any(|t| t.name == "list_crates_by_category"));
+    assert!(tools.iter().any(|t| t.name == "get_trending_crates"));
+    assert!(tools.iter().any(|t| t.name == "resolve_version"));
+    assert!(tools.iter().any(|t| t.name == "suggest_dependency_line"));
+    assert!(tools.iter().any(|t| t.name == "set_project_profile"));
+    assert!(tools.iter().any(|t| t.name == "list_feature_gated_items"));
+    assert!(tools.iter().any(|t| t.name == "list_deprecated_items"));
+    assert!(tools.iter().any(|t| t.name == "lookup_macro"));
+    assert!(tools.iter().any(|t| t.name == "get_toc"));
+    assert!(tools.iter().any(|t| t.name == "lookup_rustc_error"));
+    assert!(tools.iter().any(|t| t.name == "lookup_clippy_lint"));
+    assert!(tools.iter().any(|t| t.name == "find_symbol"));
+    assert!(tools.iter().any(|t| t.name == "extract_doc_examples"));
+    assert!(tools.iter().any(|t| t.name == "run_doc_example"));
+    assert!(tools.iter().any(|t| t.name == "top_queries"));
+    assert!(tools.iter().any(|t| t.name == "dependency_tree"));
+    assert!(tools.iter().any(|t| t.name == "crate_build_info"));
+    assert!(tools.iter().any(|t| t.name == "suggest_alternatives"));
+    assert!(tools.iter().any(|t| t.name == "set_log_level"));
+    assert!(tools.iter().any(|t| t.name == "batch_lookup_crates"));
+    assert!(tools.iter().any(|t| t.name == "analyze_cargo_toml"));
+    assert!(tools.iter().any(|t| t.name == "analyze_cargo_lock"));
+    assert!(tools.iter().any(|t| t.name == "doc_coverage"));
+    assert!(tools.iter().any(|t| t.name == "summarize_crate"));
 
     let rt = tokio::runtime::Runtime::new().unwrap();
     let err = rt
@@ -1677,7 +1747,7 @@ fn test_server_new_async_and_accessors() {
     let cache = server.cache();
     rt.block_on(async {
         cache
-            .set("server-cache-key".to_string(), "value".to_string(), None)
+            .set("server-cache-key".to_string(), "value".into(), None)
             .await
             .expect("cache set should succeed");
         let cached_value = cache.get("server-cache-key").await;